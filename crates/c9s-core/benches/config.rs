@@ -0,0 +1,21 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use c9s_core::fixtures;
+use c9s_core::session::config::{build_config_items, scan_session_config};
+
+fn build_items(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let project = dir.path().join("project");
+    fixtures::config_tree(&project, 20, 25).unwrap();
+
+    c.bench_function("config/scan_session_config", |b| {
+        b.iter(|| scan_session_config(black_box(&project)))
+    });
+    let cfg = scan_session_config(&project);
+    c.bench_function("config/build_config_items", |b| {
+        b.iter(|| build_config_items(black_box(&cfg), &project))
+    });
+}
+
+criterion_group!(benches, build_items);
+criterion_main!(benches);
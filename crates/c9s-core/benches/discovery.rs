@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use c9s_core::fixtures;
+use c9s_core::session::SessionDiscovery;
+
+const SMALL_RECORDS: usize = 3_000;
+/// Big enough to take the head-and-tail path in `parse_jsonl`.
+const LARGE_RECORDS: usize = 60_000;
+
+fn parse_jsonl(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let small = dir.path().join("small.jsonl");
+    let large = dir.path().join("large.jsonl");
+    fixtures::write_transcript(&small, "bench-small", "/tmp/bench", SMALL_RECORDS).unwrap();
+    fixtures::write_transcript(&large, "bench-large", "/tmp/bench", LARGE_RECORDS).unwrap();
+
+    c.bench_function("discovery/parse_jsonl_small", |b| {
+        b.iter(|| SessionDiscovery::parse_jsonl(black_box(&small)))
+    });
+    c.bench_function("discovery/parse_jsonl_large", |b| {
+        b.iter(|| SessionDiscovery::parse_jsonl(black_box(&large)))
+    });
+}
+
+criterion_group!(benches, parse_jsonl);
+criterion_main!(benches);
@@ -0,0 +1,17 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use c9s_core::fixtures;
+use c9s_core::session::timeline::load_timeline;
+
+fn build_timeline(c: &mut Criterion) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("session.jsonl");
+    fixtures::write_transcript(&path, "bench-timeline", "/tmp/bench", 3_000).unwrap();
+
+    c.bench_function("timeline/load", |b| {
+        b.iter(|| load_timeline(black_box(&path)))
+    });
+}
+
+criterion_group!(benches, build_timeline);
+criterion_main!(benches);
@@ -0,0 +1,77 @@
+//! Wall-clock access behind a trait, so timers and "n minutes ago" labels
+//! can be tested without waiting on real time.
+
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Blocks for `d`. Callers waiting for a deadline call this in a loop
+    /// and re-check `now`, which lets the mock release them early.
+    fn sleep(&self, d: Duration);
+
+    fn now_millis(&self) -> u64 {
+        self.now().timestamp_millis().max(0) as u64
+    }
+}
+
+pub type SharedClock = Arc<dyn Clock>;
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn sleep(&self, d: Duration) {
+        std::thread::sleep(d);
+    }
+}
+
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// A clock that only moves when told to. `sleep` returns as soon as the
+/// time is advanced (or after a few real milliseconds), so loops waiting
+/// on a deadline see each step.
+#[cfg(any(test, feature = "test-util"))]
+pub struct MockClock {
+    now: std::sync::Mutex<DateTime<Utc>>,
+    ticked: std::sync::Condvar,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl MockClock {
+    pub fn new() -> Arc<Self> {
+        use chrono::TimeZone;
+        Arc::new(Self {
+            now: std::sync::Mutex::new(Utc.with_ymd_and_hms(2026, 1, 1, 12, 0, 0).unwrap()),
+            ticked: std::sync::Condvar::new(),
+        })
+    }
+
+    pub fn advance(&self, d: Duration) {
+        let mut now = self.now.lock().unwrap();
+        *now += chrono::Duration::from_std(d).unwrap();
+        self.ticked.notify_all();
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.lock().unwrap()
+    }
+
+    fn sleep(&self, _d: Duration) {
+        let now = self.now.lock().unwrap();
+        let _ = self
+            .ticked
+            .wait_timeout(now, Duration::from_millis(5))
+            .unwrap();
+    }
+}
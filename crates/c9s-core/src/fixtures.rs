@@ -0,0 +1,102 @@
+//! Synthetic transcripts and project trees for the benches and for tests
+//! that need realistic volumes of data.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, TimeZone, Utc};
+use std::path::Path;
+
+/// A transcript of `records` JSONL lines cycling through a user prompt, an
+/// assistant reply with a tool call, and the tool's result.
+pub fn transcript(session_id: &str, cwd: &str, records: usize) -> String {
+    let start = Utc.with_ymd_and_hms(2026, 1, 1, 9, 0, 0).unwrap();
+    let mut out = String::with_capacity(records * 400);
+    for i in 0..records {
+        let ts = (start + Duration::seconds(i as i64 * 7)).to_rfc3339();
+        let line = match i % 3 {
+            0 => serde_json::json!({
+                "sessionId": session_id,
+                "cwd": cwd,
+                "type": "user",
+                "timestamp": ts,
+                "message": {"role": "user", "content": format!("Step {}: refactor the parser and rerun the tests", i)},
+            }),
+            1 => serde_json::json!({
+                "sessionId": session_id,
+                "cwd": cwd,
+                "type": "assistant",
+                "timestamp": ts,
+                "message": {
+                    "model": "claude-sonnet-4-20250514",
+                    "stop_reason": "tool_use",
+                    "usage": {"input_tokens": 1200, "output_tokens": 340, "cache_read_input_tokens": 9000},
+                    "content": [
+                        {"type": "text", "text": "Looking at the parser module to see where tokens are split."},
+                        {"type": "tool_use", "id": format!("toolu_{}", i), "name": "Bash", "input": {"command": "cargo test --workspace"}},
+                    ],
+                },
+            }),
+            _ => serde_json::json!({
+                "sessionId": session_id,
+                "cwd": cwd,
+                "type": "user",
+                "timestamp": ts,
+                "message": {"role": "user", "content": [
+                    {"type": "tool_result", "tool_use_id": format!("toolu_{}", i - 1), "is_error": i % 30 == 2, "content": "test result: ok. 117 passed; 0 failed"},
+                ]},
+            }),
+        };
+        out.push_str(&line.to_string());
+        out.push('\n');
+    }
+    out
+}
+
+pub fn write_transcript(path: &Path, session_id: &str, cwd: &str, records: usize) -> Result<()> {
+    std::fs::write(path, transcript(session_id, cwd, records))
+        .with_context(|| format!("writing {}", path.display()))
+}
+
+/// A project with a CLAUDE.md, `categories` rule directories of
+/// `files_per` rules each, and a handful of commands.
+pub fn config_tree(root: &Path, categories: usize, files_per: usize) -> Result<()> {
+    let claude = root.join(".claude");
+    std::fs::create_dir_all(claude.join("commands"))?;
+    std::fs::write(
+        root.join("CLAUDE.md"),
+        "# Project\n\nBuild with cargo.\n".repeat(40),
+    )?;
+    std::fs::write(claude.join("settings.local.json"), "{}")?;
+    for c in 0..categories {
+        let dir = claude.join("rules").join(format!("category-{:02}", c));
+        std::fs::create_dir_all(&dir)?;
+        for f in 0..files_per {
+            std::fs::write(
+                dir.join(format!("rule-{:03}.md", f)),
+                "Prefer small functions with one responsibility.\n".repeat(20),
+            )?;
+        }
+    }
+    for i in 0..10 {
+        std::fs::write(
+            claude.join("commands").join(format!("cmd-{}.md", i)),
+            "Run it.\n",
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcript_parses_as_timeline() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("t.jsonl");
+        write_transcript(&path, "abc", "/tmp/proj", 30).unwrap();
+
+        let events = crate::session::timeline::load_timeline(&path);
+        // 10 prompts, 10 replies with a tool call each, 1 tool error.
+        assert_eq!(events.len(), 10 + 20 + 1);
+    }
+}
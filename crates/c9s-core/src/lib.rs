@@ -0,0 +1,23 @@
+//! Session discovery and parsing, the session store, the Tervezo API
+//! client and plan usage lookups, without any UI. The `c9s` binary is
+//! built on this crate.
+
+pub mod clock;
+#[cfg(any(test, feature = "test-util"))]
+pub mod fixtures;
+pub mod log;
+pub mod notify;
+pub mod outcome;
+pub mod session;
+pub mod store;
+pub mod tervezo;
+pub mod timetrack;
+pub mod usage;
+pub mod wakeup;
+
+use std::path::PathBuf;
+
+/// `~/.c9s`, where c9s keeps its config, database and exports.
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".c9s"))
+}
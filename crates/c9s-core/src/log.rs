@@ -85,6 +85,8 @@ pub fn push(level: LogLevel, msg: String) {
         buf.entries.drain(..excess);
     }
     buf.dirty = true;
+    drop(buf);
+    crate::wakeup::notify();
 }
 
 pub fn take_dirty() -> bool {
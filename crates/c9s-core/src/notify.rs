@@ -0,0 +1,65 @@
+//! Bell kinds and the per-session notification setting, shared by the
+//! terminal notifier and the store.
+
+/// Why the notifier fired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BellKind {
+    /// Claude finished its turn.
+    TurnEnd,
+    /// A tool call has been pending long enough that claude is most likely
+    /// waiting on a permission prompt.
+    NeedsInput,
+}
+
+impl BellKind {
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::TurnEnd => "finished its turn",
+            Self::NeedsInput => "is waiting for input",
+        }
+    }
+}
+
+/// Per-session choice of which bells to ring.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NotifyLevel {
+    #[default]
+    All,
+    NeedsInput,
+    Mute,
+}
+
+impl NotifyLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::All => "all",
+            Self::NeedsInput => "needs-input",
+            Self::Mute => "mute",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "all" => Some(Self::All),
+            "needs-input" => Some(Self::NeedsInput),
+            "mute" => Some(Self::Mute),
+            _ => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::All => Self::NeedsInput,
+            Self::NeedsInput => Self::Mute,
+            Self::Mute => Self::All,
+        }
+    }
+
+    pub fn allows(self, kind: BellKind) -> bool {
+        match self {
+            Self::All => true,
+            Self::NeedsInput => kind == BellKind::NeedsInput,
+            Self::Mute => false,
+        }
+    }
+}
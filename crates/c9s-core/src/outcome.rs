@@ -0,0 +1,173 @@
+//! How a session ended, as rated by the user, and the success rates built
+//! from those ratings.
+
+/// What came of a session's work.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Shipped,
+    Abandoned,
+    NeedsHuman,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Shipped, Outcome::Abandoned, Outcome::NeedsHuman];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Shipped => "shipped",
+            Self::Abandoned => "abandoned",
+            Self::NeedsHuman => "needs-human",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|o| o.as_str() == s)
+    }
+}
+
+/// Rated sessions of one project and model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutcomeStats {
+    pub project: String,
+    pub model: String,
+    pub sessions: u32,
+    pub shipped: u32,
+    pub abandoned: u32,
+    pub needs_human: u32,
+    /// Mean of the sessions given a rating.
+    pub avg_rating: Option<f64>,
+}
+
+impl OutcomeStats {
+    /// Share of rated sessions that shipped, 0.0–1.0.
+    pub fn success_rate(&self) -> f64 {
+        if self.sessions == 0 {
+            0.0
+        } else {
+            self.shipped as f64 / self.sessions as f64
+        }
+    }
+}
+
+/// A rated session with what it cost and how long it ran.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RatedSession {
+    pub model: String,
+    pub outcome: Outcome,
+    pub cost_usd: f64,
+    /// First to last activity; unknown for sessions never stored.
+    pub duration: Option<chrono::Duration>,
+}
+
+/// Rated sessions of one model family, for comparing models.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelComparison {
+    /// "opus", "sonnet", "haiku" or "other".
+    pub family: &'static str,
+    pub sessions: u32,
+    pub shipped: u32,
+    /// Abandoned or needing a human.
+    pub failed: u32,
+    pub total_cost: f64,
+    pub median_duration: Option<chrono::Duration>,
+}
+
+impl ModelComparison {
+    /// What each shipped task cost, counting the failed attempts too.
+    pub fn cost_per_shipped(&self) -> Option<f64> {
+        (self.shipped > 0).then(|| self.total_cost / self.shipped as f64)
+    }
+
+    pub fn failure_rate(&self) -> f64 {
+        if self.sessions == 0 {
+            0.0
+        } else {
+            self.failed as f64 / self.sessions as f64
+        }
+    }
+}
+
+pub fn model_family(model: &str) -> &'static str {
+    ["opus", "sonnet", "haiku"]
+        .into_iter()
+        .find(|f| model.contains(f))
+        .unwrap_or("other")
+}
+
+/// One row per model family that has rated sessions, opus first.
+pub fn compare_models(sessions: &[RatedSession]) -> Vec<ModelComparison> {
+    ["opus", "sonnet", "haiku", "other"]
+        .into_iter()
+        .filter_map(|family| {
+            let rows: Vec<_> = sessions
+                .iter()
+                .filter(|s| model_family(&s.model) == family)
+                .collect();
+            if rows.is_empty() {
+                return None;
+            }
+            let mut durations: Vec<_> = rows.iter().filter_map(|s| s.duration).collect();
+            durations.sort();
+            let median_duration = match durations.len() {
+                0 => None,
+                n if n % 2 == 1 => Some(durations[n / 2]),
+                n => Some((durations[n / 2 - 1] + durations[n / 2]) / 2),
+            };
+            let shipped = rows
+                .iter()
+                .filter(|s| s.outcome == Outcome::Shipped)
+                .count() as u32;
+            Some(ModelComparison {
+                family,
+                sessions: rows.len() as u32,
+                shipped,
+                failed: rows.len() as u32 - shipped,
+                total_cost: rows.iter().map(|s| s.cost_usd).sum(),
+                median_duration,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_models() {
+        let rated = |model: &str, outcome, cost, minutes: Option<i64>| RatedSession {
+            model: model.to_string(),
+            outcome,
+            cost_usd: cost,
+            duration: minutes.map(chrono::Duration::minutes),
+        };
+        let sessions = [
+            rated("claude-sonnet-4-5", Outcome::Shipped, 1.0, Some(10)),
+            rated("claude-opus-4-1", Outcome::Shipped, 4.0, Some(30)),
+            rated("claude-opus-4-1", Outcome::Abandoned, 2.0, Some(50)),
+            rated("claude-opus-4-1", Outcome::NeedsHuman, 3.0, None),
+            rated("claude-sonnet-4-5", Outcome::Shipped, 2.0, Some(20)),
+        ];
+        let report = compare_models(&sessions);
+        assert_eq!(report.len(), 2);
+
+        let opus = &report[0];
+        assert_eq!((opus.family, opus.sessions, opus.failed), ("opus", 3, 2));
+        assert_eq!(opus.cost_per_shipped(), Some(9.0));
+        assert!((opus.failure_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(opus.median_duration, Some(chrono::Duration::minutes(40)));
+
+        let sonnet = &report[1];
+        assert_eq!(sonnet.cost_per_shipped(), Some(1.5));
+        assert_eq!(sonnet.failure_rate(), 0.0);
+        assert_eq!(sonnet.median_duration, Some(chrono::Duration::minutes(15)));
+    }
+
+    #[test]
+    fn test_outcome_names_roundtrip() {
+        for outcome in Outcome::ALL {
+            assert_eq!(Outcome::parse(outcome.as_str()), Some(outcome));
+        }
+        assert_eq!(Outcome::parse("done"), None);
+    }
+}
@@ -1,6 +1,8 @@
 use std::io::Read;
 use std::path::{Path, PathBuf};
 
+use super::mcp::{scan_mcp_servers, McpHealth, McpServer};
+
 #[derive(Debug, Clone)]
 pub struct ConfigEntry {
     pub category: String,
@@ -14,6 +16,7 @@ pub struct ConfigItem {
     pub kind: ConfigItemKind,
     pub tokens: Option<u32>,
     pub always_loaded: Option<bool>,
+    pub health: Option<McpHealth>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -23,7 +26,12 @@ pub enum ConfigItemKind {
     FileExists,
     FileMissing,
     MemoryFile,
+    McpServer,
+    EnvSection,
+    EnvVar,
     SectionTotal,
+    /// The plan the session is currently working from.
+    ActivePlan,
 }
 
 fn estimate_file_tokens(path: &Path) -> (Option<u32>, Option<bool>) {
@@ -75,6 +83,7 @@ fn make_item(label: String, path: Option<PathBuf>, kind: ConfigItemKind) -> Conf
         kind,
         tokens,
         always_loaded,
+        health: None,
     }
 }
 
@@ -98,6 +107,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
 
     let global_md = claude_dir.join("CLAUDE.md");
@@ -175,6 +185,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionTotal,
         tokens: Some(global_total),
         always_loaded: None,
+        health: None,
     });
 
     items.push(ConfigItem {
@@ -183,6 +194,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
 
     items.push(ConfigItem {
@@ -191,6 +203,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
 
     let mut project_total: u32 = 0;
@@ -270,6 +283,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionTotal,
         tokens: Some(project_total),
         always_loaded: None,
+        health: None,
     });
 
     items.push(ConfigItem {
@@ -278,6 +292,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
 
     let mut mem_total: u32 = 0;
@@ -288,6 +303,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
 
     if cfg.project_memories.is_empty() {
@@ -316,8 +332,38 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionTotal,
         tokens: Some(mem_total),
         always_loaded: None,
+        health: None,
     });
 
+    if !cfg.mcp_servers.is_empty() {
+        items.push(ConfigItem {
+            label: String::new(),
+            path: None,
+            kind: ConfigItemKind::SectionHeader,
+            tokens: None,
+            always_loaded: None,
+            health: None,
+        });
+        items.push(ConfigItem {
+            label: format!("MCP Servers ({})", cfg.mcp_servers.len()),
+            path: None,
+            kind: ConfigItemKind::SectionHeader,
+            tokens: None,
+            always_loaded: None,
+            health: None,
+        });
+        for server in &cfg.mcp_servers {
+            items.push(ConfigItem {
+                label: server.name.clone(),
+                path: Some(server.source.clone()),
+                kind: ConfigItemKind::McpServer,
+                tokens: None,
+                always_loaded: None,
+                health: None,
+            });
+        }
+    }
+
     let grand_always = global_always + project_always;
     let grand_total = global_total + project_total + mem_total;
     items.push(ConfigItem {
@@ -326,6 +372,7 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionHeader,
         tokens: None,
         always_loaded: None,
+        health: None,
     });
     items.push(ConfigItem {
         label: format!(
@@ -337,11 +384,115 @@ pub fn build_config_items(cfg: &SessionConfig, cwd: &Path) -> Vec<ConfigItem> {
         kind: ConfigItemKind::SectionTotal,
         tokens: Some(grand_total),
         always_loaded: None,
+        health: None,
     });
 
     items
 }
 
+/// Builds the collapsible environment section shown above the config tree
+/// for live sessions. `None` means the process could not be inspected.
+pub fn build_env_items(env: Option<&[(String, String)]>, expanded: bool) -> Vec<ConfigItem> {
+    let mut items = Vec::new();
+    let (count, marker) = match env {
+        Some(vars) => (vars.len().to_string(), if expanded { "[-]" } else { "[+]" }),
+        None => ("n/a".to_string(), ""),
+    };
+    items.push(ConfigItem {
+        label: format!("Environment ({}) {}", count, marker)
+            .trim_end()
+            .to_string(),
+        path: None,
+        kind: ConfigItemKind::EnvSection,
+        tokens: None,
+        always_loaded: None,
+        health: None,
+    });
+    if expanded {
+        for (key, value) in env.unwrap_or_default() {
+            items.push(ConfigItem {
+                label: format!("  {}={}", key, value),
+                path: None,
+                kind: ConfigItemKind::EnvVar,
+                tokens: None,
+                always_loaded: None,
+                health: None,
+            });
+        }
+    }
+    items.push(ConfigItem {
+        label: String::new(),
+        path: None,
+        kind: ConfigItemKind::SectionHeader,
+        tokens: None,
+        always_loaded: None,
+        health: None,
+    });
+    items
+}
+
+/// Plan documents the session has referenced, in the order they first
+/// appeared. The last one is the active plan. Slugs resolve to
+/// `<cwd>/.claude/plans/<slug>.md`, then `~/.claude/plans/<slug>.md`.
+pub fn build_plan_items(slugs: &[String], cwd: &Path) -> Vec<ConfigItem> {
+    let mut dirs = vec![cwd.join(".claude").join("plans")];
+    if let Some(home) = dirs::home_dir() {
+        dirs.push(home.join(".claude").join("plans"));
+    }
+    build_plan_items_in(slugs, &dirs)
+}
+
+fn build_plan_items_in(slugs: &[String], dirs: &[PathBuf]) -> Vec<ConfigItem> {
+    if slugs.is_empty() {
+        return Vec::new();
+    }
+    let mut items = vec![ConfigItem {
+        label: "Plans".to_string(),
+        path: None,
+        kind: ConfigItemKind::SectionHeader,
+        tokens: None,
+        always_loaded: None,
+        health: None,
+    }];
+    for (i, slug) in slugs.iter().enumerate() {
+        let path = resolve_plan(slug, dirs);
+        let active = i + 1 == slugs.len();
+        let (label, kind) = match (&path, active) {
+            (Some(_), true) => (format!("{} (active)", slug), ConfigItemKind::ActivePlan),
+            (Some(_), false) => (slug.clone(), ConfigItemKind::FileExists),
+            (None, true) => (format!("{} (active)", slug), ConfigItemKind::FileMissing),
+            (None, false) => (slug.clone(), ConfigItemKind::FileMissing),
+        };
+        items.push(ConfigItem {
+            label,
+            path,
+            kind,
+            tokens: None,
+            always_loaded: None,
+            health: None,
+        });
+    }
+    items.push(ConfigItem {
+        label: String::new(),
+        path: None,
+        kind: ConfigItemKind::SectionHeader,
+        tokens: None,
+        always_loaded: None,
+        health: None,
+    });
+    items
+}
+
+fn resolve_plan(slug: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    // Slugs come from the transcript; never let one walk out of the dir.
+    if slug.is_empty() || slug.contains(['/', '\\']) || slug.starts_with('.') {
+        return None;
+    }
+    dirs.iter()
+        .map(|d| d.join(format!("{}.md", slug)))
+        .find(|p| p.is_file())
+}
+
 fn accumulate(item: &ConfigItem, total: &mut u32, always: &mut u32) {
     if let Some(t) = item.tokens {
         *total += t;
@@ -384,6 +535,7 @@ pub struct SessionConfig {
     pub project_settings: bool,
     pub project_commands: Vec<String>,
     pub project_memories: Vec<String>,
+    pub mcp_servers: Vec<McpServer>,
 }
 
 pub fn scan_session_config(cwd: &Path) -> SessionConfig {
@@ -407,6 +559,7 @@ pub fn scan_session_config(cwd: &Path) -> SessionConfig {
         .join(&encoded_cwd)
         .join("memory");
     let project_memories = scan_flat_files(&memory_dir);
+    let mcp_servers = scan_mcp_servers(cwd);
 
     SessionConfig {
         global_claude_md,
@@ -418,6 +571,7 @@ pub fn scan_session_config(cwd: &Path) -> SessionConfig {
         project_settings,
         project_commands,
         project_memories,
+        mcp_servers,
     }
 }
 
@@ -489,6 +643,58 @@ mod tests {
     use super::*;
     use std::io::Write;
 
+    #[test]
+    fn test_build_env_items_collapsed_and_expanded() {
+        let env = vec![("ANTHROPIC_MODEL".to_string(), "opus".to_string())];
+
+        let collapsed = build_env_items(Some(&env), false);
+        assert_eq!(collapsed.len(), 2);
+        assert_eq!(collapsed[0].kind, ConfigItemKind::EnvSection);
+        assert_eq!(collapsed[0].label, "Environment (1) [+]");
+
+        let expanded = build_env_items(Some(&env), true);
+        assert_eq!(expanded.len(), 3);
+        assert_eq!(expanded[1].kind, ConfigItemKind::EnvVar);
+        assert_eq!(expanded[1].label, "  ANTHROPIC_MODEL=opus");
+
+        let unavailable = build_env_items(None, true);
+        assert_eq!(unavailable[0].label, "Environment (n/a)");
+        assert_eq!(unavailable.len(), 2);
+    }
+
+    #[test]
+    fn test_build_plan_items() {
+        let project = tempfile::tempdir().unwrap();
+        let home = tempfile::tempdir().unwrap();
+        std::fs::write(project.path().join("old-plan.md"), "# old").unwrap();
+        std::fs::write(home.path().join("new-plan.md"), "# new").unwrap();
+        let dirs = [project.path().to_path_buf(), home.path().to_path_buf()];
+
+        let slugs = vec![
+            "old-plan".to_string(),
+            "gone-plan".to_string(),
+            "new-plan".to_string(),
+        ];
+        let items = build_plan_items_in(&slugs, &dirs);
+        assert_eq!(items.len(), 5);
+        assert_eq!(items[0].label, "Plans");
+        assert_eq!(items[1].kind, ConfigItemKind::FileExists);
+        assert_eq!(
+            items[1].path.as_deref(),
+            Some(dirs[0].join("old-plan.md").as_path())
+        );
+        assert_eq!(items[2].kind, ConfigItemKind::FileMissing);
+        assert_eq!(items[3].label, "new-plan (active)");
+        assert_eq!(items[3].kind, ConfigItemKind::ActivePlan);
+        assert_eq!(
+            items[3].path.as_deref(),
+            Some(dirs[1].join("new-plan.md").as_path())
+        );
+
+        assert!(build_plan_items_in(&[], &dirs).is_empty());
+        assert!(resolve_plan("../new-plan", &dirs).is_none());
+    }
+
     #[test]
     fn test_estimate_file_tokens() {
         let dir = tempfile::tempdir().unwrap();
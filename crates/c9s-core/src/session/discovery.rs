@@ -0,0 +1,1501 @@
+#[cfg(target_os = "macos")]
+use anyhow::Context;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime};
+
+use super::{Session, SessionStatus, TodoItem};
+
+/// How often `discover_all_with` reports partial results.
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(150);
+
+pub struct SessionDiscovery {
+    claude_dir: PathBuf,
+    stats_cache: HashMap<PathBuf, (SystemTime, u64, JsonlStats)>,
+    /// Paths parsed since the last `take_parsed_stats`.
+    parsed: HashSet<PathBuf>,
+    show_all_per_cwd: bool,
+}
+
+/// A parsed transcript summary with the file state it was parsed at, as
+/// persisted between runs so a cold start only reparses changed files.
+#[derive(Debug, Clone)]
+pub struct StatsEntry {
+    pub path: PathBuf,
+    pub mtime: SystemTime,
+    pub size: u64,
+    pub stats: JsonlStats,
+}
+
+impl StatsEntry {
+    /// The session this transcript belongs to.
+    pub fn owner(&self) -> String {
+        transcript_owner(&self.path, &self.stats)
+    }
+}
+
+fn transcript_owner(path: &Path, stats: &JsonlStats) -> String {
+    // Subagent lines carry their parent's id; see `from_stats`.
+    match &stats.session_id {
+        Some(id) if !stats.is_sidechain && stats.parent_session_id.is_none() => id.clone(),
+        _ => path
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .into_owned(),
+    }
+}
+
+#[derive(Debug)]
+struct ProcessInfo {
+    pid: u32,
+    cwd: PathBuf,
+    /// Full command line, used to spot `--resume <id>`.
+    args: String,
+    started_at: Option<DateTime<Utc>>,
+}
+
+impl ProcessInfo {
+    fn new(pid: u32, cwd: PathBuf) -> Self {
+        Self {
+            pid,
+            cwd,
+            args: String::new(),
+            started_at: None,
+        }
+    }
+}
+
+/// Bumped whenever `JsonlStats` gains fields or parsing changes what they
+/// hold, so persisted summaries from older versions are reparsed.
+pub const STATS_VERSION: u32 = 6;
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JsonlStats {
+    session_id: Option<String>,
+    /// First sessionId seen; differs from `session_id` when the file was
+    /// created by resuming an earlier session.
+    first_session_id: Option<String>,
+    /// Set on subagent transcripts: the session that spawned them.
+    parent_session_id: Option<String>,
+    is_sidechain: bool,
+    /// Time from each user/tool-result message to the first assistant reply.
+    turn_latencies_ms: Vec<u32>,
+    /// Only the head and tail of a large transcript were parsed, so the
+    /// latencies leave out the middle. Errors are counted throughout.
+    sampled: bool,
+    api_error_count: u32,
+    interrupt_count: u32,
+    tool_error_count: u32,
+    last_error_at: Option<DateTime<Utc>>,
+    cwd: Option<String>,
+    git_branch: Option<String>,
+    model: Option<String>,
+    claude_version: Option<String>,
+    input_tokens: u64,
+    output_tokens: u64,
+    cache_read_tokens: u64,
+    cache_write_tokens: u64,
+    message_count: u32,
+    tool_call_count: u32,
+    first_timestamp: Option<DateTime<Utc>>,
+    last_timestamp: Option<DateTime<Utc>>,
+    last_message_type: Option<String>,
+    last_stop_reason: Option<String>,
+    permission_mode: Option<String>,
+    plan_slugs: Vec<String>,
+    compaction_count: u32,
+    hook_run_count: u32,
+    hook_error_count: u32,
+    /// Complete lines that were not valid JSON.
+    parse_errors: u32,
+    /// Where an unterminated, unparseable last line starts: usually one
+    /// claude is still writing, so it is retried once the file changes
+    /// rather than counted as an error.
+    partial_offset: Option<u64>,
+    /// Todo list from the last TodoWrite call; each call replaces it whole.
+    todos: Vec<TodoItem>,
+    /// Tokens used per minute (unix minute) over the transcript's last
+    /// usage window, for the quota estimate.
+    window_tokens: Vec<(i64, u64)>,
+}
+
+impl JsonlStats {
+    fn add_window_tokens(&mut self, at: DateTime<Utc>, tokens: u64) {
+        let minute = at.timestamp().div_euclid(60);
+        let oldest = minute - crate::usage::WINDOW.num_minutes();
+        self.window_tokens.retain(|&(m, _)| m > oldest);
+        match self.window_tokens.last_mut() {
+            Some((m, t)) if *m == minute => *t += tokens,
+            _ => self.window_tokens.push((minute, tokens)),
+        }
+    }
+}
+
+/// Parse problems found in one transcript.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDiagnostics {
+    pub path: PathBuf,
+    pub session_id: Option<String>,
+    pub parse_errors: u32,
+    pub partial_offset: Option<u64>,
+}
+
+impl Default for SessionDiscovery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionDiscovery {
+    pub fn new() -> Self {
+        let claude_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join(".claude");
+        Self {
+            claude_dir,
+            stats_cache: HashMap::new(),
+            parsed: HashSet::new(),
+            show_all_per_cwd: false,
+        }
+    }
+
+    /// Fills the stats cache with summaries from an earlier run. Files whose
+    /// mtime has changed since are reparsed as usual.
+    pub fn seed_stats(&mut self, entries: Vec<StatsEntry>) {
+        for e in entries {
+            self.stats_cache
+                .entry(e.path)
+                .or_insert((e.mtime, e.size, e.stats));
+        }
+    }
+
+    /// Every transcript seen so far with the session it belongs to.
+    pub fn transcripts(&self) -> Vec<(PathBuf, String)> {
+        self.stats_cache
+            .iter()
+            .map(|(path, (_, _, s))| (path.clone(), transcript_owner(path, s)))
+            .collect()
+    }
+
+    /// Transcripts with skipped or unfinished lines, as of their last parse.
+    pub fn diagnostics(&self) -> Vec<FileDiagnostics> {
+        let mut found: Vec<FileDiagnostics> = self
+            .stats_cache
+            .iter()
+            .filter(|(_, (_, _, s))| s.parse_errors > 0 || s.partial_offset.is_some())
+            .map(|(path, (_, _, s))| FileDiagnostics {
+                path: path.clone(),
+                session_id: s.session_id.clone(),
+                parse_errors: s.parse_errors,
+                partial_offset: s.partial_offset,
+            })
+            .collect();
+        found.sort_by(|a, b| {
+            b.parse_errors
+                .cmp(&a.parse_errors)
+                .then(a.path.cmp(&b.path))
+        });
+        found
+    }
+
+    /// Summaries parsed since the last call, for persisting.
+    pub fn take_parsed_stats(&mut self) -> Vec<StatsEntry> {
+        std::mem::take(&mut self.parsed)
+            .into_iter()
+            .filter_map(|path| {
+                let (mtime, size, stats) = self.stats_cache.get(&path)?.clone();
+                Some(StatsEntry {
+                    path,
+                    mtime,
+                    size,
+                    stats,
+                })
+            })
+            .collect()
+    }
+
+    /// When set, every JSONL in a directory becomes its own session instead
+    /// of only the most recent one per cwd.
+    pub fn set_show_all_per_cwd(&mut self, show_all: bool) {
+        self.show_all_per_cwd = show_all;
+    }
+
+    pub fn discover_all(&mut self) -> Result<Vec<Session>> {
+        self.discover_all_with(&mut |_| {})
+    }
+
+    /// `discover_all`, also handing `on_progress` the sessions found so far
+    /// every `PROGRESS_INTERVAL` while project directories are still being
+    /// read, so a first scan of a large install can be shown as it goes.
+    pub fn discover_all_with(
+        &mut self,
+        on_progress: &mut dyn FnMut(Vec<Session>),
+    ) -> Result<Vec<Session>> {
+        let live_processes = self.find_claude_processes()?;
+
+        let projects_dir = self.claude_dir.join("projects");
+        if !projects_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut candidates: Vec<Candidate> = Vec::new();
+        let mut last_progress = Instant::now();
+        let mut reported = 0;
+
+        if let Ok(entries) = std::fs::read_dir(&projects_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_dir() {
+                    continue;
+                }
+
+                let dir_name = path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+                    .to_string();
+
+                if dir_name.starts_with('.') {
+                    continue;
+                }
+
+                let fallback_cwd = decode_project_path(&dir_name);
+
+                if let Ok(jsonl_files) = std::fs::read_dir(&path) {
+                    for jf in jsonl_files.flatten() {
+                        let jf_path = jf.path();
+                        if jf_path.is_dir() {
+                            self.collect_subagents(&jf_path, &fallback_cwd, &mut candidates);
+                            continue;
+                        }
+                        if jf_path.extension().is_none_or(|e| e != "jsonl") {
+                            continue;
+                        }
+
+                        let session_id = jf_path
+                            .file_stem()
+                            .unwrap_or_default()
+                            .to_string_lossy()
+                            .to_string();
+
+                        if session_id.contains('.') {
+                            continue;
+                        }
+
+                        let stats = self.parse_jsonl_cached(&jf_path);
+
+                        if stats.session_id.is_none() && stats.message_count == 0 {
+                            continue;
+                        }
+
+                        candidates.push(Candidate::from_stats(
+                            stats,
+                            session_id,
+                            &fallback_cwd,
+                            None,
+                        ));
+                    }
+                }
+
+                if candidates.len() > reported && last_progress.elapsed() >= PROGRESS_INTERVAL {
+                    on_progress(self.finish(candidates.clone(), &live_processes));
+                    reported = candidates.len();
+                    last_progress = Instant::now();
+                }
+            }
+        }
+
+        Ok(self.finish(candidates, &live_processes))
+    }
+
+    fn finish(&self, candidates: Vec<Candidate>, processes: &[ProcessInfo]) -> Vec<Session> {
+        let mut sessions = assign_live_pids(candidates, processes, self.show_all_per_cwd);
+        sessions.sort_by(|a, b| b.last_activity.cmp(&a.last_activity));
+        sessions
+    }
+
+    /// Newer claude versions store subagent transcripts under
+    /// `<project>/<session-id>/subagents/*.jsonl`.
+    fn collect_subagents(
+        &mut self,
+        session_dir: &Path,
+        fallback_cwd: &str,
+        candidates: &mut Vec<Candidate>,
+    ) {
+        let parent_id = session_dir
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+        let Ok(files) = std::fs::read_dir(session_dir.join("subagents")) else {
+            return;
+        };
+        for f in files.flatten() {
+            let path = f.path();
+            if path.extension().is_none_or(|e| e != "jsonl") {
+                continue;
+            }
+            let stem = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            let stats = self.parse_jsonl_cached(&path);
+            if stats.message_count == 0 {
+                continue;
+            }
+            candidates.push(Candidate::from_stats(
+                stats,
+                stem,
+                fallback_cwd,
+                Some(&parent_id),
+            ));
+        }
+    }
+
+    fn find_claude_processes(&self) -> Result<Vec<ProcessInfo>> {
+        let pgrep_output = Command::new("pgrep").arg("-x").arg("claude").output();
+
+        let pids: Vec<u32> = match pgrep_output {
+            Ok(output) => String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(|line| line.trim().parse().ok())
+                .collect(),
+            Err(_) => return Ok(Vec::new()),
+        };
+
+        if pids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut processes = self.resolve_process_cwds(&pids)?;
+        add_process_details(&mut processes);
+        Ok(processes)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn resolve_process_cwds(&self, pids: &[u32]) -> Result<Vec<ProcessInfo>> {
+        let pid_list = pids
+            .iter()
+            .map(|p| p.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let output = Command::new("lsof")
+            .args(["-p", &pid_list, "-a", "-d", "cwd", "-Fn"])
+            .output()
+            .context("failed to run lsof")?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut processes = Vec::new();
+        let mut current_pid: Option<u32> = None;
+
+        for line in stdout.lines() {
+            if let Some(pid_str) = line.strip_prefix('p') {
+                current_pid = pid_str.parse().ok();
+            } else if let Some(path) = line.strip_prefix('n') {
+                if let Some(pid) = current_pid {
+                    processes.push(ProcessInfo::new(pid, PathBuf::from(path)));
+                }
+            }
+        }
+
+        Ok(processes)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn resolve_process_cwds(&self, pids: &[u32]) -> Result<Vec<ProcessInfo>> {
+        let mut processes = Vec::new();
+        for &pid in pids {
+            if let Ok(link) = std::fs::read_link(format!("/proc/{}/cwd", pid)) {
+                processes.push(ProcessInfo::new(pid, link));
+            }
+        }
+        Ok(processes)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    fn resolve_process_cwds(&self, _pids: &[u32]) -> Result<Vec<ProcessInfo>> {
+        Ok(Vec::new())
+    }
+
+    fn parse_jsonl_cached(&mut self, path: &Path) -> JsonlStats {
+        let meta = std::fs::metadata(path).ok();
+        let mtime = meta.as_ref().and_then(|m| m.modified().ok());
+        let file_size = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+
+        if let Some(mtime) = mtime {
+            if let Some((cached_mtime, cached_size, cached_stats)) = self.stats_cache.get(path) {
+                // A half-written last line is retried as soon as the file
+                // grows, even within the same mtime tick.
+                let partial = cached_stats.partial_offset.is_some();
+                if *cached_mtime == mtime && (!partial || *cached_size == file_size) {
+                    return cached_stats.clone();
+                }
+                if !partial && file_size > 2 * 1024 * 1024 {
+                    let growth = file_size.saturating_sub(*cached_size);
+                    if growth < 64 * 1024 {
+                        return cached_stats.clone();
+                    }
+                }
+            }
+        }
+
+        let stats = Self::parse_jsonl(path);
+
+        if let Some(mtime) = mtime {
+            self.stats_cache
+                .insert(path.to_path_buf(), (mtime, file_size, stats.clone()));
+            self.parsed.insert(path.to_path_buf());
+        }
+
+        stats
+    }
+
+    /// The session in a transcript found anywhere, as of its last line and
+    /// not running.
+    pub fn read_transcript(path: &Path) -> Session {
+        let stem = path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Candidate::from_stats(Self::parse_jsonl(path), stem, "", None).into_session(None)
+    }
+
+    pub fn parse_jsonl(path: &Path) -> JsonlStats {
+        let mut stats = JsonlStats::default();
+
+        let file_size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        const TAIL_SIZE: u64 = 512 * 1024;
+        const LARGE_FILE_THRESHOLD: u64 = 2 * 1024 * 1024;
+
+        if file_size > LARGE_FILE_THRESHOLD {
+            Self::parse_jsonl_fast(path, file_size, TAIL_SIZE, &mut stats);
+        } else {
+            Self::parse_jsonl_full(path, &mut stats);
+        }
+
+        stats
+    }
+
+    fn parse_jsonl_full(path: &Path, stats: &mut JsonlStats) {
+        let content = match std::fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        Self::parse_jsonl_lines(&content, 0, stats, true);
+    }
+
+    fn parse_jsonl_fast(path: &Path, file_size: u64, tail_size: u64, stats: &mut JsonlStats) {
+        use std::io::{Read, Seek, SeekFrom, BufRead, BufReader};
+
+        let file = match std::fs::File::open(path) {
+            Ok(f) => f,
+            Err(_) => return,
+        };
+        stats.sampled = true;
+
+        let mut reader = BufReader::new(&file);
+
+        let mut head = String::new();
+        let mut head_bytes = 0u64;
+        const HEAD_LIMIT: u64 = 64 * 1024;
+        loop {
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => break,
+                Ok(n) => {
+                    head_bytes += n as u64;
+                    head.push_str(&line);
+                    if head_bytes >= HEAD_LIMIT {
+                        break;
+                    }
+                }
+                Err(_) => break,
+            }
+        }
+        Self::parse_jsonl_lines(&head, 0, stats, false);
+
+        let tail_start = if file_size > tail_size { file_size - tail_size } else { 0 };
+        if tail_start > head_bytes {
+            // Lines up to the one the tail cuts into are only skimmed for
+            // errors, so the counts and the last error cover the whole file.
+            let mut pos = head_bytes;
+            let mut line = Vec::new();
+            while pos <= tail_start {
+                line.clear();
+                match reader.read_until(b'\n', &mut line) {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => pos += n as u64,
+                }
+                let Ok(text) = std::str::from_utf8(&line) else {
+                    continue;
+                };
+                if !ERROR_HINTS.iter().any(|h| text.contains(h)) {
+                    continue;
+                }
+                if let Ok(value) = serde_json::from_str::<Value>(text) {
+                    let at = value
+                        .get("timestamp")
+                        .and_then(|v| v.as_str())
+                        .and_then(|ts| ts.parse::<DateTime<Utc>>().ok());
+                    let msg_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
+                    add_errors(stats, &value, msg_type, at);
+                }
+            }
+
+            let file2 = match std::fs::File::open(path) {
+                Ok(f) => f,
+                Err(_) => return,
+            };
+            let mut reader2 = BufReader::new(file2);
+            if reader2.seek(SeekFrom::Start(tail_start)).is_err() {
+                return;
+            }
+            let mut tail = String::new();
+            if reader2.read_to_string(&mut tail).is_err() {
+                return;
+            }
+            if let Some(first_nl) = tail.find('\n') {
+                let tail_clean = &tail[first_nl + 1..];
+                let base = tail_start + first_nl as u64 + 1;
+                Self::parse_jsonl_lines(tail_clean, base, stats, false);
+            }
+        }
+    }
+
+    /// `base` is the file offset `content` starts at.
+    fn parse_jsonl_lines(content: &str, base: u64, stats: &mut JsonlStats, count_messages: bool) {
+        let mut pending_user_ts: Option<DateTime<Utc>> = None;
+        let mut offset = base;
+        stats.partial_offset = None;
+        for raw in content.split_inclusive('\n') {
+            let line_start = offset;
+            offset += raw.len() as u64;
+            let line = raw.trim_end_matches(['\n', '\r']);
+            if line.trim().is_empty() {
+                continue;
+            }
+            let value: Value = match serde_json::from_str(line) {
+                Ok(v) => v,
+                Err(_) if !raw.ends_with('\n') => {
+                    stats.partial_offset = Some(line_start);
+                    continue;
+                }
+                Err(_) => {
+                    stats.parse_errors += 1;
+                    continue;
+                }
+            };
+
+            if let Some(sid) = value.get("sessionId").and_then(|v| v.as_str()) {
+                if stats.first_session_id.is_none() {
+                    stats.first_session_id = Some(sid.to_string());
+                }
+                stats.session_id = Some(sid.to_string());
+            }
+
+            if stats.parent_session_id.is_none() {
+                if let Some(parent) = value.get("parentSessionId").and_then(|v| v.as_str()) {
+                    stats.parent_session_id = Some(parent.to_string());
+                }
+            }
+
+            if value.get("isSidechain").and_then(|v| v.as_bool()) == Some(true) {
+                stats.is_sidechain = true;
+            }
+
+            if stats.cwd.is_none() {
+                if let Some(cwd) = value.get("cwd").and_then(|v| v.as_str()) {
+                    stats.cwd = Some(cwd.to_string());
+                }
+            }
+
+            if let Some(branch) = value.get("gitBranch").and_then(|v| v.as_str()) {
+                stats.git_branch = Some(branch.to_string());
+            }
+
+            if let Some(version) = value.get("version").and_then(|v| v.as_str()) {
+                stats.claude_version = Some(version.to_string());
+            }
+
+            if stats.permission_mode.is_none() {
+                if let Some(pm) = value.get("permissionMode").and_then(|v| v.as_str()) {
+                    stats.permission_mode = Some(pm.to_string());
+                }
+            }
+
+            if let Some(slug) = value.get("slug").and_then(|v| v.as_str()) {
+                if !stats.plan_slugs.contains(&slug.to_string()) {
+                    stats.plan_slugs.push(slug.to_string());
+                }
+            }
+
+            if let Some(hc) = value.get("hookCount").and_then(|v| v.as_u64()) {
+                stats.hook_run_count += hc as u32;
+            }
+
+            if let Some(errs) = value.get("hookErrors").and_then(|v| v.as_array()) {
+                stats.hook_error_count += errs.len() as u32;
+            }
+
+            let mut line_ts = None;
+            if let Some(ts_str) = value.get("timestamp").and_then(|v| v.as_str()) {
+                if let Ok(ts) = ts_str.parse::<DateTime<Utc>>() {
+                    if stats.first_timestamp.is_none() {
+                        stats.first_timestamp = Some(ts);
+                    }
+                    stats.last_timestamp = Some(ts);
+                    line_ts = Some(ts);
+                }
+            }
+
+            let msg_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
+
+            add_errors(stats, &value, msg_type, line_ts);
+
+            match msg_type {
+                "user" => pending_user_ts = line_ts.or(pending_user_ts),
+                "assistant" => {
+                    if let (Some(start), Some(end)) = (pending_user_ts.take(), line_ts) {
+                        let ms = end.signed_duration_since(start).num_milliseconds();
+                        if ms >= 0 {
+                            stats.turn_latencies_ms.push(ms.min(u32::MAX as i64) as u32);
+                        }
+                    }
+                }
+                _ => {}
+            }
+
+            match msg_type {
+                "user" => {
+                    if count_messages {
+                        stats.message_count += 1;
+                    }
+                    stats.last_message_type = Some("user".to_string());
+                }
+                "assistant" => {
+                    if count_messages {
+                        stats.message_count += 1;
+                    }
+                    stats.last_message_type = Some("assistant".to_string());
+
+                    if let Some(message) = value.get("message") {
+                        if let Some(sr) = message.get("stop_reason").and_then(|v| v.as_str()) {
+                            stats.last_stop_reason = Some(sr.to_string());
+                        }
+                        if let Some(model) = message.get("model").and_then(|v| v.as_str()) {
+                            stats.model = Some(model.to_string());
+                        }
+                        if let Some(todos) = latest_todo_write(message) {
+                            stats.todos = todos;
+                        }
+
+                        if let Some(usage) = message.get("usage") {
+                            let count =
+                                |key: &str| usage.get(key).and_then(|v| v.as_u64()).unwrap_or(0);
+                            let input = count("input_tokens");
+                            let output = count("output_tokens");
+                            let cache_write = count("cache_creation_input_tokens");
+                            stats.input_tokens += input;
+                            stats.output_tokens += output;
+                            stats.cache_read_tokens += count("cache_read_input_tokens");
+                            stats.cache_write_tokens += cache_write;
+                            // Cache reads are left out: they dwarf the rest
+                            // and barely move the quota.
+                            if let Some(ts) = line_ts {
+                                stats.add_window_tokens(ts, input + output + cache_write);
+                            }
+                        }
+                    }
+                }
+                "progress" => {
+                    if value.get("data").is_some() {
+                        stats.tool_call_count += 1;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// The todo list from the last TodoWrite call in an assistant message.
+fn latest_todo_write(message: &Value) -> Option<Vec<TodoItem>> {
+    message
+        .get("content")?
+        .as_array()?
+        .iter()
+        .rev()
+        .filter(|c| c.get("type").and_then(|v| v.as_str()) == Some("tool_use"))
+        .filter(|c| c.get("name").and_then(|v| v.as_str()) == Some("TodoWrite"))
+        .find_map(|c| serde_json::from_value(c.get("input")?.get("todos")?.clone()).ok())
+}
+
+/// A parsed session before live processes are matched to it.
+#[derive(Clone)]
+struct Candidate {
+    session: Session,
+    last_message_type: Option<String>,
+    last_stop_reason: Option<String>,
+}
+
+impl Candidate {
+    /// `dir_parent` is the owning session for transcripts found in a
+    /// session's `subagents/` directory.
+    fn from_stats(
+        stats: JsonlStats,
+        file_stem: String,
+        fallback_cwd: &str,
+        dir_parent: Option<&str>,
+    ) -> Self {
+        let is_subagent =
+            stats.is_sidechain || stats.parent_session_id.is_some() || dir_parent.is_some();
+        let (real_session_id, parent_id) = if is_subagent {
+            // Subagent lines carry the parent's sessionId, so the file name
+            // is the only unique id.
+            let parent = stats
+                .parent_session_id
+                .clone()
+                .or_else(|| dir_parent.map(String::from))
+                .or_else(|| stats.session_id.clone())
+                .filter(|p| *p != file_stem);
+            (file_stem, parent)
+        } else {
+            let id = stats.session_id.clone().unwrap_or(file_stem);
+            let parent = stats.first_session_id.clone().filter(|first| *first != id);
+            (id, parent)
+        };
+        let project_cwd = stats
+            .cwd
+            .clone()
+            .unwrap_or_else(|| fallback_cwd.to_string());
+        let project_name = extract_project_name(&project_cwd);
+        let now = Utc::now();
+        let latency_p50_ms = percentile(&stats.turn_latencies_ms, 50);
+        let latency_p95_ms = percentile(&stats.turn_latencies_ms, 95);
+
+        let session = Session {
+            id: real_session_id,
+            pid: None,
+            cwd: PathBuf::from(&project_cwd),
+            project_name,
+            git_branch: stats.git_branch,
+            model: stats.model,
+            status: SessionStatus::Dead,
+            started_at: stats.first_timestamp.unwrap_or(now),
+            last_activity: stats.last_timestamp.unwrap_or(now),
+            input_tokens: stats.input_tokens,
+            output_tokens: stats.output_tokens,
+            cache_read_tokens: stats.cache_read_tokens,
+            cache_write_tokens: stats.cache_write_tokens,
+            message_count: stats.message_count,
+            tool_call_count: stats.tool_call_count,
+            claude_version: stats.claude_version,
+            permission_mode: stats.permission_mode,
+            plan_slugs: stats.plan_slugs,
+            compaction_count: stats.compaction_count,
+            hook_run_count: stats.hook_run_count,
+            hook_error_count: stats.hook_error_count,
+            parent_id,
+            is_subagent,
+            latency_p50_ms,
+            latency_p95_ms,
+            stats_sampled: stats.sampled,
+            api_error_count: stats.api_error_count,
+            interrupt_count: stats.interrupt_count,
+            tool_error_count: stats.tool_error_count,
+            last_error_at: stats.last_error_at,
+            parse_errors: stats.parse_errors,
+            partial_line: stats.partial_offset.is_some(),
+            todos: stats.todos,
+            window_tokens: stats
+                .window_tokens
+                .iter()
+                .filter_map(|&(m, t)| DateTime::from_timestamp(m * 60, 0).map(|at| (at, t)))
+                .collect(),
+            imported: false,
+        };
+
+        Self {
+            session,
+            last_message_type: stats.last_message_type,
+            last_stop_reason: stats.last_stop_reason,
+        }
+    }
+
+    fn into_session(mut self, pid: Option<u32>) -> Session {
+        self.session.status = match pid {
+            Some(_) => infer_live_status(
+                self.session.last_activity,
+                self.last_message_type.as_deref(),
+                self.last_stop_reason.as_deref(),
+            ),
+            None => SessionStatus::Dead,
+        };
+        self.session.pid = pid;
+        self.session
+    }
+}
+
+/// Matches live processes to sessions, allowing several processes per cwd.
+/// Unless `show_all` is set, sessions without a process are dropped when
+/// their cwd has a newer or live session.
+fn assign_live_pids(
+    candidates: Vec<Candidate>,
+    processes: &[ProcessInfo],
+    show_all: bool,
+) -> Vec<Session> {
+    let (subagents, candidates): (Vec<Candidate>, Vec<Candidate>) =
+        candidates.into_iter().partition(|c| c.session.is_subagent);
+
+    let mut by_cwd: HashMap<String, Vec<Candidate>> = HashMap::new();
+    for c in candidates {
+        let key = c.session.cwd.to_string_lossy().to_string();
+        by_cwd.entry(key).or_default().push(c);
+    }
+    let mut procs_by_cwd: HashMap<String, Vec<&ProcessInfo>> = HashMap::new();
+    for p in processes {
+        procs_by_cwd
+            .entry(p.cwd.to_string_lossy().to_string())
+            .or_default()
+            .push(p);
+    }
+
+    let mut sessions = Vec::new();
+    for (cwd, mut group) in by_cwd {
+        group.sort_by_key(|c| std::cmp::Reverse(c.session.last_activity));
+        let procs = procs_by_cwd.remove(&cwd).unwrap_or_default();
+        let pids = correlate_pids(&group, &procs);
+        let any_live = pids.iter().any(Option::is_some);
+
+        for (i, (candidate, pid)) in group.into_iter().zip(pids).enumerate() {
+            let keep = show_all || pid.is_some() || (i == 0 && !any_live);
+            if keep {
+                sessions.push(candidate.into_session(pid));
+            }
+        }
+    }
+
+    // Subagents run inside their parent's process: they are live only while
+    // the parent is, and are listed only when the parent is.
+    let live_parents: HashMap<String, bool> = sessions
+        .iter()
+        .map(|s| (s.id.clone(), s.pid.is_some()))
+        .collect();
+    for sub in subagents {
+        let parent_live = sub
+            .session
+            .parent_id
+            .as_ref()
+            .and_then(|p| live_parents.get(p))
+            .copied();
+        if parent_live.is_none() && !show_all {
+            continue;
+        }
+        // A finished subagent reads as Idle; report it as Dead instead.
+        let status = match parent_live {
+            Some(true) => match infer_live_status(
+                sub.session.last_activity,
+                sub.last_message_type.as_deref(),
+                sub.last_stop_reason.as_deref(),
+            ) {
+                SessionStatus::Idle => SessionStatus::Dead,
+                live => live,
+            },
+            _ => SessionStatus::Dead,
+        };
+        let mut session = sub.into_session(None);
+        session.status = status;
+        sessions.push(session);
+    }
+    sessions
+}
+
+/// Returns the pid for each candidate (same order as `group`, which is
+/// sorted newest first). A process whose command line names a session id
+/// claims that session; the rest are paired by start time with the session
+/// that began closest to when the process started.
+fn correlate_pids(group: &[Candidate], procs: &[&ProcessInfo]) -> Vec<Option<u32>> {
+    let mut pids: Vec<Option<u32>> = vec![None; group.len()];
+    let mut unmatched: Vec<&ProcessInfo> = Vec::new();
+
+    for proc in procs {
+        let named = (0..group.len())
+            .find(|&i| pids[i].is_none() && proc.args.contains(&group[i].session.id));
+        match named {
+            Some(i) => pids[i] = Some(proc.pid),
+            None => unmatched.push(proc),
+        }
+    }
+
+    // Newest processes first so they pair with the newest sessions.
+    unmatched.sort_by_key(|c| std::cmp::Reverse(c.started_at));
+    for proc in unmatched {
+        let free: Vec<usize> = (0..group.len()).filter(|&i| pids[i].is_none()).collect();
+        let best = proc
+            .started_at
+            .and_then(|start| {
+                free.iter()
+                    .copied()
+                    .filter(|&i| group[i].session.last_activity >= start)
+                    .min_by_key(|&i| {
+                        (group[i].session.started_at - start)
+                            .num_seconds()
+                            .unsigned_abs()
+                    })
+            })
+            .or_else(|| free.first().copied());
+        if let Some(i) = best {
+            pids[i] = Some(proc.pid);
+        }
+    }
+    pids
+}
+
+/// Fills in command line and start time for each process with one `ps` call.
+fn add_process_details(processes: &mut [ProcessInfo]) {
+    if processes.is_empty() {
+        return;
+    }
+    let pid_list = processes
+        .iter()
+        .map(|p| p.pid.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let Ok(output) = Command::new("ps")
+        .args(["-o", "pid=,etime=,args=", "-p", &pid_list])
+        .output()
+    else {
+        return;
+    };
+    let now = Utc::now();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(pid), Some(etime)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            continue;
+        };
+        if let Some(p) = processes.iter_mut().find(|p| p.pid == pid) {
+            p.started_at = parse_etime(etime).map(|secs| now - chrono::Duration::seconds(secs));
+            p.args = parts.collect::<Vec<_>>().join(" ");
+        }
+    }
+}
+
+/// Parses `ps` elapsed time, formatted as `[[dd-]hh:]mm:ss`, into seconds.
+fn parse_etime(etime: &str) -> Option<i64> {
+    let (days, rest) = match etime.split_once('-') {
+        Some((d, r)) => (d.parse::<i64>().ok()?, r),
+        None => (0, etime),
+    };
+    let mut secs = 0i64;
+    for part in rest.split(':') {
+        secs = secs * 60 + part.parse::<i64>().ok()?;
+    }
+    Some(days * 86_400 + secs)
+}
+
+const INTERRUPT_MARKER: &str = "[Request interrupted by user";
+
+/// One of these is on every line `count_error_markers` finds an error on,
+/// so lines without any aren't worth parsing for errors.
+const ERROR_HINTS: [&str; 4] = [
+    "isApiErrorMessage",
+    "\"error\"",
+    INTERRUPT_MARKER,
+    "\"is_error\":true",
+];
+
+/// Adds the errors on one line to the counts; `at` is the line's time.
+fn add_errors(stats: &mut JsonlStats, value: &Value, msg_type: &str, at: Option<DateTime<Utc>>) {
+    let (api_errors, interrupts, tool_errors) = count_error_markers(value, msg_type);
+    if api_errors + interrupts + tool_errors > 0 {
+        stats.api_error_count += api_errors;
+        stats.interrupt_count += interrupts;
+        stats.tool_error_count += tool_errors;
+        if at.is_some() {
+            stats.last_error_at = at;
+        }
+    }
+}
+
+/// Returns (api errors, user interrupts, failed tool results) found on one
+/// JSONL line.
+fn count_error_markers(value: &Value, msg_type: &str) -> (u32, u32, u32) {
+    let is_api_error = value.get("isApiErrorMessage").and_then(|v| v.as_bool()) == Some(true)
+        || (msg_type == "system" && value.get("level").and_then(|v| v.as_str()) == Some("error"));
+    let api_errors = u32::from(is_api_error);
+
+    if msg_type != "user" {
+        return (api_errors, 0, 0);
+    }
+
+    let content = value.get("message").and_then(|m| m.get("content"));
+    let mut interrupts = 0;
+    let mut tool_errors = 0;
+    match content {
+        Some(Value::String(text)) if text.starts_with(INTERRUPT_MARKER) => interrupts += 1,
+        Some(Value::Array(blocks)) => {
+            for block in blocks {
+                match block.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => {
+                        let text = block.get("text").and_then(|t| t.as_str()).unwrap_or("");
+                        if text.starts_with(INTERRUPT_MARKER) {
+                            interrupts += 1;
+                        }
+                    }
+                    Some("tool_result")
+                        if block.get("is_error").and_then(|e| e.as_bool()) == Some(true) =>
+                    {
+                        tool_errors += 1;
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+    (api_errors, interrupts, tool_errors)
+}
+
+/// Nearest-rank percentile; `None` for an empty sample.
+fn percentile(samples: &[u32], pct: usize) -> Option<u32> {
+    if samples.is_empty() {
+        return None;
+    }
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let rank = (pct * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+fn infer_live_status(
+    last_activity: DateTime<Utc>,
+    last_message_type: Option<&str>,
+    last_stop_reason: Option<&str>,
+) -> SessionStatus {
+    let hung_threshold = chrono::Duration::minutes(5);
+    if Utc::now().signed_duration_since(last_activity) > hung_threshold {
+        return SessionStatus::Idle;
+    }
+    match last_message_type {
+        Some("user") => SessionStatus::Thinking,
+        Some("assistant") => match last_stop_reason {
+            Some("end_turn") => SessionStatus::Idle,
+            _ => SessionStatus::Active,
+        },
+        _ => SessionStatus::Active,
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SessionFile {
+    pub session_id: String,
+    pub path: PathBuf,
+    pub size_bytes: u64,
+    pub last_modified: Option<DateTime<Utc>>,
+    pub message_count: u32,
+    pub is_current: bool,
+}
+
+pub fn list_session_files(cwd: &Path, current_session_id: &str) -> Vec<SessionFile> {
+    let claude_dir = dirs::home_dir()
+        .unwrap_or_else(|| PathBuf::from("~"))
+        .join(".claude");
+
+    let encoded = cwd.to_string_lossy().replace('/', "-");
+    let project_dir = claude_dir.join("projects").join(&encoded);
+
+    if !project_dir.exists() {
+        return Vec::new();
+    }
+
+    let mut files: Vec<SessionFile> = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(&project_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|e| e != "jsonl") {
+                continue;
+            }
+            let session_id = path
+                .file_stem()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string();
+            if session_id.contains('.') {
+                continue;
+            }
+
+            let meta = std::fs::metadata(&path).ok();
+            let size_bytes = meta.as_ref().map(|m| m.len()).unwrap_or(0);
+            if size_bytes == 0 {
+                continue;
+            }
+            let last_modified = meta
+                .and_then(|m| m.modified().ok())
+                .map(|t| DateTime::<Utc>::from(t));
+
+            let msg_count = count_messages(&path);
+            if msg_count == 0 && session_id != current_session_id {
+                continue;
+            }
+
+            files.push(SessionFile {
+                is_current: session_id == current_session_id,
+                session_id,
+                path,
+                size_bytes,
+                last_modified,
+                message_count: msg_count,
+            });
+        }
+    }
+
+    files.sort_by(|a, b| b.last_modified.cmp(&a.last_modified));
+    files
+}
+
+fn count_messages(path: &Path) -> u32 {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return 0,
+    };
+    content
+        .lines()
+        .filter(|l| {
+            l.contains("\"type\":\"user\"") || l.contains("\"type\":\"assistant\"")
+        })
+        .count() as u32
+}
+
+fn decode_project_path(encoded: &str) -> String {
+    encoded.replace('-', "/")
+}
+
+fn extract_project_name(cwd: &str) -> String {
+    let parts: Vec<&str> = cwd.trim_end_matches('/').rsplit('/').collect();
+    match parts.len() {
+        0 => cwd.to_string(),
+        1 => parts[0].to_string(),
+        _ => format!("{}/{}", parts[1], parts[0]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::TodoStatus;
+
+    #[test]
+    fn test_decode_project_path() {
+        assert_eq!(decode_project_path("-Users-foo-bar"), "/Users/foo/bar");
+        assert_eq!(
+            decode_project_path("-home-user-project"),
+            "/home/user/project"
+        );
+    }
+
+    #[test]
+    fn test_parse_jsonl_extracts_session_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.jsonl");
+        let lines = [
+            r#"{"sessionId":"abc-123","cwd":"/tmp/proj","type":"user","timestamp":"2026-01-01T00:00:00Z"}"#,
+            r#"{"type":"assistant","message":{"model":"claude-sonnet-4-20250514","stop_reason":"end_turn","usage":{"input_tokens":100,"output_tokens":50}},"timestamp":"2026-01-01T00:01:00Z"}"#,
+        ];
+        std::fs::write(&file, lines.join("\n")).unwrap();
+
+        let stats = SessionDiscovery::parse_jsonl(&file);
+        assert_eq!(stats.session_id.as_deref(), Some("abc-123"));
+        assert_eq!(stats.cwd.as_deref(), Some("/tmp/proj"));
+        assert_eq!(stats.input_tokens, 100);
+        assert_eq!(stats.output_tokens, 50);
+        assert_eq!(stats.message_count, 2);
+        assert!(stats.model.as_deref().unwrap().contains("sonnet"));
+        let minute = "2026-01-01T00:01:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(stats.window_tokens, [(minute.timestamp() / 60, 150)]);
+    }
+
+    #[test]
+    fn test_latest_todo_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.jsonl");
+        let write = |todos: &str| {
+            format!(
+                r#"{{"type":"assistant","message":{{"content":[{{"type":"text","text":"ok"}},{{"type":"tool_use","name":"TodoWrite","input":{{"todos":{}}}}}]}}}}"#,
+                todos
+            )
+        };
+        let first =
+            write(r#"[{"content":"Read code","status":"pending","activeForm":"Reading code"}]"#);
+        let second = write(
+            r#"[{"content":"Read code","status":"completed","activeForm":"Reading code"},{"content":"Fix bug","status":"in_progress","activeForm":"Fixing bug"},{"content":"Test","status":"pending"}]"#,
+        );
+        std::fs::write(&file, format!("{}\n{}\n", first, second)).unwrap();
+
+        let mut discovery = SessionDiscovery::new();
+        let stats = discovery.parse_jsonl_cached(&file);
+        assert_eq!(stats.todos.len(), 3);
+        assert_eq!(stats.todos[0].status, TodoStatus::Completed);
+        assert_eq!(stats.todos[1].active_form.as_deref(), Some("Fixing bug"));
+        assert_eq!(stats.todos[2].active_form, None);
+    }
+
+    #[test]
+    fn test_malformed_and_partial_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.jsonl");
+        let first = r#"{"sessionId":"abc","type":"user","timestamp":"2026-01-01T00:00:00Z"}"#;
+        let partial = r#"{"type":"assistant","message":{"usa"#;
+        std::fs::write(
+            &file,
+            format!(
+                "{}
+{{not json
+
+{}",
+                first, partial
+            ),
+        )
+        .unwrap();
+
+        let mut discovery = SessionDiscovery::new();
+        let stats = discovery.parse_jsonl_cached(&file);
+        assert_eq!(stats.message_count, 1);
+        assert_eq!(stats.parse_errors, 1);
+        assert_eq!(stats.partial_offset, Some(first.len() as u64 + 12));
+        assert_eq!(discovery.diagnostics().len(), 1);
+
+        // Claude finishes the line; it is picked up on the next parse.
+        let done = r#"{"type":"assistant","timestamp":"2026-01-01T00:01:00Z"}"#;
+        std::fs::write(
+            &file,
+            format!(
+                "{}
+{{not json
+
+{}
+",
+                first, done
+            ),
+        )
+        .unwrap();
+        let stats = discovery.parse_jsonl_cached(&file);
+        assert_eq!(stats.message_count, 2);
+        assert_eq!(stats.partial_offset, None);
+        assert_eq!(discovery.diagnostics()[0].parse_errors, 1);
+    }
+
+    #[test]
+    fn test_large_transcripts_are_sampled() {
+        let dir = tempfile::tempdir().unwrap();
+        let small = dir.path().join("small.jsonl");
+        let large = dir.path().join("large.jsonl");
+        crate::fixtures::write_transcript(&small, "small", "/tmp/proj", 30).unwrap();
+        crate::fixtures::write_transcript(&large, "large", "/tmp/proj", 8_000).unwrap();
+
+        assert!(!SessionDiscovery::parse_jsonl(&small).sampled);
+        let stats = SessionDiscovery::parse_jsonl(&large);
+        assert!(stats.sampled);
+        assert!(SessionDiscovery::read_transcript(&large).stats_sampled);
+        // Every 30th record is a failed tool result, middle included.
+        assert_eq!(stats.tool_error_count, 8_000 / 30 + 1);
+    }
+
+    fn candidate(id: &str, cwd: &str, minutes_ago: i64) -> Candidate {
+        let stats = JsonlStats {
+            session_id: Some(id.to_string()),
+            cwd: Some(cwd.to_string()),
+            last_timestamp: Some(Utc::now() - chrono::Duration::minutes(minutes_ago)),
+            last_message_type: Some("assistant".to_string()),
+            last_stop_reason: Some("end_turn".to_string()),
+            ..Default::default()
+        };
+        Candidate::from_stats(stats, id.to_string(), cwd, None)
+    }
+
+    #[test]
+    fn test_assign_live_pids_collapses_per_cwd_by_default() {
+        let live = vec![ProcessInfo::new(42, PathBuf::from("/a"))];
+        let sessions = assign_live_pids(
+            vec![candidate("old", "/a", 30), candidate("new", "/a", 1)],
+            &live,
+            false,
+        );
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "new");
+        assert_eq!(sessions[0].pid, Some(42));
+        assert_eq!(sessions[0].status, SessionStatus::Idle);
+    }
+
+    #[test]
+    fn test_assign_live_pids_show_all_keeps_older_as_dead() {
+        let live = vec![ProcessInfo::new(42, PathBuf::from("/a"))];
+        let mut sessions = assign_live_pids(
+            vec![candidate("old", "/a", 30), candidate("new", "/a", 1)],
+            &live,
+            true,
+        );
+        sessions.sort_by(|a, b| a.id.cmp(&b.id));
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, "new");
+        assert_eq!(sessions[0].pid, Some(42));
+        assert_eq!(sessions[1].id, "old");
+        assert_eq!(sessions[1].status, SessionStatus::Dead);
+    }
+
+    #[test]
+    fn test_two_processes_in_one_cwd_are_both_live() {
+        let mut resumed = ProcessInfo::new(1, PathBuf::from("/a"));
+        resumed.args = "claude --resume old".to_string();
+        let mut fresh = ProcessInfo::new(2, PathBuf::from("/a"));
+        fresh.started_at = Some(Utc::now() - chrono::Duration::minutes(3));
+
+        let mut new = candidate("new", "/a", 1);
+        new.session.started_at = Utc::now() - chrono::Duration::minutes(3);
+        let mut sessions = assign_live_pids(
+            vec![candidate("old", "/a", 2), new],
+            &[resumed, fresh],
+            false,
+        );
+        sessions.sort_by(|a, b| a.id.cmp(&b.id));
+
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions[0].id, "new");
+        assert_eq!(sessions[0].pid, Some(2));
+        assert_eq!(sessions[1].id, "old");
+        assert_eq!(sessions[1].pid, Some(1));
+    }
+
+    #[test]
+    fn test_start_time_pairs_with_closest_session() {
+        let now = Utc::now();
+        let mut early = ProcessInfo::new(10, PathBuf::from("/a"));
+        early.started_at = Some(now - chrono::Duration::hours(2));
+        let mut late = ProcessInfo::new(20, PathBuf::from("/a"));
+        late.started_at = Some(now - chrono::Duration::minutes(10));
+
+        let mut a = candidate("a", "/a", 1);
+        a.session.started_at = now - chrono::Duration::hours(2);
+        let mut b = candidate("b", "/a", 2);
+        b.session.started_at = now - chrono::Duration::minutes(10);
+
+        let pids = correlate_pids(&[a, b], &[&early, &late]);
+        assert_eq!(pids, vec![Some(10), Some(20)]);
+    }
+
+    #[test]
+    fn test_subagent_nested_under_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("agent-1.jsonl");
+        let lines = [
+            r#"{"sessionId":"parent","isSidechain":true,"cwd":"/a","type":"user","timestamp":"2026-01-01T00:00:00Z"}"#,
+            r#"{"sessionId":"parent","isSidechain":true,"cwd":"/a","type":"assistant","message":{"stop_reason":"end_turn"},"timestamp":"2026-01-01T00:01:00Z"}"#,
+        ];
+        std::fs::write(&file, lines.join("\n")).unwrap();
+        let stats = SessionDiscovery::parse_jsonl(&file);
+        let sub = Candidate::from_stats(stats, "agent-1".to_string(), "/a", None);
+        assert!(sub.session.is_subagent);
+        assert_eq!(sub.session.id, "agent-1");
+        assert_eq!(sub.session.parent_id.as_deref(), Some("parent"));
+
+        let live = vec![ProcessInfo::new(7, PathBuf::from("/a"))];
+        let sessions = assign_live_pids(vec![candidate("parent", "/a", 1), sub], &live, false);
+        assert_eq!(sessions.len(), 2);
+        let agent = sessions.iter().find(|s| s.id == "agent-1").unwrap();
+        assert_eq!(agent.pid, None);
+        assert_eq!(agent.status, SessionStatus::Dead);
+    }
+
+    #[test]
+    fn test_orphan_subagent_hidden_by_default() {
+        let mut sub = candidate("agent-2", "/a", 1);
+        sub.session.is_subagent = true;
+        sub.session.parent_id = Some("gone".to_string());
+        assert!(assign_live_pids(vec![sub], &[], false).is_empty());
+    }
+
+    #[test]
+    fn test_turn_latencies_and_percentiles() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("lat.jsonl");
+        let lines = [
+            r#"{"sessionId":"s","type":"user","timestamp":"2026-01-01T00:00:00Z"}"#,
+            r#"{"type":"assistant","message":{},"timestamp":"2026-01-01T00:00:02Z"}"#,
+            r#"{"type":"assistant","message":{},"timestamp":"2026-01-01T00:00:05Z"}"#,
+            r#"{"type":"user","timestamp":"2026-01-01T00:01:00Z"}"#,
+            r#"{"type":"assistant","message":{},"timestamp":"2026-01-01T00:01:10Z"}"#,
+        ];
+        std::fs::write(&file, lines.join("\n")).unwrap();
+
+        let stats = SessionDiscovery::parse_jsonl(&file);
+        assert_eq!(stats.turn_latencies_ms, vec![2000, 10000]);
+
+        assert_eq!(percentile(&[], 50), None);
+        let samples: Vec<u32> = (1..=100).collect();
+        assert_eq!(percentile(&samples, 50), Some(50));
+        assert_eq!(percentile(&samples, 95), Some(95));
+        assert_eq!(percentile(&[7], 95), Some(7));
+    }
+
+    #[test]
+    fn test_error_and_interrupt_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("err.jsonl");
+        let lines = [
+            r#"{"sessionId":"s","type":"user","message":{"content":"hi"},"timestamp":"2026-01-01T00:00:00Z"}"#,
+            r#"{"type":"assistant","isApiErrorMessage":true,"message":{},"timestamp":"2026-01-01T00:00:01Z"}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","is_error":true,"content":"boom"},{"type":"tool_result","content":"ok"}]},"timestamp":"2026-01-01T00:00:02Z"}"#,
+            r#"{"type":"user","message":{"content":[{"type":"text","text":"[Request interrupted by user for tool use]"}]},"timestamp":"2026-01-01T00:00:03Z"}"#,
+            r#"{"type":"user","message":{"content":"[Request interrupted by user]"},"timestamp":"2026-01-01T00:00:04Z"}"#,
+        ];
+        std::fs::write(&file, lines.join("\n")).unwrap();
+
+        let stats = SessionDiscovery::parse_jsonl(&file);
+        assert_eq!(stats.api_error_count, 1);
+        assert_eq!(stats.tool_error_count, 1);
+        assert_eq!(stats.interrupt_count, 2);
+        assert_eq!(
+            stats.last_error_at,
+            Some("2026-01-01T00:00:04Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn test_parse_etime() {
+        assert_eq!(parse_etime("05:07"), Some(307));
+        assert_eq!(parse_etime("01:00:00"), Some(3600));
+        assert_eq!(
+            parse_etime("2-03:04:05"),
+            Some(2 * 86_400 + 3 * 3600 + 4 * 60 + 5)
+        );
+        assert_eq!(parse_etime("bogus"), None);
+    }
+
+    #[test]
+    fn test_resumed_session_records_parent() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("new-id.jsonl");
+        let lines = [
+            r#"{"sessionId":"old-id","cwd":"/tmp/proj","type":"user","timestamp":"2026-01-01T00:00:00Z"}"#,
+            r#"{"sessionId":"new-id","cwd":"/tmp/proj","type":"user","timestamp":"2026-01-02T00:00:00Z"}"#,
+        ];
+        std::fs::write(&file, lines.join("\n")).unwrap();
+
+        let stats = SessionDiscovery::parse_jsonl(&file);
+        let c = Candidate::from_stats(stats, "new-id".to_string(), "/tmp/proj", None);
+        assert_eq!(c.session.id, "new-id");
+        assert_eq!(c.session.parent_id.as_deref(), Some("old-id"));
+    }
+}
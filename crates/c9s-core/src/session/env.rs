@@ -0,0 +1,145 @@
+/// Variable name prefixes worth surfacing; everything else is noise for
+/// debugging why a session behaves differently.
+const RELEVANT_PREFIXES: &[&str] = &[
+    "ANTHROPIC_",
+    "CLAUDE_",
+    "NODE_OPTIONS",
+    "NODE_EXTRA_CA_CERTS",
+    "HTTP_PROXY",
+    "HTTPS_PROXY",
+    "NO_PROXY",
+    "ALL_PROXY",
+    "MCP_",
+    "DISABLE_",
+    "AWS_REGION",
+    "AWS_PROFILE",
+    "CLOUD_ML_REGION",
+];
+
+const SECRET_MARKERS: &[&str] = &["KEY", "TOKEN", "SECRET", "PASSWORD"];
+
+/// Reads the environment of a running process, filtered to relevant vars
+/// and sorted by name. Returns `None` if the process can't be inspected.
+pub fn read_session_env(pid: u32) -> Option<Vec<(String, String)>> {
+    let vars = read_process_env(pid)?;
+    let mut relevant: Vec<(String, String)> = vars
+        .into_iter()
+        .filter(|(k, _)| is_relevant(k))
+        .map(|(k, v)| {
+            let v = if is_secret(&k) { mask_value(&v) } else { v };
+            (k, v)
+        })
+        .collect();
+    relevant.sort();
+    relevant.dedup_by(|a, b| a.0 == b.0);
+    Some(relevant)
+}
+
+#[cfg(target_os = "linux")]
+fn read_process_env(pid: u32) -> Option<Vec<(String, String)>> {
+    let raw = std::fs::read(format!("/proc/{}/environ", pid)).ok()?;
+    Some(parse_environ(&raw))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_process_env(pid: u32) -> Option<Vec<(String, String)>> {
+    let output = std::process::Command::new("ps")
+        .args(["eww", "-o", "command=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(parse_ps_env(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_environ(raw: &[u8]) -> Vec<(String, String)> {
+    raw.split(|&b| b == 0)
+        .filter_map(|entry| {
+            let entry = String::from_utf8_lossy(entry);
+            let (k, v) = entry.split_once('=')?;
+            Some((k.to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+/// `ps eww` appends the environment to the command line as space-separated
+/// `KEY=value` words; values containing spaces are not recoverable, so only
+/// whole `KEY=value` tokens are kept.
+#[cfg_attr(target_os = "linux", allow(dead_code))]
+fn parse_ps_env(output: &str) -> Vec<(String, String)> {
+    output
+        .split_whitespace()
+        .filter_map(|word| {
+            let (k, v) = word.split_once('=')?;
+            let valid_name = !k.is_empty()
+                && k.chars()
+                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+            valid_name.then(|| (k.to_string(), v.to_string()))
+        })
+        .collect()
+}
+
+fn is_relevant(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    RELEVANT_PREFIXES.iter().any(|p| upper.starts_with(p))
+}
+
+fn is_secret(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|m| upper.contains(m))
+}
+
+fn mask_value(value: &str) -> String {
+    if value.chars().count() <= 8 {
+        return "****".to_string();
+    }
+    let prefix: String = value.chars().take(6).collect();
+    format!("{}****", prefix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_environ() {
+        let raw = b"PATH=/usr/bin\0ANTHROPIC_MODEL=opus\0EMPTY=\0junk\0";
+        let vars = parse_environ(raw);
+        assert_eq!(
+            vars,
+            vec![
+                ("PATH".to_string(), "/usr/bin".to_string()),
+                ("ANTHROPIC_MODEL".to_string(), "opus".to_string()),
+                ("EMPTY".to_string(), String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ps_env() {
+        let out = "node /usr/bin/claude --resume HOME=/Users/me HTTPS_PROXY=http://p:8080\n";
+        let vars = parse_ps_env(out);
+        assert_eq!(
+            vars,
+            vec![
+                ("HOME".to_string(), "/Users/me".to_string()),
+                ("HTTPS_PROXY".to_string(), "http://p:8080".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_relevance_and_masking() {
+        assert!(is_relevant("ANTHROPIC_BASE_URL"));
+        assert!(is_relevant("https_proxy"));
+        assert!(is_relevant("NODE_OPTIONS"));
+        assert!(!is_relevant("PATH"));
+
+        assert!(is_secret("ANTHROPIC_API_KEY"));
+        assert!(!is_secret("ANTHROPIC_MODEL"));
+        assert_eq!(mask_value("sk-ant-abcdef123456"), "sk-ant****");
+        assert_eq!(mask_value("short"), "****");
+    }
+}
@@ -0,0 +1,372 @@
+use anyhow::{Context, Result};
+use serde_json::Value;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use super::Session;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Markdown,
+    Html,
+}
+
+impl ExportFormat {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" | "htm" => Some(Self::Html),
+            _ => None,
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Block {
+    Text(String),
+    ToolCall { name: String, input: String },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Turn {
+    from_user: bool,
+    timestamp: Option<String>,
+    blocks: Vec<Block>,
+}
+
+/// `~/.c9s/exports/<project>-<short id>.<ext>`, used when no path is given.
+pub fn default_export_path(session: &Session, format: ExportFormat) -> Option<PathBuf> {
+    crate::config_dir().map(|d| {
+        d.join("exports")
+            .join(format!("{}.{}", export_stem(session), format.extension()))
+    })
+}
+
+/// `<project>-<short id>`, safe as a file name.
+pub(crate) fn export_stem(session: &Session) -> String {
+    let project = session.project_name.replace(
+        |c: char| !c.is_ascii_alphanumeric() && c != '-' && c != '_',
+        "-",
+    );
+    let short_id: String = session.id.chars().take(8).collect();
+    format!("{}-{}", project, short_id)
+}
+
+/// Renders the session transcript at `path` as a standalone document.
+/// `cost` turns the estimated cost in USD into the text shown for it.
+pub fn export_transcript(
+    session: &Session,
+    path: &Path,
+    format: ExportFormat,
+    cost: &dyn Fn(f64) -> String,
+) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("reading transcript {}", path.display()))?;
+    let turns = parse_turns(&content);
+    let cost = cost(session.estimated_cost_usd());
+    Ok(match format {
+        ExportFormat::Markdown => render_markdown(session, &turns, &cost),
+        ExportFormat::Html => render_html(session, &turns, &cost),
+    })
+}
+
+/// Collects user and assistant turns, merging consecutive assistant records
+/// (one per content block in the JSONL) into a single turn. Tool results and
+/// meta records are dropped.
+fn parse_turns(content: &str) -> Vec<Turn> {
+    let mut turns: Vec<Turn> = Vec::new();
+    for line in content.lines() {
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let from_user = match value.get("type").and_then(|t| t.as_str()) {
+            Some("user") => true,
+            Some("assistant") => false,
+            _ => continue,
+        };
+        if value.get("isMeta").and_then(|v| v.as_bool()) == Some(true) {
+            continue;
+        }
+
+        let blocks = match value.get("message").and_then(|m| m.get("content")) {
+            Some(Value::String(text)) => vec![Block::Text(text.trim().to_string())],
+            Some(Value::Array(items)) => items.iter().filter_map(parse_block).collect(),
+            _ => Vec::new(),
+        };
+        let blocks: Vec<Block> = blocks
+            .into_iter()
+            .filter(|b| !matches!(b, Block::Text(t) if t.is_empty()))
+            .collect();
+        if blocks.is_empty() {
+            continue;
+        }
+
+        match turns.last_mut() {
+            Some(last) if !last.from_user && !from_user => last.blocks.extend(blocks),
+            _ => turns.push(Turn {
+                from_user,
+                timestamp: value
+                    .get("timestamp")
+                    .and_then(|t| t.as_str())
+                    .map(String::from),
+                blocks,
+            }),
+        }
+    }
+    turns
+}
+
+fn parse_block(item: &Value) -> Option<Block> {
+    match item.get("type").and_then(|t| t.as_str())? {
+        "text" => Some(Block::Text(item.get("text")?.as_str()?.trim().to_string())),
+        "tool_use" => Some(Block::ToolCall {
+            name: item
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or("tool")
+                .to_string(),
+            input: item
+                .get("input")
+                .map(|i| serde_json::to_string_pretty(i).unwrap_or_default())
+                .unwrap_or_default(),
+        }),
+        _ => None,
+    }
+}
+
+fn tool_summary(name: &str, input: &str) -> String {
+    let arg = serde_json::from_str::<Value>(input).ok().and_then(|v| {
+        [
+            "file_path",
+            "path",
+            "pattern",
+            "command",
+            "query",
+            "url",
+            "description",
+        ]
+        .iter()
+        .find_map(|k| v.get(*k).and_then(|s| s.as_str()).map(String::from))
+    });
+    match arg {
+        Some(arg) => {
+            let arg = arg.lines().next().unwrap_or("");
+            let short: String = arg.chars().take(80).collect();
+            if short.len() < arg.len() {
+                format!("{}: {}...", name, short)
+            } else {
+                format!("{}: {}", name, short)
+            }
+        }
+        None => name.to_string(),
+    }
+}
+
+fn footer_line(session: &Session, cost: &str) -> String {
+    format!(
+        "{} messages, {} tool calls, {} tokens ({} in / {} out / {} cache read / {} cache write), est. {}",
+        session.message_count,
+        session.tool_call_count,
+        session.total_tokens(),
+        session.input_tokens,
+        session.output_tokens,
+        session.cache_read_tokens,
+        session.cache_write_tokens,
+        cost,
+    )
+}
+
+fn render_markdown(session: &Session, turns: &[Turn], cost: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# {}", session.project_name);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "- Session: `{}`", session.id);
+    let _ = writeln!(out, "- Directory: `{}`", session.cwd.display());
+    if let Some(ref branch) = session.git_branch {
+        let _ = writeln!(out, "- Branch: `{}`", branch);
+    }
+    if let Some(ref model) = session.model {
+        let _ = writeln!(out, "- Model: {}", model);
+    }
+    let _ = writeln!(
+        out,
+        "- Started: {}",
+        session.started_at.format("%Y-%m-%d %H:%M UTC")
+    );
+
+    for turn in turns {
+        let who = if turn.from_user { "User" } else { "Assistant" };
+        let _ = writeln!(out);
+        match turn.timestamp {
+            Some(ref ts) => {
+                let _ = writeln!(out, "## {} <sub>{}</sub>", who, ts);
+            }
+            None => {
+                let _ = writeln!(out, "## {}", who);
+            }
+        }
+        for block in &turn.blocks {
+            let _ = writeln!(out);
+            match block {
+                Block::Text(text) => {
+                    let _ = writeln!(out, "{}", text);
+                }
+                Block::ToolCall { name, input } => {
+                    let _ = writeln!(
+                        out,
+                        "<details><summary>{}</summary>\n\n```json\n{}\n```\n\n</details>",
+                        escape_html(&tool_summary(name, input)),
+                        input
+                    );
+                }
+            }
+        }
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "---");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "_{}_", footer_line(session, cost));
+    out
+}
+
+fn render_html(session: &Session, turns: &[Turn], cost: &str) -> String {
+    let mut out = String::new();
+    let title = escape_html(&session.project_name);
+    let _ = writeln!(out, "<!DOCTYPE html>");
+    let _ = writeln!(
+        out,
+        "<html><head><meta charset=\"utf-8\"><title>{}</title>",
+        title
+    );
+    let _ = writeln!(
+        out,
+        "<style>body{{font-family:sans-serif;max-width:52em;margin:2em auto;padding:0 1em}}\
+         .user{{border-left:3px solid #3a7;padding-left:1em}}\
+         .assistant{{border-left:3px solid #a3a;padding-left:1em}}\
+         pre{{background:#f4f4f4;padding:.5em;overflow-x:auto;white-space:pre-wrap}}\
+         .ts{{color:#888;font-size:.8em}}footer{{color:#666;margin-top:2em}}</style>"
+    );
+    let _ = writeln!(out, "</head><body>");
+    let _ = writeln!(out, "<h1>{}</h1>", title);
+    let _ = writeln!(
+        out,
+        "<p>Session <code>{}</code> in <code>{}</code>{}</p>",
+        escape_html(&session.id),
+        escape_html(&session.cwd.to_string_lossy()),
+        session
+            .git_branch
+            .as_deref()
+            .map(|b| format!(" on <code>{}</code>", escape_html(b)))
+            .unwrap_or_default()
+    );
+
+    for turn in turns {
+        let (class, who) = if turn.from_user {
+            ("user", "User")
+        } else {
+            ("assistant", "Assistant")
+        };
+        let _ = writeln!(out, "<section class=\"{}\">", class);
+        let _ = write!(out, "<h2>{}", who);
+        if let Some(ref ts) = turn.timestamp {
+            let _ = write!(out, " <span class=\"ts\">{}</span>", escape_html(ts));
+        }
+        let _ = writeln!(out, "</h2>");
+        for block in &turn.blocks {
+            match block {
+                Block::Text(text) => {
+                    let _ = writeln!(out, "<pre>{}</pre>", escape_html(text));
+                }
+                Block::ToolCall { name, input } => {
+                    let _ = writeln!(
+                        out,
+                        "<details><summary>{}</summary><pre>{}</pre></details>",
+                        escape_html(&tool_summary(name, input)),
+                        escape_html(input)
+                    );
+                }
+            }
+        }
+        let _ = writeln!(out, "</section>");
+    }
+
+    let _ = writeln!(
+        out,
+        "<footer>{}</footer>",
+        escape_html(&footer_line(session, cost))
+    );
+    let _ = writeln!(out, "</body></html>");
+    out
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TRANSCRIPT: &str = r#"{"type":"user","timestamp":"2026-01-01T10:00:00Z","message":{"content":"Fix <the> build"}}
+{"type":"assistant","message":{"content":[{"type":"text","text":"Checking."}]}}
+{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"cargo build"}}]}}
+{"type":"user","message":{"content":[{"type":"tool_result","content":"ok"}]}}
+{"type":"user","isMeta":true,"message":{"content":"caveat"}}
+{"type":"assistant","message":{"content":[{"type":"text","text":"Done."}]}}"#;
+
+    #[test]
+    fn test_parse_turns_merges_assistant_records() {
+        let turns = parse_turns(TRANSCRIPT);
+        assert_eq!(turns.len(), 2);
+        assert!(turns[0].from_user);
+        assert_eq!(turns[0].blocks, vec![Block::Text("Fix <the> build".into())]);
+        assert!(!turns[1].from_user);
+        assert_eq!(turns[1].blocks.len(), 3);
+        assert!(matches!(&turns[1].blocks[1], Block::ToolCall { name, .. } if name == "Bash"));
+    }
+
+    #[test]
+    fn test_tool_summary() {
+        assert_eq!(
+            tool_summary("Bash", r#"{"command":"cargo build\ncargo test"}"#),
+            "Bash: cargo build"
+        );
+        assert_eq!(tool_summary("TodoWrite", r#"{"todos":[]}"#), "TodoWrite");
+    }
+
+    #[test]
+    fn test_format_parse() {
+        assert_eq!(ExportFormat::parse("MD"), Some(ExportFormat::Markdown));
+        assert_eq!(ExportFormat::parse("html"), Some(ExportFormat::Html));
+        assert_eq!(ExportFormat::parse("pdf"), None);
+    }
+
+    #[test]
+    fn test_html_escapes_content() {
+        assert_eq!(
+            escape_html("a <b> & \"c\""),
+            "a &lt;b&gt; &amp; &quot;c&quot;"
+        );
+    }
+
+    #[test]
+    fn test_markdown_escapes_tool_summary() {
+        let turns = parse_turns(
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"Bash","input":{"command":"test $a < 3 && exit"}}]}}"#,
+        );
+        let session = Session::fixture("abc", "/tmp/proj");
+        let doc = render_markdown(&session, &turns, "$0.00");
+        assert!(doc.contains("<summary>Bash: test $a &lt; 3 &amp;&amp; exit</summary>"));
+    }
+}
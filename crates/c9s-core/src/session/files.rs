@@ -0,0 +1,243 @@
+//! The files a local session changed, from the Edit and Write calls in its
+//! transcript, like the Changes tab of a Tervezo implementation.
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The session's first change wrote a new file.
+    Created,
+    Modified,
+}
+
+impl FileStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Created => "added",
+            Self::Modified => "modified",
+        }
+    }
+}
+
+/// One file the session changed, with the lines its calls added and
+/// removed. A Write replacing a file counts its lines as added only, as
+/// the transcript doesn't hold what it replaced.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileTouch {
+    pub path: PathBuf,
+    pub status: FileStatus,
+    pub additions: u32,
+    pub deletions: u32,
+    /// Successful calls that changed the file.
+    pub edits: u32,
+    pub last_touched: Option<DateTime<Utc>>,
+}
+
+impl FileTouch {
+    /// The path relative to `cwd` when it is inside it.
+    pub fn display_path(&self, cwd: &Path) -> String {
+        self.path
+            .strip_prefix(cwd)
+            .unwrap_or(&self.path)
+            .to_string_lossy()
+            .into_owned()
+    }
+}
+
+/// A change one tool call made, before its result is known.
+struct Change {
+    id: String,
+    path: PathBuf,
+    write: bool,
+    additions: u32,
+    deletions: u32,
+    timestamp: Option<DateTime<Utc>>,
+}
+
+/// The files changed by the transcript at `path`, sorted by path. Calls
+/// that failed are left out.
+pub fn files_touched(path: &Path) -> Vec<FileTouch> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let mut changes = Vec::new();
+    // tool_use id -> (failed, result text)
+    let mut results: HashMap<String, (bool, String)> = HashMap::new();
+    for value in content
+        .lines()
+        .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+    {
+        let timestamp = value
+            .get("timestamp")
+            .and_then(|v| v.as_str())
+            .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+        let Some(Value::Array(blocks)) = value.get("message").and_then(|m| m.get("content")) else {
+            continue;
+        };
+        for block in blocks {
+            match block.get("type").and_then(|t| t.as_str()) {
+                Some("tool_use") => changes.extend(change(block, timestamp)),
+                Some("tool_result") => {
+                    let Some(id) = block.get("tool_use_id").and_then(|i| i.as_str()) else {
+                        continue;
+                    };
+                    let failed = block.get("is_error").and_then(|e| e.as_bool()) == Some(true);
+                    results.insert(id.to_string(), (failed, result_text(block)));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    let mut files: BTreeMap<PathBuf, FileTouch> = BTreeMap::new();
+    for change in changes {
+        let result = results.get(&change.id);
+        if result.is_some_and(|(failed, _)| *failed) {
+            continue;
+        }
+        let created =
+            change.write && result.is_some_and(|(_, text)| text.starts_with("File created"));
+        let file = files
+            .entry(change.path.clone())
+            .or_insert_with(|| FileTouch {
+                path: change.path,
+                status: if created {
+                    FileStatus::Created
+                } else {
+                    FileStatus::Modified
+                },
+                additions: 0,
+                deletions: 0,
+                edits: 0,
+                last_touched: None,
+            });
+        file.additions += change.additions;
+        file.deletions += change.deletions;
+        file.edits += 1;
+        file.last_touched = change.timestamp.or(file.last_touched);
+    }
+    files.into_values().collect()
+}
+
+fn change(block: &Value, timestamp: Option<DateTime<Utc>>) -> Option<Change> {
+    let id = block.get("id").and_then(|i| i.as_str()).unwrap_or("");
+    let input = block.get("input")?;
+    let str_field = |key: &str| input.get(key).and_then(|v| v.as_str());
+    let (path, write, (additions, deletions)) = match block.get("name").and_then(|n| n.as_str())? {
+        "Edit" => (
+            str_field("file_path")?,
+            false,
+            line_changes(
+                str_field("old_string").unwrap_or(""),
+                str_field("new_string").unwrap_or(""),
+            ),
+        ),
+        "MultiEdit" => {
+            let edits = input.get("edits").and_then(|e| e.as_array())?;
+            let counts = edits.iter().fold((0, 0), |(a, d), edit| {
+                let field = |key: &str| edit.get(key).and_then(|v| v.as_str()).unwrap_or("");
+                let (added, removed) = line_changes(field("old_string"), field("new_string"));
+                (a + added, d + removed)
+            });
+            (str_field("file_path")?, false, counts)
+        }
+        "Write" => (
+            str_field("file_path")?,
+            true,
+            (str_field("content").unwrap_or("").lines().count() as u32, 0),
+        ),
+        "NotebookEdit" => (
+            str_field("notebook_path")?,
+            false,
+            (
+                str_field("new_source").unwrap_or("").lines().count() as u32,
+                0,
+            ),
+        ),
+        _ => return None,
+    };
+    Some(Change {
+        id: id.to_string(),
+        path: PathBuf::from(path),
+        write,
+        additions,
+        deletions,
+        timestamp,
+    })
+}
+
+/// Lines added and removed replacing `old` with `new`, leaving out the
+/// lines they start and end with in common.
+fn line_changes(old: &str, new: &str) -> (u32, u32) {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    let prefix = old.iter().zip(&new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    (
+        (new.len() - prefix - suffix) as u32,
+        (old.len() - prefix - suffix) as u32,
+    )
+}
+
+fn result_text(block: &Value) -> String {
+    match block.get("content") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .find_map(|p| p.get("text").and_then(|t| t.as_str()))
+            .unwrap_or("")
+            .to_string(),
+        _ => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_files_touched() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let lines = [
+            r#"{"type":"assistant","timestamp":"2026-03-01T10:00:00Z","message":{"content":[
+                {"type":"tool_use","id":"t1","name":"Edit","input":{"file_path":"/w/src/lib.rs","old_string":"a\nb\nc","new_string":"a\nB\nB2\nc"}},
+                {"type":"tool_use","id":"t2","name":"Write","input":{"file_path":"/w/src/new.rs","content":"x\ny\n"}},
+                {"type":"tool_use","id":"t3","name":"Read","input":{"file_path":"/w/README.md"}}]}}"#,
+            r#"{"type":"user","message":{"content":[
+                {"type":"tool_result","tool_use_id":"t1","content":"The file has been updated."},
+                {"type":"tool_result","tool_use_id":"t2","content":"File created successfully at: /w/src/new.rs"}]}}"#,
+            r#"{"type":"assistant","timestamp":"2026-03-01T10:05:00Z","message":{"content":[
+                {"type":"tool_use","id":"t4","name":"MultiEdit","input":{"file_path":"/w/src/lib.rs","edits":[{"old_string":"x","new_string":""},{"old_string":"y","new_string":"y\nz"}]}},
+                {"type":"tool_use","id":"t5","name":"Edit","input":{"file_path":"/w/src/main.rs","old_string":"q","new_string":"r"}}]}}"#,
+            r#"{"type":"user","message":{"content":[
+                {"type":"tool_result","tool_use_id":"t5","is_error":true,"content":"String to replace not found"}]}}"#,
+        ];
+        std::fs::write(&path, lines.map(|l| l.replace('\n', "")).join("\n")).unwrap();
+
+        let files = files_touched(&path);
+        assert_eq!(files.len(), 2);
+        let lib = &files[0];
+        assert_eq!(lib.display_path(Path::new("/w")), "src/lib.rs");
+        assert_eq!(lib.status, FileStatus::Modified);
+        assert_eq!((lib.additions, lib.deletions, lib.edits), (3, 2, 2));
+        assert_eq!(
+            lib.last_touched,
+            "2026-03-01T10:05:00Z".parse::<DateTime<Utc>>().ok()
+        );
+        let new = &files[1];
+        assert_eq!(new.display_path(Path::new("/elsewhere")), "/w/src/new.rs");
+        assert_eq!(new.status, FileStatus::Created);
+        assert_eq!((new.additions, new.deletions), (2, 0));
+
+        assert!(files_touched(&dir.path().join("missing.jsonl")).is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Repository chores offered from the session list, run in a session's cwd.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GitOp {
+    Fetch,
+    PullRebase,
+    /// Create the branch and switch to it.
+    CreateBranch(String),
+    Stash,
+    /// Commit every tracked change, then optionally push the branch and
+    /// open a pull request for it with `gh`.
+    Commit {
+        message: String,
+        pull_request: bool,
+    },
+}
+
+impl GitOp {
+    /// What the menu offers, in order. The branch name is asked for.
+    pub fn menu() -> [GitOp; 4] {
+        [
+            GitOp::Fetch,
+            GitOp::PullRebase,
+            GitOp::CreateBranch(String::new()),
+            GitOp::Stash,
+        ]
+    }
+
+    pub fn args(&self) -> Vec<&str> {
+        match self {
+            GitOp::Fetch => vec!["fetch", "--prune"],
+            GitOp::PullRebase => vec!["pull", "--rebase"],
+            GitOp::CreateBranch(name) => vec!["switch", "-c", name],
+            GitOp::Stash => vec!["stash", "push"],
+            GitOp::Commit { message, .. } => vec!["commit", "-a", "-m", message],
+        }
+    }
+
+    /// The commands `run` goes through, in order, as program and arguments.
+    pub fn commands(&self) -> Vec<(&'static str, Vec<&str>)> {
+        let mut commands = vec![("git", self.args())];
+        if let GitOp::Commit {
+            pull_request: true, ..
+        } = self
+        {
+            commands.push(("git", vec!["push", "-u", "origin", "HEAD"]));
+            commands.push(("gh", vec!["pr", "create", "--fill"]));
+        }
+        commands
+    }
+
+    /// The command as shown in the menu and in messages.
+    pub fn label(&self) -> String {
+        match self {
+            GitOp::CreateBranch(name) if name.is_empty() => "git switch -c <branch>".to_string(),
+            GitOp::Commit {
+                pull_request: true, ..
+            } => "git commit + gh pr create".to_string(),
+            GitOp::Commit { .. } => "git commit".to_string(),
+            op => format!("git {}", op.args().join(" ")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitOutcome {
+    pub success: bool,
+    /// The last line git printed, which is usually the one that matters.
+    pub summary: String,
+    /// Everything git printed, stdout then stderr.
+    pub output: String,
+}
+
+/// Runs `op`'s commands in `cwd` without a terminal, stopping at the
+/// first that fails: git and gh fail instead of prompting for credentials.
+pub fn run(op: &GitOp, cwd: &Path) -> GitOutcome {
+    let mut output = String::new();
+    for (program, args) in op.commands() {
+        let result = Command::new(program)
+            .args(args)
+            .current_dir(cwd)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .env("GH_PROMPT_DISABLED", "1")
+            .stdin(Stdio::null())
+            .output();
+        match result {
+            Ok(out) => {
+                let step = format!(
+                    "{}{}",
+                    String::from_utf8_lossy(&out.stdout),
+                    String::from_utf8_lossy(&out.stderr)
+                );
+                output.push_str(&step);
+                if !out.status.success() {
+                    return GitOutcome {
+                        success: false,
+                        summary: summarize(&step, false),
+                        output,
+                    };
+                }
+            }
+            Err(e) => {
+                return GitOutcome {
+                    success: false,
+                    summary: format!("could not run {}: {}", program, e),
+                    output,
+                }
+            }
+        }
+    }
+    GitOutcome {
+        success: true,
+        summary: summarize(&output, true),
+        output,
+    }
+}
+
+/// Longest diff kept; the rest is cut off with a note.
+const MAX_DIFF_BYTES: usize = 2 * 1024 * 1024;
+
+/// Uncommitted changes in a working tree, staged or not, against HEAD.
+/// Untracked files are not in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkingDiff {
+    /// `git diff --stat` output.
+    pub stat: String,
+    pub diff: String,
+}
+
+impl WorkingDiff {
+    pub fn is_empty(&self) -> bool {
+        self.diff.trim().is_empty()
+    }
+}
+
+/// Runs `git diff --stat` and `git diff` against HEAD in `cwd`. A
+/// repository without commits yet is diffed against the index.
+pub fn working_diff(cwd: &Path) -> Result<WorkingDiff, String> {
+    let base = if output(cwd, &["rev-parse", "--verify", "-q", "HEAD"]).is_ok() {
+        vec!["HEAD"]
+    } else {
+        vec![]
+    };
+    let diff_args = |extra: &[&'static str]| {
+        let mut args = vec!["diff", "--no-color", "--no-ext-diff"];
+        args.extend(extra);
+        args.extend(&base);
+        args
+    };
+    let stat = output(cwd, &diff_args(&["--stat"]))?;
+    let mut diff = output(cwd, &diff_args(&[]))?;
+    if diff.len() > MAX_DIFF_BYTES {
+        let mut cut = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        diff.push_str("\n... (diff cut off at 2 MB)\n");
+    }
+    Ok(WorkingDiff { stat, diff })
+}
+
+/// The working tree `dir` is in: the nearest directory at or above it with
+/// a `.git` entry (a directory, or a file in linked worktrees).
+pub fn worktree_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join(".git").exists())
+        .map(Path::to_path_buf)
+}
+
+/// Stdout of a git command that must succeed, or what went wrong.
+fn output(cwd: &Path, args: &[&str]) -> Result<String, String> {
+    let out = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| format!("could not run git: {}", e))?;
+    if !out.status.success() {
+        return Err(summarize(&String::from_utf8_lossy(&out.stderr), false));
+    }
+    Ok(String::from_utf8_lossy(&out.stdout).into_owned())
+}
+
+/// The last non-empty line of `output`; progress lines overwritten with
+/// `\r` count only with their final state.
+fn summarize(output: &str, success: bool) -> String {
+    output
+        .lines()
+        .filter_map(|l| l.rsplit('\r').next())
+        .map(str::trim)
+        .rfind(|l| !l.is_empty())
+        .map(str::to_string)
+        .unwrap_or_else(|| if success { "done" } else { "failed" }.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let ok = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        assert!(ok, "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_summarize_takes_last_line() {
+        let out = "remote: Counting objects\rremote: Counting objects: 100%, done.\n\n";
+        assert_eq!(
+            summarize(out, true),
+            "remote: Counting objects: 100%, done."
+        );
+        assert_eq!(summarize("", true), "done");
+        assert_eq!(summarize("  \n", false), "failed");
+    }
+
+    #[test]
+    fn test_branch_and_stash() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "t@example.com"]);
+        git(repo, &["config", "user.name", "t"]);
+        std::fs::write(repo.join("a.txt"), "one").unwrap();
+        git(repo, &["add", "a.txt"]);
+        git(repo, &["commit", "-qm", "init"]);
+
+        let branch = run(&GitOp::CreateBranch("feat/x".into()), repo);
+        assert!(branch.success, "{}", branch.output);
+
+        std::fs::write(repo.join("a.txt"), "two").unwrap();
+        let stash = run(&GitOp::Stash, repo);
+        assert!(stash.success, "{}", stash.output);
+        assert_eq!(std::fs::read_to_string(repo.join("a.txt")).unwrap(), "one");
+
+        let fetch = run(&GitOp::Fetch, repo);
+        assert!(fetch.success, "{}", fetch.output);
+        let pull = run(&GitOp::PullRebase, repo);
+        assert!(!pull.success);
+        assert!(!pull.summary.is_empty());
+    }
+
+    #[test]
+    fn test_commit_tracked_changes() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "t@example.com"]);
+        git(repo, &["config", "user.name", "t"]);
+        std::fs::write(repo.join("a.txt"), "one\n").unwrap();
+        git(repo, &["add", "a.txt"]);
+        git(repo, &["commit", "-qm", "init"]);
+
+        std::fs::write(repo.join("a.txt"), "two\n").unwrap();
+        let op = GitOp::Commit {
+            message: "Change a".into(),
+            pull_request: false,
+        };
+        assert_eq!(op.label(), "git commit");
+        let commit = run(&op, repo);
+        assert!(commit.success, "{}", commit.output);
+        assert!(working_diff(repo).unwrap().is_empty());
+        assert_eq!(
+            output(repo, &["log", "-1", "--format=%s"]).unwrap(),
+            "Change a\n"
+        );
+
+        // Nothing left to commit stops before the push.
+        let pr = GitOp::Commit {
+            message: "Again".into(),
+            pull_request: true,
+        };
+        assert_eq!(pr.commands().len(), 3);
+        let again = run(&pr, repo);
+        assert!(!again.success);
+        assert!(!again.output.contains("origin"), "{}", again.output);
+    }
+
+    #[test]
+    fn test_working_diff() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path();
+        assert!(working_diff(repo).is_err());
+        git(repo, &["init", "-q"]);
+        git(repo, &["config", "user.email", "t@example.com"]);
+        git(repo, &["config", "user.name", "t"]);
+        std::fs::write(repo.join("a.txt"), "one\n").unwrap();
+        git(repo, &["add", "a.txt"]);
+        assert!(working_diff(repo).unwrap().is_empty());
+        git(repo, &["commit", "-qm", "init"]);
+        assert!(working_diff(repo).unwrap().is_empty());
+
+        std::fs::write(repo.join("a.txt"), "two\n").unwrap();
+        std::fs::write(repo.join("b.txt"), "new\n").unwrap();
+        git(repo, &["add", "b.txt"]);
+        let diff = working_diff(repo).unwrap();
+        assert!(diff.diff.contains("-one\n+two"));
+        assert!(diff.diff.contains("+new"));
+        assert!(diff.stat.contains("2 files changed"));
+    }
+}
@@ -0,0 +1,443 @@
+//! A handoff bundle: everything a colleague needs to take over a session,
+//! written to a directory and optionally packed into a tarball.
+
+use anyhow::{Context, Result};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::config::{scan_session_config, SessionConfig};
+use super::export::{export_stem, export_transcript, ExportFormat};
+use super::{Session, SessionDiscovery, TodoStatus};
+use crate::store::Store;
+
+/// `~/.c9s/handoffs/<project>-<short id>`, used when no path is given.
+pub fn default_handoff_dir(session: &Session) -> Option<PathBuf> {
+    crate::config_dir().map(|d| d.join("handoffs").join(export_stem(session)))
+}
+
+/// Writes `README.md` (summary, open todos, branch and configuration),
+/// `transcript.md`, the raw `transcript.jsonl` for `import_bundle` and, in
+/// a git repository, `changes.diff` with the uncommitted changes into `dir`. `cost` turns the estimated cost in USD
+/// into the text shown for it.
+pub fn write_handoff(
+    session: &Session,
+    transcript: &Path,
+    dir: &Path,
+    cost: &dyn Fn(f64) -> String,
+) -> Result<()> {
+    std::fs::create_dir_all(dir).with_context(|| format!("creating {}", dir.display()))?;
+    let doc = export_transcript(session, transcript, ExportFormat::Markdown, cost)?;
+    std::fs::write(dir.join("transcript.md"), doc)?;
+    std::fs::copy(transcript, dir.join(TRANSCRIPT))?;
+
+    let git = GitState::read(&session.cwd);
+    if let Some(ref git) = git {
+        std::fs::write(dir.join("changes.diff"), &git.diff)?;
+    }
+    let config = scan_session_config(&session.cwd);
+    std::fs::write(
+        dir.join("README.md"),
+        render_readme(
+            session,
+            git.as_ref(),
+            &config,
+            &cost(session.estimated_cost_usd()),
+        ),
+    )?;
+    Ok(())
+}
+
+/// Packs `dir` into `<dir>.tar.gz` next to it and removes the directory.
+pub fn pack(dir: &Path) -> Result<PathBuf> {
+    let name = dir
+        .file_name()
+        .ok_or_else(|| anyhow::anyhow!("{} has no name", dir.display()))?;
+    let parent = dir.parent().unwrap_or(Path::new("."));
+    let archive = dir.with_file_name(format!("{}.tar.gz", name.to_string_lossy()));
+    let output = Command::new("tar")
+        .arg("-czf")
+        .arg(&archive)
+        .arg("-C")
+        .arg(parent)
+        .arg(name)
+        .output()
+        .context("running tar")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "tar failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    std::fs::remove_dir_all(dir)?;
+    Ok(archive)
+}
+
+/// The raw transcript in a bundle.
+const TRANSCRIPT: &str = "transcript.jsonl";
+
+/// `~/.c9s/imported/<id>.jsonl`: where an imported session's transcript is
+/// kept.
+pub fn imported_transcript_path(id: &str) -> Option<PathBuf> {
+    crate::config_dir().map(|d| d.join("imported").join(format!("{}.jsonl", id)))
+}
+
+/// Imports the session in a handoff bundle (directory or `.tar.gz`) or a
+/// bare transcript: copies its transcript to `~/.c9s/imported/` and
+/// records it in `store`. Importing it again replaces the copy.
+pub fn import_bundle(bundle: &Path, store: &Store) -> Result<Session> {
+    let name = bundle.to_string_lossy();
+    let scratch = if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+        let dir = std::env::temp_dir().join(format!("c9s-import-{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let output = Command::new("tar")
+            .arg("-xzf")
+            .arg(bundle)
+            .arg("-C")
+            .arg(&dir)
+            .output()
+            .context("running tar")?;
+        if !output.status.success() {
+            let _ = std::fs::remove_dir_all(&dir);
+            anyhow::bail!(
+                "tar failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Some(dir)
+    } else {
+        None
+    };
+    let result = crate::config_dir()
+        .ok_or_else(|| anyhow::anyhow!("no home directory"))
+        .and_then(|d| {
+            import_from(
+                scratch.as_deref().unwrap_or(bundle),
+                &name,
+                store,
+                &d.join("imported"),
+            )
+        });
+    if let Some(dir) = scratch {
+        let _ = std::fs::remove_dir_all(dir);
+    }
+    result
+}
+
+/// Copies the transcript at or in `path` into `dir` as `<id>.jsonl`.
+fn import_from(path: &Path, source: &str, store: &Store, dir: &Path) -> Result<Session> {
+    let transcript =
+        find_transcript(path).ok_or_else(|| anyhow::anyhow!("no {} in {}", TRANSCRIPT, source))?;
+    let mut session = SessionDiscovery::read_transcript(&transcript);
+    if session.message_count == 0 {
+        anyhow::bail!("{} holds no session", transcript.display());
+    }
+    session.imported = true;
+    std::fs::create_dir_all(dir)?;
+    std::fs::copy(&transcript, dir.join(format!("{}.jsonl", session.id)))?;
+    store.add_imported_session(&session, source)?;
+    Ok(session)
+}
+
+/// The transcript in a bundle directory, one level down as a tarball
+/// unpacks it, or `path` itself.
+fn find_transcript(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+    let direct = path.join(TRANSCRIPT);
+    if direct.is_file() {
+        return Some(direct);
+    }
+    std::fs::read_dir(path)
+        .ok()?
+        .flatten()
+        .map(|e| e.path().join(TRANSCRIPT))
+        .find(|p| p.is_file())
+}
+
+/// The imported sessions in `store`, read from their copied transcripts.
+pub fn load_imported(store: &Store, skip: &dyn Fn(&str) -> bool) -> Vec<Session> {
+    store
+        .imported_session_ids()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| !skip(id))
+        .filter_map(|id| imported_transcript_path(&id).filter(|p| p.exists()))
+        .map(|path| Session {
+            imported: true,
+            ..SessionDiscovery::read_transcript(&path)
+        })
+        .collect()
+}
+
+/// The repository as the session left it.
+struct GitState {
+    head: String,
+    /// `git diff HEAD`: staged and unstaged changes.
+    diff: String,
+    stat: String,
+    untracked: Vec<String>,
+}
+
+impl GitState {
+    fn read(cwd: &Path) -> Option<Self> {
+        let git = |args: &[&str]| -> Option<String> {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(cwd)
+                .output()
+                .ok()?;
+            output
+                .status
+                .success()
+                .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+        };
+        let head = git(&["log", "-1", "--format=%h %s"])?;
+        Some(Self {
+            head: head.trim().to_string(),
+            diff: git(&["diff", "HEAD"]).unwrap_or_default(),
+            stat: git(&["diff", "HEAD", "--shortstat"])
+                .unwrap_or_default()
+                .trim()
+                .to_string(),
+            untracked: git(&["ls-files", "--others", "--exclude-standard"])
+                .unwrap_or_default()
+                .lines()
+                .map(str::to_string)
+                .collect(),
+        })
+    }
+}
+
+fn render_readme(
+    session: &Session,
+    git: Option<&GitState>,
+    config: &SessionConfig,
+    cost: &str,
+) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "# Handoff: {}", session.project_name);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "- Session: `{}` ({})", session.id, session.status);
+    let _ = writeln!(out, "- Directory: `{}`", session.cwd.display());
+    if let Some(ref model) = session.model {
+        let _ = writeln!(out, "- Model: {}", model);
+    }
+    let _ = writeln!(
+        out,
+        "- Started: {}, last active {}",
+        session.started_at.format("%Y-%m-%d %H:%M UTC"),
+        session.last_activity.format("%Y-%m-%d %H:%M UTC")
+    );
+    let _ = writeln!(out, "- Estimated cost: {}", cost);
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Continue it with:");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "    cd {} && claude --resume {}",
+        session.cwd.display(),
+        session.id
+    );
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Open todos");
+    let _ = writeln!(out);
+    let open: Vec<_> = session
+        .todos
+        .iter()
+        .filter(|t| t.status != TodoStatus::Completed)
+        .collect();
+    if open.is_empty() {
+        let _ = writeln!(out, "None.");
+    }
+    for todo in open {
+        let marker = if todo.status == TodoStatus::InProgress {
+            " (in progress)"
+        } else {
+            ""
+        };
+        let _ = writeln!(out, "- [ ] {}{}", todo.content, marker);
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Branch");
+    let _ = writeln!(out);
+    match git {
+        Some(git) => {
+            let _ = writeln!(
+                out,
+                "`{}` at {}.",
+                session.git_branch.as_deref().unwrap_or("HEAD"),
+                git.head
+            );
+            let _ = writeln!(out);
+            if git.stat.is_empty() {
+                let _ = writeln!(out, "No uncommitted changes.");
+            } else {
+                let _ = writeln!(out, "Uncommitted: {}; see `changes.diff`.", git.stat);
+            }
+            if !git.untracked.is_empty() {
+                let _ = writeln!(out);
+                let _ = writeln!(out, "Untracked files:");
+                let _ = writeln!(out);
+                for file in &git.untracked {
+                    let _ = writeln!(out, "- `{}`", file);
+                }
+            }
+        }
+        None => {
+            let _ = writeln!(out, "Not a git repository.");
+        }
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Configuration");
+    let _ = writeln!(out);
+    let yes_no = |b: bool| if b { "yes" } else { "no" };
+    let _ = writeln!(
+        out,
+        "- CLAUDE.md: project {}, global {}",
+        yes_no(config.project_claude_md),
+        yes_no(config.global_claude_md)
+    );
+    let list = |names: Vec<String>| {
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    };
+    let rules = config
+        .project_rules
+        .iter()
+        .chain(&config.global_rules)
+        .map(|e| format!("{}/{}", e.category, e.name))
+        .collect();
+    let _ = writeln!(out, "- Rules: {}", list(rules));
+    let _ = writeln!(out, "- Commands: {}", list(config.project_commands.clone()));
+    let _ = writeln!(out, "- Skills: {}", list(config.global_skills.clone()));
+    let _ = writeln!(
+        out,
+        "- MCP servers: {}",
+        list(config.mcp_servers.iter().map(|s| s.name.clone()).collect())
+    );
+    if let Some(ref mode) = session.permission_mode {
+        let _ = writeln!(out, "- Permission mode: {}", mode);
+    }
+
+    let _ = writeln!(out);
+    let _ = writeln!(out, "## Files");
+    let _ = writeln!(out);
+    let _ = writeln!(
+        out,
+        "- `transcript.md`: the conversation, tool calls collapsed"
+    );
+    let _ = writeln!(
+        out,
+        "- `transcript.jsonl`: the raw transcript; `c9s import` on this bundle makes it browsable"
+    );
+    if git.is_some() {
+        let _ = writeln!(out, "- `changes.diff`: uncommitted changes against HEAD");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{SessionStatus, TodoItem};
+
+    fn git(dir: &Path, args: &[&str]) {
+        let ok = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        assert!(ok, "git {:?} failed", args);
+    }
+
+    fn session(cwd: &Path) -> Session {
+        let mut session = Session::fixture("abc12345-0000", cwd);
+        session.project_name = "webshop".to_string();
+        session.git_branch = Some("feature/retry".to_string());
+        session.model = Some("claude-sonnet-4-20250514".to_string());
+        session.status = SessionStatus::Idle;
+        session.message_count = 2;
+        session.todos = vec![
+            TodoItem {
+                content: "Write the retry loop".to_string(),
+                status: TodoStatus::Completed,
+                active_form: None,
+            },
+            TodoItem {
+                content: "Add a backoff test".to_string(),
+                status: TodoStatus::InProgress,
+                active_form: None,
+            },
+        ];
+        session
+    }
+
+    #[test]
+    fn test_write_handoff() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let repo = dir.path().join("webshop");
+        std::fs::create_dir(&repo).unwrap();
+        git(&repo, &["init", "-q"]);
+        git(&repo, &["config", "user.email", "t@example.com"]);
+        git(&repo, &["config", "user.name", "t"]);
+        std::fs::write(repo.join("a.txt"), "one\n").unwrap();
+        git(&repo, &["add", "a.txt"]);
+        git(&repo, &["commit", "-qm", "first"]);
+        std::fs::write(repo.join("a.txt"), "two\n").unwrap();
+        std::fs::write(repo.join("new.txt"), "").unwrap();
+
+        let transcript = dir.path().join("t.jsonl");
+        let line = serde_json::json!({
+            "type": "user", "sessionId": "abc12345-0000", "cwd": repo,
+            "timestamp": "2026-03-01T09:00:00Z",
+            "message": {"role": "user", "content": "add retries"},
+        });
+        std::fs::write(&transcript, format!("{}\n", line)).unwrap();
+
+        let out = dir.path().join("handoff");
+        write_handoff(&session(&repo), &transcript, &out, &|c| {
+            format!("${:.2}", c)
+        })
+        .unwrap();
+
+        let readme = std::fs::read_to_string(out.join("README.md")).unwrap();
+        assert!(readme.contains("claude --resume abc12345-0000"));
+        assert!(readme.contains("- [ ] Add a backoff test (in progress)\n"));
+        assert!(!readme.contains("Write the retry loop"));
+        assert!(readme.contains("at ") && readme.contains(" first."));
+        assert!(readme.contains("1 file changed"));
+        assert!(readme.contains("- `new.txt`"));
+        let diff = std::fs::read_to_string(out.join("changes.diff")).unwrap();
+        assert!(diff.contains("-one\n+two\n"));
+        assert!(std::fs::read_to_string(out.join("transcript.md"))
+            .unwrap()
+            .contains("add retries"));
+
+        let store = Store::in_memory().unwrap();
+        let imported = dir.path().join("imported");
+        let session = import_from(&out, "handoff", &store, &imported).unwrap();
+        assert!(session.imported);
+        assert_eq!(session.id, "abc12345-0000");
+        assert!(imported.join("abc12345-0000.jsonl").is_file());
+        assert_eq!(store.imported_session_ids().unwrap(), ["abc12345-0000"]);
+        assert!(import_from(&repo, "repo", &store, &imported).is_err());
+
+        if Command::new("tar").arg("--version").output().is_ok() {
+            let archive = pack(&out).unwrap();
+            assert_eq!(archive, dir.path().join("handoff.tar.gz"));
+            assert!(archive.is_file() && !out.exists());
+        }
+    }
+}
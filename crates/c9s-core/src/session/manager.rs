@@ -0,0 +1,24 @@
+use std::process::Command;
+
+pub struct SessionManager;
+
+impl SessionManager {
+    pub fn is_claude_installed() -> bool {
+        Command::new("claude")
+            .arg("--version")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    }
+
+    /// Version reported by `claude --version`, e.g. `2.0.14` from
+    /// `2.0.14 (Claude Code)`.
+    pub fn installed_version() -> Option<String> {
+        let output = Command::new("claude").arg("--version").output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        stdout.split_whitespace().next().map(String::from)
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+const PROBE_TIMEOUT: Duration = Duration::from_secs(8);
+const SLOW_THRESHOLD_MS: u64 = 2000;
+
+const INITIALIZE_REQUEST: &str = r#"{"jsonrpc":"2.0","id":1,"method":"initialize","params":{"protocolVersion":"2024-11-05","capabilities":{},"clientInfo":{"name":"c9s","version":"0.1"}}}"#;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpTransport {
+    Stdio {
+        command: String,
+        args: Vec<String>,
+        env: HashMap<String, String>,
+    },
+    Http {
+        url: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct McpServer {
+    pub name: String,
+    pub transport: McpTransport,
+    /// File the server definition was read from, used for previewing.
+    pub source: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum McpHealth {
+    Checking,
+    Ok(u64),
+    Slow(u64),
+    Unreachable(String),
+}
+
+impl McpHealth {
+    pub fn label(&self) -> String {
+        match self {
+            Self::Checking => "checking...".to_string(),
+            Self::Ok(ms) => format!("ok {}ms", ms),
+            Self::Slow(ms) => format!("slow {}ms", ms),
+            Self::Unreachable(reason) => format!("unreachable: {}", reason),
+        }
+    }
+
+    fn from_elapsed(ms: u64) -> Self {
+        if ms >= SLOW_THRESHOLD_MS {
+            Self::Slow(ms)
+        } else {
+            Self::Ok(ms)
+        }
+    }
+}
+
+/// Collects MCP servers visible to a session in `cwd`: project `.mcp.json`,
+/// then the user-level and per-project entries in `~/.claude.json`.
+/// Later definitions with the same name are ignored.
+pub fn scan_mcp_servers(cwd: &Path) -> Vec<McpServer> {
+    let mut servers = Vec::new();
+
+    let project_file = cwd.join(".mcp.json");
+    if let Some(json) = read_json(&project_file) {
+        collect_servers(json.get("mcpServers"), &project_file, &mut servers);
+    }
+
+    if let Some(home) = dirs::home_dir() {
+        let user_file = home.join(".claude.json");
+        if let Some(json) = read_json(&user_file) {
+            let project_entry = json
+                .get("projects")
+                .and_then(|p| p.get(cwd.to_string_lossy().as_ref()));
+            collect_servers(
+                project_entry.and_then(|p| p.get("mcpServers")),
+                &user_file,
+                &mut servers,
+            );
+            collect_servers(json.get("mcpServers"), &user_file, &mut servers);
+        }
+    }
+
+    servers
+}
+
+fn read_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn collect_servers(value: Option<&serde_json::Value>, source: &Path, out: &mut Vec<McpServer>) {
+    let Some(map) = value.and_then(|v| v.as_object()) else {
+        return;
+    };
+    let mut names: Vec<&String> = map.keys().collect();
+    names.sort();
+    for name in names {
+        if out.iter().any(|s| &s.name == name) {
+            continue;
+        }
+        if let Some(transport) = parse_transport(&map[name]) {
+            out.push(McpServer {
+                name: name.clone(),
+                transport,
+                source: source.to_path_buf(),
+            });
+        }
+    }
+}
+
+fn parse_transport(def: &serde_json::Value) -> Option<McpTransport> {
+    if let Some(url) = def.get("url").and_then(|u| u.as_str()) {
+        return Some(McpTransport::Http {
+            url: url.to_string(),
+        });
+    }
+    let command = def.get("command")?.as_str()?.to_string();
+    let args = def
+        .get("args")
+        .and_then(|a| a.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default();
+    let env = def
+        .get("env")
+        .and_then(|e| e.as_object())
+        .map(|e| {
+            e.iter()
+                .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(McpTransport::Stdio { command, args, env })
+}
+
+/// Probes a server by sending an MCP `initialize` request. Blocking; call
+/// from a background thread.
+pub fn check_server(server: &McpServer, cwd: &Path) -> McpHealth {
+    match &server.transport {
+        McpTransport::Stdio { command, args, env } => probe_stdio(command, args, env, cwd),
+        McpTransport::Http { url } => probe_http(url),
+    }
+}
+
+fn probe_stdio(
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+    cwd: &Path,
+) -> McpHealth {
+    let start = Instant::now();
+    let mut child = match Command::new(command)
+        .args(args)
+        .envs(env)
+        .current_dir(cwd)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => return McpHealth::Unreachable(format!("spawn failed: {}", e)),
+    };
+
+    // Held open until the probe finishes; some servers exit on stdin EOF.
+    let mut stdin = child.stdin.take();
+    if let Some(ref mut pipe) = stdin {
+        let _ = writeln!(pipe, "{}", INITIALIZE_REQUEST);
+        let _ = pipe.flush();
+    }
+
+    let (tx, rx) = mpsc::channel();
+    if let Some(stdout) = child.stdout.take() {
+        std::thread::spawn(move || {
+            let reader = BufReader::new(stdout);
+            for line in reader.lines() {
+                let Ok(line) = line else { break };
+                if is_jsonrpc_response(&line) {
+                    let _ = tx.send(true);
+                    return;
+                }
+            }
+            let _ = tx.send(false);
+        });
+    }
+
+    let result = match rx.recv_timeout(PROBE_TIMEOUT) {
+        Ok(true) => McpHealth::from_elapsed(start.elapsed().as_millis() as u64),
+        Ok(false) => McpHealth::Unreachable("exited without responding".to_string()),
+        Err(_) => McpHealth::Unreachable("no response".to_string()),
+    };
+
+    drop(stdin);
+    let _ = child.kill();
+    let _ = child.wait();
+    result
+}
+
+fn is_jsonrpc_response(line: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(line.trim())
+        .map(|v| {
+            v.get("jsonrpc").is_some() && (v.get("result").is_some() || v.get("error").is_some())
+        })
+        .unwrap_or(false)
+}
+
+fn probe_http(url: &str) -> McpHealth {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(PROBE_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .new_agent();
+
+    let start = Instant::now();
+    let resp = agent
+        .post(url)
+        .header("User-Agent", "c9s/0.1")
+        .header("Accept", "application/json, text/event-stream")
+        .header("Content-Type", "application/json")
+        .send(INITIALIZE_REQUEST.as_bytes());
+
+    match resp {
+        // Auth-protected servers answer 401/403; they are up, just not probeable.
+        Ok(r) if r.status().as_u16() < 500 => {
+            McpHealth::from_elapsed(start.elapsed().as_millis() as u64)
+        }
+        Ok(r) => McpHealth::Unreachable(format!("HTTP {}", r.status().as_u16())),
+        Err(e) => McpHealth::Unreachable(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stdio_and_http_servers() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(".mcp.json"),
+            r#"{"mcpServers":{
+                "fs":{"command":"npx","args":["-y","server-fs"],"env":{"A":"1"}},
+                "remote":{"type":"http","url":"http://localhost:9/mcp"}
+            }}"#,
+        )
+        .unwrap();
+
+        let mut servers = Vec::new();
+        let path = dir.path().join(".mcp.json");
+        collect_servers(
+            read_json(&path).as_ref().and_then(|j| j.get("mcpServers")),
+            &path,
+            &mut servers,
+        );
+
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].name, "fs");
+        match &servers[0].transport {
+            McpTransport::Stdio { command, args, env } => {
+                assert_eq!(command, "npx");
+                assert_eq!(args, &vec!["-y".to_string(), "server-fs".to_string()]);
+                assert_eq!(env.get("A").map(String::as_str), Some("1"));
+            }
+            other => panic!("expected stdio, got {:?}", other),
+        }
+        assert_eq!(
+            servers[1].transport,
+            McpTransport::Http {
+                url: "http://localhost:9/mcp".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_duplicate_names_keep_first() {
+        let first = serde_json::json!({"a": {"command": "one"}});
+        let second = serde_json::json!({"a": {"command": "two"}});
+        let mut servers = Vec::new();
+        collect_servers(Some(&first), Path::new("x"), &mut servers);
+        collect_servers(Some(&second), Path::new("y"), &mut servers);
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].source, PathBuf::from("x"));
+    }
+
+    #[test]
+    fn test_jsonrpc_response_detection() {
+        assert!(is_jsonrpc_response(
+            r#"{"jsonrpc":"2.0","id":1,"result":{}}"#
+        ));
+        assert!(is_jsonrpc_response(
+            r#"{"jsonrpc":"2.0","id":1,"error":{"code":-1}}"#
+        ));
+        assert!(!is_jsonrpc_response("starting server..."));
+        assert!(!is_jsonrpc_response(r#"{"jsonrpc":"2.0","method":"log"}"#));
+    }
+
+    #[test]
+    fn test_missing_command_is_unreachable() {
+        let health = probe_stdio(
+            "c9s-definitely-not-a-real-binary",
+            &[],
+            &HashMap::new(),
+            Path::new("/"),
+        );
+        assert!(matches!(health, McpHealth::Unreachable(_)));
+    }
+
+    #[test]
+    fn test_slow_threshold() {
+        assert_eq!(McpHealth::from_elapsed(150), McpHealth::Ok(150));
+        assert_eq!(McpHealth::from_elapsed(2500), McpHealth::Slow(2500));
+    }
+}
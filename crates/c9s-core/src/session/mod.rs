@@ -0,0 +1,318 @@
+pub mod config;
+mod discovery;
+pub mod env;
+pub mod export;
+pub mod files;
+pub mod git;
+pub mod handoff;
+mod manager;
+pub mod mcp;
+pub mod resources;
+pub mod timeline;
+pub mod tty;
+pub mod version;
+
+pub use config::SessionConfig;
+pub use discovery::{
+    list_session_files, FileDiagnostics, JsonlStats, SessionDiscovery, SessionFile, StatsEntry,
+    STATS_VERSION,
+};
+pub use manager::SessionManager;
+
+use crate::clock::{Clock, SystemClock};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionStatus {
+    Active,
+    Idle,
+    Thinking,
+    Dead,
+}
+
+impl SessionStatus {
+    pub fn label(&self) -> &str {
+        match self {
+            Self::Active => "Active",
+            Self::Idle => "Idle",
+            Self::Thinking => "Thinking",
+            Self::Dead => "Dead",
+        }
+    }
+}
+
+impl std::fmt::Display for SessionStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// One entry of the todo list claude keeps with its TodoWrite tool.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TodoItem {
+    pub content: String,
+    pub status: TodoStatus,
+    /// Present-tense form shown while the item is in progress.
+    #[serde(default, rename = "activeForm")]
+    pub active_form: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoStatus {
+    Pending,
+    InProgress,
+    Completed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Session {
+    pub id: String,
+    pub pid: Option<u32>,
+    pub cwd: PathBuf,
+    pub project_name: String,
+    pub git_branch: Option<String>,
+    pub model: Option<String>,
+    pub status: SessionStatus,
+    pub started_at: DateTime<Utc>,
+    pub last_activity: DateTime<Utc>,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub message_count: u32,
+    pub tool_call_count: u32,
+    pub claude_version: Option<String>,
+    pub permission_mode: Option<String>,
+    pub plan_slugs: Vec<String>,
+    pub compaction_count: u32,
+    pub hook_run_count: u32,
+    pub hook_error_count: u32,
+    /// Session this one was resumed from, if any.
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    /// Transcript of a subagent spawned by `parent_id`; not resumable.
+    #[serde(default)]
+    pub is_subagent: bool,
+    /// Median and 95th percentile time to first assistant reply per turn.
+    #[serde(default)]
+    pub latency_p50_ms: Option<u32>,
+    #[serde(default)]
+    pub latency_p95_ms: Option<u32>,
+    /// The transcript was too large to read whole: latencies come from
+    /// its head and tail only.
+    #[serde(default)]
+    pub stats_sampled: bool,
+    #[serde(default)]
+    pub api_error_count: u32,
+    /// Turns aborted by the user (Esc).
+    #[serde(default)]
+    pub interrupt_count: u32,
+    #[serde(default)]
+    pub tool_error_count: u32,
+    #[serde(default)]
+    pub last_error_at: Option<DateTime<Utc>>,
+    /// Transcript lines that were not valid JSON and were skipped.
+    #[serde(default)]
+    pub parse_errors: u32,
+    /// The transcript ends in a line still being written.
+    #[serde(default)]
+    pub partial_line: bool,
+    /// The most recent todo list written in the transcript.
+    #[serde(default)]
+    pub todos: Vec<TodoItem>,
+    /// Tokens used per minute during the transcript's last usage window,
+    /// cache reads left out.
+    #[serde(default)]
+    pub window_tokens: Vec<(DateTime<Utc>, u64)>,
+    /// Imported from another machine's handoff bundle: browsable from the
+    /// copy under `~/.c9s/imported/`, but not resumable here.
+    #[serde(default)]
+    pub imported: bool,
+}
+
+/// Totals across the subagents spawned by one session.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SubagentRollup {
+    pub count: usize,
+    pub tokens: u64,
+    pub cost: f64,
+}
+
+impl Session {
+    /// A dead session in `cwd` with nothing recorded yet, named after the
+    /// directory, for tests to fill in the fields they care about.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn fixture(id: &str, cwd: impl Into<PathBuf>) -> Self {
+        use chrono::TimeZone;
+        let cwd = cwd.into();
+        let started_at = Utc.with_ymd_and_hms(2026, 3, 1, 9, 0, 0).unwrap();
+        Self {
+            id: id.to_string(),
+            pid: None,
+            project_name: cwd
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            cwd,
+            git_branch: None,
+            model: None,
+            status: SessionStatus::Dead,
+            started_at,
+            last_activity: started_at + chrono::Duration::hours(1),
+            input_tokens: 0,
+            output_tokens: 0,
+            cache_read_tokens: 0,
+            cache_write_tokens: 0,
+            message_count: 0,
+            tool_call_count: 0,
+            claude_version: None,
+            permission_mode: None,
+            plan_slugs: Vec::new(),
+            compaction_count: 0,
+            hook_run_count: 0,
+            hook_error_count: 0,
+            parent_id: None,
+            is_subagent: false,
+            latency_p50_ms: None,
+            latency_p95_ms: None,
+            stats_sampled: false,
+            api_error_count: 0,
+            interrupt_count: 0,
+            tool_error_count: 0,
+            last_error_at: None,
+            parse_errors: 0,
+            partial_line: false,
+            todos: Vec::new(),
+            window_tokens: Vec::new(),
+            imported: false,
+        }
+    }
+
+    /// Todo items not yet completed.
+    pub fn open_todos(&self) -> usize {
+        self.todos
+            .iter()
+            .filter(|t| t.status != TodoStatus::Completed)
+            .count()
+    }
+
+    pub fn estimated_cost_usd(&self) -> f64 {
+        let model = self.model.as_deref().unwrap_or("");
+        let (input_price, output_price) = model_pricing(model);
+        let cache_read_price = input_price * 0.1;
+        let cache_write_price = input_price * 0.25;
+
+        (self.input_tokens as f64 * input_price
+            + self.output_tokens as f64 * output_price
+            + self.cache_read_tokens as f64 * cache_read_price
+            + self.cache_write_tokens as f64 * cache_write_price)
+            / 1_000_000.0
+    }
+
+    pub fn error_count(&self) -> u32 {
+        self.api_error_count + self.interrupt_count + self.tool_error_count
+    }
+
+    /// Whether any error or interrupt happened within the last `window`.
+    pub fn has_recent_errors(&self, window: chrono::Duration) -> bool {
+        self.last_error_at
+            .is_some_and(|t| Utc::now().signed_duration_since(t) <= window)
+    }
+
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens + self.cache_read_tokens + self.cache_write_tokens
+    }
+
+    pub fn duration_display(&self) -> String {
+        let duration = self.last_activity.signed_duration_since(self.started_at);
+        let hours = duration.num_hours();
+        let minutes = duration.num_minutes() % 60;
+        if hours > 0 {
+            format!("{}h {}m", hours, minutes)
+        } else {
+            format!("{}m", minutes)
+        }
+    }
+
+    pub fn last_activity_display(&self) -> String {
+        self.last_activity_display_at(&SystemClock)
+    }
+
+    pub fn last_activity_display_at(&self, clock: &dyn Clock) -> String {
+        let diff = clock.now().signed_duration_since(self.last_activity);
+        let secs = diff.num_seconds();
+        if secs < 60 {
+            format!("{}s ago", secs)
+        } else if secs < 3600 {
+            format!("{}m ago", secs / 60)
+        } else if secs < 86400 {
+            format!("{}h ago", secs / 3600)
+        } else {
+            format!("{}d ago", secs / 86400)
+        }
+    }
+}
+
+fn model_pricing(model: &str) -> (f64, f64) {
+    if model.contains("opus") {
+        (15.0, 75.0)
+    } else if model.contains("haiku") {
+        (0.80, 4.0)
+    } else {
+        (3.0, 15.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_session(
+        model: &str,
+        input: u64,
+        output: u64,
+        cache_read: u64,
+        cache_write: u64,
+    ) -> Session {
+        let mut session = Session::fixture("test", "/tmp");
+        session.model = Some(model.to_string());
+        session.input_tokens = input;
+        session.output_tokens = output;
+        session.cache_read_tokens = cache_read;
+        session.cache_write_tokens = cache_write;
+        session
+    }
+
+    #[test]
+    fn test_estimated_cost() {
+        let session = make_session("claude-sonnet-4-20250514", 1_000_000, 100_000, 0, 0);
+        let cost = session.estimated_cost_usd();
+        let expected = (1_000_000.0 * 3.0 + 100_000.0 * 15.0) / 1_000_000.0;
+        assert!((cost - expected).abs() < 0.001);
+
+        let opus = make_session("claude-opus-4-20250514", 1_000_000, 100_000, 0, 0);
+        let opus_cost = opus.estimated_cost_usd();
+        assert!(opus_cost > cost);
+    }
+
+    #[test]
+    fn test_last_activity_display() {
+        let clock = crate::clock::MockClock::new();
+        let mut session = make_session("claude-sonnet-4-20250514", 0, 0, 0, 0);
+        session.last_activity = clock.now();
+
+        let steps = [
+            (59, "59s ago"),
+            (1, "1m ago"),
+            (3540, "1h ago"),
+            (82800, "1d ago"),
+        ];
+        for (secs, expected) in steps {
+            clock.advance(std::time::Duration::from_secs(secs));
+            assert_eq!(session.last_activity_display_at(clock.as_ref()), expected);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+//! CPU and memory use of live sessions. Each claude process is counted
+//! together with everything it started (tool commands, MCP servers), so a
+//! runaway child shows up against the session that spawned it.
+
+use std::collections::HashMap;
+use std::process::Command;
+use std::time::Instant;
+
+/// One session's process tree at the last sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessUsage {
+    /// Share of one core since the previous sample; unknown on the first.
+    pub cpu_percent: Option<f64>,
+    pub rss_bytes: u64,
+}
+
+/// A row of `ps`: pid, parent, CPU seconds used so far and resident KiB.
+#[derive(Debug, Clone, PartialEq)]
+struct PsRow {
+    pid: u32,
+    ppid: u32,
+    cpu_secs: f64,
+    rss_kib: u64,
+}
+
+/// Keeps each tree's CPU time between samples, to turn it into a rate.
+#[derive(Default)]
+pub struct ResourceSampler {
+    last: HashMap<u32, f64>,
+    last_at: Option<Instant>,
+}
+
+impl ResourceSampler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Samples the trees under `pids` with one `ps` call. Empty if `ps`
+    /// can't be run.
+    pub fn sample(&mut self, pids: &[u32]) -> HashMap<u32, ProcessUsage> {
+        if pids.is_empty() {
+            self.last.clear();
+            return HashMap::new();
+        }
+        let Ok(output) = Command::new("ps")
+            .args(["-A", "-o", "pid=,ppid=,time=,rss="])
+            .output()
+        else {
+            return HashMap::new();
+        };
+        let rows = parse_ps(&String::from_utf8_lossy(&output.stdout));
+        self.update(&rows, pids, Instant::now())
+    }
+
+    fn update(&mut self, rows: &[PsRow], pids: &[u32], now: Instant) -> HashMap<u32, ProcessUsage> {
+        let mut children: HashMap<u32, Vec<&PsRow>> = HashMap::new();
+        for row in rows {
+            children.entry(row.ppid).or_default().push(row);
+        }
+        let elapsed = self.last_at.map(|t| now.duration_since(t).as_secs_f64());
+        let mut usage = HashMap::new();
+        let mut cpu_now = HashMap::new();
+        for &pid in pids {
+            let Some(root) = rows.iter().find(|r| r.pid == pid) else {
+                continue;
+            };
+            let (mut cpu_secs, mut rss_kib) = (0.0, 0);
+            let mut stack = vec![root];
+            while let Some(row) = stack.pop() {
+                cpu_secs += row.cpu_secs;
+                rss_kib += row.rss_kib;
+                // pid 0's children would be the whole table.
+                if row.pid != 0 {
+                    stack.extend(children.get(&row.pid).into_iter().flatten());
+                }
+            }
+            let cpu_percent = match (self.last.get(&pid), elapsed) {
+                (Some(before), Some(secs)) if secs > 0.0 => {
+                    Some(((cpu_secs - before).max(0.0) / secs) * 100.0)
+                }
+                _ => None,
+            };
+            cpu_now.insert(pid, cpu_secs);
+            usage.insert(
+                pid,
+                ProcessUsage {
+                    cpu_percent,
+                    rss_bytes: rss_kib * 1024,
+                },
+            );
+        }
+        self.last = cpu_now;
+        self.last_at = Some(now);
+        usage
+    }
+}
+
+fn parse_ps(output: &str) -> Vec<PsRow> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            Some(PsRow {
+                pid: parts.next()?.parse().ok()?,
+                ppid: parts.next()?.parse().ok()?,
+                cpu_secs: parse_cpu_time(parts.next()?)?,
+                rss_kib: parts.next()?.parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+/// `ps` CPU time: `[dd-]hh:mm:ss` on Linux, `mm:ss.cc` on macOS.
+fn parse_cpu_time(time: &str) -> Option<f64> {
+    let (days, rest) = match time.split_once('-') {
+        Some((d, r)) => (d.parse::<f64>().ok()?, r),
+        None => (0.0, time),
+    };
+    let mut secs = 0.0;
+    for part in rest.split(':') {
+        secs = secs * 60.0 + part.parse::<f64>().ok()?;
+    }
+    Some(days * 86_400.0 + secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_parse_cpu_time() {
+        assert_eq!(parse_cpu_time("00:01:05"), Some(65.0));
+        assert_eq!(parse_cpu_time("1-00:00:01"), Some(86_401.0));
+        assert_eq!(parse_cpu_time("2:03.50"), Some(123.5));
+        assert_eq!(parse_cpu_time("n/a"), None);
+    }
+
+    #[test]
+    fn test_sums_process_trees_and_cpu_rate() {
+        let table = |claude: &str, tool: &str| {
+            parse_ps(&format!(
+                "    1     0 00:00:09  1000\n  100     1 {claude} 400000\n  \
+                 200   100 {tool} 8000000\n  300   200 00:00:00  1000\n  400     1 00:00:00  5\n"
+            ))
+        };
+        let mut sampler = ResourceSampler::new();
+        let start = Instant::now();
+
+        let first = sampler.update(&table("00:00:10", "00:00:00"), &[100, 999], start);
+        assert_eq!(first.len(), 1);
+        assert_eq!(first[&100].rss_bytes, 8_401_000 * 1024);
+        assert_eq!(first[&100].cpu_percent, None);
+
+        // 2 + 3 CPU seconds over 10 seconds.
+        let later = start + Duration::from_secs(10);
+        let second = sampler.update(&table("00:00:12", "00:00:03"), &[100], later);
+        assert_eq!(second[&100].cpu_percent, Some(50.0));
+    }
+}
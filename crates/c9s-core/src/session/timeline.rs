@@ -0,0 +1,433 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+use super::Session;
+
+const MAX_TEXT_LEN: usize = 200;
+const MAX_ARG_LEN: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimelineKind {
+    UserPrompt,
+    Assistant,
+    ToolCall,
+    ToolError,
+    Compaction,
+    Hook,
+}
+
+/// One line of the local session timeline, derived from a JSONL record.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineEvent {
+    /// Line number of the source record in the JSONL; bookmarks key on it.
+    pub index: usize,
+    pub timestamp: Option<DateTime<Utc>>,
+    pub kind: TimelineKind,
+    pub text: String,
+}
+
+/// Location of the session's transcript under `~/.claude/projects`.
+pub fn transcript_path(session: &Session) -> Option<PathBuf> {
+    if session.imported {
+        return super::handoff::imported_transcript_path(&session.id).filter(|p| p.exists());
+    }
+    let encoded = session.cwd.to_string_lossy().replace('/', "-");
+    let project_dir = dirs::home_dir()?
+        .join(".claude")
+        .join("projects")
+        .join(encoded);
+    let path = match session.parent_id.as_deref() {
+        Some(parent) if session.is_subagent => project_dir
+            .join(parent)
+            .join("subagents")
+            .join(format!("{}.jsonl", session.id)),
+        _ => project_dir.join(format!("{}.jsonl", session.id)),
+    };
+    path.exists().then_some(path)
+}
+
+pub fn load_timeline(path: &Path) -> Vec<TimelineEvent> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, serde_json::from_str::<Value>(line).ok()?)))
+        .flat_map(|(i, value)| parse_record(&value, i))
+        .collect()
+}
+
+/// The conversation in the transcript at `path`: prompts and replies in
+/// full, tool calls and their failures, and compactions.
+pub fn load_transcript(path: &Path) -> Vec<TimelineEvent> {
+    let Ok(content) = std::fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, serde_json::from_str::<Value>(line).ok()?)))
+        .flat_map(|(i, value)| record_events(&value, i, true))
+        .filter(|e| e.kind != TimelineKind::Hook)
+        .collect()
+}
+
+/// How much of the transcript's end `last_exchange` reads.
+const TAIL_BYTES: u64 = 256 * 1024;
+
+/// The latest user prompt and assistant reply, for a glance at where a
+/// session stands.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LastExchange {
+    pub prompt: Option<String>,
+    pub reply: Option<String>,
+}
+
+/// Reads only the tail of the transcript; a prompt further back than
+/// `TAIL_BYTES` is not found.
+pub fn last_exchange(path: &Path) -> LastExchange {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let mut exchange = LastExchange::default();
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return exchange;
+    };
+    let len = file.metadata().map_or(0, |m| m.len());
+    let start = len.saturating_sub(TAIL_BYTES);
+    let mut buf = Vec::new();
+    if file.seek(SeekFrom::Start(start)).is_err() || file.read_to_end(&mut buf).is_err() {
+        return exchange;
+    }
+    let content = String::from_utf8_lossy(&buf);
+    // The first line is cut mid-record when reading from an offset.
+    let skip = usize::from(start > 0);
+    for line in content
+        .lines()
+        .skip(skip)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+    {
+        if exchange.prompt.is_some() && exchange.reply.is_some() {
+            break;
+        }
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        for event in parse_record(&value, 0).into_iter().rev() {
+            match event.kind {
+                TimelineKind::UserPrompt if exchange.prompt.is_none() => {
+                    exchange.prompt = Some(event.text)
+                }
+                TimelineKind::Assistant if exchange.reply.is_none() => {
+                    exchange.reply = Some(event.text)
+                }
+                _ => {}
+            }
+        }
+    }
+    exchange
+}
+
+fn parse_record(value: &Value, index: usize) -> Vec<TimelineEvent> {
+    record_events(value, index, false)
+}
+
+/// Prompts, replies and tool calls in one record with their full text,
+/// for the search index.
+pub fn searchable_events(value: &Value, index: usize) -> Vec<TimelineEvent> {
+    record_events(value, index, true)
+        .into_iter()
+        .filter(|e| {
+            matches!(
+                e.kind,
+                TimelineKind::UserPrompt | TimelineKind::Assistant | TimelineKind::ToolCall
+            )
+        })
+        .collect()
+}
+
+/// `full` keeps whole texts; otherwise they are cut to their first line.
+fn record_events(value: &Value, index: usize, full: bool) -> Vec<TimelineEvent> {
+    let timestamp = value
+        .get("timestamp")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse::<DateTime<Utc>>().ok());
+    let event = |kind, text: String| TimelineEvent {
+        index,
+        timestamp,
+        kind,
+        text: if full {
+            text
+        } else {
+            truncate(&text, MAX_TEXT_LEN)
+        },
+    };
+
+    let msg_type = value.get("type").and_then(|v| v.as_str()).unwrap_or("");
+    let content = value.get("message").and_then(|m| m.get("content"));
+
+    match msg_type {
+        "user" => {
+            if value.get("isCompactSummary").and_then(|v| v.as_bool()) == Some(true) {
+                return vec![event(
+                    TimelineKind::Compaction,
+                    "Resumed from compaction summary".to_string(),
+                )];
+            }
+            if value.get("isMeta").and_then(|v| v.as_bool()) == Some(true) {
+                return Vec::new();
+            }
+            match content {
+                Some(Value::String(text)) if !text.trim().is_empty() => {
+                    vec![event(TimelineKind::UserPrompt, text.clone())]
+                }
+                Some(Value::Array(blocks)) => blocks
+                    .iter()
+                    .filter_map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                        Some("text") => block
+                            .get("text")
+                            .and_then(|t| t.as_str())
+                            .map(|t| event(TimelineKind::UserPrompt, t.to_string())),
+                        Some("tool_result")
+                            if block.get("is_error").and_then(|e| e.as_bool()) == Some(true) =>
+                        {
+                            Some(event(TimelineKind::ToolError, tool_result_text(block)))
+                        }
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            }
+        }
+        "assistant" => {
+            let Some(Value::Array(blocks)) = content else {
+                return Vec::new();
+            };
+            blocks
+                .iter()
+                .filter_map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                    Some("text") => block
+                        .get("text")
+                        .and_then(|t| t.as_str())
+                        .filter(|t| !t.trim().is_empty())
+                        .map(|t| event(TimelineKind::Assistant, t.trim().to_string())),
+                    Some("tool_use") => Some(event(TimelineKind::ToolCall, tool_use_text(block))),
+                    _ => None,
+                })
+                .collect()
+        }
+        "system" => match value.get("subtype").and_then(|v| v.as_str()) {
+            Some("compact_boundary") => {
+                let meta = value.get("compactMetadata");
+                let trigger = meta
+                    .and_then(|m| m.get("trigger"))
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("auto");
+                let text = match meta
+                    .and_then(|m| m.get("preTokens"))
+                    .and_then(|t| t.as_u64())
+                {
+                    Some(tokens) => format!("Compacted ({}, {} tokens before)", trigger, tokens),
+                    None => format!("Compacted ({})", trigger),
+                };
+                vec![event(TimelineKind::Compaction, text)]
+            }
+            _ => match value.get("hookCount").and_then(|v| v.as_u64()) {
+                Some(count) if count > 0 => {
+                    let errors = value
+                        .get("hookErrors")
+                        .and_then(|v| v.as_array())
+                        .map_or(0, |a| a.len());
+                    let text = if errors > 0 {
+                        format!("{} hook(s) ran, {} failed", count, errors)
+                    } else {
+                        format!("{} hook(s) ran", count)
+                    };
+                    vec![event(TimelineKind::Hook, text)]
+                }
+                _ => Vec::new(),
+            },
+        },
+        _ => Vec::new(),
+    }
+}
+
+/// "ToolName arg" using the most identifying input field.
+fn tool_use_text(block: &Value) -> String {
+    let name = block.get("name").and_then(|n| n.as_str()).unwrap_or("tool");
+    let arg = block.get("input").and_then(|input| {
+        [
+            "file_path",
+            "path",
+            "pattern",
+            "command",
+            "query",
+            "url",
+            "description",
+        ]
+        .iter()
+        .find_map(|key| input.get(*key).and_then(|v| v.as_str()))
+    });
+    match arg {
+        Some(arg) => format!("{} {}", name, truncate(arg, MAX_ARG_LEN)),
+        None => name.to_string(),
+    }
+}
+
+fn tool_result_text(block: &Value) -> String {
+    let text = match block.get("content") {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts
+            .iter()
+            .find_map(|p| p.get("text").and_then(|t| t.as_str()))
+            .unwrap_or("")
+            .to_string(),
+        _ => String::new(),
+    };
+    if text.trim().is_empty() {
+        "Tool failed".to_string()
+    } else {
+        format!("Tool failed: {}", text.trim())
+    }
+}
+
+/// First line only, cut on a char boundary.
+fn truncate(s: &str, max: usize) -> String {
+    let first_line = s.lines().next().unwrap_or("");
+    if first_line.chars().count() <= max {
+        return first_line.to_string();
+    }
+    let cut: String = first_line.chars().take(max.saturating_sub(3)).collect();
+    format!("{}...", cut)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Vec<TimelineEvent> {
+        parse_record(&serde_json::from_str(line).unwrap(), 0)
+    }
+
+    #[test]
+    fn test_user_and_assistant_events() {
+        let user = parse(
+            r#"{"type":"user","timestamp":"2026-01-01T10:00:00Z","message":{"content":"fix the build\nplease"}}"#,
+        );
+        assert_eq!(user.len(), 1);
+        assert_eq!(user[0].kind, TimelineKind::UserPrompt);
+        assert_eq!(user[0].text, "fix the build");
+        assert!(user[0].timestamp.is_some());
+
+        let assistant = parse(
+            r#"{"type":"assistant","message":{"content":[
+                {"type":"thinking","thinking":"hmm"},
+                {"type":"text","text":"Looking at the error."},
+                {"type":"tool_use","name":"Bash","input":{"command":"cargo build"}}
+            ]}}"#,
+        );
+        assert_eq!(
+            assistant
+                .iter()
+                .map(|e| (e.kind, e.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (TimelineKind::Assistant, "Looking at the error."),
+                (TimelineKind::ToolCall, "Bash cargo build"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tool_results_only_surface_errors() {
+        let events = parse(
+            r#"{"type":"user","message":{"content":[
+                {"type":"tool_result","content":"ok"},
+                {"type":"tool_result","is_error":true,"content":"No such file"}
+            ]}}"#,
+        );
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].kind, TimelineKind::ToolError);
+        assert_eq!(events[0].text, "Tool failed: No such file");
+    }
+
+    #[test]
+    fn test_compaction_and_hooks() {
+        let compact = parse(
+            r#"{"type":"system","subtype":"compact_boundary","compactMetadata":{"trigger":"manual","preTokens":154000}}"#,
+        );
+        assert_eq!(compact[0].kind, TimelineKind::Compaction);
+        assert_eq!(compact[0].text, "Compacted (manual, 154000 tokens before)");
+
+        let hooks = parse(
+            r#"{"type":"system","subtype":"stop_hook_summary","hookCount":2,"hookErrors":["x"]}"#,
+        );
+        assert_eq!(hooks[0].kind, TimelineKind::Hook);
+        assert_eq!(hooks[0].text, "2 hook(s) ran, 1 failed");
+
+        assert!(parse(r#"{"type":"user","isMeta":true,"message":{"content":"x"}}"#).is_empty());
+        assert!(parse(r#"{"type":"file-history-snapshot"}"#).is_empty());
+    }
+
+    #[test]
+    fn test_last_exchange() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let lines = [
+            r#"{"type":"user","message":{"content":"first question"}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"first answer"}]}}"#,
+            r#"{"type":"user","message":{"content":"fix the parser"}}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Looking at it now"},{"type":"tool_use","name":"Read","input":{"file_path":"src/p.rs"}}]}}"#,
+            r#"{"type":"user","message":{"content":[{"type":"tool_result","content":"ok"}]}}"#,
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let exchange = last_exchange(&path);
+        assert_eq!(exchange.prompt.as_deref(), Some("fix the parser"));
+        assert_eq!(exchange.reply.as_deref(), Some("Looking at it now"));
+        assert_eq!(
+            last_exchange(&dir.path().join("missing.jsonl")),
+            LastExchange::default()
+        );
+    }
+
+    #[test]
+    fn test_load_transcript_keeps_full_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        let lines = [
+            r#"{"type":"user","message":{"content":"fix the build\nit fails on CI"}}"#,
+            r#"{"type":"system","subtype":"stop_hook_summary","hookCount":1}"#,
+            r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Found it.\n\nThe lockfile is stale."},{"type":"tool_use","name":"Bash","input":{"command":"cargo update"}}]}}"#,
+        ];
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let events = load_transcript(&path);
+        assert_eq!(
+            events
+                .iter()
+                .map(|e| (e.kind, e.text.as_str()))
+                .collect::<Vec<_>>(),
+            vec![
+                (TimelineKind::UserPrompt, "fix the build\nit fails on CI"),
+                (
+                    TimelineKind::Assistant,
+                    "Found it.\n\nThe lockfile is stale."
+                ),
+                (TimelineKind::ToolCall, "Bash cargo update"),
+            ]
+        );
+        assert_eq!(events[2].index, 2);
+    }
+
+    #[test]
+    fn test_truncate_is_char_safe() {
+        let long = "é".repeat(300);
+        let out = truncate(&long, 10);
+        assert_eq!(out.chars().count(), 10);
+        assert!(out.ends_with("..."));
+    }
+}
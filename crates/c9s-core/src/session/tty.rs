@@ -0,0 +1,54 @@
+//! Which terminal a session's claude is running in, to tell one attached
+//! elsewhere (another terminal window, a tmux pane) from one started by us.
+
+use std::path::Path;
+use std::process::Command;
+
+/// The controlling terminal of `pid` as `ps` names it ("pts/3",
+/// "ttys003"), or `None` if it has none or can't be looked up.
+pub fn controlling_tty(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-o", "tty=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    parse_tty(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_tty(output: &str) -> Option<String> {
+    let tty = output.trim();
+    (!tty.is_empty() && !tty.chars().all(|c| c == '?')).then(|| tty.to_string())
+}
+
+/// Whether `ps`'s name for a terminal is the device at `path`. macOS `ps`
+/// may leave off the "tty" prefix ("s003" for /dev/ttys003).
+pub fn is_device(tty: &str, path: &Path) -> bool {
+    let Ok(device) = path.strip_prefix("/dev") else {
+        return false;
+    };
+    let device = device.to_string_lossy();
+    device == tty || device.strip_prefix("tty") == Some(tty)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tty() {
+        assert_eq!(parse_tty("pts/3\n"), Some("pts/3".to_string()));
+        assert_eq!(parse_tty("?\n"), None);
+        assert_eq!(parse_tty("??"), None);
+        assert_eq!(parse_tty(""), None);
+    }
+
+    #[test]
+    fn test_is_device() {
+        assert!(is_device("pts/3", Path::new("/dev/pts/3")));
+        assert!(is_device("ttys003", Path::new("/dev/ttys003")));
+        assert!(is_device("s003", Path::new("/dev/ttys003")));
+        assert!(!is_device("pts/3", Path::new("/dev/pts/30")));
+    }
+}
@@ -0,0 +1,115 @@
+use std::cmp::Ordering;
+use std::time::Duration;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Installed and latest published claude CLI versions, as far as known.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClaudeVersions {
+    pub installed: Option<String>,
+    pub latest: Option<String>,
+}
+
+impl ClaudeVersions {
+    /// The newest version we know of: the published release, or the
+    /// installed CLI when the release check is disabled or failed.
+    pub fn newest(&self) -> Option<&str> {
+        match (self.installed.as_deref(), self.latest.as_deref()) {
+            (Some(i), Some(l)) => Some(if compare_versions(i, l) == Ordering::Greater {
+                i
+            } else {
+                l
+            }),
+            (i, l) => l.or(i),
+        }
+    }
+
+    pub fn is_outdated(&self, version: &str) -> bool {
+        self.newest()
+            .is_some_and(|newest| compare_versions(version, newest) == Ordering::Less)
+    }
+
+    pub fn installed_outdated(&self) -> bool {
+        match (self.installed.as_deref(), self.latest.as_deref()) {
+            (Some(i), Some(l)) => compare_versions(i, l) == Ordering::Less,
+            _ => false,
+        }
+    }
+}
+
+/// Compares dotted numeric versions; a pre-release suffix (`-beta.1`) or
+/// non-numeric part sorts as 0.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let parse = |v: &str| -> Vec<u64> {
+        v.trim_start_matches('v')
+            .split(['-', '+'])
+            .next()
+            .unwrap_or("")
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    };
+    let (a, b) = (parse(a), parse(b));
+    for i in 0..a.len().max(b.len()) {
+        let ord = a
+            .get(i)
+            .copied()
+            .unwrap_or(0)
+            .cmp(&b.get(i).copied().unwrap_or(0));
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+/// Fetches the latest release from an npm-registry style endpoint returning
+/// `{"version": "..."}`. Blocking; call from a background thread.
+pub fn fetch_latest_version(url: &str) -> Option<String> {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(FETCH_TIMEOUT))
+        .build()
+        .new_agent();
+    let body = agent
+        .get(url)
+        .header("User-Agent", "c9s/0.1")
+        .call()
+        .ok()?
+        .body_mut()
+        .read_to_string()
+        .ok()?;
+    let json: serde_json::Value = serde_json::from_str(&body).ok()?;
+    json.get("version")?.as_str().map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("2.0.14", "2.0.9"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.100", "2.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.0", "2.0.0"), Ordering::Equal);
+        assert_eq!(compare_versions("v2.1.0", "2.1.0-beta.3"), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_outdated_against_newest_known() {
+        let versions = ClaudeVersions {
+            installed: Some("2.0.20".into()),
+            latest: Some("2.0.25".into()),
+        };
+        assert!(versions.installed_outdated());
+        assert!(versions.is_outdated("2.0.20"));
+        assert!(!versions.is_outdated("2.0.25"));
+
+        let offline = ClaudeVersions {
+            installed: Some("2.0.20".into()),
+            latest: None,
+        };
+        assert!(!offline.installed_outdated());
+        assert!(offline.is_outdated("2.0.3"));
+        assert!(!ClaudeVersions::default().is_outdated("0.1.0"));
+    }
+}
@@ -0,0 +1,1117 @@
+use anyhow::Result;
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension};
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::notify::NotifyLevel;
+use crate::outcome::{Outcome, OutcomeStats, RatedSession};
+use crate::session::timeline::{searchable_events, TimelineKind};
+use crate::session::{Session, StatsEntry, STATS_VERSION};
+use crate::timetrack::TrackedTime;
+
+/// Time tracked on one project on one day, over all its sessions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectTime {
+    pub date: NaiveDate,
+    pub project: String,
+    pub millis: u64,
+}
+
+/// Sessions, tokens and estimated cost of one day or one project.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CostTotals {
+    /// The day ("2026-03-10") or the project name.
+    pub key: String,
+    pub sessions: u64,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+/// A transcript line matching a search.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub session_id: String,
+    pub path: PathBuf,
+    /// Line number in the JSONL, as the timeline and bookmarks count them.
+    pub line: usize,
+    /// "user", "assistant" or "tool".
+    pub kind: String,
+    /// The matching text around the hit, terms wrapped in `[` `]`.
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bookmark {
+    /// Line number of the bookmarked record in the session's JSONL.
+    pub message_index: usize,
+    pub label: String,
+    pub created_at: String,
+}
+
+pub struct Store {
+    conn: Connection,
+}
+
+impl Store {
+    pub fn open() -> Result<Self> {
+        let data_dir = dirs::home_dir()
+            .unwrap_or_else(|| PathBuf::from("~"))
+            .join(".c9s");
+        std::fs::create_dir_all(&data_dir)?;
+
+        let db_path = data_dir.join("data.db");
+        let conn = Connection::open(&db_path)?;
+        // The initial session scan writes from its own connection.
+        conn.busy_timeout(Duration::from_secs(2))?;
+        let store = Self { conn };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// A store that lives only as long as it does, for tests.
+    #[cfg(any(test, feature = "test-util"))]
+    pub fn in_memory() -> Result<Self> {
+        let store = Self {
+            conn: Connection::open_in_memory()?,
+        };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    fn migrate(&self) -> Result<()> {
+        self.conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                id TEXT PRIMARY KEY,
+                cwd TEXT NOT NULL,
+                project_name TEXT NOT NULL,
+                git_branch TEXT,
+                model TEXT,
+                started_at TEXT NOT NULL,
+                ended_at TEXT,
+                total_input_tokens INTEGER DEFAULT 0,
+                total_output_tokens INTEGER DEFAULT 0,
+                total_cache_read_tokens INTEGER DEFAULT 0,
+                total_cache_write_tokens INTEGER DEFAULT 0,
+                estimated_cost_usd REAL DEFAULT 0.0,
+                message_count INTEGER DEFAULT 0,
+                tool_call_count INTEGER DEFAULT 0,
+                claude_version TEXT
+            );
+
+            CREATE TABLE IF NOT EXISTS daily_stats (
+                date TEXT PRIMARY KEY,
+                total_sessions INTEGER DEFAULT 0,
+                total_messages INTEGER DEFAULT 0,
+                total_tool_calls INTEGER DEFAULT 0,
+                total_input_tokens INTEGER DEFAULT 0,
+                total_output_tokens INTEGER DEFAULT 0,
+                total_cost_usd REAL DEFAULT 0.0
+            );
+
+            CREATE TABLE IF NOT EXISTS bookmarks (
+                session_id TEXT NOT NULL,
+                message_index INTEGER NOT NULL,
+                label TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                PRIMARY KEY (session_id, message_index)
+            );
+
+            CREATE TABLE IF NOT EXISTS notify_levels (
+                session_id TEXT PRIMARY KEY,
+                level TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS tracked_time (
+                date TEXT NOT NULL,
+                session_id TEXT NOT NULL,
+                project TEXT NOT NULL,
+                millis INTEGER NOT NULL,
+                PRIMARY KEY (date, session_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS outcomes (
+                session_id TEXT PRIMARY KEY,
+                project TEXT NOT NULL,
+                model TEXT NOT NULL,
+                outcome TEXT NOT NULL,
+                rating INTEGER,
+                rated_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS tickets (
+                session_id TEXT PRIMARY KEY,
+                ticket TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS hidden_sessions (
+                session_id TEXT PRIMARY KEY,
+                hidden_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS imported_sessions (
+                session_id TEXT PRIMARY KEY,
+                source TEXT NOT NULL,
+                imported_at TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS jsonl_stats (
+                path TEXT PRIMARY KEY,
+                mtime_ns INTEGER NOT NULL,
+                size INTEGER NOT NULL,
+                version INTEGER NOT NULL,
+                stats TEXT NOT NULL
+            );
+
+            CREATE VIRTUAL TABLE IF NOT EXISTS transcript_fts USING fts5(
+                text,
+                session_id UNINDEXED,
+                path UNINDEXED,
+                line UNINDEXED,
+                kind UNINDEXED
+            );
+
+            CREATE TABLE IF NOT EXISTS fts_files (
+                path TEXT PRIMARY KEY,
+                offset INTEGER NOT NULL,
+                lines INTEGER NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_sessions_project ON sessions(project_name);
+            CREATE INDEX IF NOT EXISTS idx_sessions_started ON sessions(started_at);",
+        )?;
+        // Columns added since, for databases created before them.
+        if self
+            .conn
+            .prepare("SELECT last_activity FROM sessions LIMIT 0")
+            .is_err()
+        {
+            self.conn
+                .execute("ALTER TABLE sessions ADD COLUMN last_activity TEXT", [])?;
+        }
+        Ok(())
+    }
+
+    pub fn upsert_session(&self, session: &Session) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO sessions (id, cwd, project_name, git_branch, model, started_at,
+                total_input_tokens, total_output_tokens, total_cache_read_tokens,
+                total_cache_write_tokens, estimated_cost_usd, message_count,
+                tool_call_count, claude_version, last_activity)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
+            ON CONFLICT(id) DO UPDATE SET
+                git_branch = excluded.git_branch,
+                model = excluded.model,
+                total_input_tokens = excluded.total_input_tokens,
+                total_output_tokens = excluded.total_output_tokens,
+                total_cache_read_tokens = excluded.total_cache_read_tokens,
+                total_cache_write_tokens = excluded.total_cache_write_tokens,
+                estimated_cost_usd = excluded.estimated_cost_usd,
+                message_count = excluded.message_count,
+                tool_call_count = excluded.tool_call_count,
+                claude_version = excluded.claude_version,
+                last_activity = excluded.last_activity",
+            rusqlite::params![
+                session.id,
+                session.cwd.to_string_lossy(),
+                session.project_name,
+                session.git_branch,
+                session.model,
+                session.started_at.to_rfc3339(),
+                session.input_tokens,
+                session.output_tokens,
+                session.cache_read_tokens,
+                session.cache_write_tokens,
+                session.estimated_cost_usd(),
+                session.message_count,
+                session.tool_call_count,
+                session.claude_version,
+                session.last_activity.to_rfc3339(),
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn add_bookmark(&self, session_id: &str, message_index: usize, label: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO bookmarks (session_id, message_index, label, created_at)
+            VALUES (?1, ?2, ?3, datetime('now'))
+            ON CONFLICT(session_id, message_index) DO UPDATE SET label = excluded.label",
+            rusqlite::params![session_id, message_index as i64, label],
+        )?;
+        Ok(())
+    }
+
+    pub fn remove_bookmark(&self, session_id: &str, message_index: usize) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM bookmarks WHERE session_id = ?1 AND message_index = ?2",
+            rusqlite::params![session_id, message_index as i64],
+        )?;
+        Ok(())
+    }
+
+    /// Bookmarks for one session in transcript order.
+    pub fn list_bookmarks(&self, session_id: &str) -> Result<Vec<Bookmark>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT message_index, label, created_at FROM bookmarks
+            WHERE session_id = ?1 ORDER BY message_index",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![session_id], |row| {
+            Ok(Bookmark {
+                message_index: row.get::<_, i64>(0)? as usize,
+                label: row.get(1)?,
+                created_at: row.get(2)?,
+            })
+        })?;
+        Ok(rows.collect::<rusqlite::Result<Vec<_>>>()?)
+    }
+
+    /// Stores a session's notification level; the default level is stored
+    /// as the absence of a row.
+    pub fn set_notify_level(&self, session_id: &str, level: NotifyLevel) -> Result<()> {
+        if level == NotifyLevel::default() {
+            self.conn.execute(
+                "DELETE FROM notify_levels WHERE session_id = ?1",
+                rusqlite::params![session_id],
+            )?;
+        } else {
+            self.conn.execute(
+                "INSERT INTO notify_levels (session_id, level) VALUES (?1, ?2)
+                ON CONFLICT(session_id) DO UPDATE SET level = excluded.level",
+                rusqlite::params![session_id, level.as_str()],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn notify_levels(&self) -> Result<HashMap<String, NotifyLevel>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id, level FROM notify_levels")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?;
+        let mut levels = HashMap::new();
+        for row in rows {
+            let (id, level) = row?;
+            if let Some(level) = NotifyLevel::parse(&level) {
+                levels.insert(id, level);
+            }
+        }
+        Ok(levels)
+    }
+
+    /// Links a session to a ticket by hand; `None` goes back to the one
+    /// in its branch name.
+    pub fn set_ticket(&self, session_id: &str, ticket: Option<&str>) -> Result<()> {
+        match ticket {
+            Some(ticket) => self.conn.execute(
+                "INSERT INTO tickets (session_id, ticket) VALUES (?1, ?2)
+                ON CONFLICT(session_id) DO UPDATE SET ticket = excluded.ticket",
+                rusqlite::params![session_id, ticket],
+            )?,
+            None => self.conn.execute(
+                "DELETE FROM tickets WHERE session_id = ?1",
+                rusqlite::params![session_id],
+            )?,
+        };
+        Ok(())
+    }
+
+    pub fn tickets(&self) -> Result<HashMap<String, String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id, ticket FROM tickets")?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Hides a session from the list, or shows it again.
+    pub fn set_hidden(&self, session_id: &str, hidden: bool) -> Result<()> {
+        if hidden {
+            self.conn.execute(
+                "INSERT OR IGNORE INTO hidden_sessions (session_id, hidden_at)
+                VALUES (?1, datetime('now'))",
+                rusqlite::params![session_id],
+            )?;
+        } else {
+            self.conn.execute(
+                "DELETE FROM hidden_sessions WHERE session_id = ?1",
+                rusqlite::params![session_id],
+            )?;
+        }
+        Ok(())
+    }
+
+    pub fn hidden_sessions(&self) -> Result<HashSet<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id FROM hidden_sessions")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Records a session imported from a handoff bundle at `source`, along
+    /// with its summary.
+    pub fn add_imported_session(&self, session: &Session, source: &str) -> Result<()> {
+        self.upsert_session(session)?;
+        self.conn.execute(
+            "INSERT INTO imported_sessions (session_id, source, imported_at)
+            VALUES (?1, ?2, datetime('now'))
+            ON CONFLICT(session_id) DO UPDATE SET
+                source = excluded.source,
+                imported_at = excluded.imported_at",
+            rusqlite::params![session.id, source],
+        )?;
+        Ok(())
+    }
+
+    /// Ids of the imported sessions, oldest import first.
+    pub fn imported_session_ids(&self) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT session_id FROM imported_sessions ORDER BY imported_at, session_id")?;
+        let rows = stmt.query_map([], |row| row.get(0))?;
+        Ok(rows.collect::<rusqlite::Result<_>>()?)
+    }
+
+    /// Adds tracked time to what each session already has for the day.
+    pub fn add_tracked_time(&self, times: &[TrackedTime]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        for t in times {
+            tx.execute(
+                "INSERT INTO tracked_time (date, session_id, project, millis)
+                VALUES (?1, ?2, ?3, ?4)
+                ON CONFLICT(date, session_id) DO UPDATE SET
+                    millis = millis + excluded.millis,
+                    project = excluded.project",
+                rusqlite::params![t.date.to_string(), t.session_id, t.project, t.millis as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Tracked time per project and day from `since` on, oldest first.
+    pub fn project_time(&self, since: NaiveDate) -> Result<Vec<ProjectTime>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT date, project, SUM(millis) FROM tracked_time
+            WHERE date >= ?1 GROUP BY date, project ORDER BY date, project",
+        )?;
+        let rows = stmt.query_map(rusqlite::params![since.to_string()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        let mut times = Vec::new();
+        for row in rows {
+            let (date, project, millis) = row?;
+            if let Ok(date) = date.parse() {
+                times.push(ProjectTime {
+                    date,
+                    project,
+                    millis: millis.max(0) as u64,
+                });
+            }
+        }
+        Ok(times)
+    }
+
+    /// Totals per day from `since` on, oldest first. Sessions count on the
+    /// local day they started; days without any are left out.
+    pub fn cost_by_day(&self, since: NaiveDate) -> Result<Vec<CostTotals>> {
+        self.cost_totals("date(started_at, 'localtime')", "key", since)
+    }
+
+    /// Totals per project for the sessions started from `since` on,
+    /// costliest first.
+    pub fn cost_by_project(&self, since: NaiveDate) -> Result<Vec<CostTotals>> {
+        self.cost_totals("project_name", "SUM(estimated_cost_usd) DESC, key", since)
+    }
+
+    fn cost_totals(&self, key: &str, order: &str, since: NaiveDate) -> Result<Vec<CostTotals>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} AS key, COUNT(*), SUM(total_input_tokens), SUM(total_output_tokens),
+                SUM(estimated_cost_usd)
+            FROM sessions WHERE date(started_at, 'localtime') >= ?1
+            GROUP BY key ORDER BY {}",
+            key, order
+        ))?;
+        let totals = stmt
+            .query_map(rusqlite::params![since.to_string()], |row| {
+                Ok(CostTotals {
+                    key: row.get(0)?,
+                    sessions: row.get(1)?,
+                    input_tokens: row.get::<_, i64>(2)?.max(0) as u64,
+                    output_tokens: row.get::<_, i64>(3)?.max(0) as u64,
+                    cost_usd: row.get(4)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(totals)
+    }
+
+    /// Records how a session ended; rating it again replaces the record.
+    pub fn set_outcome(
+        &self,
+        session_id: &str,
+        project: &str,
+        model: &str,
+        outcome: Outcome,
+        rating: Option<u8>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO outcomes (session_id, project, model, outcome, rating, rated_at)
+            VALUES (?1, ?2, ?3, ?4, ?5, datetime('now'))
+            ON CONFLICT(session_id) DO UPDATE SET
+                project = excluded.project,
+                model = excluded.model,
+                outcome = excluded.outcome,
+                rating = excluded.rating,
+                rated_at = excluded.rated_at",
+            rusqlite::params![session_id, project, model, outcome.as_str(), rating],
+        )?;
+        Ok(())
+    }
+
+    pub fn outcome(&self, session_id: &str) -> Result<Option<(Outcome, Option<u8>)>> {
+        let row = self
+            .conn
+            .query_row(
+                "SELECT outcome, rating FROM outcomes WHERE session_id = ?1",
+                rusqlite::params![session_id],
+                |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<u8>>(1)?)),
+            )
+            .optional()?;
+        Ok(row.and_then(|(o, r)| Outcome::parse(&o).map(|o| (o, r))))
+    }
+
+    /// Outcomes per project and model, most rated first.
+    pub fn outcome_stats(&self) -> Result<Vec<OutcomeStats>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT project, model, COUNT(*),
+                SUM(outcome = 'shipped'), SUM(outcome = 'abandoned'),
+                SUM(outcome = 'needs-human'), AVG(rating)
+            FROM outcomes GROUP BY project, model
+            ORDER BY COUNT(*) DESC, project, model",
+        )?;
+        let stats = stmt
+            .query_map([], |row| {
+                Ok(OutcomeStats {
+                    project: row.get(0)?,
+                    model: row.get(1)?,
+                    sessions: row.get(2)?,
+                    shipped: row.get(3)?,
+                    abandoned: row.get(4)?,
+                    needs_human: row.get(5)?,
+                    avg_rating: row.get(6)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(stats)
+    }
+
+    /// Every rated session, with cost and duration from its stored
+    /// summary where there is one.
+    pub fn rated_sessions(&self) -> Result<Vec<RatedSession>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT o.model, o.outcome, COALESCE(s.estimated_cost_usd, 0.0),
+                s.started_at, s.last_activity
+            FROM outcomes o LEFT JOIN sessions s ON s.id = o.session_id",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, f64>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, Option<String>>(4)?,
+            ))
+        })?;
+        let time =
+            |s: Option<String>| s.and_then(|s| chrono::DateTime::parse_from_rfc3339(&s).ok());
+        let mut sessions = Vec::new();
+        for row in rows {
+            let (model, outcome, cost_usd, started, last) = row?;
+            let Some(outcome) = Outcome::parse(&outcome) else {
+                continue;
+            };
+            let duration = time(started)
+                .zip(time(last))
+                .map(|(start, end)| end - start);
+            sessions.push(RatedSession {
+                model,
+                outcome,
+                cost_usd,
+                duration,
+            });
+        }
+        Ok(sessions)
+    }
+
+    /// Transcript summaries saved by earlier runs, skipping any written by a
+    /// version that parsed differently.
+    pub fn load_jsonl_stats(&self) -> Result<Vec<StatsEntry>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT path, mtime_ns, size, stats FROM jsonl_stats WHERE version = ?1")?;
+        let rows = stmt.query_map(rusqlite::params![STATS_VERSION], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?;
+        let mut entries = Vec::new();
+        for row in rows {
+            let (path, mtime_ns, size, stats) = row?;
+            let Ok(stats) = serde_json::from_str(&stats) else {
+                continue;
+            };
+            entries.push(StatsEntry {
+                path: PathBuf::from(path),
+                mtime: UNIX_EPOCH + Duration::from_nanos(mtime_ns as u64),
+                size: size as u64,
+                stats,
+            });
+        }
+        Ok(entries)
+    }
+
+    pub fn save_jsonl_stats(&self, entries: &[StatsEntry]) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO jsonl_stats (path, mtime_ns, size, version, stats)
+                VALUES (?1, ?2, ?3, ?4, ?5)
+                ON CONFLICT(path) DO UPDATE SET
+                    mtime_ns = excluded.mtime_ns,
+                    size = excluded.size,
+                    version = excluded.version,
+                    stats = excluded.stats",
+            )?;
+            for e in entries {
+                stmt.execute(rusqlite::params![
+                    e.path.to_string_lossy(),
+                    mtime_nanos(e.mtime),
+                    e.size as i64,
+                    STATS_VERSION,
+                    serde_json::to_string(&e.stats)?,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    /// Drops summaries of transcripts that no longer exist. Returns how many
+    /// were removed.
+    pub fn prune_jsonl_stats(&self) -> Result<usize> {
+        let mut stmt = self.conn.prepare("SELECT path FROM jsonl_stats")?;
+        let gone: Vec<String> = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .filter_map(|p| p.ok())
+            .filter(|p| !std::path::Path::new(p).exists())
+            .collect();
+        let tx = self.conn.unchecked_transaction()?;
+        for path in &gone {
+            tx.execute(
+                "DELETE FROM jsonl_stats WHERE path = ?1",
+                rusqlite::params![path],
+            )?;
+        }
+        tx.commit()?;
+        Ok(gone.len())
+    }
+
+    /// Brings the search index up to date with `files` (path, session id).
+    /// Appended transcripts only have their new lines indexed; a file that
+    /// shrank is indexed again from the start. Returns the lines added.
+    pub fn index_transcripts(&self, files: &[(PathBuf, String)]) -> Result<usize> {
+        let mut added = 0;
+        for (path, session_id) in files {
+            added += self.index_transcript(path, session_id)?;
+        }
+        Ok(added)
+    }
+
+    fn index_transcript(&self, path: &Path, session_id: &str) -> Result<usize> {
+        let key = path.to_string_lossy();
+        let Ok(len) = std::fs::metadata(path).map(|m| m.len()) else {
+            self.drop_from_index(&key)?;
+            return Ok(0);
+        };
+        let indexed: Option<(i64, i64)> = self
+            .conn
+            .query_row(
+                "SELECT offset, lines FROM fts_files WHERE path = ?1",
+                rusqlite::params![key],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+        let (mut offset, mut line) = match indexed {
+            Some((offset, _)) if offset as u64 == len => return Ok(0),
+            Some((offset, lines)) if (offset as u64) < len => (offset as u64, lines as usize),
+            Some(_) => {
+                self.drop_from_index(&key)?;
+                (0, 0)
+            }
+            None => (0, 0),
+        };
+
+        let mut file = std::fs::File::open(path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf)?;
+        // Leave a line still being written for next time.
+        let complete = buf.iter().rposition(|b| *b == b'\n').map_or(0, |i| i + 1);
+        let content = String::from_utf8_lossy(&buf[..complete]);
+
+        let tx = self.conn.unchecked_transaction()?;
+        let mut added = 0;
+        {
+            let mut insert = tx.prepare(
+                "INSERT INTO transcript_fts (text, session_id, path, line, kind)
+                VALUES (?1, ?2, ?3, ?4, ?5)",
+            )?;
+            for raw in content.lines() {
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(raw) {
+                    for event in searchable_events(&value, line) {
+                        insert.execute(rusqlite::params![
+                            event.text,
+                            session_id,
+                            key,
+                            line as i64,
+                            kind_label(event.kind),
+                        ])?;
+                        added += 1;
+                    }
+                }
+                line += 1;
+            }
+        }
+        offset += complete as u64;
+        tx.execute(
+            "INSERT INTO fts_files (path, offset, lines) VALUES (?1, ?2, ?3)
+            ON CONFLICT(path) DO UPDATE SET offset = excluded.offset, lines = excluded.lines",
+            rusqlite::params![key, offset as i64, line as i64],
+        )?;
+        tx.commit()?;
+        Ok(added)
+    }
+
+    fn drop_from_index(&self, path: &str) -> Result<()> {
+        self.conn.execute(
+            "DELETE FROM transcript_fts WHERE path = ?1",
+            rusqlite::params![path],
+        )?;
+        self.conn.execute(
+            "DELETE FROM fts_files WHERE path = ?1",
+            rusqlite::params![path],
+        )?;
+        Ok(())
+    }
+
+    /// Best matches first. Every word of `query` must appear; the last may
+    /// be the start of a word, so results show up while typing.
+    pub fn search_transcripts(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+        let Some(expr) = fts_query(query) else {
+            return Ok(Vec::new());
+        };
+        let mut stmt = self.conn.prepare(
+            "SELECT session_id, path, line, kind,
+                snippet(transcript_fts, 0, '[', ']', '...', 12)
+            FROM transcript_fts WHERE transcript_fts MATCH ?1
+            ORDER BY rank LIMIT ?2",
+        )?;
+        let hits = stmt
+            .query_map(rusqlite::params![expr, limit as i64], |row| {
+                Ok(SearchHit {
+                    session_id: row.get(0)?,
+                    path: PathBuf::from(row.get::<_, String>(1)?),
+                    line: row.get::<_, i64>(2)? as usize,
+                    kind: row.get(3)?,
+                    snippet: row.get::<_, String>(4)?.replace('\n', " "),
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(hits)
+    }
+
+    #[allow(dead_code)]
+    pub fn mark_session_ended(&self, session_id: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE sessions SET ended_at = datetime('now') WHERE id = ?1 AND ended_at IS NULL",
+            rusqlite::params![session_id],
+        )?;
+        Ok(())
+    }
+
+    #[allow(dead_code)]
+    pub fn get_total_cost(&self) -> Result<f64> {
+        let cost: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(estimated_cost_usd), 0.0) FROM sessions",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_today_cost(&self) -> Result<f64> {
+        let cost: f64 = self.conn.query_row(
+            "SELECT COALESCE(SUM(estimated_cost_usd), 0.0) FROM sessions WHERE date(started_at) = date('now')",
+            [],
+            |row| row.get(0),
+        )?;
+        Ok(cost)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_session_count(&self) -> Result<u64> {
+        let count: u64 = self
+            .conn
+            .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))?;
+        Ok(count)
+    }
+}
+
+fn kind_label(kind: TimelineKind) -> &'static str {
+    match kind {
+        TimelineKind::UserPrompt => "user",
+        TimelineKind::Assistant => "assistant",
+        _ => "tool",
+    }
+}
+
+/// Quotes each word so punctuation in it is not read as FTS5 syntax.
+fn fts_query(query: &str) -> Option<String> {
+    let words: Vec<String> = query
+        .split_whitespace()
+        .map(|w| format!("\"{}\"", w.replace('"', "\"\"")))
+        .collect();
+    let last = words.last()?;
+    let mut expr = words[..words.len() - 1].join(" ");
+    if !expr.is_empty() {
+        expr.push(' ');
+    }
+    expr.push_str(last);
+    expr.push('*');
+    Some(expr)
+}
+
+fn mtime_nanos(t: SystemTime) -> i64 {
+    t.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory_store() -> Store {
+        Store::in_memory().unwrap()
+    }
+
+    #[test]
+    fn test_bookmark_roundtrip() {
+        let store = memory_store();
+        store.add_bookmark("s1", 40, "second").unwrap();
+        store.add_bookmark("s1", 12, "first").unwrap();
+        store.add_bookmark("s2", 3, "other session").unwrap();
+        store.add_bookmark("s1", 12, "first, renamed").unwrap();
+
+        let marks = store.list_bookmarks("s1").unwrap();
+        assert_eq!(
+            marks
+                .iter()
+                .map(|b| (b.message_index, b.label.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(12, "first, renamed"), (40, "second")]
+        );
+
+        store.remove_bookmark("s1", 12).unwrap();
+        assert_eq!(store.list_bookmarks("s1").unwrap().len(), 1);
+        assert_eq!(store.list_bookmarks("s2").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_notify_levels_roundtrip() {
+        let store = memory_store();
+        store.set_notify_level("s1", NotifyLevel::Mute).unwrap();
+        store
+            .set_notify_level("s2", NotifyLevel::NeedsInput)
+            .unwrap();
+        store.set_notify_level("s1", NotifyLevel::All).unwrap();
+
+        let levels = store.notify_levels().unwrap();
+        assert_eq!(levels.len(), 1);
+        assert_eq!(levels.get("s2"), Some(&NotifyLevel::NeedsInput));
+    }
+
+    #[test]
+    fn test_tracked_time_adds_up_per_project_and_day() {
+        let store = memory_store();
+        let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+        let time = |d, id: &str, project: &str, millis| TrackedTime {
+            date: day(d),
+            session_id: id.to_string(),
+            project: project.to_string(),
+            millis,
+        };
+        store
+            .add_tracked_time(&[time(9, "s1", "api", 1_000), time(10, "s1", "api", 2_000)])
+            .unwrap();
+        store
+            .add_tracked_time(&[time(10, "s1", "api", 3_000), time(10, "s2", "api", 4_000)])
+            .unwrap();
+        store
+            .add_tracked_time(&[time(10, "s3", "web", 5_000)])
+            .unwrap();
+
+        let times = store.project_time(day(10)).unwrap();
+        let got: Vec<_> = times
+            .iter()
+            .map(|t| (t.project.as_str(), t.millis))
+            .collect();
+        assert_eq!(got, [("api", 9_000), ("web", 5_000)]);
+        assert_eq!(store.project_time(day(1)).unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_outcome_stats() {
+        let store = memory_store();
+        store
+            .set_outcome("s1", "api", "opus", Outcome::Shipped, Some(5))
+            .unwrap();
+        store
+            .set_outcome("s2", "api", "opus", Outcome::Abandoned, Some(2))
+            .unwrap();
+        store
+            .set_outcome("s3", "api", "opus", Outcome::Shipped, None)
+            .unwrap();
+        store
+            .set_outcome("s4", "web", "sonnet", Outcome::NeedsHuman, Some(3))
+            .unwrap();
+        // Re-rating replaces the earlier record.
+        store
+            .set_outcome("s4", "web", "sonnet", Outcome::Shipped, Some(4))
+            .unwrap();
+
+        let stats = store.outcome_stats().unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!((stats[0].project.as_str(), stats[0].sessions), ("api", 3));
+        assert_eq!(stats[0].shipped, 2);
+        assert_eq!(stats[0].abandoned, 1);
+        assert_eq!(stats[0].avg_rating, Some(3.5));
+        assert!((stats[0].success_rate() - 2.0 / 3.0).abs() < 1e-9);
+        assert_eq!(stats[1].needs_human, 0);
+        assert_eq!(
+            store.outcome("s4").unwrap(),
+            Some((Outcome::Shipped, Some(4)))
+        );
+        assert_eq!(store.outcome("nope").unwrap(), None);
+
+        store
+            .conn
+            .execute(
+                "INSERT INTO sessions (id, cwd, project_name, started_at, last_activity,
+                    estimated_cost_usd)
+                VALUES ('s1', '/tmp', 'api', '2026-03-10T12:00:00+00:00',
+                    '2026-03-10T12:45:00+00:00', 2.5)",
+                [],
+            )
+            .unwrap();
+        let rated = store.rated_sessions().unwrap();
+        assert_eq!(rated.len(), 4);
+        let s1 = rated.iter().find(|r| r.cost_usd > 0.0).unwrap();
+        assert_eq!(s1.duration, Some(chrono::Duration::minutes(45)));
+        assert_eq!(rated.iter().filter(|r| r.duration.is_none()).count(), 3);
+    }
+
+    #[test]
+    fn test_cost_totals_per_day_and_project() {
+        let store = memory_store();
+        let session = |id: &str, project: &str, started: &str, input: i64, cost: f64| {
+            store
+                .conn
+                .execute(
+                    "INSERT INTO sessions (id, cwd, project_name, started_at,
+                        total_input_tokens, total_output_tokens, estimated_cost_usd)
+                    VALUES (?1, '/tmp', ?2, ?3, ?4, 10, ?5)",
+                    rusqlite::params![id, project, started, input, cost],
+                )
+                .unwrap();
+        };
+        session("s1", "api", "2026-03-08T12:00:00+00:00", 100, 1.0);
+        session("s2", "api", "2026-03-10T12:00:00+00:00", 200, 2.0);
+        session("s3", "web", "2026-03-10T12:30:00+00:00", 300, 4.0);
+        session("s4", "api", "2026-03-11T12:00:00+00:00", 400, 0.5);
+        let since = NaiveDate::from_ymd_opt(2026, 3, 10).unwrap();
+
+        let days = store.cost_by_day(since).unwrap();
+        let got: Vec<_> = days
+            .iter()
+            .map(|d| (d.key.as_str(), d.sessions, d.input_tokens, d.cost_usd))
+            .collect();
+        assert_eq!(
+            got,
+            [("2026-03-10", 2, 500, 6.0), ("2026-03-11", 1, 400, 0.5)]
+        );
+        assert_eq!(days[0].output_tokens, 20);
+
+        let projects = store.cost_by_project(since).unwrap();
+        let got: Vec<_> = projects
+            .iter()
+            .map(|p| (p.key.as_str(), p.sessions, p.cost_usd))
+            .collect();
+        assert_eq!(got, [("web", 1, 4.0), ("api", 2, 2.5)]);
+    }
+
+    #[test]
+    fn test_tickets_roundtrip() {
+        let store = memory_store();
+        store.set_ticket("s1", Some("ABC-1")).unwrap();
+        store.set_ticket("s1", Some("ABC-2")).unwrap();
+        store.set_ticket("s2", Some("#3")).unwrap();
+        store.set_ticket("s2", None).unwrap();
+
+        let tickets = store.tickets().unwrap();
+        assert_eq!(tickets.len(), 1);
+        assert_eq!(tickets.get("s1").map(String::as_str), Some("ABC-2"));
+    }
+
+    #[test]
+    fn test_hidden_sessions_roundtrip() {
+        let store = memory_store();
+        store.set_hidden("s1", true).unwrap();
+        store.set_hidden("s1", true).unwrap();
+        store.set_hidden("s2", true).unwrap();
+        store.set_hidden("s2", false).unwrap();
+        store.set_hidden("s3", false).unwrap();
+
+        let hidden = store.hidden_sessions().unwrap();
+        assert_eq!(hidden, HashSet::from(["s1".to_string()]));
+    }
+
+    #[test]
+    fn test_imported_sessions() {
+        let store = memory_store();
+        let mut session = Session::fixture("s1", "/home/dev/webshop");
+        session.imported = true;
+        store
+            .add_imported_session(&session, "/tmp/a.tar.gz")
+            .unwrap();
+        store.add_imported_session(&session, "/tmp/b").unwrap();
+        assert_eq!(store.imported_session_ids().unwrap(), ["s1"]);
+        assert_eq!(store.get_session_count().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_fts_query_quotes_words() {
+        assert_eq!(fts_query("  "), None);
+        assert_eq!(fts_query("parser"), Some("\"parser\"*".to_string()));
+        assert_eq!(
+            fts_query("fix foo-bar \"x"),
+            Some("\"fix\" \"foo-bar\" \"\"\"x\"*".to_string())
+        );
+    }
+
+    #[test]
+    fn test_transcript_index_is_incremental() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        let user = |text: &str| format!(r#"{{"type":"user","message":{{"content":"{}"}}}}"#, text);
+        std::fs::write(
+            &path,
+            format!("{}\n{}\n", user("tokenizer crash"), user("unrelated")),
+        )
+        .unwrap();
+        let store = memory_store();
+        let files = vec![(path.clone(), "s1".to_string())];
+        assert_eq!(store.index_transcripts(&files).unwrap(), 2);
+        assert_eq!(store.index_transcripts(&files).unwrap(), 0);
+
+        // Appended lines, including one still being written.
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        use std::io::Write;
+        write!(f, "{}\n{{\"type\":\"us", user("tokenizer fixed")).unwrap();
+        drop(f);
+        assert_eq!(store.index_transcripts(&files).unwrap(), 1);
+
+        let hits = store.search_transcripts("tokeni", 10).unwrap();
+        assert_eq!(hits.len(), 2);
+        assert!(hits
+            .iter()
+            .all(|h| h.session_id == "s1" && h.kind == "user"));
+        let mut lines: Vec<usize> = hits.iter().map(|h| h.line).collect();
+        lines.sort();
+        assert_eq!(lines, vec![0, 2]);
+        assert!(hits.iter().any(|h| h.snippet.contains("[tokenizer] crash")));
+
+        // Rewritten shorter: indexed from scratch.
+        std::fs::write(&path, format!("{}\n", user("fresh start"))).unwrap();
+        store.index_transcripts(&files).unwrap();
+        assert!(store
+            .search_transcripts("tokenizer", 10)
+            .unwrap()
+            .is_empty());
+        assert_eq!(store.search_transcripts("fresh", 10).unwrap()[0].line, 0);
+
+        std::fs::remove_file(&path).unwrap();
+        store.index_transcripts(&files).unwrap();
+        assert!(store.search_transcripts("fresh", 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_jsonl_stats_roundtrip_and_prune() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s1.jsonl");
+        std::fs::write(
+            &path,
+            r#"{"type":"user","sessionId":"s1","cwd":"/tmp/p","timestamp":"2026-01-01T10:00:00Z"}"#,
+        )
+        .unwrap();
+        let stats = crate::session::SessionDiscovery::parse_jsonl(&path);
+        let mtime = std::fs::metadata(&path).unwrap().modified().unwrap();
+        let store = memory_store();
+        store
+            .save_jsonl_stats(&[
+                StatsEntry {
+                    path: path.clone(),
+                    mtime,
+                    size: 80,
+                    stats: stats.clone(),
+                },
+                StatsEntry {
+                    path: dir.path().join("deleted.jsonl"),
+                    mtime,
+                    size: 1,
+                    stats: Default::default(),
+                },
+            ])
+            .unwrap();
+
+        assert_eq!(store.prune_jsonl_stats().unwrap(), 1);
+        let loaded = store.load_jsonl_stats().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].path, path);
+        assert_eq!(loaded[0].mtime, mtime);
+        assert_eq!(format!("{:?}", loaded[0].stats), format!("{:?}", stats));
+
+        store
+            .conn
+            .execute("UPDATE jsonl_stats SET version = version + 1", [])
+            .unwrap();
+        assert!(store.load_jsonl_stats().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,886 @@
+use std::sync::Arc;
+
+use crate::tlog;
+
+use super::cache::{ResponseCache, Validators};
+use super::config::TervezoConfig;
+use super::error::TervezoError;
+use super::models::{
+    AnalysisResponse, ChangesResponse, CreateImplementationRequest, CreatePrResponse, FileChange,
+    Implementation, ListResponse, Org, OrgsResponse, PlanResponse, PrDetails, PromptRequest,
+    PromptResponse, QueuePositionRequest, RestartRequest, RestartResponse, SshCredentials,
+    StatusResponse, Step, StepsResponse, SuccessResponse, TestOutputResponse, TestReport,
+    TimelineMessage, Workspace, WorkspacesResponse,
+};
+use super::timeline_stream::{read_timeline, TimelineStats};
+
+fn simple_percent_encode(input: &str) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char);
+            }
+            _ => {
+                encoded.push_str(&format!("%{:02X}", byte));
+            }
+        }
+    }
+    encoded
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(body: &str, label: &str) -> Result<T, TervezoError> {
+    serde_json::from_str(body).map_err(|e| {
+        tlog!(error, "{} parse error: {} — body: {}", label, e, body);
+        TervezoError::Parse {
+            context: label.to_string(),
+            message: e.to_string(),
+        }
+    })
+}
+
+fn serialize<T: serde::Serialize>(value: &T, label: &str) -> Result<String, TervezoError> {
+    serde_json::to_string(value).map_err(|e| TervezoError::Parse {
+        context: label.to_string(),
+        message: e.to_string(),
+    })
+}
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+/// Selects the organization a request acts for; without it the server
+/// uses the key's default.
+pub const ORG_HEADER: &str = "X-Tervezo-Org";
+pub const TIMELINE_TIMEOUT_SECS: u64 = 60;
+/// Timeline messages per batch handed to `stream_timeline` callers.
+const TIMELINE_BATCH: usize = 200;
+/// Messages per `stream_timeline_page` request: the detail view opens
+/// with this many of the newest and loads older ones on request.
+pub const TIMELINE_PAGE: usize = 200;
+
+pub struct TervezoClient {
+    agent: ureq::Agent,
+    base_url: String,
+    api_key: String,
+    org: Option<String>,
+    cache: Arc<ResponseCache>,
+    cache_ttl: u64,
+}
+
+impl TervezoClient {
+    pub fn new(config: &TervezoConfig) -> Self {
+        Self::with_cache(config, ResponseCache::global())
+    }
+
+    pub fn with_cache(config: &TervezoConfig, cache: Arc<ResponseCache>) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .timeout_global(Some(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS)))
+            .http_status_as_error(false)
+            .build()
+            .new_agent();
+
+        Self {
+            agent,
+            base_url: config.base_url.trim_end_matches('/').to_string(),
+            api_key: config.api_key.clone(),
+            org: config.org.clone(),
+            cache,
+            cache_ttl: config.cache_ttl,
+        }
+    }
+
+    /// Makes cached responses for implementation `id` be revalidated on
+    /// their next use, e.g. after an explicit refresh.
+    pub fn expire_cached(&self, id: &str) {
+        self.cache
+            .expire(&format!("{}/implementations/{}/", self.base_url, id));
+    }
+
+    pub fn list_implementations(
+        &self,
+        status_filter: Option<&str>,
+    ) -> Result<Vec<Implementation>, TervezoError> {
+        let mut url = format!("{}/implementations", self.base_url);
+        if let Some(status) = status_filter {
+            url.push_str(&format!("?status={}", simple_percent_encode(status)));
+        }
+
+        let resp = self.get_cached(&self.agent, &url, 0)?;
+        let list: ListResponse = parse_json(&resp, "list_implementations")?;
+        tlog!(info, "parsed {} implementations", list.items.len());
+        Ok(list.items)
+    }
+
+    pub fn list_workspaces(&self) -> Result<Vec<Workspace>, TervezoError> {
+        let url = format!("{}/workspaces", self.base_url);
+        let resp = self.get_cached(&self.agent, &url, 0)?;
+        let list: WorkspacesResponse = parse_json(&resp, "list_workspaces")?;
+        tlog!(info, "parsed {} workspaces", list.items.len());
+        Ok(list.items)
+    }
+
+    /// The organizations and teams the key has access to.
+    pub fn list_orgs(&self) -> Result<Vec<Org>, TervezoError> {
+        let url = format!("{}/orgs", self.base_url);
+        let resp = self.get_cached(&self.agent, &url, 0)?;
+        let list: OrgsResponse = parse_json(&resp, "list_orgs")?;
+        tlog!(info, "parsed {} orgs", list.items.len());
+        Ok(list.items)
+    }
+
+    pub fn get_implementation(&self, id: &str) -> Result<Implementation, TervezoError> {
+        let url = format!("{}/implementations/{}", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, 0)?;
+        parse_json(&resp, "get_implementation")
+    }
+
+    pub fn get_timeline(
+        &self,
+        id: &str,
+        after_cursor: Option<&str>,
+    ) -> Result<Vec<TimelineMessage>, TervezoError> {
+        let mut messages = Vec::new();
+        self.stream_timeline(id, after_cursor, &mut |batch| messages.extend(batch))?;
+        if let Some(first) = messages.first() {
+            tlog!(
+                info,
+                "first parsed msg: type={:?} text='{}'",
+                first.msg_type,
+                first.display_text()
+            );
+        }
+        Ok(messages)
+    }
+
+    /// Fetches the timeline, parsing it as it arrives and passing messages
+    /// to `on_batch` `TIMELINE_BATCH` at a time, so a huge timeline can be
+    /// shown before it has finished downloading. Not cached: the payload is
+    /// never held whole.
+    pub fn stream_timeline(
+        &self,
+        id: &str,
+        after_cursor: Option<&str>,
+        on_batch: &mut dyn FnMut(Vec<TimelineMessage>),
+    ) -> Result<TimelineStats, TervezoError> {
+        let mut url = format!("{}/implementations/{}/timeline", self.base_url, id);
+        if let Some(cursor) = after_cursor {
+            url.push_str(&format!("?after={}", cursor));
+        }
+        self.stream_timeline_url(&url, on_batch)
+    }
+
+    /// Streams the newest `limit` messages older than message `before`
+    /// (the newest overall without it), oldest first. Whether there are
+    /// more before those is `has_more` on the result, always set.
+    pub fn stream_timeline_page(
+        &self,
+        id: &str,
+        before: Option<&str>,
+        limit: usize,
+        on_batch: &mut dyn FnMut(Vec<TimelineMessage>),
+    ) -> Result<TimelineStats, TervezoError> {
+        let mut url = format!(
+            "{}/implementations/{}/timeline?limit={}",
+            self.base_url, id, limit
+        );
+        if let Some(cursor) = before {
+            url.push_str(&format!("&before={}", simple_percent_encode(cursor)));
+        }
+        let mut stats = self.stream_timeline_url(&url, on_batch)?;
+        // Servers that don't say are assumed to have more after a full page.
+        stats.has_more = Some(
+            stats
+                .has_more
+                .unwrap_or(stats.parsed + stats.skipped >= limit),
+        );
+        Ok(stats)
+    }
+
+    fn stream_timeline_url(
+        &self,
+        url: &str,
+        on_batch: &mut dyn FnMut(Vec<TimelineMessage>),
+    ) -> Result<TimelineStats, TervezoError> {
+        let resp = check_status(self.send_get(&self.timeline_agent(), url, None)?, "")?;
+        let stats = read_timeline(resp.into_body().into_reader(), TIMELINE_BATCH, on_batch)
+            .map_err(|e| {
+                tlog!(error, "get_timeline parse error: {}", e);
+                if e.is_io() {
+                    TervezoError::Transport(e.to_string())
+                } else {
+                    TervezoError::Parse {
+                        context: "get_timeline".to_string(),
+                        message: e.to_string(),
+                    }
+                }
+            })?;
+        tlog!(
+            info,
+            "parsed {} timeline messages (skipped {})",
+            stats.parsed,
+            stats.skipped
+        );
+        Ok(stats)
+    }
+
+    pub fn get_plan(&self, id: &str) -> Result<String, TervezoError> {
+        let url = format!("{}/implementations/{}/plan", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        let plan: PlanResponse = parse_json(&resp, "get_plan")?;
+        tlog!(info, "parsed plan: {} chars", plan.plan.len());
+        Ok(plan.plan)
+    }
+
+    pub fn get_analysis(&self, id: &str) -> Result<String, TervezoError> {
+        let url = format!("{}/implementations/{}/analysis", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        let analysis: AnalysisResponse = parse_json(&resp, "get_analysis")?;
+        tlog!(info, "parsed analysis: {} chars", analysis.analysis.len());
+        Ok(analysis.analysis)
+    }
+
+    pub fn get_changes(&self, id: &str) -> Result<Vec<FileChange>, TervezoError> {
+        let url = format!("{}/implementations/{}/changes", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        let changes: ChangesResponse = parse_json(&resp, "get_changes")?;
+        let files: Vec<FileChange> = changes.files.into_iter().flatten().collect();
+        tlog!(info, "parsed {} file changes", files.len());
+        Ok(files)
+    }
+
+    pub fn get_test_output(&self, id: &str) -> Result<Vec<TestReport>, TervezoError> {
+        let url = format!("{}/implementations/{}/test-output", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        let tests: TestOutputResponse = parse_json(&resp, "get_test_output")?;
+
+        let mut reports = Vec::with_capacity(tests.test_reports.len());
+        let mut skipped = 0;
+        for raw in tests.test_reports {
+            match serde_json::from_value::<TestReport>(raw) {
+                Ok(report) => reports.push(report),
+                Err(e) => {
+                    skipped += 1;
+                    if skipped <= 3 {
+                        tlog!(warn, "test report parse skip: {}", e);
+                    }
+                }
+            }
+        }
+
+        tlog!(
+            info,
+            "parsed {} test reports (skipped {})",
+            reports.len(),
+            skipped
+        );
+        Ok(reports)
+    }
+
+    pub fn get_ssh(&self, id: &str) -> Result<SshCredentials, TervezoError> {
+        let url = format!("{}/implementations/{}/ssh", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        let creds: SshCredentials = parse_json(&resp, "get_ssh")?;
+        tlog!(info, "parsed SSH creds: host={}", creds.host);
+        Ok(creds)
+    }
+
+    #[allow(dead_code)]
+    pub fn get_steps(&self, id: &str) -> Result<Vec<Step>, TervezoError> {
+        let url = format!("{}/implementations/{}/steps", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, 0)?;
+        let steps: StepsResponse = parse_json(&resp, "get_steps")?;
+        tlog!(info, "parsed {} steps", steps.steps.len());
+        Ok(steps.steps)
+    }
+
+    pub fn get_status(&self, id: &str) -> Result<StatusResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/status", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        parse_json(&resp, "get_status")
+    }
+
+    pub fn get_pr_details(&self, id: &str) -> Result<PrDetails, TervezoError> {
+        let url = format!("{}/implementations/{}/pr", self.base_url, id);
+        let resp = self.get_cached(&self.agent, &url, self.cache_ttl)?;
+        parse_json(&resp, "get_pr_details")
+    }
+
+    pub fn create_pr(&self, id: &str) -> Result<CreatePrResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/pr", self.base_url, id);
+        let resp = self.post(&url, "{}")?;
+        parse_json(&resp, "create_pr")
+    }
+
+    pub fn merge_pr(&self, id: &str) -> Result<SuccessResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/pr/merge", self.base_url, id);
+        let resp = self.post(&url, "{}")?;
+        parse_json(&resp, "merge_pr")
+    }
+
+    pub fn close_pr(&self, id: &str) -> Result<SuccessResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/pr/close", self.base_url, id);
+        let resp = self.post(&url, "{}")?;
+        parse_json(&resp, "close_pr")
+    }
+
+    pub fn reopen_pr(&self, id: &str) -> Result<SuccessResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/pr/reopen", self.base_url, id);
+        let resp = self.post(&url, "{}")?;
+        parse_json(&resp, "reopen_pr")
+    }
+
+    pub fn restart(&self, id: &str) -> Result<RestartResponse, TervezoError> {
+        self.restart_with_guidance(id, None)
+    }
+
+    /// Restarts with `guidance` for the agent, e.g. what to do differently
+    /// after a failure.
+    pub fn restart_with_guidance(
+        &self,
+        id: &str,
+        guidance: Option<&str>,
+    ) -> Result<RestartResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/restart", self.base_url, id);
+        let body = serialize(
+            &RestartRequest {
+                guidance: guidance.map(str::to_string),
+            },
+            "restart",
+        )?;
+        let resp = self.post(&url, &body)?;
+        parse_json(&resp, "restart")
+    }
+
+    /// Moves a waiting implementation to `position` in the queue, 0 being
+    /// the next to start.
+    pub fn move_in_queue(
+        &self,
+        id: &str,
+        position: usize,
+    ) -> Result<SuccessResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/queue", self.base_url, id);
+        let body = serialize(&QueuePositionRequest { position }, "move_in_queue")?;
+        let resp = self.post(&url, &body)?;
+        parse_json(&resp, "move_in_queue")
+    }
+
+    /// Cancels an implementation that hasn't started yet.
+    pub fn cancel(&self, id: &str) -> Result<SuccessResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/cancel", self.base_url, id);
+        let resp = self.post(&url, "{}")?;
+        parse_json(&resp, "cancel")
+    }
+
+    pub fn send_prompt(&self, id: &str, message: &str) -> Result<PromptResponse, TervezoError> {
+        let url = format!("{}/implementations/{}/prompt", self.base_url, id);
+        let body = serialize(
+            &PromptRequest {
+                message: message.to_string(),
+            },
+            "send_prompt",
+        )?;
+        let resp = self.post(&url, &body)?;
+        parse_json(&resp, "send_prompt")
+    }
+
+    #[allow(dead_code)]
+    pub fn create_implementation(
+        &self,
+        request: &CreateImplementationRequest,
+    ) -> Result<Implementation, TervezoError> {
+        let url = format!("{}/implementations", self.base_url);
+        let body = serialize(request, "create_implementation")?;
+        let resp = self.post(&url, &body)?;
+        parse_json(&resp, "create_implementation")
+    }
+
+    /// Timeline payloads can be very large, so they get a longer timeout.
+    fn timeline_agent(&self) -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .timeout_global(Some(std::time::Duration::from_secs(TIMELINE_TIMEOUT_SECS)))
+            .http_status_as_error(false)
+            .build()
+            .new_agent()
+    }
+
+    /// GET through the response cache: an entry younger than `ttl_secs` is
+    /// returned as is, an older one is revalidated with its ETag or
+    /// Last-Modified.
+    fn get_cached(
+        &self,
+        agent: &ureq::Agent,
+        url: &str,
+        ttl_secs: u64,
+    ) -> Result<String, TervezoError> {
+        // The same URL answers differently for each org.
+        let key = match self.org {
+            Some(ref org) => format!("{}#org={}", url, org),
+            None => url.to_string(),
+        };
+        if let Some(body) = self.cache.fresh(&key, ttl_secs) {
+            tlog!(info, "GET {} (cached)", url);
+            return Ok(body);
+        }
+        let validators = self.cache.validators(&key);
+        let resp = self.send_get(agent, url, validators.as_ref())?;
+        if resp.status().as_u16() == 304 {
+            tlog!(info, "response: HTTP 304");
+            if let Some(body) = self.cache.revalidated(&key) {
+                return Ok(body);
+            }
+            // Evicted since we read the validators; ask again without them.
+            let resp = self.send_get(agent, url, None)?;
+            return read_response(resp, "");
+        }
+        let header = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let validators = Validators {
+            etag: header("etag"),
+            last_modified: header("last-modified"),
+        };
+        let body = read_response(resp, "")?;
+        self.cache.store(&key, &body, validators);
+        Ok(body)
+    }
+
+    fn send_get(
+        &self,
+        agent: &ureq::Agent,
+        url: &str,
+        validators: Option<&Validators>,
+    ) -> Result<ureq::http::Response<ureq::Body>, TervezoError> {
+        let revalidate = if validators.is_some() {
+            " (revalidate)"
+        } else {
+            ""
+        };
+        tlog!(info, "GET {}{}", url, revalidate);
+        let mut req = agent
+            .get(url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("User-Agent", "c9s/0.1")
+            .header("Accept", "application/json");
+        if let Some(ref org) = self.org {
+            req = req.header(ORG_HEADER, org);
+        }
+        if let Some(v) = validators {
+            if let Some(ref etag) = v.etag {
+                req = req.header("If-None-Match", etag);
+            }
+            if let Some(ref modified) = v.last_modified {
+                req = req.header("If-Modified-Since", modified);
+            }
+        }
+        req.call().map_err(|e| {
+            tlog!(error, "request error: {}", e);
+            TervezoError::from_ureq(e)
+        })
+    }
+
+    fn post(&self, url: &str, json_body: &str) -> Result<String, TervezoError> {
+        tlog!(info, "POST {}", url);
+        let mut req = self
+            .agent
+            .post(url)
+            .header("Authorization", &format!("Bearer {}", self.api_key))
+            .header("User-Agent", "c9s/0.1")
+            .header("Accept", "application/json")
+            .header("Content-Type", "application/json");
+        if let Some(ref org) = self.org {
+            req = req.header(ORG_HEADER, org);
+        }
+        let resp = req.send(json_body.as_bytes()).map_err(|e| {
+            tlog!(error, "POST request error: {}", e);
+            TervezoError::from_ureq(e)
+        })?;
+        let body = read_response(resp, "POST ")?;
+        // Actions change what the detail endpoints return.
+        if let Some(id) = url
+            .strip_prefix(&format!("{}/implementations/", self.base_url))
+            .and_then(|rest| rest.split('/').next())
+        {
+            self.expire_cached(id);
+        }
+        Ok(body)
+    }
+}
+
+/// Returns the body of a 200/201 response, or the classified error.
+fn read_response(
+    resp: ureq::http::Response<ureq::Body>,
+    log_prefix: &str,
+) -> Result<String, TervezoError> {
+    let body = check_status(resp, log_prefix)?
+        .into_body()
+        .read_to_string()
+        .map_err(TervezoError::from_ureq)?;
+    tlog!(info, "{}response body: {} bytes", log_prefix, body.len());
+    Ok(body)
+}
+
+/// Passes a 200/201 response through, or returns the classified error.
+fn check_status(
+    resp: ureq::http::Response<ureq::Body>,
+    log_prefix: &str,
+) -> Result<ureq::http::Response<ureq::Body>, TervezoError> {
+    let status = resp.status().as_u16();
+    tlog!(info, "{}response: HTTP {}", log_prefix, status);
+
+    if status != 200 && status != 201 {
+        let retry_after = resp
+            .headers()
+            .get("retry-after")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok());
+        let body = resp
+            .into_body()
+            .read_to_string()
+            .unwrap_or_else(|_| "(unreadable body)".to_string());
+        tlog!(error, "{}HTTP {}: {}", log_prefix, status, body);
+        return Err(TervezoError::from_status(status, retry_after, body));
+    }
+    Ok(resp)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tervezo::mock::{MockServer, Reply, API_KEY};
+
+    const IMPL: &str = r#"{"id":"impl_1","title":"Fix login","status":"running"}"#;
+
+    #[test]
+    fn test_list_implementations() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations",
+            Reply::Json(200, format!(r#"{{"items":[{}],"total":1}}"#, IMPL)),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let items = client.list_implementations(Some("in progress")).unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].display_name(), "Fix login");
+
+        let req = &server.requests()[0];
+        assert_eq!(req.path, "/implementations?status=in%20progress");
+        assert_eq!(
+            req.headers.get("authorization").map(String::as_str),
+            Some(format!("Bearer {}", API_KEY).as_str())
+        );
+    }
+
+    #[test]
+    fn test_http_errors_are_classified() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1",
+            Reply::Json(429, r#"{"error":"rate limited"}"#.to_string()),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let err = client.get_implementation("impl_1").unwrap_err();
+        assert_eq!(err, TervezoError::RateLimited { retry_after: None });
+        let err = client.get_plan("missing").unwrap_err();
+        assert!(
+            matches!(err, TervezoError::Http { status: 404, ref body } if body.contains("not found")),
+            "{:?}",
+            err
+        );
+
+        server.on(
+            "GET",
+            "/workspaces",
+            Reply::Json(401, r#"{"error":"bad key"}"#.to_string()),
+        );
+        let err = client.list_workspaces().unwrap_err();
+        assert_eq!(err, TervezoError::Auth { status: 401 });
+        assert!(err.hint().unwrap().contains("API key"));
+    }
+
+    #[test]
+    fn test_malformed_json_is_a_parse_error() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1/plan",
+            Reply::Json(200, r##"{"plan": "# Plan"##.to_string()),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let err = client.get_plan("impl_1").unwrap_err();
+        assert!(
+            matches!(err, TervezoError::Parse { ref context, .. } if context == "get_plan"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_timeline_skips_bad_messages() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1/timeline",
+            Reply::Json(
+                200,
+                r#"{"messages":[
+                    {"id":"m1","type":"agent_message","message":"Reading the code"},
+                    null,
+                    {"id":"m2","timestamp":"yesterday"},
+                    {"id":"m3","type":"status_change","toStatus":"running"}
+                ]}"#
+                .to_string(),
+            ),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let msgs = client.get_timeline("impl_1", Some("m0")).unwrap();
+        let ids: Vec<_> = msgs.iter().filter_map(|m| m.id.as_deref()).collect();
+        assert_eq!(ids, ["m1", "m3"]);
+        assert_eq!(
+            server.requests()[0].path,
+            "/implementations/impl_1/timeline?after=m0"
+        );
+    }
+
+    #[test]
+    fn test_timeline_pages_backwards() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1/timeline",
+            Reply::Json(
+                200,
+                r#"{"messages":[
+                    {"id":"m1","type":"agent_message","message":"a"},
+                    {"id":"m2","type":"agent_message","message":"b"}
+                ]}"#
+                .to_string(),
+            ),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let mut got = Vec::new();
+        let full = client
+            .stream_timeline_page("impl_1", None, 2, &mut |b| got.extend(b))
+            .unwrap();
+        assert_eq!(got.len(), 2);
+        assert_eq!(full.has_more, Some(true));
+
+        let short = client
+            .stream_timeline_page("impl_1", Some("m 1"), 5, &mut |_| {})
+            .unwrap();
+        assert_eq!(short.has_more, Some(false));
+        let paths: Vec<_> = server.requests().into_iter().map(|r| r.path).collect();
+        assert_eq!(
+            paths,
+            [
+                "/implementations/impl_1/timeline?limit=2",
+                "/implementations/impl_1/timeline?limit=5&before=m%201"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_create_implementation_posts_json() {
+        let server = MockServer::start();
+        server.on(
+            "POST",
+            "/implementations",
+            Reply::Json(201, IMPL.to_string()),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let created = client
+            .create_implementation(&CreateImplementationRequest {
+                prompt: "Fix login".to_string(),
+                mode: "bugfix".to_string(),
+                workspace_id: "ws_1".to_string(),
+                repository_name: None,
+                base_branch: Some("main".to_string()),
+            })
+            .unwrap();
+        assert_eq!(created.id, "impl_1");
+
+        let body: serde_json::Value = serde_json::from_str(&server.requests()[0].body).unwrap();
+        assert_eq!(body["workspaceId"], "ws_1");
+        assert!(body.get("repositoryName").is_none());
+    }
+
+    #[test]
+    fn test_restart_with_guidance() {
+        let server = MockServer::start();
+        server.on(
+            "POST",
+            "/implementations/impl_1/restart",
+            Reply::Json(200, r#"{"isNewImplementation":false}"#.to_string()),
+        );
+        let client = TervezoClient::new(&server.config());
+        client.restart("impl_1").unwrap();
+        client
+            .restart_with_guidance("impl_1", Some("Pin the lockfile"))
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].body, "{}");
+        assert_eq!(requests[1].body, r#"{"guidance":"Pin the lockfile"}"#);
+    }
+
+    #[test]
+    fn test_queue_actions() {
+        let server = MockServer::start();
+        for path in [
+            "/implementations/impl_2/queue",
+            "/implementations/impl_2/cancel",
+        ] {
+            server.on(
+                "POST",
+                path,
+                Reply::Json(200, r#"{"success":true}"#.to_string()),
+            );
+        }
+        let client = TervezoClient::new(&server.config());
+        assert!(client.move_in_queue("impl_2", 0).unwrap().success);
+        assert!(client.cancel("impl_2").unwrap().success);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].body, r#"{"position":0}"#);
+        assert_eq!(requests[1].path, "/implementations/impl_2/cancel");
+    }
+
+    #[test]
+    fn test_org_is_sent_and_cached_separately() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/orgs",
+            Reply::Json(
+                200,
+                r#"{"items":[{"id":"org_1","name":"Personal"},{"id":"org_2","name":"Platform"}]}"#
+                    .to_string(),
+            ),
+        );
+        server.on(
+            "GET",
+            "/implementations/impl_1/plan",
+            Reply::Json(200, r#"{"plan":"Step 1"}"#.to_string()),
+        );
+        let mut config = server.config();
+        config.cache_ttl = 30;
+        let cache = Arc::new(ResponseCache::new(crate::clock::MockClock::new()));
+
+        let orgs = TervezoClient::with_cache(&config, cache.clone())
+            .list_orgs()
+            .unwrap();
+        assert_eq!(orgs[1].name, "Platform");
+        TervezoClient::with_cache(&config, cache.clone())
+            .get_plan("impl_1")
+            .unwrap();
+
+        config.org = Some("org_2".to_string());
+        TervezoClient::with_cache(&config, cache)
+            .get_plan("impl_1")
+            .unwrap();
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert_eq!(requests[1].headers.get("x-tervezo-org"), None);
+        assert_eq!(
+            requests[2].headers.get("x-tervezo-org").map(String::as_str),
+            Some("org_2")
+        );
+    }
+
+    #[test]
+    fn test_detail_responses_are_cached() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1/plan",
+            Reply::Tagged("\"p1\"".to_string(), r#"{"plan":"Step 1"}"#.to_string()),
+        );
+        server.on(
+            "POST",
+            "/implementations/impl_1/restart",
+            Reply::Json(200, r#"{"isNewImplementation":false}"#.to_string()),
+        );
+        let clock = crate::clock::MockClock::new();
+        let mut config = server.config();
+        config.cache_ttl = 30;
+        let client =
+            TervezoClient::with_cache(&config, Arc::new(ResponseCache::new(clock.clone())));
+
+        // Within the TTL the second open never reaches the server.
+        assert_eq!(client.get_plan("impl_1").unwrap(), "Step 1");
+        assert_eq!(client.get_plan("impl_1").unwrap(), "Step 1");
+        assert_eq!(server.requests().len(), 1);
+
+        // Past it, the ETag is sent and the 304 reuses the cached body.
+        clock.advance(std::time::Duration::from_secs(31));
+        assert_eq!(client.get_plan("impl_1").unwrap(), "Step 1");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1].headers.get("if-none-match").map(String::as_str),
+            Some("\"p1\"")
+        );
+
+        // An action on the implementation forces a revalidation.
+        client.restart("impl_1").unwrap();
+        client.get_plan("impl_1").unwrap();
+        assert_eq!(server.requests().len(), 4);
+    }
+
+    #[test]
+    fn test_list_is_revalidated_with_last_modified() {
+        let server = MockServer::start();
+        let date = "Wed, 14 Oct 2026 09:00:00 GMT";
+        server.on(
+            "GET",
+            "/implementations",
+            Reply::LastModified(
+                date.to_string(),
+                format!(r#"{{"items":[{}],"total":1}}"#, IMPL),
+            ),
+        );
+        let client = TervezoClient::with_cache(
+            &server.config(),
+            Arc::new(ResponseCache::new(crate::clock::system())),
+        );
+
+        assert_eq!(client.list_implementations(None).unwrap().len(), 1);
+        // Polling re-asks every time, but an unchanged list comes back as a
+        // 304 and is served from the cache.
+        assert_eq!(client.list_implementations(None).unwrap().len(), 1);
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(
+            requests[1]
+                .headers
+                .get("if-modified-since")
+                .map(String::as_str),
+            Some(date)
+        );
+    }
+
+    #[test]
+    fn test_gzip_responses_are_decoded() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations/impl_1/changes",
+            Reply::Gzip(r#"{"files":[{"filename":"src/lib.rs","status":"modified"}]}"#.to_string()),
+        );
+        let client = TervezoClient::new(&server.config());
+
+        let files = client.get_changes("impl_1").unwrap();
+        assert_eq!(files.len(), 1);
+        let accept = server.requests()[0].headers.get("accept-encoding").cloned();
+        assert!(accept.is_some_and(|e| e.contains("gzip")));
+    }
+}
@@ -0,0 +1,145 @@
+//! Response cache for Tervezo GETs. `TervezoClient`s are created per
+//! request, so they share one process-wide cache: a detail response younger
+//! than the TTL is served without touching the network, and anything else
+//! is revalidated with `If-None-Match` / `If-Modified-Since` when the server
+//! sent an ETag or Last-Modified, so unchanged payloads cost only a 304.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::clock::{self, SharedClock};
+
+/// Oldest entries are evicted past this many.
+const MAX_ENTRIES: usize = 256;
+
+/// What a response can be revalidated with.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Validators {
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl Validators {
+    pub fn is_empty(&self) -> bool {
+        self.etag.is_none() && self.last_modified.is_none()
+    }
+}
+
+struct Entry {
+    body: String,
+    validators: Validators,
+    fetched_at: u64,
+    /// Set by `expire`: revalidate on next use whatever the age.
+    stale: bool,
+}
+
+pub struct ResponseCache {
+    entries: Mutex<HashMap<String, Entry>>,
+    clock: SharedClock,
+}
+
+impl ResponseCache {
+    pub fn new(clock: SharedClock) -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            clock,
+        }
+    }
+
+    /// The cache every `TervezoClient::new` uses.
+    pub fn global() -> Arc<ResponseCache> {
+        static GLOBAL: OnceLock<Arc<ResponseCache>> = OnceLock::new();
+        GLOBAL
+            .get_or_init(|| Arc::new(ResponseCache::new(clock::system())))
+            .clone()
+    }
+
+    /// The body for `url` if it was fetched less than `ttl_secs` ago.
+    pub fn fresh(&self, url: &str, ttl_secs: u64) -> Option<String> {
+        let entries = self.entries.lock().unwrap();
+        let entry = entries.get(url)?;
+        let age = self.clock.now_millis().saturating_sub(entry.fetched_at);
+        (!entry.stale && age < ttl_secs * 1000).then(|| entry.body.clone())
+    }
+
+    /// What to revalidate `url` with, if the server sent anything.
+    pub fn validators(&self, url: &str) -> Option<Validators> {
+        let entries = self.entries.lock().unwrap();
+        let validators = &entries.get(url)?.validators;
+        (!validators.is_empty()).then(|| validators.clone())
+    }
+
+    /// Marks `url` as confirmed unchanged (a 304) and returns its body.
+    pub fn revalidated(&self, url: &str) -> Option<String> {
+        let mut entries = self.entries.lock().unwrap();
+        let entry = entries.get_mut(url)?;
+        entry.fetched_at = self.clock.now_millis();
+        entry.stale = false;
+        Some(entry.body.clone())
+    }
+
+    pub fn store(&self, url: &str, body: &str, validators: Validators) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(url) {
+            if let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, e)| e.fetched_at)
+                .map(|(k, _)| k.clone())
+            {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(
+            url.to_string(),
+            Entry {
+                body: body.to_string(),
+                validators,
+                fetched_at: self.clock.now_millis(),
+                stale: false,
+            },
+        );
+    }
+
+    /// Forces every URL starting with `prefix` to be revalidated on next
+    /// use. Validators are kept, so unchanged responses still cost only a
+    /// 304.
+    pub fn expire(&self, prefix: &str) {
+        for (url, entry) in self.entries.lock().unwrap().iter_mut() {
+            if url.starts_with(prefix) {
+                entry.stale = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_ttl_and_expire() {
+        let clock = MockClock::new();
+        let cache = ResponseCache::new(clock.clone());
+        let tag = Validators {
+            etag: Some("\"v1\"".into()),
+            last_modified: None,
+        };
+        cache.store("/impl/1/plan", "plan", tag.clone());
+        assert_eq!(cache.fresh("/impl/1/plan", 30).as_deref(), Some("plan"));
+
+        clock.advance(Duration::from_secs(31));
+        assert_eq!(cache.fresh("/impl/1/plan", 30), None);
+        assert_eq!(cache.validators("/impl/1/plan"), Some(tag));
+        assert_eq!(cache.revalidated("/impl/1/plan").as_deref(), Some("plan"));
+        assert!(cache.fresh("/impl/1/plan", 30).is_some());
+
+        cache.expire("/impl/1/");
+        assert_eq!(cache.fresh("/impl/1/plan", 30), None);
+        assert!(cache.validators("/impl/1/plan").is_some());
+
+        cache.store("/impl/1/changes", "[]", Validators::default());
+        assert_eq!(cache.validators("/impl/1/changes"), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! What a restarted implementation changed differently from the attempt
+//! it retried: the two change sets side by side, per file, with a diff of
+//! their patches.
+
+use super::models::FileChange;
+
+/// Patches longer than this (in lines, multiplied) are shown whole rather
+/// than diffed line by line.
+const MAX_DIFF_CELLS: usize = 4_000_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delta {
+    /// Only the previous attempt touched the file.
+    Dropped,
+    /// Only the retry touched the file.
+    New,
+    /// Both changed the file, differently.
+    Changed,
+    /// Both made the same change.
+    Same,
+}
+
+impl Delta {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Dropped => "dropped",
+            Self::New => "new",
+            Self::Changed => "changed",
+            Self::Same => "same",
+        }
+    }
+}
+
+/// A line of the diff between two patches. The text keeps the patch's own
+/// `+`/`-`/` ` prefix.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Both(String),
+    Before(String),
+    After(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct FileComparison {
+    pub path: String,
+    pub delta: Delta,
+    /// Lines added and removed by each attempt, if it touched the file.
+    pub before: Option<(u32, u32)>,
+    pub after: Option<(u32, u32)>,
+    pub lines: Vec<DiffLine>,
+}
+
+/// Every file either attempt changed, differences first, then by path.
+pub fn compare_changes(before: &[FileChange], after: &[FileChange]) -> Vec<FileComparison> {
+    let mut paths: Vec<&str> = before
+        .iter()
+        .chain(after)
+        .map(FileChange::display_path)
+        .collect();
+    paths.sort_unstable();
+    paths.dedup();
+
+    let find = |changes: &[FileChange], path: &str| -> Option<Vec<String>> {
+        changes
+            .iter()
+            .find(|c| c.display_path() == path)
+            .map(|c| patch_lines(c.diff.as_deref().unwrap_or("")))
+    };
+
+    let mut files: Vec<FileComparison> = paths
+        .into_iter()
+        .map(|path| {
+            let old = find(before, path);
+            let new = find(after, path);
+            let (delta, lines) = match (&old, &new) {
+                (Some(old), None) => (
+                    Delta::Dropped,
+                    old.iter().cloned().map(DiffLine::Before).collect(),
+                ),
+                (None, Some(new)) => (
+                    Delta::New,
+                    new.iter().cloned().map(DiffLine::After).collect(),
+                ),
+                (Some(old), Some(new)) if old == new => (Delta::Same, Vec::new()),
+                (Some(old), Some(new)) => (Delta::Changed, diff_lines(old, new)),
+                (None, None) => unreachable!("path comes from one of the change sets"),
+            };
+            FileComparison {
+                path: path.to_string(),
+                delta,
+                before: old.as_deref().map(counts),
+                after: new.as_deref().map(counts),
+                lines,
+            }
+        })
+        .collect();
+    files.sort_by_key(|f| f.delta == Delta::Same);
+    files
+}
+
+/// The patch without file and hunk headers, whose line numbers move
+/// whenever anything above them does.
+fn patch_lines(diff: &str) -> Vec<String> {
+    diff.lines()
+        .filter(|l| !l.starts_with("@@") && !l.starts_with("+++") && !l.starts_with("---"))
+        .map(str::to_string)
+        .collect()
+}
+
+fn counts(lines: &[String]) -> (u32, u32) {
+    let added = lines.iter().filter(|l| l.starts_with('+')).count();
+    let removed = lines.iter().filter(|l| l.starts_with('-')).count();
+    (added as u32, removed as u32)
+}
+
+/// Longest-common-subsequence diff of two patches.
+fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    if old.len().saturating_mul(new.len()) > MAX_DIFF_CELLS {
+        return old
+            .iter()
+            .cloned()
+            .map(DiffLine::Before)
+            .chain(new.iter().cloned().map(DiffLine::After))
+            .collect();
+    }
+
+    // common[i][j]: length of the LCS of old[i..] and new[j..].
+    let width = new.len() + 1;
+    let mut common = vec![0u32; (old.len() + 1) * width];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i * width + j] = if old[i] == new[j] {
+                common[(i + 1) * width + j + 1] + 1
+            } else {
+                common[(i + 1) * width + j].max(common[i * width + j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i] == new[j] {
+            lines.push(DiffLine::Both(old[i].clone()));
+            i += 1;
+            j += 1;
+        } else if common[(i + 1) * width + j] >= common[i * width + j + 1] {
+            lines.push(DiffLine::Before(old[i].clone()));
+            i += 1;
+        } else {
+            lines.push(DiffLine::After(new[j].clone()));
+            j += 1;
+        }
+    }
+    lines.extend(old[i..].iter().cloned().map(DiffLine::Before));
+    lines.extend(new[j..].iter().cloned().map(DiffLine::After));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(path: &str, diff: &str) -> FileChange {
+        FileChange {
+            path: Some(path.to_string()),
+            diff: Some(diff.to_string()),
+            status: None,
+            additions: None,
+            deletions: None,
+            changes: None,
+        }
+    }
+
+    #[test]
+    fn test_compare_changes() {
+        let before = [
+            change("src/lib.rs", "@@ -1,2 +1,3 @@\n fn a() {}\n+fn b() {}\n"),
+            change("src/old.rs", "@@ -0,0 +1 @@\n+gone\n"),
+            change("README.md", "@@ -1 +1 @@\n-x\n+y\n"),
+        ];
+        let after = [
+            change("README.md", "@@ -3 +3 @@\n-x\n+y\n"),
+            change("src/lib.rs", "@@ -1,2 +1,3 @@\n fn a() {}\n+fn c() {}\n"),
+            change("src/new.rs", "@@ -0,0 +1 @@\n+fresh\n"),
+        ];
+
+        let files = compare_changes(&before, &after);
+        let summary: Vec<_> = files.iter().map(|f| (f.path.as_str(), f.delta)).collect();
+        assert_eq!(
+            summary,
+            [
+                ("src/lib.rs", Delta::Changed),
+                ("src/new.rs", Delta::New),
+                ("src/old.rs", Delta::Dropped),
+                // Only the hunk header moved.
+                ("README.md", Delta::Same),
+            ]
+        );
+        assert_eq!(
+            files[0].lines,
+            [
+                DiffLine::Both(" fn a() {}".to_string()),
+                DiffLine::Before("+fn b() {}".to_string()),
+                DiffLine::After("+fn c() {}".to_string()),
+            ]
+        );
+        assert_eq!(files[0].before, Some((1, 0)));
+        assert_eq!(files[2].after, None);
+    }
+
+    #[test]
+    fn test_diff_lines_keeps_common_order() {
+        let lines = |s: &str| s.split(' ').map(str::to_string).collect::<Vec<_>>();
+        let diff = diff_lines(&lines("a b c d"), &lines("a c d e"));
+        assert_eq!(
+            diff,
+            [
+                DiffLine::Both("a".to_string()),
+                DiffLine::Before("b".to_string()),
+                DiffLine::Both("c".to_string()),
+                DiffLine::Both("d".to_string()),
+                DiffLine::After("e".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,239 @@
+use crate::tlog;
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::models::ImplementationStatus;
+
+const DEFAULT_BASE_URL: &str = "https://app.tervezo.ai/api/v1";
+const DEFAULT_POLL_INTERVAL: u64 = 30;
+const DEFAULT_CACHE_TTL: u64 = 30;
+const DEFAULT_RESTART_TEMPLATE: &str = "The last attempt failed: {reason}. ";
+
+#[derive(Debug, Clone)]
+pub struct TervezoConfig {
+    pub api_key: String,
+    pub base_url: String,
+    pub poll_interval: u64,
+    /// Statuses that ring the bell when an implementation moves into them.
+    pub notify_on: Vec<ImplementationStatus>,
+    /// Seconds a detail response (plan, changes, ...) is reused without
+    /// asking the server again.
+    pub cache_ttl: u64,
+    /// Organization or team requests act for; the key's default if unset.
+    pub org: Option<String>,
+    /// Pre-fills the guidance asked for when restarting a failed
+    /// implementation; `{reason}` is its last error.
+    pub restart_template: String,
+}
+
+/// An org picked at runtime, remembered per API key and server in
+/// `~/.c9s/tervezo_orgs.json` so it survives restarts and doesn't follow
+/// a different key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SavedOrg {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Deserialize)]
+struct ConfigFile {
+    tervezo: Option<TervezoSection>,
+}
+
+#[derive(Deserialize)]
+struct TervezoSection {
+    api_key: Option<String>,
+    base_url: Option<String>,
+    poll_interval: Option<u64>,
+    notify_on: Option<Vec<String>>,
+    cache_ttl: Option<u64>,
+    org: Option<String>,
+    restart_template: Option<String>,
+}
+
+impl TervezoConfig {
+    pub fn load() -> Option<Self> {
+        let api_key = Self::resolve_api_key()?;
+
+        let section = Self::read_config_file();
+        let section = section.as_ref();
+
+        let mut config = Self {
+            api_key,
+            base_url: section
+                .and_then(|s| s.base_url.clone())
+                .unwrap_or_else(|| DEFAULT_BASE_URL.to_string()),
+            poll_interval: section
+                .and_then(|s| s.poll_interval)
+                .unwrap_or(DEFAULT_POLL_INTERVAL),
+            notify_on: parse_notify_on(section.and_then(|s| s.notify_on.clone())),
+            cache_ttl: section
+                .and_then(|s| s.cache_ttl)
+                .unwrap_or(DEFAULT_CACHE_TTL),
+            org: section.and_then(|s| s.org.clone()),
+            restart_template: section
+                .and_then(|s| s.restart_template.clone())
+                .unwrap_or_else(|| DEFAULT_RESTART_TEMPLATE.to_string()),
+        };
+        if let Some(saved) = config.saved_org() {
+            config.org = Some(saved.id);
+        }
+
+        tlog!(
+            info,
+            "config loaded: base_url={} poll={}s key={} org={}",
+            config.base_url,
+            config.poll_interval,
+            config.key_hint(),
+            config.org.as_deref().unwrap_or("-"),
+        );
+
+        Some(config)
+    }
+
+    /// The restart guidance to start from for a failure with `reason`;
+    /// empty when the reason is unknown.
+    pub fn restart_guidance(&self, reason: Option<&str>) -> String {
+        match reason {
+            Some(reason) => self.restart_template.replace("{reason}", reason),
+            None => String::new(),
+        }
+    }
+
+    /// The start and end of the key, enough to tell keys apart in logs.
+    pub fn key_hint(&self) -> String {
+        format!(
+            "{}...{}",
+            &self.api_key[..6.min(self.api_key.len())],
+            &self.api_key[self.api_key.len().saturating_sub(4)..]
+        )
+    }
+
+    fn profile(&self) -> String {
+        format!("{} {}", self.base_url, self.key_hint())
+    }
+
+    /// The org last picked with this key on this server.
+    pub fn saved_org(&self) -> Option<SavedOrg> {
+        read_saved_orgs(&saved_orgs_path()?).remove(&self.profile())
+    }
+
+    /// Remembers `org` for this key and server.
+    pub fn save_org(&self, org: &SavedOrg) {
+        let Some(path) = saved_orgs_path() else {
+            return;
+        };
+        let mut saved = read_saved_orgs(&path);
+        saved.insert(self.profile(), org.clone());
+        if let Err(e) = write_saved_orgs(&path, &saved) {
+            tlog!(warn, "could not save the org to {}: {}", path.display(), e);
+        }
+    }
+
+    fn resolve_api_key() -> Option<String> {
+        if let Ok(key) = std::env::var("TERVEZO_API_KEY") {
+            if !key.is_empty() {
+                return Some(key);
+            }
+        }
+
+        let section = Self::read_config_file()?;
+        section.api_key.filter(|k| !k.is_empty())
+    }
+
+    fn read_config_file() -> Option<TervezoSection> {
+        let path = config_path()?;
+        let content = std::fs::read_to_string(path).ok()?;
+        let file: ConfigFile = toml::from_str(&content).ok()?;
+        file.tervezo
+    }
+}
+
+/// Completed, failed and merged by default. Unknown names are logged and
+/// skipped rather than failing the whole config.
+fn parse_notify_on(raw: Option<Vec<String>>) -> Vec<ImplementationStatus> {
+    let Some(raw) = raw else {
+        return vec![
+            ImplementationStatus::Completed,
+            ImplementationStatus::Failed,
+            ImplementationStatus::Merged,
+        ];
+    };
+    raw.into_iter()
+        .filter_map(|name| {
+            let status = toml::Value::String(name.to_lowercase())
+                .try_into::<ImplementationStatus>()
+                .ok();
+            if status.is_none() {
+                tlog!(warn, "tervezo.notify_on: unknown status {:?}", name);
+            }
+            status
+        })
+        .collect()
+}
+
+fn config_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".c9s").join("config.toml"))
+}
+
+fn saved_orgs_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|h| h.join(".c9s").join("tervezo_orgs.json"))
+}
+
+fn read_saved_orgs(path: &Path) -> HashMap<String, SavedOrg> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn write_saved_orgs(path: &Path, saved: &HashMap<String, SavedOrg>) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(saved)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_path() {
+        let path = config_path();
+        assert!(path.is_some());
+        let p = path.unwrap();
+        assert!(p.ends_with(".c9s/config.toml"));
+    }
+
+    #[test]
+    fn test_parse_notify_on() {
+        assert_eq!(parse_notify_on(None).len(), 3);
+        assert_eq!(
+            parse_notify_on(Some(vec!["Failed".into(), "bogus".into()])),
+            vec![ImplementationStatus::Failed]
+        );
+        assert!(parse_notify_on(Some(Vec::new())).is_empty());
+    }
+
+    #[test]
+    fn test_saved_orgs_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tervezo_orgs.json");
+        assert!(read_saved_orgs(&path).is_empty());
+
+        let org = SavedOrg {
+            id: "org_2".into(),
+            name: "Platform".into(),
+        };
+        let mut saved = HashMap::new();
+        saved.insert("https://x/api tzv_ab...wxyz".to_string(), org.clone());
+        write_saved_orgs(&path, &saved).unwrap();
+        assert_eq!(
+            read_saved_orgs(&path).remove("https://x/api tzv_ab...wxyz"),
+            Some(org)
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use thiserror::Error;
+
+/// Why a Tervezo request failed, classified so the UI can say what to do
+/// about it.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TervezoError {
+    /// Any other non-success status.
+    #[error("HTTP {status}: {body}")]
+    Http { status: u16, body: String },
+    #[error("request timed out")]
+    Timeout,
+    /// The body (or, for requests, our own payload) was not the expected
+    /// JSON.
+    #[error("{context} parse error: {message}")]
+    Parse { context: String, message: String },
+    /// 401 or 403: the API key is missing, wrong or revoked.
+    #[error("not authorized (HTTP {status})")]
+    Auth { status: u16 },
+    #[error("rate limited{}", retry_after.map(|s| format!(", retry after {}s", s)).unwrap_or_default())]
+    RateLimited { retry_after: Option<u64> },
+    /// The server could not be reached or the connection broke.
+    #[error("request failed: {0}")]
+    Transport(String),
+}
+
+impl TervezoError {
+    /// Classifies a non-success response.
+    pub fn from_status(status: u16, retry_after: Option<u64>, body: String) -> Self {
+        match status {
+            401 | 403 => Self::Auth { status },
+            429 => Self::RateLimited { retry_after },
+            _ => Self::Http { status, body },
+        }
+    }
+
+    pub fn from_ureq(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Timeout(_) => Self::Timeout,
+            other => Self::Transport(other.to_string()),
+        }
+    }
+
+    /// What the user can do about this error, if anything.
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::Auth { .. } => Some(
+                "check the API key (TERVEZO_API_KEY or [tervezo] api_key in ~/.c9s/config.toml)",
+            ),
+            Self::RateLimited { .. } => Some("too many requests; try again in a moment"),
+            Self::Timeout | Self::Transport(_) => {
+                Some("check your connection and [tervezo] base_url")
+            }
+            Self::Http { status, .. } if *status >= 500 => {
+                Some("Tervezo is having trouble; try again shortly")
+            }
+            Self::Parse { .. } => Some("unexpected response from Tervezo; c9s may need updating"),
+            Self::Http { .. } => None,
+        }
+    }
+
+    /// The error followed by its hint, for toasts and inline messages.
+    pub fn with_hint(&self) -> String {
+        match self.hint() {
+            Some(hint) => format!("{} ({})", self, hint),
+            None => self.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_status() {
+        assert_eq!(
+            TervezoError::from_status(401, None, String::new()),
+            TervezoError::Auth { status: 401 }
+        );
+        let limited = TervezoError::from_status(429, Some(30), String::new());
+        assert_eq!(limited.to_string(), "rate limited, retry after 30s");
+        let other = TervezoError::from_status(502, None, "bad gateway".into());
+        assert_eq!(other.to_string(), "HTTP 502: bad gateway");
+        assert!(other.hint().is_some());
+        assert_eq!(
+            TervezoError::from_status(404, None, "nope".into()).with_hint(),
+            "HTTP 404: nope"
+        );
+    }
+}
@@ -6,18 +6,21 @@ use std::thread::JoinHandle;
 
 use super::api::TervezoClient;
 use super::config::TervezoConfig;
+use super::error::TervezoError;
 use super::models::Implementation;
 
 #[derive(Debug, Default)]
 struct FetcherState {
     implementations: Vec<Implementation>,
-    error: Option<String>,
+    error: Option<TervezoError>,
     dirty: bool,
 }
 
 pub struct TervezoFetcher {
     state: Arc<Mutex<FetcherState>>,
     stop: Arc<AtomicBool>,
+    /// Set to cut the wait before the next poll short.
+    poll_now: Arc<AtomicBool>,
     _handle: JoinHandle<()>,
 }
 
@@ -25,19 +28,28 @@ impl TervezoFetcher {
     pub fn spawn(config: &TervezoConfig) -> Self {
         let state = Arc::new(Mutex::new(FetcherState::default()));
         let stop = Arc::new(AtomicBool::new(false));
+        let poll_now = Arc::new(AtomicBool::new(false));
 
         let client = TervezoClient::new(config);
         let poll_interval = config.poll_interval;
         let state_clone = Arc::clone(&state);
         let stop_clone = Arc::clone(&stop);
+        let poll_now_clone = Arc::clone(&poll_now);
 
         let handle = std::thread::spawn(move || {
-            Self::poll_loop(client, poll_interval, state_clone, stop_clone);
+            Self::poll_loop(
+                client,
+                poll_interval,
+                state_clone,
+                stop_clone,
+                poll_now_clone,
+            );
         });
 
         Self {
             state,
             stop,
+            poll_now,
             _handle: handle,
         }
     }
@@ -47,6 +59,7 @@ impl TervezoFetcher {
         interval_secs: u64,
         state: Arc<Mutex<FetcherState>>,
         stop: Arc<AtomicBool>,
+        poll_now: Arc<AtomicBool>,
     ) {
         loop {
             if stop.load(Ordering::Relaxed) {
@@ -61,12 +74,14 @@ impl TervezoFetcher {
                     s.implementations = impls;
                     s.error = None;
                     s.dirty = true;
+                    crate::wakeup::notify();
                 }
                 Err(e) => {
                     tlog!(error, "fetch error: {}", e);
                     let mut s = state.lock().unwrap_or_else(|e| e.into_inner());
                     s.error = Some(e);
                     s.dirty = true;
+                    crate::wakeup::notify();
                 }
             }
 
@@ -74,6 +89,9 @@ impl TervezoFetcher {
                 if stop.load(Ordering::Relaxed) {
                     return;
                 }
+                if poll_now.swap(false, Ordering::Relaxed) {
+                    break;
+                }
                 std::thread::sleep(std::time::Duration::from_millis(100));
             }
         }
@@ -86,9 +104,15 @@ impl TervezoFetcher {
         was_dirty
     }
 
+    /// Polls again right away, e.g. after an action changed the list.
+    pub fn poll_now(&self) {
+        self.poll_now.store(true, Ordering::Relaxed);
+    }
+
     pub fn mark_dirty(&self) {
         let mut s = self.state.lock().unwrap_or_else(|e| e.into_inner());
         s.dirty = true;
+        crate::wakeup::notify();
     }
 
     pub fn implementations(&self) -> Vec<Implementation> {
@@ -99,8 +123,8 @@ impl TervezoFetcher {
             .clone()
     }
 
-    #[allow(dead_code)]
-    pub fn error(&self) -> Option<String> {
+    /// The last poll's error, cleared by the next successful poll.
+    pub fn error(&self) -> Option<TervezoError> {
         self.state
             .lock()
             .unwrap_or_else(|e| e.into_inner())
@@ -114,3 +138,67 @@ impl Drop for TervezoFetcher {
         self.stop.store(true, Ordering::Relaxed);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tervezo::mock::{MockServer, Reply};
+    use std::time::{Duration, Instant};
+
+    fn wait_dirty(fetcher: &TervezoFetcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while Instant::now() < deadline {
+            if fetcher.take_dirty() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    fn test_fetcher_publishes_implementations() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations",
+            Reply::Json(
+                200,
+                r#"{"items":[{"id":"a","status":"running"},{"id":"b","status":"merged"}]}"#
+                    .to_string(),
+            ),
+        );
+
+        let fetcher = TervezoFetcher::spawn(&server.config());
+        assert!(wait_dirty(&fetcher));
+        assert_eq!(fetcher.implementations().len(), 2);
+        assert!(fetcher.error().is_none());
+    }
+
+    #[test]
+    fn test_fetcher_keeps_last_list_on_error() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            "/implementations",
+            Reply::Json(
+                200,
+                r#"{"items":[{"id":"a","status":"running"}]}"#.to_string(),
+            ),
+        );
+        let fetcher = TervezoFetcher::spawn(&server.config());
+        assert!(wait_dirty(&fetcher));
+
+        server.on(
+            "GET",
+            "/implementations",
+            Reply::Json(500, "upstream down".to_string()),
+        );
+        assert!(wait_dirty(&fetcher));
+        assert!(matches!(
+            fetcher.error(),
+            Some(TervezoError::Http { status: 500, .. })
+        ));
+        assert_eq!(fetcher.implementations().len(), 1);
+    }
+}
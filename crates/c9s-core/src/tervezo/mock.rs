@@ -0,0 +1,252 @@
+//! A small HTTP/1.1 server standing in for the Tervezo API in tests, so
+//! the client, fetcher and SSE stream run against real sockets.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use super::config::TervezoConfig;
+
+pub const API_KEY: &str = "tzv_test_key";
+const PREFIX: &str = "/api/v1";
+
+#[derive(Clone)]
+pub enum Reply {
+    /// A JSON (or deliberately broken) body with the given status.
+    Json(u16, String),
+    /// A 200 JSON body with an `ETag`, or 304 when the request's
+    /// `If-None-Match` matches it.
+    Tagged(String, String),
+    /// Like `Tagged`, with a `Last-Modified` date and `If-Modified-Since`.
+    LastModified(String, String),
+    /// A 200 JSON body, gzip-compressed when the request accepts it.
+    Gzip(String),
+    /// An event stream of the given raw event blocks, closed after the
+    /// last one.
+    Sse(Vec<String>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Request {
+    pub method: String,
+    /// Path below the API prefix, query string included.
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+type Routes = Arc<Mutex<HashMap<(String, String), Reply>>>;
+
+pub struct MockServer {
+    port: u16,
+    routes: Routes,
+    requests: Arc<Mutex<Vec<Request>>>,
+    stop: Arc<AtomicBool>,
+}
+
+impl MockServer {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("bind mock server");
+        let port = listener.local_addr().unwrap().port();
+        let routes: Routes = Arc::default();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let (r, q, s) = (routes.clone(), requests.clone(), stop.clone());
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if s.load(Ordering::Relaxed) {
+                    break;
+                }
+                let Ok(stream) = stream else { continue };
+                let (r, q) = (r.clone(), q.clone());
+                std::thread::spawn(move || serve(stream, &r, &q));
+            }
+        });
+
+        Self {
+            port,
+            routes,
+            requests,
+            stop,
+        }
+    }
+
+    /// Answers `method path` (without query) with `reply` from now on.
+    pub fn on(&self, method: &str, path: &str, reply: Reply) {
+        self.routes
+            .lock()
+            .unwrap()
+            .insert((method.to_string(), path.to_string()), reply);
+    }
+
+    pub fn config(&self) -> TervezoConfig {
+        TervezoConfig {
+            api_key: API_KEY.to_string(),
+            base_url: format!("http://127.0.0.1:{}{}", self.port, PREFIX),
+            poll_interval: 1,
+            notify_on: Vec::new(),
+            cache_ttl: 0,
+            org: None,
+            restart_template: "Failed: {reason}. ".to_string(),
+        }
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Waits up to `timeout` for `n` requests to have arrived.
+    pub fn wait_for_requests(&self, n: usize, timeout: Duration) -> Vec<Request> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let requests = self.requests();
+            if requests.len() >= n || Instant::now() >= deadline {
+                return requests;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        // Unblocks the accept loop so it sees the stop flag.
+        let _ = TcpStream::connect(("127.0.0.1", self.port));
+    }
+}
+
+fn serve(stream: TcpStream, routes: &Routes, requests: &Mutex<Vec<Request>>) {
+    let Some(request) = read_request(&stream) else {
+        return;
+    };
+    let route = request.path.split('?').next().unwrap_or("").to_string();
+    let reply = routes
+        .lock()
+        .unwrap()
+        .get(&(request.method.clone(), route))
+        .cloned()
+        .unwrap_or_else(|| Reply::Json(404, r#"{"error":"not found"}"#.to_string()));
+    let reply = match reply {
+        Reply::Tagged(etag, _) if request.headers.get("if-none-match") == Some(&etag) => {
+            Reply::Json(304, String::new())
+        }
+        Reply::LastModified(date, _) if request.headers.get("if-modified-since") == Some(&date) => {
+            Reply::Json(304, String::new())
+        }
+        Reply::Gzip(body)
+            if !request
+                .headers
+                .get("accept-encoding")
+                .is_some_and(|e| e.contains("gzip")) =>
+        {
+            Reply::Json(200, body)
+        }
+        other => other,
+    };
+    requests.lock().unwrap().push(request);
+    let _ = write_reply(stream, &reply);
+}
+
+fn read_request(stream: &TcpStream) -> Option<Request> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line).ok()?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next()?.to_string();
+    let path = parts.next()?.strip_prefix(PREFIX)?.to_string();
+
+    let mut headers = HashMap::new();
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            headers.insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+        }
+    }
+
+    let len = headers
+        .get("content-length")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0);
+    let mut body = vec![0; len];
+    reader.read_exact(&mut body).ok()?;
+    Some(Request {
+        method,
+        path,
+        headers,
+        body: String::from_utf8_lossy(&body).into_owned(),
+    })
+}
+
+fn write_reply(mut stream: TcpStream, reply: &Reply) -> std::io::Result<()> {
+    match reply {
+        Reply::Json(status, body) => write!(
+            stream,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status,
+            reason(*status),
+            body.len(),
+            body
+        ),
+        Reply::Tagged(etag, body) => write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            etag,
+            body.len(),
+            body
+        ),
+        Reply::LastModified(date, body) => write!(
+            stream,
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nLast-Modified: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            date,
+            body.len(),
+            body
+        ),
+        Reply::Gzip(body) => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(body.as_bytes())?;
+            let compressed = encoder.finish()?;
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Encoding: gzip\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                compressed.len()
+            )?;
+            stream.write_all(&compressed)
+        }
+        Reply::Sse(events) => {
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n"
+            )?;
+            for event in events {
+                let chunk = format!("{}\n\n", event.trim_end());
+                write!(stream, "{:x}\r\n{}\r\n", chunk.len(), chunk)?;
+                stream.flush()?;
+            }
+            write!(stream, "0\r\n\r\n")
+        }
+    }
+}
+
+fn reason(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        304 => "Not Modified",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
@@ -1,16 +1,24 @@
 pub mod api;
+pub mod cache;
+pub mod compare;
 pub mod config;
+pub mod error;
 pub mod fetcher;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod models;
+pub mod queue;
 pub mod sse;
+pub mod timeline_stream;
 
 pub use api::TervezoClient;
 pub use config::TervezoConfig;
+pub use error::TervezoError;
 pub use fetcher::TervezoFetcher;
 #[allow(unused_imports)]
 pub use models::CreateImplementationRequest;
 pub use models::{
-    FileChange, Implementation, ImplementationStatus, PrDetails, SshCredentials, StatusResponse,
-    TimelineMessage, Workspace,
+    FileChange, Implementation, ImplementationStatus, Org, PrDetails, SshCredentials,
+    StatusResponse, TimelineMessage, Workspace,
 };
 pub use sse::{SseMessage, SseStream};
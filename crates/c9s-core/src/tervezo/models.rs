@@ -32,6 +32,11 @@ impl ImplementationStatus {
         matches!(self, Self::Running)
     }
 
+    /// Waiting for the remote agent to pick it up.
+    pub fn is_waiting(&self) -> bool {
+        matches!(self, Self::Pending | Self::Queued)
+    }
+
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
@@ -76,6 +81,15 @@ pub struct Implementation {
     pub pr_status: Option<String>,
     #[serde(default)]
     pub mode: Option<String>,
+    /// Place among the org's waiting implementations, 0 next.
+    #[serde(default)]
+    pub queue_position: Option<u32>,
+    /// When the server expects a waiting implementation to start.
+    #[serde(default)]
+    pub estimated_start_at: Option<DateTime<Utc>>,
+    /// The implementation this one retries, when a restart created it.
+    #[serde(default)]
+    pub restarted_from: Option<String>,
 }
 
 impl Implementation {
@@ -222,6 +236,24 @@ pub struct TimelineMessage {
 }
 
 impl TimelineMessage {
+    /// What went wrong, for error messages and changes to the failed
+    /// status.
+    pub fn error_text(&self) -> Option<&str> {
+        match self.msg_type.as_deref() {
+            Some("error") => self
+                .message
+                .as_deref()
+                .or(self.content.as_deref())
+                .or(self.details.as_deref()),
+            Some("status_change") if self.to_status.as_deref() == Some("failed") => {
+                self.reason.as_deref()
+            }
+            _ => None,
+        }
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    }
+
     /// Best-effort display text: check all known text fields in priority order.
     /// Returns owned string because tool_call messages need composing.
     /// Truncated to 200 chars — the timeline shows one line per message.
@@ -798,6 +830,18 @@ pub struct PromptRequest {
     pub message: String,
 }
 
+/// Restart body: optional guidance the agent gets along with its plan.
+#[derive(Debug, Serialize)]
+pub struct RestartRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub guidance: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct QueuePositionRequest {
+    pub position: usize,
+}
+
 // --- Workspace ---
 
 #[derive(Debug, Clone, Deserialize)]
@@ -816,6 +860,18 @@ pub struct WorkspacesResponse {
     pub items: Vec<Workspace>,
 }
 
+/// An organization or team the API key can act for.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Org {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct OrgsResponse {
+    pub items: Vec<Org>,
+}
+
 // --- Create implementation request body ---
 
 #[allow(dead_code)]
@@ -997,6 +1053,24 @@ mod tests {
         assert_eq!(ImplementationStatus::Failed.label(), "Failed");
     }
 
+    #[test]
+    fn test_error_text() {
+        let msg = |json: &str| serde_json::from_str::<TimelineMessage>(json).unwrap();
+        assert_eq!(
+            msg(r#"{"type":"error","severity":"fatal","message":"npm ci failed "}"#).error_text(),
+            Some("npm ci failed")
+        );
+        assert_eq!(
+            msg(r#"{"type":"status_change","toStatus":"failed","reason":"Tests failed"}"#)
+                .error_text(),
+            Some("Tests failed")
+        );
+        assert_eq!(
+            msg(r#"{"type":"status_change","toStatus":"running","reason":"Started"}"#).error_text(),
+            None
+        );
+    }
+
     #[test]
     fn test_implementation_display_name_fallback() {
         let impl_ = Implementation {
@@ -1014,6 +1088,9 @@ mod tests {
             pr_number: None,
             pr_status: None,
             mode: None,
+            queue_position: None,
+            estimated_start_at: None,
+            restarted_from: None,
         };
         assert_eq!(impl_.display_name(), "(untitled)");
     }
@@ -1035,6 +1112,9 @@ mod tests {
             pr_number: None,
             pr_status: None,
             mode: None,
+            queue_position: None,
+            estimated_start_at: None,
+            restarted_from: None,
         };
         assert_eq!(impl_.display_name(), "Fix bug");
     }
@@ -1215,28 +1295,6 @@ mod tests {
         assert!(TIMELINE_TIMEOUT_SECS > 10);
     }
 
-    #[test]
-    fn test_tervezo_detail_state_timeline_error_default_none() {
-        let imp = Implementation {
-            id: "test-id".to_string(),
-            title: Some("Test".to_string()),
-            status: ImplementationStatus::Running,
-            branch: None,
-            repo_url: None,
-            created_at: None,
-            updated_at: None,
-            estimated_cost_usd: None,
-            total_tokens: None,
-            message_count: None,
-            pr_url: None,
-            pr_number: None,
-            pr_status: None,
-            mode: None,
-        };
-        let state = crate::app::TervezoDetailState::new(imp);
-        assert!(state.timeline_error.is_none());
-    }
-
     fn default_timeline_message() -> TimelineMessage {
         TimelineMessage {
             id: None,
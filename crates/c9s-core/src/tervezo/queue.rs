@@ -0,0 +1,115 @@
+//! The implementations waiting for the remote agent, in the order it will
+//! pick them up, with a guess at when each starts.
+
+use chrono::{DateTime, Duration, Utc};
+
+use super::models::Implementation;
+
+#[derive(Debug, Clone)]
+pub struct QueueEntry {
+    pub implementation: Implementation,
+    /// The server's estimate, or one from how long recent runs took.
+    pub starts_at: Option<DateTime<Utc>>,
+}
+
+/// Pending and queued implementations by queue position, then age.
+///
+/// Without a server estimate, each is assumed to run as long as the
+/// median finished implementation, with as many at once as are running
+/// now and the running ones half done.
+pub fn build_queue(implementations: &[Implementation], now: DateTime<Utc>) -> Vec<QueueEntry> {
+    let mut waiting: Vec<&Implementation> = implementations
+        .iter()
+        .filter(|i| i.status.is_waiting())
+        .collect();
+    waiting.sort_by_key(|i| (i.queue_position.unwrap_or(u32::MAX), i.created_at));
+
+    let running = implementations
+        .iter()
+        .filter(|i| i.status.is_running())
+        .count();
+    let slots = running.max(1);
+    let typical = typical_run(implementations);
+
+    waiting
+        .into_iter()
+        .enumerate()
+        .map(|(i, imp)| {
+            let estimate = typical.map(|run| {
+                let ahead = if running > 0 {
+                    run / 2
+                } else {
+                    Duration::zero()
+                };
+                now + ahead + run * (i / slots) as i32
+            });
+            QueueEntry {
+                implementation: imp.clone(),
+                starts_at: imp.estimated_start_at.or(estimate),
+            }
+        })
+        .collect()
+}
+
+/// Median time from creation to last update of finished implementations.
+fn typical_run(implementations: &[Implementation]) -> Option<Duration> {
+    let mut runs: Vec<Duration> = implementations
+        .iter()
+        .filter(|i| i.status.is_terminal())
+        .filter_map(|i| Some(i.updated_at? - i.created_at?))
+        .filter(|d| *d > Duration::zero())
+        .collect();
+    runs.sort();
+    runs.get(runs.len() / 2).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn imp(id: &str, status: &str, extra: &str) -> Implementation {
+        serde_json::from_str(&format!(
+            r#"{{"id":"{}","status":"{}"{}}}"#,
+            id, status, extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_orders_waiting_and_estimates_starts() {
+        let now: DateTime<Utc> = "2026-03-01T12:00:00Z".parse().unwrap();
+        let done = r#","createdAt":"2026-03-01T09:00:00Z","updatedAt":"2026-03-01T10:00:00Z""#;
+        let impls = [
+            imp("old", "completed", done),
+            imp("run", "running", ""),
+            imp("b", "queued", r#","queuePosition":1"#),
+            imp("a", "queued", r#","queuePosition":0"#),
+            imp("late", "pending", r#","createdAt":"2026-03-01T11:00:00Z""#),
+            imp(
+                "eta",
+                "pending",
+                r#","createdAt":"2026-03-01T11:30:00Z","estimatedStartAt":"2026-03-01T18:00:00Z""#,
+            ),
+        ];
+
+        let queue = build_queue(&impls, now);
+        let ids: Vec<_> = queue.iter().map(|e| e.implementation.id.as_str()).collect();
+        assert_eq!(ids, ["a", "b", "late", "eta"]);
+
+        // One running, an hour a run: the next starts in half an hour and
+        // each after it an hour later.
+        let starts: Vec<_> = queue.iter().map(|e| e.starts_at.unwrap()).collect();
+        assert_eq!(starts[0], now + Duration::minutes(30));
+        assert_eq!(starts[2], now + Duration::minutes(150));
+        assert_eq!(
+            starts[3],
+            "2026-03-01T18:00:00Z".parse::<DateTime<Utc>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_no_estimate_without_history() {
+        let queue = build_queue(&[imp("a", "queued", "")], Utc::now());
+        assert_eq!(queue[0].starts_at, None);
+    }
+}
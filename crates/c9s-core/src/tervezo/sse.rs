@@ -5,10 +5,13 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc;
 use std::sync::Arc;
 use std::thread::JoinHandle;
-use std::time::{Duration, Instant};
+use std::time::Duration;
 
+use super::api::ORG_HEADER;
 use super::config::TervezoConfig;
+use super::error::TervezoError;
 use super::models::TimelineMessage;
+use crate::clock::SharedClock;
 
 const MAX_BACKOFF_SECS: u64 = 30;
 /// Only the TCP connect phase gets a timeout. All other timeouts
@@ -22,7 +25,7 @@ const HEALTHY_CONNECTION_SECS: u64 = 10;
 pub enum SseMessage {
     Event(Box<TimelineMessage>),
     WaitingForInput(bool),
-    Error(String),
+    Error(TervezoError),
 }
 
 pub struct SseStream {
@@ -36,16 +39,43 @@ impl SseStream {
         implementation_id: &str,
         last_cursor: Option<String>,
         tx: mpsc::Sender<SseMessage>,
+    ) -> Self {
+        Self::connect_with_clock(
+            config,
+            implementation_id,
+            last_cursor,
+            tx,
+            crate::clock::system(),
+        )
+    }
+
+    /// `connect` with reconnect backoff timed by `clock`.
+    pub fn connect_with_clock(
+        config: &TervezoConfig,
+        implementation_id: &str,
+        last_cursor: Option<String>,
+        tx: mpsc::Sender<SseMessage>,
+        clock: SharedClock,
     ) -> Self {
         let stop = Arc::new(AtomicBool::new(false));
         let stop_clone = Arc::clone(&stop);
 
         let base_url = config.base_url.trim_end_matches('/').to_string();
         let api_key = config.api_key.clone();
+        let org = config.org.clone();
         let impl_id = implementation_id.to_string();
 
         let handle = std::thread::spawn(move || {
-            Self::stream_loop(base_url, api_key, impl_id, last_cursor, stop_clone, tx);
+            Self::stream_loop(
+                base_url,
+                api_key,
+                org,
+                impl_id,
+                last_cursor,
+                stop_clone,
+                tx,
+                clock,
+            );
         });
 
         Self {
@@ -54,13 +84,16 @@ impl SseStream {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn stream_loop(
         base_url: String,
         api_key: String,
+        org: Option<String>,
         impl_id: String,
         initial_cursor: Option<String>,
         stop: Arc<AtomicBool>,
         tx: mpsc::Sender<SseMessage>,
+        clock: SharedClock,
     ) {
         let mut cursor = initial_cursor;
         let mut backoff_secs = 1u64;
@@ -76,12 +109,16 @@ impl SseStream {
             }
 
             tlog!(info, "SSE connecting: {}", url);
-            let connected_at = Instant::now();
-            match Self::open_sse(&url, &api_key) {
+            let connected_at = clock.now_millis();
+            // The backoff is measured from when the attempt ends, which is
+            // fixed before the UI hears about it.
+            let retry_at;
+            match Self::open_sse(&url, &api_key, org.as_deref()) {
                 Ok(reader) => {
                     tlog!(info, "SSE connected, reading events...");
                     Self::read_events(reader, &stop, &tx, &mut cursor);
-                    let alive_secs = connected_at.elapsed().as_secs();
+                    let ended_at = clock.now_millis();
+                    let alive_secs = ended_at.saturating_sub(connected_at) / 1000;
                     tlog!(
                         info,
                         "SSE stream ended after {}s, will reconnect (cursor={:?})",
@@ -94,10 +131,13 @@ impl SseStream {
                     if alive_secs >= HEALTHY_CONNECTION_SECS {
                         backoff_secs = 1;
                     }
+                    retry_at = ended_at + backoff_secs * 1000;
                 }
                 Err(e) => {
                     tlog!(error, "SSE connect error: {}", e);
+                    retry_at = clock.now_millis() + backoff_secs * 1000;
                     let _ = tx.send(SseMessage::Error(e));
+                    crate::wakeup::notify();
                 }
             }
 
@@ -106,18 +146,22 @@ impl SseStream {
             }
 
             tlog!(info, "SSE backoff: {}s before reconnect", backoff_secs);
-            for _ in 0..(backoff_secs * 10) {
+            while clock.now_millis() < retry_at {
                 if stop.load(Ordering::Relaxed) {
                     return;
                 }
-                std::thread::sleep(Duration::from_millis(100));
+                clock.sleep(Duration::from_millis(100));
             }
 
             backoff_secs = (backoff_secs * 2).min(MAX_BACKOFF_SECS);
         }
     }
 
-    fn open_sse(url: &str, api_key: &str) -> Result<Box<dyn BufRead + Send>, String> {
+    fn open_sse(
+        url: &str,
+        api_key: &str,
+        org: Option<&str>,
+    ) -> Result<Box<dyn BufRead + Send>, TervezoError> {
         // SSE connections are long-lived streams. Only timeout_connect is safe:
         // - timeout_global kills the entire request after N seconds
         // - timeout_recv_response kills the body read in ureq 3 (not just headers)
@@ -130,22 +174,24 @@ impl SseStream {
             .build()
             .new_agent();
 
-        let resp = agent
+        let mut req = agent
             .get(url)
             .header("Authorization", &format!("Bearer {}", api_key))
             .header("Accept", "text/event-stream")
-            .header("User-Agent", "c9s/0.1")
-            .call()
-            .map_err(|e| format!("SSE connect failed: {}", e))?;
+            .header("User-Agent", "c9s/0.1");
+        if let Some(org) = org {
+            req = req.header(ORG_HEADER, org);
+        }
+        let resp = req.call().map_err(TervezoError::from_ureq)?;
 
-        let status = resp.status();
+        let status = resp.status().as_u16();
         if status != 200 {
             let body = resp
                 .into_body()
                 .read_to_string()
                 .unwrap_or_else(|_| "(unreadable)".to_string());
             tlog!(error, "SSE HTTP {}: {}", status, body);
-            return Err(format!("SSE HTTP {}", status));
+            return Err(TervezoError::from_status(status, None, body));
         }
 
         let reader = resp.into_body().into_reader();
@@ -213,6 +259,7 @@ impl SseStream {
                                                 *cursor = Some(id.clone());
                                             }
                                             let _ = tx.send(SseMessage::Event(Box::new(msg)));
+                                            crate::wakeup::notify();
                                         }
                                         Err(e) => {
                                             let raw_str =
@@ -244,6 +291,7 @@ impl SseStream {
                                     envelope.get("waitingForInput").and_then(|v| v.as_bool())
                                 {
                                     let _ = tx.send(SseMessage::WaitingForInput(waiting));
+                                    crate::wakeup::notify();
                                 }
 
                                 // Status change events can clear waiting state
@@ -251,6 +299,7 @@ impl SseStream {
                                     || envelope.get("complete").is_some()
                                 {
                                     let _ = tx.send(SseMessage::WaitingForInput(false));
+                                    crate::wakeup::notify();
                                 }
                             }
                             // Update cursor from event id if no message had one
@@ -281,3 +330,109 @@ impl Drop for SseStream {
         self.stop.store(true, Ordering::Relaxed);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::tervezo::mock::{MockServer, Reply};
+
+    const STREAM: &str = "/implementations/impl_1/stream";
+
+    fn next(rx: &mpsc::Receiver<SseMessage>) -> SseMessage {
+        rx.recv_timeout(Duration::from_secs(5))
+            .unwrap_or_else(|_| panic!("no SSE message"))
+    }
+
+    #[test]
+    fn test_stream_delivers_messages_and_resumes_after_cursor() {
+        let server = MockServer::start();
+        server.on(
+            "GET",
+            STREAM,
+            Reply::Sse(vec![
+                r#"data: {"messages":[{"id":"m1","type":"agent_message","message":"Planning"}]}"#
+                    .to_string(),
+                "data: {not json".to_string(),
+                r#"data: {"waitingForInput":true}"#.to_string(),
+                r#"data: {"messages":[null,{"id":"m2","type":"agent_message","message":"Done"}]}"#
+                    .to_string(),
+            ]),
+        );
+        let (tx, rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let _stream = SseStream::connect_with_clock(
+            &server.config(),
+            "impl_1",
+            Some("m0".into()),
+            tx,
+            clock.clone(),
+        );
+
+        match next(&rx) {
+            SseMessage::Event(m) => assert_eq!(m.id.as_deref(), Some("m1")),
+            _ => panic!("expected m1"),
+        }
+        assert!(matches!(next(&rx), SseMessage::WaitingForInput(true)));
+        match next(&rx) {
+            SseMessage::Event(m) => assert_eq!(m.id.as_deref(), Some("m2")),
+            _ => panic!("expected m2"),
+        }
+
+        // The server closes the stream; the reconnect picks up after m2.
+        // The stream may still be draining, so keep time moving until then.
+        for _ in 0..250 {
+            if server.requests().len() >= 2 {
+                break;
+            }
+            clock.advance(Duration::from_secs(1));
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        let requests = server.requests();
+        assert_eq!(requests[0].path, format!("{}?after=m0", STREAM));
+        assert_eq!(requests[1].path, format!("{}?after=m2", STREAM));
+        assert_eq!(
+            requests[0].headers.get("accept").map(String::as_str),
+            Some("text/event-stream")
+        );
+    }
+
+    #[test]
+    fn test_stream_reports_http_errors() {
+        let server = MockServer::start();
+        server.on("GET", STREAM, Reply::Json(503, "maintenance".to_string()));
+        let (tx, rx) = mpsc::channel();
+        let _stream = SseStream::connect(&server.config(), "impl_1", None, tx);
+
+        match next(&rx) {
+            SseMessage::Error(e) => assert_eq!(
+                e,
+                TervezoError::Http {
+                    status: 503,
+                    body: "maintenance".to_string()
+                }
+            ),
+            _ => panic!("expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_reconnect_backoff_doubles() {
+        let server = MockServer::start();
+        server.on("GET", STREAM, Reply::Json(503, "maintenance".to_string()));
+        let (tx, rx) = mpsc::channel();
+        let clock = MockClock::new();
+        let _stream =
+            SseStream::connect_with_clock(&server.config(), "impl_1", None, tx, clock.clone());
+
+        for (attempt, backoff) in [(1, 1000), (2, 2000), (3, 4000)] {
+            assert!(matches!(next(&rx), SseMessage::Error(_)));
+            clock.advance(Duration::from_millis(backoff - 1));
+            std::thread::sleep(Duration::from_millis(50));
+            assert_eq!(server.requests().len(), attempt);
+            clock.advance(Duration::from_millis(1));
+            let requests = server.wait_for_requests(attempt + 1, Duration::from_secs(5));
+            assert_eq!(requests.len(), attempt + 1);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Incremental parsing of the timeline envelope. Timelines of long-running
+//! implementations reach tens of megabytes; reading them into a `String`
+//! and then a `Value` held the payload in memory three times over. Here the
+//! `messages` array is walked element by element straight off the socket
+//! and handed out in batches, so only one batch is ever buffered.
+
+use std::fmt;
+use std::io::Read;
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use super::models::TimelineMessage;
+use crate::tlog;
+
+/// Counts from one parse.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct TimelineStats {
+    pub parsed: usize,
+    /// Messages that were not valid `TimelineMessage`s. One bad message
+    /// doesn't cost the rest of the timeline.
+    pub skipped: usize,
+    /// The envelope's `hasMore`, when the server sends one.
+    pub has_more: Option<bool>,
+}
+
+struct Sink<'a> {
+    batch: Vec<TimelineMessage>,
+    batch_size: usize,
+    on_batch: &'a mut dyn FnMut(Vec<TimelineMessage>),
+    stats: TimelineStats,
+}
+
+impl Sink<'_> {
+    fn push(&mut self, raw: serde_json::Value) {
+        if raw.is_null() {
+            return;
+        }
+        match serde_json::from_value::<TimelineMessage>(raw) {
+            Ok(msg) => {
+                self.stats.parsed += 1;
+                self.batch.push(msg);
+                if self.batch.len() >= self.batch_size {
+                    self.flush();
+                }
+            }
+            Err(e) => {
+                self.stats.skipped += 1;
+                if self.stats.skipped <= 3 {
+                    tlog!(warn, "timeline msg parse skip: {}", e);
+                }
+            }
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.batch.is_empty() {
+            let batch = std::mem::replace(&mut self.batch, Vec::with_capacity(self.batch_size));
+            (self.on_batch)(batch);
+        }
+    }
+}
+
+/// Reads `{"messages": [...], ...}` from `reader`, passing parsed messages
+/// to `on_batch` at most `batch_size` at a time. Other envelope fields but
+/// `hasMore` are skipped; a missing or null `messages` is an empty timeline.
+pub fn read_timeline<R: Read>(
+    reader: R,
+    batch_size: usize,
+    on_batch: &mut dyn FnMut(Vec<TimelineMessage>),
+) -> Result<TimelineStats, serde_json::Error> {
+    let mut sink = Sink {
+        batch: Vec::with_capacity(batch_size.max(1)),
+        batch_size: batch_size.max(1),
+        on_batch,
+        stats: TimelineStats::default(),
+    };
+    let mut de = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+    Envelope(&mut sink).deserialize(&mut de)?;
+    de.end()?;
+    sink.flush();
+    Ok(sink.stats)
+}
+
+struct Envelope<'a, 'b>(&'a mut Sink<'b>);
+
+impl<'de> DeserializeSeed<'de> for Envelope<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Envelope<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a timeline object")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            if key == "messages" {
+                map.next_value_seed(Messages(&mut *self.0))?;
+            } else if key == "hasMore" {
+                self.0.stats.has_more = map.next_value()?;
+            } else {
+                map.next_value::<IgnoredAny>()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Messages<'a, 'b>(&'a mut Sink<'b>);
+
+impl<'de> DeserializeSeed<'de> for Messages<'_, '_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Messages<'_, '_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("an array of timeline messages")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(raw) = seq.next_element::<serde_json::Value>()? {
+            self.0.push(raw);
+        }
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn msg(id: usize) -> String {
+        format!(
+            r#"{{"id":"m{}","type":"agent_message","message":"hi"}}"#,
+            id
+        )
+    }
+
+    #[test]
+    fn test_batches_and_skips() {
+        let mut items: Vec<String> = (0..5).map(msg).collect();
+        items.insert(2, r#"{"id":"bad","timestamp":"yesterday"}"#.to_string());
+        items.insert(3, "null".to_string());
+        let body = format!(
+            r#"{{"cursor":"c1","meta":{{"nested":[1,2]}},"messages":[{}],"hasMore":true}}"#,
+            items.join(",")
+        );
+
+        let mut batches = Vec::new();
+        let stats = read_timeline(body.as_bytes(), 2, &mut |b| batches.push(b.len())).unwrap();
+        assert_eq!(batches, vec![2, 2, 1]);
+        assert_eq!(
+            stats,
+            TimelineStats {
+                parsed: 5,
+                skipped: 1,
+                has_more: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn test_empty_and_broken() {
+        let mut calls = 0;
+        let stats = read_timeline(&b"{\"messages\":null}"[..], 10, &mut |_| calls += 1).unwrap();
+        assert_eq!((stats.parsed, calls), (0, 0));
+        assert!(read_timeline(&b"{\"messages\":[{"[..], 10, &mut |_| {}).is_err());
+    }
+}
@@ -0,0 +1,223 @@
+//! Time spent attached to sessions. The UI reports which session has
+//! focus and when keys are pressed; time counts while a session has focus
+//! and the keyboard has been used within the idle limit, and is booked to
+//! the local day it was spent on.
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Time booked to one session on one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrackedTime {
+    pub date: NaiveDate,
+    pub session_id: String,
+    pub project: String,
+    pub millis: u64,
+}
+
+/// The session being worked in, by id and project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Focus {
+    pub session_id: String,
+    pub project: String,
+}
+
+pub struct TimeTracker {
+    enabled: bool,
+    idle_after: Duration,
+    focus: Option<Focus>,
+    last_tick: DateTime<Utc>,
+    last_input: DateTime<Utc>,
+    /// Booked since the last `take_pending`, by (day, session).
+    pending: BTreeMap<(NaiveDate, String), TrackedTime>,
+}
+
+impl TimeTracker {
+    pub fn new(enabled: bool, idle_after: Duration, now: DateTime<Utc>) -> Self {
+        Self {
+            enabled,
+            idle_after,
+            focus: None,
+            last_tick: now,
+            last_input: now,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Starts or stops tracking by hand. Time up to `now` is booked first.
+    pub fn set_enabled(&mut self, enabled: bool, now: DateTime<Utc>) {
+        self.tick(self.focus.clone(), now);
+        self.enabled = enabled;
+        self.last_input = now;
+    }
+
+    /// A key press: the user is at the keyboard.
+    pub fn input(&mut self, now: DateTime<Utc>) {
+        self.last_input = now;
+    }
+
+    /// Books the time since the last tick to the session that had focus,
+    /// up to where the user went idle, then moves focus to `focus`.
+    pub fn tick(&mut self, focus: Option<Focus>, now: DateTime<Utc>) {
+        let idle_at = self.last_input
+            + chrono::Duration::from_std(self.idle_after).unwrap_or(chrono::Duration::MAX);
+        let end = now.min(idle_at);
+        if let Some(ref current) = self.focus {
+            if self.enabled && end > self.last_tick {
+                let millis = (end - self.last_tick).num_milliseconds().max(0) as u64;
+                self.book(current.clone(), self.last_tick, millis);
+            }
+        }
+        if focus != self.focus {
+            // Switching to a session counts as being at the keyboard.
+            self.last_input = now;
+        }
+        self.focus = focus;
+        self.last_tick = now;
+    }
+
+    /// Whether time is being counted right now.
+    pub fn is_counting(&self, now: DateTime<Utc>) -> bool {
+        self.enabled
+            && self.focus.is_some()
+            && now
+                .signed_duration_since(self.last_input)
+                .to_std()
+                .unwrap_or_default()
+                < self.idle_after
+    }
+
+    /// Booked time not yet handed out, for `day`.
+    pub fn pending_millis(&self, day: NaiveDate) -> u64 {
+        self.pending
+            .values()
+            .filter(|t| t.date == day)
+            .map(|t| t.millis)
+            .sum()
+    }
+
+    /// Hands out the time booked so far, to be stored.
+    pub fn take_pending(&mut self) -> Vec<TrackedTime> {
+        std::mem::take(&mut self.pending).into_values().collect()
+    }
+
+    /// Books `millis` from `start`, split at local midnights.
+    fn book(&mut self, focus: Focus, start: DateTime<Utc>, millis: u64) {
+        let mut start = start.with_timezone(&Local);
+        let mut left = millis;
+        while left > 0 {
+            let date = start.date_naive();
+            let next_day = date
+                .succ_opt()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .and_then(|d| d.and_local_timezone(Local).earliest());
+            let today = next_day
+                .map(|n| (n - start).num_milliseconds().max(0) as u64)
+                .unwrap_or(left)
+                .min(left);
+            let entry = self
+                .pending
+                .entry((date, focus.session_id.clone()))
+                .or_insert_with(|| TrackedTime {
+                    date,
+                    session_id: focus.session_id.clone(),
+                    project: focus.project.clone(),
+                    millis: 0,
+                });
+            entry.millis += today;
+            left -= today;
+            match next_day {
+                Some(n) if left > 0 => start = n,
+                _ => break,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn focus(id: &str) -> Option<Focus> {
+        Some(Focus {
+            session_id: id.to_string(),
+            project: format!("proj-{}", id),
+        })
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        // Midday local time, so the tests don't cross midnight.
+        let noon = Local
+            .with_ymd_and_hms(2026, 3, 10, 12, 0, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        noon + chrono::Duration::seconds(secs)
+    }
+
+    fn total(tracker: &mut TimeTracker) -> Vec<(String, u64)> {
+        tracker
+            .take_pending()
+            .into_iter()
+            .map(|t| (t.session_id, t.millis / 1000))
+            .collect()
+    }
+
+    #[test]
+    fn test_counts_focused_time_until_idle() {
+        let mut tracker = TimeTracker::new(true, Duration::from_secs(300), at(0));
+        tracker.tick(focus("a"), at(0));
+        tracker.tick(focus("a"), at(60));
+        tracker.input(at(100));
+        // Idle from 400s on; the rest of the gap doesn't count.
+        tracker.tick(focus("a"), at(1000));
+        assert_eq!(total(&mut tracker), [("a".to_string(), 400)]);
+        assert!(!tracker.is_counting(at(1000)));
+
+        tracker.input(at(1000));
+        tracker.tick(focus("b"), at(1030));
+        tracker.tick(None, at(1090));
+        tracker.tick(None, at(2000));
+        assert_eq!(
+            total(&mut tracker),
+            [("a".to_string(), 30), ("b".to_string(), 60)]
+        );
+    }
+
+    #[test]
+    fn test_paused_tracking_books_nothing() {
+        let mut tracker = TimeTracker::new(true, Duration::from_secs(300), at(0));
+        tracker.tick(focus("a"), at(0));
+        tracker.set_enabled(false, at(10));
+        tracker.tick(focus("a"), at(100));
+        assert!(!tracker.is_counting(at(100)));
+        tracker.set_enabled(true, at(200));
+        tracker.tick(focus("a"), at(250));
+        assert_eq!(total(&mut tracker), [("a".to_string(), 60)]);
+        assert_eq!(
+            tracker.pending_millis(at(0).with_timezone(&Local).date_naive()),
+            0
+        );
+    }
+
+    #[test]
+    fn test_splits_at_midnight() {
+        let before = Local
+            .with_ymd_and_hms(2026, 3, 10, 23, 59, 0)
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut tracker = TimeTracker::new(true, Duration::from_secs(600), before);
+        tracker.tick(focus("a"), before);
+        tracker.tick(focus("a"), before + chrono::Duration::seconds(180));
+        let booked = tracker.take_pending();
+        assert_eq!(booked.len(), 2);
+        assert_eq!(booked[0].millis, 60_000);
+        assert_eq!(booked[1].millis, 120_000);
+        assert_eq!(booked[1].date, booked[0].date.succ_opt().unwrap());
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use serde::Deserialize;
 use std::sync::mpsc;
 use std::time::{Instant, SystemTime, UNIX_EPOCH};
@@ -17,6 +18,77 @@ pub struct UsageData {
     pub api_available: bool,
 }
 
+/// Length of the rolling usage window.
+pub const WINDOW: chrono::Duration = chrono::Duration::hours(5);
+
+/// Local estimate of the current usage window, from transcript token
+/// counts, for when the usage API has no figure.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WindowEstimate {
+    pub tokens: u64,
+    pub started_at: DateTime<Utc>,
+    pub resets_at: DateTime<Utc>,
+}
+
+impl WindowEstimate {
+    /// Share of `limit` used, 0-100.
+    pub fn percent(&self, limit: u64) -> Option<u8> {
+        (limit > 0).then(|| (self.tokens.saturating_mul(100) / limit).min(100) as u8)
+    }
+}
+
+/// Replays token use in time order: a window opens with the first use
+/// after the previous one ended and lasts `WINDOW`. Returns the window
+/// open at `now`, if any.
+pub fn estimate_window(
+    mut used: Vec<(DateTime<Utc>, u64)>,
+    now: DateTime<Utc>,
+) -> Option<WindowEstimate> {
+    used.sort_by_key(|&(at, _)| at);
+    let mut window: Option<WindowEstimate> = None;
+    for (at, tokens) in used {
+        if at > now {
+            break;
+        }
+        match window {
+            Some(ref mut w) if at < w.resets_at => w.tokens += tokens,
+            _ => {
+                window = Some(WindowEstimate {
+                    tokens,
+                    started_at: at,
+                    resets_at: at + WINDOW,
+                })
+            }
+        }
+    }
+    window.filter(|w| w.resets_at > now)
+}
+
+/// Tokens used over the `span` before `now`, split into `buckets` equal
+/// slices, oldest first.
+pub fn bucket_usage(
+    used: &[(DateTime<Utc>, u64)],
+    now: DateTime<Utc>,
+    span: chrono::Duration,
+    buckets: usize,
+) -> Vec<u64> {
+    let mut out = vec![0; buckets];
+    let span_ms = span.num_milliseconds();
+    if buckets == 0 || span_ms <= 0 {
+        return out;
+    }
+    let start = now - span;
+    for &(at, tokens) in used {
+        if at < start || at > now {
+            continue;
+        }
+        let offset = (at - start).num_milliseconds();
+        let idx = (offset * buckets as i64 / span_ms) as usize;
+        out[idx.min(buckets - 1)] += tokens;
+    }
+    out
+}
+
 pub struct UsageFetcher {
     cached: UsageData,
     last_request: Option<Instant>,
@@ -53,6 +125,12 @@ struct WindowData {
     resets_at: Option<String>,
 }
 
+impl Default for UsageFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl UsageFetcher {
     pub fn new() -> Self {
         let (result_tx, result_rx) = mpsc::channel();
@@ -64,6 +142,7 @@ impl UsageFetcher {
                 if result_tx.send(data).is_err() {
                     break;
                 }
+                crate::wakeup::notify();
             }
         });
 
@@ -242,6 +321,46 @@ mod tests {
         assert_eq!(plan_from_subscription("api_key"), None);
     }
 
+    #[test]
+    fn test_estimate_window() {
+        let start = DateTime::parse_from_rfc3339("2026-03-10T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let at = |m: i64| start + chrono::Duration::minutes(m);
+        let used = vec![(at(200), 300), (at(0), 100), (at(90), 200), (at(310), 50)];
+
+        // The first window runs 08:00-13:00; use at 13:10 opens the next.
+        let first = estimate_window(used.clone(), at(250)).unwrap();
+        assert_eq!(first.tokens, 600);
+        assert_eq!(first.resets_at, at(300));
+        assert_eq!(first.percent(1000), Some(60));
+        assert_eq!(first.percent(0), None);
+
+        assert_eq!(estimate_window(used.clone(), at(305)), None);
+        let second = estimate_window(used.clone(), at(320)).unwrap();
+        assert_eq!(second.tokens, 50);
+        assert_eq!(second.started_at, at(310));
+        assert_eq!(estimate_window(used, at(700)), None);
+    }
+
+    #[test]
+    fn test_bucket_usage() {
+        let now = DateTime::parse_from_rfc3339("2026-03-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ago = |m: i64| now - chrono::Duration::minutes(m);
+        let used = [
+            (ago(90), 1),
+            (ago(50), 2),
+            (ago(45), 3),
+            (ago(0), 4),
+            (ago(5), 5),
+        ];
+        let buckets = bucket_usage(&used, now, chrono::Duration::hours(1), 4);
+        assert_eq!(buckets, [2, 3, 0, 9]);
+        assert!(bucket_usage(&used, now, chrono::Duration::hours(1), 0).is_empty());
+    }
+
     #[test]
     fn test_parse_utilization() {
         assert_eq!(parse_utilization(Some(50.0)), Some(50));
@@ -0,0 +1,19 @@
+//! Lets background threads in this crate (fetchers, streams, the log)
+//! tell the embedding app that new data is waiting to be drained.
+
+use std::sync::OnceLock;
+
+static HOOK: OnceLock<fn()> = OnceLock::new();
+
+/// Registers the function `notify` calls. Only the first call has an
+/// effect.
+pub fn set_hook(hook: fn()) {
+    let _ = HOOK.set(hook);
+}
+
+/// Calls the registered hook, if any.
+pub fn notify() {
+    if let Some(hook) = HOOK.get() {
+        hook();
+    }
+}
@@ -1,18 +1,69 @@
 use std::collections::{HashMap, HashSet};
 use std::sync::mpsc;
 
-use crate::session::config::{build_config_items, scan_session_config, ConfigItem};
-use crate::session::{Session, SessionConfig, SessionDiscovery, SessionStatus};
-use crate::store::Store;
-use crate::terminal::{EmbeddedTerminal, TerminalManager};
+use crate::config::{
+    AppConfig, DashboardWidget, FocusConfig, SessionsConfig, TicketsConfig, UpdatesConfig,
+    UserAction, WatchdogAction,
+};
+use crate::deeplink::DeepLink;
+use crate::host::HostStats;
+use crate::i18n::{t, tf};
+use crate::instance::{Instance, Message};
+use crate::mirror::Mirror;
+use crate::outcome::{compare_models, ModelComparison, Outcome, OutcomeStats};
+use crate::plugin::Plugins;
+use crate::pomodoro::{Phase, Pomodoro};
+use crate::session::config::{
+    build_config_items, build_env_items, build_plan_items, scan_session_config, ConfigItem,
+    ConfigItemKind,
+};
+use crate::session::env::read_session_env;
+use crate::session::export::{default_export_path, export_transcript, ExportFormat};
+use crate::session::files::{files_touched, FileTouch};
+use crate::session::git::{self, GitOp, GitOutcome, WorkingDiff};
+use crate::session::handoff::{default_handoff_dir, load_imported, write_handoff};
+use crate::session::mcp::{check_server, McpHealth};
+use crate::session::resources::{ProcessUsage, ResourceSampler};
+use crate::session::timeline::{
+    last_exchange, load_timeline, load_transcript, transcript_path, LastExchange, TimelineEvent,
+};
+use crate::session::version::{fetch_latest_version, ClaudeVersions};
+use crate::session::{
+    FileDiagnostics, Session, SessionConfig, SessionDiscovery, SessionManager, SessionStatus,
+    SubagentRollup,
+};
+use crate::store::{Bookmark, CostTotals, Store};
+use crate::tasks::{CancelToken, TaskPool};
+use crate::terminal::{EmbeddedTerminal, Motion, NotifyLevel, Point, SpawnConfig, TerminalManager};
+use crate::tervezo::compare::FileComparison;
+use crate::tervezo::config::SavedOrg;
 use crate::tervezo::models::TestReport;
+use crate::tervezo::queue::{build_queue, QueueEntry};
 use crate::tervezo::{
-    FileChange, Implementation, ImplementationStatus, PrDetails, SseMessage, SseStream,
-    SshCredentials, StatusResponse, TervezoConfig, TervezoFetcher, TimelineMessage, Workspace,
+    FileChange, Implementation, ImplementationStatus, Org, PrDetails, SseMessage, SseStream,
+    SshCredentials, StatusResponse, TervezoConfig, TervezoError, TervezoFetcher, TimelineMessage,
+    Workspace,
+};
+use crate::timetrack::{Focus, TimeTracker};
+use crate::tlog;
+use crate::toast::{Severity, Toasts};
+use crate::ui::theme::Theme;
+use crate::usage::{
+    bucket_usage, estimate_window, UsageData, UsageFetcher, WindowEstimate, WINDOW,
 };
-use crate::usage::{UsageData, UsageFetcher};
+use crate::watchdog::Watchdog;
 use anyhow::Result;
 
+/// Length of do-not-disturb when started with `z` or a bare `--dnd`.
+pub const DND_MINUTES: u64 = 30;
+
+/// Most one-shot Tervezo requests allowed in flight at once.
+const TASK_WORKERS: usize = 4;
+/// List states kept for `Ctrl+o` / `Ctrl+i`.
+const LIST_HISTORY: usize = 50;
+/// Days the Stats view goes back, today included.
+pub const STATS_DAYS: i64 = 30;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ViewMode {
     List,
@@ -22,6 +73,7 @@ pub enum ViewMode {
     QSwitcher,
     Terminal,
     TerminalQSwitcher,
+    TerminalCopy,
     Command,
     ConfirmQuit,
     TervezoDetail,
@@ -32,8 +84,28 @@ pub enum ViewMode {
     TervezoCreateDialog,
     NewSessionMenu,
     ConfirmKill,
+    ConfirmAttach,
     SessionFilePicker,
     Log,
+    SessionTimeline,
+    TimelineExport,
+    QuickPrompt,
+    RecentlyExited,
+    ErrorModal,
+    Diagnostics,
+    ActionMenu,
+    GitMenu,
+    GitBranchName,
+    GitCommit,
+    TicketInput,
+    OutcomePrompt,
+    Outcomes,
+    Stats,
+    Dashboard,
+    OrgPicker,
+    Queue,
+    QueueConfirm,
+    TervezoCompare,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -48,10 +120,122 @@ pub enum CiStatus {
     Running,
 }
 
+/// The open pull request for a local session's branch, as `gh` reports it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrStatus {
+    pub number: u64,
+    pub draft: bool,
+    pub review: PrReview,
+    pub checks: CiStatus,
+    pub url: String,
+}
+
+/// An attach that would end the session in the terminal it runs in now.
+#[derive(Debug, Clone)]
+pub struct PendingAttach {
+    pub session_id: String,
+    pub project_name: String,
+    pub cwd: std::path::PathBuf,
+    pub pid: u32,
+    /// Where it runs, as `ps` names it ("pts/3").
+    pub tty: String,
+}
+
+/// Copy mode over the active terminal's scrollback and screen.
+#[derive(Debug, Clone, Copy)]
+pub struct CopyMode {
+    pub cursor: Point,
+    /// Where the selection was started; it runs to the cursor.
+    pub anchor: Option<Point>,
+}
+
+/// Other live sessions working in the same checkout as a session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WorktreeClash {
+    /// Project names of the other sessions.
+    pub others: Vec<String>,
+    /// One of them is on the same branch. Otherwise they are on different
+    /// branches, so one has switched the checkout under the other.
+    pub same_branch: bool,
+}
+
+impl WorktreeClash {
+    /// Short warning for the session list.
+    pub fn badge(&self) -> &'static str {
+        if self.same_branch {
+            "!same branch"
+        } else {
+            "!branch clash"
+        }
+    }
+
+    /// One line for the detail view.
+    pub fn summary(&self) -> String {
+        let what = if self.same_branch {
+            "same branch"
+        } else {
+            "other branch"
+        };
+        format!("shared with {} ({})", self.others.join(", "), what)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrReview {
+    Approved,
+    ChangesRequested,
+    Required,
+    /// No review requested or required.
+    None,
+}
+
+impl PrReview {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Approved => "approved",
+            Self::ChangesRequested => "changes requested",
+            Self::Required => "review required",
+            Self::None => "no review",
+        }
+    }
+}
+
+impl PrStatus {
+    /// "#42" plus ✓ when approved or ✗ when changes were requested.
+    pub fn badge(&self) -> String {
+        let mark = match self.review {
+            PrReview::Approved => "✓",
+            PrReview::ChangesRequested => "✗",
+            _ => "",
+        };
+        format!("#{}{}", self.number, mark)
+    }
+
+    /// One line for the detail view: "#42 draft, approved, checks passing".
+    pub fn summary(&self) -> String {
+        let checks = match self.checks {
+            CiStatus::Passing => "checks passing",
+            CiStatus::Failing => "checks failing",
+            CiStatus::Running => "checks running",
+            _ => "no checks",
+        };
+        let draft = if self.draft { " draft," } else { "" };
+        format!(
+            "#{}{} {}, {}",
+            self.number,
+            draft,
+            self.review.label(),
+            checks
+        )
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NewSessionOption {
     Local,
     Tervezo,
+    ResumeHere,
+    FreshHere,
 }
 
 impl NewSessionOption {
@@ -59,6 +243,8 @@ impl NewSessionOption {
         match self {
             Self::Local => "Local session",
             Self::Tervezo => "Tervezo session",
+            Self::ResumeHere => "Resume here",
+            Self::FreshHere => "Fresh session, same cwd",
         }
     }
 }
@@ -66,6 +252,96 @@ impl NewSessionOption {
 pub struct NewSessionMenuState {
     pub items: Vec<NewSessionOption>,
     pub cursor: usize,
+    pub title: &'static str,
+    /// Dead session the resume/fresh options act on.
+    pub session: Option<Session>,
+    /// Extra context line, e.g. the branch the fresh session will start on.
+    pub hint: Option<String>,
+}
+
+/// The Tervezo organizations to switch between, fetched when opened.
+#[derive(Default)]
+pub struct OrgPickerState {
+    pub orgs: Vec<Org>,
+    pub cursor: usize,
+    pub loading: bool,
+    pub error: Option<String>,
+}
+
+/// The `[[actions]]` menu for one local session.
+pub struct ActionMenuState {
+    pub session: Session,
+    pub cursor: usize,
+}
+
+/// A finished git command: cwd, project name, what ran and how it went.
+type GitResult = (std::path::PathBuf, String, GitOp, GitOutcome);
+
+/// Git chores for one local session's working directory. `branch` holds
+/// the name being typed for a new branch.
+pub struct GitMenuState {
+    pub session: Session,
+    pub items: [GitOp; 4],
+    pub cursor: usize,
+    pub branch: String,
+}
+
+/// The message being typed to commit a session's working diff, and
+/// whether to push and open a pull request afterwards.
+pub struct CommitPromptState {
+    pub session: Session,
+    pub message: String,
+    pub pull_request: bool,
+}
+
+/// Session history from the store for the Stats view: totals per day and
+/// per project since `since`.
+pub struct CostStats {
+    pub since: chrono::NaiveDate,
+    pub days: Vec<CostTotals>,
+    pub projects: Vec<CostTotals>,
+    /// Chart tokens instead of cost.
+    pub tokens: bool,
+}
+
+/// The outcome and rating being picked for a session that ended.
+pub struct OutcomePromptState {
+    pub session_id: String,
+    pub project: String,
+    pub model: String,
+    pub outcome: Option<Outcome>,
+    pub rating: Option<u8>,
+}
+
+/// The ticket being typed for one session; empty clears it.
+pub struct TicketInputState {
+    pub session_id: String,
+    pub project: String,
+    pub input: String,
+}
+
+/// The format and destination picked for exporting the timeline's session.
+pub struct ExportPromptState {
+    pub format: ExportFormat,
+    /// File to write; empty copies to the clipboard.
+    pub path: String,
+}
+
+impl ExportPromptState {
+    /// Switches between Markdown and HTML, and the path's extension with it.
+    pub fn toggle_format(&mut self) {
+        let next = match self.format {
+            ExportFormat::Markdown => ExportFormat::Html,
+            ExportFormat::Html => ExportFormat::Markdown,
+        };
+        if let Some(stem) = self
+            .path
+            .strip_suffix(&format!(".{}", self.format.extension()))
+        {
+            self.path = format!("{}.{}", stem, next.extension());
+        }
+        self.format = next;
+    }
 }
 
 impl NewSessionMenuState {
@@ -74,10 +350,65 @@ impl NewSessionMenuState {
         if has_tervezo {
             items.push(NewSessionOption::Tervezo);
         }
-        Self { items, cursor: 0 }
+        Self {
+            items,
+            cursor: 0,
+            title: " New Session ",
+            session: None,
+            hint: None,
+        }
+    }
+
+    /// Choice between resuming a dead session and starting over in its cwd.
+    /// The hint waits for `set_cwd_branch`.
+    pub fn for_dead_session(session: Session) -> Self {
+        Self {
+            items: vec![NewSessionOption::ResumeHere, NewSessionOption::FreshHere],
+            cursor: 0,
+            title: " Dead Session ",
+            session: Some(session),
+            hint: None,
+        }
+    }
+
+    /// Notes the branch the session's cwd is on now, against the one the
+    /// session recorded.
+    pub fn set_cwd_branch(&mut self, current: Option<&str>) {
+        let was = self.session.as_ref().and_then(|s| s.git_branch.as_deref());
+        self.hint = match (was, current) {
+            (Some(was), Some(now)) if was != now => Some(tf(
+                "cwd is on {now} (session was {was})",
+                &[("now", now), ("was", was)],
+            )),
+            (_, Some(now)) => Some(tf("on branch {branch}", &[("branch", now)])),
+            _ => None,
+        };
     }
 }
 
+fn current_git_branch(cwd: &std::path::Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty()).then_some(branch)
+}
+
+/// The list's filter, sort and selected session at one moment, to go
+/// back to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ListSnapshot {
+    filter_query: String,
+    errors_only: bool,
+    sort_column: SortColumn,
+    selected_id: Option<String>,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SortColumn {
     LastActive,
@@ -132,16 +463,18 @@ impl SessionEntry {
 
     pub fn status_label(&self) -> &str {
         match self {
+            Self::Local(s) if s.imported => "Imported",
             Self::Local(s) => s.status.label(),
             Self::Remote(i) => i.status.label(),
         }
     }
 
     pub fn last_activity_display(&self) -> String {
-        match self {
-            Self::Local(s) => s.last_activity_display(),
-            Self::Remote(i) => i.last_activity_display(),
-        }
+        let ts = match self {
+            Self::Local(s) => Some(s.last_activity),
+            Self::Remote(i) => i.updated_at.or(i.created_at),
+        };
+        ts.map_or_else(|| "-".to_string(), crate::format::since)
     }
 
     pub fn is_remote(&self) -> bool {
@@ -211,6 +544,22 @@ impl SessionEntry {
         }
     }
 
+    /// Local sessions with an API error, tool failure or interrupt in the
+    /// last hour.
+    pub fn has_recent_errors(&self) -> bool {
+        match self {
+            Self::Local(s) => s.has_recent_errors(chrono::Duration::hours(1)),
+            Self::Remote(_) => false,
+        }
+    }
+
+    pub fn parent_id(&self) -> Option<&str> {
+        match self {
+            Self::Local(s) => s.parent_id.as_deref(),
+            Self::Remote(_) => None,
+        }
+    }
+
     pub fn as_local(&self) -> Option<&Session> {
         match self {
             Self::Local(s) => Some(s),
@@ -304,6 +653,70 @@ impl TervezoTab {
     }
 }
 
+/// How session paths are shown; `p` steps through these.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    /// The list shows project names; paths elsewhere are shortened.
+    #[default]
+    Names,
+    /// The list shows shortened paths instead of project names.
+    Short,
+    /// Paths are shown in full everywhere.
+    Full,
+}
+
+impl PathStyle {
+    pub fn next(self) -> Self {
+        match self {
+            Self::Names => Self::Short,
+            Self::Short => Self::Full,
+            Self::Full => Self::Names,
+        }
+    }
+}
+
+/// Tabs of the local session detail view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DetailTab {
+    Overview,
+    Files,
+    Diff,
+    Transcript,
+}
+
+impl DetailTab {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Overview => "Overview",
+            Self::Files => "Files",
+            Self::Diff => "Working diff",
+            Self::Transcript => "Transcript",
+        }
+    }
+
+    pub fn all() -> &'static [DetailTab] {
+        &[Self::Overview, Self::Files, Self::Diff, Self::Transcript]
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            Self::Overview => Self::Files,
+            Self::Files => Self::Diff,
+            Self::Diff => Self::Transcript,
+            Self::Transcript => Self::Overview,
+        }
+    }
+
+    pub fn prev(self) -> Self {
+        match self {
+            Self::Overview => Self::Transcript,
+            Self::Files => Self::Overview,
+            Self::Diff => Self::Files,
+            Self::Transcript => Self::Diff,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TervezoAction {
     CreatePr,
@@ -313,6 +726,7 @@ pub enum TervezoAction {
     Restart,
     SendPrompt,
     ViewPrInBrowser,
+    ComparePrevious,
 }
 
 impl TervezoAction {
@@ -325,6 +739,7 @@ impl TervezoAction {
             Self::Restart => "Restart",
             Self::SendPrompt => "Send prompt",
             Self::ViewPrInBrowser => "View PR",
+            Self::ComparePrevious => "Compare with previous attempt",
         }
     }
 
@@ -427,21 +842,26 @@ impl TervezoCreateState {
 
 pub enum WorkspaceMsg {
     Loaded(Vec<Workspace>),
-    Error(String),
+    Error(TervezoError),
 }
 
 #[allow(clippy::large_enum_variant)]
 pub enum TervezoCreateMsg {
     Success(Implementation),
-    Error(String),
+    Error(TervezoError),
 }
 
 #[allow(clippy::large_enum_variant)]
 pub enum TervezoDetailMsg {
     Timeline(Vec<TimelineMessage>),
-    #[allow(dead_code)]
-    TimelineAppend(TimelineMessage),
-    TimelineError(String),
+    /// Further batches of a timeline that is still downloading; the first
+    /// arrives as `Timeline`.
+    TimelineMore(Vec<TimelineMessage>),
+    /// Sent once the newest page has arrived: whether older messages exist.
+    TimelineHasOlder(bool),
+    /// A page of older messages, and whether there are more before it.
+    TimelineOlder(Vec<TimelineMessage>, bool),
+    TimelineError(TervezoError),
     Plan(String),
     Analysis(String),
     Changes(Vec<FileChange>),
@@ -450,10 +870,58 @@ pub enum TervezoDetailMsg {
     Status(StatusResponse),
     PrDetails(PrDetails),
     ActionSuccess(String),
-    ActionError(String),
+    ActionError(TervezoError),
     PromptSent(String),
-    PromptError(String),
-    Error(TervezoTab, String),
+    PromptError(TervezoError),
+    Comparison(Vec<FileComparison>),
+    ComparisonError(TervezoError),
+    Error(TervezoTab, TervezoError),
+}
+
+/// The open implementation's changes set against the attempt it retried.
+pub struct ComparisonState {
+    pub previous_id: String,
+    pub files: Option<Vec<FileComparison>>,
+    pub error: Option<TervezoError>,
+    pub cursor: usize,
+    /// Showing the selected file's diff rather than the file list.
+    pub expanded: bool,
+    pub scroll: usize,
+}
+
+impl ComparisonState {
+    pub fn new(previous_id: String) -> Self {
+        Self {
+            previous_id,
+            files: None,
+            error: None,
+            cursor: 0,
+            expanded: false,
+            scroll: 0,
+        }
+    }
+
+    pub fn up(&mut self) {
+        if self.expanded {
+            self.scroll = self.scroll.saturating_sub(1);
+        } else {
+            self.cursor = self.cursor.saturating_sub(1);
+        }
+    }
+
+    pub fn down(&mut self) {
+        if self.expanded {
+            self.scroll += 1;
+        } else {
+            let max = self.files.as_ref().map_or(0, |f| f.len().saturating_sub(1));
+            self.cursor = (self.cursor + 1).min(max);
+        }
+    }
+
+    pub fn toggle(&mut self) {
+        self.expanded = !self.expanded && self.files.as_ref().is_some_and(|f| !f.is_empty());
+        self.scroll = 0;
+    }
 }
 
 pub struct TervezoDetailState {
@@ -461,7 +929,12 @@ pub struct TervezoDetailState {
     pub implementation: Implementation,
     pub active_tab: TervezoTab,
     pub timeline: Vec<TimelineMessage>,
-    pub timeline_error: Option<String>,
+    pub timeline_error: Option<TervezoError>,
+    /// Only the newest page is loaded up front; `o` fetches the one before.
+    pub timeline_has_older: bool,
+    pub timeline_loading_older: bool,
+    /// Why a tab's last load failed; cleared when it loads.
+    pub tab_errors: HashMap<TervezoTab, TervezoError>,
     pub timeline_scroll: usize,
     pub plan_content: Option<String>,
     pub analysis_content: Option<String>,
@@ -491,21 +964,41 @@ pub struct TervezoDetailState {
     pub action_menu_cursor: usize,
     pub confirm_action: Option<TervezoAction>,
     pub action_loading: bool,
-    pub action_result: Option<Result<String, String>>,
+    /// Set by a restart action: the implementation to switch the view to.
+    pub navigate_to: Option<String>,
     // Prompt input
     pub prompt_input: String,
     pub prompt_sending: bool,
+    /// The prompt input holds guidance for a restart rather than a
+    /// follow-up.
+    pub restart_guidance: bool,
+    /// The implementation this one was restarted from.
+    pub previous_attempt: Option<String>,
+    pub comparison: Option<ComparisonState>,
+    /// Every attempt at the task, oldest first, this one among them.
+    pub chain: Vec<ChainLink>,
+}
+
+/// One implementation in a restart chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainLink {
+    pub id: String,
+    pub name: String,
 }
 
 impl TervezoDetailState {
     pub fn new(implementation: Implementation) -> Self {
         let id = implementation.id.clone();
+        let previous_attempt = implementation.restarted_from.clone();
         Self {
             implementation_id: id,
             implementation,
             active_tab: TervezoTab::Plan,
             timeline: Vec::new(),
             timeline_error: None,
+            timeline_has_older: false,
+            timeline_loading_older: false,
+            tab_errors: HashMap::new(),
             timeline_scroll: 0,
             plan_content: None,
             analysis_content: None,
@@ -531,12 +1024,31 @@ impl TervezoDetailState {
             action_menu_cursor: 0,
             confirm_action: None,
             action_loading: false,
-            action_result: None,
+            navigate_to: None,
             prompt_input: String::new(),
             prompt_sending: false,
+            restart_guidance: false,
+            previous_attempt,
+            comparison: None,
+            chain: Vec::new(),
         }
     }
 
+    pub fn chain_position(&self) -> Option<usize> {
+        self.chain
+            .iter()
+            .position(|l| l.id == self.implementation_id)
+    }
+
+    /// First line of the newest error in the loaded timeline.
+    pub fn failure_reason(&self) -> Option<&str> {
+        self.timeline
+            .iter()
+            .rev()
+            .find_map(|m| m.error_text())
+            .and_then(|text| text.lines().next())
+    }
+
     #[allow(dead_code)]
     pub fn active_tab_scroll(&self) -> usize {
         match self.active_tab {
@@ -652,6 +1164,10 @@ impl TervezoDetailState {
             actions.push(TervezoAction::Restart);
         }
 
+        if self.previous_attempt.is_some() {
+            actions.push(TervezoAction::ComparePrevious);
+        }
+
         // Send prompt: waiting for input or terminal status
         let waiting = self
             .status_info
@@ -677,14 +1193,252 @@ impl TervezoDetailState {
     }
 }
 
+/// One-line prompt typed from the list and written to a background terminal.
+pub struct QuickPromptState {
+    pub session_id: String,
+    pub project_name: String,
+    pub input: String,
+}
+
+/// A failed spawn or attach, shown until dismissed. `return_to` is the view
+/// to go back to, never the terminal that failed to open.
+pub struct ErrorModalState {
+    pub title: String,
+    pub message: String,
+    pub return_to: ViewMode,
+}
+
+/// Picker over closed terminal tabs, and the read-only viewer for one of
+/// their final scrollbacks.
+pub struct RecentlyExitedState {
+    pub cursor: usize,
+    /// Index into the recently exited list being viewed, if any.
+    pub viewing: Option<usize>,
+    /// Viewer scroll, counted in lines up from the end of the output.
+    pub from_bottom: usize,
+    pub visible_height: std::cell::Cell<usize>,
+}
+
+impl RecentlyExitedState {
+    /// Moves the picker cursor, or scrolls the viewer (negative is up),
+    /// given the number of entries or lines currently shown.
+    pub fn move_by(&mut self, delta: isize, rows: usize) {
+        if self.viewing.is_some() {
+            let max = rows.saturating_sub(self.visible_height.get().max(1));
+            self.from_bottom = self
+                .from_bottom
+                .saturating_add_signed(delta.saturating_neg())
+                .min(max);
+        } else {
+            self.cursor = self
+                .cursor
+                .saturating_add_signed(delta)
+                .min(rows.saturating_sub(1));
+        }
+    }
+}
+
+/// The detail Files tab: the files a local session changed.
+pub struct DetailFiles {
+    pub cwd: std::path::PathBuf,
+    pub files: Vec<FileTouch>,
+    pub cursor: usize,
+    /// Current content of the file under the cursor, once opened with Enter.
+    pub preview: Option<String>,
+    pub preview_scroll: usize,
+}
+
+impl DetailFiles {
+    pub fn selected(&self) -> Option<&FileTouch> {
+        self.files.get(self.cursor)
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        self.cursor = self
+            .cursor
+            .saturating_add_signed(delta)
+            .min(self.files.len().saturating_sub(1));
+    }
+
+    /// Reads the selected file as it is now.
+    pub fn open_preview(&mut self) {
+        let Some(file) = self.selected() else {
+            return;
+        };
+        let content = match std::fs::read(&file.path) {
+            Ok(bytes) if bytes.contains(&0) => "(binary file)".to_string(),
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                "(the file no longer exists)".to_string()
+            }
+            Err(e) => format!("(error reading file: {})", e),
+        };
+        self.preview = Some(content);
+        self.preview_scroll = 0;
+    }
+}
+
+/// The detail Working diff tab: `git diff` in the session's cwd.
+pub struct DetailDiff {
+    pub cwd: std::path::PathBuf,
+    /// None while git runs; the error is git's.
+    pub result: Option<Result<WorkingDiff, String>>,
+    pub scroll: usize,
+}
+
+/// The detail Transcript tab: the conversation, in full.
+pub struct DetailTranscript {
+    pub session_id: String,
+    /// Set until the transcript has been read in the background.
+    pub loading: bool,
+    pub events: Vec<TimelineEvent>,
+    /// First line shown.
+    pub scroll: usize,
+    /// Furthest `scroll` can go, as of the last render.
+    pub max_scroll: std::cell::Cell<usize>,
+}
+
+impl DetailTranscript {
+    pub fn scroll_by(&mut self, delta: isize) {
+        self.scroll = self
+            .scroll
+            .saturating_add_signed(delta)
+            .min(self.max_scroll.get());
+    }
+}
+
+/// Event stream parsed from a local session's JSONL transcript.
+/// Which view a transcript read in the background is for.
+#[derive(Debug, Clone, Copy)]
+enum TranscriptView {
+    Detail,
+    Timeline,
+}
+
+pub struct LocalTimelineState {
+    pub session_id: String,
+    pub title: String,
+    /// Set until the transcript has been read in the background.
+    pub loading: bool,
+    pub events: Vec<TimelineEvent>,
+    pub bookmarks: Vec<Bookmark>,
+    /// Show the session's bookmark list instead of the event stream.
+    pub bookmarks_only: bool,
+    /// Selected row: an event index, or a bookmark index in `bookmarks_only`.
+    pub cursor: usize,
+    pub scroll: usize,
+    pub visible_height: std::cell::Cell<usize>,
+    /// View to go back to on Esc (list or detail).
+    pub return_to: ViewMode,
+}
+
+impl LocalTimelineState {
+    pub fn row_count(&self) -> usize {
+        if self.bookmarks_only {
+            self.bookmarks.len()
+        } else {
+            self.events.len()
+        }
+    }
+
+    pub fn is_bookmarked(&self, index: usize) -> bool {
+        self.bookmarks.iter().any(|b| b.message_index == index)
+    }
+
+    pub fn selected_event(&self) -> Option<&TimelineEvent> {
+        if self.bookmarks_only {
+            None
+        } else {
+            self.events.get(self.cursor)
+        }
+    }
+
+    pub fn move_cursor(&mut self, delta: isize) {
+        let target = self.cursor.saturating_add_signed(delta);
+        self.cursor_to(target);
+    }
+
+    pub fn cursor_to(&mut self, row: usize) {
+        self.cursor = row.min(self.row_count().saturating_sub(1));
+        let height = self.visible_height.get().max(1);
+        if self.cursor < self.scroll {
+            self.scroll = self.cursor;
+        } else if self.cursor >= self.scroll + height {
+            self.scroll = self.cursor + 1 - height;
+        }
+    }
+
+    /// Moves to the next (or previous) bookmarked event, wrapping around.
+    pub fn jump_to_bookmark(&mut self, forward: bool) {
+        if self.bookmarks_only || self.bookmarks.is_empty() {
+            return;
+        }
+        let marked: Vec<usize> = (0..self.events.len())
+            .filter(|&i| self.is_bookmarked(self.events[i].index))
+            .collect();
+        let target = if forward {
+            marked.iter().find(|&&i| i > self.cursor).or(marked.first())
+        } else {
+            marked
+                .iter()
+                .rev()
+                .find(|&&i| i < self.cursor)
+                .or(marked.last())
+        };
+        if let Some(&row) = target {
+            self.cursor_to(row);
+        }
+    }
+
+    pub fn toggle_bookmarks_only(&mut self) {
+        self.bookmarks_only = !self.bookmarks_only;
+        self.scroll = 0;
+        self.cursor_to(0);
+    }
+
+    /// Leaves the bookmark list with the cursor on the chosen bookmark.
+    pub fn open_selected_bookmark(&mut self) {
+        let Some(bookmark) = self.bookmarks.get(self.cursor) else {
+            return;
+        };
+        let row = self
+            .events
+            .iter()
+            .position(|e| e.index >= bookmark.message_index)
+            .unwrap_or(0);
+        self.bookmarks_only = false;
+        self.cursor_to(row);
+    }
+}
+
 pub struct App {
     local_sessions: Vec<Session>,
+    /// Sessions imported from other machines' handoff bundles, listed with
+    /// the local ones unless the transcript is on this machine too.
+    imported: Vec<Session>,
     entries: Vec<SessionEntry>,
+    tree_depth: HashMap<String, usize>,
+    subagent_rollups: HashMap<String, SubagentRollup>,
+    /// Live sessions sharing a checkout with another one, by session id.
+    worktree_clashes: HashMap<String, WorktreeClash>,
+    /// Working tree of each session directory seen, looked up once.
+    worktree_roots: HashMap<std::path::PathBuf, Option<std::path::PathBuf>>,
     filtered: Vec<usize>,
     selected: usize,
     view_mode: ViewMode,
     sort_column: SortColumn,
     filter_query: String,
+    errors_only: bool,
+    /// `[sessions]` settings, for the `exclude` patterns.
+    sessions_config: SessionsConfig,
+    /// Sessions hidden with `h`, as stored.
+    hidden: HashSet<String>,
+    /// List hidden, excluded and stale sessions anyway.
+    show_hidden: bool,
+    /// Sessions left out of the list as hidden or excluded.
+    hidden_count: usize,
+    /// Sessions left out as dead for longer than `hide_after_days`.
+    stale_count: usize,
     command_input: String,
     discovery: SessionDiscovery,
     store: Option<Store>,
@@ -695,115 +1449,625 @@ pub struct App {
     detail_cursor: usize,
     detail_preview: Option<(String, String)>,
     detail_preview_scroll: usize,
+    detail_env: Option<Vec<(String, String)>>,
+    detail_env_expanded: bool,
+    detail_tab: DetailTab,
+    detail_files: Option<DetailFiles>,
+    detail_diff: Option<DetailDiff>,
+    detail_transcript: Option<DetailTranscript>,
+    diff_tx: mpsc::Sender<(std::path::PathBuf, Result<WorkingDiff, String>)>,
+    diff_rx: mpsc::Receiver<(std::path::PathBuf, Result<WorkingDiff, String>)>,
+    transcript_tx: mpsc::Sender<(TranscriptView, String, Vec<TimelineEvent>)>,
+    transcript_rx: mpsc::Receiver<(TranscriptView, String, Vec<TimelineEvent>)>,
+    /// The branch a dead session's cwd is on, by session id.
+    branch_tx: mpsc::Sender<(String, Option<String>)>,
+    branch_rx: mpsc::Receiver<(String, Option<String>)>,
+    mcp_tx: mpsc::Sender<(std::path::PathBuf, String, McpHealth)>,
+    mcp_rx: mpsc::Receiver<(std::path::PathBuf, String, McpHealth)>,
+    claude_versions: ClaudeVersions,
+    versions_rx: Option<mpsc::Receiver<ClaudeVersions>>,
     usage_fetcher: UsageFetcher,
     usage: UsageData,
     tervezo_config: Option<TervezoConfig>,
+    frame_budget: std::time::Duration,
     tervezo_fetcher: Option<TervezoFetcher>,
     pub tervezo_detail: Option<TervezoDetailState>,
     pub tervezo_detail_tx: Option<mpsc::Sender<TervezoDetailMsg>>,
     tervezo_detail_rx: Option<mpsc::Receiver<TervezoDetailMsg>>,
+    /// Cancelled when the detail view closes or switches implementation.
+    tervezo_detail_cancel: CancelToken,
+    tasks: TaskPool,
+    row_cache: std::cell::RefCell<crate::ui::session_list::RowCache>,
+    /// Show the selected session's last prompt and reply beneath its row.
+    row_preview: bool,
+    path_style: PathStyle,
+    /// Earlier list states, oldest first. `list_history_pos` is the one
+    /// shown while stepping through them, or the length when at the live
+    /// state.
+    list_history: Vec<ListSnapshot>,
+    list_history_pos: usize,
+    /// Last exchange of the previewed session, keyed by id and activity.
+    row_preview_cache:
+        std::cell::RefCell<Option<(String, chrono::DateTime<chrono::Utc>, LastExchange)>>,
+    plugins: Plugins,
+    /// Results of the initial scan while it is still running.
+    discovery_rx: Option<mpsc::Receiver<DiscoveryMsg>>,
+    /// Today's exchange rate, while it is being fetched.
+    fx_rx: Option<mpsc::Receiver<f64>>,
+    host_rx: Option<mpsc::Receiver<HostStats>>,
+    host: Option<HostStats>,
     ssh_cache: HashMap<String, SshCredentials>,
+    /// Implementations restarted from this session, new id to old, for
+    /// servers that don't report `restartedFrom`.
+    restarted_from: HashMap<String, String>,
     sse_stream: Option<SseStream>,
     sse_rx: Option<mpsc::Receiver<SseMessage>>,
     pub tervezo_create: Option<TervezoCreateState>,
     tervezo_create_tx: Option<mpsc::Sender<TervezoCreateMsg>>,
     tervezo_create_rx: Option<mpsc::Receiver<TervezoCreateMsg>>,
     workspace_rx: Option<mpsc::Receiver<WorkspaceMsg>>,
+    pub org_picker: Option<OrgPickerState>,
+    org_rx: Option<mpsc::Receiver<Result<Vec<Org>, TervezoError>>>,
+    /// Name of the org Tervezo requests act for, if one was picked.
+    tervezo_org: Option<String>,
+    queue_cursor: usize,
+    /// Waiting implementation the cancel confirmation is for.
+    queue_cancel: Option<(String, String)>,
+    queue_rx: Option<mpsc::Receiver<Result<String, TervezoError>>>,
     pub new_session_menu: Option<NewSessionMenuState>,
+    pub action_menu: Option<ActionMenuState>,
+    pub git_menu: Option<GitMenuState>,
+    pub commit_prompt: Option<CommitPromptState>,
+    git_tx: mpsc::Sender<GitResult>,
+    git_rx: mpsc::Receiver<GitResult>,
+    /// Working directories with a git command still running.
+    git_running: HashSet<std::path::PathBuf>,
+    user_actions: Vec<UserAction>,
+    pub ticket_input: Option<TicketInputState>,
+    pub export_prompt: Option<ExportPromptState>,
+    pub outcome_prompt: Option<OutcomePromptState>,
+    outcome_stats: Vec<OutcomeStats>,
+    model_comparison: Vec<ModelComparison>,
+    pub cost_stats: Option<CostStats>,
+    prompt_outcomes: bool,
+    /// Tickets set by hand, which win over the branch name.
+    tickets: HashMap<String, String>,
+    tickets_config: TicketsConfig,
     log_scroll: usize,
+    diagnostics: Vec<FileDiagnostics>,
+    diagnostics_scroll: usize,
+    /// View to go back to from diagnostics (the list or a session's detail).
+    diagnostics_return: ViewMode,
     side_panel_open: bool,
     side_panel_focused: bool,
     pub ci_statuses: HashMap<String, CiStatus>,
     ci_tx: mpsc::Sender<(String, CiStatus)>,
     ci_rx: mpsc::Receiver<(String, CiStatus)>,
     ci_last_check: std::time::Instant,
+    /// Last finished CI result per session (true = green), to spot flips.
+    ci_outcomes: HashMap<String, bool>,
+    ci_notify: bool,
+    resource_sampler: ResourceSampler,
+    /// CPU and memory of live sessions by id, from the last refresh.
+    resources: HashMap<String, ProcessUsage>,
+    show_resources: bool,
+    /// Set when `[watchdog]` is enabled.
+    watchdog: Option<Watchdog>,
+    watchdog_action: WatchdogAction,
+    /// Hung sessions to kill and resume; the main loop has the screen size.
+    pending_restarts: Vec<String>,
+    time_tracker: TimeTracker,
+    pomodoro: Option<Pomodoro>,
+    focus_config: FocusConfig,
+    /// The web mirror of the active terminal, while it runs.
+    mirror: Option<Mirror>,
+    /// This c9s's claim on being the running one, with the control socket
+    /// `c9s open` and other invocations reach it through.
+    instance: Option<Instance>,
+    /// A deep link waiting for its session to be discovered.
+    pending_link: Option<DeepLink>,
+    mirror_bind: String,
+    window_tokens: Option<u64>,
+    dashboard_rows: Vec<Vec<DashboardWidget>>,
+    /// The focus timer's last label, to redraw only when it changes.
+    focus_label: String,
+    time_flushed: std::time::Instant,
+    /// Time tracked today and already stored, and the day it is for.
+    tracked_today: (chrono::NaiveDate, u64),
+    /// Open pull requests of local sessions' branches, by session id.
+    pub pr_statuses: HashMap<String, PrStatus>,
+    pr_tx: mpsc::Sender<(String, Option<PrStatus>)>,
+    pr_rx: mpsc::Receiver<(String, Option<PrStatus>)>,
     pub confirm_kill_session_id: Option<String>,
+    /// Attach waiting for a yes because the session runs in another terminal.
+    pub confirm_attach: Option<PendingAttach>,
+    pub copy_mode: Option<CopyMode>,
     unfollowed: HashSet<String>,
     pub session_files: Vec<crate::session::SessionFile>,
     pub session_file_cursor: usize,
+    pub local_timeline: Option<LocalTimelineState>,
+    pub quick_prompt: Option<QuickPromptState>,
+    pub recently_exited: Option<RecentlyExitedState>,
+    pub error_modal: Option<ErrorModalState>,
+    /// Last seen status of each remote implementation, for bells on change.
+    remote_statuses: HashMap<String, ImplementationStatus>,
+    toasts: Toasts,
 }
 
 impl App {
     pub fn new() -> Result<Self> {
-        let discovery = SessionDiscovery::new();
+        let config = AppConfig::load();
+        crate::format::init(config.format.clone());
+        Theme::set_palette(config.ui.palette);
+        crate::i18n::init(config.ui.locale.as_deref());
+        let fx_rx = crate::fx::start(&config.currency, true);
+        let mut discovery = SessionDiscovery::new();
+        discovery.set_show_all_per_cwd(config.sessions.show_all_per_cwd);
         let store = Store::open().ok();
-
         let tervezo_config = TervezoConfig::load();
+        let tervezo_org = tervezo_config
+            .as_ref()
+            .and_then(|c| c.saved_org().map(|o| o.name).or_else(|| c.org.clone()));
+
+        // The first scan parses every transcript and can take seconds on a
+        // large install, so it runs in the background and the list fills
+        // in as it goes. Its discovery, with the stats it cached, replaces
+        // this placeholder once done.
+        let mut app = Self::from_parts(&config, SessionDiscovery::new(), store, tervezo_config);
+        app.versions_rx = Some(spawn_version_check(&config.updates));
+        app.tervezo_org = tervezo_org;
+        app.load_imported();
+        app.discovery_rx = Some(spawn_discovery(discovery, app.store.is_some()));
+        app.fx_rx = fx_rx;
+        if config.ui.host {
+            app.host_rx = Some(crate::host::start());
+        }
+        if config.mirror.enabled {
+            app.start_mirror(false);
+        }
+        app.refresh_usage();
+        Ok(app)
+    }
+
+    /// An app over already-loaded parts, without sessions and without the
+    /// version check.
+    fn from_parts(
+        config: &AppConfig,
+        discovery: SessionDiscovery,
+        store: Option<Store>,
+        tervezo_config: Option<TervezoConfig>,
+    ) -> Self {
         let tervezo_fetcher = tervezo_config.as_ref().map(TervezoFetcher::spawn);
         let (ci_tx, ci_rx) = mpsc::channel();
+        let (pr_tx, pr_rx) = mpsc::channel();
+        let (mcp_tx, mcp_rx) = mpsc::channel();
+        let (diff_tx, diff_rx) = mpsc::channel();
+        let (transcript_tx, transcript_rx) = mpsc::channel();
+        let (branch_tx, branch_rx) = mpsc::channel();
+        let (git_tx, git_rx) = mpsc::channel();
+        let mut terminal_manager = TerminalManager::new();
+        terminal_manager.set_log_config(config.terminal_log.clone());
+        terminal_manager.set_tab_order(config.terminal.tab_order);
+        terminal_manager.set_spawn_config(SpawnConfig {
+            shell: config.terminal.shell.clone(),
+            term: config.terminal.term(),
+            colorterm: config.terminal.colorterm(),
+            env: config.terminal.env.clone(),
+        });
+        if let Some(levels) = store.as_ref().and_then(|s| s.notify_levels().ok()) {
+            terminal_manager.set_notify_levels(levels);
+        }
+        let tickets = store
+            .as_ref()
+            .and_then(|s| s.tickets().ok())
+            .unwrap_or_default();
+        let hidden = store
+            .as_ref()
+            .and_then(|s| s.hidden_sessions().ok())
+            .unwrap_or_default();
+        let today = chrono::Local::now().date_naive();
+        let tracked_today = (today, stored_time_on(store.as_ref(), today));
 
-        let mut app = Self {
+        Self {
             local_sessions: Vec::new(),
+            imported: Vec::new(),
             entries: Vec::new(),
+            tree_depth: HashMap::new(),
+            subagent_rollups: HashMap::new(),
+            worktree_clashes: HashMap::new(),
+            worktree_roots: HashMap::new(),
             filtered: Vec::new(),
             selected: 0,
             view_mode: ViewMode::List,
             sort_column: SortColumn::LastActive,
             filter_query: String::new(),
+            errors_only: false,
+            sessions_config: config.sessions.clone(),
+            hidden,
+            show_hidden: false,
+            hidden_count: 0,
+            stale_count: 0,
             command_input: String::new(),
             discovery,
             store,
             should_quit: false,
-            terminal_manager: TerminalManager::new(),
+            terminal_manager,
             detail_config: None,
             detail_items: Vec::new(),
             detail_cursor: 0,
             detail_preview: None,
+            detail_tab: DetailTab::Overview,
+            detail_files: None,
+            detail_diff: None,
+            detail_transcript: None,
+            diff_tx,
+            diff_rx,
+            transcript_tx,
+            transcript_rx,
+            branch_tx,
+            branch_rx,
             detail_preview_scroll: 0,
+            detail_env: None,
+            detail_env_expanded: false,
+            mcp_tx,
+            mcp_rx,
+            claude_versions: ClaudeVersions::default(),
+            versions_rx: None,
             usage_fetcher: UsageFetcher::new(),
             usage: UsageData::default(),
             tervezo_config,
+            frame_budget: config.terminal.frame_budget(),
             tervezo_fetcher,
             tervezo_detail: None,
             tervezo_detail_tx: None,
             tervezo_detail_rx: None,
+            tervezo_detail_cancel: CancelToken::new(),
+            tasks: TaskPool::new(TASK_WORKERS),
+            row_cache: Default::default(),
+            row_preview: false,
+            path_style: PathStyle::default(),
+            list_history: Vec::new(),
+            list_history_pos: 0,
+            plugins: Plugins::load(&config.plugins),
+            row_preview_cache: Default::default(),
+            discovery_rx: None,
+            fx_rx: None,
+            host_rx: None,
+            host: None,
             ssh_cache: HashMap::new(),
+            restarted_from: HashMap::new(),
             sse_stream: None,
             sse_rx: None,
             tervezo_create: None,
             tervezo_create_tx: None,
             tervezo_create_rx: None,
             workspace_rx: None,
+            org_picker: None,
+            org_rx: None,
+            tervezo_org: None,
+            queue_cursor: 0,
+            queue_cancel: None,
+            queue_rx: None,
             new_session_menu: None,
+            action_menu: None,
+            git_menu: None,
+            commit_prompt: None,
+            git_tx,
+            git_rx,
+            git_running: HashSet::new(),
+            user_actions: config.actions.clone(),
+            ticket_input: None,
+            export_prompt: None,
+            outcome_prompt: None,
+            outcome_stats: Vec::new(),
+            model_comparison: Vec::new(),
+            cost_stats: None,
+            prompt_outcomes: config.outcomes.prompt,
+            tickets,
+            tickets_config: config.tickets.clone(),
             log_scroll: 0,
+            diagnostics: Vec::new(),
+            diagnostics_scroll: 0,
+            diagnostics_return: ViewMode::List,
             side_panel_open: false,
             side_panel_focused: false,
             ci_statuses: HashMap::new(),
             ci_tx,
             ci_rx,
+            ci_outcomes: HashMap::new(),
+            ci_notify: config.ci.notify,
+            resource_sampler: ResourceSampler::new(),
+            resources: HashMap::new(),
+            show_resources: config.ui.resources,
+            watchdog: config
+                .watchdog
+                .enabled
+                .then(|| Watchdog::new(chrono::Duration::minutes(config.watchdog.minutes as i64))),
+            watchdog_action: config.watchdog.action,
+            pending_restarts: Vec::new(),
+            time_tracker: TimeTracker::new(
+                config.time_tracking.enabled,
+                std::time::Duration::from_secs(config.time_tracking.idle_minutes * 60),
+                chrono::Utc::now(),
+            ),
+            time_flushed: std::time::Instant::now(),
+            pomodoro: None,
+            focus_config: config.focus.clone(),
+            mirror: None,
+            instance: None,
+            pending_link: None,
+            mirror_bind: config.mirror.bind.clone(),
+            window_tokens: config.usage.window_tokens,
+            dashboard_rows: config.dashboard.rows.clone(),
+            focus_label: String::new(),
+            tracked_today,
+            pr_statuses: HashMap::new(),
+            pr_tx,
+            pr_rx,
             ci_last_check: std::time::Instant::now()
                 .checked_sub(std::time::Duration::from_secs(300))
                 .unwrap_or_else(std::time::Instant::now),
             confirm_kill_session_id: None,
+            confirm_attach: None,
+            copy_mode: None,
             unfollowed: HashSet::new(),
             session_files: Vec::new(),
             session_file_cursor: 0,
-        };
+            local_timeline: None,
+            quick_prompt: None,
+            error_modal: None,
+            recently_exited: None,
+            remote_statuses: HashMap::new(),
+            toasts: Toasts::default(),
+        }
+    }
 
-        app.refresh()?;
-        Ok(app)
+    /// An app showing `sessions` with default config and nothing read from
+    /// disk, for rendering tests.
+    #[cfg(test)]
+    pub(crate) fn with_sessions(sessions: Vec<Session>) -> Self {
+        let mut app = Self::from_parts(&AppConfig::default(), SessionDiscovery::new(), None, None);
+        // Compiled-in plugins would add columns to every fixture.
+        app.plugins = Plugins::default();
+        app.local_sessions = sessions;
+        app.merge_entries();
+        app.apply_sort();
+        app.apply_filter();
+        app
     }
 
     pub fn refresh(&mut self) -> Result<()> {
-        self.local_sessions = self.discovery.discover_all().unwrap_or_default();
-
-        if let Some(ref store) = self.store {
-            for session in &self.local_sessions {
-                let _ = store.upsert_session(session);
+        // Until the initial scan is in, a second one would only compete
+        // with it.
+        if !self.is_discovering() {
+            self.load_imported();
+            let sessions = self.discovery.discover_all().unwrap_or_default();
+            if let Some(ref store) = self.store {
+                let parsed = self.discovery.take_parsed_stats();
+                if !parsed.is_empty() {
+                    let _ = store.save_jsonl_stats(&parsed);
+                }
+            }
+            self.set_local_sessions(sessions, true);
+            if self.view_mode == ViewMode::Diagnostics {
+                self.diagnostics = self.discovery.diagnostics();
             }
         }
 
-        self.merge_entries();
-        self.apply_sort();
-        self.apply_filter();
+        self.usage = self.usage_fetcher.get().clone();
 
-        if self.selected >= self.filtered.len() && !self.filtered.is_empty() {
+        Ok(())
+    }
+
+    /// Reads sessions imported since the last call, e.g. with `c9s import`
+    /// while the TUI runs.
+    fn load_imported(&mut self) {
+        let Some(ref store) = self.store else {
+            return;
+        };
+        let known: HashSet<String> = self.imported.iter().map(|s| s.id.clone()).collect();
+        let new = load_imported(store, &|id| known.contains(id));
+        self.imported.extend(new);
+    }
+
+    /// Whether the initial scan is still running.
+    pub fn is_discovering(&self) -> bool {
+        self.discovery_rx.is_some()
+    }
+
+    /// Applies partial and final results of the initial scan. Returns true
+    /// if the list changed.
+    pub fn drain_discovery(&mut self) -> bool {
+        let Some(rx) = self.discovery_rx.take() else {
+            return false;
+        };
+        let mut latest = None;
+        let mut done = false;
+        loop {
+            match rx.try_recv() {
+                Ok(DiscoveryMsg::Partial(sessions)) => latest = Some(sessions),
+                Ok(DiscoveryMsg::Done(sessions, discovery)) => {
+                    latest = Some(sessions);
+                    self.discovery = *discovery;
+                    done = true;
+                    break;
+                }
+                Err(mpsc::TryRecvError::Empty) => break,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    done = true;
+                    break;
+                }
+            }
+        }
+        if !done {
+            self.discovery_rx = Some(rx);
+        }
+        let changed = latest.is_some() || done;
+        if let Some(sessions) = latest {
+            self.set_local_sessions(sessions, done);
+        }
+        changed
+    }
+
+    /// Replaces the local sessions and rebuilds the list. Only complete
+    /// scans are written to the store.
+    fn set_local_sessions(&mut self, mut sessions: Vec<Session>, complete: bool) {
+        let local: HashSet<&str> = sessions.iter().map(|s| s.id.as_str()).collect();
+        let imported: Vec<Session> = self
+            .imported
+            .iter()
+            .filter(|s| !local.contains(s.id.as_str()))
+            .cloned()
+            .collect();
+        sessions.extend(imported);
+        self.local_sessions = sessions;
+
+        if complete {
+            if let Some(ref store) = self.store {
+                for session in &self.local_sessions {
+                    let _ = store.upsert_session(session);
+                }
+            }
+            self.check_watchdog();
+            self.sample_resources();
+        }
+
+        self.merge_entries();
+        self.apply_sort();
+        self.apply_filter();
+
+        if self.selected >= self.filtered.len() && !self.filtered.is_empty() {
             self.selected = self.filtered.len() - 1;
         }
+    }
 
-        self.usage = self.usage_fetcher.get().clone();
+    fn sample_resources(&mut self) {
+        let live: Vec<(u32, String)> = self
+            .local_sessions
+            .iter()
+            .filter_map(|s| s.pid.map(|pid| (pid, s.id.clone())))
+            .collect();
+        let pids: Vec<u32> = live.iter().map(|(pid, _)| *pid).collect();
+        let mut usage = self.resource_sampler.sample(&pids);
+        self.resources = live
+            .into_iter()
+            .filter_map(|(pid, id)| usage.remove(&pid).map(|u| (id, u)))
+            .collect();
+    }
 
-        Ok(())
+    pub fn resources(&self, id: &str) -> Option<&ProcessUsage> {
+        self.resources.get(id)
+    }
+
+    pub fn show_resources(&self) -> bool {
+        self.show_resources
+    }
+
+    pub fn toggle_resources(&mut self) {
+        self.show_resources = !self.show_resources;
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_resources(&mut self, id: &str, usage: ProcessUsage) {
+        self.resources.insert(id.to_string(), usage);
+    }
+
+    fn check_watchdog(&mut self) {
+        let Some(ref mut watchdog) = self.watchdog else {
+            return;
+        };
+        for id in watchdog.check(&self.local_sessions, chrono::Utc::now()) {
+            let Some(session) = self.local_sessions.iter().find(|s| s.id == id) else {
+                continue;
+            };
+            let name = session.project_name.clone();
+            crate::tlog!(
+                warn,
+                "Watchdog: {} ({}) has waited on the model with no output since {}",
+                name,
+                id,
+                session.last_activity.to_rfc3339()
+            );
+            match self.watchdog_action {
+                WatchdogAction::Notify => {
+                    self.toasts.push(
+                        Severity::Warning,
+                        tf("{name} looks hung: K restarts it", &[("name", &name)]),
+                    );
+                    self.terminal_manager
+                        .notify_external(&id, &format!("{} looks hung", name));
+                }
+                WatchdogAction::Restart => self.pending_restarts.push(id),
+            }
+        }
+    }
+
+    pub fn is_hung(&self, id: &str) -> bool {
+        self.watchdog.as_ref().is_some_and(|w| w.is_hung(id))
+    }
+
+    pub fn take_pending_restarts(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending_restarts)
+    }
+
+    /// Restarts the selected session if the watchdog found it hung.
+    pub fn restart_selected(&mut self, rows: u16, cols: u16) {
+        let Some(id) = self.selected_session().map(|e| e.id().to_string()) else {
+            return;
+        };
+        if self.watchdog.is_none() {
+            self.toasts.push(
+                Severity::Info,
+                t("The watchdog is off: enable [watchdog] in ~/.c9s/config.toml"),
+            );
+        } else if !self.is_hung(&id) {
+            self.toasts.push(
+                Severity::Info,
+                t("K restarts sessions the watchdog found hung"),
+            );
+        } else {
+            self.restart_session(&id, rows, cols);
+        }
+    }
+
+    /// Kills a session's claude and resumes it in a terminal tab.
+    pub fn restart_session(&mut self, id: &str, rows: u16, cols: u16) {
+        let Some(session) = self.local_sessions.iter().find(|s| s.id == id) else {
+            return;
+        };
+        let (name, cwd, pid) = (
+            session.project_name.clone(),
+            session.cwd.clone(),
+            session.pid,
+        );
+        crate::tlog!(
+            info,
+            "Watchdog: restarting {} ({}), killing pid {:?}",
+            name,
+            id,
+            pid
+        );
+        match self
+            .terminal_manager
+            .restart(id, &name, &cwd, pid, rows, cols)
+        {
+            Ok(()) => {
+                if let Some(ref mut watchdog) = self.watchdog {
+                    watchdog.forget(id);
+                }
+                self.toasts.push(
+                    Severity::Success,
+                    tf("Restarted {name}", &[("name", &name)]),
+                );
+            }
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf(
+                    "Restarting {name} failed: {error}",
+                    &[("name", &name), ("error", &e.to_string())],
+                ),
+            ),
+        }
     }
 
     pub fn refresh_usage(&mut self) {
@@ -818,6 +2082,7 @@ impl App {
         if let Some(ref fetcher) = self.tervezo_fetcher {
             if fetcher.take_dirty() {
                 self.merge_entries();
+                self.notify_remote_transitions();
                 self.apply_sort();
                 self.apply_filter();
                 if self.selected >= self.filtered.len() && !self.filtered.is_empty() {
@@ -845,34 +2110,55 @@ impl App {
                         changed = true;
                     }
                     TervezoDetailMsg::TimelineError(err) => {
-                        state.timeline_error = Some(err);
+                        if state.timeline_loading_older {
+                            state.timeline_loading_older = false;
+                            self.toasts.push(Severity::Error, err.with_hint());
+                        } else {
+                            state.timeline_error = Some(err);
+                        }
                         changed = true;
                     }
-                    TervezoDetailMsg::TimelineAppend(msg) => {
-                        state.timeline.push(msg);
-                        if state.timeline.len() > 1000 {
-                            state.timeline.drain(..state.timeline.len() - 1000);
-                        }
+                    TervezoDetailMsg::TimelineHasOlder(has_older) => {
+                        state.timeline_has_older = has_older;
+                        changed = true;
+                    }
+                    TervezoDetailMsg::TimelineOlder(mut msgs, has_older) => {
+                        msgs.append(&mut state.timeline);
+                        state.timeline = msgs;
+                        state.timeline_has_older = has_older;
+                        state.timeline_loading_older = false;
+                        // Line offsets below the new messages have moved;
+                        // start from the top, where they are.
+                        state.timeline_scroll = 0;
+                        state.timeline_at_bottom = false;
+                        changed = true;
+                    }
+                    TervezoDetailMsg::TimelineMore(msgs) => {
+                        state.timeline.extend(msgs);
                         changed = true;
                     }
                     TervezoDetailMsg::Plan(content) => {
                         state.plan_content = Some(content);
                         state.loading.remove(&TervezoTab::Plan);
+                        state.tab_errors.remove(&TervezoTab::Plan);
                         changed = true;
                     }
                     TervezoDetailMsg::Analysis(content) => {
                         state.analysis_content = Some(content);
                         state.loading.remove(&TervezoTab::Analysis);
+                        state.tab_errors.remove(&TervezoTab::Analysis);
                         changed = true;
                     }
                     TervezoDetailMsg::Changes(changes) => {
                         state.changes = Some(changes);
                         state.loading.remove(&TervezoTab::Changes);
+                        state.tab_errors.remove(&TervezoTab::Changes);
                         changed = true;
                     }
                     TervezoDetailMsg::TestOutput(reports) => {
                         state.test_output = Some(reports);
                         state.loading.remove(&TervezoTab::TestOutput);
+                        state.tab_errors.remove(&TervezoTab::TestOutput);
                         changed = true;
                     }
                     TervezoDetailMsg::SshCreds(creds) => {
@@ -891,27 +2177,44 @@ impl App {
                     }
                     TervezoDetailMsg::ActionSuccess(msg) => {
                         state.action_loading = false;
-                        state.action_result = Some(Ok(msg));
+                        match msg.strip_prefix("NAVIGATE:") {
+                            Some(id) => state.navigate_to = Some(id.to_string()),
+                            None => self.toasts.push(Severity::Success, msg),
+                        }
                         changed = true;
                     }
-                    TervezoDetailMsg::ActionError(msg) => {
+                    TervezoDetailMsg::ActionError(err) => {
                         state.action_loading = false;
-                        state.action_result = Some(Err(msg));
+                        self.toasts.push(Severity::Error, err.with_hint());
                         changed = true;
                     }
                     TervezoDetailMsg::PromptSent(msg) => {
                         state.prompt_sending = false;
                         state.prompt_input.clear();
-                        state.action_result = Some(Ok(msg));
+                        self.toasts.push(Severity::Success, msg);
                         changed = true;
                     }
-                    TervezoDetailMsg::PromptError(msg) => {
+                    TervezoDetailMsg::PromptError(err) => {
                         state.prompt_sending = false;
-                        state.action_result = Some(Err(msg));
+                        self.toasts.push(Severity::Error, err.with_hint());
+                        changed = true;
+                    }
+                    TervezoDetailMsg::Comparison(files) => {
+                        if let Some(ref mut comparison) = state.comparison {
+                            comparison.files = Some(files);
+                            comparison.error = None;
+                        }
+                        changed = true;
+                    }
+                    TervezoDetailMsg::ComparisonError(err) => {
+                        if let Some(ref mut comparison) = state.comparison {
+                            comparison.error = Some(err);
+                        }
                         changed = true;
                     }
-                    TervezoDetailMsg::Error(tab, _err) => {
+                    TervezoDetailMsg::Error(tab, err) => {
                         state.loading.remove(&tab);
+                        state.tab_errors.insert(tab, err);
                         changed = true;
                     }
                 }
@@ -935,6 +2238,7 @@ impl App {
                         if state.timeline.len() > 1000 {
                             let excess = state.timeline.len() - 1000;
                             state.timeline.drain(..excess);
+                            state.timeline_has_older = true;
                             // Adjust scroll position so it stays on the same content
                             state.timeline_scroll = state.timeline_scroll.saturating_sub(excess);
                         }
@@ -980,6 +2284,206 @@ impl App {
         rx.try_recv().ok()
     }
 
+    /// Opens the org picker and fetches the orgs the key can act for.
+    pub fn open_org_picker(&mut self) {
+        let Some(config) = self.tervezo_config.clone() else {
+            self.toasts.push(
+                Severity::Info,
+                t("Tervezo is not configured: set TERVEZO_API_KEY or [tervezo] api_key"),
+            );
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.org_rx = Some(rx);
+        crate::wakeup::spawn(move || {
+            let _ = tx.send(crate::tervezo::TervezoClient::new(&config).list_orgs());
+        });
+        self.org_picker = Some(OrgPickerState {
+            loading: true,
+            ..Default::default()
+        });
+        self.set_view_mode(ViewMode::OrgPicker);
+    }
+
+    /// Fills the org picker once the list arrives, with the cursor on the
+    /// active org. Returns true if it needs redrawing.
+    pub fn drain_orgs(&mut self) -> bool {
+        let Some(result) = self.org_rx.as_ref().and_then(|rx| rx.try_recv().ok()) else {
+            return false;
+        };
+        self.org_rx = None;
+        let active = self.tervezo_config.as_ref().and_then(|c| c.org.clone());
+        let Some(ref mut state) = self.org_picker else {
+            return false;
+        };
+        state.loading = false;
+        match result {
+            Ok(orgs) => {
+                state.cursor = orgs
+                    .iter()
+                    .position(|o| Some(&o.id) == active.as_ref())
+                    .unwrap_or(0);
+                state.orgs = orgs;
+            }
+            Err(e) => state.error = Some(e.to_string()),
+        }
+        true
+    }
+
+    /// Makes every Tervezo request act for the org under the picker's
+    /// cursor, remembers it for this key and reloads implementations.
+    pub fn switch_org(&mut self) {
+        let Some(org) = self
+            .org_picker
+            .as_ref()
+            .and_then(|s| s.orgs.get(s.cursor))
+            .cloned()
+        else {
+            return;
+        };
+        self.set_view_mode(ViewMode::List);
+        let Some(ref mut config) = self.tervezo_config else {
+            return;
+        };
+        if config.org.as_ref() == Some(&org.id) {
+            return;
+        }
+        config.org = Some(org.id.clone());
+        config.save_org(&SavedOrg {
+            id: org.id.clone(),
+            name: org.name.clone(),
+        });
+        tlog!(info, "switched tervezo org to {} ({})", org.name, org.id);
+        // Statuses from the old org aren't transitions in the new one.
+        self.remote_statuses.clear();
+        self.tervezo_fetcher = Some(TervezoFetcher::spawn(config));
+        self.tervezo_org = Some(org.name.clone());
+        self.merge_and_refilter();
+        self.toasts.push(
+            Severity::Success,
+            tf("Switched to {org}", &[("org", &org.name)]),
+        );
+    }
+
+    pub fn tervezo_org(&self) -> Option<&str> {
+        self.tervezo_org.as_deref()
+    }
+
+    /// Implementations waiting for the remote agent, next first.
+    pub fn queue(&self) -> Vec<QueueEntry> {
+        let implementations: Vec<Implementation> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.as_remote())
+            .cloned()
+            .collect();
+        build_queue(&implementations, chrono::Utc::now())
+    }
+
+    pub fn queue_cursor(&self) -> usize {
+        self.queue_cursor.min(self.queue().len().saturating_sub(1))
+    }
+
+    pub fn open_queue(&mut self) {
+        if !self.has_tervezo() {
+            self.toasts.push(
+                Severity::Info,
+                t("Tervezo is not configured: set TERVEZO_API_KEY or [tervezo] api_key"),
+            );
+            return;
+        }
+        self.queue_cursor = 0;
+        self.set_view_mode(ViewMode::Queue);
+    }
+
+    pub fn queue_up(&mut self) {
+        self.queue_cursor = self.queue_cursor().saturating_sub(1);
+    }
+
+    pub fn queue_down(&mut self) {
+        if self.queue_cursor + 1 < self.queue().len() {
+            self.queue_cursor += 1;
+        }
+    }
+
+    /// Moves the implementation under the cursor one place up, or to the
+    /// front with `to_front`. The cursor follows it.
+    pub fn queue_bump(&mut self, to_front: bool) {
+        let cursor = self.queue_cursor();
+        let Some(entry) = self.queue().into_iter().nth(cursor) else {
+            return;
+        };
+        if cursor == 0 {
+            return;
+        }
+        let position = if to_front { 0 } else { cursor - 1 };
+        let id = entry.implementation.id.clone();
+        let name = entry.implementation.display_name().to_string();
+        self.spawn_queue_action(move |client| {
+            client
+                .move_in_queue(&id, position)
+                .map(|_| format!("Moved {} to #{}", name, position + 1))
+        });
+        self.queue_cursor = position;
+    }
+
+    /// Asks to confirm cancelling the implementation under the cursor.
+    pub fn queue_confirm_cancel(&mut self) {
+        if let Some(entry) = self.queue().into_iter().nth(self.queue_cursor()) {
+            let imp = entry.implementation;
+            self.queue_cancel = Some((imp.id.clone(), imp.display_name().to_string()));
+            self.set_view_mode(ViewMode::QueueConfirm);
+        }
+    }
+
+    pub fn queue_cancel_pending(&self) -> Option<&str> {
+        self.queue_cancel.as_ref().map(|(_, name)| name.as_str())
+    }
+
+    /// Cancels the confirmed implementation, or drops the confirmation.
+    pub fn queue_finish_cancel(&mut self, confirmed: bool) {
+        self.set_view_mode(ViewMode::Queue);
+        let Some((id, name)) = self.queue_cancel.take() else {
+            return;
+        };
+        if confirmed {
+            self.spawn_queue_action(move |client| {
+                client.cancel(&id).map(|_| format!("Cancelled {}", name))
+            });
+        }
+    }
+
+    fn spawn_queue_action<F>(&mut self, f: F)
+    where
+        F: FnOnce(&crate::tervezo::TervezoClient) -> Result<String, TervezoError> + Send + 'static,
+    {
+        let Some(config) = self.tervezo_config.clone() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel();
+        self.queue_rx = Some(rx);
+        crate::wakeup::spawn(move || {
+            let _ = tx.send(f(&crate::tervezo::TervezoClient::new(&config)));
+        });
+    }
+
+    /// Reports a finished queue action and fetches the new order. Returns
+    /// true if anything needs redrawing.
+    pub fn drain_queue_actions(&mut self) -> bool {
+        let Some(result) = self.queue_rx.as_ref().and_then(|rx| rx.try_recv().ok()) else {
+            return false;
+        };
+        self.queue_rx = None;
+        match result {
+            Ok(message) => self.toasts.push(Severity::Success, message),
+            Err(e) => self.toasts.push(Severity::Error, e.with_hint()),
+        }
+        if let Some(ref fetcher) = self.tervezo_fetcher {
+            fetcher.poll_now();
+        }
+        true
+    }
+
     fn spawn_workspace_fetch(&mut self) {
         let config = match self.tervezo_config.as_ref() {
             Some(c) => c.clone(),
@@ -987,7 +2491,7 @@ impl App {
         };
         let (tx, rx) = mpsc::channel();
         self.workspace_rx = Some(rx);
-        std::thread::spawn(move || {
+        crate::wakeup::spawn(move || {
             let client = crate::tervezo::TervezoClient::new(&config);
             match client.list_workspaces() {
                 Ok(workspaces) => {
@@ -1000,6 +2504,28 @@ impl App {
         });
     }
 
+    /// Drops requests still queued for the previous detail view.
+    pub fn renew_detail_cancel(&mut self) {
+        self.tervezo_detail_cancel.cancel();
+        self.tervezo_detail_cancel = CancelToken::new();
+    }
+
+    /// Runs `f` on the task pool for the open Tervezo detail view. `what`
+    /// names the request: while one is queued or running, another with the
+    /// same name for the same implementation is dropped. Returns whether
+    /// `f` was queued. Only for reads: closing the view drops what is
+    /// still queued, so writes run on `wakeup::spawn` instead.
+    pub fn spawn_detail_task<F>(&self, what: &str, f: F) -> bool
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let Some(state) = self.tervezo_detail.as_ref() else {
+            return false;
+        };
+        let key = format!("tervezo:{}:{}", state.implementation_id, what);
+        self.tasks.spawn(key, &self.tervezo_detail_cancel, f)
+    }
+
     pub fn start_sse_stream(&mut self, implementation_id: &str) {
         self.stop_sse_stream();
 
@@ -1020,6 +2546,24 @@ impl App {
     }
 
     fn merge_entries(&mut self) {
+        self.subagent_rollups.clear();
+        for sub in self.local_sessions.iter().filter(|s| s.is_subagent) {
+            if let Some(ref parent) = sub.parent_id {
+                let r = self.subagent_rollups.entry(parent.clone()).or_default();
+                r.count += 1;
+                r.tokens += sub.total_tokens();
+                r.cost += sub.estimated_cost_usd();
+            }
+        }
+
+        let roots = &mut self.worktree_roots;
+        self.worktree_clashes = worktree_clashes(&self.local_sessions, |dir| {
+            roots
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| git::worktree_root(dir))
+                .clone()
+        });
+
         let mut entries: Vec<SessionEntry> = self
             .local_sessions
             .iter()
@@ -1040,6 +2584,31 @@ impl App {
         self.entries = entries;
     }
 
+    /// Rings the bell for implementations that moved into one of the
+    /// configured statuses since the last fetch. The first fetch only
+    /// records statuses.
+    fn notify_remote_transitions(&mut self) {
+        let notify_on = match self.tervezo_config {
+            Some(ref cfg) => cfg.notify_on.clone(),
+            None => return,
+        };
+        let mut changed = Vec::new();
+        for imp in self.entries.iter().filter_map(|e| e.as_remote()) {
+            let previous = self
+                .remote_statuses
+                .insert(imp.id.clone(), imp.status.clone());
+            let moved = previous.is_some_and(|p| p != imp.status);
+            if moved && notify_on.contains(&imp.status) {
+                let name = imp.title.clone().unwrap_or_else(|| imp.id.clone());
+                changed.push((imp.id.clone(), name, imp.status.label().to_string()));
+            }
+        }
+        for (id, name, status) in changed {
+            self.terminal_manager
+                .notify_external(&id, &format!("Tervezo {} is {}", name, status));
+        }
+    }
+
     pub fn unfollow_session(&mut self, id: &str) {
         self.unfollowed.insert(id.to_string());
     }
@@ -1051,12 +2620,44 @@ impl App {
         if self.selected >= self.filtered.len() && !self.filtered.is_empty() {
             self.selected = self.filtered.len() - 1;
         }
+        self.refresh_restart_chain();
     }
 
     pub fn usage(&self) -> &UsageData {
         &self.usage
     }
 
+    /// The current 5-hour window estimated from transcripts, with the
+    /// configured limit; only when the usage API has no figure for it.
+    pub fn usage_window(&self) -> Option<(WindowEstimate, Option<u64>)> {
+        if self.usage.five_hour.is_some() {
+            return None;
+        }
+        let used = self
+            .entries
+            .iter()
+            .filter_map(|e| e.as_local())
+            .flat_map(|s| s.window_tokens.iter().copied())
+            .collect();
+        estimate_window(used, chrono::Utc::now()).map(|w| (w, self.window_tokens))
+    }
+
+    /// Tokens used across local sessions over the last usage window, in
+    /// `buckets` slices, oldest first.
+    pub fn usage_history(&self, buckets: usize) -> Vec<u64> {
+        let used: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.as_local())
+            .flat_map(|s| s.window_tokens.iter().copied())
+            .collect();
+        bucket_usage(&used, chrono::Utc::now(), WINDOW, buckets)
+    }
+
+    pub fn dashboard_rows(&self) -> &[Vec<DashboardWidget>] {
+        &self.dashboard_rows
+    }
+
     fn apply_sort(&mut self) {
         match self.sort_column {
             SortColumn::LastActive => {
@@ -1082,63 +2683,1182 @@ impl App {
                     .sort_by_key(|e| std::cmp::Reverse(e.sort_key_tokens()));
             }
         }
+        self.group_children();
     }
 
-    fn apply_filter(&mut self) {
-        let query = self.filter_query.to_lowercase();
-        self.filtered = self
-            .entries
-            .iter()
-            .enumerate()
-            .filter(|(_, e)| e.matches_filter(&query))
-            .map(|(i, _)| i)
-            .collect();
+    fn group_children(&mut self) {
+        let (entries, depths) = group_by_parent(std::mem::take(&mut self.entries));
+        self.entries = entries;
+        self.tree_depth = depths;
     }
 
-    pub fn filtered_sessions(&self) -> Vec<&SessionEntry> {
-        self.filtered
-            .iter()
-            .filter_map(|&i| self.entries.get(i))
-            .collect()
+    pub fn subagent_rollup(&self, id: &str) -> Option<&SubagentRollup> {
+        self.subagent_rollups.get(id)
     }
 
-    pub fn selected_session(&self) -> Option<&SessionEntry> {
-        self.filtered
-            .get(self.selected)
-            .and_then(|&i| self.entries.get(i))
+    pub fn worktree_clash(&self, id: &str) -> Option<&WorktreeClash> {
+        self.worktree_clashes.get(id)
     }
 
-    pub fn selected_index(&self) -> usize {
-        self.selected
+    /// Imported sessions have no transcript in `~/.claude` to resume from.
+    pub fn toast_imported(&mut self) {
+        self.toasts.push(
+            Severity::Warning,
+            t("Imported sessions are read-only: t shows the timeline, d the detail"),
+        );
     }
 
-    pub fn set_selected(&mut self, idx: usize) {
-        self.selected = idx;
+    /// Subagents can't be resumed on their own; attaching to one attaches
+    /// to the session that spawned it.
+    pub fn attach_target<'a>(&'a self, session: &'a Session) -> &'a Session {
+        if !session.is_subagent {
+            return session;
+        }
+        session
+            .parent_id
+            .as_deref()
+            .and_then(|p| self.local_sessions.iter().find(|s| s.id == p))
+            .unwrap_or(session)
+    }
+
+    /// Starts a quick prompt for the selected session if it has a running
+    /// terminal in the background.
+    pub fn open_quick_prompt(&mut self) -> bool {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            return false;
+        };
+        let target = self.attach_target(session);
+        let running = self
+            .terminal_manager
+            .terminal_for(&target.id)
+            .is_some_and(|t| !t.is_exited());
+        if !running {
+            return false;
+        }
+        self.quick_prompt = Some(QuickPromptState {
+            session_id: target.id.clone(),
+            project_name: target.project_name.clone(),
+            input: String::new(),
+        });
+        self.set_view_mode(ViewMode::QuickPrompt);
+        true
     }
 
-    pub fn view_mode(&self) -> &ViewMode {
-        &self.view_mode
+    /// Sends the quick prompt line followed by Enter and returns to the list.
+    pub fn submit_quick_prompt(&mut self) {
+        let Some(prompt) = self.quick_prompt.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::List);
+        if prompt.input.is_empty() {
+            return;
+        }
+        let result = self
+            .terminal_manager
+            .submit_to(&prompt.session_id, prompt.input.as_bytes());
+        match result {
+            Ok(()) => self.toasts.push(
+                Severity::Success,
+                tf(
+                    "Sent to {project}: {prompt}",
+                    &[("project", &prompt.project_name), ("prompt", &prompt.input)],
+                ),
+            ),
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf("Quick prompt failed: {error}", &[("error", &e.to_string())]),
+            ),
+        }
     }
 
-    pub fn set_view_mode(&mut self, mode: ViewMode) {
-        if matches!(
-            mode,
-            ViewMode::QSwitcher | ViewMode::TerminalQSwitcher | ViewMode::TervezoQSwitcher
-        ) {
-            let max = self.filtered.len().min(9);
-            if self.selected >= max && max > 0 {
-                self.selected = max - 1;
+    /// Opens the picker of closed terminal tabs. Returns false if no tab
+    /// has been closed yet in this run.
+    pub fn open_recently_exited(&mut self) -> bool {
+        if self.terminal_manager.recently_exited().is_empty() {
+            return false;
+        }
+        self.recently_exited = Some(RecentlyExitedState {
+            cursor: 0,
+            viewing: None,
+            from_bottom: 0,
+            visible_height: std::cell::Cell::new(20),
+        });
+        self.set_view_mode(ViewMode::RecentlyExited);
+        true
+    }
+
+    pub fn recently_exited_move(&mut self, delta: isize) {
+        let Some(ref mut state) = self.recently_exited else {
+            return;
+        };
+        let exited = self.terminal_manager.recently_exited();
+        let rows = match state.viewing {
+            Some(i) => exited.get(i).map_or(0, |e| e.lines.len()),
+            None => exited.len(),
+        };
+        state.move_by(delta, rows);
+    }
+
+    /// Opens the selected entry's output, scrolled to the end.
+    pub fn recently_exited_select(&mut self) {
+        if let Some(ref mut state) = self.recently_exited {
+            state.viewing = Some(state.cursor);
+            state.from_bottom = 0;
+        }
+    }
+
+    /// Closes the viewer, or the picker when no entry is open.
+    pub fn recently_exited_back(&mut self) {
+        match self.recently_exited {
+            Some(ref mut state) if state.viewing.is_some() => state.viewing = None,
+            _ => {
+                self.recently_exited = None;
+                self.set_view_mode(ViewMode::List);
             }
         }
-        if mode == ViewMode::Detail {
-            if let Some(entry) = self.selected_session() {
-                if let Some(session) = entry.as_local() {
-                    let cfg = scan_session_config(&session.cwd);
-                    self.detail_items = build_config_items(&cfg, &session.cwd);
-                    self.detail_config = Some(cfg);
-                    self.detail_cursor = 0;
+    }
+
+    /// Starts copy mode on the active terminal, at its cursor.
+    pub fn enter_copy_mode(&mut self) {
+        let Some(term) = self.terminal_manager.active_terminal() else {
+            return;
+        };
+        self.copy_mode = Some(CopyMode {
+            cursor: term.copy_start(),
+            anchor: None,
+        });
+        self.set_view_mode(ViewMode::TerminalCopy);
+    }
+
+    pub fn copy_mode_move(&mut self, motion: Motion) {
+        let (Some(state), Some(term)) = (
+            self.copy_mode.as_mut(),
+            self.terminal_manager.active_terminal(),
+        ) else {
+            return;
+        };
+        state.cursor = term.copy_step(state.cursor, motion);
+    }
+
+    /// Starts a selection at the cursor, or drops the one there is.
+    pub fn copy_mode_mark(&mut self) {
+        if let Some(ref mut state) = self.copy_mode {
+            state.anchor = match state.anchor {
+                Some(_) => None,
+                None => Some(state.cursor),
+            };
+        }
+    }
+
+    /// Copies the selection, or the cursor's line without one, to the
+    /// clipboard (by OSC 52 over ssh or without a clipboard tool) and
+    /// leaves copy mode.
+    pub fn copy_mode_yank(&mut self) {
+        let (Some(state), Some(term)) = (self.copy_mode, self.terminal_manager.active_terminal())
+        else {
+            return;
+        };
+        let (a, b) = match state.anchor {
+            Some(anchor) => (anchor, state.cursor),
+            None => (
+                Point {
+                    line: state.cursor.line,
+                    col: 0,
+                },
+                Point {
+                    line: state.cursor.line,
+                    col: u16::MAX,
+                },
+            ),
+        };
+        let text = term.copy_text(a, b);
+        match crate::clipboard::copy_or_osc52(&text, &mut std::io::stdout()) {
+            Ok(()) => {
+                let lines = text.lines().count().max(1);
+                self.toasts.push(
+                    Severity::Success,
+                    match lines {
+                        1 => t("Copied 1 line").to_string(),
+                        n => tf("Copied {n} lines", &[("n", &n.to_string())]),
+                    },
+                );
+            }
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf("Copy failed: {error}", &[("error", &e.to_string())]),
+            ),
+        }
+        self.exit_copy_mode();
+    }
+
+    /// Leaves copy mode with the terminal back on its live screen.
+    pub fn exit_copy_mode(&mut self) {
+        self.copy_mode = None;
+        if let Some(term) = self.terminal_manager.active_terminal() {
+            term.scroll_down(usize::MAX);
+        }
+        self.set_view_mode(ViewMode::Terminal);
+    }
+
+    /// Shows `err` with its full cause chain in a modal. Dismissing it
+    /// returns to the detail view for remote sessions and to the list
+    /// otherwise.
+    pub fn show_error(&mut self, title: &str, err: &anyhow::Error) {
+        let message = format!("{:#}", err);
+        tlog!(error, "{}: {}", title, message);
+        let return_to = match self.view_mode {
+            ViewMode::TervezoDetail | ViewMode::TervezoQSwitcher => ViewMode::TervezoDetail,
+            _ => ViewMode::List,
+        };
+        self.error_modal = Some(ErrorModalState {
+            title: title.to_string(),
+            message,
+            return_to,
+        });
+        self.set_view_mode(ViewMode::ErrorModal);
+    }
+
+    pub fn dismiss_error(&mut self) {
+        let return_to = self
+            .error_modal
+            .take()
+            .map(|m| m.return_to)
+            .unwrap_or(ViewMode::List);
+        self.set_view_mode(return_to);
+    }
+
+    pub fn toast(&mut self, severity: Severity, message: impl Into<String>) {
+        self.toasts.push(severity, message);
+    }
+
+    pub fn toasts(&self) -> &Toasts {
+        &self.toasts
+    }
+
+    /// Drops expired toasts; returns true if the screen needs a redraw.
+    pub fn expire_toasts(&mut self) -> bool {
+        self.toasts.expire(std::time::Instant::now())
+    }
+
+    /// Switches to the next color palette for the rest of this run.
+    pub fn plugins(&self) -> &Plugins {
+        &self.plugins
+    }
+
+    #[cfg(test)]
+    pub fn set_plugins(&mut self, plugins: Plugins) {
+        self.plugins = plugins;
+    }
+
+    /// Runs the plugin action bound to `key` on the selected session.
+    /// Returns false when no plugin takes the key.
+    pub fn run_plugin_action(&mut self, key: char) -> bool {
+        let Some(entry) = self.selected_session() else {
+            return false;
+        };
+        match self.plugins.run(key, entry) {
+            None => return false,
+            Some(Ok(Some(msg))) => self.toasts.push(Severity::Info, msg),
+            Some(Ok(None)) => {}
+            Some(Err(e)) => self.toasts.push(Severity::Error, format!("{:#}", e)),
+        }
+        true
+    }
+
+    pub fn cycle_palette(&mut self) {
+        let palette = Theme::palette().next();
+        Theme::set_palette(palette);
+        self.toasts.push(
+            Severity::Info,
+            tf("Palette: {name}", &[("name", palette.label())]),
+        );
+    }
+
+    /// Turns do-not-disturb on for `DND_MINUTES`, or off if it is on.
+    pub fn toggle_dnd(&mut self) {
+        if self.terminal_manager.dnd_remaining().is_some() {
+            self.terminal_manager.set_dnd(None);
+            self.toasts.push(Severity::Info, t("Do not disturb off"));
+        } else {
+            self.set_dnd_minutes(DND_MINUTES);
+        }
+    }
+
+    pub fn set_dnd_minutes(&mut self, minutes: u64) {
+        let secs = minutes.saturating_mul(60);
+        self.terminal_manager
+            .set_dnd(Some(std::time::Duration::from_secs(secs)));
+        self.toasts.push(
+            Severity::Info,
+            tf("Do not disturb for {n} min", &[("n", &minutes.to_string())]),
+        );
+    }
+
+    /// Starts the focus timer, or stops it and the do-not-disturb it set.
+    pub fn toggle_focus_timer(&mut self) {
+        if self.pomodoro.take().is_some() {
+            if self.focus_config.dnd {
+                self.terminal_manager.set_dnd(None);
+            }
+            self.toasts.push(Severity::Info, t("Focus timer stopped"));
+            return;
+        }
+        let timer = Pomodoro::start(self.focus_config.clone(), std::time::Instant::now());
+        self.start_focus_interval(&timer);
+        self.toasts.push(
+            Severity::Info,
+            tf(
+                "Focus for {n} min",
+                &[("n", &self.focus_config.work_minutes.to_string())],
+            ),
+        );
+        self.pomodoro = Some(timer);
+    }
+
+    /// Covers a work interval with do-not-disturb; it lapses on its own
+    /// when the interval ends.
+    fn start_focus_interval(&mut self, timer: &Pomodoro) {
+        if self.focus_config.dnd && timer.phase() == Phase::Work {
+            self.terminal_manager.set_dnd(Some(timer.length()));
+        }
+    }
+
+    /// Starts serving the read-only web mirror, with its URL copied to the
+    /// clipboard, or stops it.
+    pub fn toggle_mirror(&mut self) {
+        if self.mirror.take().is_some() {
+            self.toasts.push(Severity::Info, t("Web mirror stopped"));
+            return;
+        }
+        self.start_mirror(true);
+    }
+
+    fn start_mirror(&mut self, copy_url: bool) {
+        match Mirror::start(&self.mirror_bind) {
+            Ok(mirror) => {
+                let message = if copy_url && crate::clipboard::copy(mirror.url()).is_ok() {
+                    "Mirroring the attached terminal at {url} (copied)"
+                } else {
+                    "Mirroring the attached terminal at {url}"
+                };
+                self.toasts
+                    .push(Severity::Info, tf(message, &[("url", mirror.url())]));
+                self.mirror = Some(mirror);
+            }
+            Err(e) => self.toasts.push(
+                Severity::Warning,
+                tf("Web mirror: {error}", &[("error", &format!("{:#}", e))]),
+            ),
+        }
+    }
+
+    /// Hands the active terminal's screen to the web mirror, which only
+    /// sends it on when it changed.
+    pub fn publish_mirror(&self) {
+        let Some(ref mirror) = self.mirror else {
+            return;
+        };
+        match self.terminal_manager.active_terminal() {
+            Some(term) => mirror.publish(term.project_name(), Some(term.lock_parser().screen())),
+            None => mirror.publish("", None),
+        }
+    }
+
+    /// Browsers watching the web mirror, while it runs.
+    pub fn mirror_viewers(&self) -> Option<usize> {
+        self.mirror.as_ref().map(Mirror::viewers)
+    }
+
+    /// Moves the focus timer on, with a toast and a single bell when an
+    /// interval ends. True when its label changed and needs drawing.
+    pub fn tick_focus_timer(&mut self) -> bool {
+        let now = std::time::Instant::now();
+        let Some(mut timer) = self.pomodoro.take() else {
+            return !std::mem::take(&mut self.focus_label).is_empty();
+        };
+        if let Some(ended) = timer.advance(now) {
+            let message = match ended {
+                Phase::Work => tf(
+                    "Focus interval done: {n} min break",
+                    &[("n", &(timer.length().as_secs() / 60).to_string())],
+                ),
+                Phase::Break => t("Break over: back to focus").to_string(),
+            };
+            self.terminal_manager.chime(&message);
+            self.toasts.push(Severity::Success, message);
+            self.start_focus_interval(&timer);
+        }
+        let label = timer.label(now);
+        self.pomodoro = Some(timer);
+        if label == self.focus_label {
+            return false;
+        }
+        self.focus_label = label;
+        true
+    }
+
+    /// The focus timer's status, e.g. "focus 18m", while it runs.
+    pub fn focus_label(&self) -> Option<(&str, bool)> {
+        let timer = self.pomodoro.as_ref()?;
+        Some((self.focus_label.as_str(), timer.phase() == Phase::Work))
+    }
+
+    pub fn dnd_remaining(&self) -> Option<std::time::Duration> {
+        self.terminal_manager.dnd_remaining()
+    }
+
+    pub fn notify_level(&self, session_id: &str) -> NotifyLevel {
+        self.terminal_manager.notify_level(session_id)
+    }
+
+    /// Steps the selected session through all / needs-input / mute and
+    /// persists the choice.
+    pub fn cycle_notify_level(&mut self) {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            return;
+        };
+        let id = session.id.clone();
+        let name = session.project_name.clone();
+        let level = self.terminal_manager.notify_level(&id).next();
+        self.terminal_manager.set_notify_level(&id, level);
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.set_notify_level(&id, level) {
+                self.toasts.push(
+                    Severity::Warning,
+                    tf(
+                        "Failed to save notification level: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+            }
+        }
+        self.toasts.push(
+            Severity::Info,
+            tf(
+                "Notifications for {name}: {level}",
+                &[("name", &name), ("level", level.as_str())],
+            ),
+        );
+    }
+
+    /// Starts or stops tee-ing the selected session's terminal output to
+    /// its log file. Only sessions open in a terminal have output to log.
+    pub fn toggle_terminal_log(&mut self) {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            return;
+        };
+        let id = self.attach_target(session).id.clone();
+        match self.terminal_manager.toggle_log(&id) {
+            Ok(Some(path)) => self.toasts.push(
+                Severity::Info,
+                tf(
+                    "Logging terminal output to {path}",
+                    &[("path", &path.display().to_string())],
+                ),
+            ),
+            Ok(None) => self.toasts.push(
+                Severity::Info,
+                tf(
+                    "Stopped terminal log for {id}",
+                    &[("id", &id[..8.min(id.len())])],
+                ),
+            ),
+            Err(e) => self.toasts.push(
+                Severity::Warning,
+                tf("Terminal log: {error}", &[("error", &e.to_string())]),
+            ),
+        }
+    }
+
+    /// Opens the resume-or-fresh chooser when the selected row is a dead
+    /// local session that isn't already open in a terminal. Returns false
+    /// otherwise so the caller can attach directly.
+    pub fn open_dead_session_menu(&mut self) -> bool {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            return false;
+        };
+        let target = self.attach_target(session);
+        if target.imported {
+            self.toast_imported();
+            return true;
+        }
+        if target.status != SessionStatus::Dead || self.terminal_manager.is_attached(&target.id) {
+            return false;
+        }
+        let (id, cwd) = (target.id.clone(), target.cwd.clone());
+        self.new_session_menu = Some(NewSessionMenuState::for_dead_session(target.clone()));
+        self.set_view_mode(ViewMode::NewSessionMenu);
+        let tx = self.branch_tx.clone();
+        crate::wakeup::spawn(move || {
+            let _ = tx.send((id, current_git_branch(&cwd)));
+        });
+        true
+    }
+
+    /// Puts the cwd's branch on the dead-session menu once git answers.
+    pub fn drain_cwd_branch(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((id, branch)) = self.branch_rx.try_recv() {
+            if let Some(menu) = self
+                .new_session_menu
+                .as_mut()
+                .filter(|m| m.session.as_ref().is_some_and(|s| s.id == id))
+            {
+                menu.set_cwd_branch(branch.as_deref());
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn user_actions(&self) -> &[UserAction] {
+        &self.user_actions
+    }
+
+    #[cfg(test)]
+    pub fn set_user_actions(&mut self, actions: Vec<UserAction>) {
+        self.user_actions = actions;
+    }
+
+    /// Opens the `[[actions]]` menu for the selected local session.
+    pub fn open_action_menu(&mut self) {
+        if self.user_actions.is_empty() {
+            self.toasts.push(
+                Severity::Info,
+                t("No actions configured: add [[actions]] to ~/.c9s/config.toml"),
+            );
+            return;
+        }
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            self.toasts
+                .push(Severity::Info, t("Actions run in local sessions only"));
+            return;
+        };
+        self.action_menu = Some(ActionMenuState {
+            session: session.clone(),
+            cursor: 0,
+        });
+        self.set_view_mode(ViewMode::ActionMenu);
+    }
+
+    /// Tab label, command with the session's values filled in, and the
+    /// directory to run the chosen action in.
+    pub fn user_action_command(
+        &self,
+        index: usize,
+    ) -> Option<(String, String, std::path::PathBuf)> {
+        let state = self.action_menu.as_ref()?;
+        let action = self.user_actions.get(index)?;
+        let label = format!("{}: {}", action.name, state.session.project_name);
+        Some((
+            label,
+            action.expand(&state.session),
+            state.session.cwd.clone(),
+        ))
+    }
+
+    /// The ticket `entry` works on: the one set by hand, else one in its
+    /// branch name.
+    pub fn ticket(&self, entry: &SessionEntry) -> Option<String> {
+        self.tickets
+            .get(entry.id())
+            .cloned()
+            .or_else(|| entry.branch().and_then(crate::ticket::from_branch))
+    }
+
+    /// The list only gets a Ticket column when a listed session has one.
+    pub fn show_ticket_column(&self) -> bool {
+        self.filtered_sessions()
+            .iter()
+            .any(|e| self.ticket(e).is_some())
+    }
+
+    /// Opens the selected session's ticket using the `[tickets]` templates.
+    pub fn open_ticket(&mut self) {
+        let Some(entry) = self.selected_session() else {
+            return;
+        };
+        let name = entry.display_name().to_string();
+        let Some(ticket) = self.ticket(entry) else {
+            self.toasts.push(
+                Severity::Info,
+                tf("No ticket for {name} (I to set one)", &[("name", &name)]),
+            );
+            return;
+        };
+        let Some(url) = crate::ticket::url(&self.tickets_config, &ticket, &name) else {
+            self.toasts.push(
+                Severity::Warning,
+                tf(
+                    "No URL template for {ticket} in [tickets]",
+                    &[("ticket", &ticket)],
+                ),
+            );
+            return;
+        };
+        match open::that(&url) {
+            Ok(()) => self
+                .toasts
+                .push(Severity::Info, tf("Opened {url}", &[("url", &url)])),
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf(
+                    "Failed to open {url}: {error}",
+                    &[("url", &url), ("error", &e.to_string())],
+                ),
+            ),
+        }
+    }
+
+    /// Asks for the selected session's ticket, starting from the current one.
+    pub fn edit_ticket(&mut self) {
+        let Some(entry) = self.selected_session() else {
+            return;
+        };
+        self.ticket_input = Some(TicketInputState {
+            session_id: entry.id().to_string(),
+            project: entry.display_name().to_string(),
+            input: self.ticket(entry).unwrap_or_default(),
+        });
+        self.set_view_mode(ViewMode::TicketInput);
+    }
+
+    /// Saves the typed ticket; a bare number is taken as an issue.
+    pub fn submit_ticket(&mut self) {
+        let Some(state) = self.ticket_input.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::List);
+        let input = state.input.trim();
+        let ticket = if !input.is_empty() && input.chars().all(|c| c.is_ascii_digit()) {
+            Some(format!("#{}", input))
+        } else {
+            (!input.is_empty()).then(|| input.to_string())
+        };
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.set_ticket(&state.session_id, ticket.as_deref()) {
+                self.toasts.push(
+                    Severity::Warning,
+                    tf(
+                        "Failed to save ticket: {error}",
+                        &[("error", &e.to_string())],
+                    ),
+                );
+            }
+        }
+        let message = match &ticket {
+            Some(ticket) => tf(
+                "{project} linked to {ticket}",
+                &[("project", &state.project), ("ticket", ticket)],
+            ),
+            None => tf(
+                "Ticket link for {project} cleared",
+                &[("project", &state.project)],
+            ),
+        };
+        match ticket {
+            Some(t) => self.tickets.insert(state.session_id, t),
+            None => self.tickets.remove(&state.session_id),
+        };
+        self.toasts.push(Severity::Info, message);
+    }
+
+    /// Asks how the selected local session went.
+    pub fn rate_selected(&mut self) {
+        let Some(id) = self
+            .selected_session()
+            .and_then(|e| e.as_local())
+            .map(|s| s.id.clone())
+        else {
+            self.toasts
+                .push(Severity::Info, t("Only local sessions can be rated"));
+            return;
+        };
+        self.open_outcome_prompt(&id);
+    }
+
+    /// Asks for an outcome after `session_id` was killed or closed, unless
+    /// `[outcomes] prompt` is off or it is not a known session.
+    pub fn prompt_outcome(&mut self, session_id: &str) {
+        if self.prompt_outcomes {
+            self.open_outcome_prompt(session_id);
+        }
+    }
+
+    fn open_outcome_prompt(&mut self, session_id: &str) {
+        let Some(session) = self.local_sessions.iter().find(|s| s.id == session_id) else {
+            return;
+        };
+        let (outcome, rating) = self
+            .store
+            .as_ref()
+            .and_then(|s| s.outcome(session_id).ok().flatten())
+            .map_or((None, None), |(o, r)| (Some(o), r));
+        self.outcome_prompt = Some(OutcomePromptState {
+            session_id: session.id.clone(),
+            project: session.project_name.clone(),
+            model: session
+                .model
+                .clone()
+                .unwrap_or_else(|| "unknown".to_string()),
+            outcome,
+            rating,
+        });
+        self.set_view_mode(ViewMode::OutcomePrompt);
+    }
+
+    /// Saves the picked outcome; without one there is nothing to save.
+    pub fn submit_outcome(&mut self) {
+        let Some(state) = self.outcome_prompt.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::List);
+        let Some(outcome) = state.outcome else {
+            return;
+        };
+        let result = match self.store {
+            Some(ref store) => store.set_outcome(
+                &state.session_id,
+                &state.project,
+                &state.model,
+                outcome,
+                state.rating,
+            ),
+            None => Err(anyhow::anyhow!("no database")),
+        };
+        match result {
+            Ok(()) => self.toasts.push(
+                Severity::Success,
+                format!("{}: {}", state.project, outcome.as_str()),
+            ),
+            Err(e) => self.toasts.push(
+                Severity::Warning,
+                tf(
+                    "Failed to save outcome: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            ),
+        }
+    }
+
+    /// Shows success rates per project and model, and models compared.
+    pub fn open_outcomes(&mut self) {
+        self.outcome_stats = self
+            .store
+            .as_ref()
+            .and_then(|s| s.outcome_stats().ok())
+            .unwrap_or_default();
+        self.model_comparison = self
+            .store
+            .as_ref()
+            .and_then(|s| s.rated_sessions().ok())
+            .map(|rated| compare_models(&rated))
+            .unwrap_or_default();
+        self.set_view_mode(ViewMode::Outcomes);
+    }
+
+    /// Shows what sessions cost per day and per project over the last
+    /// `STATS_DAYS` days.
+    pub fn open_stats(&mut self) {
+        let Some(store) = self.store.as_ref() else {
+            self.toasts
+                .push(Severity::Warning, t("Stats need the session store"));
+            return;
+        };
+        let since = chrono::Local::now().date_naive() - chrono::Duration::days(STATS_DAYS - 1);
+        let totals = store
+            .cost_by_day(since)
+            .and_then(|days| Ok((days, store.cost_by_project(since)?)));
+        match totals {
+            Ok((days, projects)) => {
+                self.cost_stats = Some(CostStats {
+                    since,
+                    days,
+                    projects,
+                    tokens: false,
+                });
+                self.set_view_mode(ViewMode::Stats);
+            }
+            Err(e) => self.toasts.push(
+                Severity::Warning,
+                tf(
+                    "Failed to read stats: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            ),
+        }
+    }
+
+    pub fn outcome_stats(&self) -> &[OutcomeStats] {
+        &self.outcome_stats
+    }
+
+    pub fn model_comparison(&self) -> &[ModelComparison] {
+        &self.model_comparison
+    }
+
+    /// Opens the git menu for the selected local session.
+    pub fn open_git_menu(&mut self) {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            self.toasts
+                .push(Severity::Info, t("Git actions need a local session"));
+            return;
+        };
+        self.git_menu = Some(GitMenuState {
+            session: session.clone(),
+            items: GitOp::menu(),
+            cursor: 0,
+            branch: String::new(),
+        });
+        self.set_view_mode(ViewMode::GitMenu);
+    }
+
+    /// Runs the menu's `index`-th item, or asks for a name first when it
+    /// creates a branch.
+    pub fn select_git_item(&mut self, index: usize) {
+        let Some(op) = self
+            .git_menu
+            .as_ref()
+            .and_then(|s| s.items.get(index).cloned())
+        else {
+            return;
+        };
+        if let GitOp::CreateBranch(_) = op {
+            self.set_view_mode(ViewMode::GitBranchName);
+        } else {
+            self.run_git(op);
+        }
+    }
+
+    pub fn submit_git_branch(&mut self) {
+        let Some(name) = self.git_menu.as_ref().map(|s| s.branch.trim().to_string()) else {
+            return;
+        };
+        if name.is_empty() {
+            self.set_view_mode(ViewMode::GitMenu);
+            return;
+        }
+        self.run_git(GitOp::CreateBranch(name));
+    }
+
+    /// Runs `op` in the menu session's cwd in the background and closes
+    /// the menu.
+    fn run_git(&mut self, op: GitOp) {
+        let Some(state) = self.git_menu.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::List);
+        self.spawn_git(state.session, op);
+    }
+
+    /// Asks for a commit message for the working diff shown in the detail.
+    pub fn open_commit_prompt(&mut self) {
+        if self.view_mode != ViewMode::Detail || self.detail_tab != DetailTab::Diff {
+            return;
+        }
+        let has_changes = self
+            .detail_diff
+            .as_ref()
+            .and_then(|d| d.result.as_ref())
+            .is_some_and(|r| r.as_ref().is_ok_and(|diff| !diff.is_empty()));
+        let session = self.selected_session().and_then(|e| e.as_local()).cloned();
+        let Some(session) = session.filter(|_| has_changes) else {
+            self.toasts.push(Severity::Info, t("Nothing to commit"));
+            return;
+        };
+        self.commit_prompt = Some(CommitPromptState {
+            session,
+            message: String::new(),
+            pull_request: false,
+        });
+        self.set_view_mode(ViewMode::GitCommit);
+    }
+
+    /// Commits with the typed message and goes back to the diff. An empty
+    /// message keeps the prompt open.
+    pub fn submit_commit(&mut self) {
+        let message = self
+            .commit_prompt
+            .as_ref()
+            .map(|s| s.message.trim().to_string())
+            .unwrap_or_default();
+        if message.is_empty() {
+            return;
+        }
+        let Some(state) = self.commit_prompt.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::Detail);
+        self.spawn_git(
+            state.session,
+            GitOp::Commit {
+                message,
+                pull_request: state.pull_request,
+            },
+        );
+    }
+
+    /// Runs `op` in `session`'s cwd in the background. The outcome arrives
+    /// via `drain_git_results`.
+    fn spawn_git(&mut self, session: Session, op: GitOp) {
+        let cwd = session.cwd;
+        let project = session.project_name;
+        if !self.git_running.insert(cwd.clone()) {
+            self.toasts.push(
+                Severity::Warning,
+                tf(
+                    "git is still running in {project}",
+                    &[("project", &project)],
+                ),
+            );
+            return;
+        }
+        self.toasts.push(
+            Severity::Info,
+            tf(
+                "{op} in {project}...",
+                &[("op", &op.label()), ("project", &project)],
+            ),
+        );
+        let tx = self.git_tx.clone();
+        crate::wakeup::spawn(move || {
+            let outcome = git::run(&op, &cwd);
+            let _ = tx.send((cwd, project, op, outcome));
+        });
+    }
+
+    /// Reports finished git commands: a toast with git's last line, and the
+    /// full output in the log panel. A commit reloads the working diff when
+    /// it is on screen.
+    pub fn drain_git_results(&mut self) -> bool {
+        let mut any = false;
+        while let Ok((cwd, project, op, outcome)) = self.git_rx.try_recv() {
+            any = true;
+            self.git_running.remove(&cwd);
+            if matches!(op, GitOp::Commit { .. })
+                && self.view_mode == ViewMode::Detail
+                && self.detail_tab == DetailTab::Diff
+                && self.detail_diff.as_ref().is_some_and(|d| d.cwd == cwd)
+            {
+                self.load_working_diff();
+            }
+            for line in outcome.output.lines().filter(|l| !l.trim().is_empty()) {
+                tlog!(info, "[{}] {}: {}", project, op.label(), line);
+            }
+            let (severity, message) = if outcome.success {
+                (Severity::Success, "{op} ok in {project}: {summary}")
+            } else {
+                (Severity::Error, "{op} failed in {project}: {summary}")
+            };
+            self.toasts.push(
+                severity,
+                tf(
+                    message,
+                    &[
+                        ("op", &op.label()),
+                        ("project", &project),
+                        ("summary", &outcome.summary),
+                    ],
+                ),
+            );
+        }
+        any
+    }
+
+    /// Nesting level of a session under its parent (0 for top-level rows).
+    pub fn tree_depth(&self, id: &str) -> usize {
+        self.tree_depth.get(id).copied().unwrap_or(0)
+    }
+
+    fn apply_filter(&mut self) {
+        let query = self.filter_query.to_lowercase();
+        let now = chrono::Utc::now();
+        let (mut hidden_count, mut stale_count) = (0, 0);
+        self.filtered = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                if self.is_hidden(e) {
+                    hidden_count += 1;
+                } else if self.is_stale(e, now) {
+                    stale_count += 1;
+                } else {
+                    return true;
+                }
+                self.show_hidden
+            })
+            .filter(|(_, e)| e.matches_filter(&query))
+            .filter(|(_, e)| !self.errors_only || e.has_recent_errors())
+            .map(|(i, _)| i)
+            .collect();
+        self.hidden_count = hidden_count;
+        self.stale_count = stale_count;
+    }
+
+    /// Whether `entry` is a local session dead for longer than
+    /// `hide_after_days`.
+    fn is_stale(&self, entry: &SessionEntry, now: chrono::DateTime<chrono::Utc>) -> bool {
+        entry
+            .as_local()
+            .is_some_and(|s| self.sessions_config.is_stale(s, now))
+    }
+
+    /// Whether `entry` is a local session hidden with `h` or matched by an
+    /// `exclude` pattern.
+    fn is_hidden(&self, entry: &SessionEntry) -> bool {
+        entry
+            .as_local()
+            .is_some_and(|s| self.hidden.contains(&s.id) || self.sessions_config.excludes(s))
+    }
+
+    /// Hides the selected local session from the list, or lists it again
+    /// while hidden sessions are shown. Kept in the store across restarts.
+    pub fn toggle_hide_selected(&mut self) {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            self.toasts
+                .push(Severity::Info, t("Only local sessions can be hidden"));
+            return;
+        };
+        let (id, project) = (session.id.clone(), session.project_name.clone());
+        let hide = !self.hidden.contains(&id);
+        if hide && self.sessions_config.excludes(session) {
+            self.toasts.push(
+                Severity::Info,
+                tf(
+                    "{project} is excluded in config.toml ([sessions] exclude)",
+                    &[("project", &project)],
+                ),
+            );
+            return;
+        }
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.set_hidden(&id, hide) {
+                self.toasts.push(
+                    Severity::Warning,
+                    tf("Failed to save: {error}", &[("error", &e.to_string())]),
+                );
+                return;
+            }
+        }
+        if hide {
+            self.hidden.insert(id);
+            self.toasts.push(
+                Severity::Info,
+                tf(
+                    "Hid {project}; . lists hidden sessions",
+                    &[("project", &project)],
+                ),
+            );
+        } else {
+            self.hidden.remove(&id);
+            self.toasts.push(
+                Severity::Info,
+                tf("{project} is listed again", &[("project", &project)]),
+            );
+        }
+        self.apply_filter();
+        self.selected = self.selected.min(self.filtered.len().saturating_sub(1));
+    }
+
+    /// Lists hidden, excluded and stale sessions too, or leaves them out
+    /// again.
+    pub fn toggle_show_hidden(&mut self) {
+        let selected = self.selected_session().map(|e| e.id().to_string());
+        self.show_hidden = !self.show_hidden;
+        self.apply_filter();
+        self.selected = selected
+            .and_then(|id| {
+                self.filtered
+                    .iter()
+                    .position(|&i| self.entries.get(i).is_some_and(|e| e.id() == id))
+            })
+            .unwrap_or(0);
+    }
+
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    pub fn hidden_count(&self) -> usize {
+        self.hidden_count
+    }
+
+    pub fn stale_count(&self) -> usize {
+        self.stale_count
+    }
+
+    pub fn hide_after_days(&self) -> u64 {
+        self.sessions_config.hide_after_days
+    }
+
+    /// Formatted session-list cells, reused across frames.
+    pub fn row_cache(&self) -> std::cell::RefMut<'_, crate::ui::session_list::RowCache> {
+        self.row_cache.borrow_mut()
+    }
+
+    pub fn filtered_sessions(&self) -> Vec<&SessionEntry> {
+        self.filtered
+            .iter()
+            .filter_map(|&i| self.entries.get(i))
+            .collect()
+    }
+
+    pub fn selected_session(&self) -> Option<&SessionEntry> {
+        self.filtered
+            .get(self.selected)
+            .and_then(|&i| self.entries.get(i))
+    }
+
+    pub fn selected_index(&self) -> usize {
+        self.selected
+    }
+
+    pub fn set_selected(&mut self, idx: usize) {
+        self.selected = idx;
+    }
+
+    pub fn view_mode(&self) -> &ViewMode {
+        &self.view_mode
+    }
+
+    pub fn set_view_mode(&mut self, mode: ViewMode) {
+        if self.view_mode == ViewMode::List && mode != ViewMode::List {
+            self.remember_list_state();
+        }
+        if matches!(
+            mode,
+            ViewMode::QSwitcher | ViewMode::TerminalQSwitcher | ViewMode::TervezoQSwitcher
+        ) {
+            let max = self.filtered.len().min(9);
+            if self.selected >= max && max > 0 {
+                self.selected = max - 1;
+            }
+        }
+        if mode == ViewMode::Detail {
+            // Back from the commit prompt, the detail stays on its diff.
+            let returning = self.view_mode == ViewMode::GitCommit;
+            if let Some(entry) = self.selected_session().filter(|_| !returning) {
+                if let Some(session) = entry.as_local() {
+                    let cfg = scan_session_config(&session.cwd);
+                    let env = session.pid.and_then(read_session_env);
+                    let mut items = Vec::new();
+                    if session.pid.is_some() {
+                        items = build_env_items(env.as_deref(), false);
+                    }
+                    items.extend(build_plan_items(&session.plan_slugs, &session.cwd));
+                    items.extend(build_config_items(&cfg, &session.cwd));
+                    self.detail_items = items;
+                    self.detail_env = env;
+                    self.detail_env_expanded = false;
+                    self.detail_config = Some(cfg);
+                    self.detail_cursor = 0;
                     self.detail_preview = None;
                     self.detail_preview_scroll = 0;
+                    self.detail_tab = DetailTab::Overview;
+                    self.detail_files = None;
+                    self.detail_diff = None;
+                    self.detail_transcript = None;
                 }
             }
         } else if !matches!(
@@ -1148,16 +3868,23 @@ impl App {
                 | ViewMode::TervezoConfirm
                 | ViewMode::TervezoPromptInput
                 | ViewMode::TervezoCreateDialog
+                | ViewMode::TervezoCompare
+                | ViewMode::GitCommit
         ) {
             self.detail_config = None;
             self.detail_items.clear();
             self.detail_preview = None;
+            self.detail_files = None;
+            self.detail_diff = None;
+            self.detail_transcript = None;
         }
         if mode == ViewMode::TervezoDetail {
             if let Some(entry) = self.selected_session() {
                 if let Some(imp) = entry.as_remote() {
-                    let state = TervezoDetailState::new(imp.clone());
+                    let state = self.detail_state_for(imp.clone());
                     self.tervezo_detail = Some(state);
+                    self.renew_detail_cancel();
+                    self.refresh_restart_chain();
                     let (tx, rx) = mpsc::channel();
                     self.tervezo_detail_tx = Some(tx);
                     self.tervezo_detail_rx = Some(rx);
@@ -1169,14 +3896,46 @@ impl App {
                 | ViewMode::TervezoConfirm
                 | ViewMode::TervezoPromptInput
                 | ViewMode::TervezoQSwitcher
+                | ViewMode::TervezoCompare
         ) {
             self.tervezo_detail = None;
             self.tervezo_detail_tx = None;
             self.tervezo_detail_rx = None;
+            self.tervezo_detail_cancel.cancel();
             self.stop_sse_stream();
         }
+        if mode != ViewMode::QuickPrompt {
+            self.quick_prompt = None;
+        }
+        if mode != ViewMode::ActionMenu {
+            self.action_menu = None;
+        }
+        if !matches!(mode, ViewMode::GitMenu | ViewMode::GitBranchName) {
+            self.git_menu = None;
+        }
+        if mode != ViewMode::GitCommit {
+            self.commit_prompt = None;
+        }
+        if mode != ViewMode::TicketInput {
+            self.ticket_input = None;
+        }
+        if mode != ViewMode::TimelineExport {
+            self.export_prompt = None;
+        }
+        if mode != ViewMode::OutcomePrompt {
+            self.outcome_prompt = None;
+        }
+        if mode != ViewMode::Stats {
+            self.cost_stats = None;
+        }
+        if mode != ViewMode::OrgPicker {
+            self.org_picker = None;
+            self.org_rx = None;
+        }
         if mode == ViewMode::NewSessionMenu {
-            self.new_session_menu = Some(NewSessionMenuState::new(self.has_tervezo()));
+            if self.new_session_menu.is_none() {
+                self.new_session_menu = Some(NewSessionMenuState::new(self.has_tervezo()));
+            }
         } else if self.new_session_menu.is_some() {
             self.new_session_menu = None;
         }
@@ -1207,6 +3966,11 @@ impl App {
         self.view_mode = mode;
     }
 
+    /// Minimum time between redraws caused by terminal output.
+    pub fn frame_budget(&self) -> std::time::Duration {
+        self.frame_budget
+    }
+
     pub fn tervezo_config(&self) -> Option<&TervezoConfig> {
         self.tervezo_config.as_ref()
     }
@@ -1223,6 +3987,118 @@ impl App {
         self.entries.iter().filter(|e| e.is_remote()).count()
     }
 
+    /// Remembers that restarting `old_id` created `new_id`, so the new
+    /// one can be compared with it.
+    pub fn record_restart(&mut self, new_id: &str, old_id: &str) {
+        self.restarted_from
+            .insert(new_id.to_string(), old_id.to_string());
+    }
+
+    pub fn remote_implementation(&self, id: &str) -> Option<&Implementation> {
+        self.entries
+            .iter()
+            .filter_map(|e| e.as_remote())
+            .find(|i| i.id == id)
+    }
+
+    fn previous_attempt_of(&self, id: &str) -> Option<String> {
+        self.restarted_from.get(id).cloned().or_else(|| {
+            self.remote_implementation(id)
+                .and_then(|i| i.restarted_from.clone())
+        })
+    }
+
+    /// The newest implementation restarted from `id`.
+    fn next_attempt_of(&self, id: &str) -> Option<String> {
+        let mut children: Vec<&Implementation> = self
+            .entries
+            .iter()
+            .filter_map(|e| e.as_remote())
+            .filter(|i| i.restarted_from.as_deref() == Some(id))
+            .collect();
+        children.sort_by_key(|i| i.created_at);
+        children.last().map(|i| i.id.clone()).or_else(|| {
+            self.restarted_from
+                .iter()
+                .find(|(_, old)| *old == id)
+                .map(|(new, _)| new.clone())
+        })
+    }
+
+    fn detail_state_for(&self, imp: Implementation) -> TervezoDetailState {
+        let mut state = TervezoDetailState::new(imp);
+        if state.previous_attempt.is_none() {
+            state.previous_attempt = self.previous_attempt_of(&state.implementation_id);
+        }
+        state
+    }
+
+    /// Opens `imp` in the detail view in place of the one shown, selecting
+    /// it in the list when it is there.
+    pub fn show_tervezo_implementation(&mut self, imp: Implementation) {
+        if let Some(pos) = self
+            .filtered
+            .iter()
+            .position(|&i| self.entries.get(i).is_some_and(|e| e.id() == imp.id))
+        {
+            self.selected = pos;
+        }
+        self.tervezo_detail = Some(self.detail_state_for(imp));
+        self.renew_detail_cancel();
+        self.refresh_restart_chain();
+    }
+
+    /// Works out the restart chain of the open implementation, oldest
+    /// attempt first, from the loaded implementations and the restarts
+    /// made here.
+    pub fn refresh_restart_chain(&mut self) {
+        let Some(state) = self.tervezo_detail.as_ref() else {
+            return;
+        };
+        let current = state.implementation_id.clone();
+        let link = |id: &str| ChainLink {
+            id: id.to_string(),
+            name: self
+                .remote_implementation(id)
+                .map(|i| i.display_name().to_string())
+                .unwrap_or_else(|| id.to_string()),
+        };
+
+        let mut seen = HashSet::from([current.clone()]);
+        let mut older = Vec::new();
+        let mut id = state.previous_attempt.clone();
+        while let Some(prev) = id.filter(|p| seen.insert(p.clone())) {
+            older.push(link(&prev));
+            id = self.previous_attempt_of(&prev);
+        }
+        let mut chain: Vec<ChainLink> = older.into_iter().rev().collect();
+        chain.push(ChainLink {
+            id: current.clone(),
+            name: state.implementation.display_name().to_string(),
+        });
+        let mut id = self.next_attempt_of(&current);
+        while let Some(next) = id.filter(|n| seen.insert(n.clone())) {
+            chain.push(link(&next));
+            id = self.next_attempt_of(&next);
+        }
+
+        if let Some(ref mut state) = self.tervezo_detail {
+            state.chain = chain;
+        }
+    }
+
+    /// The attempt before or after the open one in its restart chain.
+    pub fn related_attempt(&self, forward: bool) -> Option<String> {
+        let state = self.tervezo_detail.as_ref()?;
+        let pos = state.chain_position()?;
+        let target = if forward {
+            pos + 1
+        } else {
+            pos.checked_sub(1)?
+        };
+        state.chain.get(target).map(|l| l.id.clone())
+    }
+
     #[allow(dead_code)]
     pub fn ssh_cache(&self) -> &HashMap<String, SshCredentials> {
         &self.ssh_cache
@@ -1259,22 +4135,352 @@ impl App {
         }
     }
 
+    /// Expands or collapses the environment section if the cursor is on its
+    /// header. Returns false when the cursor is elsewhere.
+    pub fn detail_toggle_env(&mut self) -> bool {
+        let on_header = self
+            .detail_items
+            .get(self.detail_cursor)
+            .is_some_and(|i| i.kind == ConfigItemKind::EnvSection);
+        if !on_header || self.detail_env.is_none() {
+            return on_header;
+        }
+        self.detail_env_expanded = !self.detail_env_expanded;
+        let env_len = self
+            .detail_items
+            .iter()
+            .take_while(|i| matches!(i.kind, ConfigItemKind::EnvSection | ConfigItemKind::EnvVar))
+            .count();
+        // +1 for the spacer that closes the section
+        let old_len = (env_len + 1).min(self.detail_items.len());
+        let new_items = build_env_items(self.detail_env.as_deref(), self.detail_env_expanded);
+        self.detail_items.splice(0..old_len, new_items);
+        true
+    }
+
     pub fn detail_close_preview(&mut self) {
         self.detail_preview = None;
         self.detail_preview_scroll = 0;
     }
 
+    pub fn detail_tab(&self) -> DetailTab {
+        self.detail_tab
+    }
+
+    pub fn detail_files(&self) -> Option<&DetailFiles> {
+        self.detail_files.as_ref()
+    }
+
+    /// Switches the detail view's tab, reading the session's changed
+    /// files the first time their tab is shown. The transcript is read in
+    /// the background, and the working diff each time.
+    pub fn detail_switch_tab(&mut self, forward: bool) {
+        self.detail_close_preview();
+        self.detail_tab = if forward {
+            self.detail_tab.next()
+        } else {
+            self.detail_tab.prev()
+        };
+        if self.detail_tab == DetailTab::Diff {
+            self.load_working_diff();
+        }
+        if self.detail_tab == DetailTab::Transcript && self.detail_transcript.is_none() {
+            let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+                return;
+            };
+            let session_id = session.id.clone();
+            let path = transcript_path(session);
+            if let Some(ref path) = path {
+                self.read_transcript(TranscriptView::Detail, &session_id, path);
+            }
+            self.detail_transcript = Some(DetailTranscript {
+                session_id,
+                loading: path.is_some(),
+                events: Vec::new(),
+                scroll: 0,
+                max_scroll: std::cell::Cell::new(0),
+            });
+        }
+        if self.detail_tab == DetailTab::Files && self.detail_files.is_none() {
+            let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+                return;
+            };
+            let files = transcript_path(session)
+                .map(|path| files_touched(&path))
+                .unwrap_or_default();
+            self.detail_files = Some(DetailFiles {
+                cwd: session.cwd.clone(),
+                files,
+                cursor: 0,
+                preview: None,
+                preview_scroll: 0,
+            });
+        }
+    }
+
+    pub fn detail_diff(&self) -> Option<&DetailDiff> {
+        self.detail_diff.as_ref()
+    }
+
+    pub fn detail_transcript(&self) -> Option<&DetailTranscript> {
+        self.detail_transcript.as_ref()
+    }
+
+    /// The transcript, when the detail shows its tab.
+    fn transcript_tab(&mut self) -> Option<&mut DetailTranscript> {
+        if self.view_mode != ViewMode::Detail || self.detail_tab != DetailTab::Transcript {
+            return None;
+        }
+        self.detail_transcript.as_mut()
+    }
+
+    /// Runs `git diff` in the detail session's cwd in the background, each
+    /// time the tab is shown so it is current. The result arrives via
+    /// `drain_working_diff`.
+    fn load_working_diff(&mut self) {
+        let Some(cwd) = self
+            .selected_session()
+            .and_then(|e| e.as_local())
+            .map(|s| s.cwd.clone())
+        else {
+            return;
+        };
+        let scroll = self
+            .detail_diff
+            .as_ref()
+            .filter(|d| d.cwd == cwd)
+            .map_or(0, |d| d.scroll);
+        self.detail_diff = Some(DetailDiff {
+            cwd: cwd.clone(),
+            result: None,
+            scroll,
+        });
+        let tx = self.diff_tx.clone();
+        crate::wakeup::spawn(move || {
+            let result = git::working_diff(&cwd);
+            let _ = tx.send((cwd, result));
+        });
+    }
+
+    pub fn drain_working_diff(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((cwd, result)) = self.diff_rx.try_recv() {
+            if let Some(ref mut diff) = self.detail_diff {
+                if diff.cwd == cwd {
+                    diff.result = Some(result);
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Whether Enter, Esc and the arrows go to the Files tab.
+    fn on_files_tab(&self) -> bool {
+        self.view_mode == ViewMode::Detail && self.detail_tab == DetailTab::Files
+    }
+
+    /// Enter on the Files tab: previews the selected file. False on the
+    /// other tabs.
+    pub fn detail_files_select(&mut self) -> bool {
+        if !self.on_files_tab() {
+            return false;
+        }
+        if let Some(ref mut files) = self.detail_files {
+            files.open_preview();
+        }
+        true
+    }
+
+    /// Esc on the Files tab closes the preview, if one is open.
+    pub fn detail_files_close_preview(&mut self) -> bool {
+        if !self.on_files_tab() {
+            return false;
+        }
+        self.detail_files
+            .as_mut()
+            .and_then(|files| files.preview.take())
+            .is_some()
+    }
+
+    /// Probes every MCP server listed in the detail config tree in the
+    /// background. Results arrive via `drain_mcp_health`.
+    pub fn check_mcp_health(&mut self) {
+        let Some(cwd) = self
+            .selected_session()
+            .and_then(|e| e.as_local())
+            .map(|s| s.cwd.clone())
+        else {
+            return;
+        };
+        let Some(ref cfg) = self.detail_config else {
+            return;
+        };
+
+        for server in cfg.mcp_servers.clone() {
+            if let Some(item) = self
+                .detail_items
+                .iter_mut()
+                .find(|i| i.kind == ConfigItemKind::McpServer && i.label == server.name)
+            {
+                if item.health == Some(McpHealth::Checking) {
+                    continue;
+                }
+                item.health = Some(McpHealth::Checking);
+            }
+            let tx = self.mcp_tx.clone();
+            let cwd = cwd.clone();
+            crate::wakeup::spawn(move || {
+                let health = check_server(&server, &cwd);
+                tlog!(info, "mcp {}: {}", server.name, health.label());
+                let _ = tx.send((cwd, server.name, health));
+            });
+        }
+    }
+
+    /// Applies a freshly fetched exchange rate. Returns true if costs need
+    /// redrawing.
+    pub fn drain_fx_rate(&mut self) -> bool {
+        let Some(ref rx) = self.fx_rx else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(rate) => {
+                crate::format::set_rate(Some(rate));
+                self.row_cache.borrow_mut().clear();
+                self.fx_rx = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.fx_rx = None;
+                false
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+        }
+    }
+
+    /// Takes the latest host sample. Returns true if the header needs
+    /// redrawing.
+    pub fn drain_host_stats(&mut self) -> bool {
+        let Some(ref rx) = self.host_rx else {
+            return false;
+        };
+        let latest = rx.try_iter().last();
+        if latest.is_none() || latest == self.host {
+            return false;
+        }
+        self.host = latest;
+        true
+    }
+
+    pub fn host_stats(&self) -> Option<&HostStats> {
+        self.host.as_ref()
+    }
+
+    #[cfg(test)]
+    pub(crate) fn set_host_stats(&mut self, stats: HostStats) {
+        self.host = Some(stats);
+    }
+
+    pub fn drain_claude_versions(&mut self) -> bool {
+        let Some(ref rx) = self.versions_rx else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(versions) => {
+                if versions.installed_outdated() {
+                    tlog!(
+                        info,
+                        "claude {} installed, {} available",
+                        versions.installed.as_deref().unwrap_or("?"),
+                        versions.latest.as_deref().unwrap_or("?")
+                    );
+                }
+                self.claude_versions = versions;
+                self.versions_rx = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.versions_rx = None;
+                false
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+        }
+    }
+
+    pub fn claude_versions(&self) -> &ClaudeVersions {
+        &self.claude_versions
+    }
+
+    /// Live local sessions whose transcript reports an older CLI than the
+    /// newest known release.
+    pub fn outdated_live_count(&self) -> usize {
+        self.local_sessions
+            .iter()
+            .filter(|s| s.pid.is_some())
+            .filter_map(|s| s.claude_version.as_deref())
+            .filter(|v| self.claude_versions.is_outdated(v))
+            .count()
+    }
+
+    pub fn drain_mcp_health(&mut self) -> bool {
+        let current_cwd = self
+            .selected_session()
+            .and_then(|e| e.as_local())
+            .map(|s| s.cwd.clone());
+        let mut changed = false;
+        while let Ok((cwd, name, health)) = self.mcp_rx.try_recv() {
+            if current_cwd.as_ref() != Some(&cwd) {
+                continue;
+            }
+            if let Some(item) = self
+                .detail_items
+                .iter_mut()
+                .find(|i| i.kind == ConfigItemKind::McpServer && i.label == name)
+            {
+                item.health = Some(health);
+                changed = true;
+            }
+        }
+        changed
+    }
+
     pub fn sort_label(&self) -> &str {
         self.sort_column.label()
     }
 
     pub fn cycle_sort(&mut self) {
+        self.remember_list_state();
         self.sort_column = self.sort_column.next();
         self.apply_sort();
         self.apply_filter();
     }
 
     pub fn move_up(&mut self) {
+        if self.view_mode == ViewMode::RecentlyExited {
+            self.recently_exited_move(-1);
+            return;
+        }
+        if self.view_mode == ViewMode::Detail && self.detail_tab == DetailTab::Diff {
+            if let Some(ref mut diff) = self.detail_diff {
+                diff.scroll = diff.scroll.saturating_sub(1);
+            }
+            return;
+        }
+        if let Some(transcript) = self.transcript_tab() {
+            transcript.scroll_by(-1);
+            return;
+        }
+        if self.on_files_tab() {
+            if let Some(ref mut files) = self.detail_files {
+                if files.preview.is_some() {
+                    files.preview_scroll = files.preview_scroll.saturating_sub(1);
+                } else {
+                    files.move_cursor(-1);
+                }
+            }
+            return;
+        }
         if self.view_mode == ViewMode::Detail {
             if self.detail_preview.is_some() {
                 self.detail_preview_scroll = self.detail_preview_scroll.saturating_sub(1);
@@ -1293,12 +4499,50 @@ impl App {
             self.log_scroll_up();
             return;
         }
+        if self.view_mode == ViewMode::Diagnostics {
+            self.diagnostics_scroll = self.diagnostics_scroll.saturating_sub(1);
+            return;
+        }
+        if let Some(ref mut timeline) = self.local_timeline {
+            if self.view_mode == ViewMode::SessionTimeline {
+                timeline.move_cursor(-1);
+                return;
+            }
+        }
         if self.selected > 0 {
             self.selected -= 1;
         }
     }
 
     pub fn move_down(&mut self) {
+        if self.view_mode == ViewMode::RecentlyExited {
+            self.recently_exited_move(1);
+            return;
+        }
+        if self.view_mode == ViewMode::Detail && self.detail_tab == DetailTab::Diff {
+            if let Some(ref mut diff) = self.detail_diff {
+                let lines = match diff.result {
+                    Some(Ok(ref d)) => d.diff.lines().count(),
+                    _ => 0,
+                };
+                diff.scroll = (diff.scroll + 1).min(lines.saturating_sub(1));
+            }
+            return;
+        }
+        if let Some(transcript) = self.transcript_tab() {
+            transcript.scroll_by(1);
+            return;
+        }
+        if self.on_files_tab() {
+            if let Some(ref mut files) = self.detail_files {
+                if files.preview.is_some() {
+                    files.preview_scroll += 1;
+                } else {
+                    files.move_cursor(1);
+                }
+            }
+            return;
+        }
         if self.view_mode == ViewMode::Detail {
             if self.detail_preview.is_some() {
                 self.detail_preview_scroll += 1;
@@ -1317,6 +4561,17 @@ impl App {
             self.log_scroll_down();
             return;
         }
+        if self.view_mode == ViewMode::Diagnostics {
+            let max = self.diagnostics.len().saturating_sub(1);
+            self.diagnostics_scroll = (self.diagnostics_scroll + 1).min(max);
+            return;
+        }
+        if let Some(ref mut timeline) = self.local_timeline {
+            if self.view_mode == ViewMode::SessionTimeline {
+                timeline.move_cursor(1);
+                return;
+            }
+        }
         let limit = if matches!(
             self.view_mode,
             ViewMode::QSwitcher | ViewMode::TerminalQSwitcher | ViewMode::TervezoQSwitcher
@@ -1331,18 +4586,54 @@ impl App {
     }
 
     pub fn move_to_top(&mut self) {
+        if self.view_mode == ViewMode::RecentlyExited {
+            self.recently_exited_move(isize::MIN);
+            return;
+        }
+        if let Some(transcript) = self.transcript_tab() {
+            transcript.scroll = 0;
+            return;
+        }
         if self.view_mode == ViewMode::Log {
             self.log_scroll_to_top();
             return;
         }
+        if self.view_mode == ViewMode::Diagnostics {
+            self.diagnostics_scroll = 0;
+            return;
+        }
+        if let Some(ref mut timeline) = self.local_timeline {
+            if self.view_mode == ViewMode::SessionTimeline {
+                timeline.cursor_to(0);
+                return;
+            }
+        }
         self.selected = 0;
     }
 
     pub fn move_to_bottom(&mut self) {
+        if self.view_mode == ViewMode::RecentlyExited {
+            self.recently_exited_move(isize::MAX);
+            return;
+        }
+        if let Some(transcript) = self.transcript_tab() {
+            transcript.scroll = transcript.max_scroll.get();
+            return;
+        }
         if self.view_mode == ViewMode::Log {
             self.log_scroll_to_bottom();
             return;
         }
+        if self.view_mode == ViewMode::Diagnostics {
+            self.diagnostics_scroll = self.diagnostics.len().saturating_sub(1);
+            return;
+        }
+        if let Some(ref mut timeline) = self.local_timeline {
+            if self.view_mode == ViewMode::SessionTimeline {
+                timeline.cursor_to(usize::MAX);
+                return;
+            }
+        }
         if !self.filtered.is_empty() {
             self.selected = self.filtered.len() - 1;
         }
@@ -1353,7 +4644,52 @@ impl App {
     }
 
     pub fn has_active_filter(&self) -> bool {
-        !self.filter_query.is_empty()
+        !self.filter_query.is_empty() || self.errors_only
+    }
+
+    pub fn errors_only(&self) -> bool {
+        self.errors_only
+    }
+
+    pub fn row_preview(&self) -> bool {
+        self.row_preview
+    }
+
+    pub fn toggle_row_preview(&mut self) {
+        self.row_preview = !self.row_preview;
+    }
+
+    pub fn path_style(&self) -> PathStyle {
+        self.path_style
+    }
+
+    pub fn cycle_path_style(&mut self) {
+        self.path_style = self.path_style.next();
+        crate::format::set_full_paths(self.path_style == PathStyle::Full);
+    }
+
+    /// The selected local session's latest prompt and reply, read from the
+    /// transcript tail again only once the session has moved on.
+    pub fn selected_exchange(&self) -> Option<LastExchange> {
+        let session = self.selected_session()?.as_local()?;
+        let mut cache = self.row_preview_cache.borrow_mut();
+        if let Some((id, at, exchange)) = cache.as_ref() {
+            if *id == session.id && *at == session.last_activity {
+                return Some(exchange.clone());
+            }
+        }
+        let exchange = transcript_path(session)
+            .map(|p| last_exchange(&p))
+            .unwrap_or_default();
+        *cache = Some((session.id.clone(), session.last_activity, exchange.clone()));
+        Some(exchange)
+    }
+
+    pub fn toggle_errors_only(&mut self) {
+        self.remember_list_state();
+        self.errors_only = !self.errors_only;
+        self.apply_filter();
+        self.selected = 0;
     }
 
     pub fn filter_query(&self) -> &str {
@@ -1372,8 +4708,151 @@ impl App {
         self.selected = 0;
     }
 
+    fn list_snapshot(&self) -> ListSnapshot {
+        ListSnapshot {
+            filter_query: self.filter_query.clone(),
+            errors_only: self.errors_only,
+            sort_column: self.sort_column,
+            selected_id: self.selected_session().map(|e| e.id().to_string()),
+        }
+    }
+
+    /// Records the list as it is before it changes; anything stepped back
+    /// past is dropped.
+    fn remember_list_state(&mut self) {
+        let snapshot = self.list_snapshot();
+        self.list_history.truncate(self.list_history_pos);
+        if self.list_history.last() != Some(&snapshot) {
+            self.list_history.push(snapshot);
+        }
+        if self.list_history.len() > LIST_HISTORY {
+            self.list_history.remove(0);
+        }
+        self.list_history_pos = self.list_history.len();
+    }
+
+    /// Steps back to the previous filter, sort and selection, or forward
+    /// again. Returns whether there was one.
+    pub fn list_history_step(&mut self, forward: bool) -> bool {
+        if !forward && self.list_history_pos == self.list_history.len() {
+            // Keep the live state so forward can return to it.
+            let snapshot = self.list_snapshot();
+            if self.list_history.last() != Some(&snapshot) {
+                self.list_history.push(snapshot);
+            }
+            self.list_history_pos = self.list_history.len() - 1;
+        }
+        let target = if forward {
+            self.list_history_pos + 1
+        } else {
+            match self.list_history_pos.checked_sub(1) {
+                Some(pos) => pos,
+                None => return false,
+            }
+        };
+        let Some(snapshot) = self.list_history.get(target).cloned() else {
+            return false;
+        };
+        self.list_history_pos = target;
+        self.filter_query = snapshot.filter_query;
+        self.errors_only = snapshot.errors_only;
+        self.sort_column = snapshot.sort_column;
+        self.apply_sort();
+        self.apply_filter();
+        self.selected = snapshot
+            .selected_id
+            .and_then(|id| {
+                self.filtered
+                    .iter()
+                    .position(|&i| self.entries.get(i).is_some_and(|e| e.id() == id))
+            })
+            .unwrap_or(0);
+        true
+    }
+
+    /// Selects the session or implementation with `id`, or the only one
+    /// whose id starts with it, clearing a filter that hides it. False when
+    /// there's no such entry.
+    pub fn select_id(&mut self, id: &str) -> bool {
+        let matches: Vec<&str> = self
+            .entries
+            .iter()
+            .map(|e| e.id())
+            .filter(|e| e.starts_with(id))
+            .collect();
+        let full = match matches.iter().find(|&&e| e == id) {
+            Some(&e) => e.to_string(),
+            None if matches.len() == 1 => matches[0].to_string(),
+            None => return false,
+        };
+        let position = |app: &Self| {
+            app.filtered
+                .iter()
+                .position(|&i| app.entries.get(i).is_some_and(|e| e.id() == full))
+        };
+        if position(self).is_none() {
+            self.clear_filter();
+        }
+        match position(self) {
+            Some(pos) => {
+                self.selected = pos;
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn set_instance(&mut self, instance: Instance) {
+        self.instance = Some(instance);
+    }
+
+    /// Opens `link` once its session is listed.
+    pub fn open_link_later(&mut self, link: DeepLink) {
+        self.pending_link = Some(link);
+    }
+
+    /// Handles what other invocations sent, and returns the next deep link
+    /// to open. One for a session waits until discovery has found it, and
+    /// is dropped with a warning if it never does.
+    pub fn take_link(&mut self) -> Option<DeepLink> {
+        while let Some(message) = self.instance.as_ref().and_then(Instance::try_recv) {
+            match message {
+                Message::Link(link) => self.pending_link = Some(link),
+                Message::Knock => {
+                    self.toasts.push(
+                        Severity::Warning,
+                        t("Another c9s was started; this one is still running"),
+                    );
+                }
+                Message::Takeover => {
+                    tlog!(info, "quitting: another c9s took over");
+                    self.quit();
+                    return None;
+                }
+            }
+        }
+        let link = self.pending_link.take()?;
+        let Some(id) = link.session_id() else {
+            return Some(link);
+        };
+        if self.entries.iter().any(|e| e.id().starts_with(id)) {
+            return Some(link);
+        }
+        if self.is_discovering() {
+            self.pending_link = Some(link);
+        } else {
+            self.toasts.push(
+                Severity::Warning,
+                tf("No session matching {id}", &[("id", id)]),
+            );
+        }
+        None
+    }
+
     pub fn clear_filter(&mut self) {
+        self.remember_list_state();
         self.filter_query.clear();
+        self.errors_only = false;
         self.apply_filter();
     }
 
@@ -1382,9 +4861,90 @@ impl App {
     }
 
     pub fn quit(&mut self) {
+        self.flush_tracked_time();
         self.should_quit = true;
     }
 
+    /// A key was pressed; tracked time keeps counting.
+    pub fn note_input(&mut self) {
+        self.time_tracker.input(chrono::Utc::now());
+    }
+
+    /// Books time to the session open in the terminal view and stores it
+    /// once a minute.
+    pub fn tick_time_tracking(&mut self) {
+        self.time_tracker
+            .tick(self.time_tracker_focus(), chrono::Utc::now());
+        if self.time_flushed.elapsed() >= std::time::Duration::from_secs(60) {
+            self.flush_tracked_time();
+        }
+    }
+
+    /// The session open in the terminal view, if any.
+    fn time_tracker_focus(&self) -> Option<Focus> {
+        if !matches!(
+            self.view_mode,
+            ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy
+        ) {
+            return None;
+        }
+        let id = self.attached_session_id()?;
+        let project = self
+            .entries
+            .iter()
+            .find(|e| e.id() == id)
+            .map_or(id, |e| e.display_name());
+        Some(Focus {
+            session_id: id.to_string(),
+            project: project.to_string(),
+        })
+    }
+
+    fn flush_tracked_time(&mut self) {
+        self.time_tracker
+            .tick(self.time_tracker_focus(), chrono::Utc::now());
+        self.time_flushed = std::time::Instant::now();
+        let booked = self.time_tracker.take_pending();
+        let today = chrono::Local::now().date_naive();
+        if self.tracked_today.0 != today {
+            self.tracked_today = (today, stored_time_on(self.store.as_ref(), today));
+        }
+        self.tracked_today.1 += booked
+            .iter()
+            .filter(|t| t.date == today)
+            .map(|t| t.millis)
+            .sum::<u64>();
+        if let Some(ref store) = self.store {
+            if let Err(e) = store.add_tracked_time(&booked) {
+                tlog!(warn, "failed to save tracked time: {}", e);
+            }
+        }
+    }
+
+    /// Starts or stops time tracking by hand.
+    pub fn toggle_time_tracking(&mut self) {
+        let enabled = !self.time_tracker.is_enabled();
+        self.time_tracker.set_enabled(enabled, chrono::Utc::now());
+        let message = if enabled {
+            "Time tracking started"
+        } else {
+            "Time tracking stopped"
+        };
+        self.toasts.push(Severity::Info, t(message));
+    }
+
+    /// Seconds tracked today, and whether tracking is on; `None` while
+    /// nothing is tracked and tracking is on, so the usage panel stays
+    /// quiet for people who never attach.
+    pub fn tracked_today(&self) -> Option<(u64, bool)> {
+        let (day, stored) = self.tracked_today;
+        let today = chrono::Local::now().date_naive();
+        let stored = if day == today { stored } else { 0 };
+        let millis = stored + self.time_tracker.pending_millis(today);
+        let enabled = self.time_tracker.is_enabled();
+        (millis > 0 || !enabled).then_some((millis / 1000, enabled))
+    }
+
     pub fn terminal_manager(&self) -> &TerminalManager {
         &self.terminal_manager
     }
@@ -1452,55 +5012,340 @@ impl App {
             (dir.to_path_buf(), prefix)
         };
 
-        let entries = match std::fs::read_dir(&dir) {
-            Ok(e) => e,
-            Err(_) => return,
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(e) => e,
+            Err(_) => return,
+        };
+
+        let mut matches: Vec<String> = entries
+            .flatten()
+            .filter(|e| {
+                e.file_type().map(|ft| ft.is_dir()).unwrap_or(false)
+                    && e.file_name().to_string_lossy().starts_with(&prefix)
+            })
+            .map(|e| {
+                let full = dir.join(e.file_name());
+                let mut s = full.to_string_lossy().to_string();
+                s.push('/');
+                if self.command_input.starts_with('~') {
+                    if let Some(home) = dirs::home_dir() {
+                        let home_str = home.to_string_lossy().to_string();
+                        if s.starts_with(&home_str) {
+                            s = format!("~{}", &s[home_str.len()..]);
+                        }
+                    }
+                }
+                s
+            })
+            .collect();
+
+        matches.sort();
+
+        if matches.len() == 1 {
+            self.command_input = matches.remove(0);
+        } else if matches.len() > 1 {
+            let common = longest_common_prefix(&matches);
+            if common.len() > self.command_input.len() {
+                self.command_input = common;
+            }
+        }
+    }
+
+    pub fn all_sessions(&self) -> &[Session] {
+        &self.local_sessions
+    }
+
+    #[allow(dead_code)]
+    pub fn live_sessions(&self) -> Vec<&Session> {
+        self.local_sessions
+            .iter()
+            .filter(|s| s.pid.is_some())
+            .collect()
+    }
+
+    /// Switches to the timeline view of the selected local session, whose
+    /// transcript is parsed in the background. Returns false if there is
+    /// no transcript to show.
+    pub fn open_local_timeline(&mut self) -> bool {
+        let Some(session) = self.selected_session().and_then(|e| e.as_local()) else {
+            return false;
+        };
+        let Some(path) = transcript_path(session) else {
+            return false;
+        };
+        let state = LocalTimelineState {
+            session_id: session.id.clone(),
+            title: match session.git_branch.as_deref() {
+                Some(branch) if !branch.is_empty() => {
+                    format!(" Timeline: {} [{}] ", session.project_name, branch)
+                }
+                _ => format!(" Timeline: {} ", session.project_name),
+            },
+            loading: true,
+            events: Vec::new(),
+            bookmarks: self.load_bookmarks(&session.id),
+            bookmarks_only: false,
+            cursor: 0,
+            scroll: 0,
+            visible_height: std::cell::Cell::new(20),
+            return_to: if self.view_mode == ViewMode::Detail {
+                ViewMode::Detail
+            } else {
+                ViewMode::List
+            },
+        };
+        self.read_transcript(TranscriptView::Timeline, &state.session_id, &path);
+        self.local_timeline = Some(state);
+        self.set_view_mode(ViewMode::SessionTimeline);
+        true
+    }
+
+    /// Re-reads the transcript, keeping the scroll position.
+    pub fn reload_local_timeline(&mut self) {
+        let Some(ref timeline) = self.local_timeline else {
+            return;
+        };
+        let id = timeline.session_id.clone();
+        let path = self
+            .local_sessions
+            .iter()
+            .find(|s| s.id == id)
+            .and_then(transcript_path);
+        if let Some(path) = path {
+            self.read_transcript(TranscriptView::Timeline, &id, &path);
+        }
+    }
+
+    /// Parses a transcript in the background for the detail's Transcript
+    /// tab or the timeline. The events arrive via `drain_transcripts`.
+    fn read_transcript(&self, view: TranscriptView, session_id: &str, path: &std::path::Path) {
+        let tx = self.transcript_tx.clone();
+        let (id, path) = (session_id.to_string(), path.to_path_buf());
+        crate::wakeup::spawn(move || {
+            let events = match view {
+                TranscriptView::Detail => load_transcript(&path),
+                TranscriptView::Timeline => load_timeline(&path),
+            };
+            let _ = tx.send((view, id, events));
+        });
+    }
+
+    pub fn drain_transcripts(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok((view, id, events)) = self.transcript_rx.try_recv() {
+            match view {
+                TranscriptView::Detail => {
+                    if let Some(ref mut transcript) = self.detail_transcript {
+                        if transcript.session_id == id {
+                            transcript.loading = false;
+                            transcript.events = events;
+                            changed = true;
+                        }
+                    }
+                }
+                TranscriptView::Timeline => {
+                    if let Some(ref mut timeline) = self.local_timeline {
+                        if timeline.session_id == id {
+                            timeline.loading = false;
+                            timeline.events = events;
+                            changed = true;
+                        }
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    fn load_bookmarks(&self, session_id: &str) -> Vec<Bookmark> {
+        let Some(ref store) = self.store else {
+            return Vec::new();
+        };
+        store.list_bookmarks(session_id).unwrap_or_else(|e| {
+            tlog!(warn, "Failed to load bookmarks: {}", e);
+            Vec::new()
+        })
+    }
+
+    /// Bookmarks the selected timeline event, or removes the bookmark if it
+    /// is already set. In the bookmark list, removes the selected bookmark.
+    pub fn toggle_timeline_bookmark(&mut self) {
+        let Some(ref timeline) = self.local_timeline else {
+            return;
+        };
+        let Some(ref store) = self.store else {
+            self.toasts.push(
+                Severity::Warning,
+                t("Bookmarks unavailable: store not open"),
+            );
+            return;
+        };
+        let result = if timeline.bookmarks_only {
+            match timeline.bookmarks.get(timeline.cursor) {
+                Some(b) => store.remove_bookmark(&timeline.session_id, b.message_index),
+                None => return,
+            }
+        } else {
+            match timeline.selected_event() {
+                Some(e) if timeline.is_bookmarked(e.index) => {
+                    store.remove_bookmark(&timeline.session_id, e.index)
+                }
+                Some(e) => store.add_bookmark(&timeline.session_id, e.index, &e.text),
+                None => return,
+            }
+        };
+        if let Err(e) = result {
+            self.toasts.push(
+                Severity::Error,
+                tf(
+                    "Failed to update bookmark: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            );
+            return;
+        }
+        let session_id = timeline.session_id.clone();
+        let bookmarks = self.load_bookmarks(&session_id);
+        if let Some(ref mut timeline) = self.local_timeline {
+            timeline.bookmarks = bookmarks;
+            let cursor = timeline.cursor;
+            timeline.cursor_to(cursor);
+        }
+    }
+
+    fn timeline_session(&self) -> Option<&Session> {
+        let timeline = self.local_timeline.as_ref()?;
+        self.local_sessions
+            .iter()
+            .find(|s| s.id == timeline.session_id)
+    }
+
+    /// Asks how to export the timeline's session: starts on Markdown, to
+    /// `~/.c9s/exports` or to the clipboard.
+    pub fn open_export_prompt(&mut self, to_clipboard: bool) {
+        let Some(session) = self.timeline_session() else {
+            return;
+        };
+        let path = if to_clipboard {
+            String::new()
+        } else {
+            default_export_path(session, ExportFormat::Markdown)
+                .map(|p| p.display().to_string())
+                .unwrap_or_default()
+        };
+        self.export_prompt = Some(ExportPromptState {
+            format: ExportFormat::Markdown,
+            path,
+        });
+        self.set_view_mode(ViewMode::TimelineExport);
+    }
+
+    /// Exports the timeline's session as the prompt says, then goes back to
+    /// the timeline.
+    pub fn submit_export_prompt(&mut self) {
+        let Some(prompt) = self.export_prompt.take() else {
+            return;
+        };
+        self.set_view_mode(ViewMode::SessionTimeline);
+        let Some(session) = self.timeline_session() else {
+            return;
+        };
+        let result = (|| -> Result<String> {
+            let path =
+                transcript_path(session).ok_or_else(|| anyhow::anyhow!("transcript not found"))?;
+            let doc = export_transcript(session, &path, prompt.format, &|c| {
+                crate::format::cost(c, 2)
+            })?;
+            let out = prompt.path.trim();
+            if out.is_empty() {
+                crate::clipboard::copy_or_osc52(&doc, &mut std::io::stdout())?;
+                return Ok(t("Transcript copied to clipboard").to_string());
+            }
+            let out = match out.strip_prefix('~') {
+                Some(rest) => dirs::home_dir()
+                    .map(|h| h.to_string_lossy().to_string() + rest)
+                    .unwrap_or_else(|| out.to_string()),
+                None => out.to_string(),
+            };
+            let out = std::path::Path::new(&out);
+            if let Some(dir) = out.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(out, doc)?;
+            Ok(tf(
+                "Exported to {path}",
+                &[("path", &out.display().to_string())],
+            ))
+        })();
+        match result {
+            Ok(msg) => self.toasts.push(Severity::Success, msg),
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf(
+                    "Transcript export failed: {error}",
+                    &[("error", &e.to_string())],
+                ),
+            ),
+        }
+    }
+
+    /// Writes a handoff bundle for the selected local session to
+    /// `~/.c9s/handoffs/`.
+    pub fn write_handoff(&mut self) {
+        let Some(SessionEntry::Local(session)) = self.selected_session() else {
+            self.toasts.push(
+                Severity::Warning,
+                t("Handoff bundles are for local sessions"),
+            );
+            return;
         };
+        let result = (|| -> Result<String> {
+            let path =
+                transcript_path(session).ok_or_else(|| anyhow::anyhow!("transcript not found"))?;
+            let dir =
+                default_handoff_dir(session).ok_or_else(|| anyhow::anyhow!("no home directory"))?;
+            write_handoff(session, &path, &dir, &|c| crate::format::cost(c, 2))?;
+            Ok(tf(
+                "Handoff written to {path}",
+                &[("path", &dir.display().to_string())],
+            ))
+        })();
+        match result {
+            Ok(msg) => self.toasts.push(Severity::Success, msg),
+            Err(e) => self.toasts.push(
+                Severity::Error,
+                tf("Handoff failed: {error}", &[("error", &e.to_string())]),
+            ),
+        }
+    }
 
-        let mut matches: Vec<String> = entries
-            .flatten()
-            .filter(|e| {
-                e.file_type().map(|ft| ft.is_dir()).unwrap_or(false)
-                    && e.file_name().to_string_lossy().starts_with(&prefix)
-            })
-            .map(|e| {
-                let full = dir.join(e.file_name());
-                let mut s = full.to_string_lossy().to_string();
-                s.push('/');
-                if self.command_input.starts_with('~') {
-                    if let Some(home) = dirs::home_dir() {
-                        let home_str = home.to_string_lossy().to_string();
-                        if s.starts_with(&home_str) {
-                            s = format!("~{}", &s[home_str.len()..]);
-                        }
-                    }
-                }
-                s
-            })
-            .collect();
+    pub fn close_local_timeline(&mut self) {
+        let return_to = self
+            .local_timeline
+            .take()
+            .map_or(ViewMode::List, |t| t.return_to);
+        self.set_view_mode(return_to);
+    }
 
-        matches.sort();
+    /// Opens the list of transcripts with skipped or unfinished lines.
+    pub fn open_diagnostics(&mut self) {
+        self.diagnostics = self.discovery.diagnostics();
+        self.diagnostics_scroll = 0;
+        self.diagnostics_return = self.view_mode.clone();
+        self.set_view_mode(ViewMode::Diagnostics);
+    }
 
-        if matches.len() == 1 {
-            self.command_input = matches.remove(0);
-        } else if matches.len() > 1 {
-            let common = longest_common_prefix(&matches);
-            if common.len() > self.command_input.len() {
-                self.command_input = common;
-            }
-        }
+    pub fn close_diagnostics(&mut self) {
+        let back = std::mem::replace(&mut self.diagnostics_return, ViewMode::List);
+        self.set_view_mode(back);
     }
 
-    pub fn all_sessions(&self) -> &[Session] {
-        &self.local_sessions
+    pub fn diagnostics(&self) -> &[FileDiagnostics] {
+        &self.diagnostics
     }
 
-    #[allow(dead_code)]
-    pub fn live_sessions(&self) -> Vec<&Session> {
-        self.local_sessions
-            .iter()
-            .filter(|s| s.pid.is_some())
-            .collect()
+    pub fn diagnostics_scroll(&self) -> usize {
+        self.diagnostics_scroll
     }
 
     pub fn log_scroll(&self) -> usize {
@@ -1557,8 +5402,19 @@ impl App {
             .unwrap_or_else(|| {
                 std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("/"))
             });
-        if !self.terminal_manager.open_side_terminal(&cwd, rows, cols) {
-            return;
+        match self.terminal_manager.open_side_terminal(&cwd, rows, cols) {
+            Ok(true) => {}
+            Ok(false) => return,
+            Err(e) => {
+                self.toast(
+                    Severity::Error,
+                    tf(
+                        "Side terminal failed: {error}",
+                        &[("error", &format!("{:#}", e))],
+                    ),
+                );
+                return;
+            }
         }
         self.side_panel_open = true;
         self.side_panel_focused = true;
@@ -1572,6 +5428,7 @@ impl App {
 
     pub fn drain_ci_statuses(&mut self) {
         while let Ok((id, status)) = self.ci_rx.try_recv() {
+            self.notify_ci_flip(&id, &status);
             if matches!(self.ci_statuses.get(&id), Some(CiStatus::Fixing)) {
                 if matches!(status, CiStatus::Passing | CiStatus::Running) {
                     self.ci_statuses.insert(id, status);
@@ -1580,6 +5437,61 @@ impl App {
                 self.ci_statuses.insert(id, status);
             }
         }
+        while let Ok((id, pr)) = self.pr_rx.try_recv() {
+            match pr {
+                Some(pr) => self.pr_statuses.insert(id, pr),
+                None => self.pr_statuses.remove(&id),
+            };
+        }
+    }
+
+    /// Toasts and rings when CI for a live session's branch breaks or
+    /// recovers.
+    fn notify_ci_flip(&mut self, id: &str, status: &CiStatus) {
+        let was_running = matches!(
+            self.ci_statuses.get(id),
+            Some(CiStatus::Running | CiStatus::Fixing)
+        );
+        let last = self.ci_outcomes.get(id).copied();
+        let flip = ci_flip(last, was_running, status);
+        match status {
+            CiStatus::Passing => self.ci_outcomes.insert(id.to_string(), true),
+            CiStatus::Failing => self.ci_outcomes.insert(id.to_string(), false),
+            _ => None,
+        };
+        let Some(green) = flip.filter(|_| self.ci_notify) else {
+            return;
+        };
+        let Some(entry) = self.entries.iter().find(|e| e.id() == id) else {
+            return;
+        };
+        let live = match entry {
+            SessionEntry::Local(s) => s.status != SessionStatus::Dead,
+            SessionEntry::Remote(_) => true,
+        };
+        if !live {
+            return;
+        }
+        let branch = entry.branch().unwrap_or("?");
+        let (severity, message) = if green {
+            (
+                Severity::Success,
+                tf(
+                    "CI green again on {branch} ({name})",
+                    &[("branch", branch), ("name", entry.display_name())],
+                ),
+            )
+        } else {
+            (
+                Severity::Error,
+                tf(
+                    "CI failed on {branch} ({name})",
+                    &[("branch", branch), ("name", entry.display_name())],
+                ),
+            )
+        };
+        self.terminal_manager.notify_external(id, &message);
+        self.toasts.push(severity, message);
     }
 
     pub fn check_ci_statuses(&mut self) {
@@ -1588,10 +5500,19 @@ impl App {
         }
         self.ci_last_check = std::time::Instant::now();
 
+        // Live local sessions are watched even when filtered out, so a
+        // broken build still gets noticed.
         let entries: Vec<SessionEntry> = self
-            .filtered
+            .entries
             .iter()
-            .filter_map(|&i| self.entries.get(i).cloned())
+            .enumerate()
+            .filter(|(i, e)| {
+                self.filtered.contains(i)
+                    || (self.ci_notify
+                        && e.as_local()
+                            .is_some_and(|s| s.status != SessionStatus::Dead))
+            })
+            .map(|(_, e)| e.clone())
             .collect();
 
         for entry in &entries {
@@ -1614,14 +5535,17 @@ impl App {
             match entry {
                 SessionEntry::Local(s) => {
                     let cwd = s.cwd.clone();
-                    std::thread::spawn(move || {
+                    let pr_tx = self.pr_tx.clone();
+                    crate::wakeup::spawn(move || {
                         let status = check_ci_for_local(&cwd, &branch);
-                        let _ = tx.send((id, status));
+                        let _ = tx.send((id.clone(), status));
+                        let pr = check_pr_for_local(&cwd, &branch);
+                        let _ = pr_tx.send((id, pr));
                     });
                 }
                 SessionEntry::Remote(i) => {
                     let repo_url = i.repo_url.clone().unwrap_or_default();
-                    std::thread::spawn(move || {
+                    crate::wakeup::spawn(move || {
                         let status = check_ci_for_remote(&repo_url, &branch);
                         let _ = tx.send((id, status));
                     });
@@ -1631,6 +5555,140 @@ impl App {
     }
 }
 
+pub enum DiscoveryMsg {
+    /// Sessions found so far.
+    Partial(Vec<Session>),
+    /// The complete list, and the discovery that produced it.
+    Done(Vec<Session>, Box<SessionDiscovery>),
+}
+
+/// Runs the initial session scan off the UI thread. With `persist`, it
+/// starts from the transcript summaries saved by the last run, so only
+/// files changed since are parsed, and saves what it parsed.
+fn spawn_discovery(mut discovery: SessionDiscovery, persist: bool) -> mpsc::Receiver<DiscoveryMsg> {
+    let (tx, rx) = mpsc::channel();
+    crate::wakeup::spawn(move || {
+        // A connection of its own: `Store` is not `Sync`.
+        let store = persist.then(Store::open).and_then(Result::ok);
+        if let Some(ref store) = store {
+            match store.load_jsonl_stats() {
+                Ok(entries) => discovery.seed_stats(entries),
+                Err(e) => tlog!(warn, "loading saved transcript stats: {}", e),
+            }
+        }
+        let progress_tx = tx.clone();
+        let sessions = discovery
+            .discover_all_with(&mut |partial| {
+                let _ = progress_tx.send(DiscoveryMsg::Partial(partial));
+                crate::wakeup::notify();
+            })
+            .unwrap_or_default();
+        let parsed = discovery.take_parsed_stats();
+        let _ = tx.send(DiscoveryMsg::Done(sessions, Box::new(discovery)));
+        crate::wakeup::notify();
+        if let Some(ref store) = store {
+            tlog!(info, "initial scan parsed {} transcripts", parsed.len());
+            if let Err(e) = store
+                .save_jsonl_stats(&parsed)
+                .and_then(|_| store.prune_jsonl_stats())
+            {
+                tlog!(warn, "saving transcript stats: {}", e);
+            }
+        }
+    });
+    rx
+}
+
+/// Runs `claude --version` and, if enabled, the latest-release lookup off the
+/// UI thread.
+fn spawn_version_check(cfg: &UpdatesConfig) -> mpsc::Receiver<ClaudeVersions> {
+    let (tx, rx) = mpsc::channel();
+    let url = cfg.check.then(|| cfg.latest_url.clone());
+    crate::wakeup::spawn(move || {
+        let versions = ClaudeVersions {
+            installed: SessionManager::installed_version(),
+            latest: url.as_deref().and_then(fetch_latest_version),
+        };
+        let _ = tx.send(versions);
+    });
+    rx
+}
+
+/// Live sessions whose directories are in the same working tree as another
+/// live session's, with what they clash with. Subagents run inside their
+/// parent and don't count.
+fn worktree_clashes(
+    sessions: &[Session],
+    mut root: impl FnMut(&std::path::Path) -> Option<std::path::PathBuf>,
+) -> HashMap<String, WorktreeClash> {
+    let mut by_root: HashMap<std::path::PathBuf, Vec<&Session>> = HashMap::new();
+    for s in sessions
+        .iter()
+        .filter(|s| s.status != SessionStatus::Dead && !s.is_subagent && !s.imported)
+    {
+        if let Some(r) = root(&s.cwd) {
+            by_root.entry(r).or_default().push(s);
+        }
+    }
+    let mut clashes = HashMap::new();
+    for group in by_root.values().filter(|g| g.len() > 1) {
+        for s in group {
+            let others: Vec<&&Session> = group.iter().filter(|o| o.id != s.id).collect();
+            clashes.insert(
+                s.id.clone(),
+                WorktreeClash {
+                    others: others.iter().map(|o| o.project_name.clone()).collect(),
+                    same_branch: others.iter().any(|o| o.git_branch == s.git_branch),
+                },
+            );
+        }
+    }
+    clashes
+}
+
+/// Moves sessions with a parent in the list directly below that parent,
+/// keeping the existing order within each level. Returns the regrouped
+/// entries and the nesting depth of every non-top-level entry.
+fn group_by_parent(entries: Vec<SessionEntry>) -> (Vec<SessionEntry>, HashMap<String, usize>) {
+    let ids: HashSet<String> = entries.iter().map(|e| e.id().to_string()).collect();
+    let mut children: HashMap<String, Vec<SessionEntry>> = HashMap::new();
+    let mut roots = Vec::new();
+    for entry in entries {
+        match entry.parent_id().filter(|p| ids.contains(*p)) {
+            Some(parent) => children.entry(parent.to_string()).or_default().push(entry),
+            None => roots.push(entry),
+        }
+    }
+
+    fn place(
+        entry: SessionEntry,
+        depth: usize,
+        children: &mut HashMap<String, Vec<SessionEntry>>,
+        out: &mut Vec<SessionEntry>,
+        depths: &mut HashMap<String, usize>,
+    ) {
+        let id = entry.id().to_string();
+        if depth > 0 {
+            depths.insert(id.clone(), depth);
+        }
+        out.push(entry);
+        for child in children.remove(&id).unwrap_or_default() {
+            place(child, depth + 1, children, out, depths);
+        }
+    }
+
+    let mut depths = HashMap::new();
+    let mut out = Vec::with_capacity(ids.len());
+    for root in roots {
+        place(root, 0, &mut children, &mut out, &mut depths);
+    }
+    // Parent cycles leave entries unplaced; keep them at the top level.
+    for (_, rest) in children.drain() {
+        out.extend(rest);
+    }
+    (out, depths)
+}
+
 fn longest_common_prefix(strings: &[String]) -> String {
     if strings.is_empty() {
         return String::new();
@@ -1673,6 +5731,36 @@ fn extract_repo_name(url: &str) -> String {
     url.to_string()
 }
 
+/// Whether a CI result is news: `Some(false)` when it just broke,
+/// `Some(true)` when it is green after failing. Without an earlier result,
+/// only a run seen going from running to failed counts.
+fn ci_flip(last: Option<bool>, was_running: bool, status: &CiStatus) -> Option<bool> {
+    let green = match status {
+        CiStatus::Passing => true,
+        CiStatus::Failing => false,
+        _ => return None,
+    };
+    match last {
+        Some(last) if last != green => Some(green),
+        None if was_running && !green => Some(false),
+        _ => None,
+    }
+}
+
+/// Milliseconds tracked on `day` according to the store.
+fn stored_time_on(store: Option<&Store>, day: chrono::NaiveDate) -> u64 {
+    store
+        .and_then(|s| s.project_time(day).ok())
+        .map(|times| {
+            times
+                .iter()
+                .filter(|t| t.date == day)
+                .map(|t| t.millis)
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
 fn check_ci_for_local(cwd: &std::path::Path, branch: &str) -> CiStatus {
     let output = std::process::Command::new("gh")
         .args([
@@ -1690,6 +5778,82 @@ fn check_ci_for_local(cwd: &std::path::Path, branch: &str) -> CiStatus {
     parse_gh_run_output(output)
 }
 
+/// The open pull request for `branch`, if any. Closed and merged ones are
+/// not shown.
+fn check_pr_for_local(cwd: &std::path::Path, branch: &str) -> Option<PrStatus> {
+    let output = std::process::Command::new("gh")
+        .args([
+            "pr",
+            "view",
+            branch,
+            "--json",
+            "number,state,isDraft,reviewDecision,statusCheckRollup,url",
+        ])
+        .current_dir(cwd)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    parse_gh_pr_output(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_gh_pr_output(text: &str) -> Option<PrStatus> {
+    let pr: serde_json::Value = serde_json::from_str(text).ok()?;
+    if pr.get("state").and_then(|v| v.as_str()) != Some("OPEN") {
+        return None;
+    }
+    let review = match pr.get("reviewDecision").and_then(|v| v.as_str()) {
+        Some("APPROVED") => PrReview::Approved,
+        Some("CHANGES_REQUESTED") => PrReview::ChangesRequested,
+        Some("REVIEW_REQUIRED") => PrReview::Required,
+        _ => PrReview::None,
+    };
+    let checks = pr
+        .get("statusCheckRollup")
+        .and_then(|v| v.as_array())
+        .map_or(CiStatus::None, |c| rollup_status(c));
+    Some(PrStatus {
+        number: pr.get("number")?.as_u64()?,
+        draft: pr.get("isDraft").and_then(|v| v.as_bool()).unwrap_or(false),
+        review,
+        checks,
+        url: pr
+            .get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .to_string(),
+    })
+}
+
+/// Failing if any check failed, running if any is unfinished, passing
+/// otherwise. Check runs report `status`/`conclusion`, commit statuses
+/// `state`.
+fn rollup_status(checks: &[serde_json::Value]) -> CiStatus {
+    if checks.is_empty() {
+        return CiStatus::None;
+    }
+    let mut running = false;
+    for check in checks {
+        let field = |k: &str| check.get(k).and_then(|v| v.as_str()).unwrap_or("");
+        let outcome = match field("conclusion") {
+            "" => field("state"),
+            c => c,
+        };
+        match outcome {
+            "FAILURE" | "ERROR" | "TIMED_OUT" | "CANCELLED" | "ACTION_REQUIRED" => {
+                return CiStatus::Failing
+            }
+            "PENDING" | "EXPECTED" => running = true,
+            "" if field("status") != "COMPLETED" => running = true,
+            _ => {}
+        }
+    }
+    if running {
+        CiStatus::Running
+    } else {
+        CiStatus::Passing
+    }
+}
+
 fn check_ci_for_remote(repo_url: &str, branch: &str) -> CiStatus {
     let owner_repo = match parse_github_owner_repo(repo_url) {
         Some(r) => r,
@@ -1741,7 +5905,7 @@ fn parse_gh_run_output(output: std::io::Result<std::process::Output>) -> CiStatu
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use crate::tervezo::models::{Implementation, ImplementationStatus, PrDetails};
 
@@ -1762,6 +5926,9 @@ mod tests {
             pr_number: None,
             pr_status: None,
             mode: None,
+            queue_position: None,
+            estimated_start_at: None,
+            restarted_from: None,
         }
     }
 
@@ -1881,6 +6048,179 @@ mod tests {
         assert_eq!(mode, TervezoCreateMode::Feature);
     }
 
+    #[test]
+    fn test_tervezo_detail_state_timeline_error_default_none() {
+        let imp = Implementation {
+            id: "test-id".to_string(),
+            title: Some("Test".to_string()),
+            status: ImplementationStatus::Running,
+            branch: None,
+            repo_url: None,
+            created_at: None,
+            updated_at: None,
+            estimated_cost_usd: None,
+            total_tokens: None,
+            message_count: None,
+            pr_url: None,
+            pr_number: None,
+            pr_status: None,
+            mode: None,
+            queue_position: None,
+            estimated_start_at: None,
+            restarted_from: None,
+        };
+        let state = TervezoDetailState::new(imp);
+        assert!(state.timeline_error.is_none());
+    }
+
+    #[test]
+    fn test_failure_reason_is_newest_error() {
+        let mut state = TervezoDetailState::new(make_impl(ImplementationStatus::Failed, None));
+        assert_eq!(state.failure_reason(), None);
+        state.timeline = [
+            r#"{"type":"error","message":"flaky network"}"#,
+            r#"{"type":"agent_message","message":"Retrying"}"#,
+            r#"{"type":"error","message":"cargo test failed\n3 tests failed"}"#,
+            r#"{"type":"agent_message","message":"Giving up"}"#,
+        ]
+        .iter()
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect();
+        assert_eq!(state.failure_reason(), Some("cargo test failed"));
+    }
+
+    #[test]
+    fn test_compare_with_previous_attempt() {
+        let mut imp = make_impl(ImplementationStatus::Completed, None);
+        assert!(!TervezoDetailState::new(imp.clone())
+            .compute_available_actions()
+            .contains(&TervezoAction::ComparePrevious));
+        imp.restarted_from = Some("old-id".into());
+        let mut app = App::with_sessions(Vec::new());
+        app.tervezo_detail = Some(TervezoDetailState::new(imp));
+        let state = app.tervezo_detail.as_mut().unwrap();
+        assert!(state
+            .compute_available_actions()
+            .contains(&TervezoAction::ComparePrevious));
+        state.comparison = Some(ComparisonState::new("old-id".into()));
+        let (tx, rx) = mpsc::channel();
+        app.tervezo_detail_rx = Some(rx);
+
+        let change = |path: &str| FileChange {
+            path: Some(path.into()),
+            diff: Some("+x".into()),
+            status: None,
+            additions: None,
+            deletions: None,
+            changes: None,
+        };
+        let files = crate::tervezo::compare::compare_changes(&[change("a")], &[change("b")]);
+        tx.send(TervezoDetailMsg::Comparison(files)).unwrap();
+        assert!(app.drain_tervezo_detail_messages());
+        let comparison = app
+            .tervezo_detail
+            .as_mut()
+            .and_then(|s| s.comparison.as_mut())
+            .unwrap();
+        comparison.down();
+        comparison.down();
+        assert_eq!(comparison.cursor, 1);
+        comparison.toggle();
+        assert!(comparison.expanded);
+        comparison.down();
+        assert_eq!((comparison.cursor, comparison.scroll), (1, 1));
+    }
+
+    #[test]
+    fn test_restart_chain() {
+        let imp = |id: &str, from: Option<&str>| {
+            let mut imp = make_impl(ImplementationStatus::Failed, None);
+            imp.id = id.into();
+            imp.title = Some(format!("Task {}", id));
+            imp.restarted_from = from.map(Into::into);
+            imp
+        };
+        let mut app = App::with_sessions(Vec::new());
+        app.entries = vec![
+            SessionEntry::Remote(imp("a", None)),
+            SessionEntry::Remote(imp("b", Some("a"))),
+        ];
+        app.filtered = vec![0, 1];
+        // The server hasn't reported c's parent yet.
+        app.record_restart("c", "b");
+
+        app.show_tervezo_implementation(imp("b", Some("a")));
+        assert_eq!(app.selected_index(), 1);
+        let state = app.tervezo_detail.as_ref().unwrap();
+        let chain: Vec<_> = state.chain.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(chain, ["Task a", "Task b", "c"]);
+        assert_eq!(app.related_attempt(false).as_deref(), Some("a"));
+        assert_eq!(app.related_attempt(true).as_deref(), Some("c"));
+
+        app.show_tervezo_implementation(imp("a", None));
+        assert_eq!(app.related_attempt(false), None);
+    }
+
+    #[test]
+    fn test_tab_error_recorded_and_cleared() {
+        let mut app = App::with_sessions(Vec::new());
+        let imp: Implementation =
+            serde_json::from_str(r#"{"id": "impl-1", "status": "running"}"#).unwrap();
+        app.tervezo_detail = Some(TervezoDetailState::new(imp));
+        let (tx, rx) = mpsc::channel();
+        app.tervezo_detail_rx = Some(rx);
+
+        let err = TervezoError::Auth { status: 401 };
+        tx.send(TervezoDetailMsg::Error(TervezoTab::Plan, err.clone()))
+            .unwrap();
+        assert!(app.drain_tervezo_detail_messages());
+        let state = app.tervezo_detail.as_ref().unwrap();
+        assert_eq!(state.tab_errors.get(&TervezoTab::Plan), Some(&err));
+
+        tx.send(TervezoDetailMsg::Plan("# Plan".to_string()))
+            .unwrap();
+        app.drain_tervezo_detail_messages();
+        let state = app.tervezo_detail.as_ref().unwrap();
+        assert!(state.tab_errors.is_empty());
+    }
+
+    #[test]
+    fn test_older_timeline_page_is_prepended() {
+        let mut app = App::with_sessions(Vec::new());
+        let imp: Implementation =
+            serde_json::from_str(r#"{"id": "impl-1", "status": "running"}"#).unwrap();
+        app.tervezo_detail = Some(TervezoDetailState::new(imp));
+        let (tx, rx) = mpsc::channel();
+        app.tervezo_detail_rx = Some(rx);
+        let msg = |id: &str| -> TimelineMessage {
+            serde_json::from_str(&format!(r#"{{"id": "{}", "type": "agent_message"}}"#, id))
+                .unwrap()
+        };
+
+        tx.send(TervezoDetailMsg::Timeline(vec![msg("m3"), msg("m4")]))
+            .unwrap();
+        tx.send(TervezoDetailMsg::TimelineHasOlder(true)).unwrap();
+        app.drain_tervezo_detail_messages();
+        let state = app.tervezo_detail.as_mut().unwrap();
+        assert!(state.timeline_has_older);
+        state.timeline_loading_older = true;
+
+        tx.send(TervezoDetailMsg::TimelineOlder(
+            vec![msg("m1"), msg("m2")],
+            false,
+        ))
+        .unwrap();
+        app.drain_tervezo_detail_messages();
+        let state = app.tervezo_detail.as_ref().unwrap();
+        let ids: Vec<_> = state
+            .timeline
+            .iter()
+            .filter_map(|m| m.id.as_deref())
+            .collect();
+        assert_eq!(ids, ["m1", "m2", "m3", "m4"]);
+        assert!(!state.timeline_has_older && !state.timeline_loading_older);
+    }
+
     #[test]
     fn test_create_state_defaults() {
         let state = TervezoCreateState::new();
@@ -1892,6 +6232,313 @@ mod tests {
         assert!(state.error.is_none());
     }
 
+    #[test]
+    fn test_ci_flip() {
+        assert_eq!(ci_flip(Some(true), false, &CiStatus::Failing), Some(false));
+        assert_eq!(ci_flip(Some(false), true, &CiStatus::Passing), Some(true));
+        assert_eq!(ci_flip(Some(true), true, &CiStatus::Passing), None);
+        assert_eq!(ci_flip(Some(false), false, &CiStatus::Failing), None);
+        assert_eq!(ci_flip(Some(true), false, &CiStatus::Running), None);
+        // First result seen: only a run that just failed is news.
+        assert_eq!(ci_flip(None, false, &CiStatus::Failing), None);
+        assert_eq!(ci_flip(None, true, &CiStatus::Failing), Some(false));
+        assert_eq!(ci_flip(None, true, &CiStatus::Passing), None);
+    }
+
+    #[test]
+    fn test_parse_gh_pr_output() {
+        let pr = parse_gh_pr_output(
+            r#"{"number":42,"state":"OPEN","isDraft":true,"reviewDecision":"APPROVED",
+            "url":"https://github.com/acme/api/pull/42","statusCheckRollup":[
+              {"__typename":"CheckRun","status":"COMPLETED","conclusion":"SUCCESS"},
+              {"__typename":"StatusContext","state":"PENDING"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(pr.number, 42);
+        assert_eq!(pr.checks, CiStatus::Running);
+        assert_eq!(pr.badge(), "#42✓");
+        assert_eq!(pr.summary(), "#42 draft, approved, checks running");
+
+        let failing = r#"{"number":7,"state":"OPEN","reviewDecision":"CHANGES_REQUESTED",
+            "statusCheckRollup":[{"status":"IN_PROGRESS","conclusion":""},
+            {"status":"COMPLETED","conclusion":"FAILURE"}]}"#;
+        let pr = parse_gh_pr_output(failing).unwrap();
+        assert_eq!(pr.checks, CiStatus::Failing);
+        assert_eq!(pr.badge(), "#7✗");
+
+        assert!(parse_gh_pr_output(r#"{"number":3,"state":"MERGED"}"#).is_none());
+        assert!(parse_gh_pr_output("not json").is_none());
+    }
+
+    pub(crate) fn make_local(id: &str, parent: Option<&str>) -> SessionEntry {
+        let mut session = Session::fixture(id, "/tmp/proj");
+        session.started_at = chrono::Utc::now();
+        session.last_activity = chrono::Utc::now();
+        session.parent_id = parent.map(String::from);
+        SessionEntry::Local(session)
+    }
+
+    #[test]
+    fn test_list_history_steps_back_and_forward() {
+        let local = |id: &str, project: &str| match make_local(id, None) {
+            SessionEntry::Local(mut s) => {
+                s.project_name = project.into();
+                s
+            }
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(vec![
+            local("a", "webshop"),
+            local("b", "api"),
+            local("c", "webhooks"),
+        ]);
+        assert!(!app.list_history_step(false));
+
+        app.set_selected(2);
+        let selected = app.selected_session().unwrap().id().to_string();
+        app.set_view_mode(ViewMode::Filter);
+        "web".chars().for_each(|c| app.filter_push(c));
+        app.set_view_mode(ViewMode::List);
+        app.cycle_sort();
+        let sort = app.sort_label().to_string();
+
+        assert!(app.list_history_step(false));
+        assert_eq!(app.filter_query(), "web");
+        assert!(app.list_history_step(false));
+        assert_eq!(app.filter_query(), "");
+        assert_eq!(app.selected_session().unwrap().id(), selected);
+        assert!(!app.list_history_step(false));
+
+        assert!(app.list_history_step(true));
+        assert!(app.list_history_step(true));
+        assert_eq!(app.sort_label(), sort);
+        assert_eq!(app.filtered_sessions().len(), 2);
+        assert!(!app.list_history_step(true));
+    }
+
+    #[test]
+    fn test_hidden_and_excluded_sessions() {
+        let local = |id: &str, project: &str| match make_local(id, None) {
+            SessionEntry::Local(mut s) => {
+                s.project_name = project.into();
+                s
+            }
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(Vec::new());
+        app.sessions_config.exclude = vec!["tmp-*".into()];
+        app.set_local_sessions(
+            vec![
+                local("a", "webshop"),
+                local("b", "tmp-1"),
+                local("c", "api"),
+            ],
+            false,
+        );
+        assert_eq!(app.filtered_sessions().len(), 2);
+        assert_eq!(app.hidden_count(), 1);
+
+        app.set_selected(app.filtered.len() - 1);
+        let id = app.selected_session().unwrap().id().to_string();
+        app.toggle_hide_selected();
+        assert_eq!(app.filtered_sessions().len(), 1);
+        assert_eq!(app.hidden_count(), 2);
+
+        app.toggle_show_hidden();
+        assert_eq!(app.filtered_sessions().len(), 3);
+        assert_eq!(app.hidden_count(), 2);
+        let pos = app
+            .filtered_sessions()
+            .iter()
+            .position(|e| e.id() == id)
+            .unwrap();
+        app.set_selected(pos);
+        app.toggle_hide_selected();
+        app.toggle_show_hidden();
+        assert_eq!(app.filtered_sessions().len(), 2);
+        assert_eq!(app.hidden_count(), 1);
+    }
+
+    #[test]
+    fn test_stale_sessions_are_left_out() {
+        let local = |id: &str, days_ago: i64| match make_local(id, None) {
+            SessionEntry::Local(mut s) => {
+                s.last_activity = chrono::Utc::now() - chrono::Duration::days(days_ago);
+                s
+            }
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(vec![local("a", 1), local("b", 20), local("c", 40)]);
+        assert_eq!(app.filtered_sessions().len(), 1);
+        assert_eq!((app.hidden_count(), app.stale_count()), (0, 2));
+
+        app.toggle_show_hidden();
+        assert_eq!(app.filtered_sessions().len(), 3);
+        assert_eq!(app.stale_count(), 2);
+
+        app.sessions_config.hide_after_days = 30;
+        app.toggle_show_hidden();
+        assert_eq!(app.filtered_sessions().len(), 2);
+        assert_eq!(app.stale_count(), 1);
+    }
+
+    #[test]
+    fn test_worktree_clashes() {
+        let repo = tempfile::tempdir().unwrap();
+        std::fs::create_dir(repo.path().join(".git")).unwrap();
+        let local = |id: &str, sub: &str, branch: &str, status| match make_local(id, None) {
+            SessionEntry::Local(mut s) => {
+                s.cwd = repo.path().join(sub);
+                s.project_name = id.to_string();
+                s.git_branch = Some(branch.to_string());
+                s.status = status;
+                s
+            }
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let app = App::with_sessions(vec![
+            local("a", "", "main", SessionStatus::Active),
+            local("b", "src", "feature", SessionStatus::Idle),
+            local("c", "", "main", SessionStatus::Dead),
+        ]);
+        let clash = app.worktree_clash("a").unwrap();
+        assert_eq!(clash.others, vec!["b".to_string()]);
+        assert!(!clash.same_branch);
+        assert_eq!(app.worktree_clash("b").unwrap().badge(), "!branch clash");
+        assert!(app.worktree_clash("c").is_none());
+
+        let app = App::with_sessions(vec![
+            local("a", "", "main", SessionStatus::Active),
+            local("b", "", "main", SessionStatus::Active),
+        ]);
+        assert!(app.worktree_clash("a").unwrap().same_branch);
+    }
+
+    #[test]
+    fn test_discovery_fills_in_progressively() {
+        let local = |id| match make_local(id, None) {
+            SessionEntry::Local(s) => s,
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(Vec::new());
+        let (tx, rx) = mpsc::channel();
+        app.discovery_rx = Some(rx);
+        assert!(!app.drain_discovery());
+
+        tx.send(DiscoveryMsg::Partial(vec![local("a")])).unwrap();
+        assert!(app.drain_discovery());
+        assert!(app.is_discovering());
+        assert_eq!(app.filtered_sessions().len(), 1);
+
+        tx.send(DiscoveryMsg::Partial(vec![local("a"), local("b")]))
+            .unwrap();
+        tx.send(DiscoveryMsg::Done(
+            vec![local("a"), local("b"), local("c")],
+            Box::new(SessionDiscovery::new()),
+        ))
+        .unwrap();
+        assert!(app.drain_discovery());
+        assert!(!app.is_discovering());
+        assert_eq!(app.filtered_sessions().len(), 3);
+    }
+
+    #[test]
+    fn test_imported_sessions_are_listed_read_only() {
+        let local = |id| match make_local(id, None) {
+            SessionEntry::Local(s) => s,
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(Vec::new());
+        app.imported = vec![
+            Session {
+                imported: true,
+                ..local("a")
+            },
+            Session {
+                imported: true,
+                ..local("b")
+            },
+        ];
+        let (tx, rx) = mpsc::channel();
+        app.discovery_rx = Some(rx);
+        tx.send(DiscoveryMsg::Done(
+            vec![local("a")],
+            Box::new(SessionDiscovery::new()),
+        ))
+        .unwrap();
+        assert!(app.drain_discovery());
+
+        // The local copy of "a" wins over the imported one.
+        let labels: Vec<(&str, &str)> = app
+            .filtered_sessions()
+            .iter()
+            .map(|e| (e.id(), e.status_label()))
+            .collect();
+        assert_eq!(labels.len(), 2);
+        assert!(labels.contains(&("a", "Dead")));
+        assert!(labels.contains(&("b", "Imported")));
+
+        let b = app
+            .filtered_sessions()
+            .iter()
+            .position(|e| e.id() == "b")
+            .unwrap();
+        app.set_selected(b);
+        assert!(app.open_dead_session_menu());
+        assert_eq!(*app.view_mode(), ViewMode::List);
+    }
+
+    #[test]
+    fn test_deep_link_waits_for_its_session() {
+        let local = |id| match make_local(id, None) {
+            SessionEntry::Local(s) => s,
+            SessionEntry::Remote(_) => unreachable!(),
+        };
+        let mut app = App::with_sessions(Vec::new());
+        let (tx, rx) = mpsc::channel();
+        app.discovery_rx = Some(rx);
+        app.open_link_later(DeepLink::Attach("abc".to_string()));
+        assert_eq!(app.take_link(), None);
+
+        tx.send(DiscoveryMsg::Partial(vec![local("xyz"), local("abc-123")]))
+            .unwrap();
+        app.drain_discovery();
+        app.set_view_mode(ViewMode::Filter);
+        "xyz".chars().for_each(|c| app.filter_push(c));
+        app.set_view_mode(ViewMode::List);
+        assert_eq!(app.take_link(), Some(DeepLink::Attach("abc".to_string())));
+        assert!(app.select_id("abc"));
+        assert_eq!(app.selected_session().unwrap().id(), "abc-123");
+        assert_eq!(app.filter_query(), "");
+
+        tx.send(DiscoveryMsg::Done(
+            vec![local("abc-123")],
+            Box::new(SessionDiscovery::new()),
+        ))
+        .unwrap();
+        app.drain_discovery();
+        app.open_link_later(DeepLink::Session("gone".to_string()));
+        assert_eq!(app.take_link(), None);
+        assert!(!app.select_id("gone"));
+    }
+
+    #[test]
+    fn test_group_by_parent_nests_resumed_sessions() {
+        let entries = vec![
+            make_local("child", Some("root")),
+            make_local("other", None),
+            make_local("grandchild", Some("child")),
+            make_local("root", None),
+            make_local("orphan", Some("missing")),
+        ];
+        let (grouped, depths) = group_by_parent(entries);
+        let ids: Vec<&str> = grouped.iter().map(|e| e.id()).collect();
+        assert_eq!(ids, vec!["other", "root", "child", "grandchild", "orphan"]);
+        assert_eq!(depths.get("child"), Some(&1));
+        assert_eq!(depths.get("grandchild"), Some(&2));
+        assert_eq!(depths.get("orphan"), None);
+    }
+
     #[test]
     fn test_parse_github_owner_repo() {
         assert_eq!(
@@ -1908,4 +6555,112 @@ mod tests {
         );
         assert_eq!(parse_github_owner_repo("not-a-url"), None);
     }
+
+    #[test]
+    fn test_timeline_bookmark_navigation() {
+        use crate::session::timeline::TimelineKind;
+
+        let event = |index| TimelineEvent {
+            index,
+            timestamp: None,
+            kind: TimelineKind::UserPrompt,
+            text: format!("line {}", index),
+        };
+        let mark = |message_index| Bookmark {
+            message_index,
+            label: String::new(),
+            created_at: String::new(),
+        };
+        let mut state = LocalTimelineState {
+            session_id: "s".into(),
+            title: String::new(),
+            loading: false,
+            events: (0..10).map(|i| event(i * 2)).collect(),
+            bookmarks: vec![mark(6), mark(14)],
+            bookmarks_only: false,
+            cursor: 0,
+            scroll: 0,
+            visible_height: std::cell::Cell::new(4),
+            return_to: ViewMode::Detail,
+        };
+
+        state.jump_to_bookmark(true);
+        assert_eq!(state.cursor, 3);
+        state.jump_to_bookmark(true);
+        assert_eq!(state.cursor, 7);
+        assert_eq!(state.scroll, 4);
+        state.jump_to_bookmark(true);
+        assert_eq!(state.cursor, 3, "wraps to the first bookmark");
+        state.jump_to_bookmark(false);
+        assert_eq!(state.cursor, 7, "wraps backwards");
+
+        state.toggle_bookmarks_only();
+        state.move_cursor(1);
+        state.open_selected_bookmark();
+        assert!(!state.bookmarks_only);
+        assert_eq!(state.selected_event().map(|e| e.index), Some(14));
+    }
+
+    #[test]
+    fn test_recently_exited_viewer_scroll_is_clamped() {
+        let mut state = RecentlyExitedState {
+            cursor: 0,
+            viewing: None,
+            from_bottom: 0,
+            visible_height: std::cell::Cell::new(10),
+        };
+        state.move_by(5, 3);
+        assert_eq!(state.cursor, 2);
+        state.move_by(isize::MIN, 3);
+        assert_eq!(state.cursor, 0);
+
+        state.viewing = Some(0);
+        state.move_by(-4, 25);
+        assert_eq!(state.from_bottom, 4);
+        state.move_by(isize::MIN, 25);
+        assert_eq!(state.from_bottom, 15);
+        state.move_by(isize::MAX, 25);
+        assert_eq!(state.from_bottom, 0);
+    }
+
+    #[test]
+    fn test_dnd_minutes_too_large_last_until_turned_off() {
+        let mut app = App::with_sessions(Vec::new());
+        app.set_dnd_minutes(u64::MAX);
+        assert!(app.dnd_remaining().is_some());
+        app.toggle_dnd();
+        assert!(app.dnd_remaining().is_none());
+    }
+
+    #[test]
+    fn test_dead_session_menu_compares_branches() {
+        let mut session = Session::fixture("abc", "/tmp/proj");
+        session.git_branch = Some("main".to_string());
+        let mut menu = NewSessionMenuState::for_dead_session(session);
+        assert_eq!(menu.hint, None);
+        menu.set_cwd_branch(Some("feat/x"));
+        assert_eq!(
+            menu.hint.as_deref(),
+            Some("cwd is on feat/x (session was main)")
+        );
+        menu.set_cwd_branch(Some("main"));
+        assert_eq!(menu.hint.as_deref(), Some("on branch main"));
+        menu.set_cwd_branch(None);
+        assert_eq!(menu.hint, None);
+    }
+
+    #[test]
+    fn test_export_prompt_toggles_extension() {
+        let mut prompt = ExportPromptState {
+            format: ExportFormat::Markdown,
+            path: "/tmp/out.md".to_string(),
+        };
+        prompt.toggle_format();
+        assert_eq!(prompt.format, ExportFormat::Html);
+        assert_eq!(prompt.path, "/tmp/out.html");
+        prompt.path = "/tmp/notes.txt".to_string();
+        prompt.toggle_format();
+        assert_eq!(prompt.format, ExportFormat::Markdown);
+        assert_eq!(prompt.path, "/tmp/notes.txt");
+    }
 }
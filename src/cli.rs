@@ -0,0 +1,219 @@
+//! Command-line arguments. Without a subcommand c9s starts the TUI.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum, ValueHint};
+
+use crate::session::export::ExportFormat;
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "c9s",
+    version,
+    about = "c9s - Claude Code Session Manager",
+    after_help = "Without a command c9s launches the TUI dashboard; piped or from cron it \
+                  prints a plain-text status summary. More with `c9s help keys`, \
+                  `c9s help config` and `c9s help tervezo`.",
+    disable_version_flag = true,
+    disable_help_subcommand = true
+)]
+pub struct Cli {
+    /// Print version
+    #[arg(short = 'v', long, action = clap::ArgAction::Version)]
+    #[allow(dead_code)]
+    version: Option<bool>,
+    /// Launch with bells silenced, for MINUTES (default 30)
+    #[arg(long, value_name = "MINUTES", num_args = 0..=1)]
+    pub dnd: Option<Option<u64>>,
+    /// Open on the dashboard, for a second screen
+    #[arg(long)]
+    pub dashboard: bool,
+    /// Take over from a running c9s; its terminals close
+    #[arg(long)]
+    pub force: bool,
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Show version
+    Version,
+    /// Open a c9s:// link, in the running c9s if there is one
+    Open {
+        /// c9s://attach/<id>, c9s://session/<id> or c9s://tervezo/<id>
+        #[arg(value_name = "URL")]
+        url: String,
+    },
+    /// List sessions, newest first, without starting the TUI
+    List {
+        /// A table, JSON for scripts, or Alfred script filter JSON for launchers
+        #[arg(short, long, value_enum, default_value_t = ListFormat::Text)]
+        format: ListFormat,
+        /// Same as --format json
+        #[arg(long, conflicts_with = "format")]
+        json: bool,
+    },
+    /// Export a session transcript (default: Markdown to stdout)
+    ExportTranscript {
+        /// Session id or unique id prefix
+        #[arg(value_name = SESSION)]
+        id: String,
+        /// Markdown or HTML
+        #[arg(short, long, value_enum, default_value_t = TranscriptFormat::Md)]
+        format: TranscriptFormat,
+        /// Write to a file instead of stdout
+        #[arg(short, long, value_hint = ValueHint::FilePath)]
+        output: Option<PathBuf>,
+        /// Copy to the clipboard instead of stdout
+        #[arg(short, long, conflicts_with = "output")]
+        clipboard: bool,
+    },
+    /// Bundle a session for a colleague: summary, todos, transcript and diff
+    Handoff {
+        /// Session id or unique id prefix
+        #[arg(value_name = SESSION)]
+        id: String,
+        /// Directory to write (default: ~/.c9s/handoffs/<project>-<id>)
+        #[arg(short, long, value_hint = ValueHint::DirPath)]
+        output: Option<PathBuf>,
+        /// Pack the directory into <dir>.tar.gz
+        #[arg(long)]
+        tar: bool,
+    },
+    /// Import a handoff bundle or transcript from another machine
+    Import {
+        /// Bundle directory, <bundle>.tar.gz or a .jsonl transcript
+        #[arg(value_name = "BUNDLE", value_hint = ValueHint::AnyPath)]
+        bundle: PathBuf,
+    },
+    /// Serve a JSON API over the sessions for dashboards and launchers
+    Serve {
+        /// host:port to listen on (default: [api] bind, 127.0.0.1:7682)
+        #[arg(short, long, value_name = "ADDR")]
+        bind: Option<String>,
+    },
+    /// Full-text search across all transcripts
+    Search {
+        #[arg(required = true, value_name = "QUERY")]
+        words: Vec<String>,
+        /// Most matches to print
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+    /// Time tracked per project and day (default: last 30 days as CSV)
+    ExportTime {
+        /// Days back from today
+        #[arg(short, long, default_value_t = 30)]
+        days: i64,
+        /// CSV or JSON
+        #[arg(short, long, value_enum, default_value_t = TableFormat::Csv)]
+        format: TableFormat,
+    },
+    /// Rated sessions per model: failure rate, cost per shipped task, median length
+    ExportModels {
+        /// CSV or JSON
+        #[arg(short, long, value_enum, default_value_t = TableFormat::Csv)]
+        format: TableFormat,
+    },
+    /// Print a message catalog to translate
+    LocaleTemplate,
+    /// Key bindings, configuration, Tervezo, or a command's options
+    Help {
+        /// keys, config, tervezo or a command
+        #[arg(value_name = TOPIC)]
+        topic: Option<String>,
+    },
+    /// Print the man page (roff): `c9s man > ~/.local/share/man/man1/c9s.1`
+    Man,
+    /// Print a bash, zsh or fish completion script
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+    /// Session ids and projects, one per line, for completion scripts
+    #[command(hide = true)]
+    CompleteSessions,
+}
+
+/// The value name completion scripts complete with session ids.
+pub const SESSION: &str = "SESSION";
+/// The value name completion scripts complete with help topics.
+pub const TOPIC: &str = "TOPIC";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TranscriptFormat {
+    #[value(alias = "markdown")]
+    Md,
+    #[value(alias = "htm")]
+    Html,
+}
+
+impl From<TranscriptFormat> for ExportFormat {
+    fn from(format: TranscriptFormat) -> Self {
+        match format {
+            TranscriptFormat::Md => ExportFormat::Markdown,
+            TranscriptFormat::Html => ExportFormat::Html,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum TableFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Text,
+    /// Every field of each session, plus cost_usd
+    Json,
+    /// Alfred script filter JSON
+    Alfred,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+
+    #[test]
+    fn test_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse() {
+        let cli = Cli::try_parse_from(["c9s", "--dnd"]).unwrap();
+        assert_eq!(cli.dnd, Some(None));
+        assert!(cli.command.is_none());
+        let cli = Cli::try_parse_from(["c9s", "--dnd", "45", "--dashboard"]).unwrap();
+        assert_eq!(cli.dnd, Some(Some(45)));
+        assert!(cli.dashboard);
+        assert!(!cli.force);
+        assert!(Cli::try_parse_from(["c9s", "--force"]).unwrap().force);
+
+        let cli =
+            Cli::try_parse_from(["c9s", "export-transcript", "ab12", "-f", "markdown"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::ExportTranscript {
+                format: TranscriptFormat::Md,
+                ..
+            })
+        ));
+        assert!(Cli::try_parse_from(["c9s", "export-transcript", "x", "-o", "a", "-c"]).is_err());
+        assert!(Cli::try_parse_from(["c9s", "search"]).is_err());
+        let cli = Cli::try_parse_from(["c9s", "help", "keys"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::Help { topic: Some(t) }) if t == "keys"));
+        assert!(Cli::try_parse_from(["c9s", "frobnicate"]).is_err());
+    }
+}
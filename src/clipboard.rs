@@ -0,0 +1,77 @@
+use anyhow::{bail, Result};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard helpers tried in order; the first one that runs wins.
+const CLIPBOARD_COMMANDS: &[(&str, &[&str])] = &[
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+pub fn copy(text: &str) -> Result<()> {
+    for (cmd, args) in CLIPBOARD_COMMANDS {
+        let Ok(mut child) = Command::new(cmd)
+            .args(*args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            continue;
+        };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    bail!("no clipboard tool found (pbcopy, wl-copy, xclip or xsel)")
+}
+
+/// Puts a clipboard write from an embedded terminal (OSC 52, base64) on
+/// the clipboard. Over ssh, or without a clipboard tool, it is passed on
+/// to the host terminal to do it, re-encoded from the decoded bytes so
+/// nothing else the application wrote gets through. Payloads that don't
+/// decode are dropped.
+pub fn copy_osc52(data: &str, out: &mut impl Write) -> Result<()> {
+    let Some(bytes) = crate::terminal::decode_osc52(data) else {
+        bail!("dropped an OSC 52 payload that is not base64");
+    };
+    let text = std::str::from_utf8(&bytes).ok();
+    copy_or_forward(text, &STANDARD.encode(&bytes), out)
+}
+
+/// Puts `text` on the clipboard the way `copy_osc52` does, so it also
+/// works over ssh and without a clipboard tool.
+pub fn copy_or_osc52(text: &str, out: &mut impl Write) -> Result<()> {
+    copy_or_forward(Some(text), &STANDARD.encode(text), out)
+}
+
+fn copy_or_forward(text: Option<&str>, data: &str, out: &mut impl Write) -> Result<()> {
+    let remote = std::env::var_os("SSH_TTY").is_some();
+    if let Some(text) = text.filter(|_| !remote) {
+        if copy(text).is_ok() {
+            return Ok(());
+        }
+    }
+    write!(out, "\x1b]52;c;{}\x07", data)?;
+    out.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_osc52_is_not_forwarded() {
+        let mut out = Vec::new();
+        assert!(copy_osc52("aGk\x07\x1b]0;pwn", &mut out).is_err());
+        assert!(out.is_empty());
+    }
+}
@@ -0,0 +1,360 @@
+//! `c9s completions <shell>`: completion scripts written from the clap
+//! definition in `cli`, so new subcommands and flags complete without
+//! touching this file. Session ids come from `c9s complete-sessions` when
+//! the script runs.
+
+use clap::builder::PossibleValue;
+use clap::{Arg, ArgAction, Command, CommandFactory, ValueHint};
+
+use crate::cli::{Cli, Shell, SESSION, TOPIC};
+use crate::manual::Topic;
+
+pub fn script(shell: Shell) -> String {
+    let cmd = Cli::command();
+    match shell {
+        Shell::Bash => bash(&cmd),
+        Shell::Zsh => zsh(&cmd),
+        Shell::Fish => fish(&cmd),
+    }
+}
+
+/// How an argument's values complete.
+enum Values {
+    None,
+    Sessions,
+    Files,
+    Choices(Vec<String>),
+}
+
+fn values(arg: &Arg) -> Values {
+    if !arg.get_action().takes_values() {
+        return Values::None;
+    }
+    let named = |name: &str| {
+        arg.get_value_names()
+            .is_some_and(|names| names.iter().any(|n| n.as_str() == name))
+    };
+    if named(SESSION) {
+        return Values::Sessions;
+    }
+    if named(TOPIC) {
+        return Values::Choices(Topic::ALL.iter().map(|t| t.name().to_string()).collect());
+    }
+    if matches!(
+        arg.get_value_hint(),
+        ValueHint::FilePath | ValueHint::AnyPath | ValueHint::DirPath
+    ) {
+        return Values::Files;
+    }
+    let choices: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(PossibleValue::get_name)
+        .map(str::to_string)
+        .collect();
+    if choices.is_empty() {
+        Values::None
+    } else {
+        Values::Choices(choices)
+    }
+}
+
+fn options(cmd: &Command) -> impl Iterator<Item = &Arg> {
+    cmd.get_arguments()
+        .filter(|a| !a.is_positional() && !a.is_hide_set())
+}
+
+fn positionals(cmd: &Command) -> impl Iterator<Item = &Arg> {
+    cmd.get_positionals().filter(|a| !a.is_hide_set())
+}
+
+fn subcommands(cmd: &Command) -> impl Iterator<Item = &Command> {
+    cmd.get_subcommands().filter(|c| !c.is_hide_set())
+}
+
+fn help(item: Option<&clap::builder::StyledStr>) -> String {
+    item.map(|h| h.to_string())
+        .unwrap_or_default()
+        .lines()
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+fn flag_names(arg: &Arg) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("-{}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("--{}", long));
+    }
+    names
+}
+
+fn bash(cmd: &Command) -> String {
+    let mut out = String::from(
+        "# bash completion for c9s\n\
+         _c9s() {\n    \
+         local cur prev\n    \
+         cur=\"${COMP_WORDS[COMP_CWORD]}\"\n    \
+         prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n    \
+         if [[ $COMP_CWORD -eq 1 ]]; then\n",
+    );
+    let top: Vec<String> = subcommands(cmd)
+        .map(|c| c.get_name().to_string())
+        .chain(options(cmd).flat_map(flag_names))
+        .collect();
+    out.push_str(&format!(
+        "        COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n        return\n    fi\n",
+        top.join(" ")
+    ));
+    out.push_str("    case \"${COMP_WORDS[1]}\" in\n");
+    for sub in subcommands(cmd) {
+        out.push_str(&format!("        {})\n", sub.get_name()));
+        let with_values: Vec<&Arg> = options(sub)
+            .filter(|a| !matches!(values(a), Values::None))
+            .collect();
+        if !with_values.is_empty() {
+            out.push_str("            case \"$prev\" in\n");
+            for arg in with_values {
+                out.push_str(&format!(
+                    "                {}) {}; return ;;\n",
+                    flag_names(arg).join("|"),
+                    bash_reply(&values(arg))
+                ));
+            }
+            out.push_str("            esac\n");
+        }
+        let flags: Vec<String> = options(sub).flat_map(flag_names).collect();
+        let positional = positionals(sub)
+            .map(values)
+            .find(|v| !matches!(v, Values::None));
+        match positional {
+            Some(v) => out.push_str(&format!(
+                "            if [[ $cur == -* ]]; then\n                \
+                 COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n            \
+                 else\n                {}\n            fi\n",
+                flags.join(" "),
+                bash_reply(&v)
+            )),
+            None => out.push_str(&format!(
+                "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))\n",
+                flags.join(" ")
+            )),
+        }
+        out.push_str("            ;;\n");
+    }
+    out.push_str("    esac\n}\ncomplete -F _c9s c9s\n");
+    out
+}
+
+fn bash_reply(values: &Values) -> String {
+    match values {
+        Values::Sessions => {
+            "COMPREPLY=($(compgen -W \"$(c9s complete-sessions 2>/dev/null | cut -f1)\" -- \"$cur\"))"
+                .to_string()
+        }
+        Values::Files => "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string(),
+        Values::Choices(choices) => format!(
+            "COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            choices.join(" ")
+        ),
+        Values::None => "COMPREPLY=()".to_string(),
+    }
+}
+
+fn zsh(cmd: &Command) -> String {
+    let mut out = String::from(
+        "#compdef c9s\n\n\
+         _c9s_sessions() {\n    \
+         local -a sessions\n    \
+         sessions=(${(f)\"$(c9s complete-sessions 2>/dev/null | sed 's/:/\\\\:/g; s/\\t/:/')\"})\n    \
+         _describe 'session' sessions\n}\n\n\
+         _c9s() {\n    \
+         local state line\n    \
+         local -a commands\n    \
+         commands=(\n",
+    );
+    for sub in subcommands(cmd) {
+        out.push_str(&format!(
+            "        '{}:{}'\n",
+            sub.get_name(),
+            zsh_escape(&help(sub.get_about())).replace(':', "\\:")
+        ));
+    }
+    out.push_str("    )\n    _arguments -C \\\n");
+    for arg in options(cmd) {
+        out.push_str(&format!("        {} \\\n", zsh_spec(arg)));
+    }
+    out.push_str(
+        "        '1: :->command' \\\n        \
+         '*:: :->args'\n    \
+         case $state in\n        \
+         command) _describe 'command' commands ;;\n        \
+         args)\n            \
+         case $line[1] in\n",
+    );
+    for sub in subcommands(cmd) {
+        let specs: Vec<String> = options(sub)
+            .map(zsh_spec)
+            .chain(positionals(sub).map(|arg| {
+                let pos = if arg.get_num_args().is_some_and(|n| n.max_values() > 1) {
+                    "*".to_string()
+                } else {
+                    arg.get_index().unwrap_or(1).to_string()
+                };
+                format!(
+                    "'{}:{}:{}'",
+                    pos,
+                    arg.get_id().as_str(),
+                    zsh_action(&values(arg))
+                )
+            }))
+            .collect();
+        if specs.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "                {}) _arguments {} ;;\n",
+            sub.get_name(),
+            specs.join(" ")
+        ));
+    }
+    out.push_str("            esac ;;\n    esac\n}\n\n_c9s \"$@\"\n");
+    out
+}
+
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+}
+
+fn zsh_spec(arg: &Arg) -> String {
+    let names = flag_names(arg);
+    let desc = zsh_escape(&help(arg.get_help()));
+    let value = match values(arg) {
+        _ if !arg.get_action().takes_values() => String::new(),
+        v => format!(
+            ":{}:{}",
+            arg.get_id().as_str().to_lowercase(),
+            zsh_action(&v)
+        ),
+    };
+    // An optional value is written `--dnd=-` and only taken when attached.
+    let optional = arg
+        .get_num_args()
+        .is_some_and(|n| n.min_values() == 0 && n.max_values() == 1);
+    match names.as_slice() {
+        [one] if optional => format!("'{}=-[{}]{}'", one, desc, value),
+        [one] => format!("'{}[{}]{}'", one, desc, value),
+        _ => format!(
+            "'({})'{{{}}}'[{}]{}'",
+            names.join(" "),
+            names.join(","),
+            desc,
+            value
+        ),
+    }
+}
+
+fn zsh_action(values: &Values) -> String {
+    match values {
+        Values::Sessions => "_c9s_sessions".to_string(),
+        Values::Files => "_files".to_string(),
+        Values::Choices(choices) => format!("({})", choices.join(" ")),
+        Values::None => " ".to_string(),
+    }
+}
+
+fn fish(cmd: &Command) -> String {
+    let mut out = String::from("# fish completion for c9s\ncomplete -c c9s -f\n");
+    for sub in subcommands(cmd) {
+        out.push_str(&format!(
+            "complete -c c9s -n __fish_use_subcommand -a {} -d '{}'\n",
+            sub.get_name(),
+            fish_escape(&help(sub.get_about()))
+        ));
+    }
+    for arg in options(cmd) {
+        out.push_str(&fish_option("__fish_use_subcommand", arg));
+    }
+    for sub in subcommands(cmd) {
+        let cond = format!("'__fish_seen_subcommand_from {}'", sub.get_name());
+        for arg in options(sub) {
+            out.push_str(&fish_option(&cond, arg));
+        }
+        for arg in positionals(sub) {
+            if let Some(source) = fish_source(&values(arg)) {
+                out.push_str(&format!("complete -c c9s -n {} {}\n", cond, source));
+            }
+        }
+    }
+    out
+}
+
+fn fish_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+fn fish_option(cond: &str, arg: &Arg) -> String {
+    let mut line = format!("complete -c c9s -n {}", cond);
+    if let Some(short) = arg.get_short() {
+        line.push_str(&format!(" -s {}", short));
+    }
+    if let Some(long) = arg.get_long() {
+        line.push_str(&format!(" -l {}", long));
+    }
+    if arg.get_action().takes_values() && !matches!(arg.get_action(), ArgAction::Count) {
+        line.push_str(" -r");
+        if let Some(source) = fish_source(&values(arg)) {
+            line.push(' ');
+            line.push_str(&source);
+        }
+    }
+    let desc = help(arg.get_help());
+    if !desc.is_empty() {
+        line.push_str(&format!(" -d '{}'", fish_escape(&desc)));
+    }
+    line.push('\n');
+    line
+}
+
+fn fish_source(values: &Values) -> Option<String> {
+    match values {
+        Values::Sessions => Some("-a '(c9s complete-sessions 2>/dev/null)'".to_string()),
+        Values::Files => Some("-F".to_string()),
+        Values::Choices(choices) => Some(format!("-a '{}'", choices.join(" "))),
+        Values::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scripts_cover_subcommands_and_values() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let out = script(shell);
+            assert!(out.contains("export-transcript"), "{:?}", shell);
+            assert!(out.contains("complete-sessions"), "{:?}", shell);
+            assert!(out.contains("md html"), "{:?}", shell);
+            assert!(out.contains("csv json"), "{:?}", shell);
+        }
+        assert!(script(Shell::Bash).ends_with("complete -F _c9s c9s\n"));
+        assert!(script(Shell::Zsh).starts_with("#compdef c9s\n"));
+        assert!(script(Shell::Zsh).contains("'--dnd=-["));
+        assert!(script(Shell::Fish).contains(
+            "complete -c c9s -n '__fish_seen_subcommand_from export-transcript' -s o -l output -r -F"
+        ));
+    }
+
+    #[test]
+    fn test_hidden_subcommand_is_not_offered() {
+        let fish = script(Shell::Fish);
+        assert!(!fish.contains("-a complete-sessions"));
+    }
+}
@@ -0,0 +1,1023 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use crate::session::Session;
+
+/// General c9s settings from `~/.c9s/config.toml`. Integration-specific
+/// sections (e.g. `[tervezo]`) are parsed by their own modules.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+    pub sessions: SessionsConfig,
+    pub updates: UpdatesConfig,
+    pub terminal: TerminalConfig,
+    pub terminal_log: TerminalLogConfig,
+    pub format: FormatConfig,
+    pub currency: CurrencyConfig,
+    pub ui: UiConfig,
+    pub plugins: PluginsConfig,
+    pub ci: CiConfig,
+    pub tickets: TicketsConfig,
+    pub time_tracking: TimeTrackingConfig,
+    pub focus: FocusConfig,
+    pub outcomes: OutcomesConfig,
+    pub usage: UsageConfig,
+    pub dashboard: DashboardConfig,
+    pub watchdog: WatchdogConfig,
+    pub mirror: MirrorConfig,
+    pub api: ApiConfig,
+    /// `[[actions]]`: commands offered in a session's action menu.
+    pub actions: Vec<UserAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SessionsConfig {
+    /// List every session in a directory instead of only the most recent.
+    pub show_all_per_cwd: bool,
+    /// Sessions to leave out of the list: glob patterns (`*`, `?`) on the
+    /// project name, or on the working directory when they contain a `/`
+    /// (`~/` for the home directory).
+    pub exclude: Vec<String>,
+    /// Leave out sessions dead for longer than this many days; 0 keeps
+    /// them all.
+    pub hide_after_days: u64,
+}
+
+impl Default for SessionsConfig {
+    fn default() -> Self {
+        Self {
+            show_all_per_cwd: false,
+            exclude: Vec::new(),
+            hide_after_days: 14,
+        }
+    }
+}
+
+impl SessionsConfig {
+    /// Whether `session` has been dead for longer than `hide_after_days`
+    /// at `now`. Imported sessions were asked for and never go stale.
+    pub fn is_stale(&self, session: &Session, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.hide_after_days > 0
+            && !session.imported
+            && session.status == crate::session::SessionStatus::Dead
+            && now - session.last_activity > chrono::Duration::days(self.hide_after_days as i64)
+    }
+
+    /// Whether one of the `exclude` patterns matches `session`.
+    pub fn excludes(&self, session: &Session) -> bool {
+        let cwd = session.cwd.to_string_lossy();
+        self.exclude.iter().any(|pattern| {
+            if !pattern.contains('/') {
+                return glob_match(pattern, &session.project_name);
+            }
+            match (pattern.strip_prefix("~/"), dirs::home_dir()) {
+                (Some(rest), Some(home)) => glob_match(&home.join(rest).to_string_lossy(), &cwd),
+                _ => glob_match(pattern, &cwd),
+            }
+        })
+    }
+}
+
+/// Shell-style match of the whole of `text`: `*` is any run of characters,
+/// `/` included, and `?` any one character.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0, 0);
+    // Where the last `*` was and how much of the text it has taken.
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        if pi < p.len() && (p[pi] == '?' || p[pi] == t[ti]) {
+            pi += 1;
+            ti += 1;
+        } else if pi < p.len() && p[pi] == '*' {
+            star = Some((pi, ti));
+            pi += 1;
+        } else if let Some((sp, st)) = star {
+            pi = sp + 1;
+            ti = st + 1;
+            star = Some((sp, st + 1));
+        } else {
+            return false;
+        }
+    }
+    p[pi..].iter().all(|&c| c == '*')
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CiConfig {
+    /// Toast and ring the bell when CI on a live session's branch breaks or
+    /// goes green again.
+    pub notify: bool,
+}
+
+impl Default for CiConfig {
+    fn default() -> Self {
+        Self { notify: true }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UpdatesConfig {
+    /// Look up the latest claude CLI release on startup. Off unless the
+    /// user opts in, so c9s makes no outbound request on its own.
+    pub check: bool,
+    /// Registry endpoint returning `{"version": "..."}`.
+    pub latest_url: String,
+}
+
+impl Default for UpdatesConfig {
+    fn default() -> Self {
+        Self {
+            check: false,
+            latest_url: "https://registry.npmjs.org/@anthropic-ai/claude-code/latest".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TerminalConfig {
+    /// Order of the tab bar and of Ctrl+n / Ctrl+p cycling.
+    pub tab_order: TabOrder,
+    /// Shell that claude and the side terminal are started under.
+    pub shell: WrapperShell,
+    /// Extra environment for every spawned terminal, e.g. `ANTHROPIC_MODEL`
+    /// or `HTTPS_PROXY`. An empty value unsets the variable.
+    pub env: BTreeMap<String, String>,
+    /// `TERM` for spawned terminals. Their output is parsed by c9s's own
+    /// xterm-compatible emulator, so the host's `TERM` is not copied.
+    pub term: Option<String>,
+    /// `COLORTERM` for spawned terminals; empty to unset. Copied from the
+    /// host when it advertises truecolor.
+    pub colorterm: Option<String>,
+    /// Cap on redraws caused by terminal output. Key presses still redraw
+    /// immediately.
+    pub max_fps: u32,
+}
+
+impl Default for TerminalConfig {
+    fn default() -> Self {
+        Self {
+            tab_order: TabOrder::default(),
+            shell: WrapperShell::default(),
+            env: BTreeMap::new(),
+            term: None,
+            colorterm: None,
+            max_fps: 30,
+        }
+    }
+}
+
+impl TerminalConfig {
+    /// Minimum time between output-driven redraws.
+    pub fn frame_budget(&self) -> std::time::Duration {
+        std::time::Duration::from_millis(1000 / u64::from(self.max_fps.clamp(1, 240)))
+    }
+
+    pub fn term(&self) -> String {
+        self.term
+            .clone()
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| "xterm-256color".to_string())
+    }
+
+    pub fn colorterm(&self) -> Option<String> {
+        match &self.colorterm {
+            Some(c) if c.is_empty() => None,
+            Some(c) => Some(c.clone()),
+            None => truecolor(std::env::var("COLORTERM").ok().as_deref()),
+        }
+    }
+}
+
+/// The host's `COLORTERM` if it is one of the truecolor values.
+fn truecolor(host: Option<&str>) -> Option<String> {
+    host.filter(|c| matches!(*c, "truecolor" | "24bit"))
+        .map(str::to_string)
+}
+
+/// Written as `"bash"` (the default), `"login"` for the user's `$SHELL` as
+/// an interactive login shell so PATH and aliases from zsh/fish config
+/// apply, `"none"` to exec commands directly, or the name or path of any
+/// other shell.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "String")]
+pub enum WrapperShell {
+    #[default]
+    Bash,
+    Login,
+    None,
+    Other(String),
+}
+
+impl From<String> for WrapperShell {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "bash" => Self::Bash,
+            "login" => Self::Login,
+            "none" => Self::None,
+            _ => Self::Other(s),
+        }
+    }
+}
+
+impl WrapperShell {
+    /// The shell and the flags that go before `-c`, or `None` when
+    /// commands are exec'd directly.
+    pub fn wrapper(&self) -> Option<(String, &'static [&'static str])> {
+        match self {
+            Self::Bash => Some(("bash".to_string(), &[])),
+            Self::Login => Some((user_shell(), &["-l", "-i"])),
+            Self::None => None,
+            Self::Other(shell) => Some((shell.clone(), &[])),
+        }
+    }
+
+    /// The program and arguments for an interactive side terminal.
+    pub fn interactive(&self) -> (String, &'static [&'static str]) {
+        match self {
+            Self::Bash => ("bash".to_string(), &["--login"]),
+            Self::Login | Self::None => (user_shell(), &["-l"]),
+            Self::Other(shell) => (shell.clone(), &["-l"]),
+        }
+    }
+}
+
+fn user_shell() -> String {
+    std::env::var("SHELL")
+        .ok()
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "bash".to_string())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TabOrder {
+    /// The order tabs were opened in.
+    #[default]
+    Attach,
+    /// Most recent terminal output first.
+    Activity,
+    /// Tabs with a pending bell first, otherwise attach order.
+    Bell,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TerminalLogConfig {
+    /// Start tee-ing raw PTY output for every terminal as it is attached.
+    pub enabled: bool,
+    /// Where `<session id>.log` files go; `~/.c9s/terminal-logs` if unset.
+    pub dir: Option<PathBuf>,
+    /// Size at which a log is rotated to `<session id>.log.1`.
+    pub max_mb: u64,
+}
+
+impl Default for TerminalLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            dir: None,
+            max_mb: 10,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MirrorConfig {
+    /// Start the read-only web mirror with c9s instead of with `w`.
+    pub enabled: bool,
+    /// `host:port` to serve it on; `0.0.0.0:<port>` lets others on the
+    /// network watch.
+    pub bind: String,
+}
+
+impl Default for MirrorConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            bind: "127.0.0.1:7681".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ApiConfig {
+    /// `host:port` for `c9s serve`.
+    pub bind: String,
+    /// Bearer token clients send; generated into `~/.c9s/api_token` when
+    /// neither this nor `C9S_API_TOKEN` is set.
+    pub token: Option<String>,
+}
+
+impl Default for ApiConfig {
+    fn default() -> Self {
+        Self {
+            bind: "127.0.0.1:7682".to_string(),
+            token: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FormatConfig {
+    /// Between groups of three digits in costs and counts; "" for none.
+    pub thousands_separator: String,
+    pub decimal_separator: String,
+    /// Symbol costs are shown with.
+    pub currency: String,
+    /// Put the symbol after the amount ("12,50 €") instead of before.
+    pub currency_after: bool,
+    pub timestamps: TimestampStyle,
+    /// 24-hour clock times; false for 12-hour with AM/PM.
+    pub clock_24h: bool,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            thousands_separator: ",".to_string(),
+            decimal_separator: ".".to_string(),
+            currency: "$".to_string(),
+            currency_after: false,
+            timestamps: TimestampStyle::Relative,
+            clock_24h: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimestampStyle {
+    /// "5m ago".
+    #[default]
+    Relative,
+    /// Time of day for today, the date before that.
+    Absolute,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    /// Colors used for statuses, diffs and errors. `P` cycles it at runtime.
+    pub palette: Palette,
+    /// Catalog in `~/.c9s/locales/` to translate the UI with, e.g. "de";
+    /// unset follows `LANG`.
+    pub locale: Option<String>,
+    /// Show CPU and memory columns for live sessions; `M` toggles them.
+    pub resources: bool,
+    /// Show load, free memory and battery in the header.
+    pub host: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            palette: Palette::default(),
+            locale: None,
+            resources: false,
+            host: true,
+        }
+    }
+}
+
+/// A shell command run from the action menu in a new terminal tab, in the
+/// session's working directory.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct UserAction {
+    pub name: String,
+    /// Run with `bash -c`. `{cwd}`, `{branch}`, `{id}` and `{project}` are
+    /// replaced with the session's values, already shell-quoted.
+    pub command: String,
+}
+
+impl UserAction {
+    pub fn expand(&self, session: &Session) -> String {
+        let vars = [
+            ("cwd", session.cwd.to_string_lossy().into_owned()),
+            ("branch", session.git_branch.clone().unwrap_or_default()),
+            ("id", session.id.clone()),
+            ("project", session.project_name.clone()),
+        ];
+        // One pass, so a value holding `{name}` is never expanded again.
+        let mut command = String::new();
+        let mut rest = self.command.as_str();
+        while let Some(open) = rest.find('{') {
+            command.push_str(&rest[..open]);
+            let token = &rest[open + 1..];
+            let var = token.find('}').and_then(|close| {
+                vars.iter()
+                    .find(|(name, _)| *name == &token[..close])
+                    .map(|(_, value)| (close, value))
+            });
+            match var {
+                Some((close, value)) => {
+                    command.push_str(&shell_quote(value));
+                    rest = &token[close + 1..];
+                }
+                None => {
+                    command.push('{');
+                    rest = token;
+                }
+            }
+        }
+        command.push_str(rest);
+        command
+    }
+}
+
+pub(crate) fn shell_quote(s: &str) -> String {
+    if !s.is_empty()
+        && s.chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@+=,".contains(c))
+    {
+        return s.to_string();
+    }
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TimeTrackingConfig {
+    /// Whether tracking starts on; `T` starts and stops it while running.
+    pub enabled: bool,
+    /// Minutes without a key press after which time stops counting.
+    pub idle_minutes: u64,
+}
+
+impl Default for TimeTrackingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            idle_minutes: 5,
+        }
+    }
+}
+
+/// What the watchdog does about a hung session.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchdogAction {
+    /// Toast and ring; `K` restarts it.
+    #[default]
+    Notify,
+    /// Kill it and resume it in a terminal tab straight away.
+    Restart,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WatchdogConfig {
+    pub enabled: bool,
+    /// Minutes a session may wait on the model with no transcript output
+    /// before it counts as hung.
+    pub minutes: u64,
+    pub action: WatchdogAction,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            minutes: 10,
+            action: WatchdogAction::Notify,
+        }
+    }
+}
+
+/// A pane of the dashboard view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DashboardWidget {
+    /// One line per local session.
+    Sessions,
+    /// Token use over the usage window, with the quota left.
+    Usage,
+    /// Recent toasts, newest first.
+    Notifications,
+    /// Tervezo implementations still queued or running.
+    Implementations,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DashboardConfig {
+    /// Rows from top to bottom, each a list of widgets side by side.
+    pub rows: Vec<Vec<DashboardWidget>>,
+}
+
+impl Default for DashboardConfig {
+    fn default() -> Self {
+        use DashboardWidget::*;
+        Self {
+            rows: vec![
+                vec![Sessions],
+                vec![Usage, Notifications],
+                vec![Implementations],
+            ],
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct UsageConfig {
+    /// Tokens your plan allows per 5-hour window, cache reads left out.
+    /// Shown as a share when the usage API has no figure.
+    pub window_tokens: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct OutcomesConfig {
+    /// Ask for an outcome and rating when a session is killed or its
+    /// finished terminal closed. `R` rates any session either way.
+    pub prompt: bool,
+}
+
+impl Default for OutcomesConfig {
+    fn default() -> Self {
+        Self { prompt: true }
+    }
+}
+
+/// The focus timer started with `F`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FocusConfig {
+    pub work_minutes: u64,
+    pub break_minutes: u64,
+    pub long_break_minutes: u64,
+    /// Work intervals before a long break; 0 never takes one.
+    pub rounds: u32,
+    /// Silence bells during work intervals.
+    pub dnd: bool,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self {
+            work_minutes: 25,
+            break_minutes: 5,
+            long_break_minutes: 15,
+            rounds: 4,
+            dnd: true,
+        }
+    }
+}
+
+/// Where ticket ids link to. `{id}` is replaced with the key ("ABC-123")
+/// or the issue number ("456"), `{project}` with the session's project.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TicketsConfig {
+    /// For tracker keys like "ABC-123".
+    pub url: Option<String>,
+    /// For issue numbers like "#456".
+    pub issue_url: Option<String>,
+    /// Per-key overrides of `url`, e.g. `OPS = "https://ops.example.com/{id}"`.
+    pub keys: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct PluginsConfig {
+    /// Names of compiled-in plugins to leave off.
+    pub disabled: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Palette {
+    /// Terminal green/red/yellow.
+    #[default]
+    Default,
+    /// Blue/orange in place of green/red, for red-green color blindness.
+    Deuteranopia,
+    /// Bright colors, bold statuses and an inverted selection.
+    #[serde(alias = "high-contrast", alias = "high_contrast")]
+    HighContrast,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [
+        Palette::Default,
+        Palette::Deuteranopia,
+        Palette::HighContrast,
+    ];
+
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Palette::Default => "default",
+            Palette::Deuteranopia => "deuteranopia",
+            Palette::HighContrast => "high contrast",
+        }
+    }
+}
+
+/// Showing costs in another currency. Costs are kept in USD and converted
+/// for display only.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct CurrencyConfig {
+    /// ISO code to convert to, e.g. "EUR"; unset or "USD" shows USD.
+    pub code: Option<String>,
+    /// Fixed USD to `code` rate. Without one the rate is fetched daily.
+    pub rate: Option<f64>,
+    /// Fetch the daily rate when no `rate` is set.
+    pub fetch: bool,
+    /// Daily rate endpoint; `{code}` is replaced with `code`. Must answer
+    /// `{"rates": {"<code>": <rate>}}`.
+    pub fx_url: String,
+}
+
+impl Default for CurrencyConfig {
+    fn default() -> Self {
+        Self {
+            code: None,
+            rate: None,
+            fetch: true,
+            fx_url: "https://api.frankfurter.app/latest?from=USD&to={code}".to_string(),
+        }
+    }
+}
+
+impl CurrencyConfig {
+    /// The code to convert to, if costs are shown in anything but USD.
+    pub fn target(&self) -> Option<String> {
+        let code = self.code.as_deref()?.trim().to_ascii_uppercase();
+        (!code.is_empty() && code != "USD").then_some(code)
+    }
+}
+
+impl TerminalLogConfig {
+    pub fn dir(&self) -> Option<PathBuf> {
+        self.dir
+            .clone()
+            .or_else(|| config_dir().map(|d| d.join("terminal-logs")))
+    }
+
+    pub fn max_bytes(&self) -> u64 {
+        self.max_mb.saturating_mul(1024 * 1024)
+    }
+}
+
+impl AppConfig {
+    pub fn load() -> Self {
+        config_path()
+            .and_then(|p| std::fs::read_to_string(p).ok())
+            .map(|content| Self::parse(&content))
+            .unwrap_or_default()
+    }
+
+    fn parse(content: &str) -> Self {
+        toml::from_str(content).unwrap_or_else(|e| {
+            crate::tlog!(warn, "config.toml parse error, using defaults: {}", e);
+            Self::default()
+        })
+    }
+}
+
+pub use c9s_core::config_dir;
+
+fn config_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("config.toml"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_when_section_missing() {
+        let cfg = AppConfig::parse("[tervezo]\napi_key = \"x\"\n");
+        assert!(!cfg.sessions.show_all_per_cwd);
+        assert_eq!(cfg.sessions.hide_after_days, 14);
+    }
+
+    #[test]
+    fn test_sessions_section() {
+        let cfg = AppConfig::parse("[sessions]\nshow_all_per_cwd = true\n");
+        assert!(cfg.sessions.show_all_per_cwd);
+    }
+
+    #[test]
+    fn test_exclude_patterns() {
+        assert!(glob_match("tmp-*", "tmp-2026"));
+        assert!(glob_match("*scratch*", "my-scratch-pad"));
+        assert!(glob_match("a?c", "abc"));
+        assert!(!glob_match("a?c", "abbc"));
+        assert!(!glob_match("tmp-*", "webshop-tmp-1"));
+        assert!(glob_match("/srv/archive/*", "/srv/archive/old/api"));
+
+        let cfg = AppConfig::parse(
+            "[sessions]\nexclude = [\"tmp-*\", \"/srv/archive/*\", \"~/scratch/*\"]\n",
+        );
+        let session = |cwd: &str| {
+            let mut s = match crate::app::tests::make_local("s1", None) {
+                crate::app::SessionEntry::Local(s) => s,
+                crate::app::SessionEntry::Remote(_) => unreachable!(),
+            };
+            s.cwd = cwd.into();
+            s.project_name = cwd.rsplit('/').next().unwrap().to_string();
+            s
+        };
+        assert!(cfg.sessions.excludes(&session("/home/dev/tmp-42")));
+        assert!(cfg.sessions.excludes(&session("/srv/archive/2024/api")));
+        assert!(!cfg.sessions.excludes(&session("/home/dev/webshop")));
+        if let Some(home) = dirs::home_dir() {
+            let cwd = home.join("scratch/idea");
+            assert!(cfg.sessions.excludes(&session(&cwd.to_string_lossy())));
+        }
+        assert!(!AppConfig::parse("")
+            .sessions
+            .excludes(&session("/tmp/tmp-1")));
+    }
+
+    #[test]
+    fn test_stale_sessions() {
+        let mut session = match crate::app::tests::make_local("s1", None) {
+            crate::app::SessionEntry::Local(s) => s,
+            crate::app::SessionEntry::Remote(_) => unreachable!(),
+        };
+        let now = session.last_activity + chrono::Duration::days(15);
+        let cfg = AppConfig::parse("");
+        assert!(cfg.sessions.is_stale(&session, now));
+        assert!(!cfg
+            .sessions
+            .is_stale(&session, now - chrono::Duration::days(2)));
+
+        let cfg = AppConfig::parse("[sessions]\nhide_after_days = 0\n");
+        assert!(!cfg.sessions.is_stale(&session, now));
+
+        let cfg = AppConfig::parse("");
+        session.imported = true;
+        assert!(!cfg.sessions.is_stale(&session, now));
+        session.imported = false;
+        session.status = crate::session::SessionStatus::Idle;
+        assert!(!cfg.sessions.is_stale(&session, now));
+    }
+
+    #[test]
+    fn test_updates_section() {
+        let cfg = AppConfig::parse("");
+        assert!(!cfg.updates.check);
+        assert!(cfg.updates.latest_url.contains("claude-code"));
+
+        let cfg = AppConfig::parse("[updates]\ncheck = true\n");
+        assert!(cfg.updates.check);
+        assert!(!cfg.updates.latest_url.is_empty());
+    }
+
+    #[test]
+    fn test_format_section() {
+        let cfg = AppConfig::parse("");
+        assert_eq!(cfg.format.currency, "$");
+        assert_eq!(cfg.format.timestamps, TimestampStyle::Relative);
+
+        let cfg = AppConfig::parse(
+            "[format]\nthousands_separator = \".\"\ndecimal_separator = \",\"\ntimestamps = \"absolute\"\n",
+        );
+        assert_eq!(cfg.format.thousands_separator, ".");
+        assert_eq!(cfg.format.timestamps, TimestampStyle::Absolute);
+        assert!(cfg.format.clock_24h);
+    }
+
+    #[test]
+    fn test_currency_section() {
+        let cfg = AppConfig::parse("");
+        assert_eq!(cfg.currency.target(), None);
+
+        let cfg = AppConfig::parse("[currency]\ncode = \"eur\"\nrate = 0.9\n");
+        assert_eq!(cfg.currency.target().as_deref(), Some("EUR"));
+        assert_eq!(cfg.currency.rate, Some(0.9));
+        assert!(cfg.currency.fetch);
+        assert_eq!(
+            AppConfig::parse("[currency]\ncode = \"USD\"\n")
+                .currency
+                .target(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_user_actions() {
+        let config = AppConfig::parse(
+            "[[actions]]\nname = \"PR\"\ncommand = \"gh pr view {branch}\"\n\
+             [[actions]]\nname = \"Tests\"\ncommand = \"cd {cwd} && make test\"\n",
+        );
+        assert_eq!(config.actions.len(), 2);
+        assert_eq!(config.actions[0].name, "PR");
+
+        let mut session = match crate::app::tests::make_local("abc-123", None) {
+            crate::app::SessionEntry::Local(s) => s,
+            crate::app::SessionEntry::Remote(_) => unreachable!(),
+        };
+        session.cwd = "/home/dev/my project".into();
+        session.git_branch = Some("feat/x".to_string());
+        assert_eq!(config.actions[0].expand(&session), "gh pr view feat/x");
+        assert_eq!(
+            config.actions[1].expand(&session),
+            "cd '/home/dev/my project' && make test"
+        );
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+
+        session.cwd = "/x/{branch}".into();
+        session.git_branch = Some("a;touch${IFS}pwn".to_string());
+        assert_eq!(
+            config.actions[1].expand(&session),
+            "cd '/x/{branch}' && make test"
+        );
+        assert_eq!(
+            config.actions[0].expand(&session),
+            "gh pr view 'a;touch${IFS}pwn'"
+        );
+    }
+
+    #[test]
+    fn test_time_tracking_config() {
+        let config = AppConfig::parse("[time_tracking]\nidle_minutes = 15\n");
+        assert!(config.time_tracking.enabled);
+        assert_eq!(config.time_tracking.idle_minutes, 15);
+    }
+
+    #[test]
+    fn test_focus_config() {
+        let config = AppConfig::parse("[focus]\nwork_minutes = 50\ndnd = false\n");
+        assert_eq!(config.focus.work_minutes, 50);
+        assert_eq!(config.focus.break_minutes, 5);
+        assert!(!config.focus.dnd);
+    }
+
+    #[test]
+    fn test_ci_notify() {
+        assert!(AppConfig::parse("").ci.notify);
+        assert!(!AppConfig::parse("[ci]\nnotify = false\n").ci.notify);
+    }
+
+    #[test]
+    fn test_usage_window_tokens() {
+        assert_eq!(AppConfig::parse("").usage.window_tokens, None);
+        let config = AppConfig::parse("[usage]\nwindow_tokens = 2000000\n");
+        assert_eq!(config.usage.window_tokens, Some(2_000_000));
+    }
+
+    #[test]
+    fn test_ui_resources() {
+        assert!(!AppConfig::parse("").ui.resources);
+        assert!(AppConfig::parse("[ui]\nresources = true\n").ui.resources);
+    }
+
+    #[test]
+    fn test_ui_host() {
+        assert!(AppConfig::parse("").ui.host);
+        assert!(
+            AppConfig::parse("[ui]\npalette = \"highcontrast\"\n")
+                .ui
+                .host
+        );
+        assert!(!AppConfig::parse("[ui]\nhost = false\n").ui.host);
+    }
+
+    #[test]
+    fn test_watchdog() {
+        let config = AppConfig::parse("");
+        assert!(!config.watchdog.enabled);
+        assert_eq!(config.watchdog.action, WatchdogAction::Notify);
+        let config =
+            AppConfig::parse("[watchdog]\nenabled = true\nminutes = 20\naction = \"restart\"\n");
+        assert!(config.watchdog.enabled);
+        assert_eq!(config.watchdog.minutes, 20);
+        assert_eq!(config.watchdog.action, WatchdogAction::Restart);
+    }
+
+    #[test]
+    fn test_dashboard_rows() {
+        assert_eq!(AppConfig::parse("").dashboard.rows.len(), 3);
+        let config = AppConfig::parse(
+            "[dashboard]\nrows = [[\"sessions\", \"implementations\"], [\"usage\"]]\n",
+        );
+        assert_eq!(
+            config.dashboard.rows,
+            [
+                vec![DashboardWidget::Sessions, DashboardWidget::Implementations],
+                vec![DashboardWidget::Usage],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plugins_disabled() {
+        assert!(AppConfig::parse("").plugins.disabled.is_empty());
+        let config = AppConfig::parse("[plugins]\ndisabled = [\"git-remote\"]\n");
+        assert_eq!(config.plugins.disabled, ["git-remote"]);
+    }
+
+    #[test]
+    fn test_ui_palette() {
+        assert_eq!(AppConfig::parse("").ui.palette, Palette::Default);
+        let palette = |v: &str| {
+            AppConfig::parse(&format!("[ui]\npalette = \"{}\"\n", v))
+                .ui
+                .palette
+        };
+        assert_eq!(palette("deuteranopia"), Palette::Deuteranopia);
+        assert_eq!(palette("highcontrast"), Palette::HighContrast);
+        assert_eq!(palette("high-contrast"), Palette::HighContrast);
+        assert_eq!(Palette::HighContrast.next(), Palette::Default);
+        assert_eq!(
+            AppConfig::parse("[ui]\nlocale = \"de\"\n")
+                .ui
+                .locale
+                .as_deref(),
+            Some("de")
+        );
+    }
+
+    #[test]
+    fn test_terminal_tab_order() {
+        assert_eq!(AppConfig::parse("").terminal.tab_order, TabOrder::Attach);
+        let cfg = AppConfig::parse("[terminal]\ntab_order = \"bell\"\n");
+        assert_eq!(cfg.terminal.tab_order, TabOrder::Bell);
+    }
+
+    #[test]
+    fn test_wrapper_shell() {
+        assert_eq!(AppConfig::parse("").terminal.shell, WrapperShell::Bash);
+        let shell = |v: &str| {
+            AppConfig::parse(&format!("[terminal]\nshell = \"{}\"\n", v))
+                .terminal
+                .shell
+        };
+        assert_eq!(shell("login"), WrapperShell::Login);
+        assert_eq!(shell("none"), WrapperShell::None);
+        assert!(shell("none").wrapper().is_none());
+        assert_eq!(
+            shell("/usr/bin/zsh"),
+            WrapperShell::Other("/usr/bin/zsh".to_string())
+        );
+        assert_eq!(shell("/usr/bin/zsh").wrapper().unwrap().0, "/usr/bin/zsh");
+    }
+
+    #[test]
+    fn test_term_and_colorterm() {
+        let cfg = AppConfig::parse("");
+        assert_eq!(cfg.terminal.term(), "xterm-256color");
+        assert_eq!(truecolor(Some("truecolor")).as_deref(), Some("truecolor"));
+        assert_eq!(truecolor(Some("yes")), None);
+        assert_eq!(truecolor(None), None);
+
+        let cfg = AppConfig::parse("[terminal]\nterm = \"screen-256color\"\ncolorterm = \"\"\n");
+        assert_eq!(cfg.terminal.term(), "screen-256color");
+        assert_eq!(cfg.terminal.colorterm(), None);
+    }
+
+    #[test]
+    fn test_frame_budget() {
+        let cfg = AppConfig::parse("");
+        assert_eq!(cfg.terminal.frame_budget().as_millis(), 33);
+        let cfg = AppConfig::parse("[terminal]\nmax_fps = 0\n");
+        assert_eq!(cfg.terminal.frame_budget().as_millis(), 1000);
+    }
+
+    #[test]
+    fn test_terminal_env() {
+        assert!(AppConfig::parse("").terminal.env.is_empty());
+        let cfg =
+            AppConfig::parse("[terminal.env]\nANTHROPIC_MODEL = \"opus\"\nHTTPS_PROXY = \"\"\n");
+        assert_eq!(cfg.terminal.env["ANTHROPIC_MODEL"], "opus");
+        assert_eq!(cfg.terminal.env["HTTPS_PROXY"], "");
+    }
+
+    #[test]
+    fn test_terminal_log_section() {
+        let cfg = AppConfig::parse("");
+        assert!(!cfg.terminal_log.enabled);
+        assert!(cfg.terminal_log.dir().unwrap().ends_with("terminal-logs"));
+
+        let cfg =
+            AppConfig::parse("[terminal_log]\nenabled = true\ndir = \"/tmp/tl\"\nmax_mb = 2\n");
+        assert!(cfg.terminal_log.enabled);
+        assert_eq!(cfg.terminal_log.dir(), Some(PathBuf::from("/tmp/tl")));
+        assert_eq!(cfg.terminal_log.max_bytes(), 2 * 1024 * 1024);
+    }
+
+    #[test]
+    fn test_invalid_file_falls_back_to_defaults() {
+        let cfg = AppConfig::parse("[sessions\nbroken");
+        assert!(!cfg.sessions.show_all_per_cwd);
+    }
+}
@@ -0,0 +1,97 @@
+//! `c9s://` links, so a link in Slack or terminal output opens the right
+//! view. `c9s open` hands them to the running c9s through its control
+//! socket (see `instance`).
+
+use anyhow::{bail, Result};
+use std::fmt;
+
+pub const SCHEME: &str = "c9s://";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeepLink {
+    /// `c9s://attach/<session id>`: open the session in a terminal.
+    Attach(String),
+    /// `c9s://session/<session id>`: the session's detail view.
+    Session(String),
+    /// `c9s://tervezo/<implementation id>`: the implementation's detail.
+    Tervezo(String),
+}
+
+impl DeepLink {
+    /// Parses a link; ids may be a unique prefix. A query or trailing
+    /// slash is ignored.
+    pub fn parse(url: &str) -> Result<Self> {
+        let Some(rest) = url.trim().strip_prefix(SCHEME) else {
+            bail!("not a {} link: {}", SCHEME, url);
+        };
+        let rest = rest.split(['?', '#']).next().unwrap_or("");
+        let (kind, id) = rest
+            .trim_end_matches('/')
+            .split_once('/')
+            .unwrap_or((rest, ""));
+        if id.is_empty()
+            || !id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+        {
+            bail!("{} needs an id: {}attach/<id>", url, SCHEME);
+        }
+        let id = id.to_string();
+        match kind {
+            "attach" => Ok(Self::Attach(id)),
+            "session" => Ok(Self::Session(id)),
+            "tervezo" => Ok(Self::Tervezo(id)),
+            _ => bail!("unknown link {}: try attach, session or tervezo", url),
+        }
+    }
+
+    /// The local session the link is for, if any.
+    pub fn session_id(&self) -> Option<&str> {
+        match self {
+            Self::Attach(id) | Self::Session(id) => Some(id),
+            Self::Tervezo(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for DeepLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Attach(id) => write!(f, "{}attach/{}", SCHEME, id),
+            Self::Session(id) => write!(f, "{}session/{}", SCHEME, id),
+            Self::Tervezo(id) => write!(f, "{}tervezo/{}", SCHEME, id),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            DeepLink::parse("c9s://attach/abc-123").unwrap(),
+            DeepLink::Attach("abc-123".to_string())
+        );
+        assert_eq!(
+            DeepLink::parse(" c9s://session/abc/?from=slack\n").unwrap(),
+            DeepLink::Session("abc".to_string())
+        );
+        let link = DeepLink::parse("c9s://tervezo/impl_9").unwrap();
+        assert_eq!(link, DeepLink::Tervezo("impl_9".to_string()));
+        assert_eq!(link.to_string(), "c9s://tervezo/impl_9");
+        assert_eq!(link.session_id(), None);
+
+        for bad in [
+            "https://attach/abc",
+            "c9s://attach",
+            "c9s://attach/",
+            "c9s://attach/a b",
+            "c9s://attach/$(x)",
+            "c9s://kill/abc",
+        ] {
+            assert!(DeepLink::parse(bad).is_err(), "{}", bad);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Synthetic terminal output for the snapshot tests.
+
+/// A parser whose screen and scrollback are full of coloured test output.
+pub fn busy_terminal(rows: u16, cols: u16, lines: usize) -> vt100::Parser {
+    let mut parser = vt100::Parser::new(rows, cols, 10000);
+    for i in 0..lines {
+        let color = 31 + (i % 7);
+        parser.process(
+            format!(
+                "\x1b[{}mtest parser::tests::case_{:05}\x1b[0m ... \x1b[1;32mok\x1b[0m  ({} ms)\r\n",
+                color,
+                i,
+                i % 97
+            )
+            .as_bytes(),
+        );
+    }
+    parser
+}
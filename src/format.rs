@@ -0,0 +1,356 @@
+//! Number, cost, time and path formatting shared by every view, following
+//! the `[format]` preferences in config.toml. The preferences are set once
+//! at startup; anything formatted before that, and tests, get the defaults.
+
+use chrono::{DateTime, Local, TimeZone, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, OnceLock};
+
+use crate::config::{FormatConfig, TimestampStyle};
+
+static FORMAT: OnceLock<FormatConfig> = OnceLock::new();
+
+/// USD to display-currency rate, as `f64` bits. 1.0 when costs are shown
+/// in USD; NaN while a conversion is configured but its rate not yet known.
+static RATE: AtomicU64 = AtomicU64::new(0x3FF0_0000_0000_0000);
+
+pub fn init(cfg: FormatConfig) {
+    let _ = FORMAT.set(cfg);
+}
+
+/// Converts costs at `rate` from now on; `None` means a conversion is
+/// wanted but the rate is unknown, so costs show in USD meanwhile.
+pub fn set_rate(rate: Option<f64>) {
+    let rate = rate
+        .filter(|r| r.is_finite() && *r > 0.0)
+        .unwrap_or(f64::NAN);
+    RATE.store(rate.to_bits(), Ordering::Relaxed);
+}
+
+/// Whether paths are shown in full rather than shortened.
+static FULL_PATHS: AtomicBool = AtomicBool::new(false);
+
+/// Nearest enclosing git repository per directory, looked up once.
+static REPO_ROOTS: OnceLock<Mutex<HashMap<PathBuf, Option<PathBuf>>>> = OnceLock::new();
+
+/// Shows paths in full from now on, or shortened again.
+pub fn set_full_paths(full: bool) {
+    FULL_PATHS.store(full, Ordering::Relaxed);
+}
+
+fn rate() -> f64 {
+    f64::from_bits(RATE.load(Ordering::Relaxed))
+}
+
+fn cfg() -> &'static FormatConfig {
+    FORMAT.get_or_init(FormatConfig::default)
+}
+
+/// Token counts: "950", "12.3K", "1.4M".
+pub fn tokens(n: u64) -> String {
+    tokens_with(cfg(), n)
+}
+
+/// Counts that rarely pass a thousand: "64", "1.2K".
+pub fn count(n: u64) -> String {
+    count_with(cfg(), n)
+}
+
+/// A whole number in full: "12,345".
+pub fn integer(n: u64) -> String {
+    group(n, &cfg().thousands_separator)
+}
+
+/// Memory sizes in binary units: "820M", "8.1G".
+pub fn bytes(n: u64) -> String {
+    bytes_with(cfg(), n)
+}
+
+/// A cost in USD with `decimals` places, converted to the configured
+/// currency: "$1,234.56", "1.136,00 €".
+pub fn cost(usd: f64, decimals: usize) -> String {
+    cost_with(cfg(), usd, decimals, rate())
+}
+
+/// When something last happened, relative ("5m ago") or absolute.
+pub fn since(ts: DateTime<Utc>) -> String {
+    since_with(cfg(), ts, Utc::now())
+}
+
+/// A span of time in hours and minutes: "2h 5m", "40m".
+pub fn elapsed(secs: u64) -> String {
+    let (hours, minutes) = (secs / 3600, secs % 3600 / 60);
+    if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
+
+/// Local time of day, with or without seconds.
+pub fn clock<Tz: TimeZone>(ts: &DateTime<Tz>, seconds: bool) -> String {
+    clock_with(cfg(), &ts.with_timezone(&Local), seconds)
+}
+
+/// A directory in at most `width` columns: relative to its git repository
+/// ("api/services/auth"), else with `~` for home ("~/scratch"), cut in the
+/// middle if still too long. Shown whole while full paths are on.
+pub fn path(dir: &Path, width: usize) -> String {
+    if FULL_PATHS.load(Ordering::Relaxed) {
+        return dir.to_string_lossy().into_owned();
+    }
+    let root = REPO_ROOTS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap()
+        .entry(dir.to_path_buf())
+        .or_insert_with(|| crate::session::git::worktree_root(dir))
+        .clone();
+    short_path(dir, dirs::home_dir().as_deref(), root.as_deref(), width)
+}
+
+fn tokens_with(cfg: &FormatConfig, n: u64) -> String {
+    if n >= 1_000_000 {
+        format!("{}M", decimal(cfg, n as f64 / 1_000_000.0, 1))
+    } else {
+        count_with(cfg, n)
+    }
+}
+
+fn count_with(cfg: &FormatConfig, n: u64) -> String {
+    if n >= 1_000 {
+        format!("{}K", decimal(cfg, n as f64 / 1_000.0, 1))
+    } else {
+        n.to_string()
+    }
+}
+
+fn cost_with(cfg: &FormatConfig, usd: f64, decimals: usize, rate: f64) -> String {
+    if rate.is_nan() {
+        // The configured symbol belongs to a currency we can't convert to
+        // yet.
+        return format!("${}", decimal(cfg, usd, decimals));
+    }
+    let value = usd * rate;
+    let sign = if value < 0.0 { "-" } else { "" };
+    let amount = decimal(cfg, value.abs(), decimals);
+    if cfg.currency_after {
+        format!("{}{} {}", sign, amount, cfg.currency)
+    } else {
+        format!("{}{}{}", sign, cfg.currency, amount)
+    }
+}
+
+/// `value` rounded to `places`, grouped and with the configured separators.
+fn bytes_with(cfg: &FormatConfig, n: u64) -> String {
+    const MIB: u64 = 1024 * 1024;
+    const GIB: u64 = 1024 * MIB;
+    if n >= GIB {
+        format!("{}G", decimal(cfg, n as f64 / GIB as f64, 1))
+    } else {
+        format!("{}M", n / MIB)
+    }
+}
+
+fn decimal(cfg: &FormatConfig, value: f64, places: usize) -> String {
+    let fixed = format!("{:.*}", places, value);
+    let (int, frac) = fixed.split_once('.').unwrap_or((&fixed, ""));
+    let int = group(int.parse().unwrap_or(0), &cfg.thousands_separator);
+    if frac.is_empty() {
+        int
+    } else {
+        format!("{}{}{}", int, cfg.decimal_separator, frac)
+    }
+}
+
+fn group(n: u64, sep: &str) -> String {
+    let digits = n.to_string();
+    if sep.is_empty() {
+        return digits;
+    }
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3 * sep.len());
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push_str(sep);
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn since_with(cfg: &FormatConfig, ts: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    match cfg.timestamps {
+        TimestampStyle::Relative => {
+            let secs = now.signed_duration_since(ts).num_seconds();
+            if secs < 60 {
+                format!("{}s ago", secs)
+            } else if secs < 3600 {
+                format!("{}m ago", secs / 60)
+            } else if secs < 86400 {
+                format!("{}h ago", secs / 3600)
+            } else {
+                format!("{}d ago", secs / 86400)
+            }
+        }
+        TimestampStyle::Absolute => {
+            let local = ts.with_timezone(&Local);
+            let today = now.with_timezone(&Local).date_naive();
+            if local.date_naive() == today {
+                clock_with(cfg, &local, false)
+            } else if today.signed_duration_since(local.date_naive()).num_days() < 365 {
+                local.format("%b %-d").to_string()
+            } else {
+                local.format("%Y-%m-%d").to_string()
+            }
+        }
+    }
+}
+
+fn clock_with(cfg: &FormatConfig, ts: &DateTime<Local>, seconds: bool) -> String {
+    let pattern = match (cfg.clock_24h, seconds) {
+        (true, true) => "%H:%M:%S",
+        (true, false) => "%H:%M",
+        (false, true) => "%-I:%M:%S %p",
+        (false, false) => "%-I:%M %p",
+    };
+    ts.format(pattern).to_string()
+}
+
+fn short_path(dir: &Path, home: Option<&Path>, root: Option<&Path>, width: usize) -> String {
+    let relative = |base: &Path, prefix: &str| {
+        let rest = dir.strip_prefix(base).ok()?;
+        Some(if rest.as_os_str().is_empty() {
+            prefix.to_string()
+        } else {
+            format!("{}/{}", prefix, rest.display())
+        })
+    };
+    // A repository that is home itself (dotfiles) says less than `~`.
+    let in_repo = root
+        .filter(|r| Some(*r) != home)
+        .and_then(|r| relative(r, &r.file_name()?.to_string_lossy()));
+    let shown = in_repo
+        .or_else(|| relative(home?, "~"))
+        .unwrap_or_else(|| dir.to_string_lossy().into_owned());
+    truncate_middle(&shown, width)
+}
+
+/// `path` in at most `width` columns, keeping its first component and as
+/// many trailing ones as fit: "~/code/…/auth".
+fn truncate_middle(path: &str, width: usize) -> String {
+    if path.chars().count() <= width {
+        return path.to_string();
+    }
+    let parts: Vec<&str> = path.split('/').collect();
+    if let [head, rest @ ..] = parts.as_slice() {
+        let mut used = head.chars().count() + 2;
+        let mut tail = Vec::new();
+        for part in rest.iter().rev() {
+            used += part.chars().count() + 1;
+            if used > width {
+                break;
+            }
+            tail.push(*part);
+        }
+        if !tail.is_empty() {
+            tail.reverse();
+            return format!("{}/…/{}", head, tail.join("/"));
+        }
+    }
+    if width == 0 {
+        return String::new();
+    }
+    // Not even the last component fits: cut it in the middle.
+    let chars: Vec<char> = path.chars().collect();
+    let keep = width - 1;
+    let front: String = chars[..keep / 2].iter().collect();
+    let back: String = chars[chars.len() - (keep - keep / 2)..].iter().collect();
+    format!("{}…{}", front, back)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn european() -> FormatConfig {
+        FormatConfig {
+            thousands_separator: ".".to_string(),
+            decimal_separator: ",".to_string(),
+            currency: "€".to_string(),
+            currency_after: true,
+            timestamps: TimestampStyle::Absolute,
+            clock_24h: false,
+        }
+    }
+
+    #[test]
+    fn test_numbers_and_costs() {
+        let us = FormatConfig::default();
+        assert_eq!(tokens_with(&us, 950), "950");
+        assert_eq!(tokens_with(&us, 1_432_000), "1.4M");
+        assert_eq!(count_with(&us, 23_150), "23.1K");
+        assert_eq!(cost_with(&us, 1234.5, 2, 1.0), "$1,234.50");
+        assert_eq!(group(1_234_567, ","), "1,234,567");
+        assert_eq!(group(999, ","), "999");
+        assert_eq!(bytes_with(&us, 820 * 1024 * 1024), "820M");
+        assert_eq!(bytes_with(&us, 8_700_000_000), "8.1G");
+
+        let eu = european();
+        assert_eq!(tokens_with(&eu, 1_432_000), "1,4M");
+        assert_eq!(tokens_with(&eu, 2_500_000_000), "2.500,0M");
+        assert_eq!(cost_with(&eu, 1234.5, 2, 1.0), "1.234,50 €");
+        assert_eq!(cost_with(&eu, 10.0, 2, 0.92), "9,20 €");
+        assert_eq!(cost_with(&eu, 10.0, 2, f64::NAN), "$10,00");
+    }
+
+    #[test]
+    fn test_times() {
+        let now = Utc::now();
+        let us = FormatConfig::default();
+        assert_eq!(since_with(&us, now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(since_with(&us, now - Duration::days(3), now), "3d ago");
+
+        let eu = european();
+        let earlier = now - Duration::days(3);
+        assert_eq!(
+            since_with(&eu, earlier, now),
+            earlier.with_timezone(&Local).format("%b %-d").to_string()
+        );
+        let noon = Local.with_ymd_and_hms(2026, 3, 1, 13, 5, 9).unwrap();
+        assert_eq!(clock_with(&eu, &noon, false), "1:05 PM");
+        assert_eq!(clock_with(&us, &noon, true), "13:05:09");
+
+        assert_eq!(elapsed(2400), "40m");
+        assert_eq!(elapsed(7530), "2h 5m");
+    }
+
+    #[test]
+    fn test_short_paths() {
+        let home = Path::new("/home/ada");
+        let dir = Path::new("/home/ada/code/api/services/auth");
+        let repo = Path::new("/home/ada/code/api");
+        assert_eq!(
+            short_path(dir, Some(home), Some(repo), 40),
+            "api/services/auth"
+        );
+        assert_eq!(short_path(repo, Some(home), Some(repo), 40), "api");
+        assert_eq!(
+            short_path(dir, Some(home), None, 40),
+            "~/code/api/services/auth"
+        );
+        assert_eq!(
+            short_path(dir, Some(home), Some(home), 40),
+            "~/code/api/services/auth"
+        );
+        assert_eq!(short_path(dir, Some(home), None, 17), "~/…/services/auth");
+        assert_eq!(short_path(dir, Some(home), None, 8), "~/…/auth");
+        assert_eq!(
+            short_path(Path::new("/tmp/proj"), Some(home), None, 40),
+            "/tmp/proj"
+        );
+        assert_eq!(truncate_middle("a-very-long-name", 7), "a-v…ame");
+    }
+}
@@ -0,0 +1,129 @@
+//! The exchange rate costs are shown at when a display currency is
+//! configured: a fixed rate from config, or one fetched at most once a day
+//! and kept in `~/.c9s/fx.json` so restarts and offline runs reuse it.
+
+use chrono::{Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use crate::config::{config_dir, CurrencyConfig};
+use crate::format;
+use crate::tlog;
+
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct CachedRate {
+    code: String,
+    rate: f64,
+    date: NaiveDate,
+}
+
+/// Sets the rate costs are formatted at from config or the saved rate.
+/// If that is missing or from an earlier day and `allow_fetch` is set,
+/// today's rate is fetched in the background and delivered on the
+/// returned channel.
+pub fn start(cfg: &CurrencyConfig, allow_fetch: bool) -> Option<mpsc::Receiver<f64>> {
+    let code = cfg.target()?;
+    if let Some(rate) = cfg.rate {
+        format::set_rate(Some(rate));
+        return None;
+    }
+
+    let path = cache_path();
+    let cached = path.as_deref().and_then(|p| read_cache(p, &code));
+    format::set_rate(cached.as_ref().map(|c| c.rate));
+
+    let today = Local::now().date_naive();
+    if !allow_fetch || !cfg.fetch || cached.is_some_and(|c| c.date == today) {
+        return None;
+    }
+    let url = cfg.fx_url.replace("{code}", &code);
+    let (tx, rx) = mpsc::channel();
+    crate::wakeup::spawn(move || match fetch_rate(&url, &code) {
+        Some(rate) => {
+            if let Some(path) = path {
+                write_cache(
+                    &path,
+                    &CachedRate {
+                        code,
+                        rate,
+                        date: today,
+                    },
+                );
+            }
+            let _ = tx.send(rate);
+        }
+        None => tlog!(warn, "could not fetch the USD/{} rate from {}", code, url),
+    });
+    Some(rx)
+}
+
+fn cache_path() -> Option<PathBuf> {
+    config_dir().map(|d| d.join("fx.json"))
+}
+
+fn read_cache(path: &Path, code: &str) -> Option<CachedRate> {
+    let cached: CachedRate = serde_json::from_str(&std::fs::read_to_string(path).ok()?).ok()?;
+    (cached.code == code).then_some(cached)
+}
+
+fn write_cache(path: &Path, rate: &CachedRate) {
+    if let Ok(json) = serde_json::to_string(rate) {
+        let _ = std::fs::write(path, json);
+    }
+}
+
+fn fetch_rate(url: &str, code: &str) -> Option<f64> {
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(FETCH_TIMEOUT))
+        .build()
+        .new_agent();
+    let body = agent
+        .get(url)
+        .header("User-Agent", "c9s")
+        .call()
+        .ok()?
+        .body_mut()
+        .read_to_string()
+        .ok()?;
+    parse_rate(&body, code)
+}
+
+/// The rate for `code` from a `{"rates": {"EUR": 0.92}}` response.
+fn parse_rate(body: &str, code: &str) -> Option<f64> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    json.get("rates")?
+        .get(code)?
+        .as_f64()
+        .filter(|r| r.is_finite() && *r > 0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_rate() {
+        let body = r#"{"amount":1.0,"base":"USD","date":"2026-10-16","rates":{"EUR":0.9213}}"#;
+        assert_eq!(parse_rate(body, "EUR"), Some(0.9213));
+        assert_eq!(parse_rate(body, "GBP"), None);
+        assert_eq!(parse_rate(r#"{"rates":{"EUR":0}}"#, "EUR"), None);
+    }
+
+    #[test]
+    fn test_cache_is_per_currency() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fx.json");
+        let rate = CachedRate {
+            code: "EUR".to_string(),
+            rate: 0.92,
+            date: NaiveDate::from_ymd_opt(2026, 10, 16).unwrap(),
+        };
+        write_cache(&path, &rate);
+        assert_eq!(read_cache(&path, "EUR"), Some(rate));
+        assert_eq!(read_cache(&path, "GBP"), None);
+    }
+}
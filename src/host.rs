@@ -0,0 +1,221 @@
+//! Load, free memory and battery of the machine c9s runs on, sampled on a
+//! background thread for the header.
+
+use std::path::Path;
+use std::process::Command;
+use std::sync::mpsc;
+use std::time::Duration;
+
+const INTERVAL: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct HostStats {
+    /// One-minute load average.
+    pub load: Option<f64>,
+    pub cpus: usize,
+    pub mem_available: Option<u64>,
+    pub mem_total: Option<u64>,
+    pub battery: Option<Battery>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Battery {
+    pub percent: u8,
+    pub charging: bool,
+}
+
+impl HostStats {
+    /// More runnable work than cores.
+    pub fn overloaded(&self) -> bool {
+        self.load.is_some_and(|l| l > self.cpus.max(1) as f64)
+    }
+
+    /// Under a tenth of memory left.
+    pub fn low_memory(&self) -> bool {
+        match (self.mem_available, self.mem_total) {
+            (Some(free), Some(total)) => free * 10 < total,
+            _ => false,
+        }
+    }
+
+    pub fn low_battery(&self) -> bool {
+        self.battery.is_some_and(|b| !b.charging && b.percent < 20)
+    }
+}
+
+/// Samples every few seconds until the receiver is dropped.
+pub fn start() -> mpsc::Receiver<HostStats> {
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || loop {
+        if tx.send(sample()).is_err() {
+            break;
+        }
+        crate::wakeup::notify();
+        std::thread::sleep(INTERVAL);
+    });
+    rx
+}
+
+fn sample() -> HostStats {
+    let cpus = std::thread::available_parallelism().map_or(1, |n| n.get());
+    if cfg!(target_os = "macos") {
+        let (mem_available, mem_total) = command("vm_stat", &[])
+            .and_then(|out| parse_vm_stat(&out))
+            .zip(command("sysctl", &["-n", "hw.memsize"]).and_then(|s| s.trim().parse().ok()))
+            .map_or((None, None), |(free, total)| (Some(free), Some(total)));
+        HostStats {
+            load: command("sysctl", &["-n", "vm.loadavg"])
+                .and_then(|s| parse_loadavg(s.trim_start_matches(['{', ' ']))),
+            cpus,
+            mem_available,
+            mem_total,
+            battery: command("pmset", &["-g", "batt"]).and_then(|s| parse_pmset(&s)),
+        }
+    } else {
+        let (mem_available, mem_total) =
+            std::fs::read_to_string("/proc/meminfo").map_or((None, None), |s| parse_meminfo(&s));
+        HostStats {
+            load: std::fs::read_to_string("/proc/loadavg")
+                .ok()
+                .and_then(|s| parse_loadavg(&s)),
+            cpus,
+            mem_available,
+            mem_total,
+            battery: linux_battery(Path::new("/sys/class/power_supply")),
+        }
+    }
+}
+
+fn command(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn parse_loadavg(s: &str) -> Option<f64> {
+    s.split_whitespace().next()?.parse().ok()
+}
+
+/// `MemAvailable` and `MemTotal` in bytes.
+fn parse_meminfo(s: &str) -> (Option<u64>, Option<u64>) {
+    let field = |name: &str| {
+        s.lines()
+            .find_map(|l| l.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|v| v.split_whitespace().next()?.parse::<u64>().ok())
+            .map(|kib| kib * 1024)
+    };
+    (field("MemAvailable"), field("MemTotal"))
+}
+
+/// Free plus reclaimable pages, in bytes.
+fn parse_vm_stat(s: &str) -> Option<u64> {
+    let page_size = s
+        .lines()
+        .next()?
+        .split("page size of ")
+        .nth(1)?
+        .split_whitespace()
+        .next()?
+        .parse::<u64>()
+        .ok()?;
+    let pages = |name: &str| {
+        s.lines()
+            .find_map(|l| l.strip_prefix(name)?.strip_prefix(':'))
+            .and_then(|v| v.trim().trim_end_matches('.').parse::<u64>().ok())
+            .unwrap_or(0)
+    };
+    let free = pages("Pages free") + pages("Pages inactive") + pages("Pages purgeable");
+    Some(free * page_size)
+}
+
+/// `pmset -g batt`: "-InternalBattery-0 (id=...) 54%; discharging; ...".
+fn parse_pmset(s: &str) -> Option<Battery> {
+    let line = s.lines().find(|l| l.contains("InternalBattery"))?;
+    let (before, after) = line.split_once('%')?;
+    let percent = before
+        .rsplit(|c: char| !c.is_ascii_digit())
+        .next()?
+        .parse()
+        .ok()?;
+    let state = after.split(';').nth(1).unwrap_or("").trim();
+    Some(Battery {
+        percent,
+        charging: matches!(state, "charging" | "charged" | "finishing charge"),
+    })
+}
+
+fn linux_battery(dir: &Path) -> Option<Battery> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| std::fs::read_to_string(p.join("type")).is_ok_and(|t| t.trim() == "Battery"))
+        .find_map(|p| {
+            let percent = std::fs::read_to_string(p.join("capacity"))
+                .ok()?
+                .trim()
+                .parse()
+                .ok()?;
+            let status = std::fs::read_to_string(p.join("status")).unwrap_or_default();
+            Some(Battery {
+                percent,
+                charging: matches!(status.trim(), "Charging" | "Full" | "Not charging"),
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_linux() {
+        assert_eq!(parse_loadavg("3.52 2.10 1.05 4/812 12345\n"), Some(3.52));
+        let meminfo = "MemTotal:       16314336 kB\nMemFree:          812000 kB\n\
+                       MemAvailable:    4194304 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            (Some(4 << 30), Some(16_314_336 * 1024))
+        );
+    }
+
+    #[test]
+    fn test_parse_macos() {
+        assert_eq!(parse_loadavg("2.31 1.98 1.75 }"), Some(2.31));
+        let vm_stat = "Mach Virtual Memory Statistics: (page size of 16384 bytes)\n\
+                       Pages free:                               10000.\n\
+                       Pages active:                            300000.\n\
+                       Pages inactive:                           50000.\n\
+                       Pages purgeable:                           4000.\n";
+        assert_eq!(parse_vm_stat(vm_stat), Some(64_000 * 16384));
+        let pmset = "Now drawing from 'Battery Power'\n \
+                     -InternalBattery-0 (id=4653155)\t54%; discharging; 3:12 remaining present: true\n";
+        assert_eq!(
+            parse_pmset(pmset),
+            Some(Battery {
+                percent: 54,
+                charging: false
+            })
+        );
+        assert_eq!(parse_pmset("Now drawing from 'AC Power'\n"), None);
+    }
+
+    #[test]
+    fn test_warnings() {
+        let stats = HostStats {
+            load: Some(9.0),
+            cpus: 8,
+            mem_available: Some(1 << 30),
+            mem_total: Some(16 << 30),
+            battery: Some(Battery {
+                percent: 15,
+                charging: true,
+            }),
+        };
+        assert!(stats.overloaded());
+        assert!(stats.low_memory());
+        assert!(!stats.low_battery());
+    }
+}
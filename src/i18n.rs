@@ -0,0 +1,617 @@
+//! Translations of user-facing strings. Views pass the English text through
+//! `t`/`tf`, which look it up in the catalog for the configured locale and
+//! fall back to the English when there is no entry.
+//!
+//! Catalogs are flat TOML files in `~/.c9s/locales/<locale>.toml` mapping
+//! the English text to its translation. `c9s locale-template` prints one
+//! with every string in `MESSAGES` to start from.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+static CATALOG: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Loads the catalog for `locale`, or for the language in `LC_ALL`,
+/// `LC_MESSAGES` or `LANG` when unset. English needs no catalog.
+pub fn init(locale: Option<&str>) {
+    let locale = locale.map(str::to_string).or_else(env_locale);
+    let Some(locale) = locale.filter(|l| !is_english(l)) else {
+        return;
+    };
+    let Some(dir) = crate::config::config_dir().map(|d| d.join("locales")) else {
+        return;
+    };
+    match load_catalog(&dir, &locale) {
+        Some(catalog) => {
+            let _ = CATALOG.set(catalog);
+        }
+        None => crate::tlog!(info, "no catalog for locale {}, using English", locale),
+    }
+}
+
+/// The translation of `msg`, or `msg` itself.
+pub fn t(msg: &'static str) -> &'static str {
+    CATALOG
+        .get()
+        .and_then(|c| c.get(msg))
+        .map(String::as_str)
+        .unwrap_or(msg)
+}
+
+/// Translates `msg` and fills in its `{name}` placeholders, which
+/// translations may reorder.
+pub fn tf(msg: &'static str, args: &[(&str, &str)]) -> String {
+    fill(t(msg), args)
+}
+
+/// Fills `{name}` placeholders in an already translated `template`.
+pub fn fill(template: &str, args: &[(&str, &str)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{}}}", name), value);
+    }
+    out
+}
+
+fn env_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|v| std::env::var(v).ok())
+        .find(|v| !v.is_empty())
+}
+
+fn is_english(locale: &str) -> bool {
+    let lang = language(locale);
+    lang.is_empty() || lang == "en" || lang == "C" || lang == "POSIX"
+}
+
+/// "de_AT.UTF-8" -> "de".
+fn language(locale: &str) -> &str {
+    locale.split(['_', '-', '.', '@']).next().unwrap_or("")
+}
+
+/// Tries the full locale ("pt_BR") before its language ("pt").
+fn load_catalog(dir: &Path, locale: &str) -> Option<HashMap<String, String>> {
+    let base = locale.split(['.', '@']).next().unwrap_or(locale);
+    let candidates: Vec<PathBuf> = [base, language(locale)]
+        .iter()
+        .map(|name| dir.join(format!("{}.toml", name)))
+        .collect();
+    candidates.iter().find_map(|path| {
+        let content = std::fs::read_to_string(path).ok()?;
+        match parse_catalog(&content) {
+            Ok(catalog) => Some(catalog),
+            Err(e) => {
+                crate::tlog!(warn, "{}: {}", path.display(), e);
+                None
+            }
+        }
+    })
+}
+
+/// Empty translations count as missing so a half-filled template works.
+fn parse_catalog(content: &str) -> Result<HashMap<String, String>, toml::de::Error> {
+    let mut catalog: HashMap<String, String> = toml::from_str(content)?;
+    catalog.retain(|_, v| !v.is_empty());
+    Ok(catalog)
+}
+
+/// A catalog template with every translatable string left empty.
+pub fn template() -> String {
+    let mut out = String::from(
+        "# c9s message catalog. Save as ~/.c9s/locales/<locale>.toml and fill in\n\
+         # the right-hand sides; empty entries stay English. Keep {placeholders}\n\
+         # and the key hints (\"a:\", \"Esc:\") as they are.\n",
+    );
+    for msg in MESSAGES {
+        out.push_str(&format!("{} = \"\"\n", toml_key(msg)));
+    }
+    out
+}
+
+fn toml_key(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Every string passed to `t`/`tf`, for the template.
+pub const MESSAGES: &[&str] = &[
+    // Session list
+    "Project",
+    "Path",
+    "!same branch",
+    "!branch clash",
+    "Branch",
+    "Model",
+    "Status",
+    "Msgs",
+    "Err",
+    "Todo",
+    "you",
+    "claude",
+    "Tokens In",
+    "Tokens Out",
+    "Cost",
+    "Last Active",
+    "Ticket",
+    " [scanning sessions...] ",
+    " [recent errors] ",
+    " [{n} hidden] ",
+    " [{n} hidden shown] ",
+    " [{n} dead over {days}d] ",
+    " [{n} dead over {days}d shown] ",
+    " {n} live session on outdated claude (latest {latest}) ",
+    " {n} live sessions on outdated claude (latest {latest}) ",
+    " claude {latest} available (installed {installed}) ",
+    "attached: {name}",
+    "{tokens} tokens",
+    "  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  c:fix-ci  /:filter  s:sort  ?:help",
+    "  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  /:filter  s:sort  ?:help",
+    // Dialogs
+    " Quit c9s? ",
+    "  Active sessions will be terminated:",
+    " y/Enter: exit ",
+    " n/Esc: cancel ",
+    " Kill Session? ",
+    "  Kill session: {name}",
+    " y/Enter: kill ",
+    "  Types /exit in its tab; SIGTERM if it lingers.",
+    " f: kill now ",
+    " Attached Elsewhere ",
+    "  {name} is running in another terminal ({tty}).",
+    "  Attaching here ends it there.",
+    " y/Enter: attach ",
+    " Enter/Esc: dismiss",
+    // Footers
+    " Esc:back  Tab:files  a:attach  f:sessions  t:timeline  Up/Dn:navigate  Enter:preview{mcp}  ~{tokens}tk always-loaded",
+    " Esc:back  Tab:files  a:attach  f:sessions  t:timeline  Up/Dn:navigate  Enter:preview file{mcp}",
+    " Esc:back  Tab:diff  Up/Dn:navigate  Enter:preview file",
+    " Esc:back  Tab:diff",
+    " Esc:back  Tab:transcript  Up/Dn:scroll  c:commit",
+    " Esc:back  Tab:overview  j/k:scroll  g/G:top/bottom  ({n} messages)",
+    "  No transcript found for this session",
+    "  Reading transcript…",
+    " (sampled)",
+    "Transcript",
+    " Working diff ",
+    "  No uncommitted changes",
+    "  git diff failed: {error}",
+    "  Running git diff…",
+    "Working diff",
+    " Changed files ",
+    " Changed files ({n}) ",
+    "  No Edit or Write calls in this session",
+    "Overview",
+    "Files",
+    "  m:check MCP",
+    " Esc:close preview  Up/Dn:scroll",
+    " B:all events  Enter:jump  b:remove  j/k:move  ({n} bookmarks)",
+    " Esc:back  j/k:move  g/G:top/bottom  b:bookmark  n/N:next/prev mark  B:bookmarks  e/y:export/copy  r:reload  ({n} events)",
+    " Transcript Diagnostics ",
+    " D:back  j/k:scroll  g/G:top/bottom  ({n} files) ",
+    " Log ",
+    " L:back  j/k:scroll  g/G:top/bottom  c:clear  ({n} entries) ",
+    " Esc:back  j/k:scroll  PgUp/PgDn:page  g/G:top/bottom",
+    " Esc:back  j/k:move  Enter:view output",
+    ": next  ",
+    ": submit  ",
+    ": cancel",
+    // Help
+    " Keybindings ",
+    "Move down",
+    "Move up",
+    "Jump to top",
+    "Jump to bottom",
+    "Session detail / Tervezo detail",
+    "Check MCP servers (in detail)",
+    "Overview / files / diff / transcript (in detail)",
+    "Commit the diff, optionally open a PR (diff tab)",
+    "Session timeline (local)",
+    "Send a line to a background session",
+    "Attach (open terminal)",
+    "Resume or fresh session in cwd",
+    "Attach to live session by #",
+    "Quick switcher",
+    "New session (local or tervezo)",
+    "Kill session (asks first)",
+    "Fix failing CI (tervezo)",
+    "Filter sessions",
+    "Only sessions with recent errors",
+    "Expand row: last prompt and reply",
+    "Back / forward: earlier filter, sort, selection",
+    "Cycle sort column",
+    "Refresh session list",
+    "Toggle log panel",
+    "Toggle terminal output log file",
+    "Read-only web mirror of the terminal on / off",
+    "Output of recently exited tabs",
+    "Transcript parse diagnostics",
+    "Cycle color palette",
+    "Bell: all / needs-input / mute",
+    "Do not disturb (30 min) on / off",
+    "Time tracking on / off",
+    "Focus timer on / off",
+    "Rate the session's outcome",
+    "Outcomes per project and model",
+    "Cost and tokens per day and project",
+    "Hide session / list it again",
+    "Show hidden, excluded and old dead sessions",
+    "Project names / short paths / full paths",
+    // Outcomes
+    " rating (1-5): ",
+    " How did {project} go? ",
+    " Enter:save  Esc:skip ",
+    "Rated",
+    "Shipped",
+    "Abandoned",
+    "Human",
+    "Success",
+    "Rating",
+    " No rated sessions yet. Rate one with R.",
+    " Outcomes ",
+    " U/Esc:back ",
+    // Stats
+    " Stats: last {n} days ",
+    " Tab:cost / tokens  S/Esc:back ",
+    " {n} sessions  ",
+    "  {input} in / {output} out",
+    " No sessions in the store for these days yet.",
+    " Tokens per day ",
+    " Cost per day ",
+    " Tokens per project ",
+    " Cost per project ",
+    "other",
+    "Failed",
+    " B/Esc:back ",
+    "Dashboard: sessions, usage, notifications, implementations",
+    "Restart a session the watchdog found hung",
+    "Hung",
+    "Show CPU and memory of live sessions",
+    "CPU",
+    "Mem",
+    " load {load}/{cpus} ",
+    " mem {free} free ",
+    " bat {pct} ",
+    "Switch Tervezo organization",
+    " Tervezo organization ",
+    "   Loading organizations...",
+    "   This key has no organizations",
+    " Enter: switch  Esc: close",
+    " [org: {org}] ",
+    "Waiting Tervezo implementations: reorder or cancel",
+    "Name",
+    "Repository",
+    "Waiting",
+    "Starts",
+    "any moment",
+    "in {time}",
+    "   Nothing waiting: every implementation has started",
+    " Queue ({count} waiting) ",
+    " j/k:select  +:move up  t:to front  x:cancel  Q/Esc:back ",
+    "  Cancel {name}?",
+    " Cancel implementation ",
+    " y/Enter:cancel it  n/Esc:keep ",
+    " No sessions",
+    " Sessions ({live} live / {total}) ",
+    " Usage ",
+    " 5h window {pct}% used, resets {reset}",
+    " 5h window {pct}% used",
+    " 5h window ~{pct}% used, resets in {time}",
+    " 5h window {tokens} tokens, resets in {time}",
+    " No usage in the last 5h",
+    " Nothing yet",
+    " Notifications ",
+    " No implementations running",
+    " Implementations ",
+    "Cost / shipped",
+    "Median length",
+    "Back / clear filter",
+    "Quit",
+    "Force quit",
+    "Toggle side shell panel",
+    "Toggle this help",
+    "In terminal",
+    "Detach (back to list)",
+    "Cycle next / prev session",
+    "Scroll history up / down",
+    "Copy mode (move, v to select, y to copy)",
+    "Session timeline",
+    "Bookmark selected event",
+    "Next / prev bookmark",
+    "Bookmark list (Enter to jump)",
+    "Export transcript to a file / the clipboard",
+    "Tervezo detail",
+    "Next / prev tab",
+    "Scroll tab content",
+    "Scroll timeline",
+    "SSH into running impl",
+    "Refresh detail data",
+    "Load older timeline messages",
+    "Previous / next attempt after a restart",
+    "Back to list",
+    "Plugins",
+    // Action menu
+    " Actions: {project} ",
+    " Enter/1-9: run in new tab  Esc: close",
+    "Run a configured action",
+    // Git menu
+    " Git: {project} ",
+    "branch: ",
+    " Enter:create and switch  Esc:cancel ",
+    " Output goes to the log (L)",
+    " Commit: {project} ",
+    "message: ",
+    "push and open a pull request (gh)",
+    " Enter:commit all tracked changes  Tab:PR  Esc:cancel ",
+    "Git: fetch, pull, branch, stash",
+    "Handoff bundle for a colleague",
+    "Open ticket / set ticket",
+    // Tervezo detail
+    " Actions ",
+    " y/Enter: confirm ",
+    " Confirm ",
+    " Steps ",
+    " Timeline ",
+    "Error loading timeline",
+    "  Loading timeline...",
+    "  Loading older messages...",
+    "  ↑ o: load older messages",
+    "Failed to load",
+    "No plan available",
+    "No file changes",
+    "Press Tab to load changes",
+    "(no diff available)",
+    "No test reports",
+    "Press Tab to load test output",
+    " PASSING ",
+    " FAILING ",
+    " PARTIAL ",
+    " UNKNOWN ",
+    "  Summary",
+    "  Approach",
+    "  Tests Added",
+    "(unknown)",
+    " critical",
+    "  Uncovered Paths",
+    "(unnamed)",
+    "Press Tab to load analysis",
+    "  Loading...",
+    "  Press 'r' to retry",
+    "s:ssh",
+    "s:ssh(loading)",
+    "J/K:scroll(diff)  Enter:collapse",
+    "J/K:navigate  Enter:expand",
+    "J/K:scroll(tab)",
+    "m:md",
+    "m:raw",
+    "p:reply",
+    "p:follow-up",
+    "w:steps",
+    "o:older",
+    "[/]:attempts",
+    "Restart with guidance (empty: retry as is)",
+    "Reply",
+    "Follow-up",
+    " (sending...)",
+    "[{status} - Awaiting reply]",
+    "  Total: {time}",
+    "      ... ({n} more lines)",
+    "New: {n}",
+    "Total: {before}>{after}",
+    "Pre-existing: {n}",
+    "      Verify: {method}",
+    "  Hint: {hint}",
+    " Esc:back  Tab/h/l:tabs  j/k:timeline  ^d/^u:page  g/G:top/btm  {tab}  {md}  r:refresh  {ssh}  a:actions  {steps}  {prompt}  {older}  {attempts}",
+    "Action",
+    "Plan",
+    "Changes",
+    "Tests",
+    "Analysis",
+    "Create PR",
+    "Merge PR",
+    "Close PR",
+    "Reopen PR",
+    "Restart",
+    "Send prompt",
+    "View PR",
+    "Compare with previous attempt",
+    // Usage panel
+    "Current session",
+    "Current week (all models)",
+    "Current session (estimate)",
+    "Sessions",
+    "Current session ({plan})",
+    " {tokens} tokens, resets in {time}",
+    "~{pct}% used",
+    " {cost} | {tokens} tokens | {live} live / {local} local + {remote}T remote",
+    " {cost} | {tokens} tokens | {live} live / {total} total",
+    " Models: {models}",
+    " Time today {time}",
+    " Time today {time} (tracking off)",
+    "{pct}% used",
+    " Resets {time}",
+    // Menus
+    " Quick Switch [1-9/Enter: attach  Esc: close] ",
+    " New Session ",
+    " Dead Session ",
+    "Local session",
+    "Tervezo session",
+    "Resume here",
+    "Fresh session, same cwd",
+    // Toasts
+    "No transcript found for selected session",
+    "Quick prompt needs a session running in a background terminal",
+    "No terminal tabs have exited yet",
+    "Tervezo is not set up",
+    "Restarted → {id}",
+    "{name} is not running",
+    "Not a directory: {path}",
+    "Tervezo {id}: {error}",
+    "Could not kill {name} (pid {pid}): {error}",
+    "The watchdog is off: enable [watchdog] in ~/.c9s/config.toml",
+    "K restarts sessions the watchdog found hung",
+    "Tervezo is not configured: set TERVEZO_API_KEY or [tervezo] api_key",
+    "Imported sessions are read-only: t shows the timeline, d the detail",
+    "Copied 1 line",
+    "Do not disturb off",
+    "Focus timer stopped",
+    "Web mirror stopped",
+    "Break over: back to focus",
+    "No actions configured: add [[actions]] to ~/.c9s/config.toml",
+    "Actions run in local sessions only",
+    "Only local sessions can be rated",
+    "Stats need the session store",
+    "Git actions need a local session",
+    "Nothing to commit",
+    "Only local sessions can be hidden",
+    "Another c9s was started; this one is still running",
+    "Bookmarks unavailable: store not open",
+    "Transcript copied to clipboard",
+    "Handoff bundles are for local sessions",
+    "cwd is on {now} (session was {was})",
+    "on branch {branch}",
+    "{name} looks hung: K restarts it",
+    "Restarted {name}",
+    "Restarting {name} failed: {error}",
+    "Switched to {org}",
+    "Sent to {project}: {prompt}",
+    "Quick prompt failed: {error}",
+    "Copied {n} lines",
+    "Copy failed: {error}",
+    "Palette: {name}",
+    "Do not disturb for {n} min",
+    "Focus for {n} min",
+    "Web mirror: {error}",
+    "Focus interval done: {n} min break",
+    "Failed to save notification level: {error}",
+    "Notifications for {name}: {level}",
+    "Logging terminal output to {path}",
+    "Stopped terminal log for {id}",
+    "Terminal log: {error}",
+    "No ticket for {name} (I to set one)",
+    "No URL template for {ticket} in [tickets]",
+    "Opened {url}",
+    "Failed to open {url}: {error}",
+    "Failed to save ticket: {error}",
+    "{project} linked to {ticket}",
+    "Ticket link for {project} cleared",
+    "Failed to save outcome: {error}",
+    "Failed to read stats: {error}",
+    "git is still running in {project}",
+    "{op} in {project}...",
+    "{project} is excluded in config.toml ([sessions] exclude)",
+    "Failed to save: {error}",
+    "Hid {project}; . lists hidden sessions",
+    "{project} is listed again",
+    "No session matching {id}",
+    "Failed to update bookmark: {error}",
+    " Export Transcript ",
+    " Tab:Markdown/HTML  Enter:export (no file copies)  Esc:cancel ",
+    "format: ",
+    "file: ",
+    "(clipboard)",
+    "Exported to {path}",
+    "Transcript export failed: {error}",
+    "Handoff written to {path}",
+    "Handoff failed: {error}",
+    "Side terminal failed: {error}",
+    "CI green again on {branch} ({name})",
+    "CI failed on {branch} ({name})",
+    "Already at the latest list state",
+    "No earlier list state",
+    "No later attempt",
+    "No earlier attempt",
+    "Mirroring the attached terminal at {url} (copied)",
+    "Mirroring the attached terminal at {url}",
+    "{op} ok in {project}: {summary}",
+    "{op} failed in {project}: {summary}",
+    "Time tracking started",
+    "Time tracking stopped",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(
+            fill("{n} von {total}", &[("n", "3"), ("total", "9")]),
+            "3 von 9"
+        );
+        assert_eq!(fill("no args", &[]), "no args");
+    }
+
+    #[test]
+    fn test_locale_language() {
+        assert_eq!(language("de_AT.UTF-8"), "de");
+        assert_eq!(language("pt-BR"), "pt");
+        assert!(is_english("en_US.UTF-8"));
+        assert!(is_english("C.UTF-8"));
+        assert!(!is_english("fr_FR"));
+    }
+
+    #[test]
+    fn test_load_catalog_falls_back_to_language() {
+        let dir = std::env::temp_dir().join(format!("c9s-i18n-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("de.toml"),
+            "\"Quit\" = \"Beenden\"\n\"Move up\" = \"\"\n",
+        )
+        .unwrap();
+
+        let catalog = load_catalog(&dir, "de_AT.UTF-8").unwrap();
+        assert_eq!(catalog["Quit"], "Beenden");
+        assert!(!catalog.contains_key("Move up"));
+        assert!(load_catalog(&dir, "fr_FR").is_none());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_template_parses_and_covers_ui() {
+        let template = template();
+        let parsed: HashMap<String, String> = toml::from_str(&template).unwrap();
+        assert_eq!(parsed.len(), MESSAGES.len());
+
+        // Every literal handed to t()/tf() in the views and the toasts of
+        // main.rs and app.rs is in the template.
+        let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+        let views = std::fs::read_dir(src.join("ui")).unwrap();
+        let paths = views
+            .map(|entry| entry.unwrap().path())
+            .chain([src.join("main.rs"), src.join("app.rs")]);
+        let mut seen = 0;
+        for path in paths {
+            let src = std::fs::read_to_string(&path).unwrap_or_default();
+            for msg in translated_literals(&src) {
+                seen += 1;
+                assert!(
+                    MESSAGES.contains(&msg),
+                    "{}: {:?} missing from MESSAGES",
+                    path.display(),
+                    msg
+                );
+            }
+        }
+        assert!(seen > 30, "only found {} translated literals", seen);
+    }
+
+    /// String literals that directly follow a `t(` or `tf(` call.
+    fn translated_literals(src: &str) -> Vec<&str> {
+        let mut out = Vec::new();
+        for call in ["t(", "tf("] {
+            for (i, _) in src.match_indices(call) {
+                let ident = |c: char| c.is_alphanumeric() || c == '_';
+                if src[..i].chars().next_back().is_some_and(ident) {
+                    continue;
+                }
+                let Some(rest) = src[i + call.len()..].trim_start().strip_prefix('"') else {
+                    continue;
+                };
+                out.push(&rest[..rest.find('"').unwrap()]);
+            }
+        }
+        out
+    }
+}
@@ -1,6 +1,8 @@
-use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEventKind};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
 use crate::app::ViewMode;
+use crate::outcome::Outcome;
+use crate::terminal::Motion;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Action {
@@ -24,6 +26,13 @@ pub enum Action {
     Refresh,
     LaunchNew,
     TerminalInput(Vec<u8>),
+    /// A mouse event in terminal view, at its position on the host screen.
+    TerminalMouse(MouseEvent),
+    EnterCopyMode,
+    CopyModeMove(Motion),
+    CopyModeMark,
+    CopyModeYank,
+    CopyModeExit,
     Detach,
     TerminalQSwitcher,
     CycleNextSession,
@@ -47,6 +56,7 @@ pub enum Action {
     TervezoScrollToBottom,
     TervezoSsh,
     TervezoRefreshDetail,
+    TervezoLoadOlder,
     TervezoToggleExpand,
     TervezoToggleRaw,
     TervezoToggleSteps,
@@ -62,6 +72,17 @@ pub enum Action {
     TervezoPromptBackspace,
     TervezoPromptSubmit,
     TervezoPromptCancel,
+    /// Step forward (true) or back through earlier filter, sort and
+    /// selection of the list.
+    ListHistory(bool),
+    /// Next (true) or previous tab of the local session detail.
+    DetailTab(bool),
+    /// Open the next (true) or previous attempt at the same task.
+    TervezoAttempt(bool),
+    TervezoCompareUp,
+    TervezoCompareDown,
+    TervezoCompareToggle,
+    TervezoCompareClose,
     NewSessionMenuUp,
     NewSessionMenuDown,
     NewSessionMenuSelect,
@@ -75,13 +96,117 @@ pub enum Action {
     TervezoCreateBackspace,
     TervezoCreateSubmit,
     ToggleLog,
+    ToggleTerminalLog,
+    ToggleMirror,
+    ShowRecentlyExited,
+    ShowDiagnostics,
+    CyclePalette,
+    CycleNotifyLevel,
+    ToggleDnd,
+    ToggleTimeTracking,
+    ToggleFocusTimer,
     ClearLog,
     KillSession,
     ConfirmKill,
+    /// Kill with SIGTERM even when the session could be sent `/exit`.
+    ForceKill,
+    ConfirmAttach,
+    CancelAttach,
+    DismissError,
     CancelKill,
     UnfollowSession,
     ResumeSessionPicker,
     OpenSessionFiles,
+    CheckMcpHealth,
+    ToggleErrorFilter,
+    ToggleRowPreview,
+    /// Project names, short paths or full paths.
+    CyclePathStyle,
+    OpenUserActions,
+    UserActionsUp,
+    UserActionsDown,
+    UserActionsSelect,
+    UserActionsClose,
+    /// Run the n-th `[[actions]]` entry straight from the menu.
+    UserActionsRun(usize),
+    OpenGitMenu,
+    /// Write a handoff bundle for the selected session.
+    WriteHandoff,
+    GitMenuUp,
+    GitMenuDown,
+    GitMenuSelect,
+    /// Run the menu item with this index directly (its shortcut key).
+    GitMenuPick(usize),
+    GitMenuClose,
+    GitBranchChar(char),
+    GitBranchBackspace,
+    GitBranchSubmit,
+    /// Commit the working diff shown in the detail.
+    OpenCommitPrompt,
+    CommitChar(char),
+    CommitBackspace,
+    /// Whether to push and open a pull request after committing.
+    CommitTogglePr,
+    CommitSubmit,
+    CommitCancel,
+    /// Open the selected session's ticket in the browser.
+    OpenTicket,
+    /// Set or clear the selected session's ticket by hand.
+    EditTicket,
+    TicketChar(char),
+    TicketBackspace,
+    TicketSubmit,
+    /// Rate the selected session's outcome.
+    RateSession,
+    OutcomePick(Outcome),
+    OutcomeRating(u8),
+    OutcomeSubmit,
+    ShowOutcomes,
+    /// Hide the selected session from the list, or list it again.
+    ToggleHideSession,
+    /// List hidden and excluded sessions too.
+    ToggleShowHidden,
+    /// Cost and tokens per day and project, from the store.
+    ShowStats,
+    /// Chart tokens instead of cost in the Stats view, or back.
+    ToggleStatsMetric,
+    ShowDashboard,
+    /// Pick the Tervezo organization requests act for.
+    OpenOrgPicker,
+    OrgPickerUp,
+    OrgPickerDown,
+    OrgPickerSelect,
+    /// Waiting Tervezo implementations, to reorder or cancel.
+    ShowQueue,
+    QueueUp,
+    QueueDown,
+    /// Move the selected implementation one place up.
+    QueueBump,
+    QueueToFront,
+    QueueCancel,
+    /// Answer to "cancel this implementation?".
+    QueueCancelConfirm(bool),
+    /// Kill and resume the selected session the watchdog found hung.
+    RestartHung,
+    ToggleResources,
+    /// A key c9s leaves unbound, offered to plugin actions.
+    PluginKey(char),
+    ShowTimeline,
+    TimelineToggleBookmark,
+    TimelineShowBookmarks,
+    TimelineNextBookmark,
+    TimelinePrevBookmark,
+    TimelineExport,
+    TimelineCopy,
+    ExportChar(char),
+    ExportBackspace,
+    ExportToggleFormat,
+    ExportSubmit,
+    OpenQuickPrompt,
+    QuickPromptChar(char),
+    QuickPromptBackspace,
+    QuickPromptSubmit,
+    QuickPromptCancel,
     SessionFileUp,
     SessionFileDown,
     SessionFileSelect,
@@ -94,31 +219,39 @@ pub enum Action {
 pub fn handle_event(event: &Event, mode: &ViewMode, side_focused: bool) -> Action {
     match event {
         Event::Key(key) => handle_key(key, mode, side_focused),
-        Event::Mouse(mouse) => handle_mouse(mouse.kind, mode),
+        Event::Mouse(mouse) => handle_mouse(mouse, mode),
         _ => Action::None,
     }
 }
 
-fn handle_mouse(kind: MouseEventKind, mode: &ViewMode) -> Action {
-    match kind {
+fn handle_mouse(mouse: &MouseEvent, mode: &ViewMode) -> Action {
+    // The terminal decides whether the application inside takes the mouse.
+    if *mode == ViewMode::Terminal {
+        return Action::TerminalMouse(*mouse);
+    }
+    match mouse.kind {
         MouseEventKind::ScrollUp => match mode {
-            ViewMode::Terminal | ViewMode::TerminalQSwitcher => Action::ScrollUp(3),
+            ViewMode::TerminalQSwitcher | ViewMode::RecentlyExited => Action::ScrollUp(3),
             ViewMode::List
             | ViewMode::Filter
             | ViewMode::QSwitcher
             | ViewMode::TervezoQSwitcher
             | ViewMode::Detail
-            | ViewMode::Log => Action::MoveUp,
+            | ViewMode::SessionTimeline
+            | ViewMode::Log
+            | ViewMode::Diagnostics => Action::MoveUp,
             _ => Action::None,
         },
         MouseEventKind::ScrollDown => match mode {
-            ViewMode::Terminal | ViewMode::TerminalQSwitcher => Action::ScrollDown(3),
+            ViewMode::TerminalQSwitcher | ViewMode::RecentlyExited => Action::ScrollDown(3),
             ViewMode::List
             | ViewMode::Filter
             | ViewMode::QSwitcher
             | ViewMode::TervezoQSwitcher
             | ViewMode::Detail
-            | ViewMode::Log => Action::MoveDown,
+            | ViewMode::SessionTimeline
+            | ViewMode::Log
+            | ViewMode::Diagnostics => Action::MoveDown,
             _ => Action::None,
         },
         _ => Action::None,
@@ -135,7 +268,7 @@ fn handle_key(key: &KeyEvent, mode: &ViewMode, side_focused: bool) -> Action {
     }
 
     match mode {
-        ViewMode::Terminal | ViewMode::TerminalQSwitcher => {}
+        ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy => {}
         _ => {
             if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
                 return Action::Quit;
@@ -151,24 +284,64 @@ fn handle_key(key: &KeyEvent, mode: &ViewMode, side_focused: bool) -> Action {
         ViewMode::QSwitcher => handle_qswitcher_key(key),
         ViewMode::Terminal => handle_terminal_key(key),
         ViewMode::TerminalQSwitcher => handle_terminal_qswitcher_key(key),
+        ViewMode::TerminalCopy => handle_terminal_copy_key(key),
         ViewMode::Command => handle_command_key(key),
         ViewMode::ConfirmQuit => handle_confirm_quit_key(key),
         ViewMode::ConfirmKill => handle_confirm_kill_key(key),
+        ViewMode::ConfirmAttach => handle_confirm_attach_key(key),
+        ViewMode::ErrorModal => handle_error_modal_key(key),
         ViewMode::SessionFilePicker => handle_session_file_picker_key(key),
         ViewMode::TervezoDetail => handle_tervezo_detail_key(key),
         ViewMode::TervezoQSwitcher => handle_qswitcher_key(key),
         ViewMode::TervezoActionMenu => handle_tervezo_action_menu_key(key),
         ViewMode::TervezoConfirm => handle_tervezo_confirm_key(key),
         ViewMode::TervezoPromptInput => handle_tervezo_prompt_key(key),
+        ViewMode::TervezoCompare => handle_tervezo_compare_key(key),
+        ViewMode::QuickPrompt => handle_quick_prompt_key(key),
         ViewMode::TervezoCreateDialog => handle_tervezo_create_key(key),
         ViewMode::NewSessionMenu => handle_new_session_menu_key(key),
+        ViewMode::ActionMenu => handle_action_menu_key(key),
+        ViewMode::GitMenu => handle_git_menu_key(key),
+        ViewMode::OrgPicker => handle_org_picker_key(key),
+        ViewMode::Queue => handle_queue_key(key),
+        ViewMode::QueueConfirm => handle_queue_confirm_key(key),
+        ViewMode::GitBranchName => handle_git_branch_key(key),
+        ViewMode::GitCommit => handle_git_commit_key(key),
+        ViewMode::TicketInput => handle_ticket_key(key),
+        ViewMode::TimelineExport => handle_export_key(key),
+        ViewMode::OutcomePrompt => handle_outcome_prompt_key(key),
+        ViewMode::Outcomes => handle_outcomes_key(key),
+        ViewMode::Stats => handle_stats_key(key),
+        ViewMode::Dashboard => handle_dashboard_key(key),
         ViewMode::Log => handle_log_key(key),
+        ViewMode::Diagnostics => handle_diagnostics_key(key),
+        ViewMode::SessionTimeline => handle_session_timeline_key(key),
+        ViewMode::RecentlyExited => handle_recently_exited_key(key),
+        ViewMode::Detail => handle_detail_key(key),
+        _ => handle_normal_key(key),
+    }
+}
+
+fn handle_detail_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Tab => Action::DetailTab(true),
+        KeyCode::BackTab => Action::DetailTab(false),
+        KeyCode::Char('c') => Action::OpenCommitPrompt,
         _ => handle_normal_key(key),
     }
 }
 
 fn handle_normal_key(key: &KeyEvent) -> Action {
+    if key.modifiers.contains(KeyModifiers::CONTROL) {
+        return match key.code {
+            KeyCode::Char('o') => Action::ListHistory(false),
+            KeyCode::Char('i') => Action::ListHistory(true),
+            _ => Action::None,
+        };
+    }
     match key.code {
+        // Terminals send Ctrl+i as Tab.
+        KeyCode::Tab => Action::ListHistory(true),
         KeyCode::Char('q') => Action::Quit,
         KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
         KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
@@ -178,7 +351,17 @@ fn handle_normal_key(key: &KeyEvent) -> Action {
         KeyCode::Esc => Action::Back,
         KeyCode::Char('d') => Action::ShowDetail,
         KeyCode::Char('f') => Action::OpenSessionFiles,
+        KeyCode::Char('m') => Action::CheckMcpHealth,
+        KeyCode::Char('E') => Action::ToggleErrorFilter,
+        KeyCode::Char('e') => Action::ToggleRowPreview,
+        KeyCode::Char('t') => Action::ShowTimeline,
+        KeyCode::Char('>') => Action::OpenQuickPrompt,
         KeyCode::Char('a') => Action::AttachSession,
+        KeyCode::Char('A') => Action::OpenUserActions,
+        KeyCode::Char('v') => Action::OpenGitMenu,
+        KeyCode::Char('H') => Action::WriteHandoff,
+        KeyCode::Char('i') => Action::OpenTicket,
+        KeyCode::Char('I') => Action::EditTicket,
         KeyCode::Char('?') => Action::ShowHelp,
         KeyCode::Char('/') => Action::ToggleFilter,
         KeyCode::Char('s') => Action::CycleSort,
@@ -188,8 +371,30 @@ fn handle_normal_key(key: &KeyEvent) -> Action {
         KeyCode::Char('u') => Action::UnfollowSession,
         KeyCode::Char('x') => Action::KillSession,
         KeyCode::Char('L') => Action::ToggleLog,
+        KeyCode::Char('O') => Action::ToggleTerminalLog,
+        KeyCode::Char('w') => Action::ToggleMirror,
+        KeyCode::Char('X') => Action::ShowRecentlyExited,
+        KeyCode::Char('D') => Action::ShowDiagnostics,
+        KeyCode::Char('P') => Action::CyclePalette,
+        KeyCode::Char('p') => Action::CyclePathStyle,
+        KeyCode::Char('b') => Action::CycleNotifyLevel,
+        KeyCode::Char('z') => Action::ToggleDnd,
+        KeyCode::Char('T') => Action::ToggleTimeTracking,
+        KeyCode::Char('F') => Action::ToggleFocusTimer,
+        KeyCode::Char('R') => Action::RateSession,
+        KeyCode::Char('U') => Action::ShowOutcomes,
+        KeyCode::Char('S') => Action::ShowStats,
+        KeyCode::Char('h') => Action::ToggleHideSession,
+        KeyCode::Char('.') => Action::ToggleShowHidden,
+        KeyCode::Char('B') => Action::ShowDashboard,
+        KeyCode::Char('K') => Action::RestartHung,
+        KeyCode::Char('M') => Action::ToggleResources,
+        KeyCode::Char('C') => Action::OpenOrgPicker,
+        KeyCode::Char('Q') => Action::ShowQueue,
         KeyCode::Char(' ') => Action::ToggleQSwitcher,
         KeyCode::Char(c @ '1'..='9') => Action::AttachByIndex((c as usize) - ('1' as usize)),
+        // Left to plugin actions.
+        KeyCode::Char(c) => Action::PluginKey(c),
         _ => Action::None,
     }
 }
@@ -224,6 +429,8 @@ fn handle_terminal_key(key: &KeyEvent) -> Action {
             KeyCode::Char('p') => return Action::CyclePrevSession,
             KeyCode::Char('k') => return Action::ScrollUp(10),
             KeyCode::Char('j') => return Action::ScrollDown(10),
+            // Terminals report Ctrl+] as either.
+            KeyCode::Char(']') | KeyCode::Char('5') => return Action::EnterCopyMode,
             _ => {}
         }
     }
@@ -231,6 +438,28 @@ fn handle_terminal_key(key: &KeyEvent) -> Action {
     Action::TerminalInput(key_event_to_bytes(key))
 }
 
+fn handle_terminal_copy_key(key: &KeyEvent) -> Action {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        return Action::CopyModeExit;
+    }
+    match key.code {
+        KeyCode::Char('h') | KeyCode::Left => Action::CopyModeMove(Motion::Left),
+        KeyCode::Char('l') | KeyCode::Right => Action::CopyModeMove(Motion::Right),
+        KeyCode::Char('k') | KeyCode::Up => Action::CopyModeMove(Motion::Up),
+        KeyCode::Char('j') | KeyCode::Down => Action::CopyModeMove(Motion::Down),
+        KeyCode::PageUp => Action::CopyModeMove(Motion::PageUp),
+        KeyCode::PageDown => Action::CopyModeMove(Motion::PageDown),
+        KeyCode::Char('0') | KeyCode::Home => Action::CopyModeMove(Motion::LineStart),
+        KeyCode::Char('$') | KeyCode::End => Action::CopyModeMove(Motion::LineEnd),
+        KeyCode::Char('g') => Action::CopyModeMove(Motion::Top),
+        KeyCode::Char('G') => Action::CopyModeMove(Motion::Bottom),
+        KeyCode::Char('v') | KeyCode::Char(' ') => Action::CopyModeMark,
+        KeyCode::Char('y') | KeyCode::Enter => Action::CopyModeYank,
+        KeyCode::Esc | KeyCode::Char('q') => Action::CopyModeExit,
+        _ => Action::None,
+    }
+}
+
 fn handle_terminal_qswitcher_key(key: &KeyEvent) -> Action {
     if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('d') {
         return Action::Detach;
@@ -274,11 +503,27 @@ fn handle_session_file_picker_key(key: &KeyEvent) -> Action {
 fn handle_confirm_kill_key(key: &KeyEvent) -> Action {
     match key.code {
         KeyCode::Char('y') | KeyCode::Enter => Action::ConfirmKill,
+        KeyCode::Char('f') => Action::ForceKill,
         KeyCode::Char('n') | KeyCode::Esc => Action::CancelKill,
         _ => Action::None,
     }
 }
 
+fn handle_confirm_attach_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => Action::ConfirmAttach,
+        KeyCode::Char('n') | KeyCode::Esc => Action::CancelAttach,
+        _ => Action::None,
+    }
+}
+
+fn handle_error_modal_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => Action::DismissError,
+        _ => Action::None,
+    }
+}
+
 fn handle_tervezo_detail_key(key: &KeyEvent) -> Action {
     let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
     match key.code {
@@ -301,10 +546,13 @@ fn handle_tervezo_detail_key(key: &KeyEvent) -> Action {
         KeyCode::Enter => Action::TervezoToggleExpand,
         KeyCode::Char('s') => Action::TervezoSsh,
         KeyCode::Char('r') => Action::TervezoRefreshDetail,
+        KeyCode::Char('o') => Action::TervezoLoadOlder,
         KeyCode::Char('m') => Action::TervezoToggleRaw,
         KeyCode::Char('w') => Action::TervezoToggleSteps,
         KeyCode::Char('a') => Action::TervezoOpenActionMenu,
         KeyCode::Char('p') => Action::TervezoOpenPrompt,
+        KeyCode::Char('[') => Action::TervezoAttempt(false),
+        KeyCode::Char(']') => Action::TervezoAttempt(true),
         KeyCode::Char(' ') => Action::ToggleQSwitcher,
         KeyCode::Char(c @ '1'..='9') => Action::AttachByIndex((c as usize) - ('1' as usize)),
         _ => Action::None,
@@ -321,6 +569,16 @@ fn handle_tervezo_action_menu_key(key: &KeyEvent) -> Action {
     }
 }
 
+fn handle_tervezo_compare_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::TervezoCompareDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::TervezoCompareUp,
+        KeyCode::Enter | KeyCode::Char('e') => Action::TervezoCompareToggle,
+        KeyCode::Esc | KeyCode::Char('q') => Action::TervezoCompareClose,
+        _ => Action::None,
+    }
+}
+
 fn handle_tervezo_confirm_key(key: &KeyEvent) -> Action {
     match key.code {
         KeyCode::Char('y') | KeyCode::Enter => Action::TervezoConfirmYes,
@@ -339,6 +597,16 @@ fn handle_tervezo_prompt_key(key: &KeyEvent) -> Action {
     }
 }
 
+fn handle_quick_prompt_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => Action::QuickPromptSubmit,
+        KeyCode::Esc => Action::QuickPromptCancel,
+        KeyCode::Backspace => Action::QuickPromptBackspace,
+        KeyCode::Char(c) => Action::QuickPromptChar(c),
+        _ => Action::None,
+    }
+}
+
 fn handle_tervezo_create_key(key: &KeyEvent) -> Action {
     let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
     match key.code {
@@ -363,6 +631,138 @@ fn handle_new_session_menu_key(key: &KeyEvent) -> Action {
     }
 }
 
+fn handle_action_menu_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::UserActionsDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::UserActionsUp,
+        KeyCode::Enter => Action::UserActionsSelect,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('A') => Action::UserActionsClose,
+        KeyCode::Char(c @ '1'..='9') => Action::UserActionsRun((c as usize) - ('1' as usize)),
+        _ => Action::None,
+    }
+}
+
+fn handle_org_picker_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::OrgPickerDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::OrgPickerUp,
+        KeyCode::Enter => Action::OrgPickerSelect,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('C') => Action::Back,
+        _ => Action::None,
+    }
+}
+
+fn handle_queue_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::QueueDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::QueueUp,
+        KeyCode::Char('+') => Action::QueueBump,
+        KeyCode::Char('t') => Action::QueueToFront,
+        KeyCode::Char('x') => Action::QueueCancel,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('Q') => Action::Back,
+        _ => Action::None,
+    }
+}
+
+fn handle_queue_confirm_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('y') | KeyCode::Enter => Action::QueueCancelConfirm(true),
+        KeyCode::Char('n') | KeyCode::Esc | KeyCode::Char('q') => Action::QueueCancelConfirm(false),
+        _ => Action::None,
+    }
+}
+
+fn handle_git_menu_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Action::GitMenuDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::GitMenuUp,
+        KeyCode::Enter => Action::GitMenuSelect,
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('v') => Action::GitMenuClose,
+        // Shortcuts in menu order: fetch, pull, branch, stash.
+        KeyCode::Char('f') => Action::GitMenuPick(0),
+        KeyCode::Char('p') => Action::GitMenuPick(1),
+        KeyCode::Char('b') => Action::GitMenuPick(2),
+        KeyCode::Char('s') => Action::GitMenuPick(3),
+        _ => Action::None,
+    }
+}
+
+fn handle_git_branch_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => Action::GitBranchSubmit,
+        KeyCode::Esc => Action::GitMenuClose,
+        KeyCode::Backspace => Action::GitBranchBackspace,
+        KeyCode::Char(c) => Action::GitBranchChar(c),
+        _ => Action::None,
+    }
+}
+
+fn handle_git_commit_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => Action::CommitSubmit,
+        KeyCode::Esc => Action::CommitCancel,
+        KeyCode::Tab => Action::CommitTogglePr,
+        KeyCode::Backspace => Action::CommitBackspace,
+        KeyCode::Char(c) => Action::CommitChar(c),
+        _ => Action::None,
+    }
+}
+
+fn handle_ticket_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => Action::TicketSubmit,
+        KeyCode::Esc => Action::Back,
+        KeyCode::Backspace => Action::TicketBackspace,
+        KeyCode::Char(c) => Action::TicketChar(c),
+        _ => Action::None,
+    }
+}
+
+fn handle_export_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Enter => Action::ExportSubmit,
+        KeyCode::Esc => Action::Back,
+        KeyCode::Tab => Action::ExportToggleFormat,
+        KeyCode::Backspace => Action::ExportBackspace,
+        KeyCode::Char(c) => Action::ExportChar(c),
+        _ => Action::None,
+    }
+}
+
+fn handle_outcome_prompt_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Char('s') => Action::OutcomePick(Outcome::Shipped),
+        KeyCode::Char('a') => Action::OutcomePick(Outcome::Abandoned),
+        KeyCode::Char('n') => Action::OutcomePick(Outcome::NeedsHuman),
+        KeyCode::Char(c @ '1'..='5') => Action::OutcomeRating(c as u8 - b'0'),
+        KeyCode::Enter => Action::OutcomeSubmit,
+        KeyCode::Esc => Action::Back,
+        _ => Action::None,
+    }
+}
+
+fn handle_outcomes_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('U') => Action::Back,
+        _ => Action::None,
+    }
+}
+
+fn handle_stats_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('S') => Action::Back,
+        KeyCode::Tab => Action::ToggleStatsMetric,
+        _ => Action::None,
+    }
+}
+
+fn handle_dashboard_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('B') => Action::Back,
+        _ => Action::None,
+    }
+}
+
 fn handle_log_key(key: &KeyEvent) -> Action {
     match key.code {
         KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('L') => Action::ToggleLog,
@@ -375,6 +775,50 @@ fn handle_log_key(key: &KeyEvent) -> Action {
     }
 }
 
+fn handle_diagnostics_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('D') => Action::Back,
+        KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+        KeyCode::Char('g') => Action::MoveToTop,
+        KeyCode::Char('G') => Action::MoveToBottom,
+        _ => Action::None,
+    }
+}
+
+fn handle_session_timeline_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('t') => Action::Back,
+        KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+        KeyCode::Char('g') => Action::MoveToTop,
+        KeyCode::Char('G') => Action::MoveToBottom,
+        KeyCode::Char('r') => Action::Refresh,
+        KeyCode::Char('b') => Action::TimelineToggleBookmark,
+        KeyCode::Char('B') => Action::TimelineShowBookmarks,
+        KeyCode::Char('n') => Action::TimelineNextBookmark,
+        KeyCode::Char('N') => Action::TimelinePrevBookmark,
+        KeyCode::Char('e') => Action::TimelineExport,
+        KeyCode::Char('y') => Action::TimelineCopy,
+        KeyCode::Enter => Action::Select,
+        _ => Action::None,
+    }
+}
+
+fn handle_recently_exited_key(key: &KeyEvent) -> Action {
+    match key.code {
+        KeyCode::Esc | KeyCode::Char('q') => Action::Back,
+        KeyCode::Char('j') | KeyCode::Down => Action::MoveDown,
+        KeyCode::Char('k') | KeyCode::Up => Action::MoveUp,
+        KeyCode::PageDown => Action::ScrollDown(20),
+        KeyCode::PageUp => Action::ScrollUp(20),
+        KeyCode::Char('g') => Action::MoveToTop,
+        KeyCode::Char('G') => Action::MoveToBottom,
+        KeyCode::Enter => Action::Select,
+        _ => Action::None,
+    }
+}
+
 fn handle_command_key(key: &KeyEvent) -> Action {
     match key.code {
         KeyCode::Esc => Action::CommandCancel,
@@ -536,6 +980,10 @@ mod tests {
             handle_tervezo_detail_key(&key(KeyCode::Char('r'))),
             Action::TervezoRefreshDetail
         );
+        assert_eq!(
+            handle_tervezo_detail_key(&key(KeyCode::Char('o'))),
+            Action::TervezoLoadOlder
+        );
         assert_eq!(
             handle_tervezo_detail_key(&key(KeyCode::Char('a'))),
             Action::TervezoOpenActionMenu
@@ -590,4 +1038,231 @@ mod tests {
         let action = handle_normal_key(&key(KeyCode::Char('c')));
         assert_eq!(action, Action::FixCi);
     }
+
+    #[test]
+    fn test_quick_prompt_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('>'))),
+            Action::OpenQuickPrompt
+        );
+        // Letters that are list shortcuts must be typed, not dispatched.
+        let action = handle_key(&key(KeyCode::Char('q')), &ViewMode::QuickPrompt, false);
+        assert_eq!(action, Action::QuickPromptChar('q'));
+        let action = handle_key(&key(KeyCode::Enter), &ViewMode::QuickPrompt, false);
+        assert_eq!(action, Action::QuickPromptSubmit);
+    }
+
+    #[test]
+    fn test_copy_mode_keys() {
+        let ctrl_bracket = key_with_mod(KeyCode::Char(']'), KeyModifiers::CONTROL);
+        let action = handle_key(&ctrl_bracket, &ViewMode::Terminal, false);
+        assert_eq!(action, Action::EnterCopyMode);
+        let action = handle_key(&key(KeyCode::Up), &ViewMode::TerminalCopy, false);
+        assert_eq!(action, Action::CopyModeMove(Motion::Up));
+        let action = handle_key(&key(KeyCode::Char('v')), &ViewMode::TerminalCopy, false);
+        assert_eq!(action, Action::CopyModeMark);
+        let action = handle_key(&key(KeyCode::Char('y')), &ViewMode::TerminalCopy, false);
+        assert_eq!(action, Action::CopyModeYank);
+        // Ctrl+C leaves copy mode rather than quitting.
+        let ctrl_c = key_with_mod(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        assert_eq!(
+            handle_key(&ctrl_c, &ViewMode::TerminalCopy, false),
+            Action::CopyModeExit
+        );
+    }
+
+    #[test]
+    fn test_diagnostics_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('D'))),
+            Action::ShowDiagnostics
+        );
+        let action = handle_key(&key(KeyCode::Char('D')), &ViewMode::Diagnostics, false);
+        assert_eq!(action, Action::Back);
+        let action = handle_key(&key(KeyCode::Char('j')), &ViewMode::Diagnostics, false);
+        assert_eq!(action, Action::MoveDown);
+    }
+
+    #[test]
+    fn test_row_preview_key() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('e'))),
+            Action::ToggleRowPreview
+        );
+    }
+
+    #[test]
+    fn test_action_menu_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('A'))),
+            Action::OpenUserActions
+        );
+        let menu = |code| handle_key(&key(code), &ViewMode::ActionMenu, false);
+        assert_eq!(menu(KeyCode::Char('2')), Action::UserActionsRun(1));
+        assert_eq!(menu(KeyCode::Enter), Action::UserActionsSelect);
+        assert_eq!(menu(KeyCode::Esc), Action::UserActionsClose);
+    }
+
+    #[test]
+    fn test_handoff_key() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('H'))),
+            Action::WriteHandoff
+        );
+    }
+
+    #[test]
+    fn test_mirror_key() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('w'))),
+            Action::ToggleMirror
+        );
+    }
+
+    #[test]
+    fn test_git_menu_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('v'))),
+            Action::OpenGitMenu
+        );
+        let menu = |code| handle_key(&key(code), &ViewMode::GitMenu, false);
+        assert_eq!(menu(KeyCode::Char('p')), Action::GitMenuPick(1));
+        assert_eq!(menu(KeyCode::Enter), Action::GitMenuSelect);
+        let name = |code| handle_key(&key(code), &ViewMode::GitBranchName, false);
+        assert_eq!(name(KeyCode::Char('s')), Action::GitBranchChar('s'));
+        assert_eq!(name(KeyCode::Enter), Action::GitBranchSubmit);
+        let commit = |code| handle_key(&key(code), &ViewMode::GitCommit, false);
+        assert_eq!(commit(KeyCode::Char('q')), Action::CommitChar('q'));
+        assert_eq!(commit(KeyCode::Tab), Action::CommitTogglePr);
+        assert_eq!(commit(KeyCode::Esc), Action::CommitCancel);
+        assert_eq!(
+            handle_key(&key(KeyCode::Char('c')), &ViewMode::Detail, false),
+            Action::OpenCommitPrompt
+        );
+    }
+
+    #[test]
+    fn test_ticket_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('i'))),
+            Action::OpenTicket
+        );
+        let input = |code| handle_key(&key(code), &ViewMode::TicketInput, false);
+        assert_eq!(input(KeyCode::Char('i')), Action::TicketChar('i'));
+        assert_eq!(input(KeyCode::Enter), Action::TicketSubmit);
+        assert_eq!(input(KeyCode::Esc), Action::Back);
+    }
+
+    #[test]
+    fn test_export_prompt_keys() {
+        let input = |code| handle_key(&key(code), &ViewMode::TimelineExport, false);
+        assert_eq!(input(KeyCode::Char('e')), Action::ExportChar('e'));
+        assert_eq!(input(KeyCode::Tab), Action::ExportToggleFormat);
+        assert_eq!(input(KeyCode::Enter), Action::ExportSubmit);
+        assert_eq!(input(KeyCode::Esc), Action::Back);
+    }
+
+    #[test]
+    fn test_outcome_prompt_keys() {
+        let prompt = |code| handle_key(&key(code), &ViewMode::OutcomePrompt, false);
+        assert_eq!(
+            prompt(KeyCode::Char('n')),
+            Action::OutcomePick(Outcome::NeedsHuman)
+        );
+        assert_eq!(prompt(KeyCode::Char('4')), Action::OutcomeRating(4));
+        assert_eq!(prompt(KeyCode::Char('6')), Action::None);
+        assert_eq!(prompt(KeyCode::Esc), Action::Back);
+    }
+
+    #[test]
+    fn test_dashboard_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('B'))),
+            Action::ShowDashboard
+        );
+        let dashboard = |code| handle_key(&key(code), &ViewMode::Dashboard, false);
+        assert_eq!(dashboard(KeyCode::Char('B')), Action::Back);
+        assert_eq!(dashboard(KeyCode::Char('j')), Action::None);
+    }
+
+    #[test]
+    fn test_org_picker_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('C'))),
+            Action::OpenOrgPicker
+        );
+        let picker = |code| handle_key(&key(code), &ViewMode::OrgPicker, false);
+        assert_eq!(picker(KeyCode::Char('j')), Action::OrgPickerDown);
+        assert_eq!(picker(KeyCode::Enter), Action::OrgPickerSelect);
+        assert_eq!(picker(KeyCode::Esc), Action::Back);
+    }
+
+    #[test]
+    fn test_queue_keys() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('Q'))),
+            Action::ShowQueue
+        );
+        let queue = |code| handle_key(&key(code), &ViewMode::Queue, false);
+        assert_eq!(queue(KeyCode::Char('+')), Action::QueueBump);
+        assert_eq!(queue(KeyCode::Char('x')), Action::QueueCancel);
+        let confirm = |code| handle_key(&key(code), &ViewMode::QueueConfirm, false);
+        assert_eq!(
+            confirm(KeyCode::Char('y')),
+            Action::QueueCancelConfirm(true)
+        );
+        assert_eq!(confirm(KeyCode::Esc), Action::QueueCancelConfirm(false));
+    }
+
+    #[test]
+    fn test_tervezo_compare_keys() {
+        let compare = |code| handle_key(&key(code), &ViewMode::TervezoCompare, false);
+        assert_eq!(compare(KeyCode::Char('j')), Action::TervezoCompareDown);
+        assert_eq!(compare(KeyCode::Enter), Action::TervezoCompareToggle);
+        assert_eq!(compare(KeyCode::Esc), Action::TervezoCompareClose);
+        let detail = |code| handle_key(&key(code), &ViewMode::TervezoDetail, false);
+        assert_eq!(detail(KeyCode::Char('[')), Action::TervezoAttempt(false));
+        assert_eq!(detail(KeyCode::Char(']')), Action::TervezoAttempt(true));
+    }
+
+    #[test]
+    fn test_list_history_keys() {
+        let ctrl = |c| handle_normal_key(&key_with_mod(KeyCode::Char(c), KeyModifiers::CONTROL));
+        assert_eq!(ctrl('o'), Action::ListHistory(false));
+        assert_eq!(ctrl('i'), Action::ListHistory(true));
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Tab)),
+            Action::ListHistory(true)
+        );
+        assert_eq!(ctrl('x'), Action::None);
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('h'))),
+            Action::ToggleHideSession
+        );
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('.'))),
+            Action::ToggleShowHidden
+        );
+        let detail = |code| handle_key(&key(code), &ViewMode::Detail, false);
+        assert_eq!(detail(KeyCode::Tab), Action::DetailTab(true));
+        assert_eq!(detail(KeyCode::BackTab), Action::DetailTab(false));
+        assert_eq!(detail(KeyCode::Char('t')), Action::ShowTimeline);
+    }
+
+    #[test]
+    fn test_unbound_keys_go_to_plugins() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('W'))),
+            Action::PluginKey('W')
+        );
+        assert_eq!(handle_normal_key(&key(KeyCode::F(5))), Action::None);
+    }
+
+    #[test]
+    fn test_palette_key() {
+        assert_eq!(
+            handle_normal_key(&key(KeyCode::Char('P'))),
+            Action::CyclePalette
+        );
+    }
 }
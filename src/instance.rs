@@ -0,0 +1,250 @@
+//! The one c9s that manages sessions at a time. It holds the control
+//! socket, which `c9s open` sends links to and a second c9s knocks on or
+//! takes over through, and a locked pid file that decides which c9s that is.
+
+use anyhow::Context;
+use std::fmt;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::os::unix::io::AsRawFd;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver};
+use std::time::{Duration, Instant};
+
+use crate::deeplink::DeepLink;
+
+const SOCKET: &str = "ctl.sock";
+const PID_FILE: &str = "c9s.pid";
+
+/// How long a takeover waits for the running c9s to quit, before and
+/// after SIGTERM.
+const TAKEOVER_WAIT: Duration = Duration::from_secs(3);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// Show what the link points at.
+    Link(DeepLink),
+    /// Another c9s was started and refused.
+    Knock,
+    /// Another c9s was started with `--force`: quit and leave it the socket.
+    Takeover,
+}
+
+impl Message {
+    fn parse(line: &str) -> Option<Self> {
+        match line.trim() {
+            "knock" => Some(Self::Knock),
+            "takeover" => Some(Self::Takeover),
+            link => DeepLink::parse(link).ok().map(Self::Link),
+        }
+    }
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Link(link) => write!(f, "{}", link),
+            Self::Knock => f.write_str("knock"),
+            Self::Takeover => f.write_str("takeover"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ClaimError {
+    #[error("c9s is already running{}", .0.map(|pid| format!(" (pid {})", pid)).unwrap_or_default())]
+    Running(Option<u32>),
+    #[error("the running c9s{} did not quit", .0.map(|pid| format!(" (pid {})", pid)).unwrap_or_default())]
+    NotQuit(Option<u32>),
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// Sends `message` to the running c9s. False when none is running.
+pub fn send(message: &Message) -> anyhow::Result<bool> {
+    send_to(&path(SOCKET)?, message)
+}
+
+fn send_to(socket: &Path, message: &Message) -> anyhow::Result<bool> {
+    let Ok(mut stream) = UnixStream::connect(socket) else {
+        return Ok(false);
+    };
+    writeln!(stream, "{}", message)?;
+    Ok(true)
+}
+
+fn path(name: &str) -> anyhow::Result<PathBuf> {
+    crate::config::config_dir()
+        .map(|d| d.join(name))
+        .context("no home directory")
+}
+
+/// Takes the exclusive lock on the open pid file. False while another c9s
+/// holds it.
+fn try_lock(file: &File) -> std::io::Result<bool> {
+    // SAFETY: flock on a descriptor `file` keeps open.
+    if unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+        return Ok(true);
+    }
+    let err = std::io::Error::last_os_error();
+    if err.kind() == std::io::ErrorKind::WouldBlock {
+        Ok(false)
+    } else {
+        Err(err)
+    }
+}
+
+/// Waits up to `TAKEOVER_WAIT` for the running c9s to let go of the lock.
+fn wait_for_lock(file: &File) -> std::io::Result<bool> {
+    let started = Instant::now();
+    while started.elapsed() < TAKEOVER_WAIT {
+        if try_lock(file)? {
+            return Ok(true);
+        }
+        std::thread::sleep(Duration::from_millis(100));
+    }
+    Ok(false)
+}
+
+/// This c9s's claim on being the running one; given up when dropped.
+pub struct Instance {
+    socket: PathBuf,
+    /// Locked for as long as this c9s runs; the lock, not the socket,
+    /// decides which c9s is the running one.
+    pid_file: File,
+    rx: Receiver<Message>,
+}
+
+impl Instance {
+    /// Becomes the running c9s. With `force` an existing one is asked to
+    /// quit, and sent SIGTERM if it doesn't.
+    pub fn claim(force: bool) -> Result<Self, ClaimError> {
+        let dir = crate::config::config_dir().context("no home directory")?;
+        Self::claim_in(&dir, force)
+    }
+
+    fn claim_in(dir: &Path, force: bool) -> Result<Self, ClaimError> {
+        std::fs::create_dir_all(dir).with_context(|| format!("cannot create {}", dir.display()))?;
+        let socket = dir.join(SOCKET);
+        let pid_path = dir.join(PID_FILE);
+        let pid_file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&pid_path)
+            .with_context(|| format!("cannot open {}", pid_path.display()))?;
+        if !try_lock(&pid_file).context("cannot lock the pid file")? {
+            let pid = std::fs::read_to_string(&pid_path)
+                .ok()
+                .and_then(|p| p.trim().parse::<u32>().ok());
+            if !force {
+                return Err(ClaimError::Running(pid));
+            }
+            send_to(&socket, &Message::Takeover)?;
+            if !wait_for_lock(&pid_file).context("cannot lock the pid file")? {
+                if let Some(pid) = pid {
+                    // SAFETY: signals the process the pid file names.
+                    unsafe {
+                        libc::kill(pid as i32, libc::SIGTERM);
+                    }
+                }
+                if !wait_for_lock(&pid_file).context("cannot lock the pid file")? {
+                    return Err(ClaimError::NotQuit(pid));
+                }
+            }
+        }
+        Ok(Self::listen(socket, pid_file)?)
+    }
+
+    /// Binds the socket and records our pid; the caller holds the lock.
+    fn listen(socket: PathBuf, mut pid_file: File) -> anyhow::Result<Self> {
+        // Left behind by a c9s that didn't exit cleanly.
+        if socket.exists() {
+            std::fs::remove_file(&socket)?;
+        }
+        let listener = UnixListener::bind(&socket)
+            .with_context(|| format!("cannot listen on {}", socket.display()))?;
+        std::fs::set_permissions(&socket, std::fs::Permissions::from_mode(0o600))?;
+        pid_file.set_len(0)?;
+        writeln!(pid_file, "{}", std::process::id()).context("cannot write the pid file")?;
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut line = String::new();
+                let _ = BufReader::new(stream.take(4096)).read_line(&mut line);
+                let Some(message) = Message::parse(&line) else {
+                    continue;
+                };
+                if tx.send(message).is_err() {
+                    break;
+                }
+                crate::wakeup::notify();
+            }
+        });
+        Ok(Self {
+            socket,
+            pid_file,
+            rx,
+        })
+    }
+
+    pub fn try_recv(&self) -> Option<Message> {
+        self.rx.try_recv().ok()
+    }
+}
+
+impl Drop for Instance {
+    fn drop(&mut self) {
+        // The pid file stays: removing it would let a c9s that already
+        // opened it lock the unlinked file while another creates a new
+        // one. Closing it releases the lock.
+        let _ = std::fs::remove_file(&self.socket);
+        let _ = self.pid_file.set_len(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        for message in [
+            Message::Knock,
+            Message::Takeover,
+            Message::Link(DeepLink::Attach("abc".to_string())),
+        ] {
+            assert_eq!(Message::parse(&format!("{}\n", message)), Some(message));
+        }
+        assert_eq!(Message::parse(""), None);
+        assert_eq!(Message::parse("quit"), None);
+        assert_eq!(
+            ClaimError::Running(Some(42)).to_string(),
+            "c9s is already running (pid 42)"
+        );
+        assert_eq!(
+            ClaimError::Running(None).to_string(),
+            "c9s is already running"
+        );
+    }
+
+    #[test]
+    fn test_claim_is_exclusive() {
+        let dir = tempfile::tempdir().unwrap();
+        let first = Instance::claim_in(dir.path(), false).unwrap();
+        let pid = std::process::id();
+        assert!(matches!(
+            Instance::claim_in(dir.path(), false),
+            Err(ClaimError::Running(Some(p))) if p == pid
+        ));
+        drop(first);
+        assert!(!dir.path().join(SOCKET).exists());
+        let second = Instance::claim_in(dir.path(), false).unwrap();
+        assert!(dir.path().join(SOCKET).exists());
+        drop(second);
+    }
+}
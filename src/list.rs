@@ -0,0 +1,181 @@
+//! `c9s list`: the sessions as a table, as JSON for scripts, or as an
+//! Alfred script filter so a launcher can jump straight into one.
+
+use anyhow::Result;
+use serde_json::{json, Value};
+
+use crate::cli::ListFormat;
+use crate::config::{shell_quote, AppConfig};
+use crate::deeplink::DeepLink;
+use crate::format;
+use crate::fx;
+use crate::session::{Session, SessionDiscovery};
+
+pub fn run(format: ListFormat) -> Result<()> {
+    let config = AppConfig::load();
+    format::init(config.format.clone());
+    fx::start(&config.currency, false);
+
+    let mut discovery = SessionDiscovery::new();
+    discovery.set_show_all_per_cwd(config.sessions.show_all_per_cwd);
+    let mut sessions: Vec<Session> = discovery
+        .discover_all()?
+        .into_iter()
+        .filter(|s| !s.is_subagent && !config.sessions.excludes(s))
+        .collect();
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+
+    match format {
+        ListFormat::Text => print!("{}", text(&sessions)),
+        ListFormat::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&sessions.iter().map(session_json).collect::<Vec<_>>())?
+        ),
+        ListFormat::Alfred => println!("{}", alfred(&sessions)),
+    }
+    Ok(())
+}
+
+/// A session as scripts and the API see it: its fields plus the
+/// estimated cost.
+pub fn session_json(session: &Session) -> Value {
+    let mut value = serde_json::to_value(session).unwrap_or(Value::Null);
+    value["cost_usd"] = json!(session.estimated_cost_usd());
+    value
+}
+
+fn text(sessions: &[Session]) -> String {
+    let row = |id: &str, status, project, branch, tokens, cost, active| {
+        format!(
+            "{:<36}  {:<9} {:<20.20} {:<24.24} {:>7} {:>9}  {}\n",
+            id, status, project, branch, tokens, cost, active
+        )
+    };
+    let mut out = row(
+        "ID",
+        "STATUS",
+        "PROJECT",
+        "BRANCH",
+        "TOKENS".to_string(),
+        "COST".to_string(),
+        "ACTIVE".to_string(),
+    );
+    for s in sessions {
+        out.push_str(&row(
+            &s.id,
+            s.status.label(),
+            &s.project_name,
+            s.git_branch.as_deref().unwrap_or("-"),
+            format::tokens(s.total_tokens()),
+            format::cost(s.estimated_cost_usd(), 2),
+            format::since(s.last_activity),
+        ));
+    }
+    out
+}
+
+/// The command that continues a session in its directory.
+fn resume_command(session: &Session) -> String {
+    format!(
+        "cd {} && claude --resume {}",
+        shell_quote(&session.cwd.to_string_lossy()),
+        shell_quote(&session.id)
+    )
+}
+
+/// Alfred's script filter JSON, which Raycast script commands read too.
+/// Enter runs the resume command, Cmd opens the project directory, Alt
+/// passes the session id on and Ctrl the session's `c9s://` link.
+fn alfred(sessions: &[Session]) -> Value {
+    let items: Vec<Value> = sessions
+        .iter()
+        .map(|s| {
+            let branch = s.git_branch.as_deref().unwrap_or("-");
+            let cwd = s.cwd.to_string_lossy();
+            json!({
+                "uid": s.id,
+                "title": s.project_name,
+                "subtitle": format!(
+                    "{} · {} · {} · {}",
+                    s.status.label(),
+                    branch,
+                    format::cost(s.estimated_cost_usd(), 2),
+                    format::since(s.last_activity),
+                ),
+                "arg": resume_command(s),
+                "match": format!("{} {} {}", s.project_name, branch, s.id),
+                "autocomplete": s.project_name,
+                "text": { "copy": s.id, "largetype": resume_command(s) },
+                "variables": {
+                    "session_id": s.id,
+                    "cwd": cwd,
+                    "status": s.status.label(),
+                },
+                "mods": {
+                    "cmd": { "arg": cwd, "subtitle": format!("Open {}", cwd) },
+                    "alt": { "arg": s.id, "subtitle": "Pass on the session id" },
+                    "ctrl": {
+                        "arg": DeepLink::Attach(s.id.clone()).to_string(),
+                        "subtitle": "Open in c9s",
+                    },
+                },
+            })
+        })
+        .collect();
+    json!({ "items": items })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, cwd: &str) -> Session {
+        let mut session = Session::fixture(id, cwd);
+        session.project_name = "webshop".to_string();
+        session.git_branch = Some("feature/retry".to_string());
+        session.model = Some("claude-sonnet-4-20250514".to_string());
+        session.input_tokens = 1_000_000;
+        session.message_count = 4;
+        session
+    }
+
+    #[test]
+    fn test_alfred_items() {
+        let sessions = [
+            session("abc-123", "/home/dev/webshop"),
+            session("def-456", "/home/dev/my shop"),
+        ];
+        let out = alfred(&sessions);
+        let items = out["items"].as_array().unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0]["uid"], "abc-123");
+        assert_eq!(items[0]["title"], "webshop");
+        assert_eq!(
+            items[0]["arg"],
+            "cd /home/dev/webshop && claude --resume abc-123"
+        );
+        assert!(items[0]["subtitle"]
+            .as_str()
+            .unwrap()
+            .starts_with("Dead · feature/retry · $3.00 · "));
+        assert_eq!(items[0]["mods"]["alt"]["arg"], "abc-123");
+        assert_eq!(items[0]["mods"]["ctrl"]["arg"], "c9s://attach/abc-123");
+        assert_eq!(
+            items[1]["arg"],
+            "cd '/home/dev/my shop' && claude --resume def-456"
+        );
+
+        let table = text(&sessions);
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].starts_with("ID   ") && lines[0].ends_with("COST  ACTIVE"));
+        assert!(lines[1].starts_with("abc-123        "));
+        assert!(lines[1].contains("  Dead      webshop"));
+        assert!(lines[1].contains("   1.0M     $3.00  "));
+        assert_eq!(lines.len(), 3);
+
+        let json = session_json(&sessions[0]);
+        assert_eq!(json["id"], "abc-123");
+        assert_eq!(json["input_tokens"], 1_000_000);
+        assert_eq!(json["cost_usd"], 3.0);
+    }
+}
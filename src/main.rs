@@ -1,61 +1,124 @@
 mod app;
+mod cli;
+mod clipboard;
+mod completions;
+mod config;
+mod deeplink;
+#[cfg(test)]
+mod fixtures;
+mod format;
+mod fx;
+mod host;
+mod http;
+mod i18n;
 mod input;
-mod log;
-mod session;
-mod store;
+mod instance;
+mod list;
+mod manual;
+mod mirror;
+mod plugin;
+mod pomodoro;
+mod serve;
+mod status;
+mod tasks;
 mod terminal;
-mod tervezo;
+mod ticket;
+mod toast;
 mod ui;
-mod usage;
+mod wakeup;
+mod watchdog;
+
+use c9s_core::{clock, log, outcome, session, store, tervezo, timetrack, tlog, usage};
 
 use anyhow::Result;
 use app::{
     App, SessionEntry, TervezoAction, TervezoCreateMsg, TervezoDetailMsg, TervezoTab, ViewMode,
     WorkspaceMsg,
 };
+use clap::Parser;
+use cli::{Cli, Command, ListFormat, TableFormat};
 use crossterm::event;
-use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture, MouseEventKind};
 use crossterm::terminal::{
     disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
 };
 use crossterm::ExecutableCommand;
+use i18n::{t, tf};
 use input::{handle_event, Action};
 use ratatui::prelude::CrosstermBackend;
 use ratatui::Terminal;
 use session::SessionManager;
 use std::io::{stdout, IsTerminal};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 use std::time::{Duration, Instant};
-use tervezo::{CreateImplementationRequest, TervezoClient};
+use tervezo::api::TIMELINE_PAGE;
+use tervezo::compare::compare_changes;
+use tervezo::{
+    CreateImplementationRequest, ImplementationStatus, TervezoClient, TervezoConfig, TervezoError,
+};
+use toast::Severity;
 
 fn main() -> Result<()> {
-    let args: Vec<String> = std::env::args().collect();
-
-    if args.len() > 1 {
-        match args[1].as_str() {
-            "version" | "--version" | "-v" => {
-                println!("c9s {}", env!("CARGO_PKG_VERSION"));
-                return Ok(());
-            }
-            "help" | "--help" | "-h" => {
-                println!("c9s - Claude Code Session Manager");
-                println!();
-                println!("Usage:");
-                println!("  c9s           Launch the TUI dashboard");
-                println!("  c9s version   Show version");
+    let cli = Cli::parse();
+    let mut link = None;
+    match cli.command {
+        None => {}
+        Some(Command::Open { url }) => {
+            let parsed = deeplink::DeepLink::parse(&url)?;
+            if instance::send(&instance::Message::Link(parsed.clone()))? {
                 return Ok(());
             }
-            other => {
-                eprintln!("Unknown command: {}", other);
-                eprintln!("Run 'c9s help' for usage.");
-                std::process::exit(1);
+            if !stdout().is_terminal() {
+                anyhow::bail!("no running c9s to open {} in", url);
             }
+            link = Some(parsed);
+        }
+        Some(Command::Version) => {
+            println!("c9s {}", env!("CARGO_PKG_VERSION"));
+            return Ok(());
+        }
+        Some(Command::ExportTranscript {
+            id,
+            format,
+            output,
+            clipboard,
+        }) => return export_transcript_cmd(&id, format.into(), output, clipboard),
+        Some(Command::List { format, json }) => {
+            return list::run(if json { ListFormat::Json } else { format })
         }
+        Some(Command::Handoff { id, output, tar }) => return handoff_cmd(&id, output, tar),
+        Some(Command::Import { bundle }) => return import_cmd(&bundle),
+        Some(Command::Serve { bind }) => return serve::run(bind.as_deref()),
+        Some(Command::Search { words, limit }) => return search_cmd(&words, limit),
+        Some(Command::ExportTime { days, format }) => {
+            return export_time_cmd(days, format == TableFormat::Json)
+        }
+        Some(Command::ExportModels { format }) => {
+            return export_models_cmd(format == TableFormat::Json)
+        }
+        Some(Command::LocaleTemplate) => {
+            print!("{}", i18n::template());
+            return Ok(());
+        }
+        Some(Command::Help { topic }) => {
+            print!("{}", manual::help(topic.as_deref())?);
+            return Ok(());
+        }
+        Some(Command::Man) => {
+            print!("{}", manual::man_page());
+            return Ok(());
+        }
+        Some(Command::Completions { shell }) => {
+            print!("{}", completions::script(shell));
+            return Ok(());
+        }
+        Some(Command::CompleteSessions) => return complete_sessions_cmd(),
     }
+    let dnd_minutes = cli.dnd.map(|m| m.unwrap_or(app::DND_MINUTES));
 
     if !stdout().is_terminal() {
-        eprintln!("Error: c9s requires an interactive terminal (TTY).");
-        std::process::exit(1);
+        return status::run();
     }
 
     if !SessionManager::is_claude_installed() {
@@ -63,7 +126,48 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    // A second screen's dashboard runs alongside the c9s managing sessions.
+    let claim = if cli.dashboard {
+        None
+    } else {
+        match instance::Instance::claim(cli.force) {
+            Ok(claim) => Some(claim),
+            Err(e @ instance::ClaimError::Running(_)) => {
+                let _ = instance::send(&instance::Message::Knock);
+                eprintln!(
+                    "{}. Switch to its terminal, or start with --force to take over (its terminals close).",
+                    e
+                );
+                std::process::exit(1);
+            }
+            Err(e @ instance::ClaimError::NotQuit(_)) => {
+                eprintln!("Error: {}.", e);
+                std::process::exit(1);
+            }
+            Err(instance::ClaimError::Other(e)) if cli.force => {
+                eprintln!("Error: cannot take over the running c9s: {:#}", e);
+                std::process::exit(1);
+            }
+            Err(instance::ClaimError::Other(e)) => {
+                tlog!(warn, "instance lock: {:#}", e);
+                None
+            }
+        }
+    };
+
     let mut app = App::new()?;
+    if let Some(claim) = claim {
+        app.set_instance(claim);
+    }
+    if let Some(link) = link {
+        app.open_link_later(link);
+    }
+    if let Some(minutes) = dnd_minutes {
+        app.set_dnd_minutes(minutes);
+    }
+    if cli.dashboard {
+        app.set_view_mode(ViewMode::Dashboard);
+    }
 
     // Install panic hook that logs to c9s.log before printing to stderr
     std::panic::set_hook(Box::new(|info| {
@@ -93,6 +197,206 @@ fn main() -> Result<()> {
     result
 }
 
+fn export_transcript_cmd(
+    id: &str,
+    format: session::export::ExportFormat,
+    output: Option<PathBuf>,
+    clipboard: bool,
+) -> Result<()> {
+    let session = find_session(id)?;
+    let path = session::timeline::transcript_path(&session)
+        .ok_or_else(|| anyhow::anyhow!("transcript for {} not found", session.id))?;
+    let config = config::AppConfig::load();
+    crate::format::init(config.format.clone());
+    fx::start(&config.currency, false);
+    let doc = session::export::export_transcript(&session, &path, format, &|c| {
+        crate::format::cost(c, 2)
+    })?;
+
+    if clipboard {
+        clipboard::copy(&doc)?;
+        eprintln!("Copied {} transcript to clipboard", session.id);
+    } else if let Some(out) = output {
+        std::fs::write(&out, doc)?;
+        eprintln!("Wrote {}", out.display());
+    } else {
+        print!("{}", doc);
+    }
+    Ok(())
+}
+
+fn handoff_cmd(id: &str, output: Option<PathBuf>, tar: bool) -> Result<()> {
+    let session = find_session(id)?;
+    let path = session::timeline::transcript_path(&session)
+        .ok_or_else(|| anyhow::anyhow!("transcript for {} not found", session.id))?;
+    let dir = match output {
+        Some(dir) => dir,
+        None => session::handoff::default_handoff_dir(&session)
+            .ok_or_else(|| anyhow::anyhow!("no home directory"))?,
+    };
+    let config = config::AppConfig::load();
+    crate::format::init(config.format.clone());
+    fx::start(&config.currency, false);
+    session::handoff::write_handoff(&session, &path, &dir, &|c| crate::format::cost(c, 2))?;
+
+    if tar {
+        let archive = session::handoff::pack(&dir)?;
+        eprintln!("Wrote {}", archive.display());
+    } else {
+        eprintln!("Wrote {}", dir.display());
+    }
+    Ok(())
+}
+
+fn import_cmd(bundle: &Path) -> Result<()> {
+    let store = store::Store::open()?;
+    let session = session::handoff::import_bundle(bundle, &store)?;
+    eprintln!("Imported {} ({})", session.id, session.project_name);
+    Ok(())
+}
+
+/// `c9s search`: brings the transcript index up to date, then prints the
+/// best matches for `words`.
+fn search_cmd(words: &[String], limit: usize) -> Result<()> {
+    // Bring the index up to date first; only new lines get read.
+    let store = store::Store::open()?;
+    let mut discovery = session::SessionDiscovery::new();
+    discovery.set_show_all_per_cwd(true);
+    discovery.seed_stats(store.load_jsonl_stats()?);
+    discovery.discover_all()?;
+    store.save_jsonl_stats(&discovery.take_parsed_stats())?;
+    store.index_transcripts(&discovery.transcripts())?;
+
+    let hits = store.search_transcripts(&words.join(" "), limit)?;
+    if hits.is_empty() {
+        eprintln!("No matches");
+    }
+    for hit in hits {
+        println!(
+            "{}:{}\t{}\t{}",
+            hit.session_id,
+            hit.line + 1,
+            hit.kind,
+            hit.snippet
+        );
+    }
+    Ok(())
+}
+
+fn export_time_cmd(days: i64, json: bool) -> Result<()> {
+    let since = chrono::Local::now().date_naive() - chrono::Duration::days(days - 1);
+    let times = store::Store::open()?.project_time(since)?;
+    if json {
+        let rows: Vec<_> = times
+            .iter()
+            .map(|t| {
+                serde_json::json!({
+                    "date": t.date.to_string(),
+                    "project": t.project,
+                    "seconds": t.millis / 1000,
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        println!("date,project,hours");
+        for t in times {
+            println!(
+                "{},{},{:.2}",
+                t.date,
+                csv_field(&t.project),
+                t.millis as f64 / 3_600_000.0
+            );
+        }
+    }
+    Ok(())
+}
+
+fn export_models_cmd(json: bool) -> Result<()> {
+    let rated = store::Store::open()?.rated_sessions()?;
+    let report = outcome::compare_models(&rated);
+    if json {
+        let rows: Vec<_> = report
+            .iter()
+            .map(|m| {
+                serde_json::json!({
+                    "model": m.family,
+                    "rated": m.sessions,
+                    "shipped": m.shipped,
+                    "failed": m.failed,
+                    "failure_rate": m.failure_rate(),
+                    "cost_per_shipped_usd": m.cost_per_shipped(),
+                    "median_minutes": m.median_duration.map(|d| d.num_minutes()),
+                })
+            })
+            .collect();
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+    } else {
+        println!("model,rated,shipped,failed,failure_rate,cost_per_shipped_usd,median_minutes");
+        for m in report {
+            println!(
+                "{},{},{},{},{:.3},{},{}",
+                m.family,
+                m.sessions,
+                m.shipped,
+                m.failed,
+                m.failure_rate(),
+                m.cost_per_shipped()
+                    .map_or_else(String::new, |c| format!("{:.4}", c)),
+                m.median_duration
+                    .map_or_else(String::new, |d| d.num_minutes().to_string()),
+            );
+        }
+    }
+    Ok(())
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Resolves a session by full id or unique id prefix, across every session on
+/// disk rather than just the newest per directory.
+fn find_session(id: &str) -> Result<session::Session> {
+    let mut discovery = session::SessionDiscovery::new();
+    discovery.set_show_all_per_cwd(true);
+    let mut matches: Vec<session::Session> = discovery
+        .discover_all()?
+        .into_iter()
+        .filter(|s| s.id.starts_with(id))
+        .collect();
+    if let Some(pos) = matches.iter().position(|s| s.id == id) {
+        return Ok(matches.swap_remove(pos));
+    }
+    match matches.len() {
+        0 => anyhow::bail!("no session matching '{}'", id),
+        1 => Ok(matches.remove(0)),
+        n => anyhow::bail!("'{}' is ambiguous ({} sessions match)", id, n),
+    }
+}
+
+/// Every session id with its project, newest first, for completion scripts.
+fn complete_sessions_cmd() -> Result<()> {
+    let mut discovery = session::SessionDiscovery::new();
+    discovery.set_show_all_per_cwd(true);
+    if let Ok(store) = store::Store::open() {
+        discovery.seed_stats(store.load_jsonl_stats()?);
+    }
+    let mut sessions = discovery.discover_all()?;
+    sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+    for s in sessions.iter().filter(|s| !s.is_subagent) {
+        println!("{}\t{}", s.id, s.project_name);
+    }
+    Ok(())
+}
+
+/// Longest the main loop sleeps with nothing to do.
+const IDLE_TICK: Duration = Duration::from_millis(250);
+
 fn run_loop(
     terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
     app: &mut App,
@@ -103,15 +407,25 @@ fn run_loop(
     let mut last_detail_refresh = Instant::now();
     let mut needs_draw = true;
     let mut mouse_captured = true;
+    // Output-driven redraws wait out the frame budget so a fast-scrolling
+    // child can't redraw at poll speed; `echo_pending` lets the first
+    // output after a key press through at once.
+    let frame_budget = app.frame_budget();
+    let wakeups = wakeup::start();
+    let mut last_draw = Instant::now();
+    let mut pty_dirty = false;
+    let mut echo_pending = false;
+    let mut last_mirror = Instant::now();
+    let mut mirror_viewers = None;
 
     loop {
         if matches!(
             app.view_mode(),
-            ViewMode::Terminal | ViewMode::TerminalQSwitcher
+            ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy
         ) {
             if let Some(term) = app.terminal_manager().active_terminal() {
                 if term.take_dirty() {
-                    needs_draw = true;
+                    pty_dirty = true;
                 }
             }
         }
@@ -119,11 +433,30 @@ fn run_loop(
         if app.is_side_panel_open() {
             if let Some(st) = app.side_terminal() {
                 if st.take_dirty() {
-                    needs_draw = true;
+                    pty_dirty = true;
                 }
             }
         }
 
+        if pty_dirty && (echo_pending || last_draw.elapsed() >= frame_budget) {
+            needs_draw = true;
+        }
+
+        if let Some(link) = app.take_link() {
+            open_link(app, link, terminal)?;
+            needs_draw = true;
+        }
+
+        // Viewers get the screen at most as often as it is drawn here.
+        if last_mirror.elapsed() >= frame_budget {
+            app.publish_mirror();
+            last_mirror = Instant::now();
+        }
+        if app.mirror_viewers() != mirror_viewers {
+            mirror_viewers = app.mirror_viewers();
+            needs_draw = true;
+        }
+
         if app.check_tervezo_dirty() {
             needs_draw = true;
         }
@@ -132,26 +465,26 @@ fn run_loop(
             needs_draw = true;
 
             // Handle navigate_to_impl from restart action
-            let nav_target = app.tervezo_detail.as_ref().and_then(|s| {
-                s.action_result.as_ref().and_then(|r| {
-                    r.as_ref()
-                        .ok()
-                        .and_then(|msg| msg.strip_prefix("NAVIGATE:").map(|id| id.to_string()))
-                })
-            });
+            let nav_target = app
+                .tervezo_detail
+                .as_mut()
+                .and_then(|s| s.navigate_to.take());
             if let Some(new_id) = nav_target {
                 // Fetch the new implementation and re-initialize the detail view
                 if let Some(config) = app.tervezo_config() {
                     let client = TervezoClient::new(config);
                     if let Ok(new_impl) = client.get_implementation(&new_id) {
-                        if let Some(ref mut state) = app.tervezo_detail {
-                            state.action_result = Some(Ok(format!("Restarted → {}", new_id)));
-                        }
-                        // Re-initialize state with the new implementation
-                        let mut new_state = app::TervezoDetailState::new(new_impl);
-                        new_state.action_result =
-                            Some(Ok("Restarted (new implementation)".to_string()));
-                        app.tervezo_detail = Some(new_state);
+                        let old_id = app
+                            .tervezo_detail
+                            .as_ref()
+                            .map(|s| s.implementation_id.clone())
+                            .unwrap_or_default();
+                        app.record_restart(&new_id, &old_id);
+                        app.show_tervezo_implementation(new_impl);
+                        app.toast(
+                            Severity::Success,
+                            tf("Restarted → {id}", &[("id", &new_id)]),
+                        );
                         trigger_tervezo_initial_fetch(app);
                     }
                 }
@@ -174,7 +507,7 @@ fn run_loop(
                 WorkspaceMsg::Error(e) => {
                     if let Some(ref mut state) = app.tervezo_create {
                         state.workspaces_loading = false;
-                        state.workspaces_error = Some(e);
+                        state.workspaces_error = Some(e.with_hint());
                     }
                 }
             }
@@ -193,7 +526,7 @@ fn run_loop(
                 TervezoCreateMsg::Error(e) => {
                     if let Some(ref mut state) = app.tervezo_create {
                         state.submitting = false;
-                        state.error = Some(e);
+                        state.error = Some(e.with_hint());
                     }
                 }
             }
@@ -202,11 +535,69 @@ fn run_loop(
 
         app.drain_ci_statuses();
         app.check_ci_statuses();
+        app.tick_time_tracking();
+        if app.tick_focus_timer() {
+            needs_draw = true;
+        }
+
+        if app.drain_mcp_health() {
+            needs_draw = true;
+        }
+
+        if app.drain_git_results() {
+            needs_draw = true;
+        }
+
+        if app.drain_working_diff() {
+            needs_draw = true;
+        }
+
+        if app.drain_transcripts() {
+            needs_draw = true;
+        }
+
+        if app.drain_cwd_branch() {
+            needs_draw = true;
+        }
+
+        if app.drain_discovery() {
+            needs_draw = true;
+        }
+
+        for id in app.take_pending_restarts() {
+            let size = terminal.size()?;
+            app.restart_session(&id, size.height.saturating_sub(1), size.width);
+            needs_draw = true;
+        }
+
+        if app.drain_fx_rate() {
+            needs_draw = true;
+        }
+
+        if app.drain_claude_versions() {
+            needs_draw = true;
+        }
+
+        if app.drain_host_stats() {
+            needs_draw = true;
+        }
+
+        if app.drain_orgs() {
+            needs_draw = true;
+        }
+
+        if app.drain_queue_actions() {
+            needs_draw = true;
+        }
 
         if *app.view_mode() == ViewMode::Log && log::take_dirty() {
             needs_draw = true;
         }
 
+        if app.expire_toasts() {
+            needs_draw = true;
+        }
+
         if needs_draw {
             terminal.draw(|f| {
                 let full_area = f.area();
@@ -222,44 +613,23 @@ fn run_loop(
                         ui::render_session_list(f, app, area);
                     }
                     ViewMode::Detail => {
-                        if let Some(entry) = app.selected_session() {
-                            if let Some(session) = entry.as_local() {
-                                let session = session.clone();
-                                let items = app.detail_items().to_vec();
-                                let cursor = app.detail_cursor();
-                                let preview = app.detail_preview().cloned();
-                                let preview_scroll = app.detail_preview_scroll();
-                                ui::render_session_detail(
-                                    f,
-                                    &session,
-                                    &items,
-                                    cursor,
-                                    preview.as_ref(),
-                                    preview_scroll,
-                                    area,
-                                );
-                            }
+                        render_local_detail(f, app, area);
+                    }
+                    ViewMode::SessionTimeline => {
+                        if let Some(ref timeline) = app.local_timeline {
+                            ui::render_session_timeline(f, timeline, area);
                         }
                     }
-                    ViewMode::SessionFilePicker => {
-                        if let Some(entry) = app.selected_session() {
-                            if let Some(session) = entry.as_local() {
-                                let session = session.clone();
-                                let items = app.detail_items().to_vec();
-                                let cursor = app.detail_cursor();
-                                let preview = app.detail_preview().cloned();
-                                let preview_scroll = app.detail_preview_scroll();
-                                ui::render_session_detail(
-                                    f,
-                                    &session,
-                                    &items,
-                                    cursor,
-                                    preview.as_ref(),
-                                    preview_scroll,
-                                    area,
-                                );
-                            }
+                    ViewMode::TimelineExport => {
+                        if let Some(ref timeline) = app.local_timeline {
+                            ui::render_session_timeline(f, timeline, area);
+                        }
+                        if let Some(ref prompt) = app.export_prompt {
+                            ui::render_export_prompt(f, prompt.format, &prompt.path, area);
                         }
+                    }
+                    ViewMode::SessionFilePicker => {
+                        render_local_detail(f, app, area);
                         ui::render_session_file_picker(
                             f,
                             &app.session_files,
@@ -289,6 +659,11 @@ fn run_loop(
                             ui::render_tervezo_detail_with_prompt(f, state, area);
                         }
                     }
+                    ViewMode::TervezoCompare => {
+                        if let Some(ref state) = app.tervezo_detail {
+                            ui::render_tervezo_compare(f, state, area);
+                        }
+                    }
                     ViewMode::TervezoQSwitcher => {
                         if let Some(ref state) = app.tervezo_detail {
                             ui::render_tervezo_detail(f, state, area);
@@ -303,13 +678,55 @@ fn run_loop(
                         ui::render_session_list(f, app, area);
                         ui::render_new_session_menu(f, &app.new_session_menu, area);
                     }
+                    ViewMode::ActionMenu => {
+                        ui::render_session_list(f, app, area);
+                        ui::render_action_menu(f, app, area);
+                    }
+                    ViewMode::OutcomePrompt => {
+                        ui::render_session_list(f, app, area);
+                        if let Some(ref state) = app.outcome_prompt {
+                            ui::render_outcome_prompt(f, state, area);
+                        }
+                    }
+                    ViewMode::Dashboard => ui::render_dashboard(f, app, area),
+                    ViewMode::Queue => ui::render_queue(f, app, area),
+                    ViewMode::QueueConfirm => {
+                        ui::render_queue(f, app, area);
+                        ui::render_queue_confirm(f, app, area);
+                    }
+                    ViewMode::OrgPicker => {
+                        ui::render_session_list(f, app, area);
+                        ui::render_org_picker(f, app, area);
+                    }
+                    ViewMode::Outcomes => {
+                        ui::render_outcomes(f, app.outcome_stats(), app.model_comparison(), area);
+                    }
+                    ViewMode::Stats => {
+                        if let Some(ref stats) = app.cost_stats {
+                            ui::render_stats(f, stats, area);
+                        }
+                    }
+                    ViewMode::TicketInput => {
+                        ui::render_session_list(f, app, area);
+                        if let Some(ref state) = app.ticket_input {
+                            ui::render_ticket_input(f, &state.project, &state.input, area);
+                        }
+                    }
+                    ViewMode::GitMenu | ViewMode::GitBranchName => {
+                        ui::render_session_list(f, app, area);
+                        ui::render_git_menu(f, app, area);
+                    }
+                    ViewMode::GitCommit => {
+                        render_local_detail(f, app, area);
+                        ui::render_commit_prompt(f, app, area);
+                    }
                     ViewMode::QSwitcher => {
                         ui::render_session_list(f, app, area);
                         ui::render_qswitcher(f, app, area);
                     }
                     ViewMode::Help => {
                         ui::render_session_list(f, app, area);
-                        ui::render_help(f, area);
+                        ui::render_help(f, app.plugins(), area);
                     }
                     ViewMode::Terminal => {
                         render_terminal_view(app, f, area);
@@ -318,10 +735,29 @@ fn run_loop(
                         render_terminal_view(app, f, area);
                         ui::render_qswitcher(f, app, area);
                     }
+                    ViewMode::TerminalCopy => {
+                        render_terminal_view(app, f, area);
+                    }
                     ViewMode::Command => {
                         ui::render_session_list(f, app, area);
                         ui::render_command_input(f, app.command_input(), area);
                     }
+                    ViewMode::RecentlyExited => {
+                        if let Some(ref state) = app.recently_exited {
+                            ui::render_recently_exited(
+                                f,
+                                state,
+                                app.terminal_manager().recently_exited(),
+                                area,
+                            );
+                        }
+                    }
+                    ViewMode::QuickPrompt => {
+                        ui::render_session_list(f, app, area);
+                        if let Some(ref prompt) = app.quick_prompt {
+                            ui::render_quick_prompt(f, &prompt.project_name, &prompt.input, area);
+                        }
+                    }
                     ViewMode::ConfirmKill => {
                         ui::render_session_list(f, app, area);
                         let name = app
@@ -334,7 +770,28 @@ fn run_loop(
                                     .map(|e| e.display_name().to_string())
                             })
                             .unwrap_or_else(|| "unknown".to_string());
-                        ui::render_confirm_kill(f, &name, area);
+                        let soft = app
+                            .confirm_kill_session_id
+                            .as_ref()
+                            .is_some_and(|id| app.terminal_manager().can_soft_kill(id));
+                        ui::render_confirm_kill(f, &name, soft, area);
+                    }
+                    ViewMode::ConfirmAttach => {
+                        ui::render_session_list(f, app, area);
+                        if let Some(ref pending) = app.confirm_attach {
+                            ui::render_confirm_attach(f, &pending.project_name, &pending.tty, area);
+                        }
+                    }
+                    ViewMode::ErrorModal => {
+                        match (&app.error_modal, &app.tervezo_detail) {
+                            (Some(m), Some(state)) if m.return_to == ViewMode::TervezoDetail => {
+                                ui::render_tervezo_detail(f, state, area)
+                            }
+                            _ => ui::render_session_list(f, app, area),
+                        }
+                        if let Some(ref modal) = app.error_modal {
+                            ui::render_error_modal(f, modal, area);
+                        }
                     }
                     ViewMode::ConfirmQuit => {
                         ui::render_session_list(f, app, area);
@@ -345,6 +802,15 @@ fn run_loop(
                         let entries = log::entries();
                         ui::render_log_panel(f, &entries, app.log_scroll(), area);
                     }
+                    ViewMode::Diagnostics => {
+                        ui::render_diagnostics(
+                            f,
+                            app.diagnostics(),
+                            app.is_discovering(),
+                            app.diagnostics_scroll(),
+                            area,
+                        );
+                    }
                 }
 
                 if app.is_side_panel_open() {
@@ -353,69 +819,112 @@ fn run_loop(
                         ui::render_side_panel(f, st, focused, side_area);
                     }
                 }
+
+                if !app.toasts().is_empty() {
+                    // Keep the footer row visible under the stack.
+                    let toast_area = ratatui::layout::Rect {
+                        height: full_area.height.saturating_sub(1),
+                        ..full_area
+                    };
+                    ui::render_toasts(f, app.toasts(), toast_area);
+                }
             })?;
             needs_draw = false;
+            pty_dirty = false;
+            echo_pending = false;
+            last_draw = Instant::now();
         }
 
-        if event::poll(Duration::from_millis(16))? {
-            loop {
-                let ev = event::read()?;
-
-                if let event::Event::Resize(cols, rows) = ev {
-                    if matches!(
-                        app.view_mode(),
-                        ViewMode::Terminal | ViewMode::TerminalQSwitcher
-                    ) {
-                        let term_cols = if app.is_side_panel_open() {
-                            cols * 60 / 100
-                        } else {
-                            cols
-                        };
-                        let _ = app
-                            .terminal_manager()
-                            .resize_active(rows.saturating_sub(2), term_cols);
-                    }
-                    if app.is_side_panel_open() {
-                        let panel_cols = cols * 40 / 100;
-                        let panel_rows = rows.saturating_sub(1);
-                        if let Some(st) = app.side_terminal() {
-                            let _ = st.resize(panel_rows, panel_cols);
-                        }
+        // Input and background threads wake the loop, so when nothing is
+        // pending it only needs to come round for timers (refreshes,
+        // toasts, bell checks).
+        let wait = if pty_dirty {
+            frame_budget.saturating_sub(last_draw.elapsed())
+        } else {
+            IDLE_TICK
+        };
+        let mut next = wakeups.recv_timeout(wait).ok();
+        while let Some(wake) = next.take() {
+            let ev = match wake {
+                wakeup::Wake::Input(ev) => {
+                    if matches!(ev, event::Event::Key(_)) {
+                        app.note_input();
                     }
-                    needs_draw = true;
-                }
-
-                let action = handle_event(&ev, app.view_mode(), app.is_side_panel_focused());
-                let is_noop = matches!(
-                    action,
-                    Action::None | Action::TerminalInput(_) | Action::SideTerminalInput(_)
-                );
-                if let Err(e) = process_action(app, action, terminal) {
-                    tlog!(
-                        error,
-                        "DIAG: process_action error: {} (view={:?})",
-                        e,
-                        app.view_mode()
-                    );
-                    return Err(e);
+                    ev
                 }
-                if !is_noop {
-                    needs_draw = true;
+                wakeup::Wake::Background => {
+                    wakeup::consumed();
+                    next = wakeups.try_recv().ok();
+                    continue;
                 }
-
-                if app.should_quit() {
-                    break;
+            };
+            if let event::Event::Resize(cols, rows) = ev {
+                if matches!(
+                    app.view_mode(),
+                    ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy
+                ) {
+                    let term_cols = if app.is_side_panel_open() {
+                        cols * 60 / 100
+                    } else {
+                        cols
+                    };
+                    let _ = app
+                        .terminal_manager()
+                        .resize_active(rows.saturating_sub(2), term_cols);
                 }
-                if !event::poll(Duration::from_millis(0))? {
-                    break;
+                if app.is_side_panel_open() {
+                    let panel_cols = cols * 40 / 100;
+                    let panel_rows = rows.saturating_sub(1);
+                    if let Some(st) = app.side_terminal() {
+                        let _ = st.resize(panel_rows, panel_cols);
+                    }
                 }
+                needs_draw = true;
+            }
+
+            let action = handle_event(&ev, app.view_mode(), app.is_side_panel_focused());
+            let is_noop = matches!(
+                action,
+                Action::None
+                    | Action::TerminalInput(_)
+                    | Action::TerminalMouse(_)
+                    | Action::SideTerminalInput(_)
+            );
+            if matches!(
+                action,
+                Action::TerminalInput(_) | Action::SideTerminalInput(_)
+            ) {
+                echo_pending = true;
             }
+            if let Err(e) = process_action(app, action, terminal) {
+                tlog!(
+                    error,
+                    "DIAG: process_action error: {} (view={:?})",
+                    e,
+                    app.view_mode()
+                );
+                return Err(e);
+            }
+            if !is_noop {
+                needs_draw = true;
+            }
+
+            if app.should_quit() {
+                break;
+            }
+            next = wakeups.try_recv().ok();
         }
 
-        let needs_native_mouse = matches!(
-            app.view_mode(),
-            ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::Log
-        );
+        // In the terminal the host keeps native selection unless the
+        // application inside asked for mouse reports.
+        let needs_native_mouse = match app.view_mode() {
+            ViewMode::Terminal => !app
+                .terminal_manager()
+                .active_terminal()
+                .is_some_and(|term| term.wants_mouse()),
+            ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy | ViewMode::Log => true,
+            _ => false,
+        };
         if needs_native_mouse && mouse_captured {
             stdout().execute(DisableMouseCapture)?;
             mouse_captured = false;
@@ -426,7 +935,7 @@ fn run_loop(
 
         let viewing_active = matches!(
             app.view_mode(),
-            ViewMode::Terminal | ViewMode::TerminalQSwitcher
+            ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy
         );
         let notified = app
             .terminal_manager_mut()
@@ -436,12 +945,18 @@ fn run_loop(
             app.invalidate_usage();
         }
 
+        for data in app.terminal_manager().take_clipboard() {
+            if let Err(e) = clipboard::copy_osc52(&data, &mut stdout()) {
+                tlog!(warn, "terminal clipboard copy failed: {}", e);
+            }
+        }
+
         app.terminal_manager_mut().cleanup_inactive_exited();
 
         if last_refresh.elapsed() >= refresh_interval {
             if matches!(
                 app.view_mode(),
-                ViewMode::Terminal | ViewMode::TerminalQSwitcher
+                ViewMode::Terminal | ViewMode::TerminalQSwitcher | ViewMode::TerminalCopy
             ) {
                 app.refresh_usage();
             } else {
@@ -483,14 +998,59 @@ fn run_loop(
     Ok(())
 }
 
+fn render_local_detail(f: &mut ratatui::Frame, app: &App, area: ratatui::layout::Rect) {
+    let Some(entry) = app.selected_session() else {
+        return;
+    };
+    let Some(session) = entry.as_local() else {
+        return;
+    };
+    let rollup = app.subagent_rollup(&session.id);
+    let panels = app.plugins().panels(entry);
+    ui::render_session_detail(
+        f,
+        session,
+        rollup,
+        app.detail_items(),
+        app.detail_cursor(),
+        app.detail_preview(),
+        app.detail_preview_scroll(),
+        ui::DetailTabs {
+            active: app.detail_tab(),
+            files: app.detail_files(),
+            diff: app.detail_diff(),
+            transcript: app.detail_transcript(),
+        },
+        app.pr_statuses.get(&session.id),
+        app.resources(&session.id),
+        app.worktree_clash(&session.id),
+        &panels,
+        area,
+    );
+}
+
 fn render_terminal_view(app: &App, f: &mut ratatui::Frame, area: ratatui::layout::Rect) {
     if let Some(term) = app.terminal_manager().active_terminal() {
+        let copy = app.copy_mode.as_ref().map(|copy| (copy, term.top_line()));
         let guard = term.lock_parser();
         let screen = guard.screen();
         let scrolled = screen.scrollback() > 0;
         let exited = term.is_exited();
         let tabs = app.terminal_manager().tab_info();
-        ui::render_terminal(f, screen, &tabs, exited, scrolled, app.usage(), area);
+        let mut cache = term.lock_render_cache();
+        ui::render_terminal(
+            f,
+            screen,
+            &mut cache,
+            &tabs,
+            exited,
+            scrolled,
+            copy,
+            app.usage(),
+            app.focus_label(),
+            app.mirror_viewers(),
+            area,
+        );
     }
 }
 
@@ -500,24 +1060,6 @@ fn process_action(
     terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
 ) -> Result<()> {
     // Clear flash message on any keypress in tervezo detail view
-    if !matches!(action, Action::None) {
-        let in_tzv = matches!(
-            app.view_mode(),
-            ViewMode::TervezoDetail
-                | ViewMode::TervezoActionMenu
-                | ViewMode::TervezoConfirm
-                | ViewMode::TervezoPromptInput
-                | ViewMode::TervezoQSwitcher
-        );
-        if in_tzv {
-            if let Some(ref mut state) = app.tervezo_detail {
-                if state.action_result.is_some() {
-                    state.action_result = None;
-                }
-            }
-        }
-    }
-
     match action {
         Action::Quit => {
             let active = app.active_attached_sessions();
@@ -530,31 +1072,50 @@ fn process_action(
         Action::ConfirmQuit => app.quit(),
         Action::CancelQuit => app.set_view_mode(ViewMode::List),
         Action::KillSession => {
-            if let Some(entry) = app.selected_session() {
-                if let Some(session) = entry.as_local() {
-                    if session.pid.is_some() {
-                        app.confirm_kill_session_id = Some(session.id.clone());
-                        app.set_view_mode(ViewMode::ConfirmKill);
-                    }
+            let target = app.selected_session().and_then(|e| e.as_local()).map(|s| {
+                let live = s.pid.is_some() && s.status != session::SessionStatus::Dead;
+                (s.id.clone(), s.project_name.clone(), live)
+            });
+            match target {
+                Some((id, _, true)) => {
+                    app.confirm_kill_session_id = Some(id);
+                    app.set_view_mode(ViewMode::ConfirmKill);
                 }
+                Some((_, name, false)) => app.toast(
+                    Severity::Warning,
+                    tf("{name} is not running", &[("name", &name)]),
+                ),
+                None => {}
             }
         }
-        Action::ConfirmKill => {
-            if let Some(session_id) = app.confirm_kill_session_id.take() {
-                if let Some(pid) = app
-                    .all_sessions()
-                    .iter()
-                    .find(|s| s.id == session_id)
-                    .and_then(|s| s.pid)
-                {
-                    unsafe {
-                        libc::kill(pid as i32, libc::SIGTERM);
-                    }
-                }
+        Action::ConfirmKill => kill_confirmed(app, false),
+        Action::ForceKill => kill_confirmed(app, true),
+        Action::ConfirmAttach => {
+            if let Some(pending) = app.confirm_attach.take() {
+                tlog!(
+                    info,
+                    "Taking over {} from {} (pid {})",
+                    pending.project_name,
+                    pending.tty,
+                    pending.pid
+                );
+                let area = terminal.size()?;
+                let result = app.terminal_manager_mut().attach(
+                    &pending.session_id,
+                    &pending.project_name,
+                    &pending.cwd,
+                    Some(pending.pid),
+                    area.height.saturating_sub(1),
+                    area.width,
+                );
+                enter_terminal(app, result);
             }
+        }
+        Action::CancelAttach => {
+            app.confirm_attach = None;
             app.set_view_mode(ViewMode::List);
-            let _ = app.refresh();
         }
+        Action::DismissError => app.dismiss_error(),
         Action::CancelKill => {
             app.confirm_kill_session_id = None;
             app.set_view_mode(ViewMode::List);
@@ -575,8 +1136,73 @@ fn process_action(
                 }
             }
         }
-        Action::SessionFileUp => {
-            if app.session_file_cursor > 0 {
+        Action::ShowTimeline => {
+            if matches!(app.view_mode(), ViewMode::List | ViewMode::Detail)
+                && !app.open_local_timeline()
+            {
+                app.toast(
+                    Severity::Warning,
+                    t("No transcript found for selected session"),
+                );
+            }
+        }
+        Action::OpenQuickPrompt => {
+            if *app.view_mode() == ViewMode::List && !app.open_quick_prompt() {
+                app.toast(
+                    Severity::Warning,
+                    t("Quick prompt needs a session running in a background terminal"),
+                );
+            }
+        }
+        Action::QuickPromptChar(c) => {
+            if let Some(ref mut prompt) = app.quick_prompt {
+                prompt.input.push(c);
+            }
+        }
+        Action::QuickPromptBackspace => {
+            if let Some(ref mut prompt) = app.quick_prompt {
+                prompt.input.pop();
+            }
+        }
+        Action::QuickPromptSubmit => app.submit_quick_prompt(),
+        Action::QuickPromptCancel => app.set_view_mode(ViewMode::List),
+        Action::TimelineToggleBookmark => app.toggle_timeline_bookmark(),
+        Action::TimelineExport => app.open_export_prompt(false),
+        Action::TimelineCopy => app.open_export_prompt(true),
+        Action::ExportChar(c) => {
+            if let Some(ref mut prompt) = app.export_prompt {
+                prompt.path.push(c);
+            }
+        }
+        Action::ExportBackspace => {
+            if let Some(ref mut prompt) = app.export_prompt {
+                prompt.path.pop();
+            }
+        }
+        Action::ExportToggleFormat => {
+            if let Some(ref mut prompt) = app.export_prompt {
+                prompt.toggle_format();
+            }
+        }
+        Action::ExportSubmit => app.submit_export_prompt(),
+        Action::TimelineShowBookmarks => {
+            if let Some(ref mut timeline) = app.local_timeline {
+                timeline.toggle_bookmarks_only();
+            }
+        }
+        Action::TimelineNextBookmark | Action::TimelinePrevBookmark => {
+            let forward = action == Action::TimelineNextBookmark;
+            if let Some(ref mut timeline) = app.local_timeline {
+                timeline.jump_to_bookmark(forward);
+            }
+        }
+        Action::CheckMcpHealth => {
+            if *app.view_mode() == ViewMode::Detail {
+                app.check_mcp_health();
+            }
+        }
+        Action::SessionFileUp => {
+            if app.session_file_cursor > 0 {
                 app.session_file_cursor -= 1;
             }
         }
@@ -596,15 +1222,7 @@ fn process_action(
                         let rows = area.height.saturating_sub(1);
                         let cols = area.width;
                         let name = session.project_name.clone();
-                        app.terminal_manager_mut().attach(
-                            &session_id,
-                            &name,
-                            &cwd,
-                            pid,
-                            rows,
-                            cols,
-                        )?;
-                        app.set_view_mode(ViewMode::Terminal);
+                        attach_local(app, &session_id, &name, &cwd, pid, rows, cols);
                     }
                 }
             }
@@ -614,6 +1232,47 @@ fn process_action(
             app.session_files.clear();
             app.set_view_mode(ViewMode::Detail);
         }
+        Action::ToggleDnd => app.toggle_dnd(),
+        Action::ToggleFocusTimer => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_focus_timer();
+            }
+        }
+        Action::ToggleTimeTracking => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_time_tracking();
+            }
+        }
+        Action::CycleNotifyLevel => {
+            if *app.view_mode() == ViewMode::List {
+                app.cycle_notify_level();
+            }
+        }
+        Action::ShowRecentlyExited => {
+            if *app.view_mode() == ViewMode::List && !app.open_recently_exited() {
+                app.toast(Severity::Info, t("No terminal tabs have exited yet"));
+            }
+        }
+        Action::ShowDiagnostics => {
+            if matches!(app.view_mode(), ViewMode::List | ViewMode::Detail) {
+                app.open_diagnostics();
+            }
+        }
+        Action::CyclePalette => {
+            if *app.view_mode() == ViewMode::List {
+                app.cycle_palette();
+            }
+        }
+        Action::ToggleTerminalLog => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_terminal_log();
+            }
+        }
+        Action::ToggleMirror => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_mirror();
+            }
+        }
         Action::UnfollowSession => {
             if let Some(entry) = app.selected_session() {
                 let id = entry.id().to_string();
@@ -628,10 +1287,10 @@ fn process_action(
                     let area = terminal.size()?;
                     let rows = area.height.saturating_sub(1);
                     let cols = area.width;
-                    let _ = app
+                    let result = app
                         .terminal_manager_mut()
                         .attach_resume_picker(&cwd, rows, cols);
-                    app.set_view_mode(ViewMode::Terminal);
+                    enter_terminal(app, result);
                 }
             }
         }
@@ -641,11 +1300,21 @@ fn process_action(
         Action::MoveToBottom => app.move_to_bottom(),
         Action::Select => match app.view_mode() {
             ViewMode::List => {
-                attach_selected(app, terminal)?;
+                if !app.open_dead_session_menu() {
+                    attach_selected(app, terminal)?;
+                }
             }
             ViewMode::Detail => {
-                app.detail_open_preview();
+                if !app.detail_files_select() && !app.detail_toggle_env() {
+                    app.detail_open_preview();
+                }
             }
+            ViewMode::SessionTimeline => {
+                if let Some(ref mut timeline) = app.local_timeline {
+                    timeline.open_selected_bookmark();
+                }
+            }
+            ViewMode::RecentlyExited => app.recently_exited_select(),
             _ => {}
         },
         Action::AttachSession => {
@@ -674,22 +1343,63 @@ fn process_action(
             app.set_view_mode(ViewMode::TerminalQSwitcher);
         }
         Action::Detach => {
+            let finished = app
+                .terminal_manager()
+                .active_is_exited()
+                .then(|| app.attached_session_id().map(str::to_string))
+                .flatten();
             if app.terminal_manager().active_is_exited() {
                 app.terminal_manager_mut().remove_active();
             } else {
                 app.terminal_manager_mut().detach();
             }
             app.set_view_mode(ViewMode::List);
+            if let Some(session_id) = finished {
+                app.prompt_outcome(&session_id);
+            }
         }
         Action::TerminalInput(bytes) => {
             let _ = app.terminal_manager_mut().write_to_active(&bytes);
         }
+        Action::TerminalMouse(mut event) => {
+            // The tab bar takes the first row above the screen.
+            let forwarded = match event.row.checked_sub(1) {
+                Some(row) => {
+                    event.row = row;
+                    app.terminal_manager_mut()
+                        .active_terminal_mut()
+                        .map(|term| term.send_mouse(event).unwrap_or(true))
+                        .unwrap_or(false)
+                }
+                None => false,
+            };
+            if !forwarded {
+                if let Some(term) = app.terminal_manager().active_terminal() {
+                    match event.kind {
+                        MouseEventKind::ScrollUp => term.scroll_up(3),
+                        MouseEventKind::ScrollDown => term.scroll_down(3),
+                        _ => {}
+                    }
+                }
+            }
+        }
+        Action::EnterCopyMode => app.enter_copy_mode(),
+        Action::CopyModeMove(motion) => app.copy_mode_move(motion),
+        Action::CopyModeMark => app.copy_mode_mark(),
+        Action::CopyModeYank => app.copy_mode_yank(),
+        Action::CopyModeExit => app.exit_copy_mode(),
         Action::CycleNextSession => {
             app.terminal_manager_mut().cycle_next();
         }
         Action::CyclePrevSession => {
             app.terminal_manager_mut().cycle_prev();
         }
+        Action::ScrollUp(n) if *app.view_mode() == ViewMode::RecentlyExited => {
+            app.recently_exited_move(-(n as isize));
+        }
+        Action::ScrollDown(n) if *app.view_mode() == ViewMode::RecentlyExited => {
+            app.recently_exited_move(n as isize);
+        }
         Action::ScrollUp(n) => {
             if let Some(term) = app.terminal_manager().active_terminal() {
                 term.scroll_up(n);
@@ -704,7 +1414,7 @@ fn process_action(
             ViewMode::Detail => {
                 if app.detail_preview().is_some() {
                     app.detail_close_preview();
-                } else {
+                } else if !app.detail_files_close_preview() {
                     app.set_view_mode(ViewMode::List);
                 }
             }
@@ -713,9 +1423,20 @@ fn process_action(
                 app.set_view_mode(ViewMode::List);
             }
             ViewMode::TervezoQSwitcher => app.set_view_mode(ViewMode::TervezoDetail),
-            ViewMode::Log | ViewMode::Help | ViewMode::QSwitcher => {
-                app.set_view_mode(ViewMode::List)
-            }
+            ViewMode::SessionTimeline => app.close_local_timeline(),
+            ViewMode::TimelineExport => app.set_view_mode(ViewMode::SessionTimeline),
+            ViewMode::RecentlyExited => app.recently_exited_back(),
+            ViewMode::Diagnostics => app.close_diagnostics(),
+            ViewMode::Log
+            | ViewMode::Help
+            | ViewMode::QSwitcher
+            | ViewMode::TicketInput
+            | ViewMode::OutcomePrompt
+            | ViewMode::Outcomes
+            | ViewMode::Stats
+            | ViewMode::Dashboard
+            | ViewMode::OrgPicker
+            | ViewMode::Queue => app.set_view_mode(ViewMode::List),
             ViewMode::TerminalQSwitcher => app.set_view_mode(ViewMode::Terminal),
             ViewMode::Filter => {
                 app.set_view_mode(ViewMode::List);
@@ -752,8 +1473,42 @@ fn process_action(
         Action::FilterBackspace => app.filter_pop(),
         Action::FilterSubmit => app.set_view_mode(ViewMode::List),
         Action::CycleSort => app.cycle_sort(),
+        Action::DetailTab(forward) => app.detail_switch_tab(forward),
+        Action::ListHistory(forward) => {
+            if *app.view_mode() == ViewMode::List && !app.list_history_step(forward) {
+                let msg = if forward {
+                    "Already at the latest list state"
+                } else {
+                    "No earlier list state"
+                };
+                app.toast(Severity::Info, t(msg));
+            }
+        }
+        Action::ToggleErrorFilter => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_errors_only();
+            }
+        }
+        Action::ToggleRowPreview => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_row_preview();
+            }
+        }
+        Action::CyclePathStyle => {
+            if matches!(app.view_mode(), ViewMode::List | ViewMode::Detail) {
+                app.cycle_path_style();
+            }
+        }
+        Action::PluginKey(c) => {
+            if matches!(app.view_mode(), ViewMode::List | ViewMode::Detail) {
+                app.run_plugin_action(c);
+            }
+        }
         Action::Refresh => {
             let _ = app.refresh();
+            if *app.view_mode() == ViewMode::SessionTimeline {
+                app.reload_local_timeline();
+            }
         }
         Action::LaunchNew => {
             if app.has_tervezo() {
@@ -781,10 +1536,17 @@ fn process_action(
                     let area = terminal.size()?;
                     let rows = area.height.saturating_sub(1);
                     let cols = area.width;
-                    let _ = app.terminal_manager_mut().attach_new(&cwd, rows, cols);
-                    app.set_view_mode(ViewMode::Terminal);
+                    let result = app.terminal_manager_mut().attach_new(&cwd, rows, cols);
+                    enter_terminal(app, result);
                 } else {
                     app.set_view_mode(ViewMode::List);
+                    app.toast(
+                        Severity::Error,
+                        tf(
+                            "Not a directory: {path}",
+                            &[("path", &cwd.display().to_string())],
+                        ),
+                    );
                 }
             } else {
                 app.set_view_mode(ViewMode::List);
@@ -868,19 +1630,23 @@ fn process_action(
                         let cols = area.width;
                         let id = state.implementation_id.clone();
                         let name = state.implementation.display_name().to_string();
-                        let _ = app.terminal_manager_mut().attach_ssh(
+                        let result = app.terminal_manager_mut().attach_ssh(
                             &id,
                             &name,
                             &creds.ssh_command,
                             rows,
                             cols,
                         );
-                        app.set_view_mode(ViewMode::Terminal);
+                        enter_terminal(app, result);
                     }
                 }
             }
         }
+        Action::TervezoLoadOlder => trigger_tervezo_load_older(app),
         Action::TervezoRefreshDetail => {
+            if let (Some(config), Some(state)) = (app.tervezo_config(), &app.tervezo_detail) {
+                TervezoClient::new(config).expire_cached(&state.implementation_id);
+            }
             trigger_tervezo_initial_fetch(app);
         }
         Action::TervezoToggleRaw => {
@@ -901,9 +1667,7 @@ fn process_action(
             }
         }
         Action::TervezoOpenActionMenu => {
-            // Clear any previous flash message
             if let Some(ref mut state) = app.tervezo_detail {
-                state.action_result = None;
                 let items = state.compute_available_actions();
                 if !items.is_empty() {
                     state.action_menu_items = items;
@@ -945,6 +1709,24 @@ fn process_action(
                         let _ = open::that(&url);
                     }
                     app.set_view_mode(ViewMode::TervezoDetail);
+                } else if action == TervezoAction::ComparePrevious {
+                    open_tervezo_compare(app);
+                } else if action == TervezoAction::Restart
+                    && app
+                        .tervezo_detail
+                        .as_ref()
+                        .is_some_and(|s| s.implementation.status == ImplementationStatus::Failed)
+                {
+                    // Ask what to do differently, starting from the failure.
+                    let guidance = app
+                        .tervezo_config()
+                        .zip(app.tervezo_detail.as_ref())
+                        .map(|(config, state)| config.restart_guidance(state.failure_reason()));
+                    if let Some(ref mut state) = app.tervezo_detail {
+                        state.prompt_input = guidance.unwrap_or_default();
+                        state.restart_guidance = true;
+                    }
+                    app.set_view_mode(ViewMode::TervezoPromptInput);
                 } else if action.is_destructive() {
                     if let Some(ref mut state) = app.tervezo_detail {
                         state.confirm_action = Some(action);
@@ -953,10 +1735,73 @@ fn process_action(
                 } else {
                     // Non-destructive: execute immediately
                     app.set_view_mode(ViewMode::TervezoDetail);
-                    execute_tervezo_action(app, action);
+                    execute_tervezo_action(app, action, None);
                 }
             }
         }
+        Action::TervezoAttempt(forward) => {
+            let Some(id) = app.related_attempt(forward) else {
+                let msg = if forward {
+                    "No later attempt"
+                } else {
+                    "No earlier attempt"
+                };
+                app.toast(Severity::Info, t(msg));
+                return Ok(());
+            };
+            let imp = match app.remote_implementation(&id) {
+                Some(imp) => Ok(imp.clone()),
+                None => match app.tervezo_config() {
+                    Some(config) => TervezoClient::new(config).get_implementation(&id),
+                    None => return Ok(()),
+                },
+            };
+            match imp {
+                Ok(imp) => {
+                    app.show_tervezo_implementation(imp);
+                    trigger_tervezo_initial_fetch(app);
+                }
+                Err(e) => app.toast(Severity::Error, e.with_hint()),
+            }
+        }
+        Action::TervezoCompareUp => {
+            if let Some(c) = app
+                .tervezo_detail
+                .as_mut()
+                .and_then(|s| s.comparison.as_mut())
+            {
+                c.up();
+            }
+        }
+        Action::TervezoCompareDown => {
+            if let Some(c) = app
+                .tervezo_detail
+                .as_mut()
+                .and_then(|s| s.comparison.as_mut())
+            {
+                c.down();
+            }
+        }
+        Action::TervezoCompareToggle => {
+            if let Some(c) = app
+                .tervezo_detail
+                .as_mut()
+                .and_then(|s| s.comparison.as_mut())
+            {
+                c.toggle();
+            }
+        }
+        Action::TervezoCompareClose => {
+            let expanded = app
+                .tervezo_detail
+                .as_mut()
+                .and_then(|s| s.comparison.as_mut())
+                .filter(|c| c.expanded);
+            match expanded {
+                Some(c) => c.toggle(),
+                None => app.set_view_mode(ViewMode::TervezoDetail),
+            }
+        }
         Action::TervezoActionMenuClose => {
             app.set_view_mode(ViewMode::TervezoDetail);
         }
@@ -967,7 +1812,7 @@ fn process_action(
                 .and_then(|s| s.confirm_action.take());
             app.set_view_mode(ViewMode::TervezoDetail);
             if let Some(action) = action {
-                execute_tervezo_action(app, action);
+                execute_tervezo_action(app, action, None);
             }
         }
         Action::TervezoConfirmNo => {
@@ -991,7 +1836,6 @@ fn process_action(
                 .unwrap_or(false);
             if can_prompt {
                 if let Some(ref mut state) = app.tervezo_detail {
-                    state.action_result = None;
                     state.prompt_input.clear();
                 }
                 app.set_view_mode(ViewMode::TervezoPromptInput);
@@ -1008,9 +1852,19 @@ fn process_action(
             }
         }
         Action::TervezoPromptSubmit => {
+            let restart = app.tervezo_detail.as_mut().and_then(|s| {
+                std::mem::take(&mut s.restart_guidance).then(|| std::mem::take(&mut s.prompt_input))
+            });
+            let restarting = restart.is_some();
+            if let Some(guidance) = restart {
+                app.set_view_mode(ViewMode::TervezoDetail);
+                let guidance = Some(guidance.trim().to_string()).filter(|g| !g.is_empty());
+                execute_tervezo_action(app, TervezoAction::Restart, guidance);
+            }
             let prompt_data = app
                 .tervezo_detail
                 .as_ref()
+                .filter(|_| !restarting)
                 .map(|s| (s.implementation_id.clone(), s.prompt_input.clone()));
             if let Some((impl_id, message)) = prompt_data {
                 if !message.trim().is_empty() {
@@ -1021,7 +1875,9 @@ fn process_action(
                     if let Some(config) = app.tervezo_config() {
                         let config = config.clone();
                         let tx = app.tervezo_detail_tx.clone();
-                        std::thread::spawn(move || {
+                        // A write: not deduped, and not dropped if the
+                        // view closes before it runs.
+                        wakeup::spawn(move || {
                             let client = TervezoClient::new(&config);
                             match client.send_prompt(&impl_id, &message) {
                                 Ok(resp) => {
@@ -1052,6 +1908,7 @@ fn process_action(
         Action::TervezoPromptCancel => {
             if let Some(ref mut state) = app.tervezo_detail {
                 state.prompt_input.clear();
+                state.restart_guidance = false;
             }
             app.set_view_mode(ViewMode::TervezoDetail);
         }
@@ -1149,6 +2006,202 @@ fn process_action(
                 let _ = st.write_input(&bytes);
             }
         }
+        Action::OpenUserActions => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_action_menu();
+            }
+        }
+        Action::UserActionsClose => {
+            app.set_view_mode(ViewMode::List);
+        }
+        Action::UserActionsUp => {
+            if let Some(ref mut state) = app.action_menu {
+                state.cursor = state.cursor.saturating_sub(1);
+            }
+        }
+        Action::UserActionsDown => {
+            let count = app.user_actions().len();
+            if let Some(ref mut state) = app.action_menu {
+                if state.cursor + 1 < count {
+                    state.cursor += 1;
+                }
+            }
+        }
+        Action::UserActionsSelect => {
+            if let Some(index) = app.action_menu.as_ref().map(|s| s.cursor) {
+                run_user_action(terminal, app, index)?;
+            }
+        }
+        Action::UserActionsRun(index) => run_user_action(terminal, app, index)?,
+        Action::OpenGitMenu => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_git_menu();
+            }
+        }
+        Action::WriteHandoff => {
+            if *app.view_mode() == ViewMode::List {
+                app.write_handoff();
+            }
+        }
+        Action::GitMenuClose => {
+            app.set_view_mode(ViewMode::List);
+        }
+        Action::GitMenuUp => {
+            if let Some(ref mut state) = app.git_menu {
+                state.cursor = state.cursor.saturating_sub(1);
+            }
+        }
+        Action::GitMenuDown => {
+            if let Some(ref mut state) = app.git_menu {
+                state.cursor = (state.cursor + 1).min(state.items.len() - 1);
+            }
+        }
+        Action::GitMenuSelect => {
+            if let Some(index) = app.git_menu.as_ref().map(|s| s.cursor) {
+                app.select_git_item(index);
+            }
+        }
+        Action::GitMenuPick(index) => app.select_git_item(index),
+        Action::GitBranchChar(c) => {
+            if let Some(ref mut state) = app.git_menu {
+                if !c.is_whitespace() {
+                    state.branch.push(c);
+                }
+            }
+        }
+        Action::GitBranchBackspace => {
+            if let Some(ref mut state) = app.git_menu {
+                state.branch.pop();
+            }
+        }
+        Action::GitBranchSubmit => app.submit_git_branch(),
+        Action::OpenCommitPrompt => app.open_commit_prompt(),
+        Action::CommitChar(c) => {
+            if let Some(ref mut state) = app.commit_prompt {
+                state.message.push(c);
+            }
+        }
+        Action::CommitBackspace => {
+            if let Some(ref mut state) = app.commit_prompt {
+                state.message.pop();
+            }
+        }
+        Action::CommitTogglePr => {
+            if let Some(ref mut state) = app.commit_prompt {
+                state.pull_request = !state.pull_request;
+            }
+        }
+        Action::CommitSubmit => app.submit_commit(),
+        Action::CommitCancel => app.set_view_mode(ViewMode::Detail),
+        Action::OpenTicket => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_ticket();
+            }
+        }
+        Action::EditTicket => {
+            if *app.view_mode() == ViewMode::List {
+                app.edit_ticket();
+            }
+        }
+        Action::TicketChar(c) => {
+            if let Some(ref mut state) = app.ticket_input {
+                if !c.is_whitespace() {
+                    state.input.push(c);
+                }
+            }
+        }
+        Action::TicketBackspace => {
+            if let Some(ref mut state) = app.ticket_input {
+                state.input.pop();
+            }
+        }
+        Action::TicketSubmit => app.submit_ticket(),
+        Action::RateSession => {
+            if *app.view_mode() == ViewMode::List {
+                app.rate_selected();
+            }
+        }
+        Action::OutcomePick(outcome) => {
+            if let Some(ref mut state) = app.outcome_prompt {
+                state.outcome = Some(outcome);
+            }
+        }
+        Action::OutcomeRating(rating) => {
+            if let Some(ref mut state) = app.outcome_prompt {
+                state.rating = Some(rating);
+            }
+        }
+        Action::OutcomeSubmit => app.submit_outcome(),
+        Action::ShowOutcomes => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_outcomes();
+            }
+        }
+        Action::ToggleHideSession => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_hide_selected();
+            }
+        }
+        Action::ToggleShowHidden => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_show_hidden();
+            }
+        }
+        Action::ShowStats => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_stats();
+            }
+        }
+        Action::ToggleStatsMetric => {
+            if let Some(ref mut stats) = app.cost_stats {
+                stats.tokens = !stats.tokens;
+            }
+        }
+        Action::ToggleResources => {
+            if *app.view_mode() == ViewMode::List {
+                app.toggle_resources();
+            }
+        }
+        Action::RestartHung => {
+            if *app.view_mode() == ViewMode::List {
+                let size = terminal.size()?;
+                app.restart_selected(size.height.saturating_sub(1), size.width);
+            }
+        }
+        Action::ShowDashboard => {
+            if *app.view_mode() == ViewMode::List {
+                app.set_view_mode(ViewMode::Dashboard);
+            }
+        }
+        Action::OpenOrgPicker => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_org_picker();
+            }
+        }
+        Action::OrgPickerUp => {
+            if let Some(ref mut state) = app.org_picker {
+                state.cursor = state.cursor.saturating_sub(1);
+            }
+        }
+        Action::OrgPickerDown => {
+            if let Some(ref mut state) = app.org_picker {
+                if state.cursor + 1 < state.orgs.len() {
+                    state.cursor += 1;
+                }
+            }
+        }
+        Action::OrgPickerSelect => app.switch_org(),
+        Action::ShowQueue => {
+            if *app.view_mode() == ViewMode::List {
+                app.open_queue();
+            }
+        }
+        Action::QueueUp => app.queue_up(),
+        Action::QueueDown => app.queue_down(),
+        Action::QueueBump => app.queue_bump(false),
+        Action::QueueToFront => app.queue_bump(true),
+        Action::QueueCancel => app.queue_confirm_cancel(),
+        Action::QueueCancelConfirm(confirmed) => app.queue_finish_cancel(confirmed),
         Action::NewSessionMenuClose => {
             app.set_view_mode(ViewMode::List);
         }
@@ -1168,6 +2221,7 @@ fn process_action(
         }
         Action::NewSessionMenuSelect => {
             if let Some(ref state) = app.new_session_menu {
+                let session = state.session.clone();
                 match state.items[state.cursor] {
                     app::NewSessionOption::Local => {
                         app.set_view_mode(ViewMode::Command);
@@ -1175,6 +2229,31 @@ fn process_action(
                     app::NewSessionOption::Tervezo => {
                         app.set_view_mode(ViewMode::TervezoCreateDialog);
                     }
+                    app::NewSessionOption::ResumeHere => {
+                        if let Some(session) = session {
+                            let area = terminal.size()?;
+                            let result = app.terminal_manager_mut().attach(
+                                &session.id,
+                                &session.project_name,
+                                &session.cwd,
+                                None,
+                                area.height.saturating_sub(1),
+                                area.width,
+                            );
+                            enter_terminal(app, result);
+                        }
+                    }
+                    app::NewSessionOption::FreshHere => {
+                        if let Some(session) = session {
+                            let area = terminal.size()?;
+                            let result = app.terminal_manager_mut().attach_new(
+                                &session.cwd,
+                                area.height.saturating_sub(1),
+                                area.width,
+                            );
+                            enter_terminal(app, result);
+                        }
+                    }
                 }
             }
         }
@@ -1199,7 +2278,7 @@ fn process_action(
                             let config = config.clone();
                             let status_id = impl_id.clone();
                             app.ci_statuses.insert(status_id, app::CiStatus::Fixing);
-                            std::thread::spawn(move || {
+                            wakeup::spawn(move || {
                                 let client = TervezoClient::new(&config);
                                 match client.send_prompt(&impl_id, &prompt) {
                                     Ok(resp) => {
@@ -1247,74 +2326,66 @@ fn trigger_tervezo_panel_refresh(app: &mut App) {
         None => return,
     };
 
+    // Periodic refresh of a running implementation: revalidate, don't reuse
+    TervezoClient::new(&config).expire_cached(&impl_id);
+
     // Fetch all four panels + status, skipping any already in-flight
-    let tabs = [
-        TervezoTab::Plan,
-        TervezoTab::Analysis,
-        TervezoTab::Changes,
-        TervezoTab::TestOutput,
-    ];
-    for &tab in &tabs {
+    for &tab in TervezoTab::all() {
         if loading.contains(&tab) {
             continue;
         }
-        if let Some(ref mut state) = app.tervezo_detail {
-            state.loading.insert(tab);
-        }
-        let tx = tx.clone();
-        let config = config.clone();
-        let impl_id = impl_id.clone();
-        std::thread::spawn(move || {
-            let client = TervezoClient::new(&config);
-            match tab {
-                TervezoTab::Plan => match client.get_plan(&impl_id) {
-                    Ok(plan) => {
-                        let _ = tx.send(TervezoDetailMsg::Plan(plan));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                    }
-                },
-                TervezoTab::Analysis => match client.get_analysis(&impl_id) {
-                    Ok(analysis) => {
-                        let _ = tx.send(TervezoDetailMsg::Analysis(analysis));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                    }
-                },
-                TervezoTab::Changes => match client.get_changes(&impl_id) {
-                    Ok(changes) => {
-                        let _ = tx.send(TervezoDetailMsg::Changes(changes));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                    }
-                },
-                TervezoTab::TestOutput => match client.get_test_output(&impl_id) {
-                    Ok(reports) => {
-                        let _ = tx.send(TervezoDetailMsg::TestOutput(reports));
-                    }
-                    Err(e) => {
-                        let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                    }
-                },
+        if spawn_tab_fetch(app, &config, &tx, &impl_id, tab) {
+            if let Some(ref mut state) = app.tervezo_detail {
+                state.loading.insert(tab);
             }
-        });
+        }
     }
 
     // Also refresh status
-    {
-        let tx = tx.clone();
-        let config = config.clone();
-        let impl_id = impl_id.clone();
-        std::thread::spawn(move || {
-            let client = TervezoClient::new(&config);
-            if let Ok(status) = client.get_status(&impl_id) {
-                let _ = tx.send(TervezoDetailMsg::Status(status));
-            }
-        });
-    }
+    spawn_status_fetch(app, &config, &tx, &impl_id);
+}
+
+/// Fetches one detail tab and wraps the result for the detail channel.
+fn fetch_tervezo_tab(client: &TervezoClient, impl_id: &str, tab: TervezoTab) -> TervezoDetailMsg {
+    let result = match tab {
+        TervezoTab::Plan => client.get_plan(impl_id).map(TervezoDetailMsg::Plan),
+        TervezoTab::Changes => client.get_changes(impl_id).map(TervezoDetailMsg::Changes),
+        TervezoTab::TestOutput => client
+            .get_test_output(impl_id)
+            .map(TervezoDetailMsg::TestOutput),
+        TervezoTab::Analysis => client.get_analysis(impl_id).map(TervezoDetailMsg::Analysis),
+    };
+    result.unwrap_or_else(|e| TervezoDetailMsg::Error(tab, e))
+}
+
+/// Queues a fetch of `tab`; false if one is already in flight.
+fn spawn_tab_fetch(
+    app: &App,
+    config: &TervezoConfig,
+    tx: &mpsc::Sender<TervezoDetailMsg>,
+    impl_id: &str,
+    tab: TervezoTab,
+) -> bool {
+    let (config, tx, impl_id) = (config.clone(), tx.clone(), impl_id.to_string());
+    app.spawn_detail_task(tab.label(), move || {
+        let client = TervezoClient::new(&config);
+        let _ = tx.send(fetch_tervezo_tab(&client, &impl_id, tab));
+    })
+}
+
+fn spawn_status_fetch(
+    app: &App,
+    config: &TervezoConfig,
+    tx: &mpsc::Sender<TervezoDetailMsg>,
+    impl_id: &str,
+) {
+    let (config, tx, impl_id) = (config.clone(), tx.clone(), impl_id.to_string());
+    app.spawn_detail_task("status", move || {
+        let client = TervezoClient::new(&config);
+        if let Ok(status) = client.get_status(&impl_id) {
+            let _ = tx.send(TervezoDetailMsg::Status(status));
+        }
+    });
 }
 
 fn trigger_tervezo_initial_fetch(app: &mut App) {
@@ -1332,102 +2403,45 @@ fn trigger_tervezo_initial_fetch(app: &mut App) {
     };
 
     if let Some(ref mut state) = app.tervezo_detail {
-        state.loading.insert(TervezoTab::Plan);
-        state.loading.insert(TervezoTab::Analysis);
-        state.loading.insert(TervezoTab::Changes);
-        state.loading.insert(TervezoTab::TestOutput);
+        state.loading.extend(TervezoTab::all());
         state.timeline_error = None;
+        state.tab_errors.clear();
     }
 
-    // Fetch timeline + plan + status + analysis + changes + test output on background threads
-    let tx_timeline = tx.clone();
-    let tx_plan = tx.clone();
-    let tx_status = tx.clone();
-    let tx_analysis = tx.clone();
-    let tx_changes = tx.clone();
-    let tx_test = tx.clone();
-    let config_timeline = config.clone();
-    let config_status = config.clone();
-    let config_analysis = config.clone();
-    let config_changes = config.clone();
-    let config_test = config.clone();
-    let id_timeline = impl_id.clone();
-    let id_plan = impl_id.clone();
-    let id_status = impl_id.clone();
-    let id_analysis = impl_id.clone();
-    let id_changes = impl_id.clone();
-    let id_test = impl_id.clone();
-
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config_timeline);
-        match client.get_timeline(&id_timeline, None) {
-            Ok(msgs) => {
-                let _ = tx_timeline.send(TervezoDetailMsg::Timeline(msgs));
-            }
-            Err(e) => {
-                let _ = tx_timeline.send(TervezoDetailMsg::TimelineError(e));
-            }
-        }
-    });
-
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config);
-        match client.get_plan(&id_plan) {
-            Ok(plan) => {
-                let _ = tx_plan.send(TervezoDetailMsg::Plan(plan));
-            }
-            Err(e) => {
-                let _ = tx_plan.send(TervezoDetailMsg::Error(TervezoTab::Plan, e));
-            }
-        }
-    });
-
-    // Fetch status (steps info)
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config_status);
-        if let Ok(status) = client.get_status(&id_status) {
-            let _ = tx_status.send(TervezoDetailMsg::Status(status));
-        }
-    });
-
-    // Fetch analysis
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config_analysis);
-        match client.get_analysis(&id_analysis) {
-            Ok(analysis) => {
-                let _ = tx_analysis.send(TervezoDetailMsg::Analysis(analysis));
-            }
-            Err(e) => {
-                let _ = tx_analysis.send(TervezoDetailMsg::Error(TervezoTab::Analysis, e));
-            }
-        }
-    });
-
-    // Fetch changes
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config_changes);
-        match client.get_changes(&id_changes) {
-            Ok(changes) => {
-                let _ = tx_changes.send(TervezoDetailMsg::Changes(changes));
-            }
-            Err(e) => {
-                let _ = tx_changes.send(TervezoDetailMsg::Error(TervezoTab::Changes, e));
-            }
-        }
-    });
-
-    // Fetch test output
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config_test);
-        match client.get_test_output(&id_test) {
-            Ok(reports) => {
-                let _ = tx_test.send(TervezoDetailMsg::TestOutput(reports));
-            }
-            Err(e) => {
-                let _ = tx_test.send(TervezoDetailMsg::Error(TervezoTab::TestOutput, e));
+    // Fetch timeline + plan + status + analysis + changes + test output on the task pool
+    {
+        let (config, tx, impl_id) = (config.clone(), tx.clone(), impl_id.clone());
+        app.spawn_detail_task("timeline", move || {
+            let client = TervezoClient::new(&config);
+            // Newest page only; show it as it downloads
+            let mut first = true;
+            let result = client.stream_timeline_page(&impl_id, None, TIMELINE_PAGE, &mut |batch| {
+                let msg = if std::mem::take(&mut first) {
+                    TervezoDetailMsg::Timeline(batch)
+                } else {
+                    TervezoDetailMsg::TimelineMore(batch)
+                };
+                let _ = tx.send(msg);
+                wakeup::notify();
+            });
+            match result {
+                Ok(stats) => {
+                    if first {
+                        let _ = tx.send(TervezoDetailMsg::Timeline(Vec::new()));
+                    }
+                    let has_older = stats.has_more.unwrap_or(false);
+                    let _ = tx.send(TervezoDetailMsg::TimelineHasOlder(has_older));
+                }
+                Err(e) => {
+                    let _ = tx.send(TervezoDetailMsg::TimelineError(e));
+                }
             }
-        }
-    });
+        });
+    }
+    for &tab in TervezoTab::all() {
+        spawn_tab_fetch(app, &config, &tx, &impl_id, tab);
+    }
+    spawn_status_fetch(app, &config, &tx, &impl_id);
 
     // Fetch PR details if implementation has a PR
     let has_pr = app
@@ -1436,50 +2450,69 @@ fn trigger_tervezo_initial_fetch(app: &mut App) {
         .map(|s| s.implementation.pr_url.is_some())
         .unwrap_or(false);
     if has_pr {
-        let pr_config = match app.tervezo_config() {
-            Some(c) => c.clone(),
-            None => return,
-        };
-        let pr_tx = match app.tervezo_detail_tx.clone() {
-            Some(tx) => tx,
-            None => return,
-        };
-        let pr_id = impl_id;
-        std::thread::spawn(move || {
-            let client = TervezoClient::new(&pr_config);
-            if let Ok(pr) = client.get_pr_details(&pr_id) {
-                let _ = pr_tx.send(TervezoDetailMsg::PrDetails(pr));
+        let (config, tx, impl_id) = (config.clone(), tx.clone(), impl_id.clone());
+        app.spawn_detail_task("pr", move || {
+            let client = TervezoClient::new(&config);
+            if let Ok(pr) = client.get_pr_details(&impl_id) {
+                let _ = tx.send(TervezoDetailMsg::PrDetails(pr));
             }
         });
     }
 
     // For running implementations: start SSE stream + fetch SSH creds
-    let running_info = app.tervezo_detail.as_ref().and_then(|s| {
-        if s.implementation.status.is_running() {
-            Some(s.implementation_id.clone())
-        } else {
-            None
-        }
-    });
+    let running = app
+        .tervezo_detail
+        .as_ref()
+        .is_some_and(|s| s.implementation.status.is_running());
 
-    if let Some(impl_id_sse) = running_info {
-        app.start_sse_stream(&impl_id_sse);
+    if running {
+        app.start_sse_stream(&impl_id);
 
         // Fetch SSH credentials in background
-        if let Some(config) = app.tervezo_config() {
-            let ssh_config = config.clone();
-            let ssh_id = impl_id_sse;
-            let ssh_tx = match app.tervezo_detail_tx.clone() {
-                Some(tx) => tx,
+        app.spawn_detail_task("ssh", move || {
+            let client = TervezoClient::new(&config);
+            if let Ok(creds) = client.get_ssh(&impl_id) {
+                let _ = tx.send(TervezoDetailMsg::SshCreds(creds));
+            }
+        });
+    }
+}
+
+/// Fetches the page of timeline messages before the oldest one shown.
+fn trigger_tervezo_load_older(app: &mut App) {
+    let config = match app.tervezo_config() {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let tx = match app.tervezo_detail_tx.clone() {
+        Some(tx) => tx,
+        None => return,
+    };
+    let (impl_id, before) = match app.tervezo_detail.as_ref() {
+        Some(state) if state.timeline_has_older && !state.timeline_loading_older => {
+            match state.timeline.first().and_then(|m| m.id.clone()) {
+                Some(before) => (state.implementation_id.clone(), before),
                 None => return,
-            };
+            }
+        }
+        _ => return,
+    };
 
-            std::thread::spawn(move || {
-                let client = TervezoClient::new(&ssh_config);
-                if let Ok(creds) = client.get_ssh(&ssh_id) {
-                    let _ = ssh_tx.send(TervezoDetailMsg::SshCreds(creds));
-                }
-            });
+    let queued = app.spawn_detail_task("timeline-older", move || {
+        let client = TervezoClient::new(&config);
+        let mut msgs = Vec::new();
+        let msg =
+            match client.stream_timeline_page(&impl_id, Some(&before), TIMELINE_PAGE, &mut |b| {
+                msgs.extend(b)
+            }) {
+                Ok(stats) => TervezoDetailMsg::TimelineOlder(msgs, stats.has_more.unwrap_or(false)),
+                Err(e) => TervezoDetailMsg::TimelineError(e),
+            };
+        let _ = tx.send(msg);
+    });
+    if queued {
+        if let Some(ref mut state) = app.tervezo_detail {
+            state.timeline_loading_older = true;
         }
     }
 }
@@ -1516,53 +2549,16 @@ fn trigger_tervezo_tab_fetch(app: &mut App) {
         return;
     }
 
-    if let Some(ref mut state) = app.tervezo_detail {
-        if state.loading.contains(&tab) {
-            return;
+    if spawn_tab_fetch(app, &config, &tx, &impl_id, tab) {
+        if let Some(ref mut state) = app.tervezo_detail {
+            state.loading.insert(tab);
         }
-        state.loading.insert(tab);
     }
-
-    std::thread::spawn(move || {
-        let client = TervezoClient::new(&config);
-        match tab {
-            TervezoTab::Plan => match client.get_plan(&impl_id) {
-                Ok(plan) => {
-                    let _ = tx.send(TervezoDetailMsg::Plan(plan));
-                }
-                Err(e) => {
-                    let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                }
-            },
-            TervezoTab::Changes => match client.get_changes(&impl_id) {
-                Ok(changes) => {
-                    let _ = tx.send(TervezoDetailMsg::Changes(changes));
-                }
-                Err(e) => {
-                    let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                }
-            },
-            TervezoTab::TestOutput => match client.get_test_output(&impl_id) {
-                Ok(reports) => {
-                    let _ = tx.send(TervezoDetailMsg::TestOutput(reports));
-                }
-                Err(e) => {
-                    let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                }
-            },
-            TervezoTab::Analysis => match client.get_analysis(&impl_id) {
-                Ok(analysis) => {
-                    let _ = tx.send(TervezoDetailMsg::Analysis(analysis));
-                }
-                Err(e) => {
-                    let _ = tx.send(TervezoDetailMsg::Error(tab, e));
-                }
-            },
-        }
-    });
 }
 
-fn execute_tervezo_action(app: &mut App, action: TervezoAction) {
+/// Runs `action` on the open implementation; `guidance` goes along with a
+/// restart.
+fn execute_tervezo_action(app: &mut App, action: TervezoAction, guidance: Option<String>) {
     let config = match app.tervezo_config() {
         Some(c) => c.clone(),
         None => return,
@@ -1576,13 +2572,10 @@ fn execute_tervezo_action(app: &mut App, action: TervezoAction) {
         None => return,
     };
 
-    if let Some(ref mut state) = app.tervezo_detail {
-        state.action_loading = true;
-    }
-
-    std::thread::spawn(move || {
+    // Writes run on their own thread, outside the view's cancel token.
+    wakeup::spawn(move || {
         let client = TervezoClient::new(&config);
-        let result: Result<String, String> = match action {
+        let result: Result<String, TervezoError> = match action {
             TervezoAction::CreatePr => client.create_pr(&impl_id).map(|r| {
                 let url = r.pr_url.unwrap_or_default();
                 format!("PR created: {}", url)
@@ -1592,19 +2585,21 @@ fn execute_tervezo_action(app: &mut App, action: TervezoAction) {
             TervezoAction::ReopenPr => client
                 .reopen_pr(&impl_id)
                 .map(|_| "PR reopened".to_string()),
-            TervezoAction::Restart => client.restart(&impl_id).map(|r| {
-                if r.is_new_implementation {
-                    let new_id = r.implementation_id.unwrap_or_default();
-                    format!("NAVIGATE:{}", new_id)
-                } else {
-                    "Restarted".to_string()
-                }
-            }),
+            TervezoAction::Restart => client
+                .restart_with_guidance(&impl_id, guidance.as_deref())
+                .map(|r| {
+                    if r.is_new_implementation {
+                        let new_id = r.implementation_id.unwrap_or_default();
+                        format!("NAVIGATE:{}", new_id)
+                    } else {
+                        "Restarted".to_string()
+                    }
+                }),
             TervezoAction::SendPrompt => {
                 // Should not reach here — handled via prompt input mode
                 Ok("(use prompt input)".to_string())
             }
-            TervezoAction::ViewPrInBrowser => {
+            TervezoAction::ViewPrInBrowser | TervezoAction::ComparePrevious => {
                 // Handled synchronously in action menu select
                 Ok("(handled locally)".to_string())
             }
@@ -1619,6 +2614,43 @@ fn execute_tervezo_action(app: &mut App, action: TervezoAction) {
             }
         }
     });
+    if let Some(ref mut state) = app.tervezo_detail {
+        state.action_loading = true;
+    }
+}
+
+/// Fetches the changes of the open implementation and of the attempt it
+/// retried, and shows how they differ.
+fn open_tervezo_compare(app: &mut App) {
+    let config = match app.tervezo_config() {
+        Some(c) => c.clone(),
+        None => return,
+    };
+    let tx = match app.tervezo_detail_tx.clone() {
+        Some(tx) => tx,
+        None => return,
+    };
+    let Some(state) = app.tervezo_detail.as_mut() else {
+        return;
+    };
+    let Some(previous_id) = state.previous_attempt.clone() else {
+        return;
+    };
+    let impl_id = state.implementation_id.clone();
+    state.comparison = Some(app::ComparisonState::new(previous_id.clone()));
+    app.set_view_mode(ViewMode::TervezoCompare);
+
+    app.spawn_detail_task("compare", move || {
+        let client = TervezoClient::new(&config);
+        let result = client
+            .get_changes(&previous_id)
+            .and_then(|before| Ok((before, client.get_changes(&impl_id)?)));
+        let msg = match result {
+            Ok((before, after)) => TervezoDetailMsg::Comparison(compare_changes(&before, &after)),
+            Err(e) => TervezoDetailMsg::ComparisonError(e),
+        };
+        let _ = tx.send(msg);
+    });
 }
 
 fn submit_tervezo_create(app: &mut App) {
@@ -1689,7 +2721,7 @@ fn submit_tervezo_create(app: &mut App) {
         },
     };
 
-    std::thread::spawn(move || {
+    wakeup::spawn(move || {
         let client = TervezoClient::new(&config);
         match client.create_implementation(&request) {
             Ok(implementation) => {
@@ -1702,6 +2734,49 @@ fn submit_tervezo_create(app: &mut App) {
     });
 }
 
+/// Shows what a `c9s://` link points at, over whatever view is open.
+fn open_link(
+    app: &mut App,
+    link: deeplink::DeepLink,
+    terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
+) -> Result<()> {
+    tlog!(info, "opening {}", link);
+    match link {
+        deeplink::DeepLink::Attach(id) => {
+            if app.select_id(&id) {
+                app.set_view_mode(ViewMode::List);
+                attach_selected(app, terminal)?;
+            }
+        }
+        deeplink::DeepLink::Session(id) => {
+            if app.select_id(&id) {
+                app.set_view_mode(ViewMode::Detail);
+            }
+        }
+        deeplink::DeepLink::Tervezo(id) => {
+            let Some(config) = app.tervezo_config() else {
+                app.toast(Severity::Warning, t("Tervezo is not set up"));
+                return Ok(());
+            };
+            match TervezoClient::new(config).get_implementation(&id) {
+                Ok(imp) => {
+                    app.show_tervezo_implementation(imp);
+                    app.set_view_mode(ViewMode::TervezoDetail);
+                    trigger_tervezo_initial_fetch(app);
+                }
+                Err(e) => app.toast(
+                    Severity::Warning,
+                    tf(
+                        "Tervezo {id}: {error}",
+                        &[("id", &id), ("error", &e.with_hint())],
+                    ),
+                ),
+            }
+        }
+    }
+    Ok(())
+}
+
 fn attach_selected(
     app: &mut App,
     terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
@@ -1709,6 +2784,11 @@ fn attach_selected(
     if let Some(entry) = app.selected_session() {
         match entry {
             SessionEntry::Local(session) => {
+                let session = app.attach_target(session);
+                if session.imported {
+                    app.toast_imported();
+                    return Ok(());
+                }
                 let id = session.id.clone();
                 let name = session.project_name.clone();
                 let cwd = session.cwd.clone();
@@ -1716,9 +2796,7 @@ fn attach_selected(
                 let area = terminal.size()?;
                 let rows = area.height.saturating_sub(1);
                 let cols = area.width;
-                app.terminal_manager_mut()
-                    .attach(&id, &name, &cwd, pid, rows, cols)?;
-                app.set_view_mode(ViewMode::Terminal);
+                attach_local(app, &id, &name, &cwd, pid, rows, cols);
             }
             SessionEntry::Remote(_) => {
                 tlog!(info, "DIAG: attach_selected → switching to TervezoDetail");
@@ -1749,13 +2827,16 @@ fn attach_by_index(
     terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
 ) -> Result<()> {
     let entry_data: Option<EntryData> = app.filtered_sessions().get(idx).map(|e| match e {
-        SessionEntry::Local(s) => (
-            s.id.clone(),
-            s.project_name.clone(),
-            Some(s.cwd.clone()),
-            s.pid,
-            false,
-        ),
+        SessionEntry::Local(s) => {
+            let s = app.attach_target(s);
+            (
+                s.id.clone(),
+                s.project_name.clone(),
+                Some(s.cwd.clone()).filter(|_| !s.imported),
+                s.pid,
+                false,
+            )
+        }
         SessionEntry::Remote(i) => (i.id.clone(), i.display_name().to_string(), None, None, true),
     });
 
@@ -1764,14 +2845,137 @@ fn attach_by_index(
             app.set_selected(idx);
             app.set_view_mode(ViewMode::TervezoDetail);
             trigger_tervezo_initial_fetch(app);
+        } else if cwd.is_none() {
+            app.toast_imported();
         } else if let Some(cwd) = cwd {
             let area = terminal.size()?;
             let rows = area.height.saturating_sub(1);
             let cols = area.width;
-            app.terminal_manager_mut()
-                .attach(&id, &name, &cwd, pid, rows, cols)?;
-            app.set_view_mode(ViewMode::Terminal);
+            attach_local(app, &id, &name, &cwd, pid, rows, cols);
         }
     }
     Ok(())
 }
+
+/// Attaches to a local session, resuming it in a new tab unless one is
+/// open. A session running in a terminal that isn't ours (another window,
+/// a tmux pane) would be ended there, so that asks first.
+fn attach_local(
+    app: &mut App,
+    id: &str,
+    name: &str,
+    cwd: &std::path::Path,
+    pid: Option<u32>,
+    rows: u16,
+    cols: u16,
+) {
+    if !app.terminal_manager().is_attached(id) {
+        if let Some(pid) = pid {
+            if let Some(tty) = app.terminal_manager().foreign_tty(pid) {
+                app.confirm_attach = Some(app::PendingAttach {
+                    session_id: id.to_string(),
+                    project_name: name.to_string(),
+                    cwd: cwd.to_path_buf(),
+                    pid,
+                    tty,
+                });
+                app.set_view_mode(ViewMode::ConfirmAttach);
+                return;
+            }
+        }
+    }
+    let result = app
+        .terminal_manager_mut()
+        .attach(id, name, cwd, pid, rows, cols);
+    enter_terminal(app, result);
+}
+
+/// Opens a terminal tab running the `index`-th `[[actions]]` command for
+/// the session in the action menu.
+fn run_user_action(
+    terminal: &Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut App,
+    index: usize,
+) -> Result<()> {
+    let Some((label, command, cwd)) = app.user_action_command(index) else {
+        return Ok(());
+    };
+    tlog!(
+        info,
+        "running action {} in {}: {}",
+        label,
+        cwd.display(),
+        command
+    );
+    let area = terminal.size()?;
+    let result = app.terminal_manager_mut().attach_command(
+        &label,
+        &command,
+        &cwd,
+        area.height.saturating_sub(1),
+        area.width,
+    );
+    enter_terminal(app, result);
+    Ok(())
+}
+
+/// Ends the session the kill dialog asked about: with `/exit` when it has
+/// a live tab and `force` isn't set, else with SIGTERM.
+fn kill_confirmed(app: &mut App, force: bool) {
+    let killed = app.confirm_kill_session_id.take();
+    if let Some(ref session_id) = killed {
+        if let Some((pid, name)) = app
+            .all_sessions()
+            .iter()
+            .find(|s| &s.id == session_id)
+            .and_then(|s| Some((s.pid?, s.project_name.clone())))
+        {
+            let soft = !force && app.terminal_manager().can_soft_kill(session_id);
+            let exited = soft
+                && match app.terminal_manager_mut().soft_kill(session_id, pid) {
+                    Ok(()) => {
+                        tlog!(info, "Sent /exit to {} (pid {})", name, pid);
+                        true
+                    }
+                    Err(e) => {
+                        tlog!(warn, "/exit to {} failed, signalling: {}", name, e);
+                        false
+                    }
+                };
+            if !exited {
+                // SAFETY: sends SIGTERM to the session's claude process.
+                let sent = unsafe { libc::kill(pid as i32, libc::SIGTERM) } == 0;
+                if sent {
+                    tlog!(info, "Sent SIGTERM to {} (pid {})", name, pid);
+                } else {
+                    let e = std::io::Error::last_os_error();
+                    app.toast(
+                        Severity::Error,
+                        tf(
+                            "Could not kill {name} (pid {pid}): {error}",
+                            &[
+                                ("name", &name),
+                                ("pid", &pid.to_string()),
+                                ("error", &e.to_string()),
+                            ],
+                        ),
+                    );
+                }
+            }
+        }
+    }
+    app.set_view_mode(ViewMode::List);
+    let _ = app.refresh();
+    if let Some(session_id) = killed {
+        app.prompt_outcome(&session_id);
+    }
+}
+
+/// Switches to the terminal view once a tab is open. A failed spawn or
+/// attach is shown in a modal over the previous view instead.
+fn enter_terminal<T>(app: &mut App, result: Result<T>) {
+    match result {
+        Ok(_) => app.set_view_mode(ViewMode::Terminal),
+        Err(e) => app.show_error("Could not open terminal", &e),
+    }
+}
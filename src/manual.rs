@@ -0,0 +1,342 @@
+//! `c9s help <topic>` and `c9s man`, written from the clap definition in
+//! `cli`, the help overlay's key table and `docs/config.toml`, so the
+//! printed help, the man page and the `?` overlay can't disagree.
+
+use anyhow::Result;
+use clap::{Arg, Command, CommandFactory};
+
+use crate::cli::Cli;
+use crate::ui::help::BINDINGS;
+
+/// Every setting with a comment on what it does.
+pub const CONFIG: &str = include_str!("../docs/config.toml");
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Keys,
+    Config,
+    Tervezo,
+}
+
+impl Topic {
+    pub const ALL: [Topic; 3] = [Topic::Keys, Topic::Config, Topic::Tervezo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Keys => "keys",
+            Self::Config => "config",
+            Self::Tervezo => "tervezo",
+        }
+    }
+
+    fn parse(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|t| t.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Usage without a topic, the topic's text, or a command's long help.
+pub fn help(topic: Option<&str>) -> Result<String> {
+    let mut cmd = Cli::command();
+    cmd.build();
+    let Some(name) = topic else {
+        return Ok(cmd.render_long_help().to_string());
+    };
+    if let Some(topic) = Topic::parse(name) {
+        return Ok(text(topic));
+    }
+    match cmd.find_subcommand_mut(name).filter(|c| !c.is_hide_set()) {
+        Some(sub) => Ok(sub.render_long_help().to_string()),
+        None => anyhow::bail!(
+            "no help for '{}': try {} or a command",
+            name,
+            Topic::ALL.map(Topic::name).join(", ")
+        ),
+    }
+}
+
+pub fn text(topic: Topic) -> String {
+    match topic {
+        Topic::Keys => {
+            let mut out = String::new();
+            for (title, keys) in key_sections() {
+                if !out.is_empty() {
+                    out.push('\n');
+                }
+                out.push_str(&format!("{}:\n", title));
+                for (key, desc) in keys {
+                    out.push_str(&format!("  {:<18}{}\n", key, desc));
+                }
+            }
+            out
+        }
+        Topic::Config => CONFIG.to_string(),
+        Topic::Tervezo => {
+            let mut out = format!("{}\n\nIn the session list:\n", TERVEZO_INTRO);
+            for (key, desc) in tervezo_list_keys() {
+                out.push_str(&format!("  {:<18}{}\n", key, desc));
+            }
+            for (title, keys) in key_sections() {
+                if title == TERVEZO_DETAIL {
+                    out.push_str(&format!("\nIn the {} view:\n", title));
+                    for (key, desc) in keys {
+                        out.push_str(&format!("  {:<18}{}\n", key, desc));
+                    }
+                }
+            }
+            out.push_str("\nSettings in ~/.c9s/config.toml:\n\n");
+            out.push_str(&config_section("tervezo"));
+            out
+        }
+    }
+}
+
+const TERVEZO_INTRO: &str = "Tervezo runs implementations remotely. c9s lists them with the local \
+sessions once an API key is set, in TERVEZO_API_KEY or as api_key under [tervezo] in \
+~/.c9s/config.toml.";
+
+/// The BINDINGS section with the Tervezo detail view's keys.
+const TERVEZO_DETAIL: &str = "Tervezo detail";
+
+type KeySection = (&'static str, Vec<(&'static str, &'static str)>);
+
+/// BINDINGS split at its section headers; the keys before the first header
+/// are the session list's.
+fn key_sections() -> Vec<KeySection> {
+    let mut sections: Vec<KeySection> = vec![("Session list", Vec::new())];
+    for &(key, desc) in BINDINGS {
+        match (key.is_empty(), desc.is_empty()) {
+            (true, true) => {}
+            (false, true) => sections.push((key, Vec::new())),
+            _ => sections.last_mut().unwrap().1.push((key, desc)),
+        }
+    }
+    sections
+}
+
+fn tervezo_list_keys() -> Vec<(&'static str, &'static str)> {
+    key_sections()
+        .into_iter()
+        .next()
+        .map(|(_, keys)| keys)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|(_, desc)| desc.to_lowercase().contains("tervezo"))
+        .collect()
+}
+
+/// `[name]` and its settings from CONFIG, up to the next section.
+fn config_section(name: &str) -> String {
+    let header = format!("[{}]", name);
+    let mut lines = CONFIG
+        .lines()
+        .skip_while(|l| *l != header)
+        .enumerate()
+        .take_while(|(i, l)| *i == 0 || !l.starts_with('['))
+        .map(|(_, l)| l)
+        .collect::<Vec<_>>();
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+/// The man page in roff.
+pub fn man_page() -> String {
+    let mut cmd = Cli::command();
+    cmd.build();
+    let about = cmd.get_about().map(|a| a.to_string()).unwrap_or_default();
+    let mut out = format!(
+        ".TH C9S 1 \"\" \"c9s {}\" \"User Commands\"\n.SH NAME\nc9s \\- {}\n",
+        env!("CARGO_PKG_VERSION"),
+        roff(about.trim_start_matches("c9s - "))
+    );
+    out.push_str(&format!(".SH SYNOPSIS\n{}\n", usage(&mut cmd.clone())));
+    if let Some(description) = cmd.get_after_help() {
+        out.push_str(&format!(
+            ".SH DESCRIPTION\n{}\n",
+            roff(&description.to_string())
+        ));
+    }
+
+    out.push_str(".SH OPTIONS\n");
+    for arg in cmd.get_arguments().filter(|a| !a.is_hide_set()) {
+        out.push_str(&man_arg(arg));
+    }
+
+    out.push_str(".SH COMMANDS\n");
+    for sub in cmd.get_subcommands().filter(|c| !c.is_hide_set()) {
+        out.push_str(&format!(
+            ".TP\n{}\n{}\n",
+            usage(&mut sub.clone()),
+            roff(&sub.get_about().map(|a| a.to_string()).unwrap_or_default())
+        ));
+        let args: Vec<&Arg> = sub
+            .get_arguments()
+            .filter(|a| !a.is_hide_set() && a.get_id() != "help")
+            .collect();
+        if !args.is_empty() {
+            out.push_str(".RS\n");
+            for arg in args {
+                out.push_str(&man_arg(arg));
+            }
+            out.push_str(".RE\n");
+        }
+    }
+
+    out.push_str(".SH KEYS\n");
+    for (title, keys) in key_sections() {
+        out.push_str(&format!(".SS {}\n", roff(title)));
+        for (key, desc) in keys {
+            out.push_str(&format!(".TP\n\\fB{}\\fR\n{}\n", roff(key), roff(desc)));
+        }
+    }
+
+    out.push_str(&format!(
+        ".SH TERVEZO\n{}\n.SH CONFIGURATION\nSettings are read from \\fI~/.c9s/config.toml\\fR; \
+         all are optional.\n.PP\n.nf\n{}.fi\n",
+        roff(TERVEZO_INTRO),
+        CONFIG
+            .lines()
+            .map(|l| format!("{}\n", roff(l)))
+            .collect::<String>()
+    ));
+    out.push_str(
+        ".SH ENVIRONMENT\n.TP\n\\fBTERVEZO_API_KEY\\fR\nTervezo API key; overrides \
+         \\fBapi_key\\fR in \\fI~/.c9s/config.toml\\fR.\n\
+         .SH FILES\n.TP\n\\fI~/.c9s/config.toml\\fR\nSettings.\n\
+         .TP\n\\fI~/.c9s/data.db\\fR\nBookmarks, tracked time, outcomes and the transcript \
+         search index.\n\
+         .TP\n\\fI~/.claude\\fR\nThe claude CLI's session transcripts c9s reads.\n",
+    );
+    out
+}
+
+/// The usage line, bold command and italic placeholders.
+fn usage(cmd: &mut Command) -> String {
+    let usage = cmd.render_usage().to_string();
+    let usage = usage.trim_start_matches("Usage: ").trim();
+    usage
+        .split(' ')
+        .map(|word| {
+            if word.starts_with('[') || word.starts_with('<') {
+                format!("\\fI{}\\fR", roff(word))
+            } else {
+                format!("\\fB{}\\fR", roff(word))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn man_arg(arg: &Arg) -> String {
+    let mut names = Vec::new();
+    if let Some(short) = arg.get_short() {
+        names.push(format!("\\fB\\-{}\\fR", short));
+    }
+    if let Some(long) = arg.get_long() {
+        names.push(format!("\\fB\\-\\-{}\\fR", roff(long)));
+    }
+    let value = arg
+        .get_value_names()
+        .and_then(|v| v.first())
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| arg.get_id().as_str().to_uppercase());
+    let mut term = names.join(", ");
+    if arg.is_positional() {
+        term = format!("\\fI{}\\fR", roff(&value));
+    } else if arg.get_action().takes_values() {
+        term.push_str(&format!(" \\fI{}\\fR", roff(&value)));
+    }
+
+    let mut desc = arg.get_help().map(|h| h.to_string()).unwrap_or_default();
+    let choices: Vec<String> = arg
+        .get_possible_values()
+        .iter()
+        .filter(|v| !v.is_hide_set())
+        .map(|v| v.get_name().to_string())
+        .collect();
+    if !choices.is_empty() && arg.get_action().takes_values() {
+        desc.push_str(&format!(" [{}]", choices.join(", ")));
+    }
+    let defaults: Vec<String> = arg
+        .get_default_values()
+        .iter()
+        .map(|v| v.to_string_lossy().into_owned())
+        .collect();
+    if !defaults.is_empty() && arg.get_action().takes_values() {
+        desc.push_str(&format!(" (default: {})", defaults.join(", ")));
+    }
+    format!(".TP\n{}\n{}\n", term, roff(desc.trim()))
+}
+
+/// Escapes backslashes and dashes, and keeps a leading `.` or `'` from
+/// being read as a request.
+fn roff(text: &str) -> String {
+    let escaped = text.replace('\\', "\\e").replace('-', "\\-");
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AppConfig;
+
+    #[test]
+    fn test_config_example_parses() {
+        toml::from_str::<AppConfig>(CONFIG).unwrap();
+        let tervezo = config_section("tervezo");
+        assert!(tervezo.starts_with("[tervezo]\n"));
+        assert!(tervezo.contains("notify_on"));
+        assert!(!tervezo.contains("[terminal]"));
+    }
+
+    #[test]
+    fn test_help_topics() {
+        let keys = help(Some("keys")).unwrap();
+        assert!(keys.starts_with("Session list:\n"));
+        assert!(keys.contains("\nTervezo detail:\n"));
+        assert!(keys.contains("  ?                 Toggle this help\n"));
+
+        let tervezo = help(Some("Tervezo")).unwrap();
+        assert!(tervezo.contains("Switch Tervezo organization"));
+        assert!(tervezo.contains("SSH into running impl"));
+        assert!(tervezo.contains("restart_template"));
+
+        assert!(help(Some("config")).unwrap().contains("[ui]"));
+        assert!(help(Some("search")).unwrap().contains("--limit"));
+        assert!(help(None).unwrap().contains("export-transcript"));
+        assert!(help(Some("complete-sessions")).is_err());
+        assert!(help(Some("nope")).is_err());
+    }
+
+    #[test]
+    fn test_man_page() {
+        let page = man_page();
+        assert!(page.starts_with(".TH C9S 1 "));
+        for section in [
+            "NAME",
+            "SYNOPSIS",
+            "OPTIONS",
+            "COMMANDS",
+            "KEYS",
+            "CONFIGURATION",
+        ] {
+            assert!(
+                page.contains(&format!("\n.SH {}\n", section)),
+                "{}",
+                section
+            );
+        }
+        assert!(page.contains("\\fBc9s\\fR \\fBexport\\-transcript\\fR"));
+        assert!(page.contains("\\fB\\-f\\fR, \\fB\\-\\-format\\fR \\fIFORMAT\\fR\nMarkdown or HTML [md, html] (default: md)\n"));
+        assert!(!page.contains("complete\\-sessions"));
+        // Comment lines of the config example can't turn into requests.
+        assert!(page.lines().all(|l| !l.starts_with("'")));
+    }
+}
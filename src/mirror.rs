@@ -0,0 +1,307 @@
+//! Read-only web mirror of the attached terminal. A page with xterm.js
+//! draws the screen c9s already emulates, pushed over a websocket whenever
+//! it changes; nothing typed in the browser reaches the terminal.
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+use tungstenite::handshake::derive_accept_key;
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+use crate::http::{read_request, respond};
+
+const PAGE: &str = include_str!("mirror.html");
+
+/// How long a viewer's socket may sit idle before it gets a ping, which is
+/// also how a closed tab is noticed.
+const PING_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Browsers that may watch at once; each holds a thread and a socket.
+const MAX_VIEWERS: usize = 8;
+
+/// What viewers see: the screen as escape codes that redraw it from
+/// scratch, plus the size and title the page sets before drawing.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct Frame {
+    seq: u64,
+    title: String,
+    rows: u16,
+    cols: u16,
+    screen: Vec<u8>,
+}
+
+#[derive(Default)]
+struct Shared {
+    frame: Mutex<Frame>,
+    changed: Condvar,
+    stopped: AtomicBool,
+    viewers: AtomicUsize,
+}
+
+pub struct Mirror {
+    url: String,
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+}
+
+impl Mirror {
+    /// Starts serving on `bind` (`host:port`; port 0 picks one). The page is
+    /// at a random path, so only someone given the URL can watch.
+    pub fn start(bind: &str) -> Result<Self> {
+        let listener =
+            TcpListener::bind(bind).with_context(|| format!("cannot listen on {}", bind))?;
+        let addr = listener.local_addr()?;
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let host = if addr.ip().is_unspecified() {
+            hostname().unwrap_or_else(|| "localhost".to_string())
+        } else {
+            match addr.ip() {
+                IpAddr::V6(ip) => format!("[{}]", ip),
+                ip => ip.to_string(),
+            }
+        };
+        let url = format!("http://{}:{}/{}", host, addr.port(), token);
+
+        let shared = Arc::new(Shared::default());
+        let accept_shared = shared.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                if accept_shared.stopped.load(Ordering::Acquire) {
+                    break;
+                }
+                let Ok(stream) = stream else {
+                    continue;
+                };
+                let shared = accept_shared.clone();
+                let token = token.clone();
+                std::thread::spawn(move || {
+                    let _ = serve(stream, &shared, &token);
+                });
+            }
+        });
+        Ok(Self { url, addr, shared })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Browsers currently watching.
+    pub fn viewers(&self) -> usize {
+        self.shared.viewers.load(Ordering::Relaxed)
+    }
+
+    /// Sends `screen` to the viewers if it differs from what they last got.
+    /// `None` tells them nothing is attached.
+    pub fn publish(&self, title: &str, screen: Option<&vt100::Screen>) {
+        let (title, rows, cols, bytes) = match screen {
+            Some(screen) => {
+                let (rows, cols) = screen.size();
+                (title, rows, cols, screen.state_formatted())
+            }
+            None => ("", 0, 0, Vec::new()),
+        };
+        let mut frame = self.shared.frame.lock().unwrap();
+        if frame.title == title && (frame.rows, frame.cols) == (rows, cols) && frame.screen == bytes
+        {
+            return;
+        }
+        frame.seq += 1;
+        frame.title = title.to_string();
+        frame.rows = rows;
+        frame.cols = cols;
+        frame.screen = bytes;
+        self.shared.changed.notify_all();
+    }
+}
+
+impl Drop for Mirror {
+    fn drop(&mut self) {
+        self.shared.stopped.store(true, Ordering::Release);
+        self.shared.changed.notify_all();
+        // Wakes the accept loop so it sees `stopped`.
+        let _ = TcpStream::connect_timeout(&self.addr, Duration::from_millis(200));
+    }
+}
+
+fn serve(mut stream: TcpStream, shared: &Shared, token: &str) -> Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let request = read_request(&mut stream)?;
+    let rest = request
+        .path
+        .strip_prefix('/')
+        .and_then(|p| p.strip_prefix(token))
+        .map(|p| p.trim_end_matches('/'));
+    match (rest, request.header("sec-websocket-key")) {
+        (Some("/ws"), Some(key)) => {
+            let joined = shared
+                .viewers
+                .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                    (n < MAX_VIEWERS).then_some(n + 1)
+                });
+            if joined.is_err() {
+                respond(
+                    &mut stream,
+                    "503 Service Unavailable",
+                    "text/plain",
+                    "Too many viewers\n",
+                )?;
+                return Ok(());
+            }
+            crate::wakeup::notify();
+            let result = upgrade(stream, key).and_then(|mut ws| stream_frames(&mut ws, shared));
+            shared.viewers.fetch_sub(1, Ordering::AcqRel);
+            crate::wakeup::notify();
+            result
+        }
+        (Some(""), _) => Ok(respond(
+            &mut stream,
+            "200 OK",
+            "text/html; charset=utf-8",
+            PAGE,
+        )?),
+        _ => Ok(respond(
+            &mut stream,
+            "404 Not Found",
+            "text/plain",
+            "Not found\n",
+        )?),
+    }
+}
+
+/// Answers the handshake `read_request` already consumed and hands the
+/// socket to tungstenite.
+fn upgrade(mut stream: TcpStream, key: &str) -> Result<WebSocket<TcpStream>> {
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\n\
+         Connection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    Ok(WebSocket::from_raw_socket(stream, Role::Server, None))
+}
+
+/// Sends every new frame until the viewer goes away or the mirror stops.
+fn stream_frames(ws: &mut WebSocket<TcpStream>, shared: &Shared) -> Result<()> {
+    let mut seq = 0;
+    let mut meta = None;
+    loop {
+        let frame = {
+            let frame = shared.frame.lock().unwrap();
+            let (frame, _) = shared
+                .changed
+                .wait_timeout_while(frame, PING_INTERVAL, |f| {
+                    f.seq == seq && !shared.stopped.load(Ordering::Acquire)
+                })
+                .unwrap();
+            (frame.seq != seq).then(|| frame.clone())
+        };
+        if shared.stopped.load(Ordering::Acquire) {
+            ws.close(None)?;
+            ws.flush()?;
+            return Ok(());
+        }
+        let Some(frame) = frame else {
+            ws.send(Message::Ping(Default::default()))?;
+            continue;
+        };
+        seq = frame.seq;
+        let size = (frame.title.clone(), frame.rows, frame.cols);
+        if meta.as_ref() != Some(&size) {
+            let json = serde_json::json!({
+                "title": frame.title,
+                "rows": frame.rows,
+                "cols": frame.cols,
+            });
+            ws.send(Message::text(json.to_string()))?;
+            meta = Some(size);
+        }
+        if !frame.screen.is_empty() {
+            ws.send(Message::binary(frame.screen))?;
+        }
+    }
+}
+
+fn hostname() -> Option<String> {
+    let mut buf = [0u8; 256];
+    // SAFETY: gethostname writes at most buf.len() bytes into buf.
+    let rc = unsafe { libc::gethostname(buf.as_mut_ptr() as *mut libc::c_char, buf.len()) };
+    if rc != 0 {
+        return None;
+    }
+    let len = buf.iter().position(|&b| b == 0).unwrap_or(buf.len());
+    String::from_utf8(buf[..len].to_vec())
+        .ok()
+        .filter(|h| !h.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    /// Opens a viewer's socket, or returns the status it was refused with.
+    fn watch(mirror: &Mirror) -> std::result::Result<WebSocket<TcpStream>, u16> {
+        let path = &mirror.url()[mirror.url().rfind('/').unwrap()..];
+        let url = format!("ws://{}{}/ws", mirror.addr, path);
+        let stream = TcpStream::connect(mirror.addr).unwrap();
+        match tungstenite::client(url, stream) {
+            Ok((ws, _)) => Ok(ws),
+            Err(tungstenite::HandshakeError::Failure(tungstenite::Error::Http(response))) => {
+                Err(response.status().as_u16())
+            }
+            Err(e) => panic!("handshake failed: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_serves_page_and_frames() {
+        let mirror = Mirror::start("127.0.0.1:0").unwrap();
+        let path = &mirror.url()[mirror.url().rfind('/').unwrap()..];
+        let get = |request: &str| {
+            let mut stream = TcpStream::connect(mirror.addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            stream
+        };
+
+        let mut page = String::new();
+        get(&format!("GET {} HTTP/1.1\r\nHost: x\r\n\r\n", path))
+            .read_to_string(&mut page)
+            .unwrap();
+        assert!(page.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(page.contains("xterm"));
+
+        let mut missing = String::new();
+        get("GET /guess HTTP/1.1\r\n\r\n")
+            .read_to_string(&mut missing)
+            .unwrap();
+        assert!(missing.starts_with("HTTP/1.1 404"));
+
+        let mut ws = watch(&mirror).unwrap();
+        let mut parser = vt100::Parser::new(4, 20, 0);
+        parser.process(b"hello");
+        mirror.publish("proj", Some(parser.screen()));
+
+        let Message::Text(meta) = ws.read().unwrap() else {
+            panic!("expected the size and title first");
+        };
+        assert!(meta.contains(r#""title":"proj""#));
+        let Message::Binary(screen) = ws.read().unwrap() else {
+            panic!("expected the screen");
+        };
+        assert!(screen.windows(5).any(|w| w == b"hello"));
+        assert_eq!(mirror.viewers(), 1);
+    }
+
+    #[test]
+    fn test_caps_viewers() {
+        let mirror = Mirror::start("127.0.0.1:0").unwrap();
+        let _watching: Vec<_> = (0..MAX_VIEWERS).map(|_| watch(&mirror).unwrap()).collect();
+        assert_eq!(watch(&mirror).err(), Some(503));
+        assert_eq!(mirror.viewers(), MAX_VIEWERS);
+    }
+}
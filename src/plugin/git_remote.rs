@@ -0,0 +1,145 @@
+//! Example plugin: the repository a session's directory pushes to, as a
+//! "Repo" column, a detail panel and `W` to open it in the browser.
+//! Built with `--features plugin-git-remote`.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use super::{Column, Panel, Plugin, PluginAction};
+use crate::app::SessionEntry;
+
+const COLUMNS: &[Column] = &[Column {
+    header: "Repo",
+    width: 18,
+}];
+
+const ACTIONS: &[PluginAction] = &[PluginAction {
+    key: 'W',
+    description: "Open repository in browser",
+}];
+
+/// Remotes are read once per directory; a changed origin shows after a
+/// restart.
+#[derive(Default)]
+pub struct GitRemote {
+    cache: RefCell<HashMap<PathBuf, Option<String>>>,
+}
+
+impl GitRemote {
+    fn origin(&self, entry: &SessionEntry) -> Option<String> {
+        let cwd = &entry.as_local()?.cwd;
+        self.cache
+            .borrow_mut()
+            .entry(cwd.clone())
+            .or_insert_with(|| read_origin(cwd))
+            .clone()
+    }
+}
+
+impl Plugin for GitRemote {
+    fn name(&self) -> &'static str {
+        "git-remote"
+    }
+
+    fn columns(&self) -> &[Column] {
+        COLUMNS
+    }
+
+    fn cell(&self, _column: usize, entry: &SessionEntry) -> String {
+        self.origin(entry)
+            .and_then(|url| repo_path(&url))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    fn panel(&self, entry: &SessionEntry) -> Option<Panel> {
+        let url = self.origin(entry)?;
+        let mut lines = vec![format!("origin {}", url)];
+        lines.extend(web_url(&url).map(|w| format!("web    {}", w)));
+        Some(Panel {
+            title: "Remote".to_string(),
+            lines,
+        })
+    }
+
+    fn actions(&self) -> &[PluginAction] {
+        ACTIONS
+    }
+
+    fn run(&self, _key: char, entry: &SessionEntry) -> anyhow::Result<Option<String>> {
+        let url = self
+            .origin(entry)
+            .and_then(|u| web_url(&u))
+            .ok_or_else(|| anyhow::anyhow!("no web remote for {}", entry.display_name()))?;
+        open::that(&url)?;
+        Ok(Some(format!("Opened {}", url)))
+    }
+}
+
+/// The `origin` url from the nearest `.git/config` at or above `dir`.
+fn read_origin(dir: &Path) -> Option<String> {
+    let config = dir
+        .ancestors()
+        .map(|d| d.join(".git").join("config"))
+        .find(|p| p.is_file())?;
+    parse_origin(&std::fs::read_to_string(config).ok()?)
+}
+
+fn parse_origin(config: &str) -> Option<String> {
+    let mut in_origin = false;
+    for line in config.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_origin = line == "[remote \"origin\"]";
+        } else if in_origin {
+            if let Some((key, value)) = line.split_once('=') {
+                if key.trim() == "url" {
+                    return Some(value.trim().to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// "git@github.com:acme/api.git" or "https://github.com/acme/api" ->
+/// "https://github.com/acme/api".
+fn web_url(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .or_else(|| url.strip_prefix("ssh://"))
+        .map(|r| r.to_string())
+        .or_else(|| url.split_once('@').map(|(_, r)| r.replacen(':', "/", 1)))?;
+    let rest = rest.rsplit_once('@').map_or(rest.as_str(), |(_, r)| r);
+    let rest = rest.trim_end_matches('/').trim_end_matches(".git");
+    Some(format!("https://{}", rest))
+}
+
+/// "acme/api" for any remote form.
+fn repo_path(url: &str) -> Option<String> {
+    let web = web_url(url)?;
+    let path = web.strip_prefix("https://")?.split_once('/')?.1;
+    Some(path.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_origin_urls() {
+        let config = "[core]\n\tbare = false\n[remote \"upstream\"]\n\turl = git@x:y/z\n\
+                      [remote \"origin\"]\n\turl = git@github.com:acme/api.git\n";
+        let origin = parse_origin(config).unwrap();
+        assert_eq!(origin, "git@github.com:acme/api.git");
+        assert_eq!(web_url(&origin).unwrap(), "https://github.com/acme/api");
+        assert_eq!(
+            web_url("ssh://git@gitlab.example.com/team/tool.git").unwrap(),
+            "https://gitlab.example.com/team/tool"
+        );
+        assert_eq!(
+            repo_path("https://github.com/acme/api/").unwrap(),
+            "acme/api"
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! Compile-time plugins. A plugin adds list columns, detail panels and
+//! key actions on top of read-only session data, so a team can show its
+//! own information without forking c9s.
+//!
+//! Plugins live in this directory, each behind a Cargo feature, and are
+//! registered in `builtin`. `[plugins] disabled` in the config turns
+//! individual ones off without rebuilding.
+
+#[cfg(feature = "plugin-git-remote")]
+mod git_remote;
+
+use crate::app::SessionEntry;
+use crate::config::PluginsConfig;
+
+/// An extra column in the session list, after the built-in ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Column {
+    pub header: &'static str,
+    pub width: u16,
+}
+
+/// A titled block of text in the session detail view.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Panel {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// A key in the session list that runs a plugin on the selected session.
+/// Only keys c9s leaves unbound reach plugins.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PluginAction {
+    pub key: char,
+    pub description: &'static str,
+}
+
+/// Every hook has a default, so a plugin only implements what it adds.
+/// Hooks run on the UI thread, some on every draw; anything slow belongs
+/// behind a cache or a thread of the plugin's own.
+pub trait Plugin {
+    /// Used in `[plugins] disabled` and in messages.
+    fn name(&self) -> &'static str;
+
+    fn columns(&self) -> &[Column] {
+        &[]
+    }
+
+    /// Text for the plugin's `column`-th column. Called for visible rows.
+    fn cell(&self, _column: usize, _entry: &SessionEntry) -> String {
+        String::new()
+    }
+
+    /// A panel for the detail view of `entry`, if the plugin has one.
+    fn panel(&self, _entry: &SessionEntry) -> Option<Panel> {
+        None
+    }
+
+    fn actions(&self) -> &[PluginAction] {
+        &[]
+    }
+
+    /// Runs the action bound to `key`. Returned text is shown as a toast.
+    fn run(&self, _key: char, _entry: &SessionEntry) -> anyhow::Result<Option<String>> {
+        Ok(None)
+    }
+}
+
+/// The enabled plugins, in registration order.
+#[derive(Default)]
+pub struct Plugins {
+    plugins: Vec<Box<dyn Plugin>>,
+}
+
+impl Plugins {
+    /// The plugins compiled in, minus the ones the config disables.
+    pub fn load(config: &PluginsConfig) -> Self {
+        Self::new(enabled(builtin(), config))
+    }
+
+    pub fn new(plugins: Vec<Box<dyn Plugin>>) -> Self {
+        Self { plugins }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = &Column> {
+        self.plugins.iter().flat_map(|p| p.columns())
+    }
+
+    /// The text of every plugin column for `entry`, in `columns` order.
+    pub fn cells(&self, entry: &SessionEntry) -> Vec<String> {
+        self.plugins
+            .iter()
+            .flat_map(|p| (0..p.columns().len()).map(move |i| p.cell(i, entry)))
+            .collect()
+    }
+
+    pub fn panels(&self, entry: &SessionEntry) -> Vec<Panel> {
+        self.plugins.iter().filter_map(|p| p.panel(entry)).collect()
+    }
+
+    pub fn actions(&self) -> impl Iterator<Item = &PluginAction> {
+        self.plugins.iter().flat_map(|p| p.actions())
+    }
+
+    /// Runs the first plugin action bound to `key`; `None` when no plugin
+    /// takes the key.
+    pub fn run(&self, key: char, entry: &SessionEntry) -> Option<anyhow::Result<Option<String>>> {
+        let plugin = self
+            .plugins
+            .iter()
+            .find(|p| p.actions().iter().any(|a| a.key == key))?;
+        Some(
+            plugin
+                .run(key, entry)
+                .map_err(|e| e.context(format!("plugin {}", plugin.name()))),
+        )
+    }
+}
+
+fn enabled(plugins: Vec<Box<dyn Plugin>>, config: &PluginsConfig) -> Vec<Box<dyn Plugin>> {
+    plugins
+        .into_iter()
+        .filter(|p| !config.disabled.iter().any(|d| d == p.name()))
+        .collect()
+}
+
+#[allow(unused_mut, clippy::vec_init_then_push)]
+fn builtin() -> Vec<Box<dyn Plugin>> {
+    let mut plugins: Vec<Box<dyn Plugin>> = Vec::new();
+    #[cfg(feature = "plugin-git-remote")]
+    plugins.push(Box::new(git_remote::GitRemote::default()));
+    plugins
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Tags sessions with the start of their id.
+    pub(crate) struct Stub;
+
+    impl Plugin for Stub {
+        fn name(&self) -> &'static str {
+            "stub"
+        }
+
+        fn columns(&self) -> &[Column] {
+            &[Column {
+                header: "Tag",
+                width: 4,
+            }]
+        }
+
+        fn cell(&self, _column: usize, entry: &SessionEntry) -> String {
+            entry.id().chars().take(4).collect()
+        }
+
+        fn panel(&self, entry: &SessionEntry) -> Option<Panel> {
+            Some(Panel {
+                title: "Stub".to_string(),
+                lines: vec![format!("id {}", entry.id())],
+            })
+        }
+
+        fn actions(&self) -> &[PluginAction] {
+            &[PluginAction {
+                key: 'W',
+                description: "Stub action",
+            }]
+        }
+
+        fn run(&self, _key: char, entry: &SessionEntry) -> anyhow::Result<Option<String>> {
+            if entry.is_remote() {
+                anyhow::bail!("local sessions only");
+            }
+            Ok(Some(format!("ran on {}", entry.id())))
+        }
+    }
+
+    fn entry(id: &str) -> SessionEntry {
+        crate::app::tests::make_local(id, None)
+    }
+
+    #[test]
+    fn test_plugins_contribute_columns_panels_and_actions() {
+        let plugins = Plugins::new(vec![Box::new(Stub)]);
+        assert_eq!(
+            plugins.columns().map(|c| c.header).collect::<Vec<_>>(),
+            ["Tag"]
+        );
+        assert_eq!(plugins.cells(&entry("abcdef")), ["abcd"]);
+        assert_eq!(plugins.panels(&entry("abcdef"))[0].lines, ["id abcdef"]);
+        assert_eq!(plugins.actions().count(), 1);
+
+        let ran = plugins.run('W', &entry("abcdef")).unwrap().unwrap();
+        assert_eq!(ran.as_deref(), Some("ran on abcdef"));
+        assert!(plugins.run('Q', &entry("abcdef")).is_none());
+    }
+
+    #[test]
+    fn test_disabled_plugins_are_not_loaded() {
+        let stub = || -> Vec<Box<dyn Plugin>> { vec![Box::new(Stub)] };
+        assert_eq!(enabled(stub(), &PluginsConfig::default()).len(), 1);
+        let config = PluginsConfig {
+            disabled: vec!["stub".to_string()],
+        };
+        assert!(enabled(stub(), &config).is_empty());
+    }
+}
@@ -0,0 +1,116 @@
+//! Focus timer: work intervals alternating with breaks, every few rounds a
+//! longer one. Shown in the terminal view's status line.
+
+use std::time::{Duration, Instant};
+
+use crate::config::FocusConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Work,
+    Break,
+}
+
+pub struct Pomodoro {
+    config: FocusConfig,
+    phase: Phase,
+    /// Work intervals finished so far.
+    rounds: u32,
+    ends_at: Instant,
+}
+
+impl Pomodoro {
+    /// Starts with a work interval.
+    pub fn start(config: FocusConfig, now: Instant) -> Self {
+        let ends_at = now + minutes(config.work_minutes);
+        Self {
+            config,
+            phase: Phase::Work,
+            rounds: 0,
+            ends_at,
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase
+    }
+
+    pub fn remaining(&self, now: Instant) -> Duration {
+        self.ends_at.saturating_duration_since(now)
+    }
+
+    /// Length of the current interval.
+    pub fn length(&self) -> Duration {
+        match self.phase {
+            Phase::Work => minutes(self.config.work_minutes),
+            Phase::Break if self.is_long_break() => minutes(self.config.long_break_minutes),
+            Phase::Break => minutes(self.config.break_minutes),
+        }
+    }
+
+    fn is_long_break(&self) -> bool {
+        self.config.rounds > 0 && self.rounds > 0 && self.rounds.is_multiple_of(self.config.rounds)
+    }
+
+    /// Starts the next interval once the current one is over and returns
+    /// the phase that ended.
+    pub fn advance(&mut self, now: Instant) -> Option<Phase> {
+        if now < self.ends_at {
+            return None;
+        }
+        let ended = self.phase;
+        self.phase = match ended {
+            Phase::Work => {
+                self.rounds += 1;
+                Phase::Break
+            }
+            Phase::Break => Phase::Work,
+        };
+        self.ends_at = now + self.length();
+        Some(ended)
+    }
+
+    /// "focus 18m", "break 4m"; the last minute counts as one.
+    pub fn label(&self, now: Instant) -> String {
+        let left = self.remaining(now).as_secs().div_ceil(60);
+        match self.phase {
+            Phase::Work => format!("focus {}m", left),
+            Phase::Break if self.is_long_break() => format!("long break {}m", left),
+            Phase::Break => format!("break {}m", left),
+        }
+    }
+}
+
+fn minutes(m: u64) -> Duration {
+    Duration::from_secs(m * 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_intervals_alternate_with_a_long_break() {
+        let config = FocusConfig {
+            work_minutes: 25,
+            break_minutes: 5,
+            long_break_minutes: 15,
+            rounds: 2,
+            dnd: true,
+        };
+        let start = Instant::now();
+        let at = |m: u64| start + minutes(m);
+        let mut timer = Pomodoro::start(config, start);
+        assert_eq!(timer.label(start), "focus 25m");
+        assert_eq!(timer.label(at(24) + Duration::from_secs(1)), "focus 1m");
+        assert_eq!(timer.advance(at(24)), None);
+
+        assert_eq!(timer.advance(at(25)), Some(Phase::Work));
+        assert_eq!(timer.label(at(25)), "break 5m");
+        assert_eq!(timer.advance(at(30)), Some(Phase::Break));
+        assert_eq!(timer.phase(), Phase::Work);
+        assert_eq!(timer.advance(at(55)), Some(Phase::Work));
+        assert_eq!(timer.label(at(55)), "long break 15m");
+        assert_eq!(timer.remaining(at(60)), minutes(10));
+    }
+}
@@ -0,0 +1,337 @@
+//! `c9s serve`: a small JSON API over the sessions for home-lab dashboards
+//! and launcher extensions. Every request needs `Authorization: Bearer
+//! <token>`; requests are answered one at a time.
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::net::TcpListener;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::config::{ApiConfig, AppConfig};
+use crate::http::{read_request, respond, Request};
+use crate::list::session_json;
+use crate::outcome::Outcome;
+use crate::session::{Session, SessionDiscovery, SessionStatus};
+use crate::store::Store;
+use crate::usage::UsageFetcher;
+
+/// Where the generated token is kept when none is configured.
+const TOKEN_FILE: &str = "api_token";
+
+const ENV_TOKEN: &str = "C9S_API_TOKEN";
+/// Shortest id prefix a request may name a session by.
+const MIN_ID_PREFIX: usize = 4;
+
+pub fn run(bind: Option<&str>) -> Result<()> {
+    let config = AppConfig::load();
+    crate::format::init(config.format.clone());
+    crate::fx::start(&config.currency, false);
+    let (token, source) = token(&config.api)?;
+    let bind = bind.unwrap_or(&config.api.bind);
+    let listener = TcpListener::bind(bind).with_context(|| format!("cannot listen on {}", bind))?;
+    let mut api = Api::new(token, Store::open()?)?;
+    eprintln!(
+        "Serving the c9s API on http://{} (token from {})",
+        listener.local_addr()?,
+        source
+    );
+
+    for stream in listener.incoming() {
+        let Ok(mut stream) = stream else {
+            continue;
+        };
+        let _ = stream.set_read_timeout(Some(Duration::from_secs(5)));
+        let Ok(request) = read_request(&mut stream) else {
+            continue;
+        };
+        let (status, body) = match api.handle(&request) {
+            Ok(body) => ("200 OK", body),
+            Err(e) => (e.status, json!({ "error": e.message })),
+        };
+        let _ = respond(&mut stream, status, "application/json", &body.to_string());
+    }
+    Ok(())
+}
+
+/// The configured token, `C9S_API_TOKEN`, or one generated on first use
+/// and kept in `~/.c9s/api_token`; with where it came from.
+fn token(config: &ApiConfig) -> Result<(String, String)> {
+    if let Ok(token) = std::env::var(ENV_TOKEN) {
+        if !token.is_empty() {
+            return Ok((token, ENV_TOKEN.to_string()));
+        }
+    }
+    if let Some(token) = config.token.as_deref().filter(|t| !t.trim().is_empty()) {
+        return Ok((token.to_string(), "[api] token".to_string()));
+    }
+    let path = crate::config::config_dir()
+        .map(|d| d.join(TOKEN_FILE))
+        .context("no home directory")?;
+    if let Ok(token) = std::fs::read_to_string(&path) {
+        if !token.trim().is_empty() {
+            return Ok((token.trim().to_string(), path.display().to_string()));
+        }
+    }
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    write_private(&path, &token)?;
+    Ok((token, path.display().to_string()))
+}
+
+fn write_private(path: &Path, contents: &str) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::OpenOptionsExt;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("cannot write {}", path.display()))?;
+    writeln!(file, "{}", contents)?;
+    Ok(())
+}
+
+#[derive(Debug)]
+struct ApiError {
+    status: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: &'static str, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+impl From<anyhow::Error> for ApiError {
+    fn from(e: anyhow::Error) -> Self {
+        Self::new("500 Internal Server Error", format!("{:#}", e))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OutcomeBody {
+    outcome: String,
+    rating: Option<u8>,
+}
+
+struct Api {
+    token: String,
+    discovery: SessionDiscovery,
+    store: Store,
+    usage: UsageFetcher,
+}
+
+impl Api {
+    fn new(token: String, store: Store) -> Result<Self> {
+        let mut discovery = SessionDiscovery::new();
+        discovery.set_show_all_per_cwd(true);
+        discovery.seed_stats(store.load_jsonl_stats()?);
+        Ok(Self {
+            token,
+            discovery,
+            store,
+            usage: UsageFetcher::new(),
+        })
+    }
+
+    fn handle(&mut self, request: &Request) -> Result<Value, ApiError> {
+        if !self.authorized(request) {
+            return Err(ApiError::new(
+                "401 Unauthorized",
+                "missing or wrong bearer token",
+            ));
+        }
+        let path = request.path.split('?').next().unwrap_or("");
+        let parts: Vec<&str> = path.trim_matches('/').split('/').collect();
+        match (request.method.as_str(), parts.as_slice()) {
+            ("GET", ["sessions"]) => {
+                let mut sessions = self.discovery.discover_all()?;
+                sessions.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+                Ok(Value::Array(sessions.iter().map(session_json).collect()))
+            }
+            ("GET", ["sessions", id]) => {
+                let session = self.find(id)?;
+                let mut value = session_json(&session);
+                if let Some((outcome, rating)) = self.store.outcome(&session.id)? {
+                    value["outcome"] = json!(outcome.as_str());
+                    value["rating"] = json!(rating);
+                }
+                Ok(value)
+            }
+            ("POST", ["sessions", id, "kill"]) => {
+                let session = self.find(id)?;
+                let pid = session
+                    .pid
+                    .filter(|_| session.status != SessionStatus::Dead)
+                    .ok_or_else(|| ApiError::new("409 Conflict", "session is not running"))?;
+                // SAFETY: sends SIGTERM to the session's claude process.
+                if unsafe { libc::kill(pid as i32, libc::SIGTERM) } != 0 {
+                    return Err(ApiError::new(
+                        "500 Internal Server Error",
+                        format!(
+                            "could not kill pid {}: {}",
+                            pid,
+                            std::io::Error::last_os_error()
+                        ),
+                    ));
+                }
+                Ok(json!({ "killed": session.id, "pid": pid }))
+            }
+            ("POST", ["sessions", id, "outcome"]) => {
+                let session = self.find(id)?;
+                let body: OutcomeBody = serde_json::from_slice(&request.body)
+                    .map_err(|e| ApiError::new("400 Bad Request", e.to_string()))?;
+                let outcome = Outcome::parse(&body.outcome).ok_or_else(|| {
+                    ApiError::new(
+                        "400 Bad Request",
+                        "outcome is shipped, abandoned or needs-human",
+                    )
+                })?;
+                if body.rating.is_some_and(|r| !(1..=5).contains(&r)) {
+                    return Err(ApiError::new("400 Bad Request", "rating is 1 to 5"));
+                }
+                self.store.set_outcome(
+                    &session.id,
+                    &session.project_name,
+                    session.model.as_deref().unwrap_or("unknown"),
+                    outcome,
+                    body.rating,
+                )?;
+                Ok(json!({ "outcome": outcome.as_str(), "rating": body.rating }))
+            }
+            ("POST", ["sessions", id, "handoff"]) => {
+                let session = self.find(id)?;
+                let transcript = crate::session::timeline::transcript_path(&session)
+                    .ok_or_else(|| ApiError::new("404 Not Found", "transcript not found"))?;
+                let dir = crate::session::handoff::default_handoff_dir(&session)
+                    .context("no home directory")?;
+                crate::session::handoff::write_handoff(&session, &transcript, &dir, &|c| {
+                    crate::format::cost(c, 2)
+                })?;
+                Ok(json!({ "path": dir }))
+            }
+            ("GET", ["usage"]) => {
+                let usage = self.usage.get();
+                Ok(json!({
+                    "plan": usage.plan_name,
+                    "five_hour_percent": usage.five_hour,
+                    "five_hour_reset": usage.five_hour_reset,
+                    "seven_day_percent": usage.seven_day,
+                    "seven_day_reset": usage.seven_day_reset,
+                    "available": usage.api_available,
+                }))
+            }
+            _ => Err(ApiError::new("404 Not Found", "no such endpoint")),
+        }
+    }
+
+    fn authorized(&self, request: &Request) -> bool {
+        let given = request
+            .header("authorization")
+            .and_then(|h| h.strip_prefix("Bearer "))
+            .unwrap_or("");
+        // An empty token would match a missing header.
+        if self.token.is_empty() {
+            return false;
+        }
+        // Compared in full so the time taken doesn't leak a prefix.
+        given.len() == self.token.len()
+            && given
+                .bytes()
+                .zip(self.token.bytes())
+                .fold(0, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    }
+
+    /// A session by full id or unique id prefix.
+    fn find(&mut self, id: &str) -> Result<Session, ApiError> {
+        // An empty or very short prefix would pick the only session there
+        // is without the caller naming it.
+        if id.len() < MIN_ID_PREFIX {
+            return Err(ApiError::new(
+                "400 Bad Request",
+                format!("session id needs at least {} characters", MIN_ID_PREFIX),
+            ));
+        }
+        let mut matches: Vec<Session> = self
+            .discovery
+            .discover_all()?
+            .into_iter()
+            .filter(|s| s.id.starts_with(id))
+            .collect();
+        if let Some(pos) = matches.iter().position(|s| s.id == id) {
+            return Ok(matches.swap_remove(pos));
+        }
+        match matches.len() {
+            1 => Ok(matches.remove(0)),
+            0 => Err(ApiError::new("404 Not Found", "no such session")),
+            _ => Err(ApiError::new("409 Conflict", "session id is ambiguous")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, token: &str, body: &str) -> Request {
+        let raw = format!(
+            "{} {} HTTP/1.1\r\nAuthorization: Bearer {}\r\nContent-Length: {}\r\n\r\n{}",
+            method,
+            path,
+            token,
+            body.len(),
+            body
+        );
+        read_request(&mut raw.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_auth_and_routes() {
+        let mut api = Api::new("secret".to_string(), Store::in_memory().unwrap()).unwrap();
+        let status = |r: Result<Value, ApiError>| r.map(|_| "200 OK").unwrap_or_else(|e| e.status);
+
+        assert_eq!(
+            status(api.handle(&request("GET", "/sessions", "wrong", ""))),
+            "401 Unauthorized"
+        );
+        assert_eq!(
+            status(api.handle(&request("GET", "/sessions", "secre", ""))),
+            "401 Unauthorized"
+        );
+        assert_eq!(
+            status(api.handle(&request("GET", "/nope", "secret", ""))),
+            "404 Not Found"
+        );
+        assert_eq!(
+            status(api.handle(&request("POST", "/sessions", "secret", ""))),
+            "404 Not Found"
+        );
+
+        for (method, path) in [
+            ("GET", "/sessions/ab"),
+            ("POST", "/sessions//kill"),
+            ("POST", "/sessions//outcome"),
+        ] {
+            assert_eq!(
+                status(api.handle(&request(method, path, "secret", ""))),
+                "400 Bad Request"
+            );
+        }
+
+        let mut open = Api::new(String::new(), Store::in_memory().unwrap()).unwrap();
+        assert_eq!(
+            status(open.handle(&request("GET", "/sessions", "", ""))),
+            "401 Unauthorized"
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! The plain-text summary printed instead of the TUI when stdout is not a
+//! terminal, so `c9s | less` and cron jobs get something useful.
+
+use anyhow::Result;
+
+use crate::config::AppConfig;
+use crate::format;
+use crate::fx;
+use crate::session::{Session, SessionDiscovery, SessionStatus};
+use crate::tervezo::{Implementation, TervezoClient, TervezoConfig};
+
+pub fn run() -> Result<()> {
+    let config = AppConfig::load();
+    format::init(config.format.clone());
+    fx::start(&config.currency, false);
+
+    let mut discovery = SessionDiscovery::new();
+    discovery.set_show_all_per_cwd(config.sessions.show_all_per_cwd);
+    let sessions = discovery.discover_all()?;
+    let implementations = match TervezoConfig::load() {
+        Some(tervezo) => TervezoClient::new(&tervezo)
+            .list_implementations(None)
+            .unwrap_or_else(|e| {
+                eprintln!("tervezo: {}", e.with_hint());
+                Vec::new()
+            }),
+        None => Vec::new(),
+    };
+    print!("{}", summary(&sessions, &implementations));
+    Ok(())
+}
+
+/// Session counts and cost, each live session, then the running
+/// implementations.
+fn summary(sessions: &[Session], implementations: &[Implementation]) -> String {
+    let top: Vec<&Session> = sessions.iter().filter(|s| !s.is_subagent).collect();
+    let count = |status: SessionStatus| top.iter().filter(|s| s.status == status).count();
+    let cost: f64 = sessions.iter().map(Session::estimated_cost_usd).sum();
+
+    let mut out = format!(
+        "Sessions: {} ({} active, {} thinking, {} idle, {} dead), {} spent\n",
+        top.len(),
+        count(SessionStatus::Active),
+        count(SessionStatus::Thinking),
+        count(SessionStatus::Idle),
+        count(SessionStatus::Dead),
+        format::cost(cost, 2),
+    );
+    let mut live: Vec<&&Session> = top
+        .iter()
+        .filter(|s| s.status != SessionStatus::Dead)
+        .collect();
+    live.sort_by_key(|s| std::cmp::Reverse(s.last_activity));
+    for s in live {
+        out.push_str(&format!(
+            "  {:<9} {:<20.20} {:<24.24} {:>9}  {}\n",
+            s.status.label(),
+            s.project_name,
+            s.git_branch.as_deref().unwrap_or("-"),
+            format::cost(s.estimated_cost_usd(), 2),
+            format::since(s.last_activity),
+        ));
+    }
+
+    if !implementations.is_empty() {
+        let running: Vec<&Implementation> = implementations
+            .iter()
+            .filter(|i| i.status.is_running())
+            .collect();
+        let waiting = implementations
+            .iter()
+            .filter(|i| i.status.is_waiting())
+            .count();
+        out.push_str(&format!(
+            "Implementations: {} running, {} waiting, {} total\n",
+            running.len(),
+            waiting,
+            implementations.len()
+        ));
+        for imp in running {
+            let repo = imp
+                .repo_url
+                .as_deref()
+                .map(|r| r.rsplit('/').next().unwrap_or(r))
+                .unwrap_or("-");
+            out.push_str(&format!(
+                "  {:<9} {:<40.40} {:<20.20} {}\n",
+                imp.status.label(),
+                imp.display_name(),
+                repo,
+                imp.created_at.map_or("-".to_string(), format::since),
+            ));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(id: &str, status: SessionStatus, subagent: bool) -> Session {
+        let mut session = Session::fixture(id, "/home/dev/webshop");
+        session.git_branch = Some("feature/retry".to_string());
+        session.model = Some("claude-sonnet-4-20250514".to_string());
+        session.status = status;
+        session.input_tokens = 1_000_000;
+        session.message_count = 4;
+        session.is_subagent = subagent;
+        session
+    }
+
+    #[test]
+    fn test_summary() {
+        let sessions = [
+            session("a", SessionStatus::Active, false),
+            session("b", SessionStatus::Dead, false),
+            session("c", SessionStatus::Active, true),
+        ];
+        let implementations: Vec<Implementation> = [
+            r#"{"id":"i1","title":"Add retry","status":"running","repository":"https://github.com/example/webshop"}"#,
+            r#"{"id":"i2","status":"queued"}"#,
+            r#"{"id":"i3","status":"completed"}"#,
+        ]
+        .iter()
+        .map(|json| serde_json::from_str(json).unwrap())
+        .collect();
+
+        let out = summary(&sessions, &implementations);
+        let lines: Vec<&str> = out.lines().collect();
+        assert_eq!(
+            lines[0],
+            "Sessions: 2 (1 active, 0 thinking, 0 idle, 1 dead), $9.00 spent"
+        );
+        assert!(lines[1].starts_with("  Active    webshop"));
+        assert_eq!(lines[2], "Implementations: 1 running, 1 waiting, 3 total");
+        assert!(lines[3].contains("Add retry") && lines[3].contains("webshop"));
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(summary(&[], &[]).lines().count(), 1);
+    }
+}
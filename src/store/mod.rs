@@ -1,3 +0,0 @@
-mod repository;
-
-pub use repository::Store;
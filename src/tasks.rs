@@ -0,0 +1,196 @@
+//! A small worker pool for the one-shot background requests the UI fires
+//! off (detail-view fetches, actions, prompts). Tasks are keyed so a
+//! request that is already queued or running is not issued twice, run on at
+//! most `limit` threads, and carry a `CancelToken` so work queued for a view
+//! that has since closed is dropped instead of run.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex};
+
+/// Shared flag telling queued tasks their results are no longer wanted.
+/// Clones share the flag.
+#[derive(Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Acquire)
+    }
+
+    fn same(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+struct Job {
+    key: String,
+    token: CancelToken,
+    run: Box<dyn FnOnce() + Send>,
+}
+
+#[derive(Default)]
+struct Queue {
+    jobs: VecDeque<Job>,
+    /// Keys of queued and running tasks, with the token they were spawned
+    /// under.
+    in_flight: HashMap<String, CancelToken>,
+    workers: usize,
+    idle: usize,
+    /// Set when the pool is dropped; idle workers then exit.
+    closed: bool,
+}
+
+struct Shared {
+    queue: Mutex<Queue>,
+    ready: Condvar,
+    limit: usize,
+}
+
+pub struct TaskPool {
+    shared: Arc<Shared>,
+}
+
+impl TaskPool {
+    /// Worker threads are started on demand, up to `limit`.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                queue: Mutex::new(Queue::default()),
+                ready: Condvar::new(),
+                limit: limit.max(1),
+            }),
+        }
+    }
+
+    /// Queues `f` under `key`; the main loop is woken once it has run.
+    /// Returns false, without queuing, if a task with the same key is
+    /// already queued or running and has not been cancelled.
+    pub fn spawn<F>(&self, key: impl Into<String>, token: &CancelToken, f: F) -> bool
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let key = key.into();
+        let mut queue = self.shared.queue.lock().unwrap();
+        if let Some(existing) = queue.in_flight.get(&key) {
+            if !existing.is_cancelled() {
+                return false;
+            }
+        }
+        queue.in_flight.insert(key.clone(), token.clone());
+        queue.jobs.push_back(Job {
+            key,
+            token: token.clone(),
+            run: Box::new(f),
+        });
+        if queue.idle == 0 && queue.workers < self.shared.limit {
+            queue.workers += 1;
+            let shared = Arc::clone(&self.shared);
+            std::thread::spawn(move || worker(shared));
+        } else {
+            self.shared.ready.notify_one();
+        }
+        true
+    }
+
+    /// Number of tasks queued or running.
+    #[cfg(test)]
+    pub fn in_flight(&self) -> usize {
+        self.shared.queue.lock().unwrap().in_flight.len()
+    }
+}
+
+impl Drop for TaskPool {
+    fn drop(&mut self) {
+        self.shared.queue.lock().unwrap().closed = true;
+        self.shared.ready.notify_all();
+    }
+}
+
+fn worker(shared: Arc<Shared>) {
+    let mut queue = shared.queue.lock().unwrap();
+    loop {
+        let job = match queue.jobs.pop_front() {
+            Some(job) => job,
+            None if queue.closed => return,
+            None => {
+                queue.idle += 1;
+                queue = shared.ready.wait(queue).unwrap();
+                queue.idle -= 1;
+                continue;
+            }
+        };
+        if !job.token.is_cancelled() {
+            drop(queue);
+            // A panicking task must not take its worker with it.
+            let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job.run));
+            crate::wakeup::notify();
+            queue = shared.queue.lock().unwrap();
+        }
+        // A cancelled task's key may have been reused by a newer one.
+        if queue
+            .in_flight
+            .get(&job.key)
+            .is_some_and(|t| t.same(&job.token))
+        {
+            queue.in_flight.remove(&job.key);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn wait_idle(pool: &TaskPool) {
+        for _ in 0..200 {
+            if pool.in_flight() == 0 {
+                return;
+            }
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        panic!("tasks still in flight");
+    }
+
+    #[test]
+    fn test_dedup_and_cancel() {
+        let pool = TaskPool::new(1);
+        let token = CancelToken::new();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let ran = Arc::new(AtomicUsize::new(0));
+
+        // Occupy the only worker so later tasks stay queued.
+        assert!(pool.spawn("block", &token, move || {
+            let _ = release_rx.recv();
+        }));
+        let first = Arc::clone(&ran);
+        assert!(pool.spawn("plan", &token, move || {
+            first.fetch_add(1, Ordering::SeqCst);
+        }));
+        assert!(!pool.spawn("plan", &token, || {}));
+        assert_eq!(pool.in_flight(), 2);
+
+        // Cancelling frees the key for a new view's task.
+        token.cancel();
+        let fresh = CancelToken::new();
+        let ran_fresh = Arc::clone(&ran);
+        assert!(pool.spawn("plan", &fresh, move || {
+            ran_fresh.fetch_add(10, Ordering::SeqCst);
+        }));
+
+        release_tx.send(()).unwrap();
+        wait_idle(&pool);
+        assert_eq!(ran.load(Ordering::SeqCst), 10);
+    }
+}
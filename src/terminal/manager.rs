@@ -1,9 +1,12 @@
 use anyhow::Result;
-use std::collections::HashMap;
-use std::path::Path;
+use chrono::{DateTime, Local};
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
 
-use super::notifier::JsonlNotifier;
-use super::EmbeddedTerminal;
+use crate::config::{TabOrder, TerminalLogConfig};
+
+use super::notifier::{BellKind, JsonlNotifier, NotifyLevel};
+use super::{EmbeddedTerminal, SpawnConfig};
 
 fn kill_process(pid: u32) {
     unsafe {
@@ -23,6 +26,46 @@ fn kill_process(pid: u32) {
     }
 }
 
+/// Stands in for "until turned off" when a do-not-disturb would run past
+/// what an `Instant` can hold.
+const DND_FOREVER: std::time::Duration = std::time::Duration::from_secs(100 * 365 * 24 * 3600);
+
+/// How long claude gets to act on `/exit` before it is signalled.
+const EXIT_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Signals `pid` as `kill_process` does unless it exits within `grace`,
+/// waiting on a thread of its own.
+fn kill_after(pid: u32, grace: std::time::Duration) {
+    std::thread::spawn(move || {
+        let start = std::time::Instant::now();
+        while start.elapsed() < grace {
+            // SAFETY: signal 0 only checks that the process exists.
+            if unsafe { libc::kill(pid as i32, 0) } != 0 {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(100));
+        }
+        crate::tlog!(warn, "pid {} ignored /exit, sending SIGTERM", pid);
+        kill_process(pid);
+    });
+}
+
+/// Minimum gap between audible bells; later bells in the window only set
+/// the per-tab flags.
+const BELL_COALESCE_WINDOW: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// How many closed tabs keep their final output for the rest of the run.
+const MAX_RECENTLY_EXITED: usize = 10;
+
+/// Final scrollback of a terminal tab that was closed after its process
+/// exited, kept so the output can still be reviewed.
+pub struct ExitedTerminal {
+    pub session_id: String,
+    pub project_name: String,
+    pub closed_at: DateTime<Local>,
+    pub lines: Vec<String>,
+}
+
 pub struct TerminalManager {
     terminals: HashMap<String, EmbeddedTerminal>,
     notifiers: HashMap<String, JsonlNotifier>,
@@ -30,6 +73,13 @@ pub struct TerminalManager {
     order: Vec<String>,
     check_count: u64,
     side_terminals: HashMap<String, EmbeddedTerminal>,
+    log_config: TerminalLogConfig,
+    recently_exited: VecDeque<ExitedTerminal>,
+    tab_order: TabOrder,
+    spawn: SpawnConfig,
+    notify_levels: HashMap<String, NotifyLevel>,
+    last_audible_bell: Option<std::time::Instant>,
+    dnd_until: Option<std::time::Instant>,
 }
 
 impl TerminalManager {
@@ -41,6 +91,114 @@ impl TerminalManager {
             order: Vec::new(),
             check_count: 0,
             side_terminals: HashMap::new(),
+            log_config: TerminalLogConfig::default(),
+            recently_exited: VecDeque::new(),
+            tab_order: TabOrder::default(),
+            spawn: SpawnConfig::default(),
+            notify_levels: HashMap::new(),
+            last_audible_bell: None,
+            dnd_until: None,
+        }
+    }
+
+    /// Silences audible bells for `duration`, or turns do-not-disturb off.
+    /// A duration too long to add to the clock lasts until turned off.
+    /// Tabs are still flagged and every bell is still logged.
+    pub fn set_dnd(&mut self, duration: Option<std::time::Duration>) {
+        self.dnd_until = duration.map(|d| {
+            let now = std::time::Instant::now();
+            now.checked_add(d).unwrap_or(now + DND_FOREVER)
+        });
+    }
+
+    /// Time left in do-not-disturb, if it is on.
+    pub fn dnd_remaining(&self) -> Option<std::time::Duration> {
+        self.dnd_until
+            .and_then(|t| t.checked_duration_since(std::time::Instant::now()))
+            .filter(|d| !d.is_zero())
+    }
+
+    pub fn set_notify_levels(&mut self, levels: HashMap<String, NotifyLevel>) {
+        self.notify_levels = levels;
+    }
+
+    pub fn set_notify_level(&mut self, session_id: &str, level: NotifyLevel) {
+        if level == NotifyLevel::default() {
+            self.notify_levels.remove(session_id);
+        } else {
+            self.notify_levels.insert(session_id.to_string(), level);
+        }
+    }
+
+    pub fn notify_level(&self, session_id: &str) -> NotifyLevel {
+        self.notify_levels
+            .get(session_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_tab_order(&mut self, tab_order: TabOrder) {
+        self.tab_order = tab_order;
+    }
+
+    pub fn set_spawn_config(&mut self, spawn: SpawnConfig) {
+        self.spawn = spawn;
+    }
+
+    /// Session ids in display order. Sorting is stable, so ties keep the
+    /// order the tabs were opened in.
+    fn ordered_ids(&self) -> Vec<String> {
+        let mut ids = self.order.clone();
+        match self.tab_order {
+            TabOrder::Attach => {}
+            TabOrder::Activity => ids.sort_by_key(|id| {
+                std::cmp::Reverse(self.terminals.get(id).map_or(0, |t| t.last_output_millis()))
+            }),
+            TabOrder::Bell => {
+                ids.sort_by_key(|id| !self.terminals.get(id).is_some_and(|t| t.has_bell()))
+            }
+        }
+        ids
+    }
+
+    pub fn set_log_config(&mut self, config: TerminalLogConfig) {
+        self.log_config = config;
+    }
+
+    fn auto_start_log(&self, term: &EmbeddedTerminal) {
+        if !self.log_config.enabled {
+            return;
+        }
+        if let Err(e) = self.start_log(term) {
+            crate::tlog!(
+                warn,
+                "terminal log for {} not started: {}",
+                term.session_id(),
+                e
+            );
+        }
+    }
+
+    fn start_log(&self, term: &EmbeddedTerminal) -> Result<PathBuf> {
+        let dir = self
+            .log_config
+            .dir()
+            .ok_or_else(|| anyhow::anyhow!("no terminal log directory"))?;
+        term.start_log(&dir, self.log_config.max_bytes())
+    }
+
+    /// Turns output logging on or off for an attached session. Returns the
+    /// log path when logging was started, `None` when it was stopped.
+    pub fn toggle_log(&mut self, session_id: &str) -> Result<Option<PathBuf>> {
+        let term = self
+            .terminals
+            .get(session_id)
+            .ok_or_else(|| anyhow::anyhow!("session {} is not attached", session_id))?;
+        if term.is_logging() {
+            term.stop_log();
+            Ok(None)
+        } else {
+            self.start_log(term).map(Some)
         }
     }
 
@@ -59,7 +217,15 @@ impl TerminalManager {
                 kill_process(pid);
                 std::thread::sleep(std::time::Duration::from_millis(200));
             }
-            let term = EmbeddedTerminal::spawn_resume(session_id, project_name, cwd, rows, cols)?;
+            let term = EmbeddedTerminal::spawn_resume(
+                session_id,
+                project_name,
+                cwd,
+                rows,
+                cols,
+                &self.spawn,
+            )?;
+            self.auto_start_log(&term);
             self.order.push(session_id.to_string());
             self.terminals.insert(session_id.to_string(), term);
             self.notifiers
@@ -70,9 +236,39 @@ impl TerminalManager {
         Ok(())
     }
 
+    /// Kills the session's claude and resumes it in a new tab, in place of
+    /// any tab it had. The active tab stays as it was.
+    pub fn restart(
+        &mut self,
+        session_id: &str,
+        project_name: &str,
+        cwd: &Path,
+        pid: Option<u32>,
+        rows: u16,
+        cols: u16,
+    ) -> Result<()> {
+        if let Some(pid) = pid {
+            kill_process(pid);
+        }
+        if let Some(term) = self.terminals.remove(session_id) {
+            self.remember_exited(&term);
+        }
+        let term =
+            EmbeddedTerminal::spawn_resume(session_id, project_name, cwd, rows, cols, &self.spawn)?;
+        self.auto_start_log(&term);
+        if !self.order.iter().any(|o| o == session_id) {
+            self.order.push(session_id.to_string());
+        }
+        self.terminals.insert(session_id.to_string(), term);
+        self.notifiers
+            .insert(session_id.to_string(), JsonlNotifier::new(cwd, session_id));
+        Ok(())
+    }
+
     pub fn attach_new(&mut self, cwd: &Path, rows: u16, cols: u16) -> Result<String> {
         self.clear_active_bells();
-        let term = EmbeddedTerminal::spawn_new(cwd, rows, cols)?;
+        let term = EmbeddedTerminal::spawn_new(cwd, rows, cols, &self.spawn)?;
+        self.auto_start_log(&term);
         let id = term.session_id().to_string();
         self.notifiers
             .insert(id.clone(), JsonlNotifier::new(cwd, &id));
@@ -84,7 +280,8 @@ impl TerminalManager {
 
     pub fn attach_resume_picker(&mut self, cwd: &Path, rows: u16, cols: u16) -> Result<String> {
         self.clear_active_bells();
-        let term = EmbeddedTerminal::spawn_resume_picker(cwd, rows, cols)?;
+        let term = EmbeddedTerminal::spawn_resume_picker(cwd, rows, cols, &self.spawn)?;
+        self.auto_start_log(&term);
         let id = term.session_id().to_string();
         self.notifiers
             .insert(id.clone(), JsonlNotifier::new(cwd, &id));
@@ -94,6 +291,26 @@ impl TerminalManager {
         Ok(id)
     }
 
+    /// Opens a tab running `command` and makes it active. No transcript
+    /// is watched: the tab is not a claude session.
+    pub fn attach_command(
+        &mut self,
+        label: &str,
+        command: &str,
+        cwd: &Path,
+        rows: u16,
+        cols: u16,
+    ) -> Result<String> {
+        self.clear_active_bells();
+        let term = EmbeddedTerminal::spawn_command(label, command, cwd, rows, cols, &self.spawn)?;
+        self.auto_start_log(&term);
+        let id = term.session_id().to_string();
+        self.order.push(id.clone());
+        self.terminals.insert(id.clone(), term);
+        self.active_id = Some(id.clone());
+        Ok(id)
+    }
+
     pub fn attach_ssh(
         &mut self,
         impl_id: &str,
@@ -104,7 +321,15 @@ impl TerminalManager {
     ) -> Result<()> {
         self.clear_active_bells();
         if !self.terminals.contains_key(impl_id) {
-            let term = EmbeddedTerminal::spawn_ssh(impl_id, project_name, ssh_command, rows, cols)?;
+            let term = EmbeddedTerminal::spawn_ssh(
+                impl_id,
+                project_name,
+                ssh_command,
+                rows,
+                cols,
+                &self.spawn,
+            )?;
+            self.auto_start_log(&term);
             self.order.push(impl_id.to_string());
             self.terminals.insert(impl_id.to_string(), term);
         }
@@ -139,6 +364,27 @@ impl TerminalManager {
         Ok(())
     }
 
+    /// Writes to a background terminal without making it active.
+    pub fn write_to(&mut self, session_id: &str, bytes: &[u8]) -> Result<()> {
+        self.live_terminal_mut(session_id)?.write_input(bytes)
+    }
+
+    /// Types a line into a background terminal and presses Enter.
+    pub fn submit_to(&mut self, session_id: &str, bytes: &[u8]) -> Result<()> {
+        self.live_terminal_mut(session_id)?.submit_input(bytes)
+    }
+
+    fn live_terminal_mut(&mut self, session_id: &str) -> Result<&mut EmbeddedTerminal> {
+        let term = self
+            .terminals
+            .get_mut(session_id)
+            .ok_or_else(|| anyhow::anyhow!("session {} is not attached", session_id))?;
+        if term.is_exited() {
+            anyhow::bail!("session {} has exited", session_id);
+        }
+        Ok(term)
+    }
+
     pub fn resize_active(&self, rows: u16, cols: u16) -> Result<()> {
         if let Some(term) = self.active_terminal() {
             term.resize(rows, cols)?;
@@ -147,39 +393,39 @@ impl TerminalManager {
     }
 
     pub fn cycle_next(&mut self) {
-        if self.order.is_empty() {
-            return;
-        }
-        self.clear_active_bells();
-        let current_idx = self
-            .active_id
-            .as_ref()
-            .and_then(|id| self.order.iter().position(|o| o == id))
-            .unwrap_or(0);
-        let next_idx = (current_idx + 1) % self.order.len();
-        self.active_id = Some(self.order[next_idx].clone());
-        self.clear_active_bells();
+        self.cycle(1);
     }
 
     pub fn cycle_prev(&mut self) {
-        if self.order.is_empty() {
+        self.cycle(-1);
+    }
+
+    fn cycle(&mut self, step: isize) {
+        let ids = self.ordered_ids();
+        if ids.is_empty() {
             return;
         }
         self.clear_active_bells();
         let current_idx = self
             .active_id
             .as_ref()
-            .and_then(|id| self.order.iter().position(|o| o == id))
+            .and_then(|id| ids.iter().position(|o| o == id))
             .unwrap_or(0);
-        let prev_idx = if current_idx == 0 {
-            self.order.len() - 1
-        } else {
-            current_idx - 1
-        };
-        self.active_id = Some(self.order[prev_idx].clone());
+        let len = ids.len() as isize;
+        let idx = (current_idx as isize + step).rem_euclid(len) as usize;
+        self.active_id = Some(ids[idx].clone());
         self.clear_active_bells();
     }
 
+    /// Clipboard writes any terminal made since the last call, as base64.
+    pub fn take_clipboard(&self) -> Vec<String> {
+        self.terminals
+            .values()
+            .chain(self.side_terminals.values())
+            .flat_map(EmbeddedTerminal::take_clipboard)
+            .collect()
+    }
+
     pub fn cleanup_inactive_exited(&mut self) {
         let active = self.active_id.clone();
         let dead: Vec<String> = self
@@ -189,7 +435,9 @@ impl TerminalManager {
             .map(|(id, _)| id.clone())
             .collect();
         for id in &dead {
-            self.terminals.remove(id);
+            if let Some(term) = self.terminals.remove(id) {
+                self.remember_exited(&term);
+            }
             self.notifiers.remove(id);
             self.order.retain(|o| o != id);
         }
@@ -197,16 +445,72 @@ impl TerminalManager {
 
     pub fn remove_active(&mut self) {
         if let Some(id) = self.active_id.take() {
-            self.terminals.remove(&id);
+            if let Some(term) = self.terminals.remove(&id) {
+                self.remember_exited(&term);
+            }
             self.notifiers.remove(&id);
             self.order.retain(|o| o != &id);
         }
     }
 
+    fn remember_exited(&mut self, term: &EmbeddedTerminal) {
+        self.recently_exited
+            .retain(|e| e.session_id != term.session_id());
+        if self.recently_exited.len() >= MAX_RECENTLY_EXITED {
+            self.recently_exited.pop_back();
+        }
+        self.recently_exited.push_front(ExitedTerminal {
+            session_id: term.session_id().to_string(),
+            project_name: term.project_name().to_string(),
+            closed_at: Local::now(),
+            lines: term.snapshot_lines(),
+        });
+    }
+
+    /// Closed tabs, most recent first.
+    pub fn recently_exited(&self) -> &VecDeque<ExitedTerminal> {
+        &self.recently_exited
+    }
+
+    /// Whether the session has a live tab that `soft_kill` can type into.
+    pub fn can_soft_kill(&self, session_id: &str) -> bool {
+        self.terminals
+            .get(session_id)
+            .is_some_and(|t| !t.is_exited())
+    }
+
+    /// Ends the session's claude the way a user would, by typing `/exit`
+    /// into its tab, so it isn't cut off mid-write. Signals follow only if
+    /// `pid` is still running after `EXIT_GRACE`.
+    pub fn soft_kill(&mut self, session_id: &str, pid: u32) -> Result<()> {
+        // Ctrl+U first, so a half-typed prompt doesn't swallow the command.
+        self.write_to(session_id, b"\x15/exit\r")?;
+        kill_after(pid, EXIT_GRACE);
+        Ok(())
+    }
+
     pub fn active_is_exited(&self) -> bool {
         self.active_terminal().is_some_and(|t| t.is_exited())
     }
 
+    pub fn terminal_for(&self, session_id: &str) -> Option<&EmbeddedTerminal> {
+        self.terminals.get(session_id)
+    }
+
+    /// The terminal `pid` runs in when it isn't one of ours, as `ps` names
+    /// it: the session is attached somewhere else, and attaching here
+    /// would end it there.
+    pub fn foreign_tty(&self, pid: u32) -> Option<String> {
+        let tty = crate::session::tty::controlling_tty(pid)?;
+        let ours = self
+            .terminals
+            .values()
+            .chain(self.side_terminals.values())
+            .filter_map(EmbeddedTerminal::tty_name)
+            .any(|path| crate::session::tty::is_device(&tty, &path));
+        (!ours).then_some(tty)
+    }
+
     pub fn is_attached(&self, session_id: &str) -> bool {
         self.terminals.contains_key(session_id)
     }
@@ -219,7 +523,7 @@ impl TerminalManager {
     }
 
     pub fn tab_info(&self) -> Vec<TabEntry> {
-        self.order
+        self.ordered_ids()
             .iter()
             .filter_map(|id| {
                 let term = self.terminals.get(id)?;
@@ -230,31 +534,106 @@ impl TerminalManager {
                     is_active,
                     has_bell: !is_active && term.has_bell(),
                     bell_blink: !is_active && term.has_bell_blink(),
+                    logging: term.is_logging(),
                 })
             })
             .collect()
     }
 
+    /// Polls every notifier, flags each session that fired, and rings the
+    /// host terminal once for the whole batch. Bells for the session being
+    /// viewed, or raised during do-not-disturb, are only logged. Returns true if any notifier fired.
     pub fn check_and_forward_notifications(&mut self, viewing_active: bool) -> bool {
         self.check_count += 1;
+        let mut fired = false;
+        let mut audible = false;
+        let dnd = self.dnd_remaining().is_some();
         for (id, notifier) in &mut self.notifiers {
-            if notifier.check() {
-                let is_focused = viewing_active && self.active_id.as_deref() == Some(id.as_str());
+            let Some(kind) = notifier.check() else {
+                continue;
+            };
+            fired = true;
+            let name = self
+                .terminals
+                .get(id)
+                .map_or(id.as_str(), |t| t.project_name());
+            let level = self.notify_levels.get(id).copied().unwrap_or_default();
+            if !level.allows(kind) {
                 notifier.debug_log_ext(&format!(
-                    "BELL: fired for {} (focused={})",
+                    "BELL: {:?} for {} suppressed (level={})",
+                    kind,
                     &id[..8.min(id.len())],
-                    is_focused
+                    level.as_str()
                 ));
-                if !is_focused {
-                    if let Some(term) = self.terminals.get(id) {
-                        term.set_bell();
-                    }
-                }
-                let _ = std::io::Write::write_all(&mut std::io::stderr(), b"\x07");
-                return true;
+                continue;
+            }
+            let is_focused = viewing_active && self.active_id.as_deref() == Some(id.as_str());
+            notifier.debug_log_ext(&format!(
+                "BELL: fired for {} (focused={})",
+                &id[..8.min(id.len())],
+                is_focused
+            ));
+            if is_focused {
+                crate::tlog!(
+                    info,
+                    "Bell: {} {} (focused, silenced)",
+                    name,
+                    kind.describe()
+                );
+                continue;
             }
+            if let Some(term) = self.terminals.get(id) {
+                term.set_bell();
+            }
+            if dnd {
+                crate::tlog!(info, "Bell: {} {} (do not disturb)", name, kind.describe());
+                continue;
+            }
+            crate::tlog!(info, "Bell: {} {}", name, kind.describe());
+            audible = true;
+        }
+        if audible {
+            self.ring();
+        }
+        fired
+    }
+
+    /// Bell for something outside the embedded terminals, such as a remote
+    /// implementation changing state. Honors the id's notification level and
+    /// do-not-disturb like terminal bells do.
+    pub fn notify_external(&mut self, id: &str, message: &str) {
+        if !self.notify_level(id).allows(BellKind::TurnEnd) {
+            return;
+        }
+        if self.dnd_remaining().is_some() {
+            crate::tlog!(info, "Bell: {} (do not disturb)", message);
+            return;
+        }
+        crate::tlog!(info, "Bell: {}", message);
+        self.ring();
+    }
+
+    /// One bell for c9s's own timers, whatever the sessions' levels; kept
+    /// quiet during do-not-disturb like any other.
+    pub fn chime(&mut self, message: &str) {
+        if self.dnd_remaining().is_some() {
+            crate::tlog!(info, "Bell: {} (do not disturb)", message);
+            return;
+        }
+        crate::tlog!(info, "Bell: {}", message);
+        self.ring();
+    }
+
+    /// Writes BEL to the host terminal. Bells from sessions finishing
+    /// together (or on adjacent ticks) collapse into one beep.
+    fn ring(&mut self) {
+        let quiet = self
+            .last_audible_bell
+            .is_none_or(|t| t.elapsed() >= BELL_COALESCE_WINDOW);
+        if quiet {
+            let _ = std::io::Write::write_all(&mut std::io::stderr(), b"\x07");
+            self.last_audible_bell = Some(std::time::Instant::now());
         }
-        false
     }
 
     fn clear_active_bells(&self) {
@@ -273,24 +652,27 @@ impl TerminalManager {
         self.side_terminals.get_mut(&id)
     }
 
-    pub fn open_side_terminal(&mut self, cwd: &std::path::Path, rows: u16, cols: u16) -> bool {
+    /// Returns `Ok(false)` when there is no active tab to attach the side
+    /// terminal to.
+    pub fn open_side_terminal(
+        &mut self,
+        cwd: &std::path::Path,
+        rows: u16,
+        cols: u16,
+    ) -> Result<bool> {
         let id = match &self.active_id {
             Some(id) => id.clone(),
-            None => return false,
+            None => return Ok(false),
         };
         if let Some(existing) = self.side_terminals.get(&id) {
             if !existing.is_exited() {
-                return true;
+                return Ok(true);
             }
             self.side_terminals.remove(&id);
         }
-        match EmbeddedTerminal::spawn_shell(cwd, rows, cols) {
-            Ok(term) => {
-                self.side_terminals.insert(id, term);
-                true
-            }
-            Err(_) => false,
-        }
+        let term = EmbeddedTerminal::spawn_shell(cwd, rows, cols, &self.spawn)?;
+        self.side_terminals.insert(id, term);
+        Ok(true)
     }
 
     pub fn close_side_terminal(&mut self) {
@@ -313,6 +695,7 @@ pub struct TabEntry {
     pub is_active: bool,
     pub has_bell: bool,
     pub bell_blink: bool,
+    pub logging: bool,
 }
 
 impl Drop for TerminalManager {
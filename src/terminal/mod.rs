@@ -1,27 +1,75 @@
 mod manager;
+mod mouse;
 mod notifier;
+mod osc52;
+mod pty_log;
+mod reflow;
+mod render_cache;
+mod selection;
 
 use anyhow::{Context, Result};
 use portable_pty::{native_pty_system, CommandBuilder, MasterPty, PtySize};
+use std::collections::BTreeMap;
 use std::io::{Read, Write};
-use std::path::Path;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::thread::JoinHandle;
 
-pub use manager::{TabEntry, TerminalManager};
+use crate::config::WrapperShell;
+
+pub use manager::{ExitedTerminal, TabEntry, TerminalManager};
+pub use notifier::NotifyLevel;
+pub use osc52::decode as decode_osc52;
+use pty_log::PtyLog;
+pub use render_cache::RenderCache;
+pub use selection::{Motion, Point};
+
+/// Lines of history kept per terminal.
+const SCROLLBACK_LEN: usize = 10000;
+/// Gap between a submitted line and its Enter.
+const SUBMIT_DELAY: std::time::Duration = std::time::Duration::from_millis(30);
+
+/// How terminal processes are started, from `[terminal]` in config.toml.
+#[derive(Debug, Clone)]
+pub struct SpawnConfig {
+    pub shell: WrapperShell,
+    pub term: String,
+    /// `None` unsets `COLORTERM` rather than inheriting it.
+    pub colorterm: Option<String>,
+    /// Set on every spawned process; an empty value unsets the variable.
+    pub env: BTreeMap<String, String>,
+}
+
+impl Default for SpawnConfig {
+    fn default() -> Self {
+        Self {
+            shell: WrapperShell::default(),
+            term: "xterm-256color".to_string(),
+            colorterm: None,
+            env: BTreeMap::new(),
+        }
+    }
+}
 
 pub struct EmbeddedTerminal {
     session_id: String,
     project_name: String,
     parser: Arc<Mutex<vt100::Parser>>,
-    writer: Box<dyn Write + Send>,
+    /// Shared with the thread that sends a submitted line's Enter.
+    writer: Arc<Mutex<Box<dyn Write + Send>>>,
     master: Box<dyn MasterPty>,
     _reader_handle: JoinHandle<()>,
     exited: Arc<AtomicBool>,
     bell: Arc<AtomicBool>,
     bell_blink: Arc<AtomicBool>,
     dirty: Arc<AtomicBool>,
+    log: Arc<Mutex<Option<PtyLog>>>,
+    /// Unix millis of the last chunk read from the PTY.
+    last_output: Arc<AtomicU64>,
+    /// Clipboard writes (OSC 52, still base64) the application made.
+    clipboard: Arc<Mutex<Vec<String>>>,
+    render_cache: Mutex<RenderCache>,
 }
 
 impl EmbeddedTerminal {
@@ -31,6 +79,7 @@ impl EmbeddedTerminal {
         cwd: &Path,
         rows: u16,
         cols: u16,
+        spawn: &SpawnConfig,
     ) -> Result<Self> {
         Self::spawn_inner(
             session_id,
@@ -40,6 +89,7 @@ impl EmbeddedTerminal {
             cwd,
             rows,
             cols,
+            spawn,
         )
     }
 
@@ -49,6 +99,7 @@ impl EmbeddedTerminal {
         ssh_command: &str,
         rows: u16,
         cols: u16,
+        spawn: &SpawnConfig,
     ) -> Result<Self> {
         Self::spawn_inner(
             session_id,
@@ -58,15 +109,44 @@ impl EmbeddedTerminal {
             Path::new("/tmp"),
             rows,
             cols,
+            spawn,
         )
     }
 
-    pub(crate) fn spawn_shell(cwd: &Path, rows: u16, cols: u16) -> Result<Self> {
+    /// Runs a user action's `command` in `cwd`, titled `label`.
+    pub fn spawn_command(
+        label: &str,
+        command: &str,
+        cwd: &Path,
+        rows: u16,
+        cols: u16,
+        spawn: &SpawnConfig,
+    ) -> Result<Self> {
+        let id = uuid::Uuid::new_v4().to_string();
+        Self::spawn_inner(&id, label, "bash", &["-c", command], cwd, rows, cols, spawn)
+    }
+
+    pub(crate) fn spawn_shell(
+        cwd: &Path,
+        rows: u16,
+        cols: u16,
+        spawn: &SpawnConfig,
+    ) -> Result<Self> {
         let id = uuid::Uuid::new_v4().to_string();
-        Self::spawn_inner(&id, "shell", "bash", &["--login"], cwd, rows, cols)
+        let (program, args) = spawn.shell.interactive();
+        let direct = SpawnConfig {
+            shell: WrapperShell::None,
+            ..spawn.clone()
+        };
+        Self::spawn_inner(&id, "shell", &program, args, cwd, rows, cols, &direct)
     }
 
-    pub fn spawn_resume_picker(cwd: &Path, rows: u16, cols: u16) -> Result<Self> {
+    pub fn spawn_resume_picker(
+        cwd: &Path,
+        rows: u16,
+        cols: u16,
+        spawn: &SpawnConfig,
+    ) -> Result<Self> {
         let project_name = cwd
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -74,10 +154,19 @@ impl EmbeddedTerminal {
 
         let id = uuid::Uuid::new_v4().to_string();
 
-        Self::spawn_inner(&id, &project_name, "claude", &["--resume"], cwd, rows, cols)
+        Self::spawn_inner(
+            &id,
+            &project_name,
+            "claude",
+            &["--resume"],
+            cwd,
+            rows,
+            cols,
+            spawn,
+        )
     }
 
-    pub fn spawn_new(cwd: &Path, rows: u16, cols: u16) -> Result<Self> {
+    pub fn spawn_new(cwd: &Path, rows: u16, cols: u16, spawn: &SpawnConfig) -> Result<Self> {
         let project_name = cwd
             .file_name()
             .map(|n| n.to_string_lossy().to_string())
@@ -85,9 +174,10 @@ impl EmbeddedTerminal {
 
         let id = uuid::Uuid::new_v4().to_string();
 
-        Self::spawn_inner(&id, &project_name, "claude", &[], cwd, rows, cols)
+        Self::spawn_inner(&id, &project_name, "claude", &[], cwd, rows, cols, spawn)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn spawn_inner(
         session_id: &str,
         project_name: &str,
@@ -96,7 +186,23 @@ impl EmbeddedTerminal {
         cwd: &Path,
         rows: u16,
         cols: u16,
+        spawn: &SpawnConfig,
     ) -> Result<Self> {
+        if !cwd.is_dir() {
+            anyhow::bail!("working directory {} does not exist", cwd.display());
+        }
+        let wrapper = spawn.shell.wrapper();
+        if let Some((program, _)) = &wrapper {
+            if !resolvable(program) {
+                anyhow::bail!("shell `{}` was not found", program);
+            }
+        }
+        // A login shell may set up its own PATH, so only check the command
+        // when it runs with ours.
+        if spawn.shell != WrapperShell::Login && !resolvable(cmd) {
+            anyhow::bail!("`{}` was not found in PATH", cmd);
+        }
+
         let pty_system = native_pty_system();
         let size = PtySize {
             rows,
@@ -109,24 +215,47 @@ impl EmbeddedTerminal {
             .openpty(size)
             .map_err(|e| anyhow::anyhow!("{}", e))?;
 
-        let inner_cmd = if args.is_empty() {
-            cmd.to_string()
-        } else {
-            let escaped_args: Vec<String> = args
-                .iter()
-                .map(|a| format!("'{}'", a.replace('\'', "'\\''")))
-                .collect();
-            format!("{} {}", cmd, escaped_args.join(" "))
+        let mut cmd_builder = match wrapper {
+            Some((program, flags)) => {
+                let escaped_args: Vec<String> = args
+                    .iter()
+                    .map(|a| format!("'{}'", a.replace('\'', "'\\''")))
+                    .collect();
+                let mut b = CommandBuilder::new(program);
+                b.args(flags);
+                b.arg("-c");
+                // No `exec`: it would skip aliases, and shells exec a lone
+                // trailing command anyway.
+                b.arg(format!("{} {}", cmd, escaped_args.join(" ")).trim_end());
+                b
+            }
+            None => {
+                let mut b = CommandBuilder::new(cmd);
+                b.args(args);
+                b
+            }
         };
-        let mut cmd_builder = CommandBuilder::new("bash");
-        cmd_builder.arg("-c");
-        cmd_builder.arg(format!("export GPG_TTY=$(tty); exec {}", inner_cmd));
+        if let Some(tty) = pair.master.tty_name() {
+            cmd_builder.env("GPG_TTY", tty);
+        }
+        cmd_builder.env("TERM", &spawn.term);
+        match &spawn.colorterm {
+            Some(colorterm) => cmd_builder.env("COLORTERM", colorterm),
+            None => cmd_builder.env_remove("COLORTERM"),
+        }
+        for (key, value) in &spawn.env {
+            if value.is_empty() {
+                cmd_builder.env_remove(key);
+            } else {
+                cmd_builder.env(key, value);
+            }
+        }
         cmd_builder.cwd(cwd);
 
         let _child = pair
             .slave
             .spawn_command(cmd_builder)
-            .map_err(|e| anyhow::anyhow!("{}", e))?;
+            .map_err(|e| anyhow::anyhow!("failed to start {}: {}", cmd, e))?;
 
         let mut reader = pair
             .master
@@ -137,33 +266,54 @@ impl EmbeddedTerminal {
             .master
             .take_writer()
             .map_err(|e| anyhow::anyhow!("{}", e))?;
+        let writer = Arc::new(Mutex::new(writer));
 
-        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, 10000)));
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK_LEN)));
         let exited = Arc::new(AtomicBool::new(false));
         let bell = Arc::new(AtomicBool::new(false));
         let bell_blink = Arc::new(AtomicBool::new(false));
         let dirty = Arc::new(AtomicBool::new(true));
+        let log: Arc<Mutex<Option<PtyLog>>> = Arc::new(Mutex::new(None));
 
         let parser_clone = Arc::clone(&parser);
         let exited_clone = Arc::clone(&exited);
         let dirty_clone = Arc::clone(&dirty);
+        let log_clone = Arc::clone(&log);
+        let last_output = Arc::new(AtomicU64::new(now_millis()));
+        let last_output_clone = Arc::clone(&last_output);
+        let clipboard: Arc<Mutex<Vec<String>>> = Arc::default();
+        let clipboard_clone = Arc::clone(&clipboard);
 
         let reader_handle = std::thread::spawn(move || {
             let mut buf = [0u8; 8192];
+            let mut osc52 = osc52::Scanner::default();
             loop {
                 match reader.read(&mut buf) {
                     Ok(0) => {
                         exited_clone.store(true, Ordering::Relaxed);
                         dirty_clone.store(true, Ordering::Relaxed);
+                        crate::wakeup::notify();
                         break;
                     }
                     Ok(n) => {
                         parser_clone.lock().unwrap().process(&buf[..n]);
+                        let copied = osc52.feed(&buf[..n]);
+                        if !copied.is_empty() {
+                            clipboard_clone.lock().unwrap().extend(copied);
+                        }
                         dirty_clone.store(true, Ordering::Relaxed);
+                        crate::wakeup::notify();
+                        last_output_clone.store(now_millis(), Ordering::Relaxed);
+                        let mut log = log_clone.lock().unwrap();
+                        if let Some(Err(e)) = log.as_mut().map(|l| l.write(&buf[..n])) {
+                            crate::tlog!(warn, "terminal log write failed, stopping: {}", e);
+                            *log = None;
+                        }
                     }
                     Err(_) => {
                         exited_clone.store(true, Ordering::Relaxed);
                         dirty_clone.store(true, Ordering::Relaxed);
+                        crate::wakeup::notify();
                         break;
                     }
                 }
@@ -181,13 +331,51 @@ impl EmbeddedTerminal {
             bell,
             bell_blink,
             dirty,
+            log,
+            last_output,
+            clipboard,
+            render_cache: Mutex::new(RenderCache::default()),
         })
     }
 
+    /// Device of the PTY the process runs in, e.g. /dev/pts/3.
+    pub fn tty_name(&self) -> Option<PathBuf> {
+        self.master.tty_name()
+    }
+
+    /// Clipboard writes made since the last call, as base64.
+    pub fn take_clipboard(&self) -> Vec<String> {
+        std::mem::take(&mut self.clipboard.lock().unwrap())
+    }
+
+    pub fn last_output_millis(&self) -> u64 {
+        self.last_output.load(Ordering::Relaxed)
+    }
+
+    /// Starts appending raw PTY output to `<dir>/<session id>.log`.
+    pub fn start_log(&self, dir: &Path, max_bytes: u64) -> Result<PathBuf> {
+        let log = PtyLog::open(dir, &self.session_id, max_bytes)?;
+        let path = log.path().to_path_buf();
+        *self.log.lock().unwrap() = Some(log);
+        Ok(path)
+    }
+
+    pub fn stop_log(&self) {
+        *self.log.lock().unwrap() = None;
+    }
+
+    pub fn is_logging(&self) -> bool {
+        self.log.lock().unwrap().is_some()
+    }
+
     pub fn lock_parser(&self) -> MutexGuard<'_, vt100::Parser> {
         self.parser.lock().unwrap()
     }
 
+    pub fn lock_render_cache(&self) -> MutexGuard<'_, RenderCache> {
+        self.render_cache.lock().unwrap()
+    }
+
     pub fn take_dirty(&self) -> bool {
         self.dirty.swap(false, Ordering::Relaxed)
     }
@@ -196,13 +384,32 @@ impl EmbeddedTerminal {
         self.clear_bell();
         self.parser.lock().unwrap().screen_mut().set_scrollback(0);
         self.dirty.store(true, Ordering::Relaxed);
-        self.writer
-            .write_all(bytes)
-            .context("failed to write to PTY")?;
-        self.writer.flush().context("failed to flush PTY")?;
+        let mut writer = self.writer.lock().unwrap();
+        writer.write_all(bytes).context("failed to write to PTY")?;
+        writer.flush().context("failed to flush PTY")?;
         Ok(())
     }
 
+    /// Writes `bytes`, then Enter `SUBMIT_DELAY` later from another thread,
+    /// so claude doesn't treat it as part of a paste and insert a newline
+    /// instead of submitting.
+    pub fn submit_input(&mut self, bytes: &[u8]) -> Result<()> {
+        self.write_input(bytes)?;
+        let writer = Arc::clone(&self.writer);
+        std::thread::spawn(move || {
+            std::thread::sleep(SUBMIT_DELAY);
+            let mut writer = writer.lock().unwrap();
+            let _ = writer.write_all(b"\r").and_then(|_| writer.flush());
+        });
+        Ok(())
+    }
+
+    /// Plain-text copy of the scrollback followed by the current screen,
+    /// with trailing blank lines dropped.
+    pub fn snapshot_lines(&self) -> Vec<String> {
+        snapshot_lines(&mut self.parser.lock().unwrap())
+    }
+
     pub fn scroll_up(&self, lines: usize) {
         let mut parser = self.parser.lock().unwrap();
         let current = parser.screen().scrollback();
@@ -219,6 +426,68 @@ impl EmbeddedTerminal {
         self.dirty.store(true, Ordering::Relaxed);
     }
 
+    /// Where copy mode starts: the application's cursor, or the bottom row
+    /// when scrolled back.
+    pub fn copy_start(&self) -> Point {
+        selection::start(&mut self.parser.lock().unwrap())
+    }
+
+    /// Moves a copy-mode cursor, scrolling to keep it on screen.
+    pub fn copy_step(&self, point: Point, motion: Motion) -> Point {
+        let point = selection::step(&mut self.parser.lock().unwrap(), point, motion);
+        self.dirty.store(true, Ordering::Relaxed);
+        point
+    }
+
+    /// The text between two points of the scrollback and screen.
+    pub fn copy_text(&self, a: Point, b: Point) -> String {
+        selection::text(&mut self.parser.lock().unwrap(), a, b)
+    }
+
+    /// The line of history on the screen's first row, which copy-mode
+    /// points are drawn relative to.
+    pub fn top_line(&self) -> usize {
+        selection::top(&mut self.parser.lock().unwrap())
+    }
+
+    /// Whether the application asked for mouse reports and the screen is
+    /// showing it live rather than scrolled back.
+    pub fn wants_mouse(&self) -> bool {
+        let parser = self.parser.lock().unwrap();
+        let screen = parser.screen();
+        screen.mouse_protocol_mode() != vt100::MouseProtocolMode::None && screen.scrollback() == 0
+    }
+
+    /// Sends `event` (relative to the screen) to the application as an
+    /// xterm mouse report. Returns false when the application isn't taking
+    /// mouse input, so the caller can handle the event itself.
+    pub fn send_mouse(&mut self, event: crossterm::event::MouseEvent) -> Result<bool> {
+        let report = {
+            let parser = self.parser.lock().unwrap();
+            let screen = parser.screen();
+            if screen.mouse_protocol_mode() == vt100::MouseProtocolMode::None
+                || screen.scrollback() > 0
+            {
+                return Ok(false);
+            }
+            let (rows, cols) = screen.size();
+            if event.row >= rows || event.column >= cols {
+                return Ok(true);
+            }
+            mouse::encode(
+                &event,
+                screen.mouse_protocol_mode(),
+                screen.mouse_protocol_encoding(),
+            )
+        };
+        if let Some(bytes) = report {
+            let mut writer = self.writer.lock().unwrap();
+            writer.write_all(&bytes).context("failed to write to PTY")?;
+            writer.flush().context("failed to flush PTY")?;
+        }
+        Ok(true)
+    }
+
     pub fn resize(&self, rows: u16, cols: u16) -> Result<()> {
         self.master
             .resize(PtySize {
@@ -228,11 +497,7 @@ impl EmbeddedTerminal {
                 pixel_height: 0,
             })
             .map_err(|e| anyhow::anyhow!("{}", e))?;
-        self.parser
-            .lock()
-            .unwrap()
-            .screen_mut()
-            .set_size(rows, cols);
+        reflow::reflow(&mut self.parser.lock().unwrap(), rows, cols, SCROLLBACK_LEN);
         self.dirty.store(true, Ordering::Relaxed);
         Ok(())
     }
@@ -271,3 +536,86 @@ impl EmbeddedTerminal {
         &self.project_name
     }
 }
+
+/// Whether `cmd` is a path to an existing file, or resolves to one in the
+/// `PATH` directories the spawned process will inherit.
+fn resolvable(cmd: &str) -> bool {
+    if cmd.contains('/') {
+        return Path::new(cmd).is_file();
+    }
+    std::env::var_os("PATH")
+        .is_some_and(|paths| std::env::split_paths(&paths).any(|dir| dir.join(cmd).is_file()))
+}
+
+fn now_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+fn snapshot_lines(parser: &mut vt100::Parser) -> Vec<String> {
+    let (_, cols) = parser.screen().size();
+    let saved = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let depth = parser.screen().scrollback();
+
+    let mut lines = Vec::with_capacity(depth + 50);
+    // At offset n the top visible row is the n-th line from the end of
+    // the scrollback, so walking n down to 1 yields it oldest-first.
+    for offset in (1..=depth).rev() {
+        parser.screen_mut().set_scrollback(offset);
+        if let Some(row) = parser.screen().rows(0, cols).next() {
+            lines.push(row.trim_end().to_string());
+        }
+    }
+    parser.screen_mut().set_scrollback(0);
+    lines.extend(
+        parser
+            .screen()
+            .rows(0, cols)
+            .map(|r| r.trim_end().to_string()),
+    );
+    parser.screen_mut().set_scrollback(saved);
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_includes_scrollback_in_order() {
+        let mut parser = vt100::Parser::new(3, 20, 100);
+        for i in 1..=6 {
+            parser.process(format!("line {}\r\n", i).as_bytes());
+        }
+        parser.screen_mut().set_scrollback(2);
+        let lines = snapshot_lines(&mut parser);
+        assert_eq!(
+            lines,
+            vec!["line 1", "line 2", "line 3", "line 4", "line 5", "line 6"]
+        );
+        assert_eq!(parser.screen().scrollback(), 2);
+    }
+
+    #[test]
+    fn test_spawn_rejects_missing_cwd() {
+        assert!(resolvable("bash"));
+        assert!(!resolvable("c9s-no-such-command"));
+        assert!(!resolvable("/nonexistent/c9s/bash"));
+        let err = EmbeddedTerminal::spawn_shell(
+            Path::new("/nonexistent/c9s"),
+            24,
+            80,
+            &SpawnConfig::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("does not exist"));
+    }
+}
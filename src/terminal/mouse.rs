@@ -0,0 +1,163 @@
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use vt100::{MouseProtocolEncoding, MouseProtocolMode};
+
+/// The xterm report for `event`, whose column and row are already relative
+/// to the terminal's screen, or `None` when the mode the application asked
+/// for doesn't cover it (or the position can't be encoded).
+pub fn encode(
+    event: &MouseEvent,
+    mode: MouseProtocolMode,
+    encoding: MouseProtocolEncoding,
+) -> Option<Vec<u8>> {
+    let wanted = match event.kind {
+        MouseEventKind::Down(_)
+        | MouseEventKind::ScrollUp
+        | MouseEventKind::ScrollDown
+        | MouseEventKind::ScrollLeft
+        | MouseEventKind::ScrollRight => mode != MouseProtocolMode::None,
+        MouseEventKind::Up(_) => {
+            !matches!(mode, MouseProtocolMode::None | MouseProtocolMode::Press)
+        }
+        MouseEventKind::Drag(_) => matches!(
+            mode,
+            MouseProtocolMode::ButtonMotion | MouseProtocolMode::AnyMotion
+        ),
+        MouseEventKind::Moved => mode == MouseProtocolMode::AnyMotion,
+    };
+    if !wanted {
+        return None;
+    }
+
+    let release = matches!(event.kind, MouseEventKind::Up(_));
+    let mut code: u32 = match event.kind {
+        MouseEventKind::Down(b) | MouseEventKind::Drag(b) => button(b),
+        // Only SGR says which button went up; the others report "released".
+        MouseEventKind::Up(b) if encoding == MouseProtocolEncoding::Sgr => button(b),
+        MouseEventKind::Up(_) | MouseEventKind::Moved => 3,
+        MouseEventKind::ScrollUp => 64,
+        MouseEventKind::ScrollDown => 65,
+        MouseEventKind::ScrollLeft => 66,
+        MouseEventKind::ScrollRight => 67,
+    };
+    if matches!(event.kind, MouseEventKind::Drag(_) | MouseEventKind::Moved) {
+        code += 32;
+    }
+    // X10 mode reports no modifiers.
+    if mode != MouseProtocolMode::Press {
+        if event.modifiers.contains(KeyModifiers::SHIFT) {
+            code += 4;
+        }
+        if event.modifiers.contains(KeyModifiers::ALT) {
+            code += 8;
+        }
+        if event.modifiers.contains(KeyModifiers::CONTROL) {
+            code += 16;
+        }
+    }
+
+    let col = u32::from(event.column) + 1;
+    let row = u32::from(event.row) + 1;
+    match encoding {
+        MouseProtocolEncoding::Sgr => Some(
+            format!(
+                "\x1b[<{};{};{}{}",
+                code,
+                col,
+                row,
+                if release { 'm' } else { 'M' }
+            )
+            .into_bytes(),
+        ),
+        MouseProtocolEncoding::Default => {
+            let mut out = b"\x1b[M".to_vec();
+            for v in [code, col, row] {
+                out.push(u8::try_from(v + 32).ok()?);
+            }
+            Some(out)
+        }
+        MouseProtocolEncoding::Utf8 => {
+            let mut out = String::from("\x1b[M");
+            for v in [code, col, row] {
+                // xterm's limit for the UTF-8 extension: two-byte sequences.
+                if v + 32 > 0x7ff {
+                    return None;
+                }
+                out.push(char::from_u32(v + 32)?);
+            }
+            Some(out.into_bytes())
+        }
+    }
+}
+
+fn button(b: MouseButton) -> u32 {
+    match b {
+        MouseButton::Left => 0,
+        MouseButton::Middle => 1,
+        MouseButton::Right => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ev(kind: MouseEventKind, column: u16, row: u16) -> MouseEvent {
+        MouseEvent {
+            kind,
+            column,
+            row,
+            modifiers: KeyModifiers::NONE,
+        }
+    }
+
+    #[test]
+    fn test_sgr_press_and_release() {
+        let down = ev(MouseEventKind::Down(MouseButton::Left), 4, 9);
+        let up = ev(MouseEventKind::Up(MouseButton::Right), 4, 9);
+        let mode = MouseProtocolMode::PressRelease;
+        let enc = MouseProtocolEncoding::Sgr;
+        assert_eq!(encode(&down, mode, enc).unwrap(), b"\x1b[<0;5;10M");
+        assert_eq!(encode(&up, mode, enc).unwrap(), b"\x1b[<2;5;10m");
+    }
+
+    #[test]
+    fn test_default_encoding() {
+        let mut down = ev(MouseEventKind::Down(MouseButton::Middle), 0, 0);
+        down.modifiers = KeyModifiers::CONTROL;
+        let up = ev(MouseEventKind::Up(MouseButton::Middle), 0, 0);
+        let mode = MouseProtocolMode::PressRelease;
+        let enc = MouseProtocolEncoding::Default;
+        assert_eq!(encode(&down, mode, enc).unwrap(), b"\x1b[M\x31\x21\x21");
+        assert_eq!(encode(&up, mode, enc).unwrap(), b"\x1b[M\x23\x21\x21");
+        // Past column 223 the byte would overflow.
+        let far = ev(MouseEventKind::Down(MouseButton::Left), 230, 0);
+        assert_eq!(encode(&far, mode, enc), None);
+        assert!(encode(&far, mode, MouseProtocolEncoding::Utf8).is_some());
+    }
+
+    #[test]
+    fn test_mode_filters_events() {
+        let drag = ev(MouseEventKind::Drag(MouseButton::Left), 1, 1);
+        let moved = ev(MouseEventKind::Moved, 1, 1);
+        let up = ev(MouseEventKind::Up(MouseButton::Left), 1, 1);
+        let scroll = ev(MouseEventKind::ScrollDown, 1, 1);
+        let enc = MouseProtocolEncoding::Sgr;
+
+        assert_eq!(encode(&scroll, MouseProtocolMode::None, enc), None);
+        assert_eq!(encode(&up, MouseProtocolMode::Press, enc), None);
+        assert_eq!(
+            encode(&scroll, MouseProtocolMode::Press, enc).unwrap(),
+            b"\x1b[<65;2;2M"
+        );
+        assert_eq!(encode(&drag, MouseProtocolMode::PressRelease, enc), None);
+        assert_eq!(
+            encode(&drag, MouseProtocolMode::ButtonMotion, enc).unwrap(),
+            b"\x1b[<32;2;2M"
+        );
+        assert_eq!(encode(&moved, MouseProtocolMode::ButtonMotion, enc), None);
+        assert_eq!(
+            encode(&moved, MouseProtocolMode::AnyMotion, enc).unwrap(),
+            b"\x1b[<35;2;2M"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use crate::clock::SharedClock;
+pub use c9s_core::notify::{BellKind, NotifyLevel};
 use serde_json::Value;
 use std::io::{BufRead, BufReader, Seek, SeekFrom, Write as IoWrite};
 use std::path::{Path, PathBuf};
@@ -32,6 +34,7 @@ pub struct JsonlNotifier {
     read_offset: u64,
     state: SessionState,
     tool_use_at: Option<u64>,
+    clock: SharedClock,
 }
 
 fn now_millis() -> u64 {
@@ -70,22 +73,23 @@ impl JsonlNotifier {
             read_offset: size,
             state: SessionState::Unknown,
             tool_use_at: None,
+            clock: crate::clock::system(),
         }
     }
 
-    pub fn check(&mut self) -> bool {
+    pub fn check(&mut self) -> Option<BellKind> {
         if self.jsonl_path.is_none() {
             self.try_discover_path();
         }
 
         let path = match &self.jsonl_path {
             Some(p) => p.clone(),
-            None => return false,
+            None => return None,
         };
 
         let current_size = match std::fs::metadata(&path) {
             Ok(m) => m.len(),
-            Err(_) => return false,
+            Err(_) => return None,
         };
 
         if current_size > self.last_known_size {
@@ -100,33 +104,33 @@ impl JsonlNotifier {
 
         if current_size == self.last_known_size && self.read_offset >= self.last_known_size {
             if let Some(tool_at) = self.tool_use_at {
-                let elapsed = now_millis().saturating_sub(tool_at);
+                let elapsed = self.clock.now_millis().saturating_sub(tool_at);
                 if elapsed >= TOOL_WAIT_MS {
                     debug_log(&format!("BELL: tool_wait timer fired after {}ms", elapsed));
                     self.tool_use_at = None;
-                    return true;
+                    return Some(BellKind::NeedsInput);
                 }
             }
-            return false;
+            return None;
         }
 
         if current_size < self.read_offset {
             self.last_known_size = current_size;
             self.read_offset = current_size;
-            return false;
+            return None;
         }
 
         let file = match std::fs::File::open(&path) {
             Ok(f) => f,
-            Err(_) => return false,
+            Err(_) => return None,
         };
 
         let mut reader = BufReader::new(file);
         if reader.seek(SeekFrom::Start(self.read_offset)).is_err() {
-            return false;
+            return None;
         }
 
-        let mut should_notify = false;
+        let mut should_notify = None;
         let mut line = String::new();
         let mut good_offset = self.read_offset;
 
@@ -195,7 +199,7 @@ impl JsonlNotifier {
                                     "BELL: end_turn from state {:?}",
                                     self.state as u8
                                 ));
-                                should_notify = true;
+                                should_notify = Some(BellKind::TurnEnd);
                             }
                             self.state = SessionState::Idle;
                             self.tool_use_at = None;
@@ -203,7 +207,7 @@ impl JsonlNotifier {
                         Some("tool_use") => {
                             debug_log("state -> ToolWait, starting 5s timer");
                             self.state = SessionState::ToolWait;
-                            self.tool_use_at = Some(now_millis());
+                            self.tool_use_at = Some(self.clock.now_millis());
                         }
                         _ => {
                             if self.state != SessionState::ToolWait {
@@ -225,7 +229,7 @@ impl JsonlNotifier {
                                 subtype.unwrap_or("?"),
                                 self.state as u8
                             ));
-                            should_notify = true;
+                            should_notify = Some(BellKind::TurnEnd);
                         }
                         self.state = SessionState::Idle;
                         self.tool_use_at = None;
@@ -284,3 +288,41 @@ impl JsonlNotifier {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use std::time::Duration;
+
+    #[test]
+    fn test_pending_tool_call_rings_after_wait() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("s.jsonl");
+        std::fs::write(&path, "").unwrap();
+        let clock = MockClock::new();
+        let mut notifier = JsonlNotifier::new(dir.path(), "s");
+        notifier.jsonl_path = Some(path.clone());
+        notifier.clock = clock.clone();
+
+        let mut f = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        writeln!(f, r#"{{"type":"user","message":{{"content":"go"}}}}"#).unwrap();
+        writeln!(
+            f,
+            r#"{{"type":"assistant","message":{{"stop_reason":"tool_use"}}}}"#
+        )
+        .unwrap();
+        assert_eq!(notifier.check(), None);
+
+        clock.advance(Duration::from_millis(TOOL_WAIT_MS - 1));
+        assert_eq!(notifier.check(), None);
+        clock.advance(Duration::from_millis(1));
+        assert_eq!(notifier.check(), Some(BellKind::NeedsInput));
+        // Fires once per pending call.
+        clock.advance(Duration::from_millis(TOOL_WAIT_MS));
+        assert_eq!(notifier.check(), None);
+    }
+}
@@ -0,0 +1,105 @@
+//! Picks OSC 52 clipboard writes (`ESC ] 52 ; <selection> ; <base64> BEL`)
+//! out of a terminal's output, which may split them across reads.
+
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+
+/// Longest sequence kept; longer ones are dropped rather than buffered.
+const MAX_LEN: usize = 1 << 20;
+
+#[derive(Default)]
+enum State {
+    #[default]
+    Ground,
+    Escape,
+    /// Inside an OSC; `None` once it is known not to be a clipboard write
+    /// (or grew too long), so the rest is skipped.
+    Osc(Option<Vec<u8>>),
+    /// An ESC inside an OSC, which is a terminator if `\` follows.
+    OscEscape(Option<Vec<u8>>),
+}
+
+#[derive(Default)]
+pub struct Scanner {
+    state: State,
+}
+
+impl Scanner {
+    /// Feeds output through, returning the base64 payload of every
+    /// clipboard write completed in it. Reads (`52;c;?`) are ignored.
+    pub fn feed(&mut self, bytes: &[u8]) -> Vec<String> {
+        let mut found = Vec::new();
+        for &b in bytes {
+            self.state = match std::mem::take(&mut self.state) {
+                State::Ground | State::Escape if b == 0x1b => State::Escape,
+                State::Ground => State::Ground,
+                State::Escape if b == b']' => State::Osc(Some(Vec::new())),
+                State::Escape => State::Ground,
+                State::Osc(buf) if b == 0x07 => {
+                    found.extend(buf.and_then(payload));
+                    State::Ground
+                }
+                State::Osc(buf) if b == 0x1b => State::OscEscape(buf),
+                State::Osc(buf) => State::Osc(buf.and_then(|mut buf| {
+                    buf.push(b);
+                    let keep = buf.len() <= MAX_LEN && b"52;".starts_with(&buf[..buf.len().min(3)]);
+                    keep.then_some(buf)
+                })),
+                State::OscEscape(buf) if b == b'\\' => {
+                    found.extend(buf.and_then(payload));
+                    State::Ground
+                }
+                // An unterminated OSC, cut short by the next sequence.
+                State::OscEscape(_) if b == b']' => State::Osc(Some(Vec::new())),
+                State::OscEscape(_) if b == 0x1b => State::Escape,
+                State::OscEscape(_) => State::Ground,
+            };
+        }
+        found
+    }
+}
+
+/// The base64 data of a finished `52;<selection>;<data>` OSC.
+fn payload(osc: Vec<u8>) -> Option<String> {
+    let osc = String::from_utf8(osc).ok()?;
+    let (_, data) = osc.strip_prefix("52;")?.split_once(';')?;
+    (data != "?").then(|| data.to_string())
+}
+
+/// Decodes standard base64, padded or not; `None` on anything else.
+pub fn decode(data: &str) -> Option<Vec<u8>> {
+    const BASE64: GeneralPurpose = GeneralPurpose::new(
+        &alphabet::STANDARD,
+        GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+    );
+    BASE64.decode(data).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_clipboard_writes() {
+        let mut scanner = Scanner::default();
+        assert_eq!(
+            scanner.feed(b"text\x1b]52;c;aGVsbG8=\x07more"),
+            vec!["aGVsbG8=".to_string()]
+        );
+        // Split across reads, terminated by ST.
+        assert!(scanner.feed(b"\x1b]5").is_empty());
+        assert!(scanner.feed(b"2;;aGk=\x1b").is_empty());
+        assert_eq!(scanner.feed(b"\\"), vec!["aGk=".to_string()]);
+        // Titles and clipboard reads are not writes.
+        assert!(scanner.feed(b"\x1b]0;title\x07\x1b]52;c;?\x07").is_empty());
+    }
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(decode("aGVsbG8=").unwrap(), b"hello");
+        assert_eq!(decode("aGk").unwrap(), b"hi");
+        assert_eq!(decode("").unwrap(), b"");
+        assert_eq!(decode("a!b"), None);
+    }
+}
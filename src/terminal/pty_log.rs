@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Append-only copy of a terminal's raw PTY output. When the file grows past
+/// `max_bytes` it is moved to `<name>.1` (replacing any previous one) and a
+/// fresh file is started, so at most about twice the cap is kept on disk.
+pub struct PtyLog {
+    path: PathBuf,
+    file: File,
+    written: u64,
+    max_bytes: u64,
+}
+
+impl PtyLog {
+    pub fn open(dir: &Path, session_id: &str, max_bytes: u64) -> Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("creating log dir {}", dir.display()))?;
+        let path = dir.join(format!("{}.log", session_id));
+        let file = open_append(&path)?;
+        let written = file.metadata().map(|m| m.len()).unwrap_or(0);
+        Ok(Self {
+            path,
+            file,
+            written,
+            max_bytes,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        if self.max_bytes > 0 && self.written + bytes.len() as u64 > self.max_bytes {
+            self.rotate()?;
+        }
+        self.file.write_all(bytes)?;
+        self.written += bytes.len() as u64;
+        Ok(())
+    }
+
+    fn rotate(&mut self) -> Result<()> {
+        let mut rotated = self.path.clone().into_os_string();
+        rotated.push(".1");
+        std::fs::rename(&self.path, &rotated)?;
+        self.file = open_append(&self.path)?;
+        self.written = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> Result<File> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("opening {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_appends_and_rotates_at_cap() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut log = PtyLog::open(dir.path(), "abc", 10).unwrap();
+        log.write(b"hello").unwrap();
+        log.write(b"world").unwrap();
+        assert_eq!(std::fs::read(log.path()).unwrap(), b"helloworld");
+
+        log.write(b"!").unwrap();
+        assert_eq!(std::fs::read(log.path()).unwrap(), b"!");
+        assert_eq!(
+            std::fs::read(dir.path().join("abc.log.1")).unwrap(),
+            b"helloworld"
+        );
+    }
+
+    #[test]
+    fn test_reopen_continues_size_accounting() {
+        let dir = tempfile::tempdir().unwrap();
+        PtyLog::open(dir.path(), "s", 8)
+            .unwrap()
+            .write(b"12345")
+            .unwrap();
+        let mut log = PtyLog::open(dir.path(), "s", 8).unwrap();
+        log.write(b"6789").unwrap();
+        assert_eq!(std::fs::read(log.path()).unwrap(), b"6789");
+    }
+}
@@ -0,0 +1,242 @@
+use std::io::Write;
+
+/// One line as the program wrote it, before the emulator soft-wrapped it
+/// into rows.
+struct LogicalLine {
+    cells: Vec<vt100::Cell>,
+}
+
+/// Rebuilds `parser` at `rows`x`cols` with soft-wrapped lines, scrollback
+/// included, re-wrapped to the new width. vt100 only truncates or pads rows
+/// on resize, which leaves old output cut off mid-line.
+///
+/// Screen rows below the cursor are dropped; the program repaints them
+/// after the SIGWINCH that comes with the resize. The alternate screen is
+/// only resized, since full-screen programs redraw it completely.
+pub(super) fn reflow(parser: &mut vt100::Parser, rows: u16, cols: u16, scrollback_len: usize) {
+    let (_, old_cols) = parser.screen().size();
+    if cols == old_cols || rows == 0 || cols == 0 || parser.screen().alternate_screen() {
+        parser.screen_mut().set_size(rows, cols);
+        return;
+    }
+
+    let (lines, cursor) = logical_lines(parser);
+    let screen = parser.screen();
+    let modes = screen.input_mode_formatted();
+    let attrs = screen.attributes_formatted();
+    let hide_cursor = screen.hide_cursor();
+
+    let mut fresh = vt100::Parser::new(rows, cols, scrollback_len);
+    let mut out = Vec::new();
+    let mut width = 0;
+    for (i, line) in lines.iter().enumerate() {
+        if i > 0 {
+            out.extend_from_slice(b"\x1b[0m\r\n");
+        }
+        width = write_cells(&mut out, &line.cells);
+    }
+    out.extend_from_slice(b"\x1b[0m");
+    fresh.process(&out);
+
+    // The cursor's line was written last, so its first row is found by
+    // counting back from where writing stopped.
+    let line_rows = (width.max(1) - 1) / usize::from(cols) + 1;
+    let (end_row, _) = fresh.screen().cursor_position();
+    let start = usize::from(end_row).saturating_sub(line_rows - 1);
+    let row = (start + cursor / usize::from(cols)).min(usize::from(rows) - 1);
+    let col = cursor % usize::from(cols);
+    fresh.process(format!("\x1b[{};{}H", row + 1, col + 1).as_bytes());
+
+    fresh.process(&modes);
+    fresh.process(&attrs);
+    if hide_cursor {
+        fresh.process(b"\x1b[?25l");
+    }
+    *parser = fresh;
+}
+
+/// Joins wrapped rows from the oldest scrollback line down to the cursor's
+/// row. Also returns the cursor's column counted from the start of its
+/// logical line.
+fn logical_lines(parser: &mut vt100::Parser) -> (Vec<LogicalLine>, usize) {
+    let saved = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let depth = parser.screen().scrollback();
+    let (cursor_row, cursor_col) = parser.screen().cursor_position();
+
+    let mut lines = Vec::new();
+    let mut current = Vec::new();
+    // At offset n the top visible row is the n-th line from the end of
+    // the scrollback, so walking n down to 1 yields it oldest-first.
+    for offset in (1..=depth).rev() {
+        parser.screen_mut().set_scrollback(offset);
+        let screen = parser.screen();
+        push_row(screen, 0, screen.row_wrapped(0), &mut current, &mut lines);
+    }
+    parser.screen_mut().set_scrollback(0);
+    let screen = parser.screen();
+    for row in 0..cursor_row {
+        push_row(
+            screen,
+            row,
+            screen.row_wrapped(row),
+            &mut current,
+            &mut lines,
+        );
+    }
+    // The cursor's line ends on its row even if that row wraps on.
+    let cursor = current.len() + usize::from(cursor_col);
+    push_row(screen, cursor_row, false, &mut current, &mut lines);
+    parser.screen_mut().set_scrollback(saved);
+    (lines, cursor)
+}
+
+fn push_row(
+    screen: &vt100::Screen,
+    row: u16,
+    wrapped: bool,
+    current: &mut Vec<vt100::Cell>,
+    lines: &mut Vec<LogicalLine>,
+) {
+    let (_, cols) = screen.size();
+    let cells = (0..cols).filter_map(|c| screen.cell(row, c).cloned());
+    if wrapped {
+        current.extend(cells);
+    } else {
+        current.extend(trim_blank(cells.collect()));
+        lines.push(LogicalLine {
+            cells: std::mem::take(current),
+        });
+    }
+}
+
+/// Drops trailing cells that would print as plain spaces.
+fn trim_blank(mut cells: Vec<vt100::Cell>) -> Vec<vt100::Cell> {
+    let blank =
+        |c: &vt100::Cell| !c.has_contents() && c.bgcolor() == vt100::Color::Default && !c.inverse();
+    while cells.last().is_some_and(blank) {
+        cells.pop();
+    }
+    cells
+}
+
+/// Writes `cells` with SGR codes wherever the attributes change, and
+/// returns the number of columns they take up.
+fn write_cells(out: &mut Vec<u8>, cells: &[vt100::Cell]) -> usize {
+    let mut prev: Option<Sgr> = None;
+    let mut width = 0;
+    for cell in cells {
+        if cell.is_wide_continuation() {
+            continue;
+        }
+        let sgr = Sgr::of(cell);
+        if prev != Some(sgr) {
+            sgr.write(out);
+            prev = Some(sgr);
+        }
+        if cell.has_contents() {
+            out.extend_from_slice(cell.contents().as_bytes());
+        } else {
+            out.push(b' ');
+        }
+        width += if cell.is_wide() { 2 } else { 1 };
+    }
+    width
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Sgr {
+    fg: vt100::Color,
+    bg: vt100::Color,
+    bold: bool,
+    dim: bool,
+    italic: bool,
+    underline: bool,
+    inverse: bool,
+}
+
+impl Sgr {
+    fn of(cell: &vt100::Cell) -> Self {
+        Self {
+            fg: cell.fgcolor(),
+            bg: cell.bgcolor(),
+            bold: cell.bold(),
+            dim: cell.dim(),
+            italic: cell.italic(),
+            underline: cell.underline(),
+            inverse: cell.inverse(),
+        }
+    }
+
+    fn write(&self, out: &mut Vec<u8>) {
+        let mut codes = vec!["0".to_string()];
+        for (on, code) in [
+            (self.bold, "1"),
+            (self.dim, "2"),
+            (self.italic, "3"),
+            (self.underline, "4"),
+            (self.inverse, "7"),
+        ] {
+            if on {
+                codes.push(code.to_string());
+            }
+        }
+        for (color, base) in [(self.fg, 38), (self.bg, 48)] {
+            match color {
+                vt100::Color::Default => {}
+                vt100::Color::Idx(i) => codes.push(format!("{};5;{}", base, i)),
+                vt100::Color::Rgb(r, g, b) => codes.push(format!("{};2;{};{};{}", base, r, g, b)),
+            }
+        }
+        let _ = write!(out, "\x1b[{}m", codes.join(";"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows_of(parser: &vt100::Parser) -> Vec<String> {
+        let (_, cols) = parser.screen().size();
+        parser
+            .screen()
+            .rows(0, cols)
+            .map(|r| r.trim_end().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_wrapped_line_rejoins_when_wider() {
+        let mut parser = vt100::Parser::new(4, 10, 100);
+        parser.process(b"abcdefghijklmno\r\nxy");
+        assert_eq!(rows_of(&parser)[..2], ["abcdefghij", "klmno"]);
+
+        reflow(&mut parser, 4, 20, 100);
+        assert_eq!(rows_of(&parser)[..2], ["abcdefghijklmno", "xy"]);
+        assert_eq!(parser.screen().cursor_position(), (1, 2));
+    }
+
+    #[test]
+    fn test_scrollback_rewraps_and_keeps_color() {
+        let mut parser = vt100::Parser::new(2, 20, 100);
+        parser.process(b"\x1b[31mred line of text\x1b[0m\r\none\r\ntwo\r\n");
+
+        reflow(&mut parser, 2, 8, 100);
+        parser.screen_mut().set_scrollback(usize::MAX);
+        let depth = parser.screen().scrollback();
+        let mut all = Vec::new();
+        for offset in (1..=depth).rev() {
+            parser.screen_mut().set_scrollback(offset);
+            all.push(rows_of(&parser)[0].clone());
+        }
+        parser.screen_mut().set_scrollback(0);
+        all.extend(rows_of(&parser));
+        assert_eq!(all[..4], ["red line", " of text", "one", "two"]);
+
+        parser.screen_mut().set_scrollback(depth);
+        assert_eq!(
+            parser.screen().cell(0, 0).unwrap().fgcolor(),
+            vt100::Color::Idx(1)
+        );
+    }
+}
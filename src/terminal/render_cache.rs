@@ -0,0 +1,73 @@
+//! Rows converted in the previous frame, kept per terminal. A row whose
+//! vt100 cells are unchanged is copied from here instead of converted
+//! again; ratatui's buffer diff then sends only changed cells to the host.
+
+use ratatui::buffer::Cell;
+
+#[derive(Default)]
+pub struct RenderCache {
+    size: (u16, u16),
+    source: Vec<Vec<vt100::Cell>>,
+    rendered: Vec<Vec<Cell>>,
+}
+
+impl RenderCache {
+    /// The top `height` rows of `screen`, at most `width` cells each. Rows
+    /// that changed since the last call are run through `convert`.
+    pub fn rows(
+        &mut self,
+        screen: &vt100::Screen,
+        width: u16,
+        height: u16,
+        convert: impl Fn(&vt100::Cell) -> Cell,
+    ) -> &[Vec<Cell>] {
+        if self.size != (width, height) {
+            *self = RenderCache {
+                size: (width, height),
+                source: vec![Vec::new(); height as usize],
+                rendered: vec![Vec::new(); height as usize],
+            };
+        }
+
+        let mut cells = Vec::with_capacity(width as usize);
+        for row in 0..height {
+            cells.clear();
+            cells.extend((0..width).map_while(|col| screen.cell(row, col).cloned()));
+            let i = row as usize;
+            if self.source[i] != cells {
+                self.rendered[i] = cells.iter().map(&convert).collect();
+                std::mem::swap(&mut self.source[i], &mut cells);
+            }
+        }
+        &self.rendered
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plain(cell: &vt100::Cell) -> Cell {
+        let mut out = Cell::default();
+        out.set_symbol(cell.contents());
+        out
+    }
+
+    #[test]
+    fn test_rows_reuses_unchanged_rows() {
+        let mut parser = vt100::Parser::new(2, 6, 0);
+        parser.process(b"ab\r\ncd");
+        let mut cache = RenderCache::default();
+
+        let rows = cache.rows(parser.screen(), 6, 2, plain);
+        assert_eq!(rows[0][0].symbol(), "a");
+        let first = rows[0].as_ptr();
+        let second = rows[1].as_ptr();
+
+        parser.process(b"e");
+        let rows = cache.rows(parser.screen(), 6, 2, plain);
+        assert_eq!(rows[0].as_ptr(), first);
+        assert_ne!(rows[1].as_ptr(), second);
+        assert_eq!(rows[1][2].symbol(), "e");
+    }
+}
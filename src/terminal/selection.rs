@@ -0,0 +1,216 @@
+//! Copy mode: a cursor moving over a terminal's scrollback and screen, and
+//! the text between two points of it.
+
+/// A cell of the terminal's history. `line` counts from the oldest line
+/// kept, so a point stays on the same text as output scrolls the screen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point {
+    pub line: usize,
+    pub col: u16,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Motion {
+    Left,
+    Right,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    LineStart,
+    LineEnd,
+    Top,
+    Bottom,
+}
+
+/// Lines of scrollback above the screen.
+pub fn depth(parser: &mut vt100::Parser) -> usize {
+    let saved = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(usize::MAX);
+    let depth = parser.screen().scrollback();
+    parser.screen_mut().set_scrollback(saved);
+    depth
+}
+
+/// The history line shown on the first row of the screen.
+pub fn top(parser: &mut vt100::Parser) -> usize {
+    depth(parser) - parser.screen().scrollback()
+}
+
+/// Where copy mode starts: on the application's cursor when the screen is
+/// live, else at the start of the bottom row.
+pub fn start(parser: &mut vt100::Parser) -> Point {
+    let top = top(parser);
+    let screen = parser.screen();
+    if screen.scrollback() == 0 {
+        let (row, col) = screen.cursor_position();
+        Point {
+            line: top + usize::from(row),
+            col,
+        }
+    } else {
+        Point {
+            line: top + usize::from(screen.size().0) - 1,
+            col: 0,
+        }
+    }
+}
+
+/// Moves `point` and scrolls so it stays on screen.
+pub fn step(parser: &mut vt100::Parser, point: Point, motion: Motion) -> Point {
+    let depth = depth(parser);
+    let (rows, cols) = parser.screen().size();
+    let page = usize::from(rows.saturating_sub(1)).max(1);
+    let last = depth + usize::from(rows) - 1;
+    let mut p = point;
+    match motion {
+        Motion::Left => p.col = p.col.saturating_sub(1),
+        Motion::Right => p.col = (p.col + 1).min(cols - 1),
+        Motion::Up => p.line = p.line.saturating_sub(1),
+        Motion::Down => p.line = (p.line + 1).min(last),
+        Motion::PageUp => p.line = p.line.saturating_sub(page),
+        Motion::PageDown => p.line = (p.line + page).min(last),
+        Motion::LineStart => p.col = 0,
+        Motion::LineEnd => p.col = line_end(parser, depth, p.line),
+        Motion::Top => p = Point { line: 0, col: 0 },
+        Motion::Bottom => p.line = last,
+    }
+    reveal(parser, depth, p.line);
+    p
+}
+
+/// Scrolls as little as possible to bring `line` on screen.
+fn reveal(parser: &mut vt100::Parser, depth: usize, line: usize) {
+    let rows = usize::from(parser.screen().size().0);
+    let top = depth - parser.screen().scrollback();
+    let top = if line < top {
+        line
+    } else if line >= top + rows {
+        line + 1 - rows
+    } else {
+        return;
+    };
+    parser
+        .screen_mut()
+        .set_scrollback(depth.saturating_sub(top));
+}
+
+/// Scrolls `line` onto the screen and returns the row it is on.
+fn show(parser: &mut vt100::Parser, depth: usize, line: usize) -> u16 {
+    if line < depth {
+        parser.screen_mut().set_scrollback(depth - line);
+        0
+    } else {
+        parser.screen_mut().set_scrollback(0);
+        (line - depth) as u16
+    }
+}
+
+/// The last column of `line` with text on it.
+fn line_end(parser: &mut vt100::Parser, depth: usize, line: usize) -> u16 {
+    let saved = parser.screen().scrollback();
+    let row = show(parser, depth, line);
+    let screen = parser.screen();
+    let end = (0..screen.size().1)
+        .rev()
+        .find(|&col| screen.cell(row, col).is_some_and(|c| c.has_contents()))
+        .unwrap_or(0);
+    parser.screen_mut().set_scrollback(saved);
+    end
+}
+
+/// The text from `a` to `b`, both included and in either order; a column
+/// past the edge means the end of the row. Rows the application wrapped
+/// are joined back into one line.
+pub fn text(parser: &mut vt100::Parser, a: Point, b: Point) -> String {
+    let (start, end) = if a <= b { (a, b) } else { (b, a) };
+    let depth = depth(parser);
+    let saved = parser.screen().scrollback();
+    let cols = parser.screen().size().1;
+
+    let mut out = String::new();
+    for line in start.line..=end.line {
+        let from = if line == start.line { start.col } else { 0 };
+        let to = if line == end.line {
+            end.col.saturating_add(1).min(cols)
+        } else {
+            cols
+        };
+        let row = show(parser, depth, line);
+        let screen = parser.screen();
+        let text = screen
+            .rows(from, to.saturating_sub(from))
+            .nth(usize::from(row))
+            .unwrap_or_default();
+        if line == end.line || !screen.row_wrapped(row) {
+            out.push_str(text.trim_end());
+            if line != end.line {
+                out.push('\n');
+            }
+        } else {
+            out.push_str(&text);
+        }
+    }
+    parser.screen_mut().set_scrollback(saved);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser() -> vt100::Parser {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        for i in 1..=5 {
+            parser.process(format!("line {}\r\n", i).as_bytes());
+        }
+        parser.process(b"prompt");
+        parser
+    }
+
+    #[test]
+    fn test_text_spans_scrollback_and_screen() {
+        let mut parser = parser();
+        assert_eq!(depth(&mut parser), 3);
+        let a = Point { line: 1, col: 5 };
+        let b = Point { line: 5, col: 2 };
+        assert_eq!(text(&mut parser, a, b), "2\nline 3\nline 4\nline 5\npro");
+        assert_eq!(text(&mut parser, b, a), text(&mut parser, a, b));
+        assert_eq!(parser.screen().scrollback(), 0);
+    }
+
+    #[test]
+    fn test_text_joins_wrapped_rows() {
+        let mut parser = vt100::Parser::new(3, 10, 100);
+        parser.process(b"0123456789abcdef\r\nnext");
+        let a = Point { line: 0, col: 0 };
+        let b = Point { line: 2, col: 9 };
+        assert_eq!(text(&mut parser, a, b), "0123456789abcdef\nnext");
+    }
+
+    #[test]
+    fn test_step_scrolls_to_follow_the_cursor() {
+        let mut parser = parser();
+        let p = start(&mut parser);
+        assert_eq!(p, Point { line: 5, col: 6 });
+
+        let p = step(&mut parser, p, Motion::Up);
+        let p = step(&mut parser, p, Motion::Up);
+        assert_eq!(parser.screen().scrollback(), 0);
+        let p = step(&mut parser, p, Motion::Up);
+        assert_eq!(p.line, 2);
+        assert_eq!(parser.screen().scrollback(), 1);
+
+        let p = step(&mut parser, p, Motion::Top);
+        assert_eq!(p, Point { line: 0, col: 0 });
+        assert_eq!(parser.screen().scrollback(), 3);
+        let p = step(&mut parser, p, Motion::LineEnd);
+        assert_eq!(p.col, 5);
+
+        let p = step(&mut parser, p, Motion::Bottom);
+        assert_eq!(p.line, 5);
+        assert_eq!(parser.screen().scrollback(), 0);
+        let p = step(&mut parser, p, Motion::Down);
+        assert_eq!(p.line, 5);
+    }
+}
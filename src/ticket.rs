@@ -0,0 +1,121 @@
+//! Tickets sessions work on: tracker keys ("ABC-123") and issue numbers
+//! ("#456") read from branch names, and the links that open them.
+
+use crate::config::TicketsConfig;
+
+/// The first ticket id in `branch`: an uppercase tracker key anywhere
+/// ("feat/ABC-123-login"), else an issue number leading a path segment
+/// ("456-fix-crash") or after "issue-"/"gh-"/"#".
+pub fn from_branch(branch: &str) -> Option<String> {
+    tracker_key(branch).or_else(|| branch.split('/').find_map(issue_number))
+}
+
+fn tracker_key(branch: &str) -> Option<String> {
+    let bytes = branch.as_bytes();
+    for start in 0..bytes.len() {
+        if start > 0 && bytes[start - 1].is_ascii_alphanumeric() {
+            continue;
+        }
+        let letters = bytes[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+            .count();
+        if letters < 2 || !bytes[start].is_ascii_uppercase() {
+            continue;
+        }
+        let rest = &bytes[start + letters..];
+        if rest.first() != Some(&b'-') {
+            continue;
+        }
+        let digits = rest[1..].iter().take_while(|b| b.is_ascii_digit()).count();
+        let end = start + letters + 1 + digits;
+        if digits > 0 && bytes.get(end).is_none_or(|b| !b.is_ascii_alphanumeric()) {
+            return Some(branch[start..end].to_string());
+        }
+    }
+    None
+}
+
+fn issue_number(segment: &str) -> Option<String> {
+    let lower = segment.to_ascii_lowercase();
+    let (rest, prefixed) = ["issue-", "issues-", "gh-", "#"]
+        .iter()
+        .find_map(|p| lower.strip_prefix(p))
+        .map_or((lower.as_str(), false), |r| (r, true));
+    let digits = rest.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let after = &rest[digits..];
+    // Without a prefix, "456-fix-crash" is an issue but "2024-01" is not.
+    let ok = if prefixed {
+        after.is_empty() || after.starts_with(['-', '_'])
+    } else {
+        after.len() > 1
+            && after.starts_with(['-', '_'])
+            && after[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+    };
+    ok.then(|| format!("#{}", &rest[..digits]))
+}
+
+/// The link for `ticket`, or `None` when no template covers it.
+pub fn url(config: &TicketsConfig, ticket: &str, project: &str) -> Option<String> {
+    let (template, id) = match ticket.strip_prefix('#') {
+        Some(number) => (config.issue_url.as_ref()?, number),
+        None => {
+            let key = ticket.split('-').next().unwrap_or(ticket);
+            (config.keys.get(key).or(config.url.as_ref())?, ticket)
+        }
+    };
+    Some(template.replace("{id}", id).replace("{project}", project))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_branch() {
+        let cases = [
+            ("feat/ABC-123-login", Some("ABC-123")),
+            ("ABC-7", Some("ABC-7")),
+            ("fix/OPS2-40_retry", Some("OPS2-40")),
+            ("456-fix-crash", Some("#456")),
+            ("user/issue-78", Some("#78")),
+            ("gh-9-typo", Some("#9")),
+            ("release/2024-01", None),
+            ("fix-123", None),
+            ("xABC-12", None),
+            ("main", None),
+        ];
+        for (branch, want) in cases {
+            assert_eq!(from_branch(branch).as_deref(), want, "{}", branch);
+        }
+    }
+
+    #[test]
+    fn test_url() {
+        let config = TicketsConfig {
+            url: Some("https://acme.atlassian.net/browse/{id}".to_string()),
+            issue_url: Some("https://github.com/acme/{project}/issues/{id}".to_string()),
+            keys: [(
+                "OPS".to_string(),
+                "https://ops.example.com/{id}".to_string(),
+            )]
+            .into(),
+        };
+        assert_eq!(
+            url(&config, "ABC-1", "api").unwrap(),
+            "https://acme.atlassian.net/browse/ABC-1"
+        );
+        assert_eq!(
+            url(&config, "OPS-2", "api").unwrap(),
+            "https://ops.example.com/OPS-2"
+        );
+        assert_eq!(
+            url(&config, "#3", "api").unwrap(),
+            "https://github.com/acme/api/issues/3"
+        );
+        assert_eq!(url(&TicketsConfig::default(), "#3", "api"), None);
+    }
+}
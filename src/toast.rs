@@ -0,0 +1,133 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Toasts beyond this many push the oldest off the stack.
+const MAX_TOASTS: usize = 4;
+/// Past toasts kept for the dashboard.
+const MAX_HISTORY: usize = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Success,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Errors stay up longer so they can be read.
+    fn ttl(self) -> Duration {
+        match self {
+            Self::Info | Self::Success => Duration::from_secs(3),
+            Self::Warning => Duration::from_secs(5),
+            Self::Error => Duration::from_secs(8),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Toast {
+    pub message: String,
+    pub severity: Severity,
+    expires_at: Instant,
+}
+
+/// A toast as it was shown, kept after it expires.
+#[derive(Debug, Clone)]
+pub struct Notice {
+    pub at: chrono::DateTime<chrono::Utc>,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Short-lived messages shown in the bottom-right corner of every view.
+/// Each one is also written to the log panel so it can be read later.
+#[derive(Debug, Default)]
+pub struct Toasts {
+    items: VecDeque<Toast>,
+    history: VecDeque<Notice>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, severity: Severity, message: impl Into<String>) {
+        let message = message.into();
+        match severity {
+            Severity::Info | Severity::Success => crate::tlog!(info, "{}", message),
+            Severity::Warning => crate::tlog!(warn, "{}", message),
+            Severity::Error => crate::tlog!(error, "{}", message),
+        }
+        if self.history.len() >= MAX_HISTORY {
+            self.history.pop_front();
+        }
+        self.history.push_back(Notice {
+            at: chrono::Utc::now(),
+            severity,
+            message: message.clone(),
+        });
+        if self.items.len() >= MAX_TOASTS {
+            self.items.pop_front();
+        }
+        self.items.push_back(Toast {
+            message,
+            severity,
+            expires_at: Instant::now() + severity.ttl(),
+        });
+    }
+
+    /// Drops expired toasts; returns true if any were removed.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let before = self.items.len();
+        self.items.retain(|t| t.expires_at > now);
+        self.items.len() != before
+    }
+
+    /// Oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &Toast> {
+        self.items.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every toast shown lately, expired or not, newest first.
+    pub fn recent(&self) -> impl Iterator<Item = &Notice> {
+        self.history.iter().rev()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expire_by_severity() {
+        let mut toasts = Toasts::default();
+        toasts.push(Severity::Success, "saved");
+        toasts.push(Severity::Error, "failed");
+
+        let now = Instant::now();
+        assert!(!toasts.expire(now));
+        assert!(toasts.expire(now + Duration::from_secs(4)));
+        let left: Vec<_> = toasts.iter().map(|t| t.message.as_str()).collect();
+        assert_eq!(left, vec!["failed"]);
+        assert!(toasts.expire(now + Duration::from_secs(9)));
+        assert!(toasts.is_empty());
+    }
+
+    #[test]
+    fn test_oldest_dropped_when_full() {
+        let mut toasts = Toasts::default();
+        for i in 0..MAX_TOASTS + 1 {
+            toasts.push(Severity::Info, format!("t{}", i));
+        }
+        assert_eq!(toasts.iter().count(), MAX_TOASTS);
+        assert_eq!(toasts.iter().next().unwrap().message, "t1");
+
+        toasts.expire(Instant::now() + Duration::from_secs(10));
+        assert!(toasts.is_empty());
+        let recent: Vec<_> = toasts.recent().map(|n| n.message.as_str()).collect();
+        assert_eq!(recent.len(), MAX_TOASTS + 1);
+        assert_eq!(recent[0], format!("t{}", MAX_TOASTS));
+    }
+}
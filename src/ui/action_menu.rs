@@ -0,0 +1,74 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::i18n::{t, tf};
+use crate::ui::theme::Theme;
+
+/// The `[[actions]]` list for the session the menu was opened on, each
+/// with its number and the command it will run.
+pub fn render_action_menu(f: &mut Frame, app: &App, area: Rect) {
+    let Some(state) = app.action_menu.as_ref() else {
+        return;
+    };
+    let actions = app.user_actions();
+
+    let mut lines = vec![Line::from("")];
+    for (i, action) in actions.iter().enumerate() {
+        let selected = i == state.cursor;
+        let marker = if selected { " > " } else { "   " };
+        let number = if i < 9 {
+            format!("{} ", i + 1)
+        } else {
+            "  ".to_string()
+        };
+        let style = if selected {
+            Theme::value().add_modifier(Modifier::BOLD)
+        } else {
+            Theme::value()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(number, Theme::help_key()),
+            Span::styled(format!("{:<14} ", action.name), style),
+            Span::styled(action.command.clone(), Style::default().fg(Theme::muted())),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t(" Enter/1-9: run in new tab  Esc: close"),
+        Theme::footer(),
+    )));
+
+    let width = lines
+        .iter()
+        .map(|l| l.width() as u16 + 4)
+        .max()
+        .unwrap_or(0)
+        .clamp(40, area.width);
+    let popup = centered_rect(width, lines.len() as u16 + 2, area);
+    f.render_widget(Clear, popup);
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .title(tf(
+                " Actions: {project} ",
+                &[("project", &state.session.project_name)],
+            ))
+            .borders(Borders::ALL)
+            .border_style(Theme::title()),
+    );
+    f.render_widget(dialog, popup);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
@@ -3,6 +3,8 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
+use crate::i18n::t;
+use crate::session::export::ExportFormat;
 use crate::ui::theme::Theme;
 
 pub fn render_command_input(f: &mut Frame, input: &str, area: Rect) {
@@ -27,6 +29,89 @@ pub fn render_command_input(f: &mut Frame, input: &str, area: Rect) {
     f.render_widget(paragraph, popup_area);
 }
 
+pub fn render_quick_prompt(f: &mut Frame, project_name: &str, input: &str, area: Rect) {
+    let popup_width = 60.min(area.width);
+    let popup_area = centered_rect(popup_width, 3, area);
+    f.render_widget(Clear, popup_area);
+
+    let line = Line::from(vec![
+        Span::styled("> ", Theme::command_bar_label()),
+        Span::styled(input.to_string(), Theme::command_bar()),
+        Span::styled("_", Theme::command_bar()),
+    ]);
+
+    let block = Block::default()
+        .title(format!(" Send to {} ", project_name))
+        .title_bottom(Line::from(" Enter:send  Esc:cancel ").centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+
+    let paragraph = Paragraph::new(line).block(block);
+    f.render_widget(paragraph, popup_area);
+}
+
+pub fn render_ticket_input(f: &mut Frame, project_name: &str, input: &str, area: Rect) {
+    let popup_width = 50.min(area.width);
+    let popup_area = centered_rect(popup_width, 3, area);
+    f.render_widget(Clear, popup_area);
+
+    let line = Line::from(vec![
+        Span::styled("ticket: ", Theme::command_bar_label()),
+        Span::styled(input.to_string(), Theme::command_bar()),
+        Span::styled("_", Theme::command_bar()),
+    ]);
+
+    let block = Block::default()
+        .title(format!(" Ticket for {} ", project_name))
+        .title_bottom(Line::from(" Enter:save (empty clears)  Esc:cancel ").centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+
+    let paragraph = Paragraph::new(line).block(block);
+    f.render_widget(paragraph, popup_area);
+}
+
+pub fn render_export_prompt(f: &mut Frame, format: ExportFormat, path: &str, area: Rect) {
+    let popup_width = 70.min(area.width);
+    let popup_area = centered_rect(popup_width, 4, area);
+    f.render_widget(Clear, popup_area);
+
+    let format = match format {
+        ExportFormat::Markdown => "Markdown",
+        ExportFormat::Html => "HTML",
+    };
+    let destination = if path.is_empty() {
+        Span::styled(t("(clipboard)"), Theme::footer())
+    } else {
+        Span::styled(path.to_string(), Theme::command_bar())
+    };
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(t("format: "), Theme::command_bar_label()),
+            Span::styled(format, Theme::command_bar()),
+        ]),
+        Line::from(vec![
+            Span::styled(t("file: "), Theme::command_bar_label()),
+            destination,
+            Span::styled("_", Theme::command_bar()),
+        ]),
+    ];
+
+    let block = Block::default()
+        .title(t(" Export Transcript "))
+        .title_bottom(
+            Line::from(t(
+                " Tab:Markdown/HTML  Enter:export (no file copies)  Esc:cancel ",
+            ))
+            .centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+
+    let paragraph = Paragraph::new(lines).block(block);
+    f.render_widget(paragraph, popup_area);
+}
+
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Length(height)])
         .flex(Flex::Center)
@@ -1,42 +1,105 @@
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
-pub fn render_confirm_kill(f: &mut Frame, session_name: &str, area: Rect) {
-    let popup_width = 50;
-    let popup_height = 7;
+use crate::i18n::{t, tf};
+use crate::ui::theme::Theme;
 
-    let popup_area = centered_rect(popup_width, popup_height, area);
+/// Asks before killing a session. With `soft`, the session has a live tab
+/// and yes types `/exit` into it, leaving SIGTERM to `f`.
+pub fn render_confirm_kill(f: &mut Frame, session_name: &str, soft: bool, area: Rect) {
+    let popup_width = if soft { 56 } else { 50 };
+    let popup_height = if soft { 8 } else { 7 };
+
+    let popup_area = centered_rect(popup_width.min(area.width), popup_height, area);
 
     f.render_widget(Clear, popup_area);
 
-    let border_style = Style::default().fg(Color::Red);
+    let border_style = Style::default().fg(Theme::bad());
     let title_style = Style::default()
-        .fg(Color::Red)
+        .fg(Theme::bad())
+        .add_modifier(Modifier::BOLD);
+    let kill_style = Style::default()
+        .fg(Theme::bad())
         .add_modifier(Modifier::BOLD);
 
-    let lines = vec![
+    let mut lines = vec![
         Line::from(""),
         Line::from(Span::styled(
-            format!("  Kill session: {}", session_name),
+            tf("  Kill session: {name}", &[("name", session_name)]),
             title_style,
         )),
+    ];
+    if soft {
+        lines.push(Line::from(Span::styled(
+            t("  Types /exit in its tab; SIGTERM if it lingers."),
+            Style::default().fg(Theme::muted()),
+        )));
+    }
+    lines.push(Line::from(""));
+    let mut buttons = vec![Span::styled("    ", Style::default())];
+    if soft {
+        buttons.push(Span::styled(t(" y/Enter: exit "), kill_style));
+        buttons.push(Span::styled("   ", Style::default()));
+        buttons.push(Span::styled(t(" f: kill now "), kill_style));
+    } else {
+        buttons.push(Span::styled(t(" y/Enter: kill "), kill_style));
+    }
+    buttons.push(Span::styled("   ", Style::default()));
+    buttons.push(Span::styled(
+        t(" n/Esc: cancel "),
+        Style::default()
+            .fg(Theme::ok())
+            .add_modifier(Modifier::BOLD),
+    ));
+    lines.push(Line::from(buttons));
+
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .title(t(" Kill Session? "))
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+
+    f.render_widget(dialog, popup_area);
+}
+
+/// Asks before attaching to a session that runs in another terminal, as
+/// resuming it here ends it there.
+pub fn render_confirm_attach(f: &mut Frame, session_name: &str, tty: &str, area: Rect) {
+    let popup_width = 60.min(area.width);
+    let popup_area = centered_rect(popup_width, 8, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let warn = Style::default()
+        .fg(Theme::warn())
+        .add_modifier(Modifier::BOLD);
+
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            tf(
+                "  {name} is running in another terminal ({tty}).",
+                &[("name", session_name), ("tty", tty)],
+            ),
+            warn,
+        )),
+        Line::from(Span::styled(
+            t("  Attaching here ends it there."),
+            Theme::value(),
+        )),
         Line::from(""),
         Line::from(vec![
             Span::styled("    ", Style::default()),
-            Span::styled(
-                " y/Enter: kill ",
-                Style::default()
-                    .fg(Color::Red)
-                    .add_modifier(Modifier::BOLD),
-            ),
+            Span::styled(t(" y/Enter: attach "), warn),
             Span::styled("   ", Style::default()),
             Span::styled(
-                " n/Esc: cancel ",
+                t(" n/Esc: cancel "),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(Theme::ok())
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
@@ -44,9 +107,9 @@ pub fn render_confirm_kill(f: &mut Frame, session_name: &str, area: Rect) {
 
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Kill Session? ")
+            .title(t(" Attached Elsewhere "))
             .borders(Borders::ALL)
-            .border_style(border_style),
+            .border_style(Style::default().fg(Theme::warn())),
     );
 
     f.render_widget(dialog, popup_area);
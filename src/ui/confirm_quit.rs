@@ -1,9 +1,12 @@
 use ratatui::layout::{Constraint, Flex, Layout, Rect};
-use ratatui::style::{Color, Modifier, Style};
+use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
+use crate::i18n::t;
+use crate::ui::theme::Theme;
+
 pub fn render_confirm_quit(f: &mut Frame, active_sessions: &[String], area: Rect) {
     let content_lines = 3 + active_sessions.len();
     let popup_width = 50;
@@ -13,13 +16,15 @@ pub fn render_confirm_quit(f: &mut Frame, active_sessions: &[String], area: Rect
 
     f.render_widget(Clear, popup_area);
 
-    let border_style = Style::default().fg(Color::Red);
-    let title_style = Style::default().fg(Color::Red).add_modifier(Modifier::BOLD);
+    let border_style = Style::default().fg(Theme::bad());
+    let title_style = Style::default()
+        .fg(Theme::bad())
+        .add_modifier(Modifier::BOLD);
 
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::from(""));
     lines.push(Line::from(Span::styled(
-        "  Active sessions will be terminated:",
+        t("  Active sessions will be terminated:"),
         title_style,
     )));
     lines.push(Line::from(""));
@@ -30,7 +35,7 @@ pub fn render_confirm_quit(f: &mut Frame, active_sessions: &[String], area: Rect
             Span::styled(
                 format!("  {}  ", name),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(Theme::warn())
                     .add_modifier(Modifier::BOLD),
             ),
         ]));
@@ -40,21 +45,23 @@ pub fn render_confirm_quit(f: &mut Frame, active_sessions: &[String], area: Rect
     lines.push(Line::from(vec![
         Span::styled("    ", Style::default()),
         Span::styled(
-            " y/Enter: exit ",
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            t(" y/Enter: exit "),
+            Style::default()
+                .fg(Theme::bad())
+                .add_modifier(Modifier::BOLD),
         ),
         Span::styled("   ", Style::default()),
         Span::styled(
-            " n/Esc: cancel ",
+            t(" n/Esc: cancel "),
             Style::default()
-                .fg(Color::Green)
+                .fg(Theme::ok())
                 .add_modifier(Modifier::BOLD),
         ),
     ]));
 
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Quit c9s? ")
+            .title(t(" Quit c9s? "))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
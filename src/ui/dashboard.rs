@@ -0,0 +1,223 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph, Sparkline};
+use ratatui::Frame;
+
+use crate::app::{App, SessionEntry};
+use crate::config::DashboardWidget;
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::toast::Severity;
+use crate::ui::theme::Theme;
+
+/// Usage sparkline slices: ten minutes each over the five-hour window.
+const USAGE_BUCKETS: usize = 30;
+
+/// The configured widgets in rows, each row and each widget in a row
+/// given an equal share; meant to be left open on a second screen.
+pub fn render_dashboard(f: &mut Frame, app: &App, area: Rect) {
+    let rows = app.dashboard_rows();
+    let [body, footer] = Layout::vertical([Constraint::Fill(1), Constraint::Length(1)]).areas(area);
+    let row_areas = Layout::vertical(rows.iter().map(|_| Constraint::Fill(1))).split(body);
+    for (widgets, row_area) in rows.iter().zip(row_areas.iter()) {
+        let cells =
+            Layout::horizontal(widgets.iter().map(|_| Constraint::Fill(1))).split(*row_area);
+        for (widget, cell) in widgets.iter().zip(cells.iter()) {
+            match widget {
+                DashboardWidget::Sessions => render_sessions(f, app, *cell),
+                DashboardWidget::Usage => render_usage(f, app, *cell),
+                DashboardWidget::Notifications => render_notifications(f, app, *cell),
+                DashboardWidget::Implementations => render_implementations(f, app, *cell),
+            }
+        }
+    }
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(t(" B/Esc:back "), Theme::footer()))),
+        footer,
+    );
+}
+
+fn pane(title: String) -> Block<'static> {
+    Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Theme::border())
+}
+
+fn empty(text: &str) -> Line<'static> {
+    Line::from(Span::styled(
+        text.to_string(),
+        Style::default().fg(Color::DarkGray),
+    ))
+}
+
+fn render_sessions(f: &mut Frame, app: &App, area: Rect) {
+    let sessions: Vec<_> = app
+        .filtered_sessions()
+        .into_iter()
+        .filter(|e| !e.is_remote())
+        .collect();
+    let live = sessions
+        .iter()
+        .filter(|e| e.as_local().is_some_and(|s| s.pid.is_some()))
+        .count();
+    let mut lines: Vec<Line> = sessions
+        .iter()
+        .map(|entry| {
+            let (icon, style) = Theme::entry_status(entry);
+            Line::from(vec![
+                Span::styled(format!(" {} {:<9.9}", icon, entry.status_label()), style),
+                Span::styled(format!(" {:<20.20}", entry.display_name()), Theme::value()),
+                Span::styled(
+                    format!(" {:<24.24}", entry.branch().unwrap_or("-")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!(
+                        " {:>9}",
+                        entry
+                            .estimated_cost()
+                            .map_or_else(|| "-".to_string(), |c| format::cost(c, 2))
+                    ),
+                    Theme::cost(),
+                ),
+                Span::styled(
+                    format!(" {:>10}", entry.last_activity_display()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(empty(t(" No sessions")));
+    }
+    let title = tf(
+        " Sessions ({live} live / {total}) ",
+        &[
+            ("live", &live.to_string()),
+            ("total", &sessions.len().to_string()),
+        ],
+    );
+    f.render_widget(Paragraph::new(lines).block(pane(title)), area);
+}
+
+fn render_usage(f: &mut Frame, app: &App, area: Rect) {
+    let block = pane(t(" Usage ").to_string());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let usage = app.usage();
+    let headline = if let Some(pct) = usage.five_hour {
+        let pct = pct.to_string();
+        match usage.five_hour_reset {
+            Some(ref reset) => tf(
+                " 5h window {pct}% used, resets {reset}",
+                &[("pct", &pct), ("reset", reset)],
+            ),
+            None => tf(" 5h window {pct}% used", &[("pct", &pct)]),
+        }
+    } else if let Some((estimate, limit)) = app.usage_window() {
+        let resets_in = (estimate.resets_at - chrono::Utc::now())
+            .num_seconds()
+            .max(0) as u64;
+        let resets_in = format::elapsed(resets_in);
+        match limit.and_then(|l| estimate.percent(l)) {
+            Some(pct) => tf(
+                " 5h window ~{pct}% used, resets in {time}",
+                &[("pct", &pct.to_string()), ("time", &resets_in)],
+            ),
+            None => tf(
+                " 5h window {tokens} tokens, resets in {time}",
+                &[
+                    ("tokens", &format::tokens(estimate.tokens)),
+                    ("time", &resets_in),
+                ],
+            ),
+        }
+    } else {
+        t(" No usage in the last 5h").to_string()
+    };
+    let [text, chart] = Layout::vertical([Constraint::Length(1), Constraint::Fill(1)]).areas(inner);
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(headline, Theme::value()))),
+        text,
+    );
+
+    let history = app.usage_history(USAGE_BUCKETS);
+    let chart = Rect {
+        x: chart.x + 1,
+        width: chart.width.saturating_sub(2),
+        ..chart
+    };
+    f.render_widget(
+        Sparkline::default()
+            .data(&history)
+            .style(Style::default().fg(Color::Indexed(75))),
+        chart,
+    );
+}
+
+fn render_notifications(f: &mut Frame, app: &App, area: Rect) {
+    let rows = area.height.saturating_sub(2) as usize;
+    let mut lines: Vec<Line> = app
+        .toasts()
+        .recent()
+        .take(rows)
+        .map(|notice| {
+            let color = match notice.severity {
+                Severity::Info => Color::Cyan,
+                Severity::Success => Theme::ok(),
+                Severity::Warning => Theme::warn(),
+                Severity::Error => Theme::bad(),
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {:>8} ", format::since(notice.at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(notice.message.clone(), Style::default().fg(color)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(empty(t(" Nothing yet")));
+    }
+    f.render_widget(
+        Paragraph::new(lines).block(pane(t(" Notifications ").to_string())),
+        area,
+    );
+}
+
+fn render_implementations(f: &mut Frame, app: &App, area: Rect) {
+    let mut lines: Vec<Line> = app
+        .filtered_sessions()
+        .into_iter()
+        .filter_map(|entry| match entry {
+            SessionEntry::Remote(imp) if !imp.status.is_terminal() => Some((entry, imp)),
+            _ => None,
+        })
+        .map(|(entry, imp)| {
+            let (icon, style) = Theme::impl_status(&imp.status);
+            Line::from(vec![
+                Span::styled(format!(" {} {:<9.9}", icon, imp.status.label()), style),
+                Span::styled(format!(" {:<30.30}", imp.display_name()), Theme::value()),
+                Span::styled(
+                    format!(" {:<24.24}", imp.branch.as_deref().unwrap_or("-")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!(" {:>10}", entry.last_activity_display()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(empty(t(" No implementations running")));
+    }
+    f.render_widget(
+        Paragraph::new(lines).block(pane(t(" Implementations ").to_string())),
+        area,
+    );
+}
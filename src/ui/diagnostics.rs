@@ -0,0 +1,77 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::i18n::{t, tf};
+use crate::session::FileDiagnostics;
+use crate::ui::theme::Theme;
+
+pub fn render_diagnostics(
+    f: &mut Frame,
+    files: &[FileDiagnostics],
+    scanning: bool,
+    scroll: usize,
+    area: Rect,
+) {
+    let inner_height = area.height.saturating_sub(2) as usize;
+
+    let lines: Vec<Line> = if files.is_empty() {
+        let note = if scanning {
+            " Still scanning transcripts..."
+        } else {
+            " Every transcript parsed cleanly."
+        };
+        vec![Line::from(Span::styled(
+            note,
+            Style::default().fg(Color::DarkGray),
+        ))]
+    } else {
+        files
+            .iter()
+            .map(|d| {
+                let bad_style = if d.parse_errors > 0 {
+                    Style::default().fg(Theme::bad())
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                };
+                let partial = match d.partial_offset {
+                    Some(at) => format!("partial line @ {:<10}", at),
+                    None => " ".repeat(25),
+                };
+                let id = d.session_id.as_deref().unwrap_or("-");
+                Line::from(vec![
+                    Span::styled(format!(" {:>5} bad  ", d.parse_errors), bad_style),
+                    Span::styled(partial, Style::default().fg(Theme::warn())),
+                    Span::styled(
+                        format!(" {:.8}  ", id),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::styled(d.path.display().to_string(), Theme::value()),
+                ])
+            })
+            .collect()
+    };
+
+    let total = lines.len();
+    let max_scroll = total.saturating_sub(inner_height);
+    let effective_scroll = scroll.min(max_scroll);
+
+    let footer_text = tf(
+        " D:back  j/k:scroll  g/G:top/bottom  ({n} files) ",
+        &[("n", &files.len().to_string())],
+    );
+
+    let block = Block::default()
+        .title(t(" Transcript Diagnostics "))
+        .title_bottom(Line::from(footer_text).centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::border());
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((effective_scroll as u16, 0));
+
+    f.render_widget(paragraph, area);
+}
@@ -0,0 +1,62 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use ratatui::Frame;
+
+use crate::app::ErrorModalState;
+use crate::i18n::t;
+use crate::ui::theme::Theme;
+
+pub fn render_error_modal(f: &mut Frame, state: &ErrorModalState, area: Rect) {
+    let popup_width = 64.min(area.width.saturating_sub(4));
+    let text_width = popup_width.saturating_sub(4).max(1) as usize;
+    let wrapped_rows: usize = state
+        .message
+        .lines()
+        .map(|l| l.chars().count().div_ceil(text_width).max(1))
+        .sum();
+    let popup_height = (wrapped_rows as u16 + 5).min(area.height.saturating_sub(2));
+
+    let popup_area = centered_rect(popup_width, popup_height, area);
+
+    f.render_widget(Clear, popup_area);
+
+    let border_style = Style::default().fg(Theme::bad());
+    let title_style = Style::default()
+        .fg(Theme::bad())
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines = vec![Line::from("")];
+    lines.extend(
+        state
+            .message
+            .lines()
+            .map(|l| Line::from(Span::styled(format!(" {}", l), Style::default()))),
+    );
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t(" Enter/Esc: dismiss"),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let dialog = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .title(format!(" {} ", state.title))
+            .title_style(title_style)
+            .borders(Borders::ALL)
+            .border_style(border_style),
+    );
+
+    f.render_widget(dialog, popup_area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
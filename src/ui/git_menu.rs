@@ -0,0 +1,115 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::Modifier;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{App, ViewMode};
+use crate::i18n::{t, tf};
+use crate::ui::theme::Theme;
+
+/// Shortcut keys for the menu items, in `GitOp::menu` order.
+const KEYS: [&str; 4] = ["f", "p", "b", "s"];
+
+/// The git chores for the session the menu was opened on, or the prompt
+/// for a new branch's name.
+pub fn render_git_menu(f: &mut Frame, app: &App, area: Rect) {
+    let Some(state) = app.git_menu.as_ref() else {
+        return;
+    };
+    let title = tf(
+        " Git: {project} ",
+        &[("project", &state.session.project_name)],
+    );
+
+    if *app.view_mode() == ViewMode::GitBranchName {
+        let popup = centered_rect(50.min(area.width), 3, area);
+        f.render_widget(Clear, popup);
+        let line = Line::from(vec![
+            Span::styled(t("branch: "), Theme::command_bar_label()),
+            Span::styled(state.branch.clone(), Theme::command_bar()),
+            Span::styled("_", Theme::command_bar()),
+        ]);
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::from(t(" Enter:create and switch  Esc:cancel ")).centered())
+            .borders(Borders::ALL)
+            .border_style(Theme::title());
+        f.render_widget(Paragraph::new(line).block(block), popup);
+        return;
+    }
+
+    let mut lines = vec![Line::from("")];
+    for (i, (op, key)) in state.items.iter().zip(KEYS).enumerate() {
+        let selected = i == state.cursor;
+        let style = if selected {
+            Theme::value().add_modifier(Modifier::BOLD)
+        } else {
+            Theme::value()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { " > " } else { "   " }, style),
+            Span::styled(format!("{} ", key), Theme::help_key()),
+            Span::styled(op.label(), style),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t(" Output goes to the log (L)"),
+        Theme::footer(),
+    )));
+
+    let popup = centered_rect(40.min(area.width), lines.len() as u16 + 2, area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// The commit message being typed for the detail's working diff, and
+/// whether a pull request follows.
+pub fn render_commit_prompt(f: &mut Frame, app: &App, area: Rect) {
+    let Some(state) = app.commit_prompt.as_ref() else {
+        return;
+    };
+    let title = tf(
+        " Commit: {project} ",
+        &[("project", &state.session.project_name)],
+    );
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(t("message: "), Theme::command_bar_label()),
+            Span::styled(state.message.clone(), Theme::command_bar()),
+            Span::styled("_", Theme::command_bar()),
+        ]),
+        Line::from(vec![
+            Span::styled(
+                if state.pull_request { "[x] " } else { "[ ] " },
+                Theme::help_key(),
+            ),
+            Span::styled(t("push and open a pull request (gh)"), Theme::value()),
+        ]),
+    ];
+    let popup = centered_rect(60.min(area.width), 4, area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(title)
+        .title_bottom(
+            Line::from(t(" Enter:commit all tracked changes  Tab:PR  Esc:cancel ")).centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
@@ -3,23 +3,64 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
+use crate::i18n::t;
+use crate::plugin::{PluginAction, Plugins};
 use crate::ui::theme::Theme;
 
-const BINDINGS: &[(&str, &str)] = &[
+/// Key and description pairs; a pair with an empty description starts a
+/// section named by the key, an empty pair separates sections.
+pub(crate) const BINDINGS: &[(&str, &str)] = &[
     ("j / Down", "Move down"),
     ("k / Up", "Move up"),
     ("g", "Jump to top"),
     ("G", "Jump to bottom"),
     ("d", "Session detail / Tervezo detail"),
+    ("m", "Check MCP servers (in detail)"),
+    ("Tab", "Overview / files / diff / transcript (in detail)"),
+    ("c", "Commit the diff, optionally open a PR (diff tab)"),
+    ("t", "Session timeline (local)"),
+    (">", "Send a line to a background session"),
     ("a", "Attach (open terminal)"),
+    ("A", "Run a configured action"),
+    ("v", "Git: fetch, pull, branch, stash"),
+    ("H", "Handoff bundle for a colleague"),
+    ("i / I", "Open ticket / set ticket"),
+    ("Enter (dead)", "Resume or fresh session in cwd"),
     ("1-9", "Attach to live session by #"),
     ("Space", "Quick switcher"),
     ("n", "New session (local or tervezo)"),
+    ("x", "Kill session (asks first)"),
     ("c", "Fix failing CI (tervezo)"),
     ("/", "Filter sessions"),
+    ("E", "Only sessions with recent errors"),
+    ("h", "Hide session / list it again"),
+    (".", "Show hidden, excluded and old dead sessions"),
+    ("p", "Project names / short paths / full paths"),
+    ("e", "Expand row: last prompt and reply"),
+    ("^o / ^i", "Back / forward: earlier filter, sort, selection"),
     ("s", "Cycle sort column"),
     ("r", "Refresh session list"),
     ("L", "Toggle log panel"),
+    ("O", "Toggle terminal output log file"),
+    ("w", "Read-only web mirror of the terminal on / off"),
+    ("X", "Output of recently exited tabs"),
+    ("D", "Transcript parse diagnostics"),
+    ("P", "Cycle color palette"),
+    ("b", "Bell: all / needs-input / mute"),
+    ("z", "Do not disturb (30 min) on / off"),
+    ("T", "Time tracking on / off"),
+    ("F", "Focus timer on / off"),
+    ("R", "Rate the session's outcome"),
+    ("U", "Outcomes per project and model"),
+    ("S", "Cost and tokens per day and project"),
+    (
+        "B",
+        "Dashboard: sessions, usage, notifications, implementations",
+    ),
+    ("K", "Restart a session the watchdog found hung"),
+    ("M", "Show CPU and memory of live sessions"),
+    ("C", "Switch Tervezo organization"),
+    ("Q", "Waiting Tervezo implementations: reorder or cancel"),
     ("Esc", "Back / clear filter"),
     ("q", "Quit"),
     ("Ctrl+c", "Force quit"),
@@ -31,44 +72,67 @@ const BINDINGS: &[(&str, &str)] = &[
     ("Ctrl+Space", "Quick switcher"),
     ("Ctrl+n / Ctrl+p", "Cycle next / prev session"),
     ("Ctrl+K / Ctrl+J", "Scroll history up / down"),
+    ("Ctrl+]", "Copy mode (move, v to select, y to copy)"),
     ("Ctrl+t", "Toggle side shell panel"),
     ("", ""),
+    ("Session timeline", ""),
+    ("b", "Bookmark selected event"),
+    ("n / N", "Next / prev bookmark"),
+    ("B", "Bookmark list (Enter to jump)"),
+    ("e / y", "Export transcript to a file / the clipboard"),
+    ("", ""),
     ("Tervezo detail", ""),
     ("Tab / l / h", "Next / prev tab"),
     ("j / k", "Scroll tab content"),
     ("J / K", "Scroll timeline"),
     ("s", "SSH into running impl"),
     ("r", "Refresh detail data"),
+    ("o", "Load older timeline messages"),
+    ("[ / ]", "Previous / next attempt after a restart"),
     ("Esc / q", "Back to list"),
 ];
 
-pub fn render_help(f: &mut Frame, area: Rect) {
-    let popup_width = 52;
-    let popup_height = (BINDINGS.len() as u16) + 4;
-
-    let popup_area = centered_rect(popup_width, popup_height, area);
-
-    f.render_widget(Clear, popup_area);
-
-    let lines: Vec<Line> = BINDINGS
+pub fn render_help(f: &mut Frame, plugins: &Plugins, area: Rect) {
+    let mut lines: Vec<Line> = BINDINGS
         .iter()
         .map(|(key, desc)| {
             if key.is_empty() && desc.is_empty() {
                 Line::from("")
             } else if desc.is_empty() {
-                Line::from(Span::styled(format!("  -- {} --", key), Theme::footer()))
+                Line::from(Span::styled(format!("  -- {} --", t(key)), Theme::footer()))
             } else {
                 Line::from(vec![
                     Span::styled(format!("  {:<16}", key), Theme::help_key()),
-                    Span::styled(*desc, Theme::help_desc()),
+                    Span::styled(t(desc), Theme::help_desc()),
                 ])
             }
         })
         .collect();
 
+    // Plugin descriptions come from the plugins and are not translated.
+    let actions: Vec<&PluginAction> = plugins.actions().collect();
+    if !actions.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  -- {} --", t("Plugins")),
+            Theme::footer(),
+        )));
+        lines.extend(actions.iter().map(|a| {
+            Line::from(vec![
+                Span::styled(format!("  {:<16}", a.key), Theme::help_key()),
+                Span::styled(a.description, Theme::help_desc()),
+            ])
+        }));
+    }
+
+    let popup_width = 52;
+    let popup_height = (lines.len() as u16) + 4;
+    let popup_area = centered_rect(popup_width, popup_height, area);
+    f.render_widget(Clear, popup_area);
+
     let help = Paragraph::new(lines).block(
         Block::default()
-            .title(" Keybindings ")
+            .title(t(" Keybindings "))
             .borders(Borders::ALL)
             .border_style(Theme::title()),
     );
@@ -85,3 +149,20 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
         .split(vertical[0]);
     horizontal[0]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bindings_are_translatable() {
+        for (key, desc) in BINDINGS {
+            let msg = if desc.is_empty() { key } else { desc };
+            assert!(
+                msg.is_empty() || crate::i18n::MESSAGES.contains(msg),
+                "{:?} missing from MESSAGES",
+                msg
+            );
+        }
+    }
+}
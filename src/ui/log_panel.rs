@@ -4,6 +4,8 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
+use crate::format;
+use crate::i18n::{t, tf};
 use crate::log::{LogEntry, LogLevel};
 use crate::ui::theme::Theme;
 
@@ -13,14 +15,14 @@ pub fn render_log_panel(f: &mut Frame, entries: &[LogEntry], scroll: usize, area
     let lines: Vec<Line> = entries
         .iter()
         .map(|entry| {
-            let ts = entry.timestamp.format("%H:%M:%S").to_string();
+            let ts = format::clock(&entry.timestamp, true);
             let level_style = match entry.level {
                 LogLevel::Info => Style::default().fg(Color::DarkGray),
-                LogLevel::Warn => Style::default().fg(Color::Yellow),
-                LogLevel::Error => Style::default().fg(Color::Red),
+                LogLevel::Warn => Style::default().fg(Theme::warn()),
+                LogLevel::Error => Style::default().fg(Theme::bad()),
             };
             let msg_style = match entry.level {
-                LogLevel::Error => Style::default().fg(Color::Red),
+                LogLevel::Error => Style::default().fg(Theme::bad()),
                 _ => Style::default().fg(Color::White),
             };
 
@@ -37,13 +39,13 @@ pub fn render_log_panel(f: &mut Frame, entries: &[LogEntry], scroll: usize, area
     let max_scroll = total.saturating_sub(inner_height);
     let effective_scroll = scroll.min(max_scroll);
 
-    let footer_text = format!(
-        " L:back  j/k:scroll  g/G:top/bottom  c:clear  ({} entries) ",
-        total
+    let footer_text = tf(
+        " L:back  j/k:scroll  g/G:top/bottom  c:clear  ({n} entries) ",
+        &[("n", &total.to_string())],
     );
 
     let block = Block::default()
-        .title(" Log ")
+        .title(t(" Log "))
         .title_bottom(Line::from(footer_text).centered())
         .borders(Borders::ALL)
         .border_style(Theme::border());
@@ -1,34 +1,64 @@
+mod action_menu;
 mod command_bar_view;
 mod confirm_kill;
 mod confirm_quit;
-mod help;
+mod dashboard;
+mod diagnostics;
+mod error_modal;
+mod git_menu;
+pub(crate) mod help;
 mod log_panel;
 mod new_session_menu;
+mod org_picker;
+mod outcomes;
 mod qswitcher;
+mod queue;
+mod recently_exited;
 mod session_detail;
 mod session_file_picker;
-mod session_list;
+pub(crate) mod session_list;
+mod session_timeline;
 mod side_panel;
+#[cfg(test)]
+mod snapshot_tests;
+mod stats;
 pub(crate) mod terminal_view;
+mod tervezo_compare;
 mod tervezo_create;
 mod tervezo_detail;
-mod theme;
+pub(crate) mod theme;
+mod toasts;
 pub mod usage_panel;
 
-pub use command_bar_view::render_command_input;
-pub use confirm_kill::render_confirm_kill;
+pub use action_menu::render_action_menu;
+pub use command_bar_view::{
+    render_command_input, render_export_prompt, render_quick_prompt, render_ticket_input,
+};
+pub use confirm_kill::{render_confirm_attach, render_confirm_kill};
 pub use confirm_quit::render_confirm_quit;
+pub use dashboard::render_dashboard;
+pub use diagnostics::render_diagnostics;
+pub use error_modal::render_error_modal;
+pub use git_menu::{render_commit_prompt, render_git_menu};
 pub use help::render_help;
 pub use log_panel::render_log_panel;
 pub use new_session_menu::render_new_session_menu;
+pub use org_picker::render_org_picker;
+pub use outcomes::{render_outcome_prompt, render_outcomes};
 pub use qswitcher::render_qswitcher;
-pub use session_detail::render_session_detail;
+pub use queue::{render_queue, render_queue_confirm};
+pub use recently_exited::render_recently_exited;
+pub use session_detail::{render_session_detail, DetailTabs};
 pub use session_file_picker::render_session_file_picker;
 pub use session_list::render_session_list;
+pub use session_timeline::render_session_timeline;
 pub use side_panel::{render_side_panel, split_with_side_panel};
+pub use stats::render_stats;
 pub use terminal_view::render_terminal;
+pub use tervezo_compare::render_tervezo_compare;
 pub use tervezo_create::render_tervezo_create_dialog;
 pub use tervezo_detail::{
     render_tervezo_action_menu, render_tervezo_confirm, render_tervezo_detail,
     render_tervezo_detail_with_prompt,
 };
+pub use toasts::render_toasts;
@@ -5,6 +5,7 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
 use crate::app::NewSessionMenuState;
+use crate::i18n::t;
 
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Length(height)])
@@ -23,8 +24,12 @@ pub fn render_new_session_menu(f: &mut Frame, state: &Option<NewSessionMenuState
     };
 
     let item_count = state.items.len();
-    let popup_height = (item_count as u16) + 4;
-    let popup_width = 28;
+    let hint_lines = if state.hint.is_some() { 2 } else { 0 };
+    let popup_height = (item_count as u16) + 4 + hint_lines;
+    let popup_width = match state.hint {
+        Some(ref hint) => (hint.chars().count() as u16 + 6).clamp(28, area.width),
+        None => 28,
+    };
     let popup_area = centered_rect(popup_width, popup_height, area);
 
     f.render_widget(Clear, popup_area);
@@ -44,14 +49,22 @@ pub fn render_new_session_menu(f: &mut Frame, state: &Option<NewSessionMenuState
         };
         lines.push(Line::from(vec![
             Span::styled(marker.to_string(), style),
-            Span::styled(option.label().to_string(), style),
+            Span::styled(t(option.label()), style),
         ]));
     }
 
+    if let Some(ref hint) = state.hint {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("   {}", hint),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     let border_style = Style::default().fg(Color::Cyan);
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" New Session ")
+            .title(t(state.title))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
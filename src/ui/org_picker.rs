@@ -0,0 +1,84 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::i18n::t;
+use crate::ui::theme::Theme;
+
+/// The Tervezo organizations the API key can act for, the active one
+/// marked.
+pub fn render_org_picker(f: &mut Frame, app: &App, area: Rect) {
+    let Some(state) = app.org_picker.as_ref() else {
+        return;
+    };
+    let active = app.tervezo_config().and_then(|c| c.org.as_deref());
+
+    let mut lines = vec![Line::from("")];
+    if state.loading {
+        lines.push(Line::from(Span::styled(
+            t("   Loading organizations..."),
+            Style::default().fg(Theme::muted()),
+        )));
+    } else if let Some(ref error) = state.error {
+        lines.push(Line::from(Span::styled(
+            format!("   {}", error),
+            Style::default().fg(Theme::bad()),
+        )));
+    } else if state.orgs.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t("   This key has no organizations"),
+            Style::default().fg(Theme::muted()),
+        )));
+    }
+    for (i, org) in state.orgs.iter().enumerate() {
+        let selected = i == state.cursor;
+        let style = if selected {
+            Theme::value().add_modifier(Modifier::BOLD)
+        } else {
+            Theme::value()
+        };
+        let current = if Some(org.id.as_str()) == active {
+            "* "
+        } else {
+            "  "
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { " > " } else { "   " }, style),
+            Span::styled(current, Theme::help_key()),
+            Span::styled(format!("{:<24} ", org.name), style),
+            Span::styled(org.id.clone(), Style::default().fg(Theme::muted())),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        t(" Enter: switch  Esc: close"),
+        Theme::footer(),
+    )));
+
+    let width = lines
+        .iter()
+        .map(|l| l.width() as u16 + 4)
+        .max()
+        .unwrap_or(0)
+        .clamp(40, area.width);
+    let popup = centered_rect(width, lines.len() as u16 + 2, area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(t(" Tervezo organization "))
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
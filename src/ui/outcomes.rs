@@ -0,0 +1,168 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::OutcomePromptState;
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::outcome::{ModelComparison, Outcome, OutcomeStats};
+use crate::ui::theme::Theme;
+
+/// Shortcut keys for the outcomes, in `Outcome::ALL` order.
+const KEYS: [char; 3] = ['s', 'a', 'n'];
+
+/// Asks how a session went: an outcome and an optional 1-5 rating.
+pub fn render_outcome_prompt(f: &mut Frame, state: &OutcomePromptState, area: Rect) {
+    let mut outcomes = vec![Span::raw(" ")];
+    for (outcome, key) in Outcome::ALL.into_iter().zip(KEYS) {
+        let style = if state.outcome == Some(outcome) {
+            Theme::value().add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Theme::value()
+        };
+        outcomes.push(Span::styled(format!("{} ", key), Theme::help_key()));
+        outcomes.push(Span::styled(outcome.as_str(), style));
+        outcomes.push(Span::raw("  "));
+    }
+    let stars = (1..=5u8)
+        .map(|n| {
+            if Some(n) <= state.rating {
+                '★'
+            } else {
+                '☆'
+            }
+        })
+        .collect::<String>();
+    let lines = vec![
+        Line::from(""),
+        Line::from(outcomes),
+        Line::from(vec![
+            Span::styled(t(" rating (1-5): "), Theme::label()),
+            Span::styled(stars, Style::default().fg(Theme::warn())),
+        ]),
+    ];
+
+    let popup = centered_rect(48.min(area.width), lines.len() as u16 + 2, area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(tf(
+            " How did {project} go? ",
+            &[("project", &state.project)],
+        ))
+        .title_bottom(Line::from(t(" Enter:save  Esc:skip ")).centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::title());
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+/// How often rated sessions shipped, per project and model, then the
+/// model families side by side.
+pub fn render_outcomes(
+    f: &mut Frame,
+    stats: &[OutcomeStats],
+    models: &[ModelComparison],
+    area: Rect,
+) {
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            " {:<20} {:<12} {:>6} {:>8} {:>10} {:>6} {:>8} {:>7}",
+            t("Project"),
+            t("Model"),
+            t("Rated"),
+            t("Shipped"),
+            t("Abandoned"),
+            t("Human"),
+            t("Success"),
+            t("Rating"),
+        ),
+        Theme::header(),
+    ))];
+    if stats.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t(" No rated sessions yet. Rate one with R."),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for s in stats {
+        let rate = s.success_rate();
+        let rate_style = if rate >= 0.7 {
+            Style::default().fg(Theme::ok())
+        } else if rate >= 0.4 {
+            Style::default().fg(Theme::warn())
+        } else {
+            Style::default().fg(Theme::bad())
+        };
+        let rating = s
+            .avg_rating
+            .map_or_else(|| "-".to_string(), |r| format!("{:.1}", r));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    " {:<20.20} {:<12.12} {:>6} {:>8} {:>10} {:>6} ",
+                    s.project, s.model, s.sessions, s.shipped, s.abandoned, s.needs_human
+                ),
+                Theme::value(),
+            ),
+            Span::styled(format!("{:>7.0}%", rate * 100.0), rate_style),
+            Span::styled(format!(" {:>7}", rating), Theme::value()),
+        ]));
+    }
+    if !models.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                " {:<12} {:>6} {:>8} {:>10} {:>14} {:>15}",
+                t("Model"),
+                t("Rated"),
+                t("Shipped"),
+                t("Failed"),
+                t("Cost / shipped"),
+                t("Median length"),
+            ),
+            Theme::header(),
+        )));
+    }
+    for m in models {
+        let failed = m.failure_rate();
+        let failed_style = if failed > 0.5 {
+            Style::default().fg(Theme::bad())
+        } else {
+            Theme::value()
+        };
+        let cost = m
+            .cost_per_shipped()
+            .map_or_else(|| "-".to_string(), |c| format::cost(c, 2));
+        let median = m.median_duration.map_or_else(
+            || "-".to_string(),
+            |d| format::elapsed(d.num_seconds().max(0) as u64),
+        );
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {:<12} {:>6} {:>8} ", m.family, m.sessions, m.shipped),
+                Theme::value(),
+            ),
+            Span::styled(format!("{:>9.0}%", failed * 100.0), failed_style),
+            Span::styled(format!(" {:>14}", cost), Theme::cost()),
+            Span::styled(format!(" {:>15}", median), Theme::value()),
+        ]));
+    }
+
+    let block = Block::default()
+        .title(t(" Outcomes "))
+        .title_bottom(Line::from(t(" U/Esc:back ")).centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::border());
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
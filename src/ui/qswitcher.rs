@@ -4,9 +4,9 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 use ratatui::Frame;
 
-use crate::app::{App, SessionEntry};
-use crate::session::SessionStatus;
-use crate::tervezo::ImplementationStatus;
+use crate::app::App;
+use crate::i18n::t;
+use crate::ui::theme::Theme;
 
 const POPUP_WIDTH: u16 = 65;
 const NAME_COL: usize = 20;
@@ -52,41 +52,11 @@ pub fn render_qswitcher(f: &mut Frame, app: &App, area: Rect) {
                 Modifier::empty()
             };
 
-            let (status_fg, status_mod) = match entry {
-                SessionEntry::Local(s) => {
-                    let fg = match s.status {
-                        SessionStatus::Active => Color::Green,
-                        SessionStatus::Idle => Color::Yellow,
-                        SessionStatus::Thinking => Color::Magenta,
-                        SessionStatus::Dead => Color::DarkGray,
-                    };
-                    let m = if matches!(s.status, SessionStatus::Thinking) {
-                        base_mod | Modifier::BOLD
-                    } else {
-                        base_mod
-                    };
-                    (fg, m)
-                }
-                SessionEntry::Remote(imp) => {
-                    let fg = match imp.status {
-                        ImplementationStatus::Running => Color::Green,
-                        ImplementationStatus::Pending | ImplementationStatus::Queued => {
-                            Color::Yellow
-                        }
-                        ImplementationStatus::Completed | ImplementationStatus::Merged => {
-                            Color::Cyan
-                        }
-                        ImplementationStatus::Failed => Color::Red,
-                        ImplementationStatus::Stopped | ImplementationStatus::Cancelled => {
-                            Color::DarkGray
-                        }
-                    };
-                    (fg, base_mod)
-                }
-            };
+            let (status_icon, status_style) = Theme::entry_status(entry);
+            let status_style = status_style.add_modifier(base_mod);
 
             let bell_style = Style::default()
-                .fg(Color::Yellow)
+                .fg(Theme::warn())
                 .add_modifier(Modifier::BOLD | base_mod);
 
             let (marker, marker_style) = if entry.is_remote() {
@@ -138,10 +108,8 @@ pub fn render_qswitcher(f: &mut Frame, app: &App, area: Rect) {
                         .add_modifier(Modifier::BOLD | base_mod),
                 ),
                 Span::styled(truncate(entry.display_name(), NAME_COL), name_style),
-                Span::styled(
-                    truncate(entry.status_label(), STATUS_COL),
-                    Style::default().fg(status_fg).add_modifier(status_mod),
-                ),
+                Span::styled(format!("{} ", status_icon), status_style),
+                Span::styled(truncate(entry.status_label(), STATUS_COL), status_style),
                 Span::styled(
                     branch_display,
                     Style::default().fg(Color::DarkGray).add_modifier(base_mod),
@@ -151,7 +119,7 @@ pub fn render_qswitcher(f: &mut Frame, app: &App, area: Rect) {
         .collect();
 
     let block = Block::default()
-        .title(" Quick Switch [1-9/Enter: attach  Esc: close] ")
+        .title(t(" Quick Switch [1-9/Enter: attach  Esc: close] "))
         .borders(Borders::ALL)
         .border_style(
             Style::default()
@@ -0,0 +1,128 @@
+use ratatui::layout::{Constraint, Flex, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::app::App;
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::ui::theme::Theme;
+
+/// Implementations waiting for the remote agent in the order it will
+/// start them, with when each is expected to.
+pub fn render_queue(f: &mut Frame, app: &App, area: Rect) {
+    let queue = app.queue();
+    let cursor = app.queue_cursor();
+    let now = chrono::Utc::now();
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "   {:>3} {:<9} {:<36} {:<24} {:>10} {:>12}",
+            "#",
+            t("Status"),
+            t("Name"),
+            t("Repository"),
+            t("Waiting"),
+            t("Starts"),
+        ),
+        Theme::header(),
+    ))];
+    for (i, entry) in queue.iter().enumerate() {
+        let imp = &entry.implementation;
+        let selected = i == cursor;
+        let style = if selected {
+            Theme::value().add_modifier(Modifier::BOLD)
+        } else {
+            Theme::value()
+        };
+        let (_, status_style) = Theme::impl_status(&imp.status);
+        let repo = imp
+            .repo_url
+            .as_deref()
+            .map(|r| r.rsplit('/').next().unwrap_or(r))
+            .unwrap_or("-");
+        let starts = match entry.starts_at {
+            Some(at) if at > now => tf(
+                "in {time}",
+                &[("time", &format::elapsed((at - now).num_seconds() as u64))],
+            ),
+            Some(_) => t("any moment").to_string(),
+            None => "-".to_string(),
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { " > " } else { "   " }, style),
+            Span::styled(format!("{:>3} ", i + 1), Theme::help_key()),
+            Span::styled(format!("{:<9.9} ", imp.status.label()), status_style),
+            Span::styled(format!("{:<36.36} ", imp.display_name()), style),
+            Span::styled(
+                format!("{:<24.24} ", repo),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(
+                    "{:>10} ",
+                    imp.created_at.map_or("-".to_string(), format::since)
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(format!("{:>12}", starts), Theme::value()),
+        ]));
+    }
+    if queue.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t("   Nothing waiting: every implementation has started"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    let block = Block::default()
+        .title(tf(
+            " Queue ({count} waiting) ",
+            &[("count", &queue.len().to_string())],
+        ))
+        .title_bottom(
+            Line::from(t(
+                " j/k:select  +:move up  t:to front  x:cancel  Q/Esc:back ",
+            ))
+            .centered(),
+        )
+        .borders(Borders::ALL)
+        .border_style(Theme::border());
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+/// Asks before cancelling a waiting implementation.
+pub fn render_queue_confirm(f: &mut Frame, app: &App, area: Rect) {
+    let Some(name) = app.queue_cancel_pending() else {
+        return;
+    };
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(
+            tf("  Cancel {name}?", &[("name", name)]),
+            Style::default()
+                .fg(Theme::bad())
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+    ];
+    let popup = centered_rect(50.min(area.width), lines.len() as u16 + 2, area);
+    f.render_widget(Clear, popup);
+    let block = Block::default()
+        .title(t(" Cancel implementation "))
+        .title_bottom(Line::from(t(" y/Enter:cancel it  n/Esc:keep ")).centered())
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Theme::bad()));
+    f.render_widget(Paragraph::new(lines).block(block), popup);
+}
+
+fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
+    let vertical = Layout::vertical([Constraint::Length(height)])
+        .flex(Flex::Center)
+        .split(area);
+    let horizontal = Layout::horizontal([Constraint::Length(width)])
+        .flex(Flex::Center)
+        .split(vertical[0]);
+    horizontal[0]
+}
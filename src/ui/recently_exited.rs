@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+use ratatui::Frame;
+
+use crate::app::RecentlyExitedState;
+use crate::format;
+use crate::i18n::t;
+use crate::terminal::ExitedTerminal;
+use crate::ui::theme::Theme;
+
+pub fn render_recently_exited(
+    f: &mut Frame,
+    state: &RecentlyExitedState,
+    exited: &VecDeque<ExitedTerminal>,
+    area: Rect,
+) {
+    let chunks = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(area);
+
+    match state.viewing.and_then(|i| exited.get(i)) {
+        Some(entry) => render_output(f, state, entry, chunks[0]),
+        None => render_picker(f, state, exited, chunks[0]),
+    }
+
+    let footer_text = if state.viewing.is_some() {
+        t(" Esc:back  j/k:scroll  PgUp/PgDn:page  g/G:top/bottom")
+    } else {
+        t(" Esc:back  j/k:move  Enter:view output")
+    };
+    let footer = Paragraph::new(Line::from(Span::styled(footer_text, Theme::footer())));
+    f.render_widget(footer, chunks[1]);
+}
+
+fn render_picker(
+    f: &mut Frame,
+    state: &RecentlyExitedState,
+    exited: &VecDeque<ExitedTerminal>,
+    area: Rect,
+) {
+    let block = Block::default()
+        .title(" Recently Exited ")
+        .title_style(Theme::title())
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let lines: Vec<Line> = exited
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let line = Line::from(vec![
+                Span::styled(
+                    format!(" {} ", format::clock(&entry.closed_at, true)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{:<24} ", entry.project_name),
+                    Theme::tzv_timeline_text(),
+                ),
+                Span::styled(
+                    format!("{:.8}  {} lines", entry.session_id, entry.lines.len()),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]);
+            if i == state.cursor {
+                line.style(Theme::selected())
+            } else {
+                line
+            }
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+}
+
+fn render_output(f: &mut Frame, state: &RecentlyExitedState, entry: &ExitedTerminal, area: Rect) {
+    let block = Block::default()
+        .title(format!(
+            " {} (exited {}) ",
+            entry.project_name,
+            format::clock(&entry.closed_at, false)
+        ))
+        .title_style(Theme::title())
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let visible = inner.height as usize;
+    state.visible_height.set(visible);
+
+    let total = entry.lines.len();
+    let max_scroll = total.saturating_sub(visible);
+    let top = max_scroll.saturating_sub(state.from_bottom);
+    let lines: Vec<Line> = entry.lines[top..total.min(top + visible)]
+        .iter()
+        .map(|l| Line::from(l.as_str()))
+        .collect();
+    f.render_widget(Paragraph::new(lines), inner);
+
+    if total > visible {
+        let mut scrollbar_state = ScrollbarState::new(max_scroll).position(top);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_stateful_widget(scrollbar, inner, &mut scrollbar_state);
+    }
+}
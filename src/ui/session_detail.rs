@@ -1,20 +1,47 @@
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{
+    Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
+};
 use ratatui::Frame;
 
+use crate::app::{DetailDiff, DetailFiles, DetailTab, DetailTranscript, PrStatus, WorktreeClash};
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::plugin::Panel;
 use crate::session::config::{ConfigItem, ConfigItemKind};
-use crate::session::Session;
+use crate::session::files::FileStatus;
+use crate::session::mcp::McpHealth;
+use crate::session::resources::ProcessUsage;
+use crate::session::timeline::{TimelineEvent, TimelineKind};
+use crate::session::{Session, SubagentRollup, TodoItem, TodoStatus};
+use crate::ui::session_timeline::event_style;
 use crate::ui::theme::Theme;
 
+/// The active tab, and what the other tabs have loaded.
+#[derive(Clone, Copy)]
+pub struct DetailTabs<'a> {
+    pub active: DetailTab,
+    pub files: Option<&'a DetailFiles>,
+    pub diff: Option<&'a DetailDiff>,
+    pub transcript: Option<&'a DetailTranscript>,
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn render_session_detail(
     f: &mut Frame,
     session: &Session,
+    rollup: Option<&SubagentRollup>,
     items: &[ConfigItem],
     cursor: usize,
     preview: Option<&(String, String)>,
     preview_scroll: usize,
+    tabs: DetailTabs,
+    pr: Option<&PrStatus>,
+    resources: Option<&ProcessUsage>,
+    clash: Option<&WorktreeClash>,
+    panels: &[Panel],
     area: Rect,
 ) {
     let branch = session.git_branch.as_deref().unwrap_or("");
@@ -24,19 +51,53 @@ pub fn render_session_detail(
         format!(" Session: {} [{}] ", session.project_name, branch)
     };
 
-    if let Some((name, content)) = preview {
-        render_preview_layout(f, session, &title, name, content, preview_scroll, area);
+    let info = Info {
+        pr,
+        resources,
+        clash,
+    };
+    if tabs.active == DetailTab::Files {
+        render_files_layout(f, session, info, &title, tabs.files, area);
+    } else if tabs.active == DetailTab::Diff {
+        render_diff_layout(f, session, info, &title, tabs.diff, area);
+    } else if tabs.active == DetailTab::Transcript {
+        render_transcript_layout(f, &title, tabs.transcript, area);
+    } else if let Some((name, content)) = preview {
+        render_preview_layout(
+            f,
+            session,
+            info,
+            &title,
+            name,
+            content,
+            preview_scroll,
+            area,
+        );
     } else {
-        render_tree_layout(f, session, &title, items, cursor, area);
+        render_tree_layout(
+            f, session, info, rollup, &title, items, cursor, panels, area,
+        );
     }
 }
 
+/// What the info column shows besides the session itself.
+#[derive(Clone, Copy)]
+struct Info<'a> {
+    pr: Option<&'a PrStatus>,
+    resources: Option<&'a ProcessUsage>,
+    clash: Option<&'a WorktreeClash>,
+}
+
+#[allow(clippy::too_many_arguments)]
 fn render_tree_layout(
     f: &mut Frame,
     session: &Session,
+    info: Info,
+    rollup: Option<&SubagentRollup>,
     title: &str,
     items: &[ConfigItem],
     cursor: usize,
+    panels: &[Panel],
     area: Rect,
 ) {
     let chunks = Layout::vertical([
@@ -46,12 +107,7 @@ fn render_tree_layout(
     ])
     .split(area);
 
-    let header = Paragraph::new(Line::from(Span::styled(title.to_string(), Theme::title()))).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .style(Theme::border()),
-    );
-    f.render_widget(header, chunks[0]);
+    render_header(f, title, DetailTab::Overview, chunks[0]);
 
     let columns = Layout::horizontal([
         Constraint::Percentage(30),
@@ -60,8 +116,8 @@ fn render_tree_layout(
     ])
     .split(chunks[1]);
 
-    render_info_column(f, session, columns[0]);
-    render_usage_column(f, session, columns[1]);
+    render_info_and_todos(f, session, info, columns[0]);
+    render_usage_and_panels(f, session, rollup, panels, columns[1]);
     render_config_tree(f, items, cursor, columns[2]);
 
     let always_total: u32 = items
@@ -70,22 +126,33 @@ fn render_tree_layout(
         .filter_map(|i| i.tokens)
         .sum();
 
+    let has_mcp = items.iter().any(|i| i.kind == ConfigItemKind::McpServer);
+    let mcp_hint = if has_mcp { t("  m:check MCP") } else { "" };
+
     let footer_text = if always_total > 0 {
-        format!(
-            " Esc:back  a:attach  f:sessions  Up/Dn:navigate  Enter:preview  ~{}tk always-loaded",
-            format_tokens_short(always_total),
+        tf(
+            " Esc:back  Tab:files  a:attach  f:sessions  t:timeline  Up/Dn:navigate  Enter:preview{mcp}  ~{tokens}tk always-loaded",
+            &[
+                ("mcp", mcp_hint),
+                ("tokens", &format::count(always_total as u64)),
+            ],
         )
     } else {
-        " Esc:back  a:attach  f:sessions  Up/Dn:navigate  Enter:preview file".to_string()
+        tf(
+            " Esc:back  Tab:files  a:attach  f:sessions  t:timeline  Up/Dn:navigate  Enter:preview file{mcp}",
+            &[("mcp", mcp_hint)],
+        )
     };
 
     let footer = Paragraph::new(Line::from(Span::styled(footer_text, Theme::footer())));
     f.render_widget(footer, chunks[2]);
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_preview_layout(
     f: &mut Frame,
     session: &Session,
+    info: Info,
     title: &str,
     filename: &str,
     content: &str,
@@ -99,17 +166,12 @@ fn render_preview_layout(
     ])
     .split(area);
 
-    let header = Paragraph::new(Line::from(Span::styled(title.to_string(), Theme::title()))).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .style(Theme::border()),
-    );
-    f.render_widget(header, chunks[0]);
+    render_header(f, title, DetailTab::Overview, chunks[0]);
 
     let columns = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
         .split(chunks[1]);
 
-    render_info_column(f, session, columns[0]);
+    render_info_and_todos(f, session, info, columns[0]);
 
     let lines: Vec<Line> = content
         .lines()
@@ -148,12 +210,384 @@ fn render_preview_layout(
     f.render_widget(para, columns[1]);
 
     let footer = Paragraph::new(Line::from(Span::styled(
-        " Esc:close preview  Up/Dn:scroll",
+        t(" Esc:close preview  Up/Dn:scroll"),
         Theme::footer(),
     )));
     f.render_widget(footer, chunks[2]);
 }
 
+/// The title with the tabs after it.
+fn render_header(f: &mut Frame, title: &str, active: DetailTab, area: Rect) {
+    let mut spans = vec![Span::styled(title.to_string(), Theme::title())];
+    for &tab in DetailTab::all() {
+        let style = if tab == active {
+            Theme::tzv_tab_active()
+        } else {
+            Theme::tzv_tab_inactive()
+        };
+        spans.push(Span::raw(" "));
+        spans.push(Span::styled(format!(" {} ", t(tab.label())), style));
+    }
+    let header = Paragraph::new(Line::from(spans)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .style(Theme::border()),
+    );
+    f.render_widget(header, area);
+}
+
+/// The Files tab: the files the session changed, and the current content
+/// of the one opened with Enter.
+fn render_files_layout(
+    f: &mut Frame,
+    session: &Session,
+    info: Info,
+    title: &str,
+    files: Option<&DetailFiles>,
+    area: Rect,
+) {
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(10),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    render_header(f, title, DetailTab::Files, chunks[0]);
+
+    let columns = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(chunks[1]);
+    render_info_and_todos(f, session, info, columns[0]);
+
+    let files = files.filter(|files| !files.files.is_empty());
+    let block = Block::default()
+        .title(match files {
+            Some(files) => tf(
+                " Changed files ({n}) ",
+                &[("n", &files.files.len().to_string())],
+            ),
+            None => t(" Changed files ").to_string(),
+        })
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let Some(files) = files else {
+        let empty = Paragraph::new(Line::from(Span::styled(
+            t("  No Edit or Write calls in this session"),
+            Style::default().fg(Color::DarkGray),
+        )))
+        .block(block);
+        f.render_widget(empty, columns[1]);
+        render_footer(f, t(" Esc:back  Tab:diff"), chunks[2]);
+        return;
+    };
+
+    let (list_area, preview_area) = if files.preview.is_some() {
+        let list_height = (files.files.len() as u16 + 2).min(columns[1].height / 3);
+        let split = Layout::vertical([Constraint::Length(list_height), Constraint::Min(3)])
+            .split(columns[1]);
+        (split[0], Some(split[1]))
+    } else {
+        (columns[1], None)
+    };
+
+    let inner = block.inner(list_area);
+    f.render_widget(block, list_area);
+    render_file_list(f, files, inner);
+
+    match (preview_area, files.preview.as_deref(), files.selected()) {
+        (Some(area), Some(content), Some(file)) => {
+            render_file_preview(
+                f,
+                &file.display_path(&files.cwd),
+                content,
+                files.preview_scroll,
+                area,
+            );
+            render_footer(f, t(" Esc:close preview  Up/Dn:scroll"), chunks[2]);
+        }
+        _ => render_footer(
+            f,
+            t(" Esc:back  Tab:diff  Up/Dn:navigate  Enter:preview file"),
+            chunks[2],
+        ),
+    }
+}
+
+/// The Working diff tab: `git diff --stat` above the diff itself.
+fn render_diff_layout(
+    f: &mut Frame,
+    session: &Session,
+    info: Info,
+    title: &str,
+    diff: Option<&DetailDiff>,
+    area: Rect,
+) {
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(10),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    render_header(f, title, DetailTab::Diff, chunks[0]);
+    render_footer(
+        f,
+        t(" Esc:back  Tab:transcript  Up/Dn:scroll  c:commit"),
+        chunks[2],
+    );
+
+    let columns = Layout::horizontal([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .split(chunks[1]);
+    render_info_and_todos(f, session, info, columns[0]);
+
+    let message = |text: String, style: Style| {
+        Paragraph::new(Line::from(Span::styled(text, style))).block(
+            Block::default()
+                .title(t(" Working diff "))
+                .borders(Borders::ALL)
+                .style(Theme::border()),
+        )
+    };
+    let muted = Style::default().fg(Color::DarkGray);
+    let diff = match diff.map(|d| (&d.result, d.scroll)) {
+        Some((Some(Ok(diff)), scroll)) if !diff.is_empty() => (diff, scroll),
+        Some((Some(Ok(_)), _)) => {
+            let text = t("  No uncommitted changes").to_string();
+            f.render_widget(message(text, muted), columns[1]);
+            return;
+        }
+        Some((Some(Err(e)), _)) => {
+            let text = tf("  git diff failed: {error}", &[("error", e)]);
+            f.render_widget(message(text, Style::default().fg(Theme::bad())), columns[1]);
+            return;
+        }
+        _ => {
+            f.render_widget(
+                message(t("  Running git diff…").to_string(), muted),
+                columns[1],
+            );
+            return;
+        }
+    };
+    let (diff, scroll) = diff;
+
+    let stat_lines: Vec<Line> = diff
+        .stat
+        .lines()
+        .map(|l| {
+            Line::from(Span::styled(
+                format!(" {}", l),
+                Style::default().fg(Color::White),
+            ))
+        })
+        .collect();
+    let stat_height = (stat_lines.len() as u16 + 2).min(columns[1].height / 3);
+    let split =
+        Layout::vertical([Constraint::Length(stat_height), Constraint::Min(3)]).split(columns[1]);
+    let stat = Paragraph::new(stat_lines).block(
+        Block::default()
+            .title(t(" Working diff "))
+            .borders(Borders::ALL)
+            .style(Theme::border()),
+    );
+    f.render_widget(stat, split[0]);
+
+    let visible_height = split[1].height.saturating_sub(2) as usize;
+    let total_lines = diff.diff.lines().count();
+    let max_scroll = total_lines.saturating_sub(visible_height);
+    let scroll = scroll.min(max_scroll);
+    let lines: Vec<Line> = diff
+        .diff
+        .lines()
+        .skip(scroll)
+        .take(visible_height)
+        .map(|line| {
+            Line::from(Span::styled(
+                format!(" {}", line),
+                Theme::tzv_diff_line(line),
+            ))
+        })
+        .collect();
+    let indicator = if max_scroll > 0 {
+        format!(" {}/{} ", scroll + 1, max_scroll + 1)
+    } else {
+        String::new()
+    };
+    let block = Block::default()
+        .title(format!(" git diff {}", indicator))
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    f.render_widget(Paragraph::new(lines).block(block), split[1]);
+}
+
+/// The Transcript tab: the whole conversation, full width, one message
+/// after the other.
+fn render_transcript_layout(
+    f: &mut Frame,
+    title: &str,
+    transcript: Option<&DetailTranscript>,
+    area: Rect,
+) {
+    let chunks = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(10),
+        Constraint::Length(1),
+    ])
+    .split(area);
+    render_header(f, title, DetailTab::Transcript, chunks[0]);
+
+    let events = transcript.map_or(&[][..], |t| &t.events);
+    render_footer(
+        f,
+        &tf(
+            " Esc:back  Tab:overview  j/k:scroll  g/G:top/bottom  ({n} messages)",
+            &[("n", &events.len().to_string())],
+        ),
+        chunks[2],
+    );
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let inner = block.inner(chunks[1]);
+    f.render_widget(block, chunks[1]);
+    let Some(transcript) = transcript.filter(|t| !t.events.is_empty()) else {
+        let msg = if transcript.is_some_and(|t| t.loading) {
+            t("  Reading transcript…")
+        } else {
+            t("  No transcript found for this session")
+        };
+        let empty = Paragraph::new(Line::from(Span::styled(msg, Theme::tzv_loading())));
+        f.render_widget(empty, inner);
+        return;
+    };
+
+    let lines = transcript_lines(&transcript.events);
+    let visible = inner.height as usize;
+    let max_scroll = lines.len().saturating_sub(visible);
+    transcript.max_scroll.set(max_scroll);
+    let scroll = transcript.scroll.min(max_scroll);
+    let shown: Vec<Line> = lines.into_iter().skip(scroll).take(visible).collect();
+    f.render_widget(Paragraph::new(shown), inner);
+
+    if max_scroll > 0 {
+        let mut scrollbar_state = ScrollbarState::new(max_scroll).position(scroll);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+            .style(Style::default().fg(Color::DarkGray));
+        f.render_stateful_widget(scrollbar, inner, &mut scrollbar_state);
+    }
+}
+
+/// Each message's first line after its time and icon, the rest indented
+/// under it; a blank line before each prompt.
+fn transcript_lines(events: &[TimelineEvent]) -> Vec<Line<'static>> {
+    let indent = " ".repeat(11);
+    let mut lines = Vec::new();
+    for (i, event) in events.iter().enumerate() {
+        if i > 0 && event.kind == TimelineKind::UserPrompt {
+            lines.push(Line::from(""));
+        }
+        let (icon, icon_style, text_style) = event_style(event);
+        let time = event
+            .timestamp
+            .map(|t| format::clock(&t, true))
+            .unwrap_or_else(|| "        ".to_string());
+        let mut text = event.text.lines();
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+            Span::styled(format!("{} ", icon), icon_style),
+            Span::styled(text.next().unwrap_or("").to_string(), text_style),
+        ]));
+        for rest in text {
+            lines.push(Line::from(Span::styled(
+                format!("{}{}", indent, rest),
+                text_style,
+            )));
+        }
+    }
+    lines
+}
+
+fn render_file_list(f: &mut Frame, files: &DetailFiles, area: Rect) {
+    let visible = area.height as usize;
+    let scroll = (files.cursor + 1).saturating_sub(visible);
+    let lines: Vec<Line> = files
+        .files
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .take(visible)
+        .map(|(i, file)| {
+            let is_selected = i == files.cursor;
+            let row_style = if is_selected {
+                Theme::selected()
+            } else {
+                Style::default()
+            };
+            let status_color = match file.status {
+                FileStatus::Created => Theme::ok(),
+                FileStatus::Modified => Theme::warn(),
+            };
+            let edits = if file.edits == 1 {
+                String::new()
+            } else {
+                format!("  {} edits", file.edits)
+            };
+            Line::from(vec![
+                Span::styled(
+                    format!(" {} ", if is_selected { ">" } else { " " }),
+                    row_style,
+                ),
+                Span::styled(
+                    file.display_path(&files.cwd),
+                    row_style.fg(Color::White).add_modifier(Modifier::BOLD),
+                ),
+                Span::styled(
+                    format!("  {}", file.status.label()),
+                    Style::default().fg(status_color),
+                ),
+                Span::styled(format!(" +{}", file.additions), Theme::tzv_diff_add()),
+                Span::styled(format!(" -{}", file.deletions), Theme::tzv_diff_remove()),
+                Span::styled(edits, Style::default().fg(Color::DarkGray)),
+            ])
+        })
+        .collect();
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+fn render_file_preview(f: &mut Frame, name: &str, content: &str, scroll: usize, area: Rect) {
+    let visible_height = area.height.saturating_sub(2) as usize;
+    let total_lines = content.lines().count();
+    let max_scroll = total_lines.saturating_sub(visible_height);
+    let scroll = scroll.min(max_scroll);
+    let lines: Vec<Line> = content
+        .lines()
+        .skip(scroll)
+        .take(visible_height)
+        .map(|l| {
+            Line::from(Span::styled(
+                l.to_string(),
+                Style::default().fg(Color::White),
+            ))
+        })
+        .collect();
+    let indicator = if max_scroll > 0 {
+        format!(" {}/{} ", scroll + 1, max_scroll + 1)
+    } else {
+        String::new()
+    };
+    let block = Block::default()
+        .title(format!(" {} {}", name, indicator))
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    f.render_widget(Paragraph::new(lines).block(block), area);
+}
+
+fn render_footer(f: &mut Frame, text: &str, area: Rect) {
+    f.render_widget(
+        Paragraph::new(Line::from(Span::styled(text.to_string(), Theme::footer()))),
+        area,
+    );
+}
+
 fn render_config_tree(f: &mut Frame, items: &[ConfigItem], cursor: usize, area: Rect) {
     let visible_height = area.height.saturating_sub(2) as usize;
     if visible_height == 0 {
@@ -170,13 +604,18 @@ fn render_config_tree(f: &mut Frame, items: &[ConfigItem], cursor: usize, area:
         .fg(Color::Cyan)
         .add_modifier(Modifier::BOLD);
     let category = Style::default().fg(Color::White);
-    let exists = Style::default().fg(Color::Green);
+    let exists = Style::default().fg(Theme::ok());
     let missing = Style::default().fg(Color::DarkGray);
     let memory = Style::default().fg(Color::Magenta);
+    let active_plan = Style::default()
+        .fg(Theme::ok())
+        .add_modifier(Modifier::BOLD);
+    let mcp = Style::default().fg(Theme::note());
+    let env_var = Style::default().fg(Color::Gray);
     let total_style = Style::default()
-        .fg(Color::Yellow)
+        .fg(Theme::warn())
         .add_modifier(Modifier::BOLD);
-    let token_style = Style::default().fg(Color::Yellow);
+    let token_style = Style::default().fg(Theme::warn());
     let conditional_style = Style::default().fg(Color::DarkGray);
     let selected_style = Style::default()
         .bg(Color::DarkGray)
@@ -204,13 +643,19 @@ fn render_config_tree(f: &mut Frame, items: &[ConfigItem], cursor: usize, area:
                 )];
                 if let Some(t) = item.tokens {
                     spans.push(Span::styled(
-                        format!("  ~{}tk", format_tokens_short(t)),
+                        format!("  ~{}tk", format::count(t as u64)),
                         selected_style,
                     ));
                 }
                 if item.always_loaded == Some(false) {
                     spans.push(Span::styled(" [cond]", selected_style));
                 }
+                if item.kind == ConfigItemKind::McpServer {
+                    spans.push(Span::styled(
+                        format!("  {}", health_label(item.health.as_ref())),
+                        selected_style,
+                    ));
+                }
                 Line::from(spans)
             } else {
                 let base_style = match item.kind {
@@ -219,18 +664,28 @@ fn render_config_tree(f: &mut Frame, items: &[ConfigItem], cursor: usize, area:
                     ConfigItemKind::FileExists => exists,
                     ConfigItemKind::FileMissing => missing,
                     ConfigItemKind::MemoryFile => memory,
+                    ConfigItemKind::McpServer => mcp,
+                    ConfigItemKind::EnvSection => section,
+                    ConfigItemKind::EnvVar => env_var,
                     ConfigItemKind::SectionTotal => total_style,
+                    ConfigItemKind::ActivePlan => active_plan,
                 };
                 let mut spans = vec![Span::styled(format!("  {}", item.label), base_style)];
                 if let Some(t) = item.tokens {
                     spans.push(Span::styled(
-                        format!("  ~{}tk", format_tokens_short(t)),
+                        format!("  ~{}tk", format::count(t as u64)),
                         token_style,
                     ));
                 }
                 if item.always_loaded == Some(false) {
                     spans.push(Span::styled(" [cond]", conditional_style));
                 }
+                if item.kind == ConfigItemKind::McpServer {
+                    spans.push(Span::styled(
+                        format!("  {}", health_label(item.health.as_ref())),
+                        health_style(item.health.as_ref()),
+                    ));
+                }
                 Line::from(spans)
             }
         })
@@ -253,10 +708,81 @@ fn render_config_tree(f: &mut Frame, items: &[ConfigItem], cursor: usize, area:
     f.render_widget(para, area);
 }
 
-fn render_info_column(f: &mut Frame, session: &Session, area: Rect) {
+fn health_label(health: Option<&McpHealth>) -> String {
+    match health {
+        Some(h) => h.label(),
+        None => "unchecked".to_string(),
+    }
+}
+
+fn health_style(health: Option<&McpHealth>) -> Style {
+    match health {
+        Some(McpHealth::Ok(_)) => Style::default().fg(Theme::ok()),
+        Some(McpHealth::Slow(_)) => Style::default().fg(Theme::warn()),
+        Some(McpHealth::Unreachable(_)) => Style::default().fg(Theme::bad()),
+        Some(McpHealth::Checking) | None => Style::default().fg(Color::DarkGray),
+    }
+}
+
+/// The info column, with the session's todo list below it if it keeps one.
+fn render_info_and_todos(f: &mut Frame, session: &Session, info: Info, area: Rect) {
+    if session.todos.is_empty() {
+        render_info_column(f, session, info, area);
+        return;
+    }
+    let rows = Layout::vertical([Constraint::Length(14), Constraint::Min(3)]).split(area);
+    render_info_column(f, session, info, rows[0]);
+    render_todos(f, &session.todos, rows[1]);
+}
+
+/// Claude's todo list as a checklist: done items dimmed, the one in
+/// progress in its present-tense form.
+fn render_todos(f: &mut Frame, todos: &[TodoItem], area: Rect) {
+    let done = todos
+        .iter()
+        .filter(|t| t.status == TodoStatus::Completed)
+        .count();
+    let lines: Vec<Line> = todos
+        .iter()
+        .map(|todo| match todo.status {
+            TodoStatus::Completed => Line::from(Span::styled(
+                format!(" [x] {}", todo.content),
+                Style::default().fg(Theme::muted()),
+            )),
+            TodoStatus::InProgress => Line::from(Span::styled(
+                format!(
+                    " [>] {}",
+                    todo.active_form.as_deref().unwrap_or(&todo.content)
+                ),
+                Style::default()
+                    .fg(Theme::warn())
+                    .add_modifier(Modifier::BOLD),
+            )),
+            TodoStatus::Pending => Line::from(Span::styled(
+                format!(" [ ] {}", todo.content),
+                Theme::value(),
+            )),
+        })
+        .collect();
+
+    let block = Block::default()
+        .title(format!(" Todos {}/{} ", done, todos.len()))
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let para = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    f.render_widget(para, area);
+}
+
+fn render_info_column(f: &mut Frame, session: &Session, info: Info, area: Rect) {
     let mut lines = vec![
         kv_line("ID", &session.id[..8.min(session.id.len())]),
-        kv_line("CWD", &session.cwd.to_string_lossy()),
+        // Inside the borders, after the label.
+        kv_line(
+            "CWD",
+            &format::path(&session.cwd, area.width.saturating_sub(18) as usize),
+        ),
         kv_line("Project", &session.project_name),
         kv_line("Branch", session.git_branch.as_deref().unwrap_or("-")),
         kv_line("Model", session.model.as_deref().unwrap_or("-")),
@@ -265,13 +791,46 @@ fn render_info_column(f: &mut Frame, session: &Session, area: Rect) {
             "PID",
             &session.pid.map_or("-".to_string(), |p| p.to_string()),
         ),
+    ];
+    if let Some(usage) = info.resources {
+        let cpu = usage
+            .cpu_percent
+            .map_or_else(|| "-".to_string(), |c| format!("{:.0}%", c));
+        lines.push(kv_line(
+            "CPU / Mem",
+            &format!("{} / {}", cpu, format::bytes(usage.rss_bytes)),
+        ));
+    }
+    lines.extend([
         kv_line("Version", session.claude_version.as_deref().unwrap_or("-")),
         kv_line("Perm", session.permission_mode.as_deref().unwrap_or("-")),
-    ];
+    ]);
+
+    if let Some(active) = session.plan_slugs.last() {
+        let plan = match session.plan_slugs.len() {
+            1 => active.clone(),
+            n => format!("{} (+{} earlier)", active, n - 1),
+        };
+        lines.push(kv_line("Plan", &plan));
+    }
+
+    if let Some(pr) = info.pr {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", "PR"), Theme::label()),
+            Span::styled(pr.summary(), Theme::pr(pr)),
+        ]));
+    }
 
-    if !session.plan_slugs.is_empty() {
-        let slugs = session.plan_slugs.join(", ");
-        lines.push(kv_line("Plans", &slugs));
+    if let Some(clash) = info.clash {
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {:<14}", "Worktree"), Theme::label()),
+            Span::styled(
+                clash.summary(),
+                Style::default()
+                    .fg(Theme::warn())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
     }
 
     let block = Block::default()
@@ -282,24 +841,93 @@ fn render_info_column(f: &mut Frame, session: &Session, area: Rect) {
     f.render_widget(para, area);
 }
 
-fn render_usage_column(f: &mut Frame, session: &Session, area: Rect) {
-    let lines = vec![
-        kv_line("In Tokens", &format_number(session.input_tokens)),
-        kv_line("Out Tokens", &format_number(session.output_tokens)),
-        kv_line("Cache Read", &format_number(session.cache_read_tokens)),
-        kv_line("Cache Write", &format_number(session.cache_write_tokens)),
-        kv_line("Messages", &session.message_count.to_string()),
-        kv_line("Tool Calls", &session.tool_call_count.to_string()),
+/// The usage column, with plugin panels stacked below it.
+fn render_usage_and_panels(
+    f: &mut Frame,
+    session: &Session,
+    rollup: Option<&SubagentRollup>,
+    panels: &[Panel],
+    area: Rect,
+) {
+    let constraints = std::iter::once(Constraint::Min(10)).chain(
+        panels
+            .iter()
+            .map(|p| Constraint::Length(p.lines.len() as u16 + 2)),
+    );
+    let rows = Layout::vertical(constraints).split(area);
+    render_usage_column(f, session, rollup, rows[0]);
+    for (panel, area) in panels.iter().zip(rows.iter().skip(1)) {
+        let lines: Vec<Line> = panel
+            .lines
+            .iter()
+            .map(|l| Line::from(Span::styled(format!("  {}", l), Theme::value())))
+            .collect();
+        let block = Block::default()
+            .title(format!(" {} ", panel.title))
+            .borders(Borders::ALL)
+            .style(Theme::border());
+        f.render_widget(Paragraph::new(lines).block(block), *area);
+    }
+}
+
+fn render_usage_column(
+    f: &mut Frame,
+    session: &Session,
+    rollup: Option<&SubagentRollup>,
+    area: Rect,
+) {
+    let mut lines = vec![
+        kv_line("In Tokens", &format::tokens(session.input_tokens)),
+        kv_line("Out Tokens", &format::tokens(session.output_tokens)),
+        kv_line("Cache Read", &format::tokens(session.cache_read_tokens)),
+        kv_line("Cache Write", &format::tokens(session.cache_write_tokens)),
+        kv_line("Messages", &format::integer(session.message_count as u64)),
+        kv_line(
+            "Tool Calls",
+            &format::integer(session.tool_call_count as u64),
+        ),
         cost_line("Cost", session.estimated_cost_usd()),
         kv_line("Compactions", &session.compaction_count.to_string()),
         kv_line(
             "Hooks",
             &format!("{}/{}err", session.hook_run_count, session.hook_error_count),
         ),
-        kv_line("Last Active", &session.last_activity_display()),
+        kv_line("Latency", &latency_display(session)),
+        kv_line(
+            "Errors",
+            &format!(
+                "{} api / {} tool / {} int",
+                session.api_error_count, session.tool_error_count, session.interrupt_count
+            ),
+        ),
+        kv_line("Last Active", &format::since(session.last_activity)),
         kv_line("Duration", &session.duration_display()),
     ];
 
+    if session.parse_errors > 0 || session.partial_line {
+        let partial = if session.partial_line {
+            " + partial last"
+        } else {
+            ""
+        };
+        lines.push(kv_line(
+            "Bad Lines",
+            &format!("{}{} (D)", session.parse_errors, partial),
+        ));
+    }
+
+    if let Some(r) = rollup {
+        lines.push(kv_line(
+            "Subagents",
+            &format!("{} ({} tok)", r.count, format::tokens(r.tokens)),
+        ));
+        lines.push(cost_line("Subagent Cost", r.cost));
+        lines.push(cost_line(
+            "Total Cost",
+            session.estimated_cost_usd() + r.cost,
+        ));
+    }
+
     let block = Block::default()
         .title(" Usage ")
         .borders(Borders::ALL)
@@ -318,24 +946,35 @@ fn kv_line(label: &str, value: &str) -> Line<'static> {
 fn cost_line(label: &str, cost: f64) -> Line<'static> {
     Line::from(vec![
         Span::styled(format!("  {:<14}", label), Theme::label()),
-        Span::styled(format!("${:.4}", cost), Theme::cost()),
+        Span::styled(format::cost(cost, 4), Theme::cost()),
     ])
 }
 
-fn format_number(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
+fn latency_display(session: &Session) -> String {
+    match (session.latency_p50_ms, session.latency_p95_ms) {
+        (Some(p50), Some(p95)) => format!(
+            "p50 {}  p95 {}{}",
+            format_latency(p50),
+            format_latency(p95),
+            sampled_note(session)
+        ),
+        _ => "-".to_string(),
+    }
+}
+
+/// Marks figures taken from only the head and tail of a large transcript.
+fn sampled_note(session: &Session) -> &'static str {
+    if session.stats_sampled {
+        t(" (sampled)")
     } else {
-        n.to_string()
+        ""
     }
 }
 
-fn format_tokens_short(t: u32) -> String {
-    if t >= 1000 {
-        format!("{:.1}K", t as f64 / 1000.0)
+fn format_latency(ms: u32) -> String {
+    if ms >= 1000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
     } else {
-        t.to_string()
+        format!("{}ms", ms)
     }
 }
@@ -1,19 +1,30 @@
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
-use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState};
+use ratatui::widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState};
 use ratatui::Frame;
-
-use crate::app::{App, SessionEntry, ViewMode};
+use std::collections::HashMap;
+
+use crate::app::{App, PathStyle, SessionEntry, ViewMode};
+use crate::format;
+use crate::host::HostStats;
+use crate::i18n::{t, tf};
+use crate::session::resources::ProcessUsage;
+use crate::session::timeline::LastExchange;
 use crate::session::SessionStatus;
-use crate::tervezo::ImplementationStatus;
+use crate::terminal::NotifyLevel;
 use crate::ui::theme::Theme;
 use crate::ui::usage_panel::render_usage_panel;
 
 pub fn render_session_list(f: &mut Frame, app: &App, area: Rect) {
     let show_command_bar = app.is_filtering() || app.attached_session_id().is_some();
 
-    let usage_height = if app.usage().api_available { 12 } else { 6 };
+    let window = app.usage_window();
+    let usage_height = match (app.usage().api_available, &window) {
+        (true, _) => 12,
+        (false, Some(_)) => 9,
+        (false, None) => 6,
+    };
 
     let chunks = if show_command_bar {
         Layout::vertical([
@@ -41,7 +52,14 @@ pub fn render_session_list(f: &mut Frame, app: &App, area: Rect) {
     }
     render_table(f, app, chunks[2]);
     let sessions = app.filtered_sessions();
-    render_usage_panel(f, app.usage(), &sessions, chunks[3]);
+    render_usage_panel(
+        f,
+        app.usage(),
+        &sessions,
+        app.tracked_today(),
+        window,
+        chunks[3],
+    );
     render_footer(f, app, chunks[4]);
 }
 
@@ -72,11 +90,76 @@ fn render_header(f: &mut Frame, app: &App, area: Rect) {
 
     let sort_info = format!(" Sort: {} ", app.sort_label());
 
-    let header = Line::from(vec![
+    let mut spans = vec![
         Span::styled(title, Theme::title()),
         Span::raw("  "),
         Span::styled(sort_info, Theme::footer()),
-    ]);
+    ];
+    if let Some(org) = app.tervezo_org() {
+        spans.push(Span::styled(
+            tf(" [org: {org}] ", &[("org", org)]),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    if let Some(left) = app.dnd_remaining() {
+        spans.push(Span::styled(
+            format!(" [dnd {}m] ", left.as_secs().div_ceil(60)),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(banner) = version_banner(app) {
+        spans.push(Span::styled(banner, Style::default().fg(Theme::warn())));
+    }
+    if app.is_discovering() {
+        spans.push(Span::styled(
+            t(" [scanning sessions...] "),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if let Some(host) = app.host_stats() {
+        spans.extend(host_spans(host));
+    }
+    let hidden = app.hidden_count();
+    if hidden > 0 {
+        let msg = if app.show_hidden() {
+            t(" [{n} hidden shown] ")
+        } else {
+            t(" [{n} hidden] ")
+        };
+        spans.push(Span::styled(
+            crate::i18n::fill(msg, &[("n", &hidden.to_string())]),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let stale = app.stale_count();
+    if stale > 0 {
+        let msg = if app.show_hidden() {
+            t(" [{n} dead over {days}d shown] ")
+        } else {
+            t(" [{n} dead over {days}d] ")
+        };
+        spans.push(Span::styled(
+            crate::i18n::fill(
+                msg,
+                &[
+                    ("n", &stale.to_string()),
+                    ("days", &app.hide_after_days().to_string()),
+                ],
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if app.errors_only() {
+        spans.push(Span::styled(
+            t(" [recent errors] "),
+            Style::default()
+                .fg(Theme::bad())
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    let header = Line::from(spans);
 
     let block = Block::default()
         .borders(Borders::ALL)
@@ -85,6 +168,76 @@ fn render_header(f: &mut Frame, app: &App, area: Rect) {
     f.render_widget(paragraph, area);
 }
 
+/// " load 3.5/8  mem 4.0G free  bat 54% ", each part in the bad color
+/// when it's why the machine is struggling.
+fn host_spans(host: &HostStats) -> Vec<Span<'static>> {
+    let style = |bad: bool| {
+        if bad {
+            Style::default().fg(Theme::bad())
+        } else {
+            Style::default().fg(Color::DarkGray)
+        }
+    };
+    let mut spans = Vec::new();
+    if let Some(load) = host.load {
+        spans.push(Span::styled(
+            tf(
+                " load {load}/{cpus} ",
+                &[
+                    ("load", &format!("{:.1}", load)),
+                    ("cpus", &host.cpus.to_string()),
+                ],
+            ),
+            style(host.overloaded()),
+        ));
+    }
+    if let Some(free) = host.mem_available {
+        spans.push(Span::styled(
+            tf(" mem {free} free ", &[("free", &format::bytes(free))]),
+            style(host.low_memory()),
+        ));
+    }
+    if let Some(battery) = host.battery {
+        // A trailing "+" while on power.
+        let charging = if battery.charging { "+" } else { "" };
+        spans.push(Span::styled(
+            tf(
+                " bat {pct} ",
+                &[("pct", &format!("{}%{}", battery.percent, charging))],
+            ),
+            style(host.low_battery()),
+        ));
+    }
+    spans
+}
+
+fn version_banner(app: &App) -> Option<String> {
+    let versions = app.claude_versions();
+    let newest = versions.newest()?;
+    let outdated = app.outdated_live_count();
+    if outdated > 0 {
+        let msg = if outdated == 1 {
+            t(" {n} live session on outdated claude (latest {latest}) ")
+        } else {
+            t(" {n} live sessions on outdated claude (latest {latest}) ")
+        };
+        Some(crate::i18n::fill(
+            msg,
+            &[("n", &outdated.to_string()), ("latest", newest)],
+        ))
+    } else if versions.installed_outdated() {
+        Some(tf(
+            " claude {latest} available (installed {installed}) ",
+            &[
+                ("latest", newest),
+                ("installed", versions.installed.as_deref().unwrap_or("?")),
+            ],
+        ))
+    } else {
+        None
+    }
+}
+
 fn render_command_bar(f: &mut Frame, app: &App, area: Rect) {
     let mut spans = Vec::new();
 
@@ -116,9 +269,19 @@ fn render_command_bar(f: &mut Frame, app: &App, area: Rect) {
 
         spans.push(Span::styled(" >> ", Theme::attached_marker()));
         spans.push(Span::styled(
-            format!("attached: {}", attached_name),
+            tf("attached: {name}", &[("name", &attached_name)]),
             Theme::attached_bar(),
         ));
+        if let Some(session) = app.all_sessions().iter().find(|s| s.id == sid) {
+            let used: usize = spans.iter().map(|s| s.width()).sum();
+            let room = (area.width as usize).saturating_sub(used + 2);
+            if room >= 8 {
+                spans.push(Span::styled(
+                    format!("  {}", format::path(&session.cwd, room)),
+                    Style::default().fg(Theme::muted()),
+                ));
+            }
+        }
     }
 
     let line = Line::from(spans);
@@ -127,66 +290,110 @@ fn render_command_bar(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_table(f: &mut Frame, app: &App, area: Rect) {
-    let header_cells = [
+    let show_ticket = app.show_ticket_column();
+    let mut header_cells = vec![
         "",
         "CI",
-        "Project",
-        "Branch",
-        "Model",
-        "Status",
-        "Msgs",
-        "Tokens In",
-        "Tokens Out",
-        "Cost",
-        "Last Active",
-    ]
-    .iter()
-    .map(|h| Cell::from(*h).style(Theme::header()));
+        if app.path_style() == PathStyle::Names {
+            t("Project")
+        } else {
+            t("Path")
+        },
+        t("Branch"),
+        t("Model"),
+        t("Status"),
+        t("Msgs"),
+        t("Err"),
+        t("Todo"),
+        t("Tokens In"),
+        t("Tokens Out"),
+        t("Cost"),
+        t("Last Active"),
+    ];
+    if show_ticket {
+        header_cells.insert(TICKET_COLUMN, t("Ticket"));
+    }
+    let show_resources = app.show_resources();
+    if show_resources {
+        header_cells.extend([t("CPU"), t("Mem")]);
+    }
+    let header_cells = header_cells
+        .into_iter()
+        .chain(app.plugins().columns().map(|c| c.header))
+        .map(|h| Cell::from(h).style(Theme::header()));
 
     let header = Row::new(header_cells).height(1);
 
     let bell_style = Style::default()
-        .fg(Color::Yellow)
+        .fg(Theme::warn())
         .add_modifier(Modifier::BOLD);
 
     let sessions = app.filtered_sessions();
-    let rows: Vec<Row> = sessions
+
+    // Build rows only for the visible window (below the borders and
+    // header), scrolled the way a fresh TableState would scroll to the
+    // selection.
+    // The expanded preview takes two extra lines under the selected row.
+    let exchange = if app.row_preview() {
+        app.selected_exchange()
+    } else {
+        None
+    };
+    let extra = if exchange.is_some() { PREVIEW_LINES } else { 0 };
+    let visible = area.height.saturating_sub(3 + extra) as usize;
+    let selected = app.selected_index();
+    let selected_id = sessions.get(selected).map(|e| e.id());
+    let offset = (selected + 1).saturating_sub(visible);
+    let window = sessions.iter().skip(offset).take(visible);
+
+    let mut widths = vec![
+        Constraint::Length(3),
+        Constraint::Length(2),
+        Constraint::Min(20),
+        Constraint::Length(15),
+        Constraint::Length(7),
+        Constraint::Length(10),
+        Constraint::Length(5),
+        Constraint::Length(3),
+        Constraint::Length(4),
+        Constraint::Length(9),
+        Constraint::Length(9),
+        Constraint::Length(8),
+        Constraint::Length(11),
+    ];
+    if show_ticket {
+        widths.insert(TICKET_COLUMN, Constraint::Length(10));
+    }
+    if show_resources {
+        widths.extend([Constraint::Length(5), Constraint::Length(6)]);
+    }
+    widths.extend(app.plugins().columns().map(|c| Constraint::Length(c.width)));
+
+    // Room left for the Project column, for paths shown in its place.
+    let fixed: u16 = widths
         .iter()
+        .map(|c| match c {
+            Constraint::Length(n) => *n,
+            _ => 0,
+        })
+        .sum();
+    let paths = (app.path_style() != PathStyle::Names).then(|| {
+        area.width
+            .saturating_sub(2 + fixed + widths.len() as u16 - 1)
+            .max(20) as usize
+    });
+
+    let mut cache = app.row_cache();
+    cache.prune(sessions.len(), || sessions.iter().map(|e| e.id()));
+
+    let mut rows: Vec<Row> = window
         .map(|entry| {
             let entry_id = entry.id().to_string();
+            let text = cache.get(app, entry, paths);
             let is_attached = app.attached_session_id() == Some(entry_id.as_str());
             let has_bell = app.has_bell(&entry_id);
 
-            let status_style = match entry {
-                SessionEntry::Local(s) => match s.status {
-                    SessionStatus::Active => Theme::status_active(),
-                    SessionStatus::Idle => Theme::status_idle(),
-                    SessionStatus::Thinking => Theme::status_thinking(),
-                    SessionStatus::Dead => Theme::status_dead(),
-                },
-                SessionEntry::Remote(i) => match i.status {
-                    ImplementationStatus::Running => Theme::status_active(),
-                    ImplementationStatus::Pending | ImplementationStatus::Queued => {
-                        Theme::status_idle()
-                    }
-                    ImplementationStatus::Completed | ImplementationStatus::Merged => {
-                        Theme::tzv_status_done()
-                    }
-                    ImplementationStatus::Failed => Theme::tzv_status_failed(),
-                    ImplementationStatus::Stopped | ImplementationStatus::Cancelled => {
-                        Theme::status_dead()
-                    }
-                },
-            };
-
-            let model_short = match entry {
-                SessionEntry::Local(s) => s
-                    .model
-                    .as_deref()
-                    .map(shorten_model)
-                    .unwrap_or("-".to_string()),
-                SessionEntry::Remote(_) => "tervezo".to_string(),
-            };
+            let (status_icon, status_style) = Theme::entry_status(entry);
 
             let (marker, marker_style) = if entry.is_remote() {
                 (
@@ -216,66 +423,107 @@ fn render_table(f: &mut Frame, app: &App, area: Rect) {
                 Style::default()
             };
 
-            let (tokens_in, tokens_out) = match entry {
-                SessionEntry::Local(s) => (
-                    format_tokens(s.input_tokens + s.cache_read_tokens),
-                    format_tokens(s.output_tokens),
-                ),
-                SessionEntry::Remote(_) => ("-".to_string(), "-".to_string()),
-            };
-
-            let cost_str = match entry.estimated_cost() {
-                Some(c) => format!("${:.2}", c),
-                None => "-".to_string(),
-            };
-
-            let msg_str = match entry.message_count() {
-                Some(m) => format_count(m as u64),
-                None => "-".to_string(),
+            // Recency of the last error changes with the clock, not the row.
+            // Recent errors also get a "!" so they don't rely on red alone.
+            let (err_text, err_style) = match entry {
+                SessionEntry::Local(s) if s.error_count() > 0 => {
+                    if entry.has_recent_errors() {
+                        (
+                            format!("!{}", text.errors),
+                            Style::default()
+                                .fg(Theme::bad())
+                                .add_modifier(Modifier::BOLD),
+                        )
+                    } else {
+                        (text.errors.clone(), Style::default().fg(Theme::warn()))
+                    }
+                }
+                _ => (text.errors.clone(), Style::default().fg(Theme::muted())),
             };
 
             let ci_status = app.ci_statuses.get(&entry_id);
             let (ci_symbol, ci_style) = match ci_status {
-                Some(crate::app::CiStatus::Passing) => ("v", Style::default().fg(Color::Green)),
+                Some(crate::app::CiStatus::Passing) => ("v", Style::default().fg(Theme::ok())),
                 Some(crate::app::CiStatus::Failing) => (
                     "x",
-                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Style::default()
+                        .fg(Theme::bad())
+                        .add_modifier(Modifier::BOLD),
                 ),
-                Some(crate::app::CiStatus::Fixing) => ("o", Style::default().fg(Color::Blue)),
-                Some(crate::app::CiStatus::Running) => ("~", Style::default().fg(Color::Yellow)),
-                _ => ("-", Style::default().fg(Color::DarkGray)),
+                Some(crate::app::CiStatus::Fixing) => ("o", Style::default().fg(Theme::note())),
+                Some(crate::app::CiStatus::Running) => ("~", Style::default().fg(Theme::warn())),
+                _ => ("-", Style::default().fg(Theme::muted())),
             };
 
-            let cells = vec![
+            let name = text.name.clone();
+            let mut name_spans = vec![Span::styled(name, name_style)];
+            if let Some(pr) = app.pr_statuses.get(&entry_id) {
+                name_spans.push(Span::styled(format!(" {}", pr.badge()), Theme::pr(pr)));
+            }
+            if let Some(clash) = app.worktree_clash(&entry_id) {
+                name_spans.push(Span::styled(
+                    format!(" [{}]", t(clash.badge())),
+                    Style::default()
+                        .fg(Theme::warn())
+                        .add_modifier(Modifier::BOLD),
+                ));
+            }
+            match app.notify_level(&entry_id) {
+                NotifyLevel::All => {}
+                level => name_spans.push(Span::styled(
+                    format!(" [{}]", level.as_str()),
+                    Style::default().fg(Color::DarkGray),
+                )),
+            }
+            let name_line = Line::from(name_spans);
+
+            let mut cells = vec![
                 Cell::from(marker).style(marker_style),
                 Cell::from(ci_symbol).style(ci_style),
-                Cell::from(entry.display_name().to_string()).style(name_style),
-                Cell::from(entry.branch().unwrap_or("-").to_string()),
-                Cell::from(model_short),
-                Cell::from(entry.status_label()).style(status_style),
-                Cell::from(msg_str),
-                Cell::from(tokens_in),
-                Cell::from(tokens_out),
-                Cell::from(cost_str).style(Theme::cost()),
+                Cell::from(name_line),
+                Cell::from(text.branch.clone()),
+                Cell::from(text.model.clone()),
+                if app.is_hung(&entry_id) {
+                    Cell::from(format!("{} {}", status_icon, t("Hung")))
+                        .style(Style::default().fg(Theme::bad()))
+                } else {
+                    Cell::from(format!("{} {}", status_icon, entry.status_label()))
+                        .style(status_style)
+                },
+                Cell::from(text.messages.clone()),
+                Cell::from(err_text).style(err_style),
+                Cell::from(text.todos.clone()),
+                Cell::from(text.tokens_in.clone()),
+                Cell::from(text.tokens_out.clone()),
+                Cell::from(text.cost.clone()).style(Theme::cost()),
                 Cell::from(entry.last_activity_display()),
             ];
-            Row::new(cells)
+            if show_ticket {
+                let ticket = app.ticket(entry).unwrap_or_else(|| "-".to_string());
+                cells.insert(
+                    TICKET_COLUMN,
+                    Cell::from(ticket).style(Style::default().fg(Theme::note())),
+                );
+            }
+            if show_resources {
+                let (cpu, mem, style) = resource_cells(app.resources(&entry_id));
+                cells.push(Cell::from(cpu).style(style));
+                cells.push(Cell::from(mem).style(style));
+            }
+            cells.extend(app.plugins().cells(entry).into_iter().map(Cell::from));
+            if selected_id == Some(entry.id()) {
+                Row::new(cells).height(1 + extra)
+            } else {
+                Row::new(cells)
+            }
         })
         .collect();
 
-    let widths = [
-        Constraint::Length(3),
-        Constraint::Length(2),
-        Constraint::Min(20),
-        Constraint::Length(15),
-        Constraint::Length(10),
-        Constraint::Length(10),
-        Constraint::Length(6),
-        Constraint::Length(10),
-        Constraint::Length(10),
-        Constraint::Length(8),
-        Constraint::Length(12),
-    ];
+    // Placeholders below what the first scan has found so far.
+    if app.is_discovering() {
+        let room = visible.saturating_sub(rows.len()).min(SKELETON_ROWS);
+        rows.extend((0..room).map(|_| skeleton_row()));
+    }
 
     let table = Table::new(rows, widths)
         .header(header)
@@ -288,8 +536,227 @@ fn render_table(f: &mut Frame, app: &App, area: Rect) {
         .row_highlight_style(Theme::selected());
 
     let mut state = TableState::default();
-    state.select(Some(app.selected_index()));
+    state.select((!sessions.is_empty()).then(|| selected - offset));
     f.render_stateful_widget(table, area, &mut state);
+
+    if let Some(exchange) = exchange {
+        // Below the top border, header and the selected row's own line.
+        let y = area.y + 3 + (selected - offset) as u16;
+        let preview =
+            Rect::new(area.x + 1, y, area.width.saturating_sub(2), extra).intersection(area);
+        render_row_preview(f, &exchange, preview);
+    }
+}
+
+/// Where the Ticket column goes when shown: after Branch.
+const TICKET_COLUMN: usize = 4;
+
+/// Memory past which a session's process tree is shown as a problem.
+const HEAVY_RSS: u64 = 4 << 30;
+
+/// CPU and memory text for the resource columns, red for a tree using
+/// more than a core or `HEAVY_RSS`.
+fn resource_cells(usage: Option<&ProcessUsage>) -> (String, String, Style) {
+    let Some(usage) = usage else {
+        return ("-".to_string(), "-".to_string(), Style::default());
+    };
+    let cpu = usage
+        .cpu_percent
+        .map_or_else(|| "-".to_string(), |c| format!("{:.0}%", c));
+    let heavy = usage.rss_bytes >= HEAVY_RSS || usage.cpu_percent.is_some_and(|c| c > 100.0);
+    let style = if heavy {
+        Style::default().fg(Theme::bad())
+    } else {
+        Style::default()
+    };
+    (cpu, format::bytes(usage.rss_bytes), style)
+}
+
+const PREVIEW_LINES: u16 = 2;
+
+fn render_row_preview(f: &mut Frame, exchange: &LastExchange, area: Rect) {
+    let line = |label: &'static str, text: &Option<String>, style: Style| {
+        Line::from(vec![
+            Span::styled(format!("      {:<8}", t(label)), Theme::label()),
+            Span::styled(text.clone().unwrap_or_else(|| "-".to_string()), style),
+        ])
+    };
+    let lines = vec![
+        line("you", &exchange.prompt, Theme::value()),
+        line("claude", &exchange.reply, Theme::footer()),
+    ];
+    f.render_widget(Clear, area);
+    f.render_widget(Paragraph::new(lines), area);
+}
+
+const SKELETON_ROWS: usize = 3;
+
+/// A grayed-out row standing in for sessions not yet discovered.
+fn skeleton_row() -> Row<'static> {
+    let bar = |n: usize| Cell::from("░".repeat(n));
+    Row::new([0, 2, 18, 12, 6, 8, 3, 1, 1, 5, 5, 5, 8].map(bar))
+        .style(Style::default().fg(Color::DarkGray))
+}
+
+/// Formatted cells of a session row that only change when the session
+/// does. Rebuilding them for hundreds of sessions every frame is
+/// noticeable; this keeps them per session id until the session changes.
+#[derive(Default)]
+pub struct RowCache {
+    rows: HashMap<String, (RowKey, RowText)>,
+}
+
+/// What the cached text is derived from.
+#[derive(PartialEq)]
+struct RowKey {
+    activity: Option<chrono::DateTime<chrono::Utc>>,
+    name: String,
+    branch: Option<String>,
+    messages: Option<u32>,
+    errors: u32,
+    open_todos: Option<usize>,
+    tokens: Option<u64>,
+    cost: Option<u64>,
+    rollup_cost: u64,
+    depth: usize,
+    paths: Option<(PathStyle, usize)>,
+}
+
+#[derive(Clone)]
+struct RowText {
+    name: String,
+    branch: String,
+    model: String,
+    messages: String,
+    errors: String,
+    todos: String,
+    tokens_in: String,
+    tokens_out: String,
+    cost: String,
+}
+
+impl RowCache {
+    fn get(&mut self, app: &App, entry: &SessionEntry, paths: Option<usize>) -> RowText {
+        let id = entry.id();
+        let rollup_cost = app.subagent_rollup(id).map_or(0.0, |r| r.cost);
+        let depth = app.tree_depth(id);
+        let key = RowKey {
+            activity: match entry {
+                SessionEntry::Local(s) => Some(s.last_activity),
+                SessionEntry::Remote(i) => i.updated_at,
+            },
+            name: entry.display_name().to_string(),
+            branch: entry.branch().map(str::to_string),
+            messages: entry.message_count(),
+            errors: entry.as_local().map_or(0, |s| s.error_count()),
+            open_todos: entry
+                .as_local()
+                .filter(|s| !s.todos.is_empty())
+                .map(|s| s.open_todos()),
+            tokens: entry.total_tokens(),
+            cost: entry.estimated_cost().map(f64::to_bits),
+            rollup_cost: rollup_cost.to_bits(),
+            depth,
+            paths: paths.map(|width| (app.path_style(), width)),
+        };
+        if let Some((cached_key, text)) = self.rows.get(id) {
+            if *cached_key == key {
+                return text.clone();
+            }
+        }
+        let text = row_text(entry, rollup_cost, depth, paths);
+        self.rows.insert(id.to_string(), (key, text.clone()));
+        text
+    }
+
+    /// Forgets every row, e.g. when costs are to be shown at a new rate.
+    pub fn clear(&mut self) {
+        self.rows.clear();
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Drops rows of sessions no longer listed, once they outnumber the
+    /// listed ones.
+    fn prune<'a, I: Iterator<Item = &'a str>>(&mut self, listed: usize, ids: impl FnOnce() -> I) {
+        if self.rows.len() > listed * 2 {
+            let keep: std::collections::HashSet<&str> = ids().collect();
+            self.rows.retain(|id, _| keep.contains(id.as_str()));
+        }
+    }
+}
+
+/// `paths` is the column width when paths replace project names.
+fn row_text(entry: &SessionEntry, rollup_cost: f64, depth: usize, paths: Option<usize>) -> RowText {
+    let model = match entry {
+        SessionEntry::Local(s) => s
+            .model
+            .as_deref()
+            .map(shorten_model)
+            .unwrap_or("-".to_string()),
+        SessionEntry::Remote(_) => "tervezo".to_string(),
+    };
+
+    let (tokens_in, tokens_out) = match entry {
+        SessionEntry::Local(s) => (
+            format::tokens(s.input_tokens + s.cache_read_tokens),
+            format::tokens(s.output_tokens),
+        ),
+        SessionEntry::Remote(_) => ("-".to_string(), "-".to_string()),
+    };
+
+    // Parents show their own cost plus everything their subagents spent.
+    let cost = match entry.estimated_cost() {
+        Some(c) => format::cost(c + rollup_cost, 2),
+        None => "-".to_string(),
+    };
+
+    let messages = match entry.message_count() {
+        Some(m) => format::count(m as u64),
+        None => "-".to_string(),
+    };
+
+    let errors = match entry {
+        SessionEntry::Local(s) if s.error_count() > 0 => format::count(s.error_count() as u64),
+        _ => "-".to_string(),
+    };
+
+    // Open items of the session's todo list; "-" when it keeps none.
+    let todos = match entry {
+        SessionEntry::Local(s) if !s.todos.is_empty() => format::count(s.open_todos() as u64),
+        _ => "-".to_string(),
+    };
+
+    let base_name = match entry {
+        SessionEntry::Local(s) if s.is_subagent => {
+            format!("agent {}", s.id.trim_start_matches("agent-"))
+        }
+        SessionEntry::Local(s) => match paths {
+            Some(width) => format::path(&s.cwd, width.saturating_sub(depth * 2)),
+            None => s.project_name.clone(),
+        },
+        _ => entry.display_name().to_string(),
+    };
+    let name = if depth > 0 {
+        format!("{}└ {}", "  ".repeat(depth - 1), base_name)
+    } else {
+        base_name
+    };
+
+    RowText {
+        name,
+        branch: entry.branch().unwrap_or("-").to_string(),
+        model,
+        messages,
+        errors,
+        todos,
+        tokens_in,
+        tokens_out,
+        cost,
+    }
 }
 
 fn render_footer(f: &mut Frame, app: &App, area: Rect) {
@@ -300,16 +767,19 @@ fn render_footer(f: &mut Frame, app: &App, area: Rect) {
     let version = env!("CARGO_PKG_VERSION");
 
     let stats = format!(
-        " c9s {} | ${:.2} | {} tokens",
+        " c9s {} | {} | {}",
         version,
-        total_cost,
-        format_tokens(total_tokens)
+        format::cost(total_cost, 2),
+        tf(
+            "{tokens} tokens",
+            &[("tokens", &format::tokens(total_tokens))]
+        )
     );
 
     let keys = if app.has_tervezo() {
-        "  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  c:fix-ci  /:filter  s:sort  ?:help"
+        t("  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  c:fix-ci  /:filter  s:sort  ?:help")
     } else {
-        "  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  /:filter  s:sort  ?:help"
+        t("  a:attach  C-b:resume  d:detail  x:kill  u:unfollow  n:new  /:filter  s:sort  ?:help")
     };
 
     let footer = Line::from(vec![
@@ -332,21 +802,3 @@ fn shorten_model(model: &str) -> String {
         model.split('-').next_back().unwrap_or(model).to_string()
     }
 }
-
-fn format_tokens(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
-    } else {
-        n.to_string()
-    }
-}
-
-fn format_count(n: u64) -> String {
-    if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
-    } else {
-        n.to_string()
-    }
-}
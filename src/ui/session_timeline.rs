@@ -0,0 +1,169 @@
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{
+    Block, Borders, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
+};
+use ratatui::Frame;
+
+use crate::app::LocalTimelineState;
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::session::timeline::{TimelineEvent, TimelineKind};
+use crate::store::Bookmark;
+use crate::ui::theme::Theme;
+
+pub fn render_session_timeline(f: &mut Frame, state: &LocalTimelineState, area: Rect) {
+    let chunks = Layout::vertical([Constraint::Min(3), Constraint::Length(1)]).split(area);
+
+    let block = Block::default()
+        .title(state.title.clone())
+        .title_style(Theme::title())
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    let inner = block.inner(chunks[0]);
+    f.render_widget(block, chunks[0]);
+
+    let visible = inner.height as usize;
+    state.visible_height.set(visible);
+
+    let rows = state.row_count();
+    if rows == 0 {
+        let msg = if state.bookmarks_only {
+            "  No bookmarks yet — press b on an event to add one"
+        } else if state.loading {
+            t("  Reading transcript…")
+        } else {
+            "  No events in transcript"
+        };
+        let empty = Paragraph::new(Line::from(Span::styled(msg, Theme::tzv_loading())));
+        f.render_widget(empty, inner);
+    } else {
+        let max_scroll = rows.saturating_sub(visible);
+        let scroll = state.scroll.min(max_scroll);
+
+        let lines: Vec<Line> = (scroll..rows.min(scroll + visible))
+            .map(|row| {
+                let line = if state.bookmarks_only {
+                    bookmark_line(&state.bookmarks[row])
+                } else {
+                    let event = &state.events[row];
+                    event_line(event, state.is_bookmarked(event.index))
+                };
+                if row == state.cursor {
+                    line.style(Theme::selected())
+                } else {
+                    line
+                }
+            })
+            .collect();
+        f.render_widget(Paragraph::new(lines), inner);
+
+        if rows > visible {
+            let mut scrollbar_state = ScrollbarState::new(max_scroll).position(scroll);
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .style(Style::default().fg(Color::DarkGray));
+            f.render_stateful_widget(scrollbar, inner, &mut scrollbar_state);
+        }
+    }
+
+    let footer_text = if state.bookmarks_only {
+        tf(
+            " B:all events  Enter:jump  b:remove  j/k:move  ({n} bookmarks)",
+            &[("n", &state.bookmarks.len().to_string())],
+        )
+    } else {
+        tf(
+            " Esc:back  j/k:move  g/G:top/bottom  b:bookmark  n/N:next/prev mark  B:bookmarks  e/y:export/copy  r:reload  ({n} events)",
+            &[("n", &state.events.len().to_string())],
+        )
+    };
+    let footer = Paragraph::new(Line::from(Span::styled(footer_text, Theme::footer())));
+    f.render_widget(footer, chunks[1]);
+}
+
+fn bookmark_line(bookmark: &Bookmark) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(
+            format!(" {} ", bookmark.created_at),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled("★ ", Style::default().fg(Theme::warn())),
+        Span::styled(
+            format!("#{:<6}", bookmark.message_index),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(bookmark.label.clone(), Theme::tzv_timeline_text()),
+    ])
+}
+
+fn event_line(event: &TimelineEvent, bookmarked: bool) -> Line<'static> {
+    let (icon, icon_style, text_style) = event_style(event);
+    let time = event
+        .timestamp
+        .map(|t| format::clock(&t, true))
+        .unwrap_or_else(|| "        ".to_string());
+
+    let mark = if bookmarked {
+        Span::styled("★", Style::default().fg(Theme::warn()))
+    } else {
+        Span::raw(" ")
+    };
+
+    Line::from(vec![
+        mark,
+        Span::styled(format!("{} ", time), Style::default().fg(Color::DarkGray)),
+        Span::styled(format!("{} ", icon), icon_style),
+        Span::styled(event.text.clone(), text_style),
+    ])
+}
+
+/// The event's icon, the icon's style and the text's.
+pub(crate) fn event_style(event: &TimelineEvent) -> (&'static str, Style, Style) {
+    match event.kind {
+        TimelineKind::UserPrompt => (
+            "›",
+            Style::default().fg(Theme::ok()),
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        ),
+        TimelineKind::Assistant => (
+            "▸",
+            Style::default().fg(Color::Magenta),
+            Theme::tzv_timeline_text(),
+        ),
+        TimelineKind::ToolCall => {
+            let tool = event.text.split_whitespace().next().unwrap_or("");
+            let icon = match tool {
+                "Read" => "◇",
+                "Write" | "Edit" | "MultiEdit" => "◆",
+                "Bash" => "$",
+                "Grep" | "Glob" => "⌕",
+                _ => "⚙",
+            };
+            (
+                icon,
+                Style::default().fg(Theme::note()),
+                Style::default().fg(Color::DarkGray),
+            )
+        }
+        TimelineKind::ToolError => (
+            "✗",
+            Style::default().fg(Theme::bad()),
+            Style::default().fg(Theme::bad()),
+        ),
+        TimelineKind::Compaction => (
+            "─",
+            Style::default().fg(Theme::warn()),
+            Style::default()
+                .fg(Theme::warn())
+                .add_modifier(Modifier::DIM),
+        ),
+        TimelineKind::Hook => (
+            "↪",
+            Style::default().fg(Color::Cyan),
+            Style::default().fg(Color::DarkGray),
+        ),
+    }
+}
@@ -49,5 +49,5 @@ pub fn render_side_panel(f: &mut Frame, terminal: &EmbeddedTerminal, focused: bo
 
     let guard = terminal.lock_parser();
     let screen = guard.screen();
-    render_screen(f, screen, chunks[1]);
+    render_screen(f, screen, &mut terminal.lock_render_cache(), chunks[1]);
 }
@@ -0,0 +1,683 @@
+//! Renders each main view into a `TestBackend` with synthetic data and
+//! compares the text against the snapshots in `snapshots/`. After an
+//! intended UI change, review and accept the new output with
+//! `cargo insta review`.
+
+use chrono::{Duration, Utc};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::Terminal;
+use std::path::PathBuf;
+
+use crate::app::{
+    App, ChainLink, CiStatus, CopyMode, CostStats, DetailDiff, DetailFiles, DetailTab,
+    DetailTranscript, PrReview, PrStatus, TervezoDetailState, WorktreeClash,
+};
+use crate::outcome::{Outcome, OutcomeStats, RatedSession};
+use crate::plugin::tests::Stub;
+use crate::plugin::Plugins;
+use crate::session::config::{ConfigItem, ConfigItemKind};
+use crate::session::files::{FileStatus, FileTouch};
+use crate::session::git::WorkingDiff;
+use crate::session::timeline::{TimelineEvent, TimelineKind};
+use crate::session::{Session, SessionStatus, SubagentRollup, TodoItem, TodoStatus};
+use crate::store::CostTotals;
+use crate::terminal::{Point, RenderCache, TabEntry};
+use crate::tervezo::{Implementation, ImplementationStatus};
+use crate::ui::DetailTabs;
+use crate::usage::UsageData;
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 36;
+
+/// The buffer as plain text, one line per row, without trailing blanks.
+fn text(buffer: &Buffer) -> String {
+    let width = buffer.area.width as usize;
+    buffer
+        .content
+        .chunks(width)
+        .map(|row| {
+            let line: String = row.iter().map(|c| c.symbol()).collect();
+            line.trim_end().to_string()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn render(draw: impl FnOnce(&mut ratatui::Frame)) -> String {
+    render_sized(WIDTH, HEIGHT, draw)
+}
+
+fn render_sized(width: u16, height: u16, draw: impl FnOnce(&mut ratatui::Frame)) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(draw).unwrap();
+    text(terminal.backend().buffer())
+}
+
+/// Times are relative to now so the "ago" columns stay stable.
+fn session(id: &str, project: &str, status: SessionStatus, minutes_ago: i64) -> Session {
+    let last_activity = Utc::now() - Duration::minutes(minutes_ago);
+    Session {
+        id: id.to_string(),
+        pid: (status != SessionStatus::Dead).then_some(4242),
+        cwd: PathBuf::from(format!("/home/dev/{}", project)),
+        project_name: project.to_string(),
+        git_branch: Some(format!("feature/{}", project)),
+        model: Some("claude-sonnet-4-20250514".to_string()),
+        status,
+        started_at: last_activity - Duration::minutes(95),
+        last_activity,
+        input_tokens: 182_400,
+        output_tokens: 23_150,
+        cache_read_tokens: 1_250_000,
+        cache_write_tokens: 48_000,
+        message_count: 64,
+        tool_call_count: 41,
+        claude_version: Some("2.0.14".to_string()),
+        permission_mode: Some("default".to_string()),
+        plan_slugs: Vec::new(),
+        compaction_count: 1,
+        hook_run_count: 12,
+        hook_error_count: 0,
+        parent_id: None,
+        is_subagent: false,
+        latency_p50_ms: Some(2100),
+        latency_p95_ms: Some(7400),
+        stats_sampled: false,
+        api_error_count: 0,
+        interrupt_count: 1,
+        tool_error_count: 2,
+        last_error_at: None,
+        parse_errors: 0,
+        partial_line: false,
+        todos: Vec::new(),
+        window_tokens: Vec::new(),
+        imported: false,
+    }
+}
+
+fn sessions() -> Vec<Session> {
+    vec![
+        session("a1b2c3d4", "c9s", SessionStatus::Thinking, 1),
+        session("e5f6a7b8", "webshop", SessionStatus::Idle, 12),
+        session("c9d0e1f2", "infra", SessionStatus::Active, 3),
+        session("a3b4c5d6", "notes", SessionStatus::Dead, 180),
+    ]
+}
+
+fn item(label: &str, kind: ConfigItemKind, tokens: Option<u32>) -> ConfigItem {
+    ConfigItem {
+        label: label.to_string(),
+        path: None,
+        kind,
+        tokens,
+        always_loaded: None,
+        health: None,
+    }
+}
+
+#[test]
+fn test_session_list() {
+    let app = App::with_sessions(sessions());
+    let out = render(|f| crate::ui::render_session_list(f, &app, f.area()));
+    insta::assert_snapshot!("session_list", out);
+}
+
+#[test]
+fn test_dashboard() {
+    let mut app = App::with_sessions(sessions());
+    app.toast(crate::toast::Severity::Error, "CI failing on c9s (main)");
+    let out = render_sized(100, 24, |f| crate::ui::render_dashboard(f, &app, f.area()));
+    insta::assert_snapshot!("dashboard", out);
+}
+
+#[test]
+fn test_session_list_row_preview() {
+    let mut app = App::with_sessions(sessions());
+    app.toggle_row_preview();
+    let out = render(|f| crate::ui::render_session_list(f, &app, f.area()));
+    insta::assert_snapshot!("session_list_row_preview", out);
+}
+
+#[test]
+fn test_session_list_plugin_column() {
+    let mut app = App::with_sessions(sessions());
+    app.set_plugins(Plugins::new(vec![Box::new(Stub)]));
+    // Room for the extra column next to a full-width project.
+    let out = render_sized(WIDTH + 6, HEIGHT, |f| {
+        crate::ui::render_session_list(f, &app, f.area())
+    });
+    insta::assert_snapshot!("session_list_plugin_column", out);
+}
+
+#[test]
+fn test_session_list_pr_badge() {
+    let mut app = App::with_sessions(sessions());
+    app.pr_statuses.insert(
+        "a1b2c3d4".to_string(),
+        PrStatus {
+            number: 42,
+            draft: false,
+            review: PrReview::Approved,
+            checks: CiStatus::Passing,
+            url: String::new(),
+        },
+    );
+    let out = render(|f| crate::ui::render_session_list(f, &app, f.area()));
+    assert!(out.contains("c9s #42✓"), "{}", out);
+}
+
+#[test]
+fn test_session_list_resource_columns() {
+    let mut app = App::with_sessions(sessions());
+    app.toggle_resources();
+    app.set_resources(
+        "a1b2c3d4",
+        crate::session::resources::ProcessUsage {
+            cpu_percent: Some(12.4),
+            rss_bytes: 8_700_000_000,
+        },
+    );
+    let out = render_sized(WIDTH + 12, HEIGHT, |f| {
+        crate::ui::render_session_list(f, &app, f.area())
+    });
+    assert!(out.contains("CPU   Mem"), "{}", out);
+    assert!(out.contains("12%   8.1G"), "{}", out);
+}
+
+#[test]
+fn test_session_list_host_summary() {
+    let mut app = App::with_sessions(sessions());
+    app.set_host_stats(crate::host::HostStats {
+        load: Some(3.46),
+        cpus: 8,
+        mem_available: Some(4 << 30),
+        mem_total: Some(16 << 30),
+        battery: Some(crate::host::Battery {
+            percent: 54,
+            charging: false,
+        }),
+    });
+    let out = render_sized(WIDTH + 40, HEIGHT, |f| {
+        crate::ui::render_session_list(f, &app, f.area())
+    });
+    assert!(
+        out.contains(" load 3.5/8  mem 4.0G free  bat 54% "),
+        "{}",
+        out
+    );
+}
+
+#[test]
+fn test_session_list_ticket_column() {
+    let out =
+        render(|f| crate::ui::render_session_list(f, &App::with_sessions(sessions()), f.area()));
+    assert!(!out.contains("Ticket"), "{}", out);
+
+    let mut with_ticket = sessions();
+    with_ticket[0].git_branch = Some("feat/ABC-123-login".to_string());
+    let app = App::with_sessions(with_ticket);
+    let out = render(|f| crate::ui::render_session_list(f, &app, f.area()));
+    assert!(out.contains("Ticket"), "{}", out);
+    assert!(out.contains("ABC-123"), "{}", out);
+}
+
+#[test]
+fn test_session_list_large() {
+    let many: Vec<Session> = (0..600)
+        .map(|i| {
+            session(
+                &format!("s{:03}", i),
+                &format!("proj-{:03}", i),
+                SessionStatus::Idle,
+                i,
+            )
+        })
+        .collect();
+    let mut app = App::with_sessions(many);
+    app.set_selected(590);
+    let out = render(|f| crate::ui::render_session_list(f, &app, f.area()));
+    assert!(out.contains("proj-590"));
+    assert!(!out.contains("proj-000"));
+    // Only the rows on screen were formatted.
+    assert!(app.row_cache().len() < 40);
+}
+
+#[test]
+fn test_action_menu() {
+    let mut app = App::with_sessions(sessions());
+    let action = |name: &str, command: &str| crate::config::UserAction {
+        name: name.to_string(),
+        command: command.to_string(),
+    };
+    app.set_user_actions(vec![
+        action("PR", "gh pr view --web {branch}"),
+        action("Tests", "make test"),
+    ]);
+    app.open_action_menu();
+    let (label, command, _) = app.user_action_command(0).unwrap();
+    assert_eq!(label, "PR: c9s");
+    assert_eq!(command, "gh pr view --web feature/c9s");
+    let out = render_sized(60, 9, |f| crate::ui::render_action_menu(f, &app, f.area()));
+    insta::assert_snapshot!("action_menu", out);
+}
+
+#[test]
+fn test_outcomes() {
+    let stats = |project: &str, model: &str, shipped, abandoned, rating| OutcomeStats {
+        project: project.to_string(),
+        model: model.to_string(),
+        sessions: shipped + abandoned,
+        shipped,
+        abandoned,
+        needs_human: 0,
+        avg_rating: rating,
+    };
+    let rows = [
+        stats("c9s", "opus", 8, 2, Some(4.2)),
+        stats("api", "sonnet", 1, 3, None),
+    ];
+    let models = crate::outcome::compare_models(&[
+        RatedSession {
+            model: "claude-opus-4-1".to_string(),
+            outcome: Outcome::Shipped,
+            cost_usd: 4.0,
+            duration: Some(chrono::Duration::minutes(95)),
+        },
+        RatedSession {
+            model: "claude-sonnet-4-5".to_string(),
+            outcome: Outcome::Abandoned,
+            cost_usd: 1.0,
+            duration: None,
+        },
+    ]);
+    let out = render_sized(90, 10, |f| {
+        crate::ui::render_outcomes(f, &rows, &models, f.area())
+    });
+    insta::assert_snapshot!("outcomes", out);
+}
+
+#[test]
+fn test_stats() {
+    let totals = |key: &str, sessions, tokens, cost_usd| CostTotals {
+        key: key.to_string(),
+        sessions,
+        input_tokens: tokens,
+        output_tokens: tokens / 10,
+        cost_usd,
+    };
+    let mut stats = CostStats {
+        since: chrono::NaiveDate::from_ymd_opt(2026, 3, 1).unwrap(),
+        days: vec![
+            totals("2026-03-02", 2, 400_000, 3.5),
+            totals("2026-03-10", 5, 1_200_000, 12.0),
+            totals("2026-03-11", 1, 90_000, 0.8),
+            totals("2026-03-30", 3, 600_000, 6.25),
+        ],
+        projects: vec![
+            totals("c9s", 6, 1_500_000, 15.3),
+            totals("webshop", 4, 700_000, 6.45),
+            totals("api", 1, 90_000, 0.8),
+        ],
+        tokens: false,
+    };
+    let out = render_sized(100, 24, |f| crate::ui::render_stats(f, &stats, f.area()));
+    insta::assert_snapshot!("stats", out);
+
+    stats.tokens = true;
+    let out = render_sized(100, 24, |f| crate::ui::render_stats(f, &stats, f.area()));
+    insta::assert_snapshot!("stats_tokens", out);
+}
+
+#[test]
+fn test_qswitcher() {
+    let app = App::with_sessions(sessions());
+    // Just big enough for the popup, which is centered.
+    let out = render_sized(67, 8, |f| crate::ui::render_qswitcher(f, &app, f.area()));
+    insta::assert_snapshot!("qswitcher", out);
+}
+
+#[test]
+fn test_session_detail() {
+    let mut session = session("a1b2c3d4", "c9s", SessionStatus::Active, 2);
+    let todo = |content: &str, status, active: &str| TodoItem {
+        content: content.to_string(),
+        status,
+        active_form: Some(active.to_string()),
+    };
+    session.todos = vec![
+        todo(
+            "Read the parser",
+            TodoStatus::Completed,
+            "Reading the parser",
+        ),
+        todo("Fix the bug", TodoStatus::InProgress, "Fixing the bug"),
+        todo("Add a test", TodoStatus::Pending, "Adding a test"),
+    ];
+    let rollup = SubagentRollup {
+        count: 2,
+        tokens: 54_000,
+        cost: 0.42,
+    };
+    let clash = WorktreeClash {
+        others: vec!["c9s".to_string()],
+        same_branch: true,
+    };
+    let items = vec![
+        item("Project", ConfigItemKind::SectionHeader, None),
+        item("CLAUDE.md", ConfigItemKind::FileExists, Some(1800)),
+        item("rules/", ConfigItemKind::Category, Some(3200)),
+        item(".claude/settings.json", ConfigItemKind::FileMissing, None),
+        item("MCP Servers", ConfigItemKind::SectionHeader, None),
+        item("github", ConfigItemKind::McpServer, None),
+    ];
+    let out = render(|f| {
+        crate::ui::render_session_detail(
+            f,
+            &session,
+            Some(&rollup),
+            &items,
+            1,
+            None,
+            0,
+            DetailTabs {
+                active: DetailTab::Overview,
+                files: None,
+                diff: None,
+                transcript: None,
+            },
+            None,
+            None,
+            Some(&clash),
+            &[],
+            f.area(),
+        )
+    });
+    insta::assert_snapshot!("session_detail", out);
+}
+
+#[test]
+fn test_session_detail_files() {
+    let session = session("a1b2c3d4", "c9s", SessionStatus::Dead, 30);
+    let touch = |path: &str, status, additions, deletions, edits| FileTouch {
+        path: PathBuf::from(path),
+        status,
+        additions,
+        deletions,
+        edits,
+        last_touched: None,
+    };
+    let mut files = DetailFiles {
+        cwd: session.cwd.clone(),
+        files: vec![
+            touch("/home/dev/c9s/src/app.rs", FileStatus::Modified, 42, 7, 3),
+            touch("/home/dev/c9s/src/files.rs", FileStatus::Created, 120, 0, 1),
+            touch("/tmp/notes.md", FileStatus::Modified, 2, 1, 1),
+        ],
+        cursor: 1,
+        preview: None,
+        preview_scroll: 0,
+    };
+    let draw = |files: &DetailFiles| {
+        render(|f| {
+            crate::ui::render_session_detail(
+                f,
+                &session,
+                None,
+                &[],
+                0,
+                None,
+                0,
+                DetailTabs {
+                    active: DetailTab::Files,
+                    files: Some(files),
+                    diff: None,
+                    transcript: None,
+                },
+                None,
+                None,
+                None,
+                &[],
+                f.area(),
+            )
+        })
+    };
+    insta::assert_snapshot!("session_detail_files", draw(&files));
+
+    files.preview = Some("//! Files a session changed.\n\npub fn files() {}\n".to_string());
+    insta::assert_snapshot!("session_detail_files_preview", draw(&files));
+}
+
+#[test]
+fn test_session_detail_diff() {
+    let session = session("a1b2c3d4", "c9s", SessionStatus::Idle, 3);
+    let diff = DetailDiff {
+        cwd: session.cwd.clone(),
+        result: Some(Ok(WorkingDiff {
+            stat: " src/app.rs | 3 ++-\n 1 file changed, 2 insertions(+), 1 deletion(-)\n"
+                .to_string(),
+            diff: "diff --git a/src/app.rs b/src/app.rs\n--- a/src/app.rs\n+++ b/src/app.rs\n\
+                   @@ -1,3 +1,4 @@\n use std::io;\n-fn old() {}\n+fn new() {}\n+fn newer() {}\n"
+                .to_string(),
+        })),
+        scroll: 0,
+    };
+    let out = render(|f| {
+        crate::ui::render_session_detail(
+            f,
+            &session,
+            None,
+            &[],
+            0,
+            None,
+            0,
+            DetailTabs {
+                active: DetailTab::Diff,
+                files: None,
+                diff: Some(&diff),
+                transcript: None,
+            },
+            None,
+            None,
+            None,
+            &[],
+            f.area(),
+        )
+    });
+    insta::assert_snapshot!("session_detail_diff", out);
+}
+
+#[test]
+fn test_session_detail_transcript() {
+    let session = session("a1b2c3d4", "c9s", SessionStatus::Dead, 30);
+    let event = |index, kind, text: &str| TimelineEvent {
+        index,
+        timestamp: None,
+        kind,
+        text: text.to_string(),
+    };
+    let transcript = DetailTranscript {
+        session_id: "a1b2c3d4".into(),
+        loading: false,
+        events: vec![
+            event(
+                0,
+                TimelineKind::UserPrompt,
+                "the build fails on CI\nsee the log",
+            ),
+            event(
+                1,
+                TimelineKind::Assistant,
+                "Found it.\n\nThe lockfile is stale.",
+            ),
+            event(1, TimelineKind::ToolCall, "Bash cargo update"),
+            event(
+                2,
+                TimelineKind::ToolError,
+                "Tool failed: network unreachable",
+            ),
+            event(3, TimelineKind::UserPrompt, "try offline"),
+        ],
+        scroll: 0,
+        max_scroll: std::cell::Cell::new(0),
+    };
+    let out = render(|f| {
+        crate::ui::render_session_detail(
+            f,
+            &session,
+            None,
+            &[],
+            0,
+            None,
+            0,
+            DetailTabs {
+                active: DetailTab::Transcript,
+                files: None,
+                diff: None,
+                transcript: Some(&transcript),
+            },
+            None,
+            None,
+            None,
+            &[],
+            f.area(),
+        )
+    });
+    insta::assert_snapshot!("session_detail_transcript", out);
+}
+
+fn webhook_impl() -> Implementation {
+    let updated = Utc::now() - Duration::minutes(4);
+    Implementation {
+        id: "impl_7f3a".to_string(),
+        title: Some("Add retry to the webhook sender".to_string()),
+        status: ImplementationStatus::Running,
+        branch: Some("tervezo/webhook-retry".to_string()),
+        repo_url: Some("https://github.com/example/webshop".to_string()),
+        created_at: Some(updated - Duration::minutes(30)),
+        updated_at: Some(updated),
+        estimated_cost_usd: Some(1.37),
+        total_tokens: Some(412_000),
+        message_count: Some(57),
+        pr_url: None,
+        pr_number: None,
+        pr_status: None,
+        mode: Some("feature".to_string()),
+        queue_position: None,
+        estimated_start_at: None,
+        restarted_from: None,
+    }
+}
+
+#[test]
+fn test_tervezo_detail() {
+    let state = TervezoDetailState::new(webhook_impl());
+    let out = render(|f| crate::ui::render_tervezo_detail(f, &state, f.area()));
+    insta::assert_snapshot!("tervezo_detail", out);
+}
+
+#[test]
+fn test_tervezo_detail_breadcrumb() {
+    let mut state = TervezoDetailState::new(webhook_impl());
+    state.chain = ["impl_51c0", "impl_7f3a", "impl_9d22"]
+        .into_iter()
+        .map(|id| ChainLink {
+            id: id.to_string(),
+            name: "Add retry to the webhook sender".to_string(),
+        })
+        .collect();
+    let out = render(|f| crate::ui::render_tervezo_detail(f, &state, f.area()));
+    insta::assert_snapshot!("tervezo_detail_breadcrumb", out);
+}
+
+#[test]
+fn test_terminal_view() {
+    let parser = crate::fixtures::busy_terminal(HEIGHT - 2, WIDTH, 200);
+    let tabs = vec![
+        TabEntry {
+            session_id: "a1b2c3d4".to_string(),
+            name: "c9s".to_string(),
+            is_active: true,
+            has_bell: false,
+            bell_blink: false,
+            logging: false,
+        },
+        TabEntry {
+            session_id: "e5f6a7b8".to_string(),
+            name: "webshop".to_string(),
+            is_active: false,
+            has_bell: true,
+            bell_blink: false,
+            logging: true,
+        },
+    ];
+    let mut cache = RenderCache::default();
+    let usage = UsageData::default();
+    let out = render(|f| {
+        crate::ui::render_terminal(
+            f,
+            parser.screen(),
+            &mut cache,
+            &tabs,
+            false,
+            false,
+            None,
+            &usage,
+            None,
+            None,
+            f.area(),
+        )
+    });
+    insta::assert_snapshot!("terminal_view", out);
+}
+
+#[test]
+fn test_terminal_copy_mode() {
+    let parser = crate::fixtures::busy_terminal(HEIGHT - 2, WIDTH, 200);
+    let copy = CopyMode {
+        cursor: Point { line: 0, col: 0 },
+        anchor: Some(Point { line: 2, col: 4 }),
+    };
+    let tabs = vec![TabEntry {
+        session_id: "a1b2c3d4".to_string(),
+        name: "c9s".to_string(),
+        is_active: true,
+        has_bell: false,
+        bell_blink: false,
+        logging: false,
+    }];
+    let mut cache = RenderCache::default();
+    let usage = UsageData::default();
+    let out = render(|f| {
+        crate::ui::render_terminal(
+            f,
+            parser.screen(),
+            &mut cache,
+            &tabs,
+            false,
+            false,
+            Some((&copy, 0)),
+            &usage,
+            None,
+            None,
+            f.area(),
+        )
+    });
+    insta::assert_snapshot!("terminal_copy_mode", out);
+}
+
+#[test]
+fn test_confirm_kill_soft() {
+    let out = render_sized(70, 10, |f| {
+        crate::ui::render_confirm_kill(f, "c9s", true, f.area())
+    });
+    insta::assert_snapshot!("confirm_kill_soft", out);
+}
+
+#[test]
+fn test_confirm_attach() {
+    let out = render_sized(70, 10, |f| {
+        crate::ui::render_confirm_attach(f, "c9s", "pts/3", f.area())
+    });
+    insta::assert_snapshot!("confirm_attach", out);
+}
@@ -0,0 +1,203 @@
+use chrono::{Datelike, Duration};
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Bar, BarChart, BarGroup, Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{CostStats, STATS_DAYS};
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::store::CostTotals;
+use crate::ui::theme::Theme;
+
+/// Projects charted; the rest are summed into the last bar.
+const MAX_PROJECTS: usize = 12;
+
+/// Cost (or tokens) per day as columns, one per day including the quiet
+/// ones, above the same totals per project as rows.
+pub fn render_stats(f: &mut Frame, stats: &CostStats, area: Rect) {
+    let block = Block::default()
+        .title(tf(
+            " Stats: last {n} days ",
+            &[("n", &STATS_DAYS.to_string())],
+        ))
+        .title_bottom(Line::from(t(" Tab:cost / tokens  S/Esc:back ")).centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::border());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let days = fill_days(stats);
+    let [summary, per_day, per_project] = Layout::vertical([
+        Constraint::Length(2),
+        Constraint::Percentage(50),
+        Constraint::Fill(1),
+    ])
+    .areas(inner);
+
+    let sessions: u64 = days.iter().map(|(_, d)| d.sessions).sum();
+    let cost: f64 = days.iter().map(|(_, d)| d.cost_usd).sum();
+    let input: u64 = days.iter().map(|(_, d)| d.input_tokens).sum();
+    let output: u64 = days.iter().map(|(_, d)| d.output_tokens).sum();
+    f.render_widget(
+        Paragraph::new(Line::from(vec![
+            Span::styled(
+                tf(" {n} sessions  ", &[("n", &sessions.to_string())]),
+                Theme::value(),
+            ),
+            Span::styled(format::cost(cost, 2), Theme::cost()),
+            Span::styled(
+                tf(
+                    "  {input} in / {output} out",
+                    &[
+                        ("input", &format::tokens(input)),
+                        ("output", &format::tokens(output)),
+                    ],
+                ),
+                Theme::value(),
+            ),
+        ])),
+        summary,
+    );
+
+    if sessions == 0 {
+        f.render_widget(
+            Paragraph::new(Line::from(Span::styled(
+                t(" No sessions in the store for these days yet."),
+                Style::default().fg(Color::DarkGray),
+            ))),
+            per_day,
+        );
+        return;
+    }
+
+    let metric = if stats.tokens {
+        t(" Tokens per day ")
+    } else {
+        t(" Cost per day ")
+    };
+    let slot = (per_day.width.saturating_sub(2) / days.len() as u16).max(2);
+    let bars: Vec<Bar> = days
+        .iter()
+        .map(|(day, totals)| {
+            Bar::default()
+                .value(value(totals, stats.tokens))
+                .label(Line::from(format!("{:02}", day.day())))
+                .text_value(String::new())
+        })
+        .collect();
+    f.render_widget(
+        BarChart::default()
+            .block(
+                Block::default()
+                    .title(metric)
+                    .borders(Borders::ALL)
+                    .border_style(Theme::border()),
+            )
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(slot - 1)
+            .bar_gap(1)
+            .bar_style(Style::default().fg(Color::Indexed(75)))
+            .label_style(Theme::label()),
+        per_day,
+    );
+
+    let metric = if stats.tokens {
+        t(" Tokens per project ")
+    } else {
+        t(" Cost per project ")
+    };
+    let projects = top_projects(&stats.projects);
+    let label_width = projects
+        .iter()
+        .map(|p| p.key.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(20);
+    let bars: Vec<Bar> = projects
+        .iter()
+        .map(|p| {
+            let text = if stats.tokens {
+                format::tokens(p.input_tokens + p.output_tokens)
+            } else {
+                format::cost(p.cost_usd, 2)
+            };
+            Bar::default()
+                .value(value(p, stats.tokens))
+                .label(Line::from(format!(
+                    "{:>width$.width$}",
+                    p.key,
+                    width = label_width
+                )))
+                .text_value(text)
+        })
+        .collect();
+    f.render_widget(
+        BarChart::default()
+            .block(
+                Block::default()
+                    .title(metric)
+                    .borders(Borders::ALL)
+                    .border_style(Theme::border()),
+            )
+            .direction(Direction::Horizontal)
+            .data(BarGroup::default().bars(&bars))
+            .bar_width(1)
+            .bar_gap(0)
+            .bar_style(Style::default().fg(Color::Indexed(75)))
+            .value_style(Theme::header())
+            .label_style(Theme::label()),
+        per_project,
+    );
+}
+
+/// The charted number: cents, or input and output tokens.
+fn value(totals: &CostTotals, tokens: bool) -> u64 {
+    if tokens {
+        totals.input_tokens + totals.output_tokens
+    } else {
+        (totals.cost_usd * 100.0).round().max(0.0) as u64
+    }
+}
+
+/// Every day the view covers from `since` on, with empty totals for the
+/// days the store has none for.
+fn fill_days(stats: &CostStats) -> Vec<(chrono::NaiveDate, CostTotals)> {
+    (0..STATS_DAYS)
+        .map(|i| {
+            let day = stats.since + Duration::days(i);
+            let key = day.to_string();
+            let totals = stats
+                .days
+                .iter()
+                .find(|d| d.key == key)
+                .cloned()
+                .unwrap_or(CostTotals {
+                    key,
+                    sessions: 0,
+                    input_tokens: 0,
+                    output_tokens: 0,
+                    cost_usd: 0.0,
+                });
+            (day, totals)
+        })
+        .collect()
+}
+
+/// The costliest projects, with the rest added up as "other".
+fn top_projects(projects: &[CostTotals]) -> Vec<CostTotals> {
+    if projects.len() <= MAX_PROJECTS {
+        return projects.to_vec();
+    }
+    let (top, rest) = projects.split_at(MAX_PROJECTS - 1);
+    let mut out = top.to_vec();
+    out.push(CostTotals {
+        key: t("other").to_string(),
+        sessions: rest.iter().map(|p| p.sessions).sum(),
+        input_tokens: rest.iter().map(|p| p.input_tokens).sum(),
+        output_tokens: rest.iter().map(|p| p.output_tokens).sum(),
+        cost_usd: rest.iter().map(|p| p.cost_usd).sum(),
+    });
+    out
+}
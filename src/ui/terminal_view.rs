@@ -1,20 +1,27 @@
+use ratatui::buffer::{Buffer, Cell};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::Paragraph;
 use ratatui::Frame;
 
-use crate::terminal::TabEntry;
+use crate::app::CopyMode;
+use crate::terminal::{Point, RenderCache, TabEntry};
 use crate::ui::theme::Theme;
 use crate::usage::UsageData;
 
+#[allow(clippy::too_many_arguments)]
 pub fn render_terminal(
     f: &mut Frame,
     screen: &vt100::Screen,
+    cache: &mut RenderCache,
     tabs: &[TabEntry],
     exited: bool,
     scrolled: bool,
+    copy: Option<(&CopyMode, usize)>,
     usage: &UsageData,
+    focus: Option<(&str, bool)>,
+    mirror_viewers: Option<usize>,
     area: Rect,
 ) {
     let chunks = Layout::vertical([
@@ -26,7 +33,10 @@ pub fn render_terminal(
 
     render_tab_bar(f, tabs, chunks[0]);
 
-    render_screen(f, screen, chunks[1]);
+    render_screen(f, screen, cache, chunks[1]);
+    if let Some((copy, top)) = copy {
+        draw_copy_mode(f.buffer_mut(), copy, top, chunks[1]);
+    }
 
     let active = tabs.iter().find(|t| t.is_active);
     let project = active.map(|t| t.name.as_str()).unwrap_or("");
@@ -34,7 +44,7 @@ pub fn render_terminal(
         Span::styled(
             " [scroll] ",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(Theme::warn())
                 .add_modifier(Modifier::BOLD),
         )
     } else {
@@ -47,14 +57,39 @@ pub fn render_terminal(
         Line::from(vec![
             Span::styled(
                 " [exited]",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                Style::default()
+                    .fg(Theme::bad())
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::styled(format!("  C-d:dismiss  {}", project), Theme::footer()),
         ])
+    } else if let Some((copy, _)) = copy {
+        let mark = if copy.anchor.is_some() {
+            "v:clear"
+        } else {
+            "v:select"
+        };
+        Line::from(vec![
+            Span::styled(
+                " [copy]",
+                Style::default()
+                    .fg(Theme::warn())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "  hjkl/arrows:move  0/$:line  g/G:top/bottom  {}  y:copy  q:exit  {}",
+                    mark, project
+                ),
+                Theme::footer(),
+            ),
+        ])
     } else {
         Line::from(vec![
             Span::styled(format!(" c9s {}", version), Theme::attached_marker()),
             scroll_indicator,
+            focus_span(focus),
+            mirror_span(mirror_viewers),
             Span::styled(
                 format!(
                     "  C-d:list  C-Space:switch  C-n/p:cycle  C-j/k:scroll  C-t:shell  {}",
@@ -73,41 +108,44 @@ pub fn render_terminal(
     }
 }
 
-pub(crate) fn render_screen(f: &mut Frame, screen: &vt100::Screen, area: Rect) {
-    let buf = f.buffer_mut();
-    for row in 0..area.height {
-        for col in 0..area.width {
-            if let Some(cell) = screen.cell(row, col) {
-                let buf_cell = &mut buf[(col + area.x, row + area.y)];
-                if cell.has_contents() {
-                    buf_cell.set_symbol(cell.contents());
-                }
-                let fg = convert_color(cell.fgcolor());
-                let bg = convert_color(cell.bgcolor());
-                let mut modifier = Modifier::empty();
-                if cell.bold() {
-                    modifier |= Modifier::BOLD;
-                }
-                if cell.dim() {
-                    modifier |= Modifier::DIM;
-                }
-                if cell.italic() {
-                    modifier |= Modifier::ITALIC;
-                }
-                if cell.underline() {
-                    modifier |= Modifier::UNDERLINED;
-                }
-                if cell.inverse() {
-                    modifier |= Modifier::REVERSED;
-                }
+/// The web mirror, so it's clear when someone can see the screen.
+fn mirror_span(viewers: Option<usize>) -> Span<'static> {
+    match viewers {
+        Some(0) => Span::styled(" [web]", Style::default().fg(Theme::muted())),
+        Some(n) => Span::styled(
+            format!(" [web: {} watching]", n),
+            Style::default()
+                .fg(Theme::warn())
+                .add_modifier(Modifier::BOLD),
+        ),
+        None => Span::raw(""),
+    }
+}
 
-                let style = Style::reset().add_modifier(modifier);
-                buf_cell.set_style(style);
-                buf_cell.set_fg(fg);
-                buf_cell.set_bg(bg);
-            }
+/// The focus timer: bold while working, muted on a break.
+fn focus_span(focus: Option<(&str, bool)>) -> Span<'static> {
+    match focus {
+        Some((label, true)) => Span::styled(
+            format!(" [{}]", label),
+            Style::default()
+                .fg(Theme::note())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Some((label, false)) => {
+            Span::styled(format!(" [{}]", label), Style::default().fg(Theme::muted()))
         }
+        None => Span::raw(""),
     }
+}
+
+pub(crate) fn render_screen(
+    f: &mut Frame,
+    screen: &vt100::Screen,
+    cache: &mut RenderCache,
+    area: Rect,
+) {
+    let buf = f.buffer_mut();
+    draw_rows(buf, screen, cache, area);
 
     if !screen.hide_cursor() {
         let (c_row, c_col) = screen.cursor_position();
@@ -127,6 +165,68 @@ pub(crate) fn render_screen(f: &mut Frame, screen: &vt100::Screen, area: Rect) {
     }
 }
 
+/// Highlights the copy-mode selection and cursor; `top` is the history
+/// line on the first row of `area`.
+fn draw_copy_mode(buf: &mut Buffer, copy: &CopyMode, top: usize, area: Rect) {
+    let selection = copy.anchor.map(|anchor| {
+        if anchor <= copy.cursor {
+            (anchor, copy.cursor)
+        } else {
+            (copy.cursor, anchor)
+        }
+    });
+    for row in 0..area.height {
+        let line = top + row as usize;
+        for col in 0..area.width {
+            let point = Point { line, col };
+            let style = if point == copy.cursor {
+                Style::default().bg(Theme::warn()).fg(Color::Black)
+            } else if selection.is_some_and(|(a, b)| a <= point && point <= b) {
+                Theme::selected()
+            } else {
+                continue;
+            };
+            buf[(area.x + col, area.y + row)].set_style(style);
+        }
+    }
+}
+
+fn draw_rows(buf: &mut Buffer, screen: &vt100::Screen, cache: &mut RenderCache, area: Rect) {
+    let rows = cache.rows(screen, area.width, area.height, convert_cell);
+    for (row, cells) in rows.iter().enumerate() {
+        for (col, cell) in cells.iter().enumerate() {
+            buf[(area.x + col as u16, area.y + row as u16)] = cell.clone();
+        }
+    }
+}
+
+fn convert_cell(cell: &vt100::Cell) -> Cell {
+    let mut out = Cell::default();
+    if cell.has_contents() {
+        out.set_symbol(cell.contents());
+    }
+    let mut modifier = Modifier::empty();
+    if cell.bold() {
+        modifier |= Modifier::BOLD;
+    }
+    if cell.dim() {
+        modifier |= Modifier::DIM;
+    }
+    if cell.italic() {
+        modifier |= Modifier::ITALIC;
+    }
+    if cell.underline() {
+        modifier |= Modifier::UNDERLINED;
+    }
+    if cell.inverse() {
+        modifier |= Modifier::REVERSED;
+    }
+    out.set_style(Style::reset().add_modifier(modifier));
+    out.set_fg(convert_color(cell.fgcolor()));
+    out.set_bg(convert_color(cell.bgcolor()));
+    out
+}
+
 fn render_tab_bar(f: &mut Frame, tabs: &[TabEntry], area: Rect) {
     let bg = Color::Indexed(236);
     let buf = f.buffer_mut();
@@ -157,9 +257,9 @@ fn render_tab_bar(f: &mut Frame, tabs: &[TabEntry], area: Rect) {
         let (fg, modifier) = if tab.is_active {
             (Color::Cyan, Modifier::BOLD)
         } else if tab.bell_blink {
-            (Color::Yellow, Modifier::BOLD | Modifier::SLOW_BLINK)
+            (Theme::warn(), Modifier::BOLD | Modifier::SLOW_BLINK)
         } else if tab.has_bell {
-            (Color::Yellow, Modifier::BOLD)
+            (Theme::warn(), Modifier::BOLD)
         } else {
             (Color::White, Modifier::empty())
         };
@@ -167,11 +267,14 @@ fn render_tab_bar(f: &mut Frame, tabs: &[TabEntry], area: Rect) {
         let tab_style = Style::default().fg(fg).bg(bg).add_modifier(modifier);
 
         let has_star = tab.has_bell && !tab.is_active;
-        let text = if has_star {
+        let mut text = if has_star {
             format!("{}: {}*", i + 1, tab.name)
         } else {
             format!("{}: {}", i + 1, tab.name)
         };
+        if tab.logging {
+            text.push_str(" ●");
+        }
 
         if tab.is_active {
             if col < max_col {
@@ -212,9 +315,9 @@ fn render_usage_bar_right(f: &mut Frame, usage: &UsageData, pct: u8, area: Rect)
     let total_len = bar_width + label.len();
 
     let color = if pct >= 80 {
-        Color::Red
+        Theme::bad()
     } else if pct >= 50 {
-        Color::Yellow
+        Theme::warn()
     } else {
         Color::Indexed(75)
     };
@@ -255,3 +358,27 @@ pub(crate) fn convert_color(c: vt100::Color) -> Color {
         vt100::Color::Rgb(r, g, b) => Color::Rgb(r, g, b),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_draw_rows_keeps_styles_across_frames() {
+        let area = Rect::new(0, 0, 6, 2);
+        let mut parser = vt100::Parser::new(2, 6, 0);
+        parser.process(b"\x1b[31mab\x1b[0m\r\ncd");
+        let mut cache = RenderCache::default();
+
+        let mut buf = Buffer::empty(area);
+        draw_rows(&mut buf, parser.screen(), &mut cache, area);
+        assert_eq!(buf[(0, 0)].symbol(), "a");
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(1));
+
+        parser.process(b"e");
+        let mut buf = Buffer::empty(area);
+        draw_rows(&mut buf, parser.screen(), &mut cache, area);
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(1));
+        assert_eq!(buf[(2, 1)].symbol(), "e");
+    }
+}
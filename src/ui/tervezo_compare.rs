@@ -0,0 +1,164 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::Frame;
+
+use crate::app::{ComparisonState, TervezoDetailState};
+use crate::tervezo::compare::{Delta, DiffLine, FileComparison};
+use crate::ui::theme::Theme;
+
+/// The open implementation's changes against the attempt it retried:
+/// every file either touched, or one file's diff of the two patches.
+pub fn render_tervezo_compare(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
+    let Some(comparison) = state.comparison.as_ref() else {
+        return;
+    };
+
+    let hints = if comparison.expanded {
+        " - previous only  + retry only  j/k:scroll  Enter/Esc:files "
+    } else {
+        " j/k:select  Enter:diff  Esc:back "
+    };
+    let block = Block::default()
+        .title(format!(
+            " {}: retry vs {} ",
+            state.implementation.display_name(),
+            comparison.previous_id
+        ))
+        .title_style(
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD),
+        )
+        .title_bottom(Line::from(hints).centered())
+        .borders(Borders::ALL)
+        .border_style(Theme::border());
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let files = match (&comparison.files, &comparison.error) {
+        (Some(files), _) => files,
+        (None, Some(err)) => {
+            let line = Line::from(Span::styled(
+                format!("  Failed to load changes: {}", err.with_hint()),
+                Style::default().fg(Theme::bad()),
+            ));
+            f.render_widget(Paragraph::new(line), inner);
+            return;
+        }
+        (None, None) => {
+            let line = Line::from(Span::styled(
+                "  Loading both attempts' changes...",
+                Style::default().fg(Color::DarkGray),
+            ));
+            f.render_widget(Paragraph::new(line), inner);
+            return;
+        }
+    };
+
+    if files.is_empty() {
+        let line = Line::from(Span::styled(
+            "  Neither attempt changed any files",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(Paragraph::new(line), inner);
+    } else if comparison.expanded {
+        if let Some(file) = files.get(comparison.cursor) {
+            render_file_diff(f, comparison, file, inner);
+        }
+    } else {
+        render_file_list(f, comparison, files, inner);
+    }
+}
+
+fn render_file_list(
+    f: &mut Frame,
+    comparison: &ComparisonState,
+    files: &[FileComparison],
+    area: Rect,
+) {
+    let counts = |c: Option<(u32, u32)>| match c {
+        Some((add, del)) => format!("+{} -{}", add, del),
+        None => "-".to_string(),
+    };
+
+    let mut lines = vec![Line::from(Span::styled(
+        format!(
+            "     {:<8} {:>12} {:>12}  {}",
+            "", "previous", "retry", "file"
+        ),
+        Theme::header(),
+    ))];
+    for (i, file) in files.iter().enumerate() {
+        let selected = i == comparison.cursor;
+        let row_style = if selected {
+            Theme::selected()
+        } else {
+            Style::default()
+        };
+        let delta_color = match file.delta {
+            Delta::New => Theme::ok(),
+            Delta::Dropped => Theme::bad(),
+            Delta::Changed => Theme::warn(),
+            Delta::Same => Color::DarkGray,
+        };
+        lines.push(Line::from(vec![
+            Span::styled(if selected { "  >  " } else { "     " }, row_style),
+            Span::styled(
+                format!("{:<8} ", file.delta.label()),
+                Style::default().fg(delta_color),
+            ),
+            Span::styled(
+                format!("{:>12} {:>12}  ", counts(file.before), counts(file.after)),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                file.path.clone(),
+                row_style.fg(Color::White).add_modifier(Modifier::BOLD),
+            ),
+        ]));
+    }
+
+    // Keep the selected file visible below the header.
+    let visible = (area.height as usize).saturating_sub(1).max(1);
+    let scroll = (comparison.cursor + 1).saturating_sub(visible);
+    let paragraph = Paragraph::new(lines).scroll((scroll as u16, 0));
+    f.render_widget(paragraph, area);
+}
+
+fn render_file_diff(
+    f: &mut Frame,
+    comparison: &ComparisonState,
+    file: &FileComparison,
+    area: Rect,
+) {
+    let mut lines = vec![Line::from(Span::styled(
+        format!(" {} ({})", file.path, file.delta.label()),
+        Style::default()
+            .fg(Color::White)
+            .add_modifier(Modifier::BOLD),
+    ))];
+    if file.lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " Both attempts made the same change",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    lines.extend(file.lines.iter().map(|line| match line {
+        DiffLine::Both(text) => Line::from(Span::styled(
+            format!("   {}", text),
+            Style::default().fg(Color::DarkGray),
+        )),
+        DiffLine::Before(text) => Line::from(Span::styled(
+            format!(" - {}", text),
+            Theme::tzv_diff_remove(),
+        )),
+        DiffLine::After(text) => {
+            Line::from(Span::styled(format!(" + {}", text), Theme::tzv_diff_add()))
+        }
+    }));
+
+    let paragraph = Paragraph::new(lines).scroll((comparison.scroll as u16, 0));
+    f.render_widget(paragraph, area);
+}
@@ -5,6 +5,8 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 use ratatui::Frame;
 
 use crate::app::{TervezoCreateField, TervezoCreateState};
+use crate::i18n::t;
+use crate::ui::theme::Theme;
 
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
     let vertical = Layout::vertical([Constraint::Length(height)])
@@ -92,18 +94,18 @@ fn render_workspace_field(
         Line::from(vec![Span::styled(
             " Loading workspaces...",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(Theme::warn())
                 .add_modifier(Modifier::ITALIC),
         )])
     } else if let Some(ref err) = state.workspaces_error {
         Line::from(vec![Span::styled(
             format!(" Error: {}", err),
-            Style::default().fg(Color::Red),
+            Style::default().fg(Theme::bad()),
         )])
     } else if state.workspaces.is_empty() {
         Line::from(vec![Span::styled(
             " No workspaces found",
-            Style::default().fg(Color::Red),
+            Style::default().fg(Theme::bad()),
         )])
     } else {
         let ws = &state.workspaces[state.selected_workspace];
@@ -252,13 +254,13 @@ fn render_status(f: &mut Frame, state: &TervezoCreateState, area: Rect) {
     let status_widget = if let Some(ref error) = state.error {
         Paragraph::new(Line::from(Span::styled(
             format!(" {}", error),
-            Style::default().fg(Color::Red),
+            Style::default().fg(Theme::bad()),
         )))
     } else if state.submitting {
         Paragraph::new(Line::from(Span::styled(
             " Submitting...",
             Style::default()
-                .fg(Color::Yellow)
+                .fg(Theme::warn())
                 .add_modifier(Modifier::ITALIC),
         )))
     } else {
@@ -275,21 +277,21 @@ fn render_footer(f: &mut Frame, area: Rect) {
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(": next  ", Style::default().fg(Color::DarkGray)),
+        Span::styled(t(": next  "), Style::default().fg(Color::DarkGray)),
         Span::styled(
             "Ctrl+Enter",
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(": submit  ", Style::default().fg(Color::DarkGray)),
+        Span::styled(t(": submit  "), Style::default().fg(Color::DarkGray)),
         Span::styled(
             "Esc",
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         ),
-        Span::styled(": cancel", Style::default().fg(Color::DarkGray)),
+        Span::styled(t(": cancel"), Style::default().fg(Color::DarkGray)),
     ]));
     f.render_widget(footer, area);
 }
@@ -7,8 +7,9 @@ use ratatui::widgets::{
 use ratatui::Frame;
 
 use crate::app::{TervezoDetailState, TervezoTab};
+use crate::i18n::{t, tf};
 use crate::tervezo::models::{format_duration_secs, FileChange, TestReport};
-use crate::tervezo::ImplementationStatus;
+use crate::tervezo::{ImplementationStatus, TervezoError};
 use crate::ui::theme::Theme;
 
 pub fn render_tervezo_detail(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
@@ -18,12 +19,11 @@ pub fn render_tervezo_detail(f: &mut Frame, state: &TervezoDetailState, area: Re
         .map(|s| !s.steps.is_empty())
         .unwrap_or(false);
     let header_height = if has_steps { 4 } else { 3 };
-    let footer_height = if state.action_result.is_some() { 2 } else { 1 };
 
     let chunks = Layout::vertical([
         Constraint::Length(header_height),
         Constraint::Min(5),
-        Constraint::Length(footer_height),
+        Constraint::Length(1),
     ])
     .split(area);
 
@@ -84,14 +84,14 @@ pub fn render_tervezo_action_menu(f: &mut Frame, state: &TervezoDetailState, are
         };
         lines.push(Line::from(vec![
             Span::styled(marker.to_string(), style),
-            Span::styled(action.label().to_string(), style),
+            Span::styled(t(action.label()), style),
         ]));
     }
 
     let border_style = Style::default().fg(Color::Cyan);
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Actions ")
+            .title(t(" Actions "))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
@@ -100,7 +100,7 @@ pub fn render_tervezo_action_menu(f: &mut Frame, state: &TervezoDetailState, are
 }
 
 pub fn render_tervezo_confirm(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
-    let action_label = state.confirm_action.map(|a| a.label()).unwrap_or("Action");
+    let action_label = t(state.confirm_action.map(|a| a.label()).unwrap_or("Action"));
 
     let popup_area = centered_rect(45, 7, area);
     f.render_widget(Clear, popup_area);
@@ -110,30 +110,32 @@ pub fn render_tervezo_confirm(f: &mut Frame, state: &TervezoDetailState, area: R
         Line::from(Span::styled(
             format!("  {}?", action_label),
             Style::default()
-                .fg(Color::Yellow)
+                .fg(Theme::warn())
                 .add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from(vec![
             Span::styled("    ", Style::default()),
             Span::styled(
-                " y/Enter: confirm ",
-                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                t(" y/Enter: confirm "),
+                Style::default()
+                    .fg(Theme::bad())
+                    .add_modifier(Modifier::BOLD),
             ),
             Span::styled("   ", Style::default()),
             Span::styled(
-                " n/Esc: cancel ",
+                t(" n/Esc: cancel "),
                 Style::default()
-                    .fg(Color::Green)
+                    .fg(Theme::ok())
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
     ];
 
-    let border_style = Style::default().fg(Color::Yellow);
+    let border_style = Style::default().fg(Theme::warn());
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Confirm ")
+            .title(t(" Confirm "))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
@@ -159,7 +161,10 @@ fn render_header(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         .unwrap_or(false);
 
     let status_label = if waiting && state.implementation.status.is_running() {
-        format!("[{} - Awaiting reply]", state.implementation.status.label())
+        tf(
+            "[{status} - Awaiting reply]",
+            &[("status", state.implementation.status.label())],
+        )
     } else {
         format!("[{}]", state.implementation.status.label())
     };
@@ -200,12 +205,37 @@ fn render_header(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         }
     }
 
-    let header = Paragraph::new(header_lines).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .style(Theme::border()),
-    );
-    f.render_widget(header, area);
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .style(Theme::border());
+    if let Some(breadcrumb) = breadcrumb(state) {
+        block = block.title(breadcrumb);
+    }
+    f.render_widget(Paragraph::new(header_lines).block(block), area);
+}
+
+/// The attempts at this task, oldest first, when it has been restarted.
+fn breadcrumb(state: &TervezoDetailState) -> Option<Line<'static>> {
+    if state.chain.len() < 2 {
+        return None;
+    }
+    let mut spans = vec![Span::raw(" ")];
+    for (i, link) in state.chain.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::styled(" › ", Style::default().fg(Color::DarkGray)));
+        }
+        let name: String = link.name.chars().take(24).collect();
+        let style = if link.id == state.implementation_id {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        spans.push(Span::styled(format!("#{} {}", i + 1, name), style));
+    }
+    spans.push(Span::raw(" "));
+    Some(Line::from(spans))
 }
 
 fn render_step_bar_line(steps: &[crate::tervezo::models::StatusStep]) -> Line<'static> {
@@ -218,9 +248,9 @@ fn render_step_bar_line(steps: &[crate::tervezo::models::StatusStep]) -> Line<'s
         }
 
         let (icon, color) = match step.status.as_str() {
-            "completed" => ("✓", Color::Green),
+            "completed" => ("✓", Theme::ok()),
             "running" => ("●", Color::Cyan),
-            "failed" => ("✗", Color::Red),
+            "failed" => ("✗", Theme::bad()),
             "skipped" => ("○", Color::DarkGray),
             _ => ("○", Color::DarkGray), // pending
         };
@@ -257,7 +287,7 @@ fn render_step_overlay(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
     // Overall duration
     if let Some(d) = status.duration {
         lines.push(Line::from(Span::styled(
-            format!("  Total: {}", format_duration_secs(d)),
+            tf("  Total: {time}", &[("time", &format_duration_secs(d))]),
             Style::default().fg(Color::White),
         )));
     }
@@ -265,9 +295,9 @@ fn render_step_overlay(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
 
     for step in &status.steps {
         let (icon, color) = match step.status.as_str() {
-            "completed" => ("✓", Color::Green),
+            "completed" => ("✓", Theme::ok()),
             "running" => ("●", Color::Cyan),
-            "failed" => ("✗", Color::Red),
+            "failed" => ("✗", Theme::bad()),
             "skipped" => ("○", Color::DarkGray),
             _ => ("○", Color::DarkGray),
         };
@@ -292,7 +322,7 @@ fn render_step_overlay(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
             for err_line in err.lines().take(3) {
                 lines.push(Line::from(Span::styled(
                     format!("      {}", err_line),
-                    Style::default().fg(Color::Red),
+                    Style::default().fg(Theme::bad()),
                 )));
             }
         }
@@ -301,7 +331,7 @@ fn render_step_overlay(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
     let border_style = Style::default().fg(Color::Cyan);
     let dialog = Paragraph::new(lines).block(
         Block::default()
-            .title(" Steps ")
+            .title(t(" Steps "))
             .borders(Borders::ALL)
             .border_style(border_style),
     );
@@ -319,7 +349,7 @@ fn render_body(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
 
 fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
     let block = Block::default()
-        .title(" Timeline ")
+        .title(t(" Timeline "))
         .title_style(Theme::title())
         .borders(Borders::ALL)
         .style(Theme::border());
@@ -329,21 +359,10 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
 
     if state.timeline.is_empty() {
         if let Some(ref err) = state.timeline_error {
-            let lines = vec![
-                Line::from(Span::styled(
-                    format!("  Error loading timeline: {}", err),
-                    Style::default().fg(Color::Red),
-                )),
-                Line::from(Span::styled(
-                    "  Press 'r' to retry",
-                    Style::default().fg(Color::DarkGray),
-                )),
-            ];
-            let paragraph = Paragraph::new(lines);
-            f.render_widget(paragraph, inner);
+            render_error(f, t("Error loading timeline"), err, inner);
         } else {
             let loading = Paragraph::new(Line::from(Span::styled(
-                "  Loading timeline...",
+                t("  Loading timeline..."),
                 Theme::tzv_loading(),
             )));
             f.render_widget(loading, inner);
@@ -353,6 +372,18 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
 
     let mut lines: Vec<Line> = Vec::new();
 
+    if state.timeline_loading_older {
+        lines.push(Line::from(Span::styled(
+            t("  Loading older messages..."),
+            Theme::tzv_loading(),
+        )));
+    } else if state.timeline_has_older {
+        lines.push(Line::from(Span::styled(
+            t("  ↑ o: load older messages"),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     for msg in &state.timeline {
         let msg_type = msg.msg_type.as_deref().unwrap_or("");
         let eff_status = msg.effective_status();
@@ -369,7 +400,7 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
                 };
                 (
                     ico,
-                    Style::default().fg(Color::Blue),
+                    Style::default().fg(Theme::note()),
                     Style::default().fg(Color::DarkGray),
                 )
             }
@@ -380,7 +411,7 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
             ),
             "file_change" => (
                 "±",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(Theme::warn()),
                 Style::default().fg(Color::DarkGray),
             ),
             "thinking" | "assistant_thinking" => (
@@ -390,7 +421,7 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
             ),
             "todo" => (
                 "☐",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(Theme::warn()),
                 Style::default().fg(Color::DarkGray),
             ),
             "iteration_marker" => (
@@ -411,13 +442,13 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
                 };
                 let sty = match eff_status {
                     Some("completed") | Some("success") | Some("merged") => {
-                        Style::default().fg(Color::Green)
+                        Style::default().fg(Theme::ok())
                     }
                     Some("running") | Some("in_progress") => Style::default()
                         .fg(Color::Cyan)
                         .add_modifier(Modifier::BOLD),
-                    Some("queued") | Some("pending") => Style::default().fg(Color::Yellow),
-                    Some("failed") | Some("error") => Style::default().fg(Color::Red),
+                    Some("queued") | Some("pending") => Style::default().fg(Theme::warn()),
+                    Some("failed") | Some("error") => Style::default().fg(Theme::bad()),
                     Some("stopped") | Some("cancelled") => Style::default().fg(Color::DarkGray),
                     _ => Theme::tzv_timeline_icon(),
                 };
@@ -436,20 +467,20 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
             "error" => {
                 let sev = msg.severity.as_deref().unwrap_or("error");
                 let color = match sev {
-                    "fatal" => Color::Red,
-                    "warning" => Color::Yellow,
-                    _ => Color::Red,
+                    "fatal" => Theme::bad(),
+                    "warning" => Theme::warn(),
+                    _ => Theme::bad(),
                 };
                 ("✗", Style::default().fg(color), Style::default().fg(color))
             }
             "test_report" => (
                 "⊘",
-                Style::default().fg(Color::Green),
+                Style::default().fg(Theme::ok()),
                 Style::default().fg(Color::White),
             ),
             "tool_result" => (
                 "←",
-                Style::default().fg(Color::Blue),
+                Style::default().fg(Theme::note()),
                 Style::default().fg(Color::DarkGray),
             ),
             _ => ("·", Theme::tzv_timeline_icon(), Theme::tzv_timeline_text()),
@@ -471,9 +502,9 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
                 for (i, diff_line) in diff.lines().enumerate() {
                     if i >= 30 {
                         lines.push(Line::from(Span::styled(
-                            format!(
-                                "      ... ({} more lines)",
-                                total_diff_lines.saturating_sub(30)
+                            tf(
+                                "      ... ({n} more lines)",
+                                &[("n", &total_diff_lines.saturating_sub(30).to_string())],
                             ),
                             Style::default().fg(Color::DarkGray),
                         )));
@@ -503,9 +534,9 @@ fn render_timeline_panel(f: &mut Frame, state: &TervezoDetailState, area: Rect)
                 for (i, content_line) in content.lines().enumerate() {
                     if i >= 30 {
                         lines.push(Line::from(Span::styled(
-                            format!(
-                                "      ... ({} more lines)",
-                                total_content_lines.saturating_sub(30)
+                            tf(
+                                "      ... ({n} more lines)",
+                                &[("n", &total_content_lines.saturating_sub(30).to_string())],
                             ),
                             Style::default().fg(Color::DarkGray),
                         )));
@@ -567,9 +598,9 @@ fn render_tab_bar(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
             };
             let loading = state.loading.contains(&tab);
             let label = if loading {
-                format!(" {}… ", tab.label())
+                format!(" {}… ", t(tab.label()))
             } else {
-                format!(" {} ", tab.label())
+                format!(" {} ", t(tab.label()))
             };
             vec![Span::styled(label, style), Span::raw(" ")]
         })
@@ -607,8 +638,10 @@ fn render_plan_tab(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         None => {
             if state.loading.contains(&TervezoTab::Plan) {
                 render_loading(f, area);
+            } else if let Some(err) = state.tab_errors.get(&TervezoTab::Plan) {
+                render_error(f, t("Failed to load"), err, area);
             } else {
-                render_empty(f, "No plan available", area);
+                render_empty(f, t("No plan available"), area);
             }
         }
     }
@@ -636,13 +669,15 @@ fn render_changes_tab(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
             }
         }
         Some(_) => {
-            render_empty(f, "No file changes", area);
+            render_empty(f, t("No file changes"), area);
         }
         None => {
             if state.loading.contains(&TervezoTab::Changes) {
                 render_loading(f, area);
+            } else if let Some(err) = state.tab_errors.get(&TervezoTab::Changes) {
+                render_error(f, t("Failed to load"), err, area);
             } else {
-                render_empty(f, "Press Tab to load changes", area);
+                render_empty(f, t("Press Tab to load changes"), area);
             }
         }
     }
@@ -658,10 +693,10 @@ fn render_file_list(f: &mut Frame, state: &TervezoDetailState, changes: &[FileCh
         let chevron = if is_expanded { "▼" } else { "▶" };
         let status_str = change.status.as_deref().unwrap_or("modified");
         let status_color = match status_str {
-            "added" => Color::Green,
-            "removed" | "deleted" => Color::Red,
+            "added" => Theme::ok(),
+            "removed" | "deleted" => Theme::bad(),
             "renamed" => Color::Cyan,
-            _ => Color::Yellow,
+            _ => Theme::warn(),
         };
 
         let add_del = match (change.additions, change.deletions) {
@@ -714,7 +749,7 @@ fn render_diff_view(f: &mut Frame, state: &TervezoDetailState, changes: &[FileCh
 
     let diff_text = match &selected.diff {
         Some(d) => d.as_str(),
-        None => "(no diff available)",
+        None => t("(no diff available)"),
     };
 
     let title = format!(" {} ", selected.display_path());
@@ -734,20 +769,10 @@ fn render_diff_view(f: &mut Frame, state: &TervezoDetailState, changes: &[FileCh
     let lines: Vec<Line> = diff_text
         .lines()
         .map(|line| {
-            let style = if line.starts_with("+++") || line.starts_with("---") {
-                Style::default()
-                    .fg(Color::White)
-                    .add_modifier(Modifier::BOLD)
-            } else if line.starts_with('+') {
-                Theme::tzv_diff_add()
-            } else if line.starts_with('-') {
-                Theme::tzv_diff_remove()
-            } else if line.starts_with("@@") {
-                Theme::tzv_diff_header()
-            } else {
-                Style::default().fg(Color::White)
-            };
-            Line::from(Span::styled(format!(" {}", line), style))
+            Line::from(Span::styled(
+                format!(" {}", line),
+                Theme::tzv_diff_line(line),
+            ))
         })
         .collect();
 
@@ -765,13 +790,15 @@ fn render_test_tab(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
             f.render_widget(paragraph, area);
         }
         Some(_) => {
-            render_empty(f, "No test reports", area);
+            render_empty(f, t("No test reports"), area);
         }
         None => {
             if state.loading.contains(&TervezoTab::TestOutput) {
                 render_loading(f, area);
+            } else if let Some(err) = state.tab_errors.get(&TervezoTab::TestOutput) {
+                render_error(f, t("Failed to load"), err, area);
             } else {
-                render_empty(f, "Press Tab to load test output", area);
+                render_empty(f, t("Press Tab to load test output"), area);
             }
         }
     }
@@ -795,28 +822,28 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
             let status = summary.status.as_deref().unwrap_or("unknown");
             let (badge, badge_style) = match status {
                 "passing" => (
-                    " PASSING ",
+                    t(" PASSING "),
                     Style::default()
                         .fg(Color::Black)
-                        .bg(Color::Green)
+                        .bg(Theme::ok())
                         .add_modifier(Modifier::BOLD),
                 ),
                 "failing" => (
-                    " FAILING ",
+                    t(" FAILING "),
                     Style::default()
                         .fg(Color::White)
-                        .bg(Color::Red)
+                        .bg(Theme::bad())
                         .add_modifier(Modifier::BOLD),
                 ),
                 "partial" => (
-                    " PARTIAL ",
+                    t(" PARTIAL "),
                     Style::default()
                         .fg(Color::Black)
-                        .bg(Color::Yellow)
+                        .bg(Theme::warn())
                         .add_modifier(Modifier::BOLD),
                 ),
                 _ => (
-                    " UNKNOWN ",
+                    t(" UNKNOWN "),
                     Style::default()
                         .fg(Color::White)
                         .bg(Color::DarkGray)
@@ -833,14 +860,20 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
             if let Some(ref stats) = summary.stats {
                 let mut stat_parts: Vec<String> = Vec::new();
                 if let Some(n) = stats.new_tests {
-                    stat_parts.push(format!("New: {}", n));
+                    stat_parts.push(tf("New: {n}", &[("n", &n.to_string())]));
                 }
                 if let (Some(before), Some(after)) = (stats.total_before, stats.total_after) {
-                    stat_parts.push(format!("Total: {}>{}", before, after));
+                    stat_parts.push(tf(
+                        "Total: {before}>{after}",
+                        &[
+                            ("before", &before.to_string()),
+                            ("after", &after.to_string()),
+                        ],
+                    ));
                 }
                 if let Some(pre) = stats.pre_existing_failures {
                     if pre > 0 {
-                        stat_parts.push(format!("Pre-existing: {}", pre));
+                        stat_parts.push(tf("Pre-existing: {n}", &[("n", &pre.to_string())]));
                     }
                 }
                 if !stat_parts.is_empty() {
@@ -857,13 +890,13 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
             // Summary message
             if let Some(ref msg) = summary.message {
                 let msg_style = match status {
-                    "passing" => Style::default().fg(Color::Green),
-                    "failing" => Style::default().fg(Color::Red),
-                    "partial" => Style::default().fg(Color::Yellow),
+                    "passing" => Style::default().fg(Theme::ok()),
+                    "failing" => Style::default().fg(Theme::bad()),
+                    "partial" => Style::default().fg(Theme::warn()),
                     _ => Style::default().fg(Color::White),
                 };
                 lines.push(Line::from(Span::styled(
-                    "  Summary",
+                    t("  Summary"),
                     Style::default()
                         .fg(Color::White)
                         .add_modifier(Modifier::BOLD),
@@ -878,7 +911,7 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
         // Approach
         if let Some(ref approach) = report.approach {
             lines.push(Line::from(Span::styled(
-                "  Approach",
+                t("  Approach"),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
@@ -895,26 +928,26 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
         // Tests Added
         if !report.tests_added.is_empty() {
             lines.push(Line::from(Span::styled(
-                "  Tests Added",
+                t("  Tests Added"),
                 Style::default()
                     .fg(Color::White)
                     .add_modifier(Modifier::BOLD),
             )));
             for test in &report.tests_added {
-                let file = test.file.as_deref().unwrap_or("(unknown)");
+                let file = test.file.as_deref().unwrap_or(t("(unknown)"));
                 let count_str = test.count.map(|c| format!(" (+{})", c)).unwrap_or_default();
                 let critical_str = if test.critical_path.as_deref() == Some("true") {
-                    " critical"
+                    t(" critical")
                 } else {
                     ""
                 };
                 let critical_style = if test.critical_path.as_deref() == Some("true") {
-                    Style::default().fg(Color::Yellow)
+                    Style::default().fg(Theme::warn())
                 } else {
                     Style::default().fg(Color::DarkGray)
                 };
                 lines.push(Line::from(vec![
-                    Span::styled("    * ", Style::default().fg(Color::Green)),
+                    Span::styled("    * ", Style::default().fg(Theme::ok())),
                     Span::styled(file.to_string(), Style::default().fg(Color::White)),
                     Span::styled(count_str, Style::default().fg(Color::DarkGray)),
                     Span::styled(critical_str.to_string(), critical_style),
@@ -926,16 +959,16 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
         // Uncovered Paths
         if !report.uncovered_paths.is_empty() {
             lines.push(Line::from(Span::styled(
-                "  Uncovered Paths",
+                t("  Uncovered Paths"),
                 Style::default()
-                    .fg(Color::Yellow)
+                    .fg(Theme::warn())
                     .add_modifier(Modifier::BOLD),
             )));
             for path in &report.uncovered_paths {
-                let name = path.name.as_deref().unwrap_or("(unnamed)");
+                let name = path.name.as_deref().unwrap_or(t("(unnamed)"));
                 lines.push(Line::from(vec![
-                    Span::styled("    ! ", Style::default().fg(Color::Yellow)),
-                    Span::styled(name.to_string(), Style::default().fg(Color::Yellow)),
+                    Span::styled("    ! ", Style::default().fg(Theme::warn())),
+                    Span::styled(name.to_string(), Style::default().fg(Theme::warn())),
                 ]));
                 if let Some(ref detail) = path.detail {
                     for l in detail.lines() {
@@ -947,7 +980,7 @@ fn build_test_report_lines(reports: &[TestReport]) -> Vec<Line<'static>> {
                 }
                 if let Some(ref method) = path.verification_method {
                     lines.push(Line::from(Span::styled(
-                        format!("      Verify: {}", method),
+                        tf("      Verify: {method}", &[("method", method)]),
                         Style::default()
                             .fg(Color::Cyan)
                             .add_modifier(Modifier::ITALIC),
@@ -972,8 +1005,10 @@ fn render_analysis_tab(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         None => {
             if state.loading.contains(&TervezoTab::Analysis) {
                 render_loading(f, area);
+            } else if let Some(err) = state.tab_errors.get(&TervezoTab::Analysis) {
+                render_error(f, t("Failed to load"), err, area);
             } else {
-                render_empty(f, "Press Tab to load analysis", area);
+                render_empty(f, t("Press Tab to load analysis"), area);
             }
         }
     }
@@ -1030,12 +1065,30 @@ fn render_markdown_or_raw(content: &str, raw: bool) -> Text<'static> {
 
 fn render_loading(f: &mut Frame, area: Rect) {
     let paragraph = Paragraph::new(Line::from(Span::styled(
-        "  Loading...",
+        t("  Loading..."),
         Theme::tzv_loading(),
     )));
     f.render_widget(paragraph, area);
 }
 
+fn render_error(f: &mut Frame, what: &str, err: &TervezoError, area: Rect) {
+    let mut lines = vec![Line::from(Span::styled(
+        format!("  {}: {}", what, err),
+        Style::default().fg(Theme::bad()),
+    ))];
+    if let Some(hint) = err.hint() {
+        lines.push(Line::from(Span::styled(
+            tf("  Hint: {hint}", &[("hint", hint)]),
+            Style::default().fg(Theme::warn()),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        t("  Press 'r' to retry"),
+        Style::default().fg(Color::DarkGray),
+    )));
+    f.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), area);
+}
+
 fn render_empty(f: &mut Frame, msg: &str, area: Rect) {
     let paragraph = Paragraph::new(Line::from(Span::styled(
         format!("  {}", msg),
@@ -1045,22 +1098,11 @@ fn render_empty(f: &mut Frame, msg: &str, area: Rect) {
 }
 
 fn render_footer(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
-    let mut lines: Vec<Line> = Vec::new();
-
-    // Flash message (action result)
-    if let Some(ref result) = state.action_result {
-        let (msg, style) = match result {
-            Ok(msg) => (msg.as_str(), Style::default().fg(Color::Green)),
-            Err(msg) => (msg.as_str(), Style::default().fg(Color::Red)),
-        };
-        lines.push(Line::from(Span::styled(format!(" {}", msg), style)));
-    }
-
     let ssh_hint = if state.implementation.status.is_running() {
         if state.ssh_creds.is_some() {
-            "s:ssh"
+            t("s:ssh")
         } else {
-            "s:ssh(loading)"
+            t("s:ssh(loading)")
         }
     } else {
         ""
@@ -1071,19 +1113,19 @@ fn render_footer(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
             .changes_expanded
             .contains(&state.changes_selected_file)
         {
-            "J/K:scroll(diff)  Enter:collapse"
+            t("J/K:scroll(diff)  Enter:collapse")
         } else {
-            "J/K:navigate  Enter:expand"
+            t("J/K:navigate  Enter:expand")
         }
     } else {
-        "J/K:scroll(tab)"
+        t("J/K:scroll(tab)")
     };
 
     let md_hint = if matches!(state.active_tab, TervezoTab::Plan | TervezoTab::Analysis) {
         if state.raw_markdown {
-            "m:md"
+            t("m:md")
         } else {
-            "m:raw"
+            t("m:raw")
         }
     } else {
         ""
@@ -1095,9 +1137,9 @@ fn render_footer(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         .map(|s| s.waiting_for_input)
         .unwrap_or(false);
     let prompt_hint = if waiting {
-        "p:reply"
+        t("p:reply")
     } else if state.implementation.status.is_terminal() {
-        "p:follow-up"
+        t("p:follow-up")
     } else {
         ""
     };
@@ -1108,19 +1150,37 @@ fn render_footer(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         .map(|s| !s.steps.is_empty())
         .unwrap_or(false)
     {
-        "w:steps"
+        t("w:steps")
     } else {
         ""
     };
 
-    let keys = format!(
-        " Esc:back  Tab/h/l:tabs  j/k:timeline  ^d/^u:page  g/G:top/btm  {}  {}  r:refresh  {}  a:actions  {}  {}",
-        tab_hint, md_hint, ssh_hint, steps_hint, prompt_hint
-    );
+    let older_hint = if state.timeline_has_older {
+        t("o:older")
+    } else {
+        ""
+    };
 
-    lines.push(Line::from(Span::styled(keys, Theme::footer())));
+    let attempts_hint = if state.chain.len() > 1 {
+        t("[/]:attempts")
+    } else {
+        ""
+    };
 
-    let paragraph = Paragraph::new(lines);
+    let keys = tf(
+        " Esc:back  Tab/h/l:tabs  j/k:timeline  ^d/^u:page  g/G:top/btm  {tab}  {md}  r:refresh  {ssh}  a:actions  {steps}  {prompt}  {older}  {attempts}",
+        &[
+            ("tab", tab_hint),
+            ("md", md_hint),
+            ("ssh", ssh_hint),
+            ("steps", steps_hint),
+            ("prompt", prompt_hint),
+            ("older", older_hint),
+            ("attempts", attempts_hint),
+        ],
+    );
+
+    let paragraph = Paragraph::new(Line::from(Span::styled(keys, Theme::footer())));
     f.render_widget(paragraph, area);
 }
 
@@ -1130,10 +1190,16 @@ fn render_prompt_input(f: &mut Frame, state: &TervezoDetailState, area: Rect) {
         .as_ref()
         .map(|s| s.waiting_for_input)
         .unwrap_or(false);
-    let label = if waiting { "Reply" } else { "Follow-up" };
+    let label = if state.restart_guidance {
+        t("Restart with guidance (empty: retry as is)")
+    } else if waiting {
+        t("Reply")
+    } else {
+        t("Follow-up")
+    };
 
     let sending_indicator = if state.prompt_sending {
-        " (sending...)"
+        t(" (sending...)")
     } else {
         ""
     };
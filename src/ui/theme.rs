@@ -1,8 +1,129 @@
 use ratatui::style::{Color, Modifier, Style};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::app::{CiStatus, PrReview, PrStatus, SessionEntry};
+use crate::config::Palette;
+use crate::session::SessionStatus;
+use crate::tervezo::ImplementationStatus;
+
+/// Index into `Palette::ALL`, set from config and cycled with `P`.
+static PALETTE: AtomicU8 = AtomicU8::new(0);
 
 pub struct Theme;
 
 impl Theme {
+    pub fn set_palette(palette: Palette) {
+        let i = Palette::ALL.iter().position(|p| *p == palette).unwrap_or(0);
+        PALETTE.store(i as u8, Ordering::Relaxed);
+    }
+
+    pub fn palette() -> Palette {
+        Palette::ALL
+            .get(PALETTE.load(Ordering::Relaxed) as usize)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // Semantic colors. Views use these instead of naming green/red/yellow
+    // so the palette can swap them out.
+
+    /// Running, passing, added.
+    pub fn ok() -> Color {
+        match Self::palette() {
+            Palette::Default => Color::Green,
+            Palette::Deuteranopia => Color::Indexed(39),
+            Palette::HighContrast => Color::LightGreen,
+        }
+    }
+
+    /// Failed, removed, recent errors.
+    pub fn bad() -> Color {
+        match Self::palette() {
+            Palette::Default => Color::Red,
+            Palette::Deuteranopia => Color::Indexed(208),
+            Palette::HighContrast => Color::LightRed,
+        }
+    }
+
+    /// Idle, pending, costs.
+    pub fn warn() -> Color {
+        match Self::palette() {
+            Palette::Default => Color::Yellow,
+            Palette::Deuteranopia => Color::Indexed(227),
+            Palette::HighContrast => Color::LightYellow,
+        }
+    }
+
+    /// In-progress fixes and other secondary highlights.
+    pub fn note() -> Color {
+        match Self::palette() {
+            Palette::Default => Color::Blue,
+            Palette::Deuteranopia => Color::Indexed(183),
+            Palette::HighContrast => Color::LightBlue,
+        }
+    }
+
+    /// Dimmed text: dead sessions, borders, hints.
+    pub fn muted() -> Color {
+        match Self::palette() {
+            Palette::HighContrast => Color::Gray,
+            _ => Color::DarkGray,
+        }
+    }
+
+    /// Bold under high contrast, where weight carries as much as color.
+    fn emphasis() -> Modifier {
+        match Self::palette() {
+            Palette::HighContrast => Modifier::BOLD,
+            _ => Modifier::empty(),
+        }
+    }
+
+    /// Icon and style for a local session's status, so states differ in
+    /// shape as well as color.
+    pub fn session_status(status: &SessionStatus) -> (&'static str, Style) {
+        match status {
+            SessionStatus::Active => ("●", Self::status_active()),
+            SessionStatus::Idle => ("○", Self::status_idle()),
+            SessionStatus::Thinking => ("◆", Self::status_thinking()),
+            SessionStatus::Dead => ("✕", Self::status_dead()),
+        }
+    }
+
+    pub fn entry_status(entry: &SessionEntry) -> (&'static str, Style) {
+        match entry {
+            SessionEntry::Local(s) => Self::session_status(&s.status),
+            SessionEntry::Remote(i) => Self::impl_status(&i.status),
+        }
+    }
+
+    /// Pull request badges: red when changes were requested or checks
+    /// fail, green once approved, otherwise neutral.
+    pub fn pr(pr: &PrStatus) -> Style {
+        let color = match (pr.review, &pr.checks) {
+            (PrReview::ChangesRequested, _) | (_, CiStatus::Failing) => Self::bad(),
+            (PrReview::Approved, _) => Self::ok(),
+            _ => Self::note(),
+        };
+        Style::default().fg(color)
+    }
+
+    pub fn impl_status(status: &ImplementationStatus) -> (&'static str, Style) {
+        match status {
+            ImplementationStatus::Running => ("●", Self::status_active()),
+            ImplementationStatus::Pending | ImplementationStatus::Queued => {
+                ("○", Self::status_idle())
+            }
+            ImplementationStatus::Completed | ImplementationStatus::Merged => {
+                ("✓", Self::tzv_status_done())
+            }
+            ImplementationStatus::Failed => ("✗", Self::tzv_status_failed()),
+            ImplementationStatus::Stopped | ImplementationStatus::Cancelled => {
+                ("✕", Self::status_dead())
+            }
+        }
+    }
+
     pub fn header() -> Style {
         Style::default()
             .fg(Color::Cyan)
@@ -10,18 +131,27 @@ impl Theme {
     }
 
     pub fn selected() -> Style {
-        Style::default()
-            .bg(Color::DarkGray)
-            .fg(Color::White)
-            .add_modifier(Modifier::BOLD)
+        let bg = match Self::palette() {
+            Palette::HighContrast => Color::White,
+            _ => Color::DarkGray,
+        };
+        let fg = match Self::palette() {
+            Palette::HighContrast => Color::Black,
+            _ => Color::White,
+        };
+        Style::default().bg(bg).fg(fg).add_modifier(Modifier::BOLD)
     }
 
     pub fn status_active() -> Style {
-        Style::default().fg(Color::Green)
+        Style::default()
+            .fg(Self::ok())
+            .add_modifier(Self::emphasis())
     }
 
     pub fn status_idle() -> Style {
-        Style::default().fg(Color::Yellow)
+        Style::default()
+            .fg(Self::warn())
+            .add_modifier(Self::emphasis())
     }
 
     pub fn status_thinking() -> Style {
@@ -31,11 +161,11 @@ impl Theme {
     }
 
     pub fn status_dead() -> Style {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(Self::muted())
     }
 
     pub fn cost() -> Style {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(Self::warn())
     }
 
     pub fn title() -> Style {
@@ -45,17 +175,17 @@ impl Theme {
     }
 
     pub fn footer() -> Style {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(Self::muted())
     }
 
     #[allow(dead_code)]
     pub fn filter_active() -> Style {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(Self::warn())
     }
 
     pub fn label() -> Style {
         Style::default()
-            .fg(Color::DarkGray)
+            .fg(Self::muted())
             .add_modifier(Modifier::BOLD)
     }
 
@@ -64,7 +194,7 @@ impl Theme {
     }
 
     pub fn border() -> Style {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(Self::muted())
     }
 
     pub fn help_key() -> Style {
@@ -88,7 +218,7 @@ impl Theme {
     }
 
     pub fn command_bar() -> Style {
-        Style::default().fg(Color::Yellow)
+        Style::default().fg(Self::warn())
     }
 
     pub fn command_bar_label() -> Style {
@@ -107,15 +237,15 @@ impl Theme {
     }
 
     pub fn tzv_tab_inactive() -> Style {
-        Style::default().fg(Color::DarkGray)
+        Style::default().fg(Self::muted())
     }
 
     pub fn tzv_diff_add() -> Style {
-        Style::default().fg(Color::Green)
+        Style::default().fg(Self::ok())
     }
 
     pub fn tzv_diff_remove() -> Style {
-        Style::default().fg(Color::Red)
+        Style::default().fg(Self::bad())
     }
 
     pub fn tzv_diff_header() -> Style {
@@ -124,17 +254,37 @@ impl Theme {
             .add_modifier(Modifier::BOLD)
     }
 
+    /// A unified diff line: file headers, hunk headers, additions and
+    /// removals.
+    pub fn tzv_diff_line(line: &str) -> Style {
+        if line.starts_with("+++") || line.starts_with("---") {
+            Style::default()
+                .fg(Color::White)
+                .add_modifier(Modifier::BOLD)
+        } else if line.starts_with('+') {
+            Self::tzv_diff_add()
+        } else if line.starts_with('-') {
+            Self::tzv_diff_remove()
+        } else if line.starts_with("@@") {
+            Self::tzv_diff_header()
+        } else {
+            Style::default().fg(Color::White)
+        }
+    }
+
     pub fn tzv_status_done() -> Style {
         Style::default().fg(Color::Cyan)
     }
 
     pub fn tzv_status_failed() -> Style {
-        Style::default().fg(Color::Red)
+        Style::default()
+            .fg(Self::bad())
+            .add_modifier(Modifier::BOLD)
     }
 
     pub fn tzv_loading() -> Style {
         Style::default()
-            .fg(Color::Yellow)
+            .fg(Self::warn())
             .add_modifier(Modifier::ITALIC)
     }
 
@@ -152,3 +302,28 @@ impl Theme {
         Style::default().fg(Color::White)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_icons_are_distinct() {
+        let icons: Vec<&str> = [
+            SessionStatus::Active,
+            SessionStatus::Idle,
+            SessionStatus::Thinking,
+            SessionStatus::Dead,
+        ]
+        .iter()
+        .map(|s| Theme::session_status(s).0)
+        .collect();
+        for (i, icon) in icons.iter().enumerate() {
+            assert!(!icons[i + 1..].contains(icon), "{} repeated", icon);
+        }
+        assert_ne!(
+            Theme::impl_status(&ImplementationStatus::Completed).0,
+            Theme::impl_status(&ImplementationStatus::Failed).0
+        );
+    }
+}
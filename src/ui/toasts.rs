@@ -0,0 +1,59 @@
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use ratatui::Frame;
+
+use crate::toast::{Severity, Toasts};
+use crate::ui::theme::Theme;
+
+const MAX_WIDTH: u16 = 48;
+
+/// Stacks toasts upward from the bottom-right corner, newest at the bottom,
+/// one bordered line each. Long messages are cut to fit.
+pub fn render_toasts(f: &mut Frame, toasts: &Toasts, area: Rect) {
+    let width = MAX_WIDTH.min(area.width.saturating_sub(2));
+    if width < 10 {
+        return;
+    }
+    let mut bottom = area.y + area.height.saturating_sub(1);
+
+    for toast in toasts.iter().collect::<Vec<_>>().into_iter().rev() {
+        if bottom < area.y + 3 {
+            break;
+        }
+        let rect = Rect {
+            x: area.x + area.width - width - 1,
+            y: bottom - 3,
+            width,
+            height: 3,
+        };
+        bottom -= 3;
+
+        let color = match toast.severity {
+            Severity::Info => Color::Cyan,
+            Severity::Success => Theme::ok(),
+            Severity::Warning => Theme::warn(),
+            Severity::Error => Theme::bad(),
+        };
+        let room = width.saturating_sub(4) as usize;
+        let text = if toast.message.chars().count() > room {
+            let cut: String = toast.message.chars().take(room.saturating_sub(1)).collect();
+            format!("{}…", cut)
+        } else {
+            toast.message.clone()
+        };
+
+        f.render_widget(Clear, rect);
+        let paragraph = Paragraph::new(Line::from(Span::styled(
+            format!(" {}", text),
+            Style::default().fg(color),
+        )))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        );
+        f.render_widget(paragraph, rect);
+    }
+}
@@ -5,18 +5,23 @@ use ratatui::widgets::{Block, Borders, Paragraph};
 use ratatui::Frame;
 
 use crate::app::SessionEntry;
-use crate::usage::UsageData;
+use crate::format;
+use crate::i18n::{t, tf};
+use crate::ui::theme::Theme;
+use crate::usage::{UsageData, WindowEstimate};
 
 pub fn render_usage_panel(
     f: &mut Frame,
     usage: &UsageData,
     sessions: &[&SessionEntry],
+    tracked: Option<(u64, bool)>,
+    window: Option<(WindowEstimate, Option<u64>)>,
     area: Rect,
 ) {
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::DarkGray))
-        .title(" Usage ")
+        .title(t(" Usage "))
         .title_style(Style::default().fg(Color::Cyan));
 
     let inner = block.inner(area);
@@ -28,8 +33,8 @@ pub fn render_usage_panel(
     if usage.api_available {
         if let Some(pct) = usage.five_hour {
             let title = match &usage.plan_name {
-                Some(p) => format!("Current session ({})", p),
-                None => "Current session".to_string(),
+                Some(p) => tf("Current session ({plan})", &[("plan", p)]),
+                None => t("Current session").to_string(),
             };
             lines.push(make_title(&title));
             lines.push(make_bar(pct, bar_width));
@@ -39,7 +44,7 @@ pub fn render_usage_panel(
         }
 
         if let Some(pct) = usage.seven_day {
-            lines.push(make_title("Current week (all models)"));
+            lines.push(make_title(t("Current week (all models)")));
             lines.push(make_bar(pct, bar_width));
             if let Some(ref reset) = usage.seven_day_reset {
                 lines.push(make_reset(reset));
@@ -47,6 +52,31 @@ pub fn render_usage_panel(
         }
     }
 
+    if let Some((estimate, limit)) = window {
+        lines.push(make_title(t("Current session (estimate)")));
+        let resets_in = (estimate.resets_at - chrono::Utc::now())
+            .num_seconds()
+            .max(0) as u64;
+        let detail = tf(
+            " {tokens} tokens, resets in {time}",
+            &[
+                ("tokens", &format::tokens(estimate.tokens)),
+                ("time", &format::elapsed(resets_in)),
+            ],
+        );
+        if let Some(pct) = limit.and_then(|l| estimate.percent(l)) {
+            lines.push(make_labeled_bar(
+                pct,
+                tf("~{pct}% used", &[("pct", &pct.to_string())]),
+                bar_width,
+            ));
+        }
+        lines.push(Line::from(Span::styled(
+            detail,
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     let local_sessions: Vec<_> = sessions.iter().filter_map(|e| e.as_local()).collect();
 
     let today_cost: f64 = local_sessions.iter().map(|s| s.estimated_cost_usd()).sum();
@@ -54,7 +84,7 @@ pub fn render_usage_panel(
     let live_count = local_sessions.iter().filter(|s| s.pid.is_some()).count();
     let total_count = local_sessions.len();
 
-    let mut model_tokens: [(&str, u64); 4] =
+    let mut model_tokens: [(&'static str, u64); 4] =
         [("opus", 0), ("sonnet", 0), ("haiku", 0), ("other", 0)];
     for s in &local_sessions {
         let idx = match s.model.as_deref() {
@@ -68,24 +98,28 @@ pub fn render_usage_panel(
 
     let remote_count = sessions.iter().filter(|e| e.is_remote()).count();
 
-    lines.push(make_title("Sessions"));
+    lines.push(make_title(t("Sessions")));
 
     let stats = if remote_count > 0 {
-        format!(
-            " ${:.2} | {} tokens | {} live / {} local + {}T remote",
-            today_cost,
-            format_tokens(today_tokens),
-            live_count,
-            total_count,
-            remote_count,
+        tf(
+            " {cost} | {tokens} tokens | {live} live / {local} local + {remote}T remote",
+            &[
+                ("cost", &format::cost(today_cost, 2)),
+                ("tokens", &format::tokens(today_tokens)),
+                ("live", &live_count.to_string()),
+                ("local", &total_count.to_string()),
+                ("remote", &remote_count.to_string()),
+            ],
         )
     } else {
-        format!(
-            " ${:.2} | {} tokens | {} live / {} total",
-            today_cost,
-            format_tokens(today_tokens),
-            live_count,
-            total_count,
+        tf(
+            " {cost} | {tokens} tokens | {live} live / {total} total",
+            &[
+                ("cost", &format::cost(today_cost, 2)),
+                ("tokens", &format::tokens(today_tokens)),
+                ("live", &live_count.to_string()),
+                ("total", &total_count.to_string()),
+            ],
         )
     };
     lines.push(Line::from(Span::styled(
@@ -95,12 +129,27 @@ pub fn render_usage_panel(
 
     let model_parts: Vec<String> = model_tokens
         .iter()
-        .filter(|(_, t)| *t > 0)
-        .map(|(name, t)| format!("{} {}", name, format_tokens(*t)))
+        .filter(|(_, tokens)| *tokens > 0)
+        .map(|(name, tokens)| format!("{} {}", t(name), format::tokens(*tokens)))
         .collect();
     if !model_parts.is_empty() {
         lines.push(Line::from(Span::styled(
-            format!(" Models: {}", model_parts.join(" | ")),
+            tf(" Models: {models}", &[("models", &model_parts.join(" | "))]),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
+    if let Some((secs, enabled)) = tracked {
+        let text = if enabled {
+            tf(" Time today {time}", &[("time", &format::elapsed(secs))])
+        } else {
+            tf(
+                " Time today {time} (tracking off)",
+                &[("time", &format::elapsed(secs))],
+            )
+        };
+        lines.push(Line::from(Span::styled(
+            text,
             Style::default().fg(Color::DarkGray),
         )));
     }
@@ -119,7 +168,14 @@ fn make_title(text: &str) -> Line<'static> {
 }
 
 fn make_bar(pct: u8, total_width: usize) -> Line<'static> {
-    let label = format!("{}% used", pct);
+    make_labeled_bar(
+        pct,
+        tf("{pct}% used", &[("pct", &pct.to_string())]),
+        total_width,
+    )
+}
+
+fn make_labeled_bar(pct: u8, label: String, total_width: usize) -> Line<'static> {
     let label_len = label.len() + 1;
     let bar_max = total_width.saturating_sub(label_len + 2);
     let filled = (bar_max as f64 * pct as f64 / 100.0).round() as usize;
@@ -140,27 +196,17 @@ fn make_bar(pct: u8, total_width: usize) -> Line<'static> {
 
 fn make_reset(reset: &str) -> Line<'static> {
     Line::from(Span::styled(
-        format!(" Resets {}", reset),
+        tf(" Resets {time}", &[("time", reset)]),
         Style::default().fg(Color::DarkGray),
     ))
 }
 
 fn bar_color(pct: u8) -> Color {
     if pct >= 80 {
-        Color::Red
+        Theme::bad()
     } else if pct >= 50 {
-        Color::Yellow
+        Theme::warn()
     } else {
         Color::Indexed(75)
     }
 }
-
-fn format_tokens(n: u64) -> String {
-    if n >= 1_000_000 {
-        format!("{:.1}M", n as f64 / 1_000_000.0)
-    } else if n >= 1_000 {
-        format!("{:.1}K", n as f64 / 1_000.0)
-    } else {
-        n.to_string()
-    }
-}
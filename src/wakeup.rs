@@ -0,0 +1,82 @@
+use crossterm::event::{self, Event};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::OnceLock;
+use std::thread::JoinHandle;
+
+/// Why the main loop woke up.
+pub enum Wake {
+    Input(Event),
+    /// A background thread has something for the UI; the loop's regular
+    /// drain/dirty checks pick it up.
+    Background,
+}
+
+static SENDER: OnceLock<Sender<Wake>> = OnceLock::new();
+/// Set while a `Background` wake is queued, so bursts (e.g. PTY output)
+/// queue only one.
+static PENDING: AtomicBool = AtomicBool::new(false);
+
+/// Starts reading terminal input on its own thread and returns the channel
+/// the main loop waits on. Background threads reach it through `notify`.
+pub fn start() -> Receiver<Wake> {
+    let (tx, rx) = mpsc::channel();
+    let input_tx = tx.clone();
+    let _ = SENDER.set(tx);
+    c9s_core::wakeup::set_hook(notify);
+    std::thread::spawn(move || {
+        while let Ok(ev) = event::read() {
+            if input_tx.send(Wake::Input(ev)).is_err() {
+                break;
+            }
+        }
+    });
+    rx
+}
+
+/// Wakes the main loop. Does nothing before `start`, and leaves `PENDING`
+/// alone then, so the first wake after it still gets through.
+pub fn notify() {
+    let Some(tx) = SENDER.get() else {
+        return;
+    };
+    if PENDING.swap(true, Ordering::AcqRel) {
+        return;
+    }
+    let _ = tx.send(Wake::Background);
+}
+
+/// Called by the main loop when it takes a `Background` wake off the
+/// channel, so the next `notify` queues another.
+pub fn consumed() {
+    PENDING.store(false, Ordering::Release);
+}
+
+/// `std::thread::spawn` for one-shot background work whose result the UI
+/// drains: the main loop is woken once `f` returns.
+pub fn spawn<F>(f: F) -> JoinHandle<()>
+where
+    F: FnOnce() + Send + 'static,
+{
+    std::thread::spawn(move || {
+        f();
+        notify();
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_notify_before_start_is_not_lost() {
+        notify();
+        let rx = start();
+        notify();
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(1)),
+            Ok(Wake::Background)
+        ));
+    }
+}
@@ -0,0 +1,127 @@
+//! Spots live sessions that have waited on the model for too long without
+//! their transcript moving, which usually means claude has hung.
+
+use chrono::{DateTime, Duration, Utc};
+use std::collections::{HashMap, HashSet};
+
+use crate::session::{Session, SessionStatus};
+
+pub struct Watchdog {
+    after: Duration,
+    /// Last transcript activity of each session seen thinking since.
+    waiting: HashMap<String, DateTime<Utc>>,
+    hung: HashSet<String>,
+}
+
+impl Watchdog {
+    pub fn new(after: Duration) -> Self {
+        Self {
+            after,
+            waiting: HashMap::new(),
+            hung: HashSet::new(),
+        }
+    }
+
+    /// Takes in the latest scan and returns the sessions newly found hung.
+    ///
+    /// Discovery reports a quiet session as idle after a few minutes, so a
+    /// session stays watched from the moment it is seen thinking until its
+    /// transcript grows or its process goes away.
+    pub fn check(&mut self, sessions: &[Session], now: DateTime<Utc>) -> Vec<String> {
+        let live: HashMap<&str, &Session> = sessions
+            .iter()
+            .filter(|s| s.pid.is_some())
+            .map(|s| (s.id.as_str(), s))
+            .collect();
+        self.waiting.retain(|id, since| {
+            live.get(id.as_str())
+                .is_some_and(|s| s.last_activity == *since)
+        });
+        self.hung.retain(|id| self.waiting.contains_key(id));
+        for s in live.values() {
+            if s.status == SessionStatus::Thinking {
+                self.waiting.entry(s.id.clone()).or_insert(s.last_activity);
+            }
+        }
+
+        let mut found: Vec<String> = self
+            .waiting
+            .iter()
+            .filter(|(id, since)| now - **since >= self.after && !self.hung.contains(*id))
+            .map(|(id, _)| id.clone())
+            .collect();
+        found.sort();
+        self.hung.extend(found.iter().cloned());
+        found
+    }
+
+    pub fn is_hung(&self, id: &str) -> bool {
+        self.hung.contains(id)
+    }
+
+    /// Stops watching a session, e.g. once it has been restarted.
+    pub fn forget(&mut self, id: &str) {
+        self.waiting.remove(id);
+        self.hung.remove(id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(status: SessionStatus, pid: Option<u32>, last_activity: DateTime<Utc>) -> Session {
+        let mut s = match crate::app::tests::make_local("s1", None) {
+            crate::app::SessionEntry::Local(s) => s,
+            _ => unreachable!(),
+        };
+        s.status = status;
+        s.pid = pid;
+        s.last_activity = last_activity;
+        s
+    }
+
+    #[test]
+    fn test_flags_thinking_without_progress() {
+        let t0 = Utc::now();
+        let at = |m: i64| t0 + Duration::minutes(m);
+        let mut dog = Watchdog::new(Duration::minutes(10));
+
+        assert!(dog
+            .check(&[session(SessionStatus::Thinking, Some(1), t0)], at(1))
+            .is_empty());
+        // Discovery calls it idle once it has been quiet a while.
+        assert!(dog
+            .check(&[session(SessionStatus::Idle, Some(1), t0)], at(6))
+            .is_empty());
+        assert_eq!(
+            dog.check(&[session(SessionStatus::Idle, Some(1), t0)], at(10)),
+            ["s1"]
+        );
+        assert!(dog.is_hung("s1"));
+        // Reported once.
+        assert!(dog
+            .check(&[session(SessionStatus::Idle, Some(1), t0)], at(11))
+            .is_empty());
+
+        // Output clears it.
+        dog.check(&[session(SessionStatus::Active, Some(1), at(12))], at(12));
+        assert!(!dog.is_hung("s1"));
+        assert!(dog
+            .check(&[session(SessionStatus::Idle, Some(1), at(12))], at(40))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_ignores_sessions_that_exit() {
+        let t0 = Utc::now();
+        let mut dog = Watchdog::new(Duration::minutes(10));
+        dog.check(&[session(SessionStatus::Thinking, Some(1), t0)], t0);
+        assert!(dog
+            .check(
+                &[session(SessionStatus::Dead, None, t0)],
+                t0 + Duration::minutes(20)
+            )
+            .is_empty());
+    }
+}